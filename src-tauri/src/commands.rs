@@ -15,6 +15,10 @@ use std::path::PathBuf;
 use std::env;
 use tauri::{AppHandle, State};
 use crate::core::cache::ArtifactCache;
+use crate::core::dry_run::{DryRunOutcome, DryRunReport};
+use crate::core::paths;
+use crate::core::cancellation::CANCELLED_ERROR;
+use crate::core::progress::ProgressReporter;
 use std::collections::HashMap;
 
 /// Parses YAML front matter from markdown content.
@@ -44,6 +48,89 @@ fn parse_front_matter(content: &str) -> Option<serde_yaml::Value> {
     }
 }
 
+/// Largest front matter block `read_front_matter_only` will read
+const FRONT_MATTER_MAX_BYTES: usize = 64 * 1024;
+
+/// Parses only the front matter block at the top of a file, without reading
+/// the rest of it.
+pub(crate) fn read_front_matter_only(path: &std::path::Path) -> Option<serde_yaml::Value> {
+    use std::io::BufRead;
+
+    let file = std::fs::File::open(path).ok()?;
+    let mut lines = std::io::BufReader::new(file).lines();
+    if lines.next()?.ok()?.trim_end() != "---" {
+        return None;
+    }
+
+    let mut block = String::new();
+    for line in lines {
+        let line = line.ok()?;
+        if line.trim_end() == "---" {
+            return serde_yaml::from_str(&block).ok();
+        }
+        block.push_str(&line);
+        block.push('\n');
+        if block.len() > FRONT_MATTER_MAX_BYTES {
+            return None;
+        }
+    }
+    None
+}
+
+/// Extracts the typed metadata fields from parsed front matter.
+pub(crate) fn artifact_metadata(front_matter: &serde_yaml::Value) -> ArtifactMetadata {
+    let text = |key: &str| {
+        front_matter
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    // Tags may be a YAML list or a comma-separated string
+    let tags = match front_matter.get("tags") {
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(|s| s.trim().to_string()))
+            .filter(|s| !s.is_empty())
+            .collect(),
+        Some(serde_yaml::Value::String(list)) => list
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    ArtifactMetadata {
+        artifact_type: text("type"),
+        alias: text("alias"),
+        description: text("description"),
+        tags,
+    }
+}
+
+/// Returns the content after the YAML front matter (or all of it if there is none).
+pub(crate) fn strip_front_matter(content: &str) -> &str {
+    let trimmed = content.trim_start();
+    if !trimmed.starts_with("---") {
+        return content;
+    }
+
+    let after_first_delim = &trimmed[3..];
+    match after_first_delim.find("\n---") {
+        Some(end_pos) => {
+            let rest = &after_first_delim[end_pos + 4..];
+            // Skip the remainder of the closing delimiter line
+            match rest.find('\n') {
+                Some(newline) => &rest[newline + 1..],
+                None => "",
+            }
+        }
+        None => content,
+    }
+}
+
 /// Response structure for the `get_app_info` command.
 /// 
 /// The `#[derive(Serialize, Deserialize)]` attributes allow this struct
@@ -182,6 +269,42 @@ pub struct ArtifactFile {
     /// Parsed YAML front matter (optional - populated when using cache)
     #[serde(skip_serializing_if = "Option::is_none", rename = "frontMatter")]
     pub front_matter: Option<serde_yaml::Value>,
+    /// Canonical file this artifact links to (only set for link stubs)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "linkedPath")]
+    pub linked_path: Option<String>,
+    /// File size, set only when the file exceeds the listing size limit and
+    /// its content was therefore left out
+    #[serde(skip_serializing_if = "Option::is_none", rename = "oversizedBytes")]
+    pub oversized_bytes: Option<u64>,
+    /// Typed summary of the front matter, so listings can be grouped without
+    /// reading each file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ArtifactMetadata>,
+    /// True when the file is a symlink or was reached through a symlinked
+    /// folder
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub symlinked: bool,
+    /// `/`-separated path below the listed folder (only set by
+    /// `get_folder_markdown_files`)
+    #[serde(skip_serializing_if = "Option::is_none", rename = "relativePath")]
+    pub relative_path: Option<String>,
+    /// Word count and reading time, when the listing asked for them
+    #[serde(skip_serializing_if = "Option::is_none", rename = "readStats")]
+    pub read_stats: Option<crate::core::read_stats::ReadStats>,
+}
+
+/// Commonly used front matter fields of an artifact.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArtifactMetadata {
+    /// `type` (kit, walkthrough, agent, diagram, task, ...)
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub artifact_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 /// Folder group structure for organizing resources within a folder.
@@ -324,6 +447,11 @@ pub struct BlueprintTask {
 /// # Arguments
 ///
 /// * `project_path` - The path to the project root directory
+/// * `include_content` - Set to false to return only front matter and the
+///   typed `metadata` (type, alias, description, tags); defaults to true
+/// * `include_read_stats` - Set to true to add `readStats` (word count,
+///   reading time, ...) to markdown artifacts, e.g. for kit and walkthrough
+///   cards; defaults to false
 ///
 /// # Returns
 ///
@@ -333,111 +461,541 @@ pub struct BlueprintTask {
 #[tauri::command]
 pub async fn get_project_artifacts(
     project_path: String,
+    include_content: Option<bool>,
+    include_read_stats: Option<bool>,
     cache: State<'_, ArtifactCache>,
 ) -> Result<Vec<ArtifactFile>, String> {
+    let include_content = include_content.unwrap_or(true);
+    let include_read_stats = include_read_stats.unwrap_or(false);
+
     // Construct the path to .bluekit directory
-    let bluekit_path = PathBuf::from(&project_path).join(".bluekit");
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
 
     // Check if .bluekit directory exists
     if !bluekit_path.exists() {
         return Ok(Vec::new()); // Return empty vector if directory doesn't exist
     }
 
-    let mut artifact_paths = Vec::new();
+    let artifact_paths = collect_artifact_paths(&bluekit_path)?;
 
-    // Helper function to read artifact files from a directory recursively
-    // Scans for: .md (markdown), .mmd (mermaid), .mermaid (mermaid)
-    fn read_artifact_files_from_dir(dir_path: &PathBuf, artifact_paths: &mut Vec<PathBuf>) -> Result<(), String> {
-        use std::fs;
+    let list_limit = crate::core::settings::load_settings()
+        .file_size_limits
+        .list_content_max_bytes;
 
-        if !dir_path.exists() {
-            return Ok(()); // Directory doesn't exist, skip it
-        }
+    // Read file contents using cache and parse front matter
+    let mut artifacts = Vec::new();
+    for path in artifact_paths {
+        artifacts.push(
+            load_artifact_file(&cache, &path, &bluekit_path, list_limit, include_content, include_read_stats).await,
+        );
+    }
 
-        let entries = fs::read_dir(dir_path)
-            .map_err(|e| format!("Failed to read directory: {}", e))?;
+    Ok(artifacts)
+}
 
-        for entry in entries {
-            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-            let path = entry.path();
+/// Filters and page bounds for `get_project_artifacts_page`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArtifactQuery {
+    /// Index of the first artifact to return
+    #[serde(default)]
+    pub offset: usize,
+    /// Page size (default 100)
+    pub limit: Option<usize>,
+    /// Only artifacts under this directory, relative to .bluekit (e.g. "kits"
+    /// or "walkthroughs/onboarding")
+    pub subdirectory: Option<String>,
+    /// Only these extensions, without the dot (e.g. ["md"])
+    pub extensions: Option<Vec<String>>,
+    /// Only artifacts modified at or after this Unix time, in milliseconds
+    #[serde(rename = "modifiedSince")]
+    pub modified_since: Option<i64>,
+    /// Set to false to list front matter and metadata only (default true)
+    #[serde(rename = "includeContent")]
+    pub include_content: Option<bool>,
+    /// Set to true to add read stats to markdown artifacts (default false)
+    #[serde(rename = "includeReadStats")]
+    pub include_read_stats: Option<bool>,
+}
+
+/// One page of artifacts.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArtifactPage {
+    pub artifacts: Vec<ArtifactFile>,
+    /// Number of artifacts matching the filters, across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    #[serde(rename = "hasMore")]
+    pub has_more: bool,
+}
 
-            if path.is_file() {
-                if let Some(extension) = path.extension() {
-                    let ext_str = extension.to_str().unwrap_or("");
-                    // Include markdown files (.md) and diagram files (.mmd, .mermaid)
-                    if ext_str == "md" || ext_str == "mmd" || ext_str == "mermaid" {
-                        artifact_paths.push(path);
-                    }
+/// Default page size for `get_project_artifacts_page`
+const DEFAULT_ARTIFACT_PAGE_SIZE: usize = 100;
+
+/// Returns one page of a project's artifacts, filtered on the backend.
+///
+/// Paginated counterpart of `get_project_artifacts` for large projects.
+/// Filters are applied to the file list before anything is read, and only
+/// the requested page is loaded. Artifacts are ordered by path so pages
+/// stay stable between calls.
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const page = await invoke('get_project_artifacts_page', {
+///   projectPath,
+///   query: { offset: 0, limit: 50, subdirectory: 'kits', extensions: ['md'] }
+/// });
+/// ```
+#[tauri::command]
+pub async fn get_project_artifacts_page(
+    project_path: String,
+    query: Option<ArtifactQuery>,
+    cache: State<'_, ArtifactCache>,
+) -> Result<ArtifactPage, String> {
+    let query = query.unwrap_or_default();
+    let limit = query.limit.unwrap_or(DEFAULT_ARTIFACT_PAGE_SIZE);
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+
+    let mut artifact_paths = if bluekit_path.exists() {
+        collect_artifact_paths(&bluekit_path)?
+    } else {
+        Vec::new()
+    };
+
+    if let Some(subdirectory) = query.subdirectory.as_deref().filter(|s| !s.trim().is_empty()) {
+        let root = bluekit_path.join(subdirectory.trim_matches(['/', '\\']));
+        artifact_paths.retain(|path| path.starts_with(&root));
+    }
+    if let Some(extensions) = &query.extensions {
+        artifact_paths.retain(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| extensions.iter().any(|wanted| wanted.trim_start_matches('.').eq_ignore_ascii_case(ext)))
+        });
+    }
+    if let Some(since) = query.modified_since {
+        artifact_paths.retain(|path| {
+            std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                .is_some_and(|modified| modified.as_millis() as i64 >= since)
+        });
+    }
+    artifact_paths.sort();
+
+    let total = artifact_paths.len();
+    let list_limit = crate::core::settings::load_settings()
+        .file_size_limits
+        .list_content_max_bytes;
+
+    let mut artifacts = Vec::new();
+    for path in artifact_paths.iter().skip(query.offset).take(limit) {
+        artifacts.push(
+            load_artifact_file(
+                &cache,
+                path,
+                &bluekit_path,
+                list_limit,
+                query.include_content.unwrap_or(true),
+                query.include_read_stats.unwrap_or(false),
+            )
+            .await,
+        );
+    }
+
+    Ok(ArtifactPage {
+        artifacts,
+        total,
+        offset: query.offset,
+        limit,
+        has_more: query.offset.saturating_add(limit) < total,
+    })
+}
+
+/// Artifact counts for one type in `ProjectStats`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArtifactTypeStats {
+    /// "kit", "walkthrough", "agent", "task", "diagram" or "asset"
+    #[serde(rename = "type")]
+    pub artifact_type: String,
+    pub count: usize,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    /// Most recent modification, Unix milliseconds
+    #[serde(rename = "lastModified")]
+    pub last_modified: Option<i64>,
+}
+
+/// How often a tag is used across a project's artifacts.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+/// Summary statistics for a project's artifacts.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectStats {
+    #[serde(rename = "totalArtifacts")]
+    pub total_artifacts: usize,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    /// Most recent modification of any artifact, Unix milliseconds
+    #[serde(rename = "lastModified")]
+    pub last_modified: Option<i64>,
+    /// Per type, in a fixed order
+    pub types: Vec<ArtifactTypeStats>,
+    /// Most used first
+    pub tags: Vec<TagCount>,
+}
+
+/// Returns artifact counts, sizes, modification times and tag frequency
+/// for a project's dashboard.
+///
+/// Computed in one pass over the same files `get_project_artifacts` lists,
+/// reading only file metadata and front matter (never whole files).
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const stats = await invoke<ProjectStats>('get_project_stats', { projectPath });
+/// ```
+#[tauri::command]
+pub async fn get_project_stats(project_path: String) -> Result<ProjectStats, String> {
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+    let artifact_paths = if bluekit_path.exists() {
+        collect_artifact_paths(&bluekit_path)?
+    } else {
+        Vec::new()
+    };
+
+    let mut types: Vec<ArtifactTypeStats> = ["kit", "walkthrough", "agent", "task", "diagram", "asset"]
+        .iter()
+        .map(|artifact_type| ArtifactTypeStats {
+            artifact_type: artifact_type.to_string(),
+            count: 0,
+            total_bytes: 0,
+            last_modified: None,
+        })
+        .collect();
+    let mut tag_counts: HashMap<String, usize> = HashMap::new();
+
+    for path in &artifact_paths {
+        let relative = path.strip_prefix(&bluekit_path).unwrap_or(path);
+        let artifact_type = match relative.components().next().and_then(|c| c.as_os_str().to_str()) {
+            Some("kits") => "kit",
+            Some("walkthroughs") => "walkthrough",
+            Some("agents") => "agent",
+            Some("tasks") => "task",
+            Some("diagrams") => "diagram",
+            _ => "asset",
+        };
+
+        let metadata = std::fs::metadata(path).ok();
+        let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+        let modified = metadata
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64);
+
+        if let Some(stats) = types.iter_mut().find(|t| t.artifact_type == artifact_type) {
+            stats.count += 1;
+            stats.total_bytes += size;
+            stats.last_modified = stats.last_modified.max(modified);
+        }
+
+        if !is_image_asset(path) {
+            if let Some(front_matter) = read_front_matter_only(path) {
+                for tag in artifact_metadata(&front_matter).tags {
+                    *tag_counts.entry(tag).or_insert(0) += 1;
                 }
-            } else if path.is_dir() {
-                // Recursively read subdirectories
-                read_artifact_files_from_dir(&path, artifact_paths)?;
             }
         }
-
-        Ok(())
     }
 
+    let mut tags: Vec<TagCount> = tag_counts
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect();
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+
+    Ok(ProjectStats {
+        total_artifacts: artifact_paths.len(),
+        total_bytes: types.iter().map(|t| t.total_bytes).sum(),
+        last_modified: types.iter().filter_map(|t| t.last_modified).max(),
+        types,
+        tags,
+    })
+}
+
+/// Lists the artifact files (.md, .mmd, .mermaid) in the kits, walkthroughs,
+/// agents, tasks and diagrams directories of a .bluekit directory, plus the
+/// images in `assets`.
+///
+/// Symlinks are followed unless the `scan.followSymlinks` setting is off;
+/// a folder reached a second time (a symlink cycle) is skipped. Files and
+/// folders matched by `.bluekit/.bluekitignore` are left out.
+pub(crate) fn collect_artifact_paths(bluekit_path: &std::path::Path) -> Result<Vec<PathBuf>, String> {
+    let follow_symlinks = crate::core::settings::load_settings().scan.follow_symlinks;
+    let ignore_rules = crate::core::ignore_rules::IgnoreRules::load(bluekit_path);
+    let mut visited = std::collections::HashSet::new();
+    let mut artifact_paths = Vec::new();
+    let mut walk = |dir: PathBuf, on_file: &mut dyn FnMut(PathBuf)| {
+        walk_artifact_dir(&dir, follow_symlinks, &ignore_rules, &mut visited, on_file)
+    };
+
     // Read from subdirectories: kits, walkthroughs, agents, tasks, and diagrams
-    let kits_dir = bluekit_path.join("kits");
-    read_artifact_files_from_dir(&kits_dir, &mut artifact_paths)?;
+    // Scans for: .md (markdown), .mmd (mermaid), .mermaid (mermaid)
+    for subdirectory in ["kits", "walkthroughs", "agents", "tasks", "diagrams"] {
+        walk(bluekit_path.join(subdirectory), &mut |path| {
+            let ext_str = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            if ext_str == "md" || ext_str == "mmd" || ext_str == "mermaid" {
+                artifact_paths.push(path);
+            }
+        })?;
+    }
+
+    // Images kits can embed, from .bluekit/assets
+    walk(bluekit_path.join("assets"), &mut |path| {
+        if is_image_asset(&path) {
+            artifact_paths.push(path);
+        }
+    })?;
 
-    let walkthroughs_dir = bluekit_path.join("walkthroughs");
-    read_artifact_files_from_dir(&walkthroughs_dir, &mut artifact_paths)?;
+    Ok(artifact_paths)
+}
 
-    let agents_dir = bluekit_path.join("agents");
-    read_artifact_files_from_dir(&agents_dir, &mut artifact_paths)?;
+// Calls `on_file` for every file under `dir` that isn't ignored. `visited`
+// holds the canonical paths of folders already walked, so symlink cycles end
+// instead of looping
+fn walk_artifact_dir(
+    dir: &std::path::Path,
+    follow_symlinks: bool,
+    ignore_rules: &crate::core::ignore_rules::IgnoreRules,
+    visited: &mut std::collections::HashSet<PathBuf>,
+    on_file: &mut dyn FnMut(PathBuf),
+) -> Result<(), String> {
+    use std::fs;
 
-    let tasks_dir = bluekit_path.join("tasks");
-    read_artifact_files_from_dir(&tasks_dir, &mut artifact_paths)?;
+    if !dir.exists() {
+        return Ok(()); // Directory doesn't exist, skip it
+    }
+    let canonical = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    if !visited.insert(canonical) {
+        tracing::debug!("Skipping already scanned folder {}", dir.display());
+        return Ok(());
+    }
 
-    let diagrams_dir = bluekit_path.join("diagrams");
-    read_artifact_files_from_dir(&diagrams_dir, &mut artifact_paths)?;
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?;
 
-    // Read file contents using cache and parse front matter
-    let mut artifacts = Vec::new();
-    for path in artifact_paths {
-        // Get file name without extension
-        let name = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_string();
-
-        // Get full path as string
-        let path_str = path
-            .to_str()
-            .ok_or_else(|| "Invalid path encoding".to_string())?
-            .to_string();
-
-        // Read content from cache
-        match cache.get_or_read(&path).await {
-            Ok(content) => {
-                // Parse front matter
-                let front_matter = parse_front_matter(&content);
-                
-                artifacts.push(ArtifactFile {
-                    name,
-                    path: path_str,
-                    content: Some(content),
-                    front_matter,
-                });
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = entry.path();
+        let is_symlink = entry.file_type().is_ok_and(|t| t.is_symlink());
+        if is_symlink && !follow_symlinks {
+            continue;
+        }
+
+        // is_file/is_dir follow links; broken links are neither
+        if path.is_file() {
+            if !ignore_rules.is_ignored(&path, false) {
+                on_file(path);
             }
-            Err(e) => {
-                // If reading fails, still return the artifact without content
-                // This maintains backward compatibility
-                tracing::warn!("Failed to read file {}: {}", path.display(), e);
-                artifacts.push(ArtifactFile {
-                    name,
-                    path: path_str,
-                    content: None,
-                    front_matter: None,
-                });
+        } else if path.is_dir() && !ignore_rules.is_ignored(&path, true) {
+            walk_artifact_dir(&path, follow_symlinks, ignore_rules, visited, on_file)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `path` is a symlink or sits in a symlinked folder below `root`
+/// (the `.bluekit` directory or folder being listed). Folders above `root`
+/// aren't checked, so a symlinked home or project directory doesn't mark
+/// everything in it.
+fn is_symlinked(path: &std::path::Path, root: &std::path::Path) -> bool {
+    let folders = path
+        .ancestors()
+        .skip(1)
+        .take_while(|ancestor| *ancestor != root && ancestor.starts_with(root));
+    std::iter::once(path)
+        .chain(folders)
+        .any(|ancestor| std::fs::symlink_metadata(ancestor).is_ok_and(|m| m.file_type().is_symlink()))
+}
+
+/// Image extensions listed from `.bluekit/assets`
+pub(crate) const IMAGE_ASSET_EXTENSIONS: [&str; 4] = ["png", "svg", "jpg", "jpeg"];
+
+fn is_image_asset(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| IMAGE_ASSET_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Loads one artifact for listing: content and front matter through the
+/// cache, or just the size and front matter for files over the listing
+/// limit. Content is left out unless `include_content` is set, and markdown
+/// read stats are added when `include_read_stats` is.
+async fn load_artifact_file(
+    cache: &ArtifactCache,
+    path: &PathBuf,
+    bluekit_dir: &std::path::Path,
+    list_limit: u64,
+    include_content: bool,
+    include_read_stats: bool,
+) -> ArtifactFile {
+    // Get file name without extension
+    let name = paths::file_stem_lossy(path);
+
+    // Get full path as string
+    let path_str = paths::to_ipc(path);
+    let symlinked = is_symlinked(path, bluekit_dir);
+
+    // Images are listed by path; read them with `read_file_binary`
+    if is_image_asset(path) {
+        return ArtifactFile {
+            name,
+            path: path_str,
+            content: None,
+            front_matter: None,
+            linked_path: None,
+            oversized_bytes: None,
+            metadata: None,
+            symlinked,
+            relative_path: None,
+            read_stats: None,
+        };
+    }
+
+    // List huge files without loading them, keeping just their front matter
+    if let Some(size) = oversized_bytes(path, list_limit) {
+        let front_matter = read_front_matter_only(path);
+        return ArtifactFile {
+            name,
+            path: path_str,
+            content: None,
+            front_matter: front_matter.clone(),
+            linked_path: None,
+            oversized_bytes: Some(size),
+            metadata: front_matter.as_ref().map(artifact_metadata),
+            symlinked,
+            relative_path: None,
+            read_stats: None,
+        };
+    }
+
+    // Read content from cache (following link stubs to the canonical file)
+    match read_artifact_resolving_links(cache, path).await {
+        Ok((content, linked_path)) => {
+            // Parse front matter
+            let front_matter = parse_front_matter(&content);
+            let read_stats = (include_read_stats && path.extension().is_some_and(|ext| ext == "md"))
+                .then(|| crate::core::read_stats::read_stats(strip_front_matter(&content)));
+
+            ArtifactFile {
+                name,
+                path: path_str,
+                content: if include_content { Some(content) } else { None },
+                metadata: front_matter.as_ref().map(artifact_metadata),
+                front_matter,
+                linked_path,
+                oversized_bytes: None,
+                symlinked,
+                relative_path: None,
+                read_stats,
+            }
+        }
+        Err(e) => {
+            // If reading fails, still return the artifact without content
+            // This maintains backward compatibility
+            tracing::warn!("Failed to read file {}: {}", path.display(), e);
+            ArtifactFile {
+                name,
+                path: path_str,
+                content: None,
+                front_matter: None,
+                linked_path: None,
+                oversized_bytes: None,
+                metadata: None,
+                symlinked,
+                relative_path: None,
+                read_stats: None,
             }
         }
     }
+}
 
-    Ok(artifacts)
+/// Reads an artifact through the cache, following link stubs.
+///
+/// Returns the content and, for link stubs, the canonical path it resolved to.
+async fn read_artifact_resolving_links(
+    cache: &ArtifactCache,
+    path: &PathBuf,
+) -> Result<(String, Option<String>), String> {
+    let content = cache.get_or_read(path).await?;
+
+    match crate::core::links::resolve_from_content(path, &content)? {
+        Some(target) => {
+            let limit = crate::core::settings::load_settings()
+                .file_size_limits
+                .list_content_max_bytes;
+            if let Some(size) = oversized_bytes(&target, limit) {
+                return Err(format!(
+                    "Linked artifact {} is too large to list ({} bytes)",
+                    target.display(),
+                    size
+                ));
+            }
+            let linked_content = cache.get_or_read(&target).await?;
+            Ok((linked_content, Some(target.to_string_lossy().to_string())))
+        }
+        None => Ok((content, None)),
+    }
+}
+
+/// Returns the file's size if it is larger than `limit` bytes.
+fn oversized_bytes(path: &std::path::Path, limit: u64) -> Option<u64> {
+    std::fs::metadata(path)
+        .ok()
+        .map(|m| m.len())
+        .filter(|&size| size > limit)
+}
+
+/// Error returned (JSON-encoded) when a file is over the read size limit.
+///
+/// The frontend can `JSON.parse` the error string and check `code` to offer
+/// opening the file externally instead.
+#[derive(Debug, Serialize)]
+struct FileTooLargeError<'a> {
+    code: &'static str,
+    message: String,
+    path: &'a str,
+    #[serde(rename = "sizeBytes")]
+    size_bytes: u64,
+    #[serde(rename = "limitBytes")]
+    limit_bytes: u64,
+}
+
+/// Fails with a `FileTooLargeError` if `path` is over the read size limit.
+fn check_read_size(path: &std::path::Path, display_path: &str, limit: u64) -> Result<(), String> {
+    let Some(size) = oversized_bytes(path, limit) else {
+        return Ok(());
+    };
+
+    let error = FileTooLargeError {
+        code: "FILE_TOO_LARGE",
+        message: format!(
+            "File is too large to open ({} bytes, limit is {} bytes): {}",
+            size, limit, display_path
+        ),
+        path: display_path,
+        size_bytes: size,
+        limit_bytes: limit,
+    };
+    Err(serde_json::to_string(&error).unwrap_or(error.message))
 }
 
 /// Gets only changed artifacts based on file paths.
@@ -458,14 +1016,18 @@ pub async fn get_project_artifacts(
 /// with content and front_matter populated.
 #[tauri::command]
 pub async fn get_changed_artifacts(
-    _project_path: String,
+    project_path: String,
     changed_paths: Vec<String>,
     cache: State<'_, ArtifactCache>,
 ) -> Result<Vec<ArtifactFile>, String> {
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+    let list_limit = crate::core::settings::load_settings()
+        .file_size_limits
+        .list_content_max_bytes;
     let mut artifacts = Vec::new();
 
     for path_str in changed_paths {
-        let path = PathBuf::from(&path_str);
+        let path = paths::from_ipc(&path_str);
 
         // Skip if file doesn't exist (might have been deleted)
         if !path.exists() {
@@ -480,15 +1042,29 @@ pub async fn get_changed_artifacts(
         cache.invalidate(&path).await;
 
         // Get file name without extension
-        let name = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_string();
+        let name = paths::file_stem_lossy(&path);
+
+        // List huge files without loading them, keeping just their front matter
+        if let Some(size) = oversized_bytes(&path, list_limit) {
+            let front_matter = read_front_matter_only(&path);
+            artifacts.push(ArtifactFile {
+                name,
+                path: path_str,
+                content: None,
+                metadata: front_matter.as_ref().map(artifact_metadata),
+                front_matter,
+                linked_path: None,
+                oversized_bytes: Some(size),
+                symlinked: is_symlinked(&path, &bluekit_path),
+                relative_path: None,
+                read_stats: None,
+            });
+            continue;
+        }
 
         // Read content from cache (will read from disk after invalidation)
-        match cache.get_or_read(&path).await {
-            Ok(content) => {
+        match read_artifact_resolving_links(&cache, &path).await {
+            Ok((content, linked_path)) => {
                 // Parse front matter
                 let front_matter = parse_front_matter(&content);
 
@@ -497,7 +1073,13 @@ pub async fn get_changed_artifacts(
                     name,
                     path: path_str,
                     content: Some(content),
+                    metadata: front_matter.as_ref().map(artifact_metadata),
                     front_matter,
+                    linked_path,
+                    oversized_bytes: None,
+                    symlinked: is_symlinked(&path, &bluekit_path),
+                    relative_path: None,
+                    read_stats: None,
                 });
             }
             Err(e) => {
@@ -508,6 +1090,12 @@ pub async fn get_changed_artifacts(
                     path: path_str,
                     content: None,
                     front_matter: None,
+                    linked_path: None,
+                    oversized_bytes: None,
+                    metadata: None,
+                    symlinked: is_symlinked(&path, &bluekit_path),
+                    relative_path: None,
+                    read_stats: None,
                 });
             }
         }
@@ -516,85 +1104,269 @@ pub async fn get_changed_artifacts(
     Ok(artifacts)
 }
 
-/// Project registry entry structure.
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ProjectEntry {
-    /// Unique identifier for the project
-    pub id: String,
-    /// Project title/name
-    pub title: String,
-    /// Project description
-    pub description: String,
-    /// Absolute path to the project directory
-    pub path: String,
-}
-
-/// Clone metadata structure matching the clones.json format.
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct CloneMetadata {
-    /// Unique clone ID (format: slugified-name-YYYYMMDD)
-    pub id: String,
-    /// Display name (e.g., "BlueKit Foundation")
-    pub name: String,
-    /// Description of what this clone represents
-    pub description: String,
-    /// Git repository URL
-    #[serde(rename = "gitUrl")]
-    pub git_url: String,
-    /// Full commit hash (40 chars)
-    #[serde(rename = "gitCommit")]
-    pub git_commit: String,
-    /// Branch name (if not detached HEAD)
-    #[serde(rename = "gitBranch", skip_serializing_if = "Option::is_none")]
-    pub git_branch: Option<String>,
-    /// Git tag (if HEAD is on a tag)
-    #[serde(rename = "gitTag", skip_serializing_if = "Option::is_none")]
-    pub git_tag: Option<String>,
-    /// Array of tags for categorization
-    pub tags: Vec<String>,
-    /// ISO 8601 timestamp
-    #[serde(rename = "createdAt")]
-    pub created_at: String,
-    /// Optional additional metadata
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<serde_json::Value>,
-}
-
-/// Starts watching a project's .bluekit directory for artifact file changes.
+/// Searches the Markdown and mermaid files in a project's .bluekit directory.
 ///
-/// This command sets up a file watcher that monitors the .bluekit directory
-/// in the specified project path. When any artifact file (.md, .mmd, etc.) is
-/// added, modified, or removed, it emits a Tauri event that the frontend can listen to.
+/// Returns matching files ranked best first, each with up to five matching
+/// lines and their line numbers. The index behind it is built on the first
+/// search and then kept current from `watch_project_artifacts` events, so
+/// repeated searches only re-read files that changed.
 ///
 /// # Arguments
 ///
-/// * `app_handle` - Tauri application handle (automatically provided)
 /// * `project_path` - The path to the project root directory
-///
-/// # Returns
-///
-/// A `Result<(), String>` containing either:
-/// - `Ok(())` - Success case
-/// - `Err(String)` - Error case with an error message
+/// * `query` - Space-separated terms; every term must match
+/// * `limit` - Maximum number of files to return (default 50)
 ///
 /// # Example Usage (from frontend)
 ///
 /// ```typescript
-/// await invoke('watch_project_artifacts', { projectPath: '/path/to/project' });
+/// const results = await invoke('search_project_artifacts', { projectPath, query: 'auth token' });
 /// ```
 #[tauri::command]
-pub async fn watch_project_artifacts(
-    app_handle: AppHandle,
+pub async fn search_project_artifacts(
     project_path: String,
-) -> Result<(), String> {
-    use crate::core::watcher;
+    query: String,
+    limit: Option<usize>,
+    index: State<'_, crate::core::search_index::SearchIndex>,
+) -> Result<Vec<crate::core::search_index::SearchResult>, String> {
+    let watched = crate::core::watcher::watcher_exists(&project_artifacts_event_name(&project_path)).await;
+    index
+        .search(
+            &paths::from_ipc(&project_path),
+            &query,
+            limit.unwrap_or(crate::core::search_index::DEFAULT_SEARCH_LIMIT),
+            watched,
+        )
+        .await
+}
 
-    // Construct the path to .bluekit directory
-    let bluekit_path = PathBuf::from(&project_path).join(".bluekit");
+/// Search matches in one project, for `search_all_projects`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectSearchResults {
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    #[serde(rename = "projectName")]
+    pub project_name: String,
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    pub results: Vec<crate::core::search_index::SearchResult>,
+}
 
-    // Generate a unique event name based on the project path
-    // Sanitize the path to create a valid event name
-    // Replace path separators and special characters with underscores
+/// Searches the `.bluekit` artifacts of every registered project.
+///
+/// Projects are searched in parallel, each with the same index as
+/// `search_project_artifacts`. Only projects with matches are returned,
+/// the one with the best match first. Archived projects are skipped, and a
+/// project whose search fails is logged and left out rather than failing
+/// the whole search.
+///
+/// # Arguments
+///
+/// * `query` - Space-separated terms; every term must match
+/// * `limit` - Maximum number of files to return per project (default 50)
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const groups = await invoke('search_all_projects', { query: 'auth token' });
+/// ```
+#[tauri::command]
+pub async fn search_all_projects(
+    query: String,
+    limit: Option<usize>,
+    db: State<'_, DatabaseConnection>,
+    index: State<'_, crate::core::search_index::SearchIndex>,
+) -> Result<Vec<ProjectSearchResults>, String> {
+    let projects: Vec<_> = crate::db::project_operations::get_all_projects(&db)
+        .await
+        .map_err(|e| format!("Failed to get projects: {}", e))?
+        .into_iter()
+        .filter(|project| !project.archived)
+        .collect();
+
+    let mut roots = Vec::with_capacity(projects.len());
+    for project in &projects {
+        let watched = crate::core::watcher::watcher_exists(&project_artifacts_event_name(&project.path)).await;
+        roots.push((paths::from_ipc(&project.path), watched));
+    }
+    let results = index
+        .search_many(
+            roots,
+            &query,
+            limit.unwrap_or(crate::core::search_index::DEFAULT_SEARCH_LIMIT),
+        )
+        .await;
+
+    let mut groups = Vec::new();
+    for (project, results) in projects.into_iter().zip(results) {
+        match results {
+            Ok(results) if !results.is_empty() => groups.push(ProjectSearchResults {
+                project_id: project.id,
+                project_name: project.name,
+                project_path: project.path,
+                results,
+            }),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Skipping project {} in search: {}", project.path, e),
+        }
+    }
+    // Results within a project are already sorted best first
+    groups.sort_by(|a, b| {
+        b.results[0]
+            .score
+            .partial_cmp(&a.results[0].score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(groups)
+}
+
+/// Local links in an artifact: inline Markdown links, reference definitions,
+/// `[[wikilinks]]` and its link stub target, each resolved to a file path.
+///
+/// Like search, the project's link graph is built on first use and kept
+/// current from `watch_project_artifacts` events.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory
+/// * `file_path` - The artifact whose links to return
+#[tauri::command]
+pub async fn get_artifact_links(
+    project_path: String,
+    file_path: String,
+    graph: State<'_, crate::core::link_graph::LinkGraph>,
+) -> Result<Vec<crate::core::link_graph::ArtifactLink>, String> {
+    let watched = crate::core::watcher::watcher_exists(&project_artifacts_event_name(&project_path)).await;
+    Ok(graph
+        .links(&paths::from_ipc(&project_path), &paths::from_ipc(&file_path), watched)
+        .await)
+}
+
+/// Artifacts in the project that link to `file_path`, from the same link
+/// graph as `get_artifact_links`.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory
+/// * `file_path` - The artifact to find backlinks to
+#[tauri::command]
+pub async fn get_artifact_backlinks(
+    project_path: String,
+    file_path: String,
+    graph: State<'_, crate::core::link_graph::LinkGraph>,
+) -> Result<Vec<crate::core::link_graph::Backlink>, String> {
+    let watched = crate::core::watcher::watcher_exists(&project_artifacts_event_name(&project_path)).await;
+    Ok(graph
+        .backlinks(&paths::from_ipc(&project_path), &paths::from_ipc(&file_path), watched)
+        .await)
+}
+
+/// Resolves `[[wikilink]]` text to an artifact in the project, by file name
+/// first and `alias` front matter second.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory
+/// * `text` - The link text, e.g. `api-client` or `API Client|label`
+#[tauri::command]
+pub async fn resolve_wikilink(
+    project_path: String,
+    text: String,
+    graph: State<'_, crate::core::link_graph::LinkGraph>,
+) -> Result<crate::core::wikilinks::WikilinkResolution, String> {
+    let mut resolutions = resolve_wikilinks(project_path, vec![text], graph).await?;
+    resolutions
+        .pop()
+        .ok_or_else(|| "Wikilink could not be resolved".to_string())
+}
+
+/// Resolves many wikilinks at once, in order, e.g. for every `[[...]]` in
+/// an open artifact.
+#[tauri::command]
+pub async fn resolve_wikilinks(
+    project_path: String,
+    texts: Vec<String>,
+    graph: State<'_, crate::core::link_graph::LinkGraph>,
+) -> Result<Vec<crate::core::wikilinks::WikilinkResolution>, String> {
+    let watched = crate::core::watcher::watcher_exists(&project_artifacts_event_name(&project_path)).await;
+    Ok(graph
+        .resolve_wikilinks(&paths::from_ipc(&project_path), &texts, watched)
+        .await)
+}
+
+/// Finds links to files that no longer exist across a project's Markdown
+/// artifacts (relative links, reference definitions, link stubs and
+/// wikilinks) and blueprint `taskFile` references.
+///
+/// Each broken link lists up to three existing files with the closest
+/// names, with the replacement text for the link.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory
+#[tauri::command]
+pub async fn check_broken_links(
+    project_path: String,
+    graph: State<'_, crate::core::link_graph::LinkGraph>,
+) -> Result<crate::core::broken_links::BrokenLinkReport, String> {
+    let project_root = paths::from_ipc(&project_path);
+    let watched = crate::core::watcher::watcher_exists(&project_artifacts_event_name(&project_path)).await;
+    let (links, artifacts) = graph.all_links(&project_root, watched).await;
+
+    tokio::task::spawn_blocking(move || crate::core::broken_links::check(&project_root, links, &artifacts))
+        .await
+        .map_err(|e| format!("Broken link check failed: {}", e))
+}
+
+/// Project registry entry structure.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectEntry {
+    /// Unique identifier for the project
+    pub id: String,
+    /// Project title/name
+    pub title: String,
+    /// Project description
+    pub description: String,
+    /// Absolute path to the project directory
+    pub path: String,
+}
+
+/// Clone metadata structure matching the clones.json format.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CloneMetadata {
+    /// Unique clone ID (format: slugified-name-YYYYMMDD)
+    pub id: String,
+    /// Display name (e.g., "BlueKit Foundation")
+    pub name: String,
+    /// Description of what this clone represents
+    pub description: String,
+    /// Git repository URL
+    #[serde(rename = "gitUrl")]
+    pub git_url: String,
+    /// Full commit hash (40 chars)
+    #[serde(rename = "gitCommit")]
+    pub git_commit: String,
+    /// Branch name (if not detached HEAD)
+    #[serde(rename = "gitBranch", skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    /// Git tag (if HEAD is on a tag)
+    #[serde(rename = "gitTag", skip_serializing_if = "Option::is_none")]
+    pub git_tag: Option<String>,
+    /// Array of tags for categorization
+    pub tags: Vec<String>,
+    /// ISO 8601 timestamp
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    /// Optional additional metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Builds the per-project artifact change event name.
+///
+/// Sanitizes the path to create a valid event name by replacing path
+/// separators and special characters with underscores.
+pub(crate) fn project_artifacts_event_name(project_path: &str) -> String {
     let sanitized_path: String = project_path
         .chars()
         .map(|c| match c {
@@ -602,7 +1374,43 @@ pub async fn watch_project_artifacts(
             _ => c,
         })
         .collect();
-    let event_name = format!("project-artifacts-changed-{}", sanitized_path);
+    format!("project-artifacts-changed-{}", sanitized_path)
+}
+
+/// Starts watching a project's .bluekit directory for artifact file changes.
+///
+/// This command sets up a file watcher that monitors the .bluekit directory
+/// in the specified project path. When any artifact file (.md, .mmd, etc.) is
+/// added, modified, or removed, it emits a Tauri event that the frontend can listen to.
+///
+/// # Arguments
+///
+/// * `app_handle` - Tauri application handle (automatically provided)
+/// * `project_path` - The path to the project root directory
+///
+/// # Returns
+///
+/// A `Result<(), String>` containing either:
+/// - `Ok(())` - Success case
+/// - `Err(String)` - Error case with an error message
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// await invoke('watch_project_artifacts', { projectPath: '/path/to/project' });
+/// ```
+#[tauri::command]
+pub async fn watch_project_artifacts(
+    app_handle: AppHandle,
+    project_path: String,
+) -> Result<(), String> {
+    use crate::core::watcher;
+
+    // Construct the path to .bluekit directory
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+
+    // Generate a unique event name based on the project path
+    let event_name = project_artifacts_event_name(&project_path);
 
     // Check if watcher already exists - prevent duplicates
     if watcher::watcher_exists(&event_name).await {
@@ -623,7 +1431,7 @@ pub async fn watch_project_artifacts(
 /// Starts watching the projects database file for changes.
 ///
 /// This command sets up a file watcher that monitors the BlueKit database file
-/// (`~/.bluekit/bluekit.db`). When the database file is modified (e.g., when a project
+/// (`bluekit.db` in the data directory). When the database file is modified (e.g., when a project
 /// is added via CLI), it emits a Tauri event that the frontend can listen to.
 ///
 /// # Arguments
@@ -673,9 +1481,18 @@ pub async fn watch_projects_database(
 
 /// Reads the contents of a file.
 /// 
+/// Link stubs (see `link_artifact_to_project`) are resolved, so the canonical
+/// artifact's content is returned. Files that aren't UTF-8 are decoded with a
+/// detected encoding unless `strict` is set; use `read_file_with_encoding` to
+/// find out which encoding was used.
+/// 
 /// # Arguments
 /// 
 /// * `file_path` - The absolute path to the file to read
+/// * `strict` - Fail on non-UTF-8 content instead of converting it (default false)
+/// 
+/// Files over the `readMaxBytes` setting are refused with a JSON-encoded
+/// error whose `code` is `FILE_TOO_LARGE`.
 /// 
 /// # Returns
 /// 
@@ -689,27 +1506,286 @@ pub async fn watch_projects_database(
 /// const contents = await invoke<string>('read_file', { filePath: '/path/to/file.md' });
 /// ```
 #[tauri::command]
-pub async fn read_file(file_path: String) -> Result<String, String> {
-    use std::fs;
-    
-    let path = PathBuf::from(&file_path);
+pub async fn read_file(file_path: String, strict: Option<bool>) -> Result<String, String> {
+    read_file_decoded(&file_path, strict.unwrap_or(false)).map(|decoded| decoded.content)
+}
+
+/// Reads a file like `read_file`, also returning the detected encoding and
+/// a warning if the content had to be converted from a non-UTF-8 encoding.
+#[tauri::command]
+pub async fn read_file_with_encoding(
+    file_path: String,
+    strict: Option<bool>,
+) -> Result<crate::core::encoding::DecodedText, String> {
+    read_file_decoded(&file_path, strict.unwrap_or(false))
+}
+
+/// Word count, heading count, code block count and estimated reading time
+/// of a markdown artifact, front matter excluded. The file is read like
+/// `read_file` (size limit, encoding detection and link stubs).
+#[tauri::command]
+pub async fn get_artifact_read_stats(file_path: String) -> Result<crate::core::read_stats::ReadStats, String> {
+    let decoded = read_file_decoded(&file_path, false)?;
+    Ok(crate::core::read_stats::read_stats(strip_front_matter(&decoded.content)))
+}
+
+fn read_file_decoded(
+    file_path: &str,
+    strict: bool,
+) -> Result<crate::core::encoding::DecodedText, String> {
+    use crate::core::encoding::read_text;
+
+    let path = paths::from_ipc(file_path);
     
     // Check if file exists
     if !path.exists() {
         return Err(format!("File does not exist: {}", file_path));
     }
     
+    // Refuse huge files rather than shipping them over IPC
+    let limit = crate::core::settings::load_settings().file_size_limits.read_max_bytes;
+    check_read_size(&path, file_path, limit)?;
+
     // Read the file
-    let contents = fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?;
+    let decoded = read_text(&path, strict)?;
     
-    Ok(contents)
+    // Follow link stubs to the canonical artifact
+    if let Some(target) = crate::core::links::resolve_from_content(&path, &decoded.content)? {
+        check_read_size(&target, &target.to_string_lossy(), limit)?;
+        return read_text(&target, strict);
+    }
+    
+    Ok(decoded)
+}
+
+/// Diffs two artifacts, or an artifact against content that isn't on disk.
+///
+/// Files are read like `read_file` (size limit, encoding detection and link
+/// stubs). Returns unified diff text plus the same changes as hunks of
+/// typed lines, for showing diffs during copies, pulls and conflict
+/// resolution.
+///
+/// # Arguments
+///
+/// * `old_path` - The file on the old (left) side
+/// * `new_path` - The file on the new (right) side
+/// * `new_content` - Content for the new side instead of `new_path`
+/// * `context_lines` - Unchanged lines around each change (default 3)
+///
+/// Exactly one of `new_path` and `new_content` must be given.
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const diff = await invoke<FileDiff>('diff_files', { oldPath, newContent: editorContent });
+/// ```
+#[tauri::command]
+pub async fn diff_files(
+    old_path: String,
+    new_path: Option<String>,
+    new_content: Option<String>,
+    context_lines: Option<usize>,
+) -> Result<crate::core::diff::FileDiff, String> {
+    let old = read_file_decoded(&old_path, false)?.content;
+    let (new, new_label) = match (new_path, new_content) {
+        (Some(new_path), None) => (read_file_decoded(&new_path, false)?.content, new_path),
+        (None, Some(new_content)) => (new_content, format!("{} (unsaved)", old_path)),
+        _ => return Err("Provide either newPath or newContent".to_string()),
+    };
+
+    Ok(crate::core::diff::diff_text(
+        &old,
+        &new,
+        &old_path,
+        &new_label,
+        context_lines.unwrap_or(crate::core::diff::DEFAULT_CONTEXT_LINES),
+    ))
+}
+
+/// Returned by `read_file_stream` once the stream has started.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileStreamStarted {
+    #[serde(rename = "streamId")]
+    pub stream_id: String,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+}
+
+/// Streams a large text file to the frontend in chunks.
+///
+/// Returns immediately; the content follows as `file-stream-chunk` events
+/// carrying the stream id, and the last event has `done: true` (with
+/// `error` set if the read failed). Pass your own `stream_id` to subscribe
+/// before the first chunk is sent; the same id can be given to
+/// `cancel_operation` to stop the stream.
+///
+/// # Arguments
+///
+/// * `file_path` - The absolute path to the file to read
+/// * `stream_id` - Id carried by the events (generated if omitted)
+/// * `chunk_size` - Approximate chunk size in bytes (default 256 KiB)
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const streamId = crypto.randomUUID();
+/// const unlisten = await listen('file-stream-chunk', (e) => { if (e.payload.streamId === streamId) ... });
+/// await invoke('read_file_stream', { filePath, streamId });
+/// ```
+#[tauri::command]
+pub async fn read_file_stream(
+    app_handle: AppHandle,
+    file_path: String,
+    stream_id: Option<String>,
+    chunk_size: Option<usize>,
+) -> Result<FileStreamStarted, String> {
+    let path = paths::from_ipc(&file_path);
+    let total_bytes = std::fs::metadata(&path)
+        .ok()
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .ok_or_else(|| format!("File does not exist: {}", file_path))?;
+
+    let stream_id = stream_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    crate::core::file_stream::spawn_stream(
+        app_handle,
+        path,
+        stream_id.clone(),
+        total_bytes,
+        chunk_size.unwrap_or(crate::core::file_stream::DEFAULT_CHUNK_BYTES),
+    );
+
+    Ok(FileStreamStarted { stream_id, total_bytes })
+}
+
+/// Binary file contents, base64-encoded for IPC.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BinaryFileContent {
+    /// MIME type guessed from the extension ("application/octet-stream" if unknown)
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    pub base64: String,
+    pub size: u64,
+}
+
+/// Reads a file as bytes, for images and other non-text assets.
+///
+/// The content is base64-encoded so it can be used directly in a
+/// `data:` URL. Files over the `readMaxBytes` setting are refused the same
+/// way as in `read_file`.
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const file = await invoke<BinaryFileContent>('read_file_binary', { filePath });
+/// const src = `data:${file.mimeType};base64,${file.base64}`;
+/// ```
+#[tauri::command]
+pub async fn read_file_binary(file_path: String) -> Result<BinaryFileContent, String> {
+    use base64::prelude::*;
+
+    let path = paths::from_ipc(&file_path);
+    if !path.is_file() {
+        return Err(format!("File does not exist: {}", file_path));
+    }
+
+    let limit = crate::core::settings::load_settings().file_size_limits.read_max_bytes;
+    check_read_size(&path, &file_path, limit)?;
+
+    let bytes = std::fs::read(&path)
+        .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?;
+
+    Ok(BinaryFileContent {
+        mime_type: mime_type_for(&path).to_string(),
+        size: bytes.len() as u64,
+        base64: BASE64_STANDARD.encode(bytes),
+    })
+}
+
+fn mime_type_for(path: &std::path::Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref() {
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("svg") => "image/svg+xml",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("pdf") => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Options for `copy_artifact_to_clipboard`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ClipboardOptions {
+    /// Drop the YAML front matter (only applies to the "raw" format)
+    #[serde(rename = "stripFrontMatter", default)]
+    pub strip_front_matter: bool,
+    /// "raw" (default), "prompt" (body only, e.g. an agent's prompt) or
+    /// "share" (title and description header followed by the body)
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// Copies an artifact to the system clipboard, ready to paste into a chat.
+///
+/// Returns the text that was copied.
+#[tauri::command]
+pub async fn copy_artifact_to_clipboard(
+    app_handle: AppHandle,
+    path: String,
+    options: Option<ClipboardOptions>,
+) -> Result<String, String> {
+    use tauri::ClipboardManager;
+
+    let options = options.unwrap_or_default();
+    let content = read_file_decoded(&path, false)?.content;
+    let body = strip_front_matter(&content).trim();
+
+    let text = match options.format.as_deref().unwrap_or("raw") {
+        "raw" if options.strip_front_matter => body.to_string(),
+        "raw" => content.clone(),
+        "prompt" => body.to_string(),
+        "share" => {
+            let front_matter = parse_front_matter(&content);
+            let field = |key: &str| {
+                front_matter
+                    .as_ref()
+                    .and_then(|fm| fm.get(key))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+            };
+
+            let mut text = String::new();
+            // Don't add a title if the body already opens with one
+            if !body.starts_with("# ") {
+                let title = field("alias")
+                    .or_else(|| field("id"))
+                    .unwrap_or_else(|| paths::file_stem_lossy(&paths::from_ipc(&path)));
+                text.push_str(&format!("# {}\n\n", title));
+            }
+            if let Some(description) = field("description") {
+                text.push_str(&format!("> {}\n\n", description));
+            }
+            text.push_str(body);
+            text
+        }
+        other => return Err(format!("Unknown clipboard format: {}", other)),
+    };
+
+    app_handle
+        .clipboard_manager()
+        .write_text(text.clone())
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+
+    Ok(text)
 }
 
 /// Writes content to a file.
 ///
 /// This command writes the provided content to the specified file path.
 /// The file will be created if it doesn't exist, or overwritten if it does.
+/// Writes to a link stub go through to the canonical artifact it points at.
 ///
 /// # Arguments
 ///
@@ -731,285 +1807,410 @@ pub async fn read_file(file_path: String) -> Result<String, String> {
 pub async fn write_file(file_path: String, content: String) -> Result<(), String> {
     use std::fs;
 
-    let path = PathBuf::from(&file_path);
+    // Writing the stub itself would break the link, so write through to its target
+    let path = resolve_write_target(&file_path)?;
 
     // Write the file
+    let _lock = crate::core::file_lock::lock(&path, "write_file").await?;
     fs::write(&path, content)
-        .map_err(|e| format!("Failed to write file {}: {}", file_path, e))?;
+        .map_err(|e| format!("Failed to write file {}: {}", path.display(), e))?;
 
     Ok(())
 }
 
-/// Copies a kit file to a project's .bluekit directory.
-/// 
-/// This command reads the source kit file and writes it to the target project's
-/// .bluekit/kits directory. It creates the directory structure if it doesn't exist.
-/// 
-/// # Arguments
-/// 
-/// * `source_file_path` - The absolute path to the source kit file
-/// * `target_project_path` - The absolute path to the target project root directory
-/// 
-/// # Returns
-/// 
-/// A `Result<String, String>` containing either:
-/// - `Ok(String)` - Success case with the path to the copied file
-/// - `Err(String)` - Error case with an error message
-/// 
-/// # Example Usage (from frontend)
-/// 
-/// ```typescript
-/// const result = await invoke<string>('copy_kit_to_project', {
-///   sourceFilePath: '/path/to/source/kit.md',
-///   targetProjectPath: '/path/to/target/project'
-/// });
-/// ```
+/// Version of a file on disk, for `write_file_checked`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileVersion {
+    /// SHA-256 of the file's bytes
+    pub hash: String,
+    /// Modification time in Unix milliseconds
+    pub modified: i64,
+}
+
+/// Error returned (JSON-encoded) by `write_file_checked` when the file
+/// changed on disk since the caller read it.
+///
+/// Carries both versions so the frontend can show a diff or let the user
+/// pick one.
+#[derive(Debug, Serialize)]
+struct WriteConflictError<'a> {
+    /// Always `file_lock::WRITE_CONFLICT`
+    code: &'static str,
+    path: &'a str,
+    message: String,
+    /// What is on disk now
+    #[serde(rename = "diskContent")]
+    disk_content: String,
+    #[serde(rename = "diskVersion")]
+    disk_version: FileVersion,
+    /// What the caller tried to write
+    #[serde(rename = "attemptedContent")]
+    attempted_content: &'a str,
+}
+
+/// Returns the hash and modification time of a file, to pass back to
+/// `write_file_checked` later.
+///
+/// Link stubs are resolved, like in `read_file`.
 #[tauri::command]
-pub async fn copy_kit_to_project(
-    source_file_path: String,
-    target_project_path: String,
-) -> Result<String, String> {
-    use std::fs;
-    
-    let source_path = PathBuf::from(&source_file_path);
-    let target_project = PathBuf::from(&target_project_path);
-    
-    // Check if source file exists
-    if !source_path.exists() {
-        return Err(format!("Source file does not exist: {}", source_file_path));
-    }
-    
-    // Check if target project directory exists
-    if !target_project.exists() {
-        return Err(format!("Target project directory does not exist: {}", target_project_path));
-    }
-    
-    // Get the source file name
-    let file_name = source_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| "Invalid source file name".to_string())?
-        .to_string();
-    
-    // Determine target directory: if .bluekit exists, use structured path, otherwise copy directly
-    let bluekit_dir = target_project.join(".bluekit");
-    let target_file_path = if bluekit_dir.exists() && bluekit_dir.is_dir() {
-        // Use structured path: target_project/.bluekit/kits/filename
-        let kits_dir = bluekit_dir.join("kits");
-        fs::create_dir_all(&kits_dir)
-            .map_err(|e| format!("Failed to create .bluekit/kits directory: {}", e))?;
-        kits_dir.join(&file_name)
-    } else {
-        // Copy directly to target directory
-        target_project.join(&file_name)
-    };
-    
-    // Read source file contents
-    let contents = fs::read_to_string(&source_path)
-        .map_err(|e| format!("Failed to read source file: {}", e))?;
-    
-    // Write to target file
-    fs::write(&target_file_path, contents)
-        .map_err(|e| format!("Failed to write target file: {}", e))?;
-    
-    // Return the target file path as a string
-    target_file_path
-        .to_str()
-        .ok_or_else(|| "Invalid target file path encoding".to_string())
-        .map(|s| s.to_string())
+pub async fn get_file_version(file_path: String) -> Result<FileVersion, String> {
+    file_version(&resolve_write_target(&file_path)?)
 }
 
-/// Copies a walkthrough file to a project's .bluekit directory.
-/// 
-/// This command reads the source walkthrough file and writes it to the target project's
-/// .bluekit/walkthroughs directory. It creates the directory structure if it doesn't exist.
-/// 
+/// Writes a file only if it hasn't changed since the caller read it.
+///
+/// `expected_hash` (from `get_file_version`) is compared with the file on
+/// disk, or `expected_modified` if no hash is given. If the file was edited
+/// in the meantime (e.g. by an agent in the terminal), nothing is written
+/// and the command fails with a JSON-encoded error whose `code` is
+/// `"write_conflict"` (shaped like a lock conflict), carrying the disk
+/// content and the attempted content. A file that doesn't exist yet is
+/// created without a check.
+///
 /// # Arguments
-/// 
-/// * `source_file_path` - The absolute path to the source walkthrough file
-/// * `target_project_path` - The absolute path to the target project root directory
-/// 
+///
+/// * `file_path` - The absolute path to the file to write
+/// * `content` - The content to write to the file
+/// * `expected_hash` - SHA-256 of the version the caller last read
+/// * `expected_modified` - Modification time (Unix ms) of that version
+///
 /// # Returns
-/// 
-/// A `Result<String, String>` containing either:
-/// - `Ok(String)` - Success case with the path to the copied file
-/// - `Err(String)` - Error case with an error message
-/// 
-/// # Example Usage (from frontend)
-/// 
-/// ```typescript
-/// const result = await invoke<string>('copy_walkthrough_to_project', {
-///   sourceFilePath: '/path/to/source/walkthrough.md',
-///   targetProjectPath: '/path/to/target/project'
-/// });
-/// ```
+///
+/// The version of the file after the write
 #[tauri::command]
-pub async fn copy_walkthrough_to_project(
-    source_file_path: String,
-    target_project_path: String,
-) -> Result<String, String> {
+pub async fn write_file_checked(
+    file_path: String,
+    content: String,
+    expected_hash: Option<String>,
+    expected_modified: Option<i64>,
+) -> Result<FileVersion, String> {
     use std::fs;
-    
-    let source_path = PathBuf::from(&source_file_path);
-    let target_project = PathBuf::from(&target_project_path);
-    
-    // Check if source file exists
-    if !source_path.exists() {
-        return Err(format!("Source file does not exist: {}", source_file_path));
+
+    let path = resolve_write_target(&file_path)?;
+    let _lock = crate::core::file_lock::lock(&path, "write_file_checked").await?;
+
+    if path.exists() {
+        let current = file_version(&path)?;
+        let changed = match (&expected_hash, expected_modified) {
+            (Some(hash), _) => !hash.eq_ignore_ascii_case(&current.hash),
+            (None, Some(modified)) => modified != current.modified,
+            (None, None) => false,
+        };
+
+        if changed {
+            let error = WriteConflictError {
+                code: crate::core::file_lock::WRITE_CONFLICT,
+                path: &file_path,
+                message: format!("File changed on disk since it was read: {}", file_path),
+                disk_content: String::from_utf8_lossy(
+                    &fs::read(&path).map_err(|e| format!("Failed to read file {}: {}", path.display(), e))?,
+                )
+                .to_string(),
+                disk_version: current,
+                attempted_content: &content,
+            };
+            return Err(serde_json::to_string(&error).unwrap_or(error.message));
+        }
     }
-    
-    // Check if target project directory exists
-    if !target_project.exists() {
-        return Err(format!("Target project directory does not exist: {}", target_project_path));
+
+    fs::write(&path, &content)
+        .map_err(|e| format!("Failed to write file {}: {}", path.display(), e))?;
+    file_version(&path)
+}
+
+/// Path a write to `file_path` should go to: the file itself, or the
+/// canonical artifact if it is a link stub.
+fn resolve_write_target(file_path: &str) -> Result<PathBuf, String> {
+    let path = paths::from_ipc(file_path);
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        if let Some(target) = crate::core::links::resolve_from_content(&path, &existing)? {
+            return Ok(target);
+        }
+    }
+    Ok(path)
+}
+
+fn file_version(path: &std::path::Path) -> Result<FileVersion, String> {
+    let hash = crate::core::checksum::hash_file(path)
+        .map_err(|e| format!("Failed to hash file {}: {}", path.display(), e))?;
+    let modified = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    Ok(FileVersion { hash, modified })
+}
+
+/// Records where a copied artifact came from so `check_outdated_copies` can
+/// flag it later. Failures are logged; the copy itself already succeeded.
+async fn record_copy_provenance(
+    db: &sea_orm::DatabaseConnection,
+    source_file_path: &str,
+    target_project_path: &str,
+    target_file_path: &str,
+    content_hash: &str,
+) {
+    use crate::db::provenance_operations::{record_provenance, ProvenanceSource};
+
+    if let Err(e) = record_provenance(
+        db,
+        target_project_path,
+        target_file_path,
+        ProvenanceSource::Local { source_path: source_file_path },
+        content_hash,
+    )
+    .await
+    {
+        tracing::warn!("Failed to record provenance for {}: {}", target_file_path, e);
     }
-    
-    // Get the source file name
-    let file_name = source_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| "Invalid source file name".to_string())?
-        .to_string();
-    
-    // Determine target directory: if .bluekit exists, use structured path, otherwise copy directly
-    let bluekit_dir = target_project.join(".bluekit");
-    let target_file_path = if bluekit_dir.exists() && bluekit_dir.is_dir() {
-        // Use structured path: target_project/.bluekit/walkthroughs/filename
-        let walkthroughs_dir = bluekit_dir.join("walkthroughs");
-        fs::create_dir_all(&walkthroughs_dir)
-            .map_err(|e| format!("Failed to create .bluekit/walkthroughs directory: {}", e))?;
-        walkthroughs_dir.join(&file_name)
-    } else {
-        // Copy directly to target directory
-        target_project.join(&file_name)
-    };
-    
-    // Read source file contents
-    let contents = fs::read_to_string(&source_path)
-        .map_err(|e| format!("Failed to read source file: {}", e))?;
-    
-    // Write to target file
-    fs::write(&target_file_path, contents)
-        .map_err(|e| format!("Failed to write target file: {}", e))?;
-    
-    // Return the target file path as a string
-    target_file_path
-        .to_str()
-        .ok_or_else(|| "Invalid target file path encoding".to_string())
-        .map(|s| s.to_string())
 }
 
-/// Copies a diagram file to a project's .bluekit directory.
-/// 
-/// This command reads the source diagram file (.mmd or .mermaid) and writes it to the target project's
-/// .bluekit/diagrams directory. It creates the directory structure if it doesn't exist.
-/// 
+/// One file to copy with `copy_resources_to_project`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceCopyRequest {
+    /// Absolute path to the source file
+    #[serde(rename = "sourcePath")]
+    pub source_path: String,
+    /// "kit", "walkthrough" or "diagram"; decides the target subdirectory
+    #[serde(rename = "artifactType")]
+    pub artifact_type: String,
+}
+
+/// Outcome of copying one file with `copy_resources_to_project`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceCopyResult {
+    #[serde(rename = "sourcePath")]
+    pub source_path: String,
+    #[serde(rename = "artifactType")]
+    pub artifact_type: String,
+    /// Path of the copy, if it succeeded (the existing file if skipped)
+    #[serde(rename = "targetPath")]
+    pub target_path: Option<String>,
+    /// What the copy did, if it succeeded
+    pub action: Option<crate::core::copy_conflict::CopyAction>,
+    pub error: Option<String>,
+}
+
+/// Copies kits, walkthroughs and diagrams to a project's .bluekit directory.
+///
+/// Each file goes to the subdirectory for its type (`.bluekit/kits`,
+/// `.bluekit/walkthroughs` or `.bluekit/diagrams`), which is created if
+/// needed; if the project has no .bluekit directory, files are copied to
+/// the project root. One failed file doesn't stop the others: every request
+/// gets a result with either the copied path or an error.
+///
+/// Kit copies are recorded in the `kit_usage` table (see `get_kit_usage`),
+/// and every copy records its provenance. Skipped files record neither.
+///
 /// # Arguments
-/// 
-/// * `source_file_path` - The absolute path to the source diagram file
+///
+/// * `resources` - Files to copy with their artifact types
 /// * `target_project_path` - The absolute path to the target project root directory
-/// 
+/// * `on_conflict` - What to do when a target file exists: "overwrite"
+///   (default), "skip", "rename-with-suffix" or "fail"
+///
 /// # Returns
-/// 
-/// A `Result<String, String>` containing either:
-/// - `Ok(String)` - Success case with the path to the copied file
-/// - `Err(String)` - Error case with an error message
-/// 
+///
+/// A `Result<Vec<ResourceCopyResult>, String>` with one result per request,
+/// in order. The call itself only fails if the target project doesn't exist.
+///
 /// # Example Usage (from frontend)
-/// 
+///
 /// ```typescript
-/// const result = await invoke<string>('copy_diagram_to_project', {
-///   sourceFilePath: '/path/to/source/diagram.mmd',
-///   targetProjectPath: '/path/to/target/project'
+/// const results = await invoke<ResourceCopyResult[]>('copy_resources_to_project', {
+///   resources: [
+///     { sourcePath: '/path/to/source/kit.md', artifactType: 'kit' },
+///     { sourcePath: '/path/to/source/diagram.mmd', artifactType: 'diagram' }
+///   ],
+///   targetProjectPath: '/path/to/target/project',
+///   onConflict: 'skip'
 /// });
 /// ```
 #[tauri::command]
-pub async fn copy_diagram_to_project(
-    source_file_path: String,
+pub async fn copy_resources_to_project(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    resources: Vec<ResourceCopyRequest>,
     target_project_path: String,
-) -> Result<String, String> {
+    on_conflict: Option<crate::core::copy_conflict::ConflictPolicy>,
+) -> Result<Vec<ResourceCopyResult>, String> {
+    let on_conflict = on_conflict.unwrap_or_default();
+
+    // Check if target project directory exists
+    if !paths::from_ipc(&target_project_path).exists() {
+        return Err(format!("Target project directory does not exist: {}", target_project_path));
+    }
+
+    let mut results = Vec::with_capacity(resources.len());
+    for resource in resources {
+        let outcome = copy_resource_file(
+            db.inner(),
+            &resource.source_path,
+            &resource.artifact_type,
+            &target_project_path,
+            on_conflict,
+        )
+        .await;
+
+        let (target_path, action, error) = match outcome {
+            Ok((target_path, action)) => (Some(target_path), Some(action), None),
+            Err(e) => {
+                tracing::warn!("Failed to copy {}: {}", resource.source_path, e);
+                (None, None, Some(e))
+            }
+        };
+        results.push(ResourceCopyResult {
+            source_path: resource.source_path,
+            artifact_type: resource.artifact_type,
+            target_path,
+            action,
+            error,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Copies one resource file for `copy_resources_to_project`, returning the
+/// path of the copy and what was done.
+async fn copy_resource_file(
+    db: &DatabaseConnection,
+    source_file_path: &str,
+    artifact_type: &str,
+    target_project_path: &str,
+    on_conflict: crate::core::copy_conflict::ConflictPolicy,
+) -> Result<(String, crate::core::copy_conflict::CopyAction), String> {
     use std::fs;
-    
-    let source_path = PathBuf::from(&source_file_path);
-    let target_project = PathBuf::from(&target_project_path);
-    
+
+    let subdirectory = match artifact_type {
+        "kit" => "kits",
+        "walkthrough" => "walkthroughs",
+        "diagram" => "diagrams",
+        other => return Err(format!("Unsupported artifact type: {}", other)),
+    };
+
+    let source_path = paths::from_ipc(source_file_path);
+    let target_project = paths::from_ipc(target_project_path);
+
     // Check if source file exists
-    if !source_path.exists() {
+    if !source_path.is_file() {
         return Err(format!("Source file does not exist: {}", source_file_path));
     }
-    
-    // Check if target project directory exists
-    if !target_project.exists() {
-        return Err(format!("Target project directory does not exist: {}", target_project_path));
-    }
-    
+
     // Get the source file name
     let file_name = source_path
         .file_name()
-        .and_then(|n| n.to_str())
         .ok_or_else(|| "Invalid source file name".to_string())?
-        .to_string();
-    
+        .to_os_string();
+
     // Determine target directory: if .bluekit exists, use structured path, otherwise copy directly
     let bluekit_dir = target_project.join(".bluekit");
-    let target_file_path = if bluekit_dir.exists() && bluekit_dir.is_dir() {
-        // Use structured path: target_project/.bluekit/diagrams/filename
-        let diagrams_dir = bluekit_dir.join("diagrams");
-        fs::create_dir_all(&diagrams_dir)
-            .map_err(|e| format!("Failed to create .bluekit/diagrams directory: {}", e))?;
-        diagrams_dir.join(&file_name)
+    let target_file_path = if bluekit_dir.is_dir() {
+        // Use structured path: target_project/.bluekit/<subdirectory>/filename
+        let type_dir = bluekit_dir.join(subdirectory);
+        fs::create_dir_all(&type_dir)
+            .map_err(|e| format!("Failed to create .bluekit/{} directory: {}", subdirectory, e))?;
+        type_dir.join(&file_name)
     } else {
         // Copy directly to target directory
         target_project.join(&file_name)
     };
-    
-    // Read source file contents
-    let contents = fs::read_to_string(&source_path)
-        .map_err(|e| format!("Failed to read source file: {}", e))?;
-    
-    // Write to target file
-    fs::write(&target_file_path, contents)
-        .map_err(|e| format!("Failed to write target file: {}", e))?;
-    
-    // Return the target file path as a string
-    target_file_path
-        .to_str()
-        .ok_or_else(|| "Invalid target file path encoding".to_string())
-        .map(|s| s.to_string())
+
+    let (target_file_path, action) =
+        crate::core::copy_conflict::resolve_target(&target_file_path, on_conflict)?;
+    if action == crate::core::copy_conflict::CopyAction::Skipped {
+        return Ok((paths::to_ipc(&target_file_path), action));
+    }
+
+    // Stream the copy so large files never sit in memory
+    let content_hash = crate::library::utils::copy_file_with_hash(&source_path, &target_file_path)
+        .map_err(|e| format!("Failed to copy file: {}", e))?;
+
+    let target_file_str = paths::to_ipc(&target_file_path);
+
+    // Usage tracking is best-effort; the copy itself already succeeded
+    if artifact_type == "kit" {
+        if let Err(e) = crate::db::kit_usage_operations::record_kit_usage(
+            db,
+            source_file_path,
+            target_project_path,
+            &target_file_str,
+            &content_hash,
+        )
+        .await
+        {
+            tracing::warn!("Failed to record kit usage for {}: {}", source_file_path, e);
+        }
+    }
+
+    record_copy_provenance(db, source_file_path, target_project_path, &target_file_str, &content_hash).await;
+
+    Ok((target_file_str, action))
+}
+
+/// One file written (or skipped) by a copy command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopiedFile {
+    pub path: String,
+    pub action: crate::core::copy_conflict::CopyAction,
+}
+
+/// Outcome of `copy_blueprint_to_project`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlueprintCopyResult {
+    /// Path of the blueprint directory in the project (the existing one if
+    /// skipped)
+    #[serde(rename = "targetPath")]
+    pub target_path: String,
+    /// What happened to the blueprint directory as a whole
+    pub action: crate::core::copy_conflict::CopyAction,
+    /// Every file copied into it; empty if skipped
+    pub files: Vec<CopiedFile>,
 }
 
 /// Copies a blueprint directory to a project's .bluekit/blueprints directory.
 /// 
 /// This command recursively copies the entire blueprint directory (including blueprint.json
 /// and all task files) to the target project's .bluekit/blueprints directory.
+///
+/// The conflict policy applies to the blueprint directory as a whole, so a
+/// blueprint is never half renamed: "skip" leaves an existing blueprint
+/// alone, "rename-with-suffix" copies it as `name-2`, and "overwrite" copies
+/// into the existing directory, replacing files with the same name.
 /// 
 /// # Arguments
 /// 
 /// * `source_blueprint_path` - The absolute path to the source blueprint directory
 /// * `target_project_path` - The absolute path to the target project root directory
+/// * `on_conflict` - What to do when the blueprint already exists: "overwrite"
+///   (default), "skip", "rename-with-suffix" or "fail"
 /// 
 /// # Returns
 /// 
-/// A `Result<String, String>` containing either:
-/// - `Ok(String)` - Success case with the path to the copied blueprint directory
+/// A `Result<BlueprintCopyResult, String>` containing either:
+/// - `Ok(BlueprintCopyResult)` - Success case with the copied blueprint directory and what happened to each file
 /// - `Err(String)` - Error case with an error message
 /// 
 /// # Example Usage (from frontend)
 /// 
 /// ```typescript
-/// const result = await invoke<string>('copy_blueprint_to_project', {
+/// const result = await invoke<BlueprintCopyResult>('copy_blueprint_to_project', {
 ///   sourceBlueprintPath: '/path/to/source/blueprint',
-///   targetProjectPath: '/path/to/target/project'
+///   targetProjectPath: '/path/to/target/project',
+///   onConflict: 'rename-with-suffix'
 /// });
 /// ```
 #[tauri::command]
 pub async fn copy_blueprint_to_project(
     source_blueprint_path: String,
     target_project_path: String,
-) -> Result<String, String> {
+    on_conflict: Option<crate::core::copy_conflict::ConflictPolicy>,
+) -> Result<BlueprintCopyResult, String> {
+    use crate::core::copy_conflict::{resolve_target, ConflictPolicy, CopyAction};
     use std::fs;
     
-    let source_path = PathBuf::from(&source_blueprint_path);
-    let target_project = PathBuf::from(&target_project_path);
+    let source_path = paths::from_ipc(&source_blueprint_path);
+    let target_project = paths::from_ipc(&target_project_path);
     
     // Check if source blueprint directory exists
     if !source_path.exists() {
@@ -1028,9 +2229,8 @@ pub async fn copy_blueprint_to_project(
     // Get the blueprint directory name
     let blueprint_name = source_path
         .file_name()
-        .and_then(|n| n.to_str())
         .ok_or_else(|| "Invalid blueprint directory name".to_string())?
-        .to_string();
+        .to_os_string();
     
     // Construct target path: target_project/.bluekit/blueprints/blueprint_name
     let bluekit_dir = target_project.join(".bluekit");
@@ -1041,10 +2241,24 @@ pub async fn copy_blueprint_to_project(
         .map_err(|e| format!("Failed to create .bluekit/blueprints directory: {}", e))?;
     
     // Construct the full target blueprint directory path
-    let target_blueprint_path = blueprints_dir.join(&blueprint_name);
+    let (target_blueprint_path, action) =
+        resolve_target(&blueprints_dir.join(&blueprint_name), on_conflict.unwrap_or_default())?;
+    if action == CopyAction::Skipped {
+        return Ok(BlueprintCopyResult {
+            target_path: paths::to_ipc(&target_blueprint_path),
+            action,
+            files: Vec::new(),
+        });
+    }
     
-    // Helper function to recursively copy directory
-    fn copy_dir_recursive(source: &PathBuf, target: &PathBuf) -> Result<(), String> {
+    // Helper function to recursively copy directory, collecting what happened
+    // to each file and the files whose copies don't match their source
+    fn copy_dir_recursive(
+        source: &std::path::Path,
+        target: &std::path::Path,
+        files: &mut Vec<CopiedFile>,
+        mismatches: &mut Vec<PathBuf>,
+    ) -> Result<(), String> {
         use std::fs;
         
         // Create target directory
@@ -1060,36 +2274,47 @@ pub async fn copy_blueprint_to_project(
             let entry_path = entry.path();
             let entry_name = entry_path
                 .file_name()
-                .and_then(|n| n.to_str())
                 .ok_or_else(|| "Invalid entry name".to_string())?;
             
             let target_path = target.join(entry_name);
             
             if entry_path.is_dir() {
                 // Recursively copy subdirectory
-                copy_dir_recursive(&entry_path, &target_path)?;
-            } else {
-                // Copy file
-                let contents = fs::read_to_string(&entry_path)
-                    .map_err(|e| format!("Failed to read file {}: {}", entry_path.display(), e))?;
-                fs::write(&target_path, contents)
-                    .map_err(|e| format!("Failed to write file {}: {}", target_path.display(), e))?;
+                copy_dir_recursive(&entry_path, &target_path, files, mismatches)?;
+                continue;
             }
+
+            // Inside a blueprint being copied, same-named files are replaced
+            let (target_path, action) = resolve_target(&target_path, ConflictPolicy::Overwrite)?;
+            if !crate::core::checksum::copy_verified(&entry_path, &target_path)? {
+                // Copied as bytes, so binary assets are fine too
+                mismatches.push(target_path.clone());
+            }
+            files.push(CopiedFile {
+                path: paths::to_ipc(&target_path),
+                action,
+            });
         }
         
         Ok(())
     }
     
     // Copy the blueprint directory
-    copy_dir_recursive(&source_path, &target_blueprint_path)?;
+    let mut files = Vec::new();
+    let mut mismatches = Vec::new();
+    copy_dir_recursive(&source_path, &target_blueprint_path, &mut files, &mut mismatches)?;
+    crate::core::checksum::check_mismatches(&mismatches)?;
     
-    // Return the target blueprint directory path as a string
-    target_blueprint_path
-        .to_str()
-        .ok_or_else(|| "Invalid target blueprint path encoding".to_string())
-        .map(|s| s.to_string())
+    Ok(BlueprintCopyResult {
+        target_path: paths::to_ipc(&target_blueprint_path),
+        action,
+        files,
+    })
 }
 
+/// Artifact type folders in `.bluekit`, which the scrapbook leaves out
+const ARTIFACT_FOLDERS: [&str; 6] = ["kits", "agents", "walkthroughs", "blueprints", "diagrams", "tasks"];
+
 /// Gets scrapbook items (folders and loose .md files) from the .bluekit directory.
 ///
 /// This command scans the .bluekit directory and returns all folders and loose .md files
@@ -1109,7 +2334,7 @@ pub async fn get_scrapbook_items(project_path: String) -> Result<Vec<ScrapbookIt
     use std::fs;
 
     // Construct the path to .bluekit directory
-    let bluekit_path = PathBuf::from(&project_path).join(".bluekit");
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
 
     // Check if .bluekit directory exists
     if !bluekit_path.exists() {
@@ -1117,7 +2342,7 @@ pub async fn get_scrapbook_items(project_path: String) -> Result<Vec<ScrapbookIt
     }
 
     let mut items = Vec::new();
-    let known_folders = vec!["kits", "agents", "walkthroughs", "blueprints", "diagrams", "tasks"];
+    let ignore_rules = crate::core::ignore_rules::IgnoreRules::load(&bluekit_path);
 
     // Read entries in .bluekit directory
     let entries = fs::read_dir(&bluekit_path)
@@ -1126,14 +2351,10 @@ pub async fn get_scrapbook_items(project_path: String) -> Result<Vec<ScrapbookIt
     for entry in entries {
         let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
         let path = entry.path();
-        let name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("")
-            .to_string();
+        let name = paths::file_name_lossy(&path);
 
         // Skip known folders
-        if known_folders.contains(&name.as_str()) {
+        if ARTIFACT_FOLDERS.contains(&name.as_str()) {
             continue;
         }
 
@@ -1147,26 +2368,27 @@ pub async fn get_scrapbook_items(project_path: String) -> Result<Vec<ScrapbookIt
             continue;
         }
 
+        // Skip anything .bluekitignore matches
+        if ignore_rules.is_ignored(&path, path.is_dir()) {
+            continue;
+        }
+
         if path.is_dir() {
             // Add folder to scrapbook
             items.push(ScrapbookItem {
                 name: name.clone(),
-                path: path.to_str().unwrap_or("").to_string(),
+                path: paths::to_ipc(&path),
                 is_folder: true,
             });
         } else if path.is_file() {
             // Only add .md files
             if let Some(extension) = path.extension() {
                 if extension == "md" {
-                    let file_name = path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("")
-                        .to_string();
+                    let file_name = paths::file_stem_lossy(&path);
 
                     items.push(ScrapbookItem {
                         name: file_name,
-                        path: path.to_str().unwrap_or("").to_string(),
+                        path: paths::to_ipc(&path),
                         is_folder: false,
                     });
                 }
@@ -1188,9 +2410,16 @@ pub async fn get_scrapbook_items(project_path: String) -> Result<Vec<ScrapbookIt
 
 /// Gets markdown files from a specific folder in the .bluekit directory.
 ///
+/// Only the folder itself is listed unless `recursive` is set or `max_depth`
+/// is given. `max_depth` is how many levels of subfolders to descend into
+/// (unlimited when `recursive` is set without it). Hidden subfolders are
+/// skipped, and each file's `relativePath` is its path below `folder_path`.
+///
 /// # Arguments
 ///
 /// * `folder_path` - The absolute path to the folder
+/// * `recursive` - Whether to include markdown files in subfolders
+/// * `max_depth` - How many levels of subfolders to include
 ///
 /// # Returns
 ///
@@ -1198,20 +2427,56 @@ pub async fn get_scrapbook_items(project_path: String) -> Result<Vec<ScrapbookIt
 /// - `Ok(Vec<ArtifactFile>)` - Success case with list of markdown files
 /// - `Err(String)` - Error case with an error message
 #[tauri::command]
-pub async fn get_folder_markdown_files(folder_path: String) -> Result<Vec<ArtifactFile>, String> {
-    use std::fs;
-
-    let path = PathBuf::from(&folder_path);
+pub async fn get_folder_markdown_files(
+    folder_path: String,
+    recursive: Option<bool>,
+    max_depth: Option<usize>,
+) -> Result<Vec<ArtifactFile>, String> {
+    let path = paths::from_ipc(&folder_path);
 
     // Check if folder exists
     if !path.exists() || !path.is_dir() {
         return Ok(Vec::new());
     }
 
+    let max_depth = match (recursive.unwrap_or(false), max_depth) {
+        (_, Some(depth)) => depth,
+        (true, None) => usize::MAX,
+        (false, None) => 0,
+    };
+
     let mut files = Vec::new();
+    let mut visited = std::collections::HashSet::new();
+    collect_folder_markdown(&path, &path, max_depth, &mut visited, &mut files)?;
+
+    // Sort alphabetically, files before those in subfolders
+    files.sort_by(|a, b| {
+        let depth = |f: &ArtifactFile| f.relative_path.as_deref().map_or(0, |p| p.matches('/').count());
+        depth(a)
+            .cmp(&depth(b))
+            .then_with(|| a.relative_path.cmp(&b.relative_path))
+    });
 
-    // Read entries in the folder
-    let entries = fs::read_dir(&path)
+    Ok(files)
+}
+
+// Adds the markdown files in `dir` to `files`, descending `depth_left` more
+// levels. `visited` holds canonical folder paths so symlink cycles end
+fn collect_folder_markdown(
+    root: &std::path::Path,
+    dir: &std::path::Path,
+    depth_left: usize,
+    visited: &mut std::collections::HashSet<PathBuf>,
+    files: &mut Vec<ArtifactFile>,
+) -> Result<(), String> {
+    use std::fs;
+
+    let canonical = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    if !visited.insert(canonical) {
+        return Ok(());
+    }
+
+    let entries = fs::read_dir(dir)
         .map_err(|e| format!("Failed to read folder: {}", e))?;
 
     for entry in entries {
@@ -1219,34 +2484,34 @@ pub async fn get_folder_markdown_files(folder_path: String) -> Result<Vec<Artifa
         let entry_path = entry.path();
 
         if entry_path.is_file() {
-            if let Some(extension) = entry_path.extension() {
-                if extension == "md" {
-                    let name = entry_path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("")
-                        .to_string();
-
-                    let path_str = entry_path
-                        .to_str()
-                        .ok_or_else(|| "Invalid path encoding".to_string())?
-                        .to_string();
-
-                    files.push(ArtifactFile {
-                        name,
-                        path: path_str,
-                        content: None,
-                        front_matter: None,
-                    });
-                }
+            if entry_path.extension().is_some_and(|ext| ext == "md") {
+                let relative_path = entry_path
+                    .strip_prefix(root)
+                    .map(|p| p.to_string_lossy().replace('\\', "/"))
+                    .ok();
+
+                files.push(ArtifactFile {
+                    name: paths::file_stem_lossy(&entry_path),
+                    path: paths::to_ipc(&entry_path),
+                    content: None,
+                    front_matter: None,
+                    linked_path: None,
+                    oversized_bytes: None,
+                    metadata: read_front_matter_only(&entry_path).as_ref().map(artifact_metadata),
+                    symlinked: is_symlinked(&entry_path, root),
+                    relative_path,
+                    read_stats: None,
+                });
             }
+        } else if entry_path.is_dir()
+            && depth_left > 0
+            && !paths::file_name_lossy(&entry_path).starts_with('.')
+        {
+            collect_folder_markdown(root, &entry_path, depth_left - 1, visited, files)?;
         }
     }
 
-    // Sort alphabetically
-    files.sort_by(|a, b| a.name.cmp(&b.name));
-
-    Ok(files)
+    Ok(())
 }
 
 /// Gets all plan files from Claude or Cursor plans directory.
@@ -1278,14 +2543,7 @@ pub async fn get_plans_files(source: String) -> Result<Vec<ArtifactFile>, String
         return Err(format!("Invalid source: {}. Must be 'claude' or 'cursor'", source));
     }
 
-    // Get home directory
-    let home_dir = env::var("HOME")
-        .map_err(|e| format!("Could not determine home directory: {:?}", e))?;
-
-    // Construct path to plans directory
-    let plans_path = PathBuf::from(&home_dir)
-        .join(if source == "claude" { ".claude" } else { ".cursor" })
-        .join("plans");
+    let plans_path = external_plans_dir(&source)?;
 
     // Check if folder exists
     if !plans_path.exists() || !plans_path.is_dir() {
@@ -1305,22 +2563,21 @@ pub async fn get_plans_files(source: String) -> Result<Vec<ArtifactFile>, String
         if entry_path.is_file() {
             if let Some(extension) = entry_path.extension() {
                 if extension == "md" {
-                    let name = entry_path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("")
-                        .to_string();
+                    let name = paths::file_stem_lossy(&entry_path);
 
-                    let path_str = entry_path
-                        .to_str()
-                        .ok_or_else(|| "Invalid path encoding".to_string())?
-                        .to_string();
+                    let path_str = paths::to_ipc(&entry_path);
 
                     files.push(ArtifactFile {
                         name,
                         path: path_str,
                         content: None,
                         front_matter: None,
+                        linked_path: None,
+                        oversized_bytes: None,
+                        metadata: read_front_matter_only(&entry_path).as_ref().map(artifact_metadata),
+                        symlinked: is_symlinked(&entry_path, &plans_path),
+                        relative_path: None,
+                        read_stats: None,
                     });
                 }
             }
@@ -1333,23 +2590,259 @@ pub async fn get_plans_files(source: String) -> Result<Vec<ArtifactFile>, String
     Ok(files)
 }
 
-/// Gets all blueprints from the .bluekit/blueprints directory.
+/// Creates a scrapbook folder in a project's .bluekit directory.
 ///
 /// # Arguments
 ///
 /// * `project_path` - The path to the project root directory
+/// * `name` - Folder name; can't be an artifact type folder (kits, walkthroughs, ...), `assets` or hidden
+#[tauri::command]
+pub async fn create_scrapbook_folder(project_path: String, name: String) -> Result<ScrapbookItem, String> {
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+    let name = scrapbook_folder_name(&name)?;
+    let folder = bluekit_path.join(name);
+    if folder.exists() {
+        return Err(format!("{} already exists in .bluekit", name));
+    }
+
+    std::fs::create_dir_all(&folder).map_err(|e| format!("Failed to create folder: {}", e))?;
+    Ok(ScrapbookItem {
+        name: name.to_string(),
+        path: paths::to_ipc(&folder),
+        is_folder: true,
+    })
+}
+
+/// Renames a scrapbook folder.
 ///
-/// # Returns
+/// # Arguments
 ///
-/// A `Result<Vec<Blueprint>, String>` containing either:
-/// - `Ok(Vec<Blueprint>)` - Success case with list of blueprints
-/// - `Err(String)` - Error case with an error message
+/// * `project_path` - The path to the project root directory
+/// * `folder_path` - The folder to rename
+/// * `new_name` - Its new name, with the same restrictions as `create_scrapbook_folder`
 #[tauri::command]
-pub async fn get_blueprints(project_path: String) -> Result<Vec<Blueprint>, String> {
-    use std::fs;
+pub async fn rename_scrapbook_folder(
+    project_path: String,
+    folder_path: String,
+    new_name: String,
+) -> Result<ScrapbookItem, String> {
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+    let folder = scrapbook_folder(&bluekit_path, &folder_path)?;
+    let new_name = scrapbook_folder_name(&new_name)?;
+    let destination = bluekit_path.join(new_name);
+    if destination.exists() {
+        return Err(format!("{} already exists in .bluekit", new_name));
+    }
+
+    std::fs::rename(&folder, &destination).map_err(|e| format!("Failed to rename folder: {}", e))?;
+    Ok(ScrapbookItem {
+        name: new_name.to_string(),
+        path: paths::to_ipc(&destination),
+        is_folder: true,
+    })
+}
+
+/// Moves a scrapbook note into a scrapbook folder, or back to the top of
+/// .bluekit when `target_folder_path` is omitted. Fails rather than
+/// overwrite a note with the same name.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory
+/// * `item_path` - A loose .md file in .bluekit or in a scrapbook folder
+/// * `target_folder_path` - The scrapbook folder to move it into
+///
+/// # Returns
+///
+/// The moved note's new path
+#[tauri::command]
+pub async fn move_scrapbook_item(
+    project_path: String,
+    item_path: String,
+    target_folder_path: Option<String>,
+) -> Result<String, String> {
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+    let item = paths::from_ipc(&item_path);
+
+    let in_scrapbook = match item.parent() {
+        Some(parent) if parent == bluekit_path => true,
+        Some(parent) => scrapbook_folder(&bluekit_path, &paths::to_ipc(parent)).is_ok(),
+        None => false,
+    };
+    if !in_scrapbook || !item.is_file() || item.extension().is_none_or(|ext| ext != "md") {
+        return Err(format!("Not a scrapbook note: {}", item_path));
+    }
+
+    let target_folder = match &target_folder_path {
+        Some(folder_path) => scrapbook_folder(&bluekit_path, folder_path)?,
+        None => bluekit_path.clone(),
+    };
+    let file_name = item
+        .file_name()
+        .ok_or_else(|| format!("Invalid note path: {}", item_path))?;
+    let destination = target_folder.join(file_name);
+    if destination == item {
+        return Ok(item_path);
+    }
+    if destination.exists() {
+        return Err(format!("{} already exists in the target folder", paths::file_name_lossy(&item)));
+    }
+
+    std::fs::rename(&item, &destination).map_err(|e| format!("Failed to move note: {}", e))?;
+    Ok(paths::to_ipc(&destination))
+}
+
+/// Deletes a scrapbook folder and its notes, moving it to the project's
+/// trash unless `permanent` is set.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory
+/// * `folder_path` - The folder to delete
+/// * `permanent` - Delete instead of moving to the trash
+#[tauri::command]
+pub async fn delete_scrapbook_folder(
+    project_path: String,
+    folder_path: String,
+    permanent: Option<bool>,
+) -> Result<(), String> {
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+    let folder = scrapbook_folder(&bluekit_path, &folder_path)?;
+
+    if permanent.unwrap_or(false) {
+        std::fs::remove_dir_all(&folder).map_err(|e| format!("Failed to delete folder: {}", e))
+    } else {
+        crate::core::trash::move_to_trash(&folder).await.map(|_| ())
+    }
+}
+
+/// Checks a new scrapbook folder name: a single path component that isn't
+/// hidden, an artifact type folder or `assets`.
+fn scrapbook_folder_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Folder name cannot be empty".to_string());
+    }
+    if name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(format!("Invalid folder name: {}", name));
+    }
+    if ARTIFACT_FOLDERS.contains(&name) || name == crate::core::assets::ASSETS_DIR {
+        return Err(format!("{} is reserved for BlueKit artifacts", name));
+    }
+    Ok(name)
+}
+
+/// Resolves `folder_path` to an existing scrapbook folder: a folder directly
+/// inside `bluekit_path` that the scrapbook lists.
+fn scrapbook_folder(bluekit_path: &std::path::Path, folder_path: &str) -> Result<PathBuf, String> {
+    let folder = paths::from_ipc(folder_path);
+    if folder.parent() != Some(bluekit_path) || !folder.is_dir() {
+        return Err(format!("Not a scrapbook folder: {}", folder_path));
+    }
+    scrapbook_folder_name(&paths::file_name_lossy(&folder))?;
+    Ok(folder)
+}
+
+/// `~/.claude/plans` or `~/.cursor/plans`, for `source` "claude" or "cursor".
+fn external_plans_dir(source: &str) -> Result<PathBuf, String> {
+    let home_dir = env::var("HOME")
+        .map_err(|e| format!("Could not determine home directory: {:?}", e))?;
+
+    Ok(PathBuf::from(&home_dir)
+        .join(if source == "claude" { ".claude" } else { ".cursor" })
+        .join("plans"))
+}
+
+/// Payload of the `linked-plans-changed` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkedPlansChanged {
+    /// "claude" or "cursor"
+    pub source: String,
+    /// Plans with a `plan_links` row pointing at a changed file
+    #[serde(rename = "planIds")]
+    pub plan_ids: Vec<String>,
+    /// Every changed plan file
+    pub paths: Vec<String>,
+}
+
+/// Starts watching `~/.claude/plans` and `~/.cursor/plans`.
+///
+/// Changes to a plans directory emit `external-plans-changed-claude` or
+/// `external-plans-changed-cursor` with the changed paths, like
+/// `watch_project_artifacts`. When a changed file is linked to a plan
+/// (`link_multiple_plans_to_plan`), `linked-plans-changed` is emitted as
+/// well, with the ids of the affected plans, so open plans can refresh
+/// their linked documents.
+///
+/// Directories that don't exist yet are skipped rather than created.
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// await invoke('watch_external_plans');
+/// await listen<LinkedPlansChanged>('linked-plans-changed', ({ payload }) => refresh(payload.planIds));
+/// ```
+#[tauri::command]
+pub async fn watch_external_plans(app_handle: AppHandle) -> Result<(), String> {
+    use crate::core::watcher;
+    use tauri::Manager;
+
+    for source in ["claude", "cursor"] {
+        let plans_path = external_plans_dir(source)?;
+        let event_name = format!("external-plans-changed-{}", source);
+        if !plans_path.is_dir() {
+            tracing::debug!("Not watching {}: directory does not exist", plans_path.display());
+            continue;
+        }
+        if watcher::watcher_exists(&event_name).await {
+            continue;
+        }
+
+        // Translate changed paths into the plans that link to them
+        let hook: watcher::ChangeHook = std::sync::Arc::new(move |app_handle, paths| {
+            let app_handle = app_handle.clone();
+            let paths = paths.to_vec();
+            tauri::async_runtime::spawn(async move {
+                let db = app_handle.state::<DatabaseConnection>();
+                match crate::db::plan_operations::plan_ids_linked_to(db.inner(), &paths).await {
+                    Ok(plan_ids) if !plan_ids.is_empty() => {
+                        let payload = LinkedPlansChanged {
+                            source: source.to_string(),
+                            plan_ids,
+                            paths,
+                        };
+                        if let Err(e) = app_handle.emit_all("linked-plans-changed", payload) {
+                            tracing::error!("Failed to emit linked plan change: {}", e);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Failed to look up linked plans: {}", e),
+                }
+            });
+        });
+        watcher::watch_directory_with_hook(app_handle.clone(), plans_path, event_name, Some(hook))?;
+    }
+
+    Ok(())
+}
+
+/// Gets all blueprints from the .bluekit/blueprints directory.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory
+///
+/// # Returns
+///
+/// A `Result<Vec<Blueprint>, String>` containing either:
+/// - `Ok(Vec<Blueprint>)` - Success case with list of blueprints
+/// - `Err(String)` - Error case with an error message
+#[tauri::command]
+pub async fn get_blueprints(project_path: String) -> Result<Vec<Blueprint>, String> {
+    use std::fs;
 
     // Construct the path to .bluekit/blueprints directory
-    let blueprints_path = PathBuf::from(&project_path).join(".bluekit").join("blueprints");
+    let blueprints_path = paths::from_ipc(&project_path).join(".bluekit").join("blueprints");
 
     // Check if blueprints directory exists
     if !blueprints_path.exists() {
@@ -1371,11 +2864,7 @@ pub async fn get_blueprints(project_path: String) -> Result<Vec<Blueprint>, Stri
             continue;
         }
 
-        let name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("")
-            .to_string();
+        let name = paths::file_name_lossy(&path);
 
         // Skip hidden directories
         if name.starts_with('.') {
@@ -1391,7 +2880,7 @@ pub async fn get_blueprints(project_path: String) -> Result<Vec<Blueprint>, Stri
                         Ok(metadata) => {
                             blueprints.push(Blueprint {
                                 name: name.clone(),
-                                path: path.to_str().unwrap_or("").to_string(),
+                                path: paths::to_ipc(&path),
                                 metadata,
                             });
                         }
@@ -1432,7 +2921,7 @@ pub async fn get_blueprint_task_file(
 ) -> Result<String, String> {
     use std::fs;
 
-    let blueprint_dir = PathBuf::from(&blueprint_path);
+    let blueprint_dir = paths::from_ipc(&blueprint_path);
     let task_file_path = blueprint_dir.join(&task_file);
 
     // Check if task file exists
@@ -1463,7 +2952,7 @@ pub async fn get_project_diagrams(project_path: String) -> Result<Vec<ArtifactFi
     use std::fs;
 
     // Construct the path to .bluekit/diagrams directory
-    let diagrams_path = PathBuf::from(&project_path).join(".bluekit").join("diagrams");
+    let diagrams_path = paths::from_ipc(&project_path).join(".bluekit").join("diagrams");
 
     // Check if diagrams directory exists
     if !diagrams_path.exists() {
@@ -1473,7 +2962,11 @@ pub async fn get_project_diagrams(project_path: String) -> Result<Vec<ArtifactFi
     let mut diagrams = Vec::new();
 
     // Helper function to read mermaid files from a directory recursively
-    fn read_mermaid_files_from_dir(dir_path: &PathBuf, diagrams: &mut Vec<ArtifactFile>) -> Result<(), String> {
+    fn read_mermaid_files_from_dir(
+        root: &std::path::Path,
+        dir_path: &PathBuf,
+        diagrams: &mut Vec<ArtifactFile>,
+    ) -> Result<(), String> {
         if !dir_path.exists() {
             return Ok(()); // Directory doesn't exist, skip it
         }
@@ -1490,29 +2983,28 @@ pub async fn get_project_diagrams(project_path: String) -> Result<Vec<ArtifactFi
                     // Accept both .mmd and .mermaid extensions
                     if extension == "mmd" || extension == "mermaid" {
                         // Get the file name without extension
-                        let name = path
-                            .file_stem()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("")
-                            .to_string();
+                        let name = paths::file_stem_lossy(&path);
                         
                         // Get the full path as a string
-                        let path_str = path
-                            .to_str()
-                            .ok_or_else(|| "Invalid path encoding".to_string())?
-                            .to_string();
+                        let path_str = paths::to_ipc(&path);
                         
                         diagrams.push(ArtifactFile {
                             name,
                             path: path_str,
                             content: None,
                             front_matter: None,
+                            linked_path: None,
+                            oversized_bytes: None,
+                            metadata: read_front_matter_only(&path).as_ref().map(artifact_metadata),
+                            symlinked: is_symlinked(&path, root),
+                            relative_path: None,
+                            read_stats: None,
                         });
                     }
                 }
             } else if path.is_dir() {
                 // Recursively read subdirectories
-                read_mermaid_files_from_dir(&path, diagrams)?;
+                read_mermaid_files_from_dir(root, &path, diagrams)?;
             }
         }
         
@@ -1520,7 +3012,8 @@ pub async fn get_project_diagrams(project_path: String) -> Result<Vec<ArtifactFi
     }
 
     // Read from diagrams directory
-    read_mermaid_files_from_dir(&diagrams_path, &mut diagrams)?;
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+    read_mermaid_files_from_dir(&bluekit_path, &diagrams_path, &mut diagrams)?;
 
     // Sort alphabetically
     diagrams.sort_by(|a, b| a.name.cmp(&b.name));
@@ -1544,25 +3037,214 @@ pub async fn get_project_diagrams(project_path: String) -> Result<Vec<ArtifactFi
 /// - `Err(String)` - Error case with an error message
 #[tauri::command]
 pub async fn get_project_clones(project_path: String) -> Result<Vec<CloneMetadata>, String> {
-    use std::fs;
-
     // Construct the path to clones.json
-    let clones_path = PathBuf::from(&project_path).join(".bluekit").join("clones.json");
+    let clones_path = paths::from_ipc(&project_path).join(".bluekit").join("clones.json");
+
+    read_clones(&clones_path)
+}
+
+/// Records the project's current git state as a new clone in
+/// `.bluekit/clones.json`.
+///
+/// The clone ID is the slugified name plus today's date
+/// (`slugified-name-YYYYMMDD`), with a numeric suffix if that ID is taken.
+/// The file is rewritten atomically under a lock so concurrent edits can't
+/// drop entries.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory (must be a git repository with an `origin` remote)
+/// * `name` - Display name for the clone
+/// * `description` - What this clone represents
+/// * `tags` - Optional tags for categorization
+///
+/// # Returns
+///
+/// The new `CloneMetadata` entry
+#[tauri::command]
+pub async fn create_clone(
+    project_path: String,
+    name: String,
+    description: String,
+    tags: Option<Vec<String>>,
+) -> Result<CloneMetadata, String> {
+    let name = name.trim().to_string();
+    let slug = crate::db::walkthrough_operations::slugify(&name);
+    if slug.is_empty() {
+        return Err("Clone name must contain letters or digits".to_string());
+    }
+
+    let root = paths::from_ipc(&project_path);
+    let git = crate::integrations::git::detect_git_metadata(&root.to_string_lossy())?;
+
+    let clones_path = root.join(".bluekit").join("clones.json");
+    let _lock = crate::core::file_lock::lock(&clones_path, "create_clone").await?;
+    let mut clones = read_clones(&clones_path)?;
+
+    let now = chrono::Utc::now();
+    let base_id = format!("{}-{}", slug, now.format("%Y%m%d"));
+    let mut id = base_id.clone();
+    let mut suffix = 2;
+    while clones.iter().any(|clone| clone.id == id) {
+        id = format!("{}-{}", base_id, suffix);
+        suffix += 1;
+    }
+
+    let clone = CloneMetadata {
+        id,
+        name,
+        description,
+        git_url: git.remote_url,
+        git_commit: git.latest_commit_sha,
+        // `rev-parse --abbrev-ref` prints "HEAD" when detached
+        git_branch: Some(git.current_branch).filter(|branch| branch != "HEAD"),
+        git_tag: exact_git_tag(&root),
+        tags: tags.unwrap_or_default(),
+        created_at: now.to_rfc3339(),
+        metadata: None,
+    };
+    clones.push(clone.clone());
+    write_clones(&clones_path, &clones)?;
+
+    Ok(clone)
+}
+
+/// Updates a clone's name, description and/or tags in `.bluekit/clones.json`.
+///
+/// The clone ID never changes, so projects created from it keep resolving.
+/// Tags are trimmed, empty tags dropped and duplicates removed.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory
+/// * `clone_id` - ID of the clone to update
+/// * `name` - New display name, if changing
+/// * `description` - New description, if changing
+/// * `tags` - New tag list, if changing
+///
+/// # Returns
+///
+/// The updated `CloneMetadata` entry
+#[tauri::command]
+pub async fn update_clone_metadata(
+    project_path: String,
+    clone_id: String,
+    name: Option<String>,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<CloneMetadata, String> {
+    let name = name.map(|name| name.trim().to_string());
+    if name.as_deref() == Some("") {
+        return Err("Clone name cannot be empty".to_string());
+    }
+
+    let clones_path = paths::from_ipc(&project_path).join(".bluekit").join("clones.json");
+    let _lock = crate::core::file_lock::lock(&clones_path, "update_clone_metadata").await?;
+    let mut clones = read_clones(&clones_path)?;
+
+    let clone = clones
+        .iter_mut()
+        .find(|clone| clone.id == clone_id)
+        .ok_or_else(|| format!("Clone not found: {}", clone_id))?;
+    if let Some(name) = name {
+        clone.name = name;
+    }
+    if let Some(description) = description {
+        clone.description = description;
+    }
+    if let Some(tags) = tags {
+        let mut cleaned: Vec<String> = Vec::new();
+        for tag in tags.iter().map(|tag| tag.trim()).filter(|tag| !tag.is_empty()) {
+            if !cleaned.iter().any(|existing| existing == tag) {
+                cleaned.push(tag.to_string());
+            }
+        }
+        clone.tags = cleaned;
+    }
+    let updated = clone.clone();
+
+    write_clones(&clones_path, &clones)?;
+    Ok(updated)
+}
+
+/// Removes a clone from `.bluekit/clones.json`.
+///
+/// # Arguments
+///
+/// * `project_path` - The path to the project root directory
+/// * `clone_id` - ID of the clone to remove
+#[tauri::command]
+pub async fn delete_clone(project_path: String, clone_id: String) -> Result<(), String> {
+    let clones_path = paths::from_ipc(&project_path).join(".bluekit").join("clones.json");
+    let _lock = crate::core::file_lock::lock(&clones_path, "delete_clone").await?;
+    let mut clones = read_clones(&clones_path)?;
+
+    let before = clones.len();
+    clones.retain(|clone| clone.id != clone_id);
+    if clones.len() == before {
+        return Err(format!("Clone not found: {}", clone_id));
+    }
+
+    write_clones(&clones_path, &clones)
+}
+
+/// Reads a clones.json file; a missing file has no clones.
+fn read_clones(clones_path: &std::path::Path) -> Result<Vec<CloneMetadata>, String> {
+    use std::fs;
 
-    // Check if clones.json exists
     if !clones_path.exists() {
-        return Ok(Vec::new()); // Return empty vector if file doesn't exist
+        return Ok(Vec::new());
     }
 
-    // Read the file
-    let content = fs::read_to_string(&clones_path)
+    let content = fs::read_to_string(clones_path)
         .map_err(|e| format!("Failed to read clones.json: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse clones.json: {}", e))
+}
+
+/// Writes a clones.json file atomically (temp file + rename).
+fn write_clones(clones_path: &std::path::Path, clones: &[CloneMetadata]) -> Result<(), String> {
+    use std::fs;
+    use std::io::Write;
+
+    if let Some(parent) = clones_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create .bluekit directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(clones)
+        .map_err(|e| format!("Failed to serialize clones: {}", e))?;
+
+    let tmp_path = clones_path.with_extension("json.tmp");
+    let mut file = fs::File::create(&tmp_path)
+        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write temp file: {}", e))?;
+    file.sync_all()
+        .map_err(|e| format!("Failed to sync temp file: {}", e))?;
+    drop(file);
 
-    // Parse JSON
-    let clones: Vec<CloneMetadata> = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse clones.json: {}", e))?;
+    fs::rename(&tmp_path, clones_path)
+        .map_err(|e| format!("Failed to rename temp file: {}", e))
+}
 
-    Ok(clones)
+/// The tag pointing exactly at HEAD, if any.
+fn exact_git_tag(repo: &std::path::Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["describe", "--tags", "--exact-match", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!tag.is_empty()).then_some(tag)
 }
 
 /// Finds a clone by ID across all projects in the registry.
@@ -1582,30 +3264,8 @@ pub async fn get_project_clones(project_path: String) -> Result<Vec<CloneMetadat
 fn find_clone_by_id(clone_id: &str) -> Result<(CloneMetadata, String), String> {
     use std::fs;
 
-    // Get home directory
-    let home_dir = env::var("HOME")
-        .or_else(|_| env::var("USERPROFILE"))
-        .map_err(|e| format!("Could not determine home directory: {:?}", e))?;
-
-    // Construct path to project registry
-    let registry_path = PathBuf::from(&home_dir)
-        .join(".bluekit")
-        .join("projectRegistry.json");
-
-    // Read project registry
-    let projects: Vec<ProjectEntry> = if registry_path.exists() {
-        let content = fs::read_to_string(&registry_path)
-            .map_err(|e| format!("Failed to read project registry: {}", e))?;
-        
-        if content.trim().is_empty() {
-            Vec::new()
-        } else {
-            serde_json::from_str(&content)
-                .map_err(|e| format!("Failed to parse project registry: {}", e))?
-        }
-    } else {
-        Vec::new()
-    };
+    // Read project registry (malformed entries are skipped)
+    let projects = crate::core::registry::read_entries()?;
 
     // Search each project's clones.json
     for project in projects {
@@ -1635,11 +3295,16 @@ fn find_clone_by_id(clone_id: &str) -> Result<(CloneMetadata, String), String> {
 
 /// Copies a directory recursively, excluding specified paths.
 ///
+/// Every copied file is checked against its source's SHA-256; mismatches
+/// are collected and reported together once the copy finishes.
+///
 /// # Arguments
 ///
 /// * `source` - Source directory path
 /// * `destination` - Destination directory path
 /// * `exclude` - Vector of path names to exclude (e.g., [".git"])
+/// * `on_copied` - Called with the running file count after each file;
+///   returning an error stops the copy
 ///
 /// # Returns
 ///
@@ -1648,6 +3313,7 @@ fn copy_directory_excluding(
     source: &PathBuf,
     destination: &PathBuf,
     exclude: &[&str],
+    on_copied: &mut dyn FnMut(u64) -> Result<(), String>,
 ) -> Result<(), String> {
     // Helper function to check if a path should be excluded
     let should_exclude = |path: &PathBuf| -> bool {
@@ -1658,12 +3324,16 @@ fn copy_directory_excluding(
         }
     };
 
-    // Recursive copy function
+    // Recursive copy function; files whose copies don't match their source
+    // are collected in `mismatches`
     fn copy_recursive(
         src: &PathBuf,
         dst: &PathBuf,
         exclude: &[&str],
         should_exclude: &dyn Fn(&PathBuf) -> bool,
+        mismatches: &mut Vec<PathBuf>,
+        copied: &mut u64,
+        on_copied: &mut dyn FnMut(u64) -> Result<(), String>,
     ) -> Result<(), String> {
         use std::fs;
 
@@ -1685,22 +3355,46 @@ fn copy_directory_excluding(
                 let src_path = entry.path();
                 let file_name = src_path
                     .file_name()
-                    .and_then(|n| n.to_str())
                     .ok_or_else(|| "Invalid file name".to_string())?;
                 let dst_path = dst.join(file_name);
 
-                copy_recursive(&src_path, &dst_path, exclude, should_exclude)?;
+                copy_recursive(&src_path, &dst_path, exclude, should_exclude, mismatches, copied, on_copied)?;
             }
         } else if src.is_file() {
-            // Copy file
-            fs::copy(src, dst)
-                .map_err(|e| format!("Failed to copy file {:?} to {:?}: {}", src, dst, e))?;
+            // Copy file and verify it against the source
+            if !crate::core::checksum::copy_verified(src, dst)? {
+                mismatches.push(dst.clone());
+            }
+            *copied += 1;
+            on_copied(*copied)?;
         }
 
         Ok(())
     }
 
-    copy_recursive(source, destination, exclude, &should_exclude)
+    let mut mismatches = Vec::new();
+    let mut copied = 0;
+    copy_recursive(source, destination, exclude, &should_exclude, &mut mismatches, &mut copied, on_copied)?;
+    crate::core::checksum::check_mismatches(&mismatches)
+}
+
+// Number of files `copy_directory_excluding` would copy
+fn count_files_excluding(dir: &std::path::Path, exclude: &[&str]) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| !exclude.iter().any(|ex| entry.file_name().to_str() == Some(*ex)))
+        .map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                count_files_excluding(&path, exclude)
+            } else {
+                u64::from(path.is_file())
+            }
+        })
+        .sum()
 }
 
 /// Creates a new project from a clone.
@@ -1713,34 +3407,82 @@ fn copy_directory_excluding(
 /// 5. Optionally registers the new project in the registry
 /// 6. Cleans up the temporary directory
 ///
+/// Steps 2-6 run in the background: the command returns the job's operation
+/// id as soon as the clone and target path are checked. Progress (clone
+/// percentage, checkout, copied file counts) is reported through
+/// `operation-progress` events, also sent on the job's own
+/// `operation-progress/<id>` channel; the final event carries the success
+/// message or the error.
+///
 /// # Arguments
 ///
 /// * `clone_id` - The unique clone ID
 /// * `target_path` - Absolute path where the new project should be created
 /// * `project_title` - Optional title for the new project (used if registering)
 /// * `register_project` - Whether to automatically register the new project
+/// * `operation_id` - Id for `operation-progress` events and `cancel_operation`
+///   (generated if not given). Cancelling removes the temp and target
+///   directories.
 ///
 /// # Returns
 ///
 /// A `Result<String, String>` containing either:
-/// - `Ok(String)` - Success message with project path
+/// - `Ok(String)` - The job's operation id
 /// - `Err(String)` - Error case with an error message
 #[tauri::command]
 pub async fn create_project_from_clone(
+    app_handle: AppHandle,
     db: State<'_, DatabaseConnection>,
     clone_id: String,
     target_path: String,
     project_title: Option<String>,
     register_project: bool,
+    operation_id: Option<String>,
+) -> Result<String, String> {
+    let (clone, _source_project) = find_clone_by_id(&clone_id)?;
+    if paths::from_ipc(&target_path).exists() {
+        return Err(format!("Target path already exists: {}", target_path));
+    }
+
+    let progress = ProgressReporter::new(app_handle, "clone_creation", operation_id);
+    let job_id = progress.operation_id().to_string();
+    let db = db.inner().clone();
+
+    tauri::async_runtime::spawn(async move {
+        let result = create_project_from_clone_inner(
+            &db,
+            clone,
+            target_path,
+            project_title,
+            register_project,
+            &progress,
+        )
+        .await;
+
+        match result {
+            Ok(message) => progress.complete(message),
+            Err(e) => {
+                progress.fail(e);
+            }
+        }
+    });
+
+    Ok(job_id)
+}
+
+async fn create_project_from_clone_inner(
+    db: &DatabaseConnection,
+    clone: CloneMetadata,
+    target_path: String,
+    project_title: Option<String>,
+    register_project: bool,
+    progress: &ProgressReporter,
 ) -> Result<String, String> {
     use std::fs;
     use std::process::Command;
 
-    // 1. Find clone
-    let (clone, _source_project) = find_clone_by_id(&clone_id)?;
-
-    // 2. Validate target path
-    let target = PathBuf::from(&target_path);
+    // 1. Validate target path (again; time may have passed since the check)
+    let target = paths::from_ipc(&target_path);
     if target.exists() {
         return Err(format!("Target path already exists: {}", target_path));
     }
@@ -1755,7 +3497,8 @@ pub async fn create_project_from_clone(
     };
 
     // 3. Create temp directory
-    let temp_dir = std::env::temp_dir().join(format!("bluekit-clone-{}", std::process::id()));
+    // Named after the job so concurrent clones don't share a directory
+    let temp_dir = std::env::temp_dir().join(format!("bluekit-clone-{}", progress.operation_id()));
 
     // Ensure temp directory doesn't exist
     if temp_dir.exists() {
@@ -1771,16 +3514,20 @@ pub async fn create_project_from_clone(
     };
 
     // 4. Clone repository
-    let clone_output = Command::new("git")
-        .arg("clone")
-        .arg("--quiet")
-        .arg(&clone.git_url)
-        .arg(&temp_dir)
-        .output()
-        .map_err(|e| {
-            cleanup_temp();
-            format!("Failed to clone repository: {}", e)
-        })?;
+    progress.report("cloning", 0, Some(100), format!("Cloning {}", clone.git_url));
+    let clone_output = run_git_clone(
+        Command::new("git")
+            .arg("clone")
+            .arg("--progress")
+            .arg(&clone.git_url)
+            .arg(&temp_dir),
+        progress,
+    )
+    .await
+    .map_err(|e| {
+        cleanup_temp();
+        if e == CANCELLED_ERROR { e } else { format!("Failed to clone repository: {}", e) }
+    })?;
 
     if !clone_output.status.success() {
         cleanup_temp();
@@ -1789,17 +3536,21 @@ pub async fn create_project_from_clone(
     }
 
     // 5. Checkout commit
-    let checkout_output = Command::new("git")
-        .arg("-C")
-        .arg(&temp_dir)
-        .arg("checkout")
-        .arg("--quiet")
-        .arg(&clone.git_commit)
-        .output()
-        .map_err(|e| {
-            cleanup_temp();
-            format!("Failed to checkout commit: {}", e)
-        })?;
+    progress.report("checkout", 0, None, format!("Checking out {}", clone.git_commit));
+    let checkout_output = run_cancellable(
+        Command::new("git")
+            .arg("-C")
+            .arg(&temp_dir)
+            .arg("checkout")
+            .arg("--quiet")
+            .arg(&clone.git_commit),
+        progress,
+    )
+    .await
+    .map_err(|e| {
+        cleanup_temp();
+        if e == CANCELLED_ERROR { e } else { format!("Failed to checkout commit: {}", e) }
+    })?;
 
     if !checkout_output.status.success() {
         cleanup_temp();
@@ -1808,31 +3559,59 @@ pub async fn create_project_from_clone(
     }
 
     // 6. Create target directory
+    if let Err(e) = progress.check_cancelled() {
+        cleanup_temp();
+        return Err(e);
+    }
+    let total_files = count_files_excluding(&temp_dir, &[".git"]);
+    progress.report("copying", 0, Some(total_files), format!("Copying files to {}", target.display()));
     fs::create_dir_all(&target).map_err(|e| {
         cleanup_temp();
         format!("Failed to create target directory: {}", e)
     })?;
 
     // 7. Copy files (excluding .git)
-    copy_directory_excluding(&temp_dir, &target, &[".git"]).map_err(|e| {
+    let mut on_copied = |copied: u64| {
+        progress.check_cancelled()?;
+        if copied.is_multiple_of(CLONE_COPY_REPORT_EVERY) || copied == total_files {
+            progress.report(
+                "copying",
+                copied,
+                Some(total_files),
+                format!("Copied {} of {} files", copied, total_files),
+            );
+        }
+        Ok(())
+    };
+    copy_directory_excluding(&temp_dir, &target, &[".git"], &mut on_copied).map_err(|e| {
+        // The target didn't exist before, so a partial copy is removed
         cleanup_temp();
-        format!("Failed to copy files: {}", e)
+        let _ = fs::remove_dir_all(&target);
+        if e == CANCELLED_ERROR { e } else { format!("Failed to copy files: {}", e) }
     })?;
 
     // 8. Clean up temp directory
     cleanup_temp();
 
+    // Last chance to cancel; nothing is registered yet, so removing the
+    // copied files undoes everything
+    if let Err(e) = progress.check_cancelled() {
+        let _ = fs::remove_dir_all(&target);
+        return Err(e);
+    }
+
     // 9. Register project in database (optional)
     if register_project {
         use sea_orm::*;
         use chrono::Utc;
         use uuid::Uuid;
 
+        progress.report("registering", 0, None, "Registering project");
+
         let title = project_title.unwrap_or_else(|| {
             target
                 .file_name()
-                .and_then(|n| n.to_str())
-                .map(|s| s.to_string())
+                .map(|n| n.to_string_lossy().into_owned())
                 .unwrap_or_else(|| "New Project".to_string())
         });
 
@@ -1855,15 +3634,126 @@ pub async fn create_project_from_clone(
             updated_at: Set(now),
             last_opened_at: Set(None),
             is_vault: Set(false),
+            sort_order: Set(None),
+            archived: Set(false),
         };
 
-        project.insert(&*db).await
+        project.insert(db).await
             .map_err(|e| format!("Failed to register project in database: {}", e))?;
     }
 
     Ok(format!("Project created successfully at: {}", target_path))
 }
 
+// Runs a command to completion, killing it if the operation is cancelled
+async fn run_cancellable(
+    command: &mut std::process::Command,
+    progress: &ProgressReporter,
+) -> Result<std::process::Output, String> {
+    use std::process::Stdio;
+
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    loop {
+        if child.try_wait().map_err(|e| e.to_string())?.is_some() {
+            return child.wait_with_output().map_err(|e| e.to_string());
+        }
+        if progress.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(CANCELLED_ERROR.to_string());
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+}
+
+/// Files copied between `copying` progress events when creating a project
+/// from a clone
+const CLONE_COPY_REPORT_EVERY: u64 = 25;
+
+// Like `run_cancellable` for `git clone --progress`, reporting the clone
+// percentage parsed from git's progress lines as they arrive
+async fn run_git_clone(
+    command: &mut std::process::Command,
+    progress: &ProgressReporter,
+) -> Result<std::process::Output, String> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    let mut child = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let mut stderr = child.stderr.take().ok_or("Failed to capture git output")?;
+
+    // git rewrites progress lines in place with `\r`, so read raw bytes and
+    // split on both line endings
+    let (sender, receiver) = std::sync::mpsc::channel::<String>();
+    let reader = std::thread::spawn(move || {
+        let mut all = Vec::new();
+        let mut line = Vec::new();
+        let mut buffer = [0u8; 4096];
+        while let Ok(read) = stderr.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            for &byte in &buffer[..read] {
+                all.push(byte);
+                if byte == b'\r' || byte == b'\n' {
+                    let _ = sender.send(String::from_utf8_lossy(&line).into_owned());
+                    line.clear();
+                } else {
+                    line.push(byte);
+                }
+            }
+        }
+        all
+    });
+
+    let mut last_percent = None;
+    loop {
+        while let Ok(line) = receiver.try_recv() {
+            if let Some(percent) = clone_progress_percent(&line) {
+                if last_percent != Some(percent) {
+                    last_percent = Some(percent);
+                    progress.report("cloning", percent, Some(100), line.trim().to_string());
+                }
+            }
+        }
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            let stderr = reader.join().unwrap_or_default();
+            return Ok(std::process::Output { status, stdout: Vec::new(), stderr });
+        }
+        if progress.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(CANCELLED_ERROR.to_string());
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+}
+
+/// Overall clone percentage for one of git's progress lines. Receiving
+/// objects counts for the first 80%, resolving deltas for the rest; other
+/// lines (counting, compressing) give None.
+fn clone_progress_percent(line: &str) -> Option<u64> {
+    let (stage_start, stage_share) = if line.starts_with("Receiving objects:") {
+        (0, 80)
+    } else if line.starts_with("Resolving deltas:") {
+        (80, 20)
+    } else {
+        return None;
+    };
+    let after_colon = line.split_once(':')?.1.trim_start();
+    let percent: u64 = after_colon.split('%').next()?.trim().parse().ok()?;
+    Some(stage_start + percent.min(100) * stage_share / 100)
+}
+
 /// Creates a new project directory and copies files to it.
 /// 
 /// This command:
@@ -1877,6 +3767,9 @@ pub async fn create_project_from_clone(
 /// * `target_path` - The absolute path where the new project should be created
 /// * `project_title` - Title for the new project
 /// * `source_files` - Array of source file paths with their types
+/// * `operation_id` - Id for `operation-progress` events and `cancel_operation`
+///   (generated if not given). If the operation is cancelled or fails, the
+///   partially created project directory is removed.
 /// 
 /// # Returns
 /// 
@@ -1885,14 +3778,16 @@ pub async fn create_project_from_clone(
 /// - `Err(String)` - Error case with an error message
 #[tauri::command]
 pub async fn create_new_project(
+    app_handle: AppHandle,
     db: State<'_, DatabaseConnection>,
     target_path: String,
     project_title: String,
     source_files: Vec<(String, String)>, // (file_path, file_type) where file_type is "kit", "walkthrough", or "diagram"
+    operation_id: Option<String>,
 ) -> Result<String, String> {
     use std::fs;
     
-    let target = PathBuf::from(&target_path);
+    let target = paths::from_ipc(&target_path);
     
     // Check if target path already exists
     if target.exists() {
@@ -1911,7 +3806,43 @@ pub async fn create_new_project(
     // Create project directory
     fs::create_dir_all(&target)
         .map_err(|e| format!("Failed to create project directory: {}", e))?;
-    
+
+    let progress = ProgressReporter::new(app_handle, "project_creation", operation_id);
+    let result = populate_new_project(
+        db.inner(),
+        &target,
+        &target_path,
+        project_title,
+        source_files,
+        &progress,
+    )
+    .await;
+
+    match result {
+        Ok(()) => {
+            progress.complete(format!("Project created at {}", target_path));
+            Ok(target_path)
+        }
+        Err(e) => {
+            // The directory didn't exist before, so nothing of the user's is lost
+            let _ = fs::remove_dir_all(&target);
+            Err(progress.fail(e))
+        }
+    }
+}
+
+// Fills a freshly created project directory and registers it; the caller
+// removes the directory if this fails
+async fn populate_new_project(
+    db: &DatabaseConnection,
+    target: &std::path::Path,
+    target_path: &str,
+    project_title: String,
+    source_files: Vec<(String, String)>,
+    progress: &ProgressReporter,
+) -> Result<(), String> {
+    use std::fs;
+
     // Create .bluekit directory structure
     let bluekit_dir = target.join(".bluekit");
     let kits_dir = bluekit_dir.join("kits");
@@ -1929,8 +3860,16 @@ pub async fn create_new_project(
     let file_count = source_files.len();
     
     // Copy files to appropriate directories
-    for (source_file_path, file_type) in source_files {
-        let source_path = PathBuf::from(&source_file_path);
+    for (index, (source_file_path, file_type)) in source_files.into_iter().enumerate() {
+        progress.check_cancelled()?;
+        progress.report(
+            "copying",
+            index as u64,
+            Some(file_count as u64),
+            format!("Copying {}", source_file_path),
+        );
+
+        let source_path = paths::from_ipc(&source_file_path);
         
         if !source_path.exists() {
             eprintln!("Warning: Source file does not exist: {}", source_file_path);
@@ -1939,9 +3878,8 @@ pub async fn create_new_project(
         
         let file_name = source_path
             .file_name()
-            .and_then(|n| n.to_str())
             .ok_or_else(|| format!("Invalid source file name: {}", source_file_path))?
-            .to_string();
+            .to_os_string();
         
         // Determine target directory based on file type
         let target_dir = match file_type.as_str() {
@@ -1964,6 +3902,10 @@ pub async fn create_new_project(
         fs::write(&target_file_path, contents)
             .map_err(|e| format!("Failed to write target file {}: {}", target_file_path.display(), e))?;
     }
+
+    // Last chance to cancel; nothing is registered yet
+    progress.check_cancelled()?;
+    progress.report("registering", file_count as u64, Some(file_count as u64), "Registering project");
     
     // Register project in database
     use sea_orm::*;
@@ -1976,7 +3918,7 @@ pub async fn create_new_project(
     let project = crate::db::entities::project::ActiveModel {
         id: Set(id),
         name: Set(project_title),
-        path: Set(target_path.clone()),
+        path: Set(target_path.to_string()),
         description: Set(Some(format!("Created with {} file{}", file_count, if file_count != 1 { "s" } else { "" }))),
         tags: Set(None),
         git_connected: Set(false),
@@ -1989,12 +3931,14 @@ pub async fn create_new_project(
         updated_at: Set(now),
         last_opened_at: Set(None),
         is_vault: Set(false),
+        sort_order: Set(None),
+        archived: Set(false),
     };
 
-    project.insert(&*db).await
+    project.insert(db).await
         .map_err(|e| format!("Failed to register project in database: {}", e))?;
     
-    Ok(target_path)
+    Ok(())
 }
 
 // How to add a new command:
@@ -2036,6 +3980,454 @@ pub async fn get_watcher_health() -> Result<HashMap<String, bool>, String> {
     Ok(crate::core::watcher::get_watcher_health().await)
 }
 
+/// Returns an aggregate health report for the diagnostics screen.
+///
+/// Covers database connectivity and size, watcher states, disk space in
+/// `~/.bluekit` and the scheduled job queue. GitHub and Supabase auth are
+/// only checked when their credentials are passed (tokens live in the
+/// frontend); otherwise they are reported as "skipped".
+///
+/// # Example
+///
+/// ```typescript
+/// const report = await invoke<AppHealthReport>('get_app_health', {
+///   githubToken: token,
+///   supabase: { url, anonKey, accessToken: session.access_token }
+/// });
+/// ```
+#[tauri::command]
+pub async fn get_app_health(
+    db: State<'_, DatabaseConnection>,
+    github_token: Option<String>,
+    supabase: Option<crate::core::health::SupabaseCredentials>,
+) -> Result<crate::core::health::AppHealthReport, String> {
+    Ok(crate::core::health::get_app_health(db.inner(), github_token, supabase).await)
+}
+
+/// Lints a project's `.bluekit` Markdown for the project health tab.
+///
+/// Reports broken relative links, missing images and diagrams, dangling
+/// `[[wikilinks]]`, unresolvable link stubs and front matter problems,
+/// grouped per file. Only files with findings are listed.
+///
+/// # Example
+///
+/// ```typescript
+/// const report = await invoke<LintReport>('lint_artifacts', { projectPath });
+/// ```
+#[tauri::command]
+pub async fn lint_artifacts(project_path: String) -> Result<crate::core::lint::LintReport, String> {
+    crate::core::lint::lint_artifacts(&paths::from_ipc(&project_path))
+}
+
+/// Checks a mermaid diagram for syntax errors.
+///
+/// Pass the editor's `content` to check unsaved changes, or just
+/// `file_path` to check the file on disk. Errors carry 1-based line and
+/// column numbers for inline diagnostics.
+///
+/// # Example
+///
+/// ```typescript
+/// const result = await invoke<DiagramValidation>('validate_diagram', { content: source });
+/// ```
+#[tauri::command]
+pub async fn validate_diagram(
+    file_path: Option<String>,
+    content: Option<String>,
+) -> Result<crate::core::mermaid::DiagramValidation, String> {
+    let source = match (content, file_path) {
+        (Some(content), _) => content,
+        (None, Some(file_path)) => std::fs::read_to_string(paths::from_ipc(&file_path))
+            .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?,
+        (None, None) => return Err("Either filePath or content is required".to_string()),
+    };
+    Ok(crate::core::mermaid::validate(&source))
+}
+
+/// Validates an artifact's front matter against the schema for its type.
+///
+/// Checks required fields, field types, tag format and that `type` matches
+/// the folder the file lives in. Pass the editor's `content` to check
+/// unsaved changes; `file_path` (if given) decides the expected type and is
+/// read when there is no `content`.
+///
+/// # Example
+///
+/// ```typescript
+/// const result = await invoke<MetadataValidation>('validate_artifact_metadata', { filePath, content });
+/// ```
+#[tauri::command]
+pub async fn validate_artifact_metadata(
+    file_path: Option<String>,
+    content: Option<String>,
+) -> Result<crate::core::metadata_schema::MetadataValidation, String> {
+    use crate::core::metadata_schema::{type_from_path, validate_metadata};
+
+    let content = match (content, &file_path) {
+        (Some(content), _) => content,
+        (None, Some(file_path)) => std::fs::read_to_string(paths::from_ipc(file_path))
+            .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?,
+        (None, None) => return Err("Either filePath or content is required".to_string()),
+    };
+    let expected_type = file_path.as_deref().and_then(type_from_path);
+    Ok(validate_metadata(&content, expected_type))
+}
+
+/// Writes a progress report for a project to `.bluekit/reports/`.
+///
+/// Lists milestones completed, tasks finished, artifacts added and
+/// checkpoints pinned in `range` (Unix seconds; the last seven days if
+/// omitted). Returns the Markdown along with where it was saved.
+///
+/// # Example
+///
+/// ```typescript
+/// const report = await invoke<ProgressReport>('generate_progress_report', {
+///   projectId,
+///   range: { since: weekStart, until: now }
+/// });
+/// ```
+#[tauri::command]
+pub async fn generate_progress_report(
+    db: State<'_, DatabaseConnection>,
+    project_id: String,
+    range: Option<crate::core::progress_report::ReportRange>,
+) -> Result<crate::core::progress_report::ProgressReport, String> {
+    crate::core::progress_report::generate_progress_report(db.inner(), &project_id, range.unwrap_or_default()).await
+}
+
+/// Result of `export_project_artifacts`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArtifactExportResult {
+    #[serde(rename = "archivePath")]
+    pub archive_path: String,
+    #[serde(rename = "fileCount")]
+    pub file_count: usize,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+}
+
+/// Packages a project's `.bluekit` directory into a zip archive.
+///
+/// The archive holds the selected files with paths relative to `.bluekit`
+/// and a `bluekit-manifest.json` listing each with its checksum, so it can
+/// be shared with people who don't use the library and imported elsewhere.
+/// The trash is never included.
+///
+/// # Arguments
+///
+/// * `project_path` - Path to the project root directory
+/// * `destination_path` - Zip file to write, or a directory to write
+///   `<project>-bluekit-<date>.zip` into
+/// * `include` - Files or folders relative to `.bluekit` to export (everything if omitted)
+/// * `operation_id` - Id for `operation-progress` events and `cancel_operation`
+///   (generated if not given). Cancelling removes the partial archive.
+///
+/// # Example
+///
+/// ```typescript
+/// const result = await invoke<ArtifactExportResult>('export_project_artifacts', {
+///   projectPath,
+///   destinationPath: '/Users/me/Desktop',
+///   include: ['kits', 'diagrams/architecture.mmd']
+/// });
+/// ```
+#[tauri::command]
+pub async fn export_project_artifacts(
+    app_handle: AppHandle,
+    project_path: String,
+    destination_path: String,
+    include: Option<Vec<String>>,
+    operation_id: Option<String>,
+) -> Result<ArtifactExportResult, String> {
+    let project_root = paths::from_ipc(&project_path);
+    let bluekit_dir = project_root.join(".bluekit");
+    if !bluekit_dir.is_dir() {
+        return Err(format!("No .bluekit directory in {}", project_path));
+    }
+
+    let mut archive_path = paths::from_ipc(&destination_path);
+    if archive_path.is_dir() {
+        archive_path = archive_path.join(format!(
+            "{}-bluekit-{}.zip",
+            paths::file_name_lossy(&project_root),
+            chrono::Local::now().format("%Y%m%d")
+        ));
+    }
+    if archive_path.starts_with(&bluekit_dir) {
+        return Err("The archive can't be written inside the .bluekit directory".to_string());
+    }
+
+    let selection: Vec<PathBuf> = include
+        .unwrap_or_default()
+        .iter()
+        .map(|entry| entry.trim_matches(['/', '\\']))
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| entry.split(['/', '\\']).collect())
+        .collect();
+    for selected in &selection {
+        if !bluekit_dir.join(selected).exists() {
+            return Err(format!("Nothing to export at {}", selected.display()));
+        }
+    }
+
+    let progress = ProgressReporter::new(app_handle, "archive_export", operation_id);
+    let manifest = crate::core::archive::create_archive(
+        &bluekit_dir,
+        &archive_path,
+        |relative| {
+            if relative.starts_with(crate::core::trash::TRASH_DIR) {
+                return false;
+            }
+            // Selected paths, everything beneath them and the folders leading to them
+            selection.is_empty()
+                || selection
+                    .iter()
+                    .any(|selected| relative.starts_with(selected) || selected.starts_with(relative))
+        },
+        &progress,
+    )
+    .map_err(|e| progress.fail(e))?;
+
+    let result = ArtifactExportResult {
+        archive_path: paths::to_ipc(&archive_path),
+        file_count: manifest.entries.len(),
+        total_bytes: manifest.entries.iter().map(|entry| entry.size).sum(),
+    };
+    progress.complete(format!("Exported {} file(s) to {}", result.file_count, result.archive_path));
+    Ok(result)
+}
+
+/// Result of `import_artifacts_from_archive`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArtifactImportResult {
+    /// Files written, including replaced ones
+    #[serde(rename = "importedFiles")]
+    pub imported_files: Vec<String>,
+    /// Files that existed and were replaced (only with `overwrite`)
+    #[serde(rename = "overwrittenFiles")]
+    pub overwritten_files: Vec<String>,
+}
+
+/// Imports artifacts from a zip archive into a project's `.bluekit`
+/// directory.
+///
+/// The archive's manifest is validated and every file is checked against
+/// its checksum while being unpacked into a staging directory, so a corrupt
+/// archive leaves the project untouched; a cancelled or failed copy removes
+/// whatever it had written. Paths are kept relative to `.bluekit` (as
+/// written by `export_project_artifacts`); loose files at the top of the
+/// archive are sorted into the subdirectory their type belongs in (front
+/// matter `type`, diagrams by extension, images into `assets`).
+///
+/// Existing files are only replaced with `overwrite`; otherwise any
+/// collision fails the whole import before anything is copied. With
+/// `dry_run`, the files that would be created or overwritten are returned
+/// instead.
+///
+/// # Arguments
+///
+/// * `project_path` - Path to the project root directory
+/// * `archive_path` - Zip archive to import
+/// * `overwrite` - Replace existing files (default false)
+/// * `dry_run` - Report what would happen without changing anything
+/// * `operation_id` - Id for `operation-progress` events and `cancel_operation`
+#[tauri::command]
+pub async fn import_artifacts_from_archive(
+    app_handle: AppHandle,
+    cache: State<'_, ArtifactCache>,
+    project_path: String,
+    archive_path: String,
+    overwrite: Option<bool>,
+    dry_run: Option<bool>,
+    operation_id: Option<String>,
+) -> Result<DryRunOutcome<ArtifactImportResult>, String> {
+    use tauri::Manager;
+
+    let project_root = paths::from_ipc(&project_path);
+    if !project_root.is_dir() {
+        return Err(format!("Project directory does not exist: {}", project_path));
+    }
+    let bluekit_dir = project_root.join(".bluekit");
+
+    let progress = ProgressReporter::new(app_handle.clone(), "archive_import", operation_id);
+    let staging = std::env::temp_dir().join(format!("bluekit-import-{}", uuid::Uuid::new_v4()));
+    let result = import_staged_artifacts(
+        &paths::from_ipc(&archive_path),
+        &staging,
+        &bluekit_dir,
+        overwrite.unwrap_or(false),
+        dry_run.unwrap_or(false),
+        &progress,
+    );
+    let _ = std::fs::remove_dir_all(&staging);
+
+    match result {
+        Ok(DryRunOutcome::Applied(imported)) => {
+            for file in &imported.imported_files {
+                cache.invalidate(&paths::from_ipc(file)).await;
+            }
+            let _ = app_handle.emit_all(&project_artifacts_event_name(&project_path), imported.imported_files.clone());
+            progress.complete(format!("Imported {} file(s)", imported.imported_files.len()));
+            Ok(DryRunOutcome::Applied(imported))
+        }
+        Ok(report) => {
+            progress.complete("Dry run finished");
+            Ok(report)
+        }
+        Err(e) => Err(progress.fail(e)),
+    }
+}
+
+fn import_staged_artifacts(
+    archive_path: &std::path::Path,
+    staging: &std::path::Path,
+    bluekit_dir: &std::path::Path,
+    overwrite: bool,
+    dry_run: bool,
+    progress: &ProgressReporter,
+) -> Result<DryRunOutcome<ArtifactImportResult>, String> {
+    use std::collections::HashSet;
+
+    let manifest = crate::core::archive::extract_archive(archive_path, staging, false, progress)?;
+
+    // Where each file goes, relative to .bluekit
+    let mut plan = Vec::new();
+    let mut targets = HashSet::new();
+    for entry in &manifest.entries {
+        let staged = crate::core::archive::safe_join(staging, &entry.path)?;
+        let relative = import_destination(&entry.path, &staged);
+        if relative.starts_with(&format!("{}/", crate::core::trash::TRASH_DIR)) {
+            continue;
+        }
+        if !targets.insert(relative.clone()) {
+            return Err(format!("Archive contains more than one file for {}", relative));
+        }
+        let target = crate::core::archive::safe_join(bluekit_dir, &relative)?;
+        plan.push((staged, target));
+    }
+
+    let collisions: Vec<String> = plan
+        .iter()
+        .filter(|(_, target)| target.exists())
+        .map(|(_, target)| paths::to_ipc(target))
+        .collect();
+
+    if dry_run {
+        let mut report = DryRunReport::default();
+        for (_, target) in &plan {
+            let target_str = paths::to_ipc(target);
+            if target.exists() {
+                report.overwritten_files.push(target_str);
+            } else {
+                report.created_files.push(target_str);
+            }
+        }
+        return Ok(DryRunOutcome::DryRun(report));
+    }
+
+    if !collisions.is_empty() && !overwrite {
+        return Err(format!(
+            "{} file(s) already exist: {}",
+            collisions.len(),
+            collisions.join(", ")
+        ));
+    }
+
+    // Everything is copied next to its target first and only renamed into
+    // place once every copy is verified, so a cancelled or failed import
+    // leaves no partial files behind
+    let total = plan.len() as u64;
+    let mut copies = Vec::new();
+    let copied = (|| {
+        let mut mismatches = Vec::new();
+        for (index, (staged, target)) in plan.iter().enumerate() {
+            progress.check_cancelled()?;
+            progress.report("copying", index as u64, Some(total), paths::file_name_lossy(target));
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let copy = target.with_file_name(format!("{}.import.tmp", paths::file_name_lossy(target)));
+            copies.push(copy.clone());
+            if !crate::core::checksum::copy_verified(staged, &copy)? {
+                mismatches.push(target.clone());
+            }
+        }
+        crate::core::checksum::check_mismatches(&mismatches)
+    })();
+    if let Err(e) = copied {
+        for copy in &copies {
+            let _ = std::fs::remove_file(copy);
+        }
+        return Err(e);
+    }
+
+    let mut imported_files = Vec::new();
+    for (index, (copy, (_, target))) in copies.iter().zip(&plan).enumerate() {
+        if let Err(e) = std::fs::rename(copy, target) {
+            for copy in &copies[index..] {
+                let _ = std::fs::remove_file(copy);
+            }
+            return Err(format!("Failed to move {} into place: {}", target.display(), e));
+        }
+        imported_files.push(paths::to_ipc(target));
+    }
+
+    Ok(DryRunOutcome::Applied(ArtifactImportResult {
+        imported_files,
+        overwritten_files: collisions,
+    }))
+}
+
+/// Destination of an archive entry relative to `.bluekit`. Entries in a
+/// folder keep their path; loose files go where their type belongs.
+fn import_destination(entry_path: &str, staged: &std::path::Path) -> String {
+    if entry_path.contains('/') {
+        return entry_path.to_string();
+    }
+
+    let extension = staged
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    let subdirectory = match extension.as_str() {
+        "mmd" | "mermaid" => Some("diagrams"),
+        ext if IMAGE_ASSET_EXTENSIONS.contains(&ext) => Some("assets"),
+        "md" => std::fs::read_to_string(staged)
+            .ok()
+            .and_then(|content| parse_front_matter(&content))
+            .and_then(|front_matter| front_matter.get("type").and_then(|t| t.as_str()).map(str::to_string))
+            .and_then(|artifact_type| match artifact_type.as_str() {
+                "kit" => Some("kits"),
+                "walkthrough" => Some("walkthroughs"),
+                "agent" => Some("agents"),
+                "diagram" => Some("diagrams"),
+                _ => None,
+            }),
+        _ => None,
+    };
+
+    match subdirectory {
+        Some(subdirectory) => format!("{}/{}", subdirectory, entry_path),
+        // Anything else stays loose in .bluekit (the scrapbook)
+        None => entry_path.to_string(),
+    }
+}
+
+/// Seeds an example project on first launch
+///
+/// Does nothing (returns `seeded: false`) unless this is a fresh install
+/// with no projects, so the frontend can call it on every start.
+#[tauri::command]
+pub async fn bootstrap_starter_content(
+    db: State<'_, DatabaseConnection>,
+) -> Result<crate::core::starter_content::BootstrapResult, String> {
+    crate::core::starter_content::bootstrap_starter_content(db.inner()).await
+}
+
 /// Stops a file watcher by event name.
 ///
 /// This command gracefully stops a running file watcher task by sending a
@@ -2068,10 +4460,94 @@ pub async fn stop_watcher(event_name: String) -> Result<(), String> {
 pub async fn db_get_tasks(
     db: State<'_, sea_orm::DatabaseConnection>,
     project_ids: Option<Vec<String>>,
+    include_archived: Option<bool>,
 ) -> Result<Vec<crate::db::task_operations::TaskDto>, String> {
-    crate::db::task_operations::get_tasks(db.inner(), project_ids)
+    let mut tasks = crate::db::task_operations::get_tasks(db.inner(), project_ids)
         .await
-        .map_err(|e| format!("Failed to get tasks: {}", e))
+        .map_err(|e| format!("Failed to get tasks: {}", e))?;
+
+    // Drop tasks that only belong to archived projects
+    if !include_archived.unwrap_or(false) {
+        let archived = crate::db::project_operations::archived_project_ids(db.inner())
+            .await
+            .map_err(|e| format!("Failed to get tasks: {}", e))?;
+        if !archived.is_empty() {
+            tasks.retain(|task| {
+                task.project_ids.is_empty() || !task.project_ids.iter().all(|id| archived.contains(id))
+            });
+        }
+    }
+    Ok(tasks)
+}
+
+/// Full-text search over task titles, descriptions and tags, best matches
+/// first, with matches highlighted in `<mark>` tags.
+///
+/// Every word must match as a prefix. Like `db_get_tasks`, tasks that only
+/// belong to archived projects are left out unless `include_archived` is set.
+#[tauri::command]
+pub async fn db_search_tasks(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    query: String,
+    project_ids: Option<Vec<String>>,
+    limit: Option<u64>,
+    include_archived: Option<bool>,
+) -> Result<Vec<crate::db::task_operations::TaskSearchHit>, String> {
+    let mut hits = crate::db::task_operations::search_tasks(
+        db.inner(),
+        &query,
+        project_ids,
+        limit.unwrap_or(crate::db::task_operations::DEFAULT_TASK_SEARCH_LIMIT),
+    )
+    .await
+    .map_err(|e| format!("Failed to search tasks: {}", e))?;
+
+    if !include_archived.unwrap_or(false) {
+        let archived = crate::db::project_operations::archived_project_ids(db.inner())
+            .await
+            .map_err(|e| format!("Failed to search tasks: {}", e))?;
+        if !archived.is_empty() {
+            hits.retain(|hit| {
+                hit.task.project_ids.is_empty() || !hit.task.project_ids.iter().all(|id| archived.contains(id))
+            });
+        }
+    }
+    Ok(hits)
+}
+
+/// Searches tasks, plans, milestones, walkthroughs and artifact front matter
+/// in one call, best hits first, for the command palette.
+///
+/// Every word must start a word of a hit's title or other text; hits of all
+/// kinds are scored alike so they can be shown in one list. `kinds` limits
+/// the search to some of "task", "plan", "milestone", "walkthrough" and
+/// "artifact"; `project_ids` limits it to those projects (otherwise
+/// archived projects are skipped).
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const hits = await invoke('global_search', { query: 'auth', kinds: ['task', 'plan'] });
+/// ```
+#[tauri::command]
+pub async fn global_search(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    query: String,
+    kinds: Option<Vec<String>>,
+    project_ids: Option<Vec<String>>,
+    limit: Option<usize>,
+    index: State<'_, crate::core::search_index::SearchIndex>,
+) -> Result<Vec<crate::core::global_search::GlobalSearchHit>, String> {
+    crate::core::global_search::global_search(
+        db.inner(),
+        index.inner(),
+        &query,
+        kinds,
+        project_ids,
+        limit.unwrap_or(crate::core::global_search::DEFAULT_LIMIT),
+    )
+    .await
+    .map_err(|e| format!("Failed to search: {}", e))
 }
 
 /// Get tasks for a specific project
@@ -2108,6 +4584,8 @@ pub async fn db_create_task(
     status: Option<String>,
     complexity: Option<String>,
     type_: Option<String>,
+    due_at: Option<String>,
+    remind_at: Option<String>,
 ) -> Result<crate::db::task_operations::TaskDto, String> {
     eprintln!("[db_create_task] Received type_: {:?}", type_);
     
@@ -2121,12 +4599,17 @@ pub async fn db_create_task(
         status,
         complexity,
         type_,
+        due_at,
+        remind_at,
     )
     .await
     .map_err(|e| format!("Failed to create task: {}", e))
 }
 
 /// Update an existing task
+///
+/// A `null` argument arrives the same as an omitted one, so `clear_due_at`
+/// and `clear_remind_at` remove the due date and reminder.
 #[tauri::command]
 pub async fn db_update_task(
     db: State<'_, sea_orm::DatabaseConnection>,
@@ -2139,8 +4622,14 @@ pub async fn db_update_task(
     status: Option<String>,
     complexity: Option<Option<String>>,
     type_: Option<Option<String>>,
+    due_at: Option<Option<String>>,
+    remind_at: Option<Option<String>>,
+    clear_due_at: Option<bool>,
+    clear_remind_at: Option<bool>,
 ) -> Result<crate::db::task_operations::TaskDto, String> {
     eprintln!("[db_update_task] Received type_: {:?}", type_);
+    let due_at = if clear_due_at.unwrap_or(false) { Some(None) } else { due_at };
+    let remind_at = if clear_remind_at.unwrap_or(false) { Some(None) } else { remind_at };
     
     crate::db::task_operations::update_task(
         db.inner(),
@@ -2153,30 +4642,198 @@ pub async fn db_update_task(
         status,
         complexity,
         type_,
+        due_at,
+        remind_at,
     )
     .await
     .map_err(|e| format!("Failed to update task: {}", e))
 }
 
-/// Delete a task
+/// Applies one patch (status, priority, tags, projects) to several tasks at
+/// once, for multi-select actions on the board. All-or-nothing.
 #[tauri::command]
-pub async fn db_delete_task(
+pub async fn db_bulk_update_tasks(
     db: State<'_, sea_orm::DatabaseConnection>,
-    task_id: String,
-) -> Result<(), String> {
-    crate::db::task_operations::delete_task(db.inner(), &task_id)
+    task_ids: Vec<String>,
+    patch: crate::db::task_operations::TaskPatch,
+) -> Result<Vec<crate::db::task_operations::TaskDto>, String> {
+    crate::db::task_operations::bulk_update_tasks(db.inner(), &task_ids, &patch)
         .await
-        .map_err(|e| format!("Failed to delete task: {}", e))
+        .map_err(|e| format!("Failed to update tasks: {}", e))
 }
 
-/// Delete resource files from the filesystem.
-///
-/// This command deletes one or more resource files (kits, walkthroughs, agents, diagrams).
+/// Persists drag-and-drop order for one status column of a project's task
+/// board. `task_ids` is the column's full new order; tasks dragged in from
+/// another column take on `status`.
+#[tauri::command]
+pub async fn db_reorder_tasks(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    project_id: String,
+    status: String,
+    task_ids: Vec<String>,
+) -> Result<(), String> {
+    crate::db::task_operations::reorder_tasks(db.inner(), &project_id, &status, &task_ids)
+        .await
+        .map_err(|e| format!("Failed to reorder tasks: {}", e))
+}
+
+/// A task's change history (creation, then each changed field), newest
+/// first
+#[tauri::command]
+pub async fn db_get_task_activity(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    task_id: String,
+) -> Result<Vec<crate::db::task_operations::TaskActivityDto>, String> {
+    crate::db::task_operations::get_task_activity(db.inner(), &task_id)
+        .await
+        .map_err(|e| format!("Failed to get task activity: {}", e))
+}
+
+/// Tasks past their due date that aren't completed, most overdue first
+#[tauri::command]
+pub async fn db_get_overdue_tasks(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    project_ids: Option<Vec<String>>,
+) -> Result<Vec<crate::db::task_operations::TaskDto>, String> {
+    crate::db::task_operations::get_overdue_tasks(db.inner(), chrono::Utc::now(), project_ids)
+        .await
+        .map_err(|e| format!("Failed to get overdue tasks: {}", e))
+}
+
+/// Tasks due in the next `days` days (default 7) that aren't completed,
+/// soonest first
+#[tauri::command]
+pub async fn db_get_upcoming_tasks(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    days: Option<u32>,
+    project_ids: Option<Vec<String>>,
+) -> Result<Vec<crate::db::task_operations::TaskDto>, String> {
+    let now = chrono::Utc::now();
+    let until = now + chrono::Duration::days(i64::from(days.unwrap_or(7)));
+    crate::db::task_operations::get_upcoming_tasks(db.inner(), now, until, project_ids)
+        .await
+        .map_err(|e| format!("Failed to get upcoming tasks: {}", e))
+}
+
+/// Delete a task. Deleted tasks can be restored with `db_restore_task`
+/// until the trash retention period passes, unless `permanent` is set.
+#[tauri::command]
+pub async fn db_delete_task(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    task_id: String,
+    permanent: Option<bool>,
+) -> Result<(), String> {
+    if permanent.unwrap_or(false) {
+        crate::db::task_operations::purge_task(db.inner(), &task_id).await
+    } else {
+        crate::db::task_operations::delete_task(db.inner(), &task_id).await
+    }
+    .map_err(|e| format!("Failed to delete task: {}", e))
+}
+
+/// Restore a deleted task
+#[tauri::command]
+pub async fn db_restore_task(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    task_id: String,
+) -> Result<crate::db::task_operations::TaskDto, String> {
+    crate::db::task_operations::restore_task(db.inner(), &task_id)
+        .await
+        .map_err(|e| format!("Failed to restore task: {}", e))
+}
+
+/// Deleted tasks that can still be restored, most recently deleted first
+#[tauri::command]
+pub async fn db_get_deleted_tasks(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    project_ids: Option<Vec<String>>,
+) -> Result<Vec<crate::db::task_operations::TaskDto>, String> {
+    crate::db::task_operations::get_deleted_tasks(db.inner(), project_ids)
+        .await
+        .map_err(|e| format!("Failed to get deleted tasks: {}", e))
+}
+
+/// Deletes several tasks in one transaction, like `db_delete_task`. Returns
+/// how many were deleted.
+#[tauri::command]
+pub async fn db_bulk_delete_tasks(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    task_ids: Vec<String>,
+    permanent: Option<bool>,
+) -> Result<usize, String> {
+    crate::db::task_operations::bulk_delete_tasks(db.inner(), &task_ids, permanent.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to delete tasks: {}", e))
+}
+
+/// Lists saved task views (named filters) by name
+#[tauri::command]
+pub async fn db_list_saved_views(
+    db: State<'_, sea_orm::DatabaseConnection>,
+) -> Result<Vec<crate::db::saved_view_operations::SavedViewDto>, String> {
+    crate::db::saved_view_operations::list_saved_views(db.inner())
+        .await
+        .map_err(|e| format!("Failed to list saved views: {}", e))
+}
+
+/// Saves a named task filter (statuses, priorities, tags, projects, sort)
+#[tauri::command]
+pub async fn db_create_saved_view(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    name: String,
+    filter: crate::db::saved_view_operations::TaskFilter,
+) -> Result<crate::db::saved_view_operations::SavedViewDto, String> {
+    crate::db::saved_view_operations::create_saved_view(db.inner(), name, filter)
+        .await
+        .map_err(|e| format!("Failed to create saved view: {}", e))
+}
+
+/// Renames a saved view and/or replaces its filter
+#[tauri::command]
+pub async fn db_update_saved_view(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    view_id: String,
+    name: Option<String>,
+    filter: Option<crate::db::saved_view_operations::TaskFilter>,
+) -> Result<crate::db::saved_view_operations::SavedViewDto, String> {
+    crate::db::saved_view_operations::update_saved_view(db.inner(), &view_id, name, filter)
+        .await
+        .map_err(|e| format!("Failed to update saved view: {}", e))
+}
+
+/// Deletes a saved view
+#[tauri::command]
+pub async fn db_delete_saved_view(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    view_id: String,
+) -> Result<(), String> {
+    crate::db::saved_view_operations::delete_saved_view(db.inner(), &view_id)
+        .await
+        .map_err(|e| format!("Failed to delete saved view: {}", e))
+}
+
+/// Runs a saved view's filter and sort, returning the matching tasks
+#[tauri::command]
+pub async fn db_get_tasks_for_view(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    view_id: String,
+) -> Result<Vec<crate::db::task_operations::TaskDto>, String> {
+    crate::db::saved_view_operations::get_tasks_for_view(db.inner(), &view_id)
+        .await
+        .map_err(|e| format!("Failed to get tasks for view: {}", e))
+}
+
+/// Delete resource files from the filesystem.
+///
+/// This command deletes one or more resource files (kits, walkthroughs, agents, diagrams).
 /// It validates that all paths are within `.bluekit` directories for safety.
+/// Files are moved to the project's `.bluekit/.trash` so they can be restored
+/// with `restore_resource`, unless `permanent` is set.
 ///
 /// # Arguments
 ///
 /// * `file_paths` - Vector of absolute file paths to delete
+/// * `permanent` - Delete outright instead of moving to the trash (default false)
 ///
 /// # Returns
 ///
@@ -2188,15 +4845,24 @@ pub async fn db_delete_task(
 ///
 /// This function validates that all file paths are within `.bluekit` directories
 /// to prevent accidental deletion of files outside the project structure.
+///
+/// With `dry_run`, nothing is deleted; the files that would be removed are
+/// returned as a `DryRunReport` instead.
 #[tauri::command]
-pub async fn delete_resources(file_paths: Vec<String>) -> Result<(), String> {
+pub async fn delete_resources(
+    file_paths: Vec<String>,
+    dry_run: Option<bool>,
+    permanent: Option<bool>,
+) -> Result<DryRunOutcome<()>, String> {
     use std::fs;
-    use std::path::Path;
 
+    let dry_run = dry_run.unwrap_or(false);
+    let permanent = permanent.unwrap_or(false);
+    let mut report = DryRunReport::default();
     let mut errors = Vec::new();
 
     for file_path in file_paths {
-        let path = Path::new(&file_path);
+        let path = paths::from_ipc(&file_path);
 
         // Validate path is within a .bluekit directory for safety
         if !path.to_string_lossy().contains(".bluekit") {
@@ -2213,6 +4879,18 @@ pub async fn delete_resources(file_paths: Vec<String>) -> Result<(), String> {
             continue;
         }
 
+        if dry_run {
+            report.deleted_files.push(file_path);
+            continue;
+        }
+
+        if !permanent {
+            if let Err(e) = crate::core::trash::move_to_trash(&path).await {
+                errors.push(format!("Failed to move {} to trash: {}", file_path, e));
+            }
+            continue;
+        }
+
         // Attempt to delete the file
         match fs::remove_file(path) {
             Ok(_) => {
@@ -2228,11 +4906,142 @@ pub async fn delete_resources(file_paths: Vec<String>) -> Result<(), String> {
         }
     }
 
-    if errors.is_empty() {
-        Ok(())
-    } else {
+    if !errors.is_empty() {
         Err(format!("Some deletions failed: {}", errors.join("; ")))
+    } else if dry_run {
+        Ok(DryRunOutcome::DryRun(report))
+    } else {
+        Ok(DryRunOutcome::Applied(()))
+    }
+}
+
+/// Lists a project's trashed artifacts, most recently deleted first.
+///
+/// # Arguments
+///
+/// * `project_path` - Path to the project root directory
+#[tauri::command]
+pub async fn list_trash(project_path: String) -> Result<Vec<crate::core::trash::TrashEntry>, String> {
+    crate::core::trash::list_trash(&paths::from_ipc(&project_path))
+}
+
+/// Restores a trashed artifact to its original location.
+///
+/// # Arguments
+///
+/// * `project_path` - Path to the project root directory
+/// * `trash_id` - Id of the trash entry (from `list_trash`)
+///
+/// # Returns
+///
+/// The restored file path
+#[tauri::command]
+pub async fn restore_resource(
+    app_handle: AppHandle,
+    project_path: String,
+    trash_id: String,
+) -> Result<String, String> {
+    use tauri::Manager;
+
+    let restored = crate::core::trash::restore(&paths::from_ipc(&project_path), &trash_id).await?;
+    let restored = paths::to_ipc(&restored);
+    let _ = app_handle.emit_all(&project_artifacts_event_name(&project_path), vec![restored.clone()]);
+    Ok(restored)
+}
+
+/// Permanently deletes trashed artifacts.
+///
+/// # Arguments
+///
+/// * `project_path` - Path to the project root directory
+/// * `older_than_days` - Only delete entries trashed more than this many days ago (all when omitted)
+///
+/// # Returns
+///
+/// The number of entries deleted
+#[tauri::command]
+pub async fn empty_trash(project_path: String, older_than_days: Option<u32>) -> Result<usize, String> {
+    crate::core::trash::empty_trash(&paths::from_ipc(&project_path), older_than_days).await
+}
+
+/// Attaches a file to an artifact.
+///
+/// The file is copied into `.bluekit/assets/<artifact name>/` (renamed with
+/// a numeric suffix if that name is taken), and markdown links in the
+/// artifact that pointed at the original file are rewritten to the copy's
+/// relative path. The returned `markdown` link can be inserted at the
+/// cursor for a new attachment.
+///
+/// # Arguments
+///
+/// * `artifact_path` - The artifact to attach to
+/// * `source_path` - The file to attach
+#[tauri::command]
+pub async fn attach_asset(
+    artifact_path: String,
+    source_path: String,
+) -> Result<crate::core::assets::AttachedAsset, String> {
+    crate::core::assets::attach_asset(&paths::from_ipc(&artifact_path), &paths::from_ipc(&source_path)).await
+}
+
+/// Lists the files attached to an artifact, with whether the artifact
+/// still links to each one.
+#[tauri::command]
+pub async fn list_artifact_assets(
+    artifact_path: String,
+) -> Result<Vec<crate::core::assets::ArtifactAsset>, String> {
+    crate::core::assets::list_assets(&paths::from_ipc(&artifact_path))
+}
+
+/// Deletes an attached asset, moving it to the project's trash unless
+/// `permanent` is set. The artifact's asset folder is removed once empty.
+#[tauri::command]
+pub async fn delete_asset(asset_path: String, permanent: Option<bool>) -> Result<(), String> {
+    let path = paths::from_ipc(&asset_path);
+    if !crate::core::assets::is_attachment(&path) || !path.is_file() {
+        return Err(format!("Not an attached asset: {}", asset_path));
+    }
+
+    if permanent.unwrap_or(false) {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete asset {}: {}", asset_path, e))?;
+    } else {
+        crate::core::trash::move_to_trash(&path).await?;
+    }
+    if let Some(asset_dir) = path.parent() {
+        // Only succeeds if nothing else is attached
+        let _ = std::fs::remove_dir(asset_dir);
     }
+    Ok(())
+}
+
+/// Moves attachments nothing in `.bluekit` links to any more into the project's
+/// trash, returning their paths.
+///
+/// With `dry_run`, returns the files that would be trashed instead.
+#[tauri::command]
+pub async fn clean_orphaned_assets(
+    project_path: String,
+    dry_run: Option<bool>,
+) -> Result<DryRunOutcome<Vec<String>>, String> {
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
+    let orphans = crate::core::assets::orphaned_assets(&bluekit_path);
+
+    if dry_run.unwrap_or(false) {
+        return Ok(DryRunOutcome::DryRun(DryRunReport {
+            deleted_files: orphans.iter().map(|path| paths::to_ipc(path)).collect(),
+            ..Default::default()
+        }));
+    }
+
+    let mut trashed = Vec::with_capacity(orphans.len());
+    for orphan in orphans {
+        crate::core::trash::move_to_trash(&orphan).await?;
+        if let Some(asset_dir) = orphan.parent() {
+            let _ = std::fs::remove_dir(asset_dir);
+        }
+        trashed.push(paths::to_ipc(&orphan));
+    }
+    Ok(DryRunOutcome::Applied(trashed))
 }
 
 /// Update metadata in a resource file's YAML front matter.
@@ -2266,11 +5075,9 @@ pub async fn update_resource_metadata(
     description: Option<String>,
     tags: Option<Vec<String>>,
 ) -> Result<(), String> {
-    use std::fs;
-    use std::path::Path;
-    use serde_yaml::{Mapping, Value};
+    use serde_yaml::Value;
 
-    let path = Path::new(&file_path);
+    let path = paths::from_ipc(&file_path);
 
     // Validate path is within a .bluekit directory for safety
     if !path.to_string_lossy().contains(".bluekit") {
@@ -2280,6 +5087,386 @@ pub async fn update_resource_metadata(
         ));
     }
 
+    // Hold the lock across the read-modify-write so concurrent updates don't
+    // lose each other's fields
+    let _lock = crate::core::file_lock::lock(&path, "update_resource_metadata").await?;
+
+    edit_front_matter(&path, &file_path, |front_matter| {
+        // Update specified fields
+        if let Some(alias_value) = alias {
+            front_matter.insert(
+                Value::String("alias".to_string()),
+                Value::String(alias_value),
+            );
+        }
+
+        if let Some(desc_value) = description {
+            front_matter.insert(
+                Value::String("description".to_string()),
+                Value::String(desc_value),
+            );
+        }
+
+        if let Some(tags_value) = tags {
+            let tags_array: Vec<Value> = tags_value
+                .into_iter()
+                .map(Value::String)
+                .collect();
+            front_matter.insert(
+                Value::String("tags".to_string()),
+                Value::Sequence(tags_array),
+            );
+        }
+    })?;
+
+    Ok(())
+}
+
+/// Changes applied to every file by `bulk_update_resource_metadata`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkMetadataUpdate {
+    /// Tags added to each file (ones it already has are kept once)
+    #[serde(rename = "addTags", default)]
+    pub add_tags: Vec<String>,
+    /// Tags removed from each file
+    #[serde(rename = "removeTags", default)]
+    pub remove_tags: Vec<String>,
+    /// Prefix put in front of each alias (the file name if there's no
+    /// alias); aliases that already start with it are left alone
+    #[serde(rename = "aliasPrefix")]
+    pub alias_prefix: Option<String>,
+    /// New description for every file
+    pub description: Option<String>,
+}
+
+/// Per-file result of `bulk_update_resource_metadata`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkMetadataResult {
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    /// Whether the file changed; false if it already matched or failed
+    pub updated: bool,
+    pub error: Option<String>,
+}
+
+/// Applies the same metadata change to many resource files.
+///
+/// Like `update_resource_metadata`, but for tag additions and removals,
+/// alias prefixes and descriptions across a selection. Each file is locked,
+/// updated and written atomically on its own, so one failure doesn't stop
+/// (or corrupt) the others. Files whose metadata already matches aren't
+/// rewritten.
+///
+/// # Arguments
+///
+/// * `file_paths` - Absolute paths to the resource files
+/// * `update` - The changes to apply to each file
+///
+/// # Returns
+///
+/// One result per file, in order.
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const results = await invoke<BulkMetadataResult[]>('bulk_update_resource_metadata', {
+///   filePaths: selected.map(f => f.path),
+///   update: { addTags: ['auth'], removeTags: ['draft'] }
+/// });
+/// ```
+#[tauri::command]
+pub async fn bulk_update_resource_metadata(
+    file_paths: Vec<String>,
+    update: BulkMetadataUpdate,
+) -> Result<Vec<BulkMetadataResult>, String> {
+    let mut results = Vec::with_capacity(file_paths.len());
+    for file_path in file_paths {
+        let outcome = bulk_update_file(&file_path, &update).await;
+        if let Err(e) = &outcome {
+            tracing::warn!("Failed to update metadata of {}: {}", file_path, e);
+        }
+        results.push(BulkMetadataResult {
+            file_path,
+            updated: outcome.as_ref().is_ok_and(|updated| *updated),
+            error: outcome.err(),
+        });
+    }
+    Ok(results)
+}
+
+// One file of `bulk_update_resource_metadata`; returns whether it changed
+async fn bulk_update_file(file_path: &str, update: &BulkMetadataUpdate) -> Result<bool, String> {
+    use serde_yaml::Value;
+
+    let path = paths::from_ipc(file_path);
+    if !path.to_string_lossy().contains(".bluekit") {
+        return Err(format!("Path is not within a .bluekit directory: {}", file_path));
+    }
+    let _lock = crate::core::file_lock::lock(&path, "bulk_update_resource_metadata").await?;
+
+    edit_front_matter(&path, file_path, |front_matter| {
+        if !update.add_tags.is_empty() || !update.remove_tags.is_empty() {
+            let mut tags = front_matter_tags(front_matter);
+            for tag in &update.add_tags {
+                let tag = tag.trim();
+                if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+            }
+            tags.retain(|tag| !update.remove_tags.iter().any(|removed| removed.trim() == tag));
+            front_matter.insert(
+                Value::String("tags".to_string()),
+                Value::Sequence(tags.into_iter().map(Value::String).collect()),
+            );
+        }
+
+        if let Some(prefix) = update.alias_prefix.as_deref().filter(|p| !p.is_empty()) {
+            let alias = front_matter
+                .get("alias")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| paths::file_stem_lossy(&path));
+            if !alias.starts_with(prefix) {
+                front_matter.insert(
+                    Value::String("alias".to_string()),
+                    Value::String(format!("{}{}", prefix, alias)),
+                );
+            }
+        }
+
+        if let Some(description) = &update.description {
+            front_matter.insert(
+                Value::String("description".to_string()),
+                Value::String(description.clone()),
+            );
+        }
+    })
+}
+
+/// How often a tag is used in a project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagUsage {
+    pub tag: String,
+    #[serde(rename = "artifactCount")]
+    pub artifact_count: usize,
+    #[serde(rename = "taskCount")]
+    pub task_count: usize,
+    /// Artifacts with the tag
+    pub files: Vec<String>,
+}
+
+/// Result of `rename_tag` and `delete_tag`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagChangeResult {
+    /// One result per artifact that had the tag
+    pub files: Vec<BulkMetadataResult>,
+    #[serde(rename = "tasksUpdated")]
+    pub tasks_updated: usize,
+}
+
+/// Lists every tag used in a project, from artifact front matter and from
+/// the project's tasks, most used first.
+///
+/// # Arguments
+///
+/// * `project_path` - Path to the project root directory
+#[tauri::command]
+pub async fn get_all_tags(
+    db: State<'_, DatabaseConnection>,
+    project_path: String,
+) -> Result<Vec<TagUsage>, String> {
+    let mut usage: std::collections::BTreeMap<String, TagUsage> = std::collections::BTreeMap::new();
+    for (path, tags) in tagged_artifacts(&project_path)? {
+        for tag in tags {
+            let tag_usage = usage.entry(tag.clone()).or_insert_with(|| new_tag_usage(&tag));
+            tag_usage.artifact_count += 1;
+            tag_usage.files.push(paths::to_ipc(&path));
+        }
+    }
+
+    if let Some(project_id) = project_id_for_path(&db, &project_path).await? {
+        let tasks = crate::db::task_operations::get_tasks(&db, Some(vec![project_id]))
+            .await
+            .map_err(|e| format!("Failed to get tasks: {}", e))?;
+        for task in tasks {
+            for tag in task.tags {
+                usage.entry(tag.clone()).or_insert_with(|| new_tag_usage(&tag)).task_count += 1;
+            }
+        }
+    }
+
+    let mut tags: Vec<TagUsage> = usage.into_values().collect();
+    tags.sort_by_key(|t| std::cmp::Reverse(t.artifact_count + t.task_count));
+    Ok(tags)
+}
+
+/// Renames a tag in every artifact and task of a project.
+///
+/// Artifacts are rewritten one at a time like `bulk_update_resource_metadata`,
+/// with a result per file. If an item already has `new_tag`, the old tag is
+/// just dropped.
+///
+/// # Arguments
+///
+/// * `project_path` - Path to the project root directory
+/// * `tag` - The tag to rename
+/// * `new_tag` - Its new name
+#[tauri::command]
+pub async fn rename_tag(
+    db: State<'_, DatabaseConnection>,
+    project_path: String,
+    tag: String,
+    new_tag: String,
+) -> Result<TagChangeResult, String> {
+    let new_tag = new_tag.trim();
+    if new_tag.is_empty() {
+        return Err("New tag name cannot be empty".to_string());
+    }
+    replace_project_tag(&db, &project_path, &tag, Some(new_tag)).await
+}
+
+/// Removes a tag from every artifact and task of a project.
+///
+/// # Arguments
+///
+/// * `project_path` - Path to the project root directory
+/// * `tag` - The tag to remove
+#[tauri::command]
+pub async fn delete_tag(
+    db: State<'_, DatabaseConnection>,
+    project_path: String,
+    tag: String,
+) -> Result<TagChangeResult, String> {
+    replace_project_tag(&db, &project_path, &tag, None).await
+}
+
+async fn replace_project_tag(
+    db: &DatabaseConnection,
+    project_path: &str,
+    tag: &str,
+    replacement: Option<&str>,
+) -> Result<TagChangeResult, String> {
+    let mut files = Vec::new();
+    for (path, tags) in tagged_artifacts(project_path)? {
+        if !tags.iter().any(|t| t == tag) {
+            continue;
+        }
+        let file_path = paths::to_ipc(&path);
+        let outcome = replace_file_tag(&path, &file_path, tag, replacement).await;
+        if let Err(e) = &outcome {
+            tracing::warn!("Failed to update tags of {}: {}", file_path, e);
+        }
+        files.push(BulkMetadataResult {
+            file_path,
+            updated: outcome.as_ref().is_ok_and(|updated| *updated),
+            error: outcome.err(),
+        });
+    }
+
+    let tasks_updated = match project_id_for_path(db, project_path).await? {
+        Some(project_id) => crate::db::task_operations::replace_task_tag(db, &project_id, tag, replacement)
+            .await
+            .map_err(|e| format!("Failed to update task tags: {}", e))?,
+        None => 0,
+    };
+
+    Ok(TagChangeResult { files, tasks_updated })
+}
+
+// Replaces `tag` in one file's front matter. The tags are read again under
+// the file's lock, so an edit made since the scan isn't overwritten
+async fn replace_file_tag(
+    path: &std::path::Path,
+    file_path: &str,
+    tag: &str,
+    replacement: Option<&str>,
+) -> Result<bool, String> {
+    use serde_yaml::Value;
+
+    let _lock = crate::core::file_lock::lock(path, "update_tags").await?;
+    edit_front_matter(path, file_path, |front_matter| {
+        let tags = front_matter_tags(front_matter);
+        if !tags.iter().any(|t| t == tag) {
+            return;
+        }
+        let tags = crate::db::task_operations::replace_tag(&tags, tag, replacement);
+        front_matter.insert(
+            Value::String("tags".to_string()),
+            Value::Sequence(tags.into_iter().map(Value::String).collect()),
+        );
+    })
+}
+
+// Tags in parsed front matter: a list, or a comma-separated string, which
+// artifact metadata also accepts
+fn front_matter_tags(front_matter: &serde_yaml::Mapping) -> Vec<String> {
+    use serde_yaml::Value;
+
+    match front_matter.get("tags") {
+        Some(Value::Sequence(tags)) => tags
+            .iter()
+            .filter_map(|tag| tag.as_str().map(str::to_string))
+            .collect(),
+        Some(Value::String(tags)) => tags
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Text artifacts of a project with the tags in their front matter
+fn tagged_artifacts(project_path: &str) -> Result<Vec<(PathBuf, Vec<String>)>, String> {
+    let bluekit_path = paths::from_ipc(project_path).join(".bluekit");
+    if !bluekit_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(collect_artifact_paths(&bluekit_path)?
+        .into_iter()
+        .filter(|path| !is_image_asset(path))
+        .filter_map(|path| {
+            let tags = artifact_metadata(&read_front_matter_only(&path)?).tags;
+            Some((path, tags))
+        })
+        .filter(|(_, tags)| !tags.is_empty())
+        .collect())
+}
+
+fn new_tag_usage(tag: &str) -> TagUsage {
+    TagUsage {
+        tag: tag.to_string(),
+        artifact_count: 0,
+        task_count: 0,
+        files: Vec::new(),
+    }
+}
+
+// Id of the registered project at `project_path`, if there is one
+async fn project_id_for_path(db: &DatabaseConnection, project_path: &str) -> Result<Option<String>, String> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    crate::db::entities::project::Entity::find()
+        .filter(crate::db::entities::project::Column::Path.eq(project_path))
+        .one(db)
+        .await
+        .map(|project| project.map(|p| p.id))
+        .map_err(|e| format!("Failed to look up project: {}", e))
+}
+
+/// Reads a resource file, lets `edit` change its YAML front matter and
+/// writes the file back atomically if anything changed. The markdown body
+/// is kept as-is; a file without front matter gets a new block. Returns
+/// whether the file was rewritten. Callers hold the file's lock.
+pub(crate) fn edit_front_matter(
+    path: &std::path::Path,
+    file_path: &str,
+    edit: impl FnOnce(&mut serde_yaml::Mapping),
+) -> Result<bool, String> {
+    use std::fs;
+    use serde_yaml::Mapping;
+
     // Read existing file content
     let content = fs::read_to_string(path)
         .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?;
@@ -2308,37 +5495,17 @@ pub async fn update_resource_metadata(
             (fm, body)
         } else {
             // Malformed front matter (no closing ---), treat as no front matter
-            (Mapping::new(), content)
+            (Mapping::new(), content.clone())
         }
     } else {
         // No front matter exists, create new
-        (Mapping::new(), content)
+        (Mapping::new(), content.clone())
     };
 
-    // Update specified fields
-    if let Some(alias_value) = alias {
-        front_matter.insert(
-            Value::String("alias".to_string()),
-            Value::String(alias_value),
-        );
-    }
-
-    if let Some(desc_value) = description {
-        front_matter.insert(
-            Value::String("description".to_string()),
-            Value::String(desc_value),
-        );
-    }
-
-    if let Some(tags_value) = tags {
-        let tags_array: Vec<Value> = tags_value
-            .into_iter()
-            .map(|tag| Value::String(tag))
-            .collect();
-        front_matter.insert(
-            Value::String("tags".to_string()),
-            Value::Sequence(tags_array),
-        );
+    let original = front_matter.clone();
+    edit(&mut front_matter);
+    if front_matter == original {
+        return Ok(false);
     }
 
     // Serialize updated front matter
@@ -2356,11 +5523,19 @@ pub async fn update_resource_metadata(
         format!("---\n{}\n---\n{}", trimmed_fm, body)
     };
 
-    // Write back to file
-    fs::write(path, new_content)
+    // Write next to the file and rename over it, so a failed write never
+    // leaves a truncated file behind
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    fs::write(&tmp_path, new_content)
         .map_err(|e| format!("Failed to write file {}: {}", file_path, e))?;
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to write file {}: {}", file_path, e)
+    })?;
 
-    Ok(())
+    Ok(true)
 }
 
 /// Gets all folders in a specific artifact type directory.
@@ -2384,7 +5559,7 @@ pub async fn get_artifact_folders(
 ) -> Result<Vec<ArtifactFolder>, String> {
     use std::fs;
 
-    let artifact_dir = PathBuf::from(&project_path)
+    let artifact_dir = paths::from_ipc(&project_path)
         .join(".bluekit")
         .join(&artifact_type);
 
@@ -2426,10 +5601,7 @@ pub async fn get_artifact_folders(
         let path = entry.path();
 
         if path.is_dir() {
-            let folder_name = path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("")
-                .to_string();
+            let folder_name = paths::file_name_lossy(&path);
 
             // Skip hidden directories
             if folder_name.starts_with('.') {
@@ -2463,7 +5635,7 @@ pub async fn get_artifact_folders(
 
             folders.push(ArtifactFolder {
                 name: folder_name,
-                path: path.to_str().unwrap_or("").to_string(),
+                path: paths::to_ipc(&path),
                 parent_path: None, // Flat structure - no parents
                 config,            // Populated from config.json if exists
                 artifact_count,
@@ -2502,7 +5674,7 @@ pub async fn create_artifact_folder(
     use std::fs;
 
     // Always create at root level (flat structure)
-    let base_dir = PathBuf::from(&project_path)
+    let base_dir = paths::from_ipc(&project_path)
         .join(".bluekit")
         .join(&artifact_type);
 
@@ -2524,7 +5696,7 @@ pub async fn create_artifact_folder(
     fs::write(&config_path, config_json)
         .map_err(|e| format!("Failed to write config.json: {}", e))?;
 
-    Ok(folder_path.to_str().unwrap_or("").to_string())
+    Ok(paths::to_ipc(&folder_path))
 }
 
 /// DEPRECATED: Updates a folder's config.json file.
@@ -2547,7 +5719,7 @@ pub async fn update_folder_config(
 ) -> Result<(), String> {
     use std::fs;
 
-    let config_path = PathBuf::from(&folder_path).join("config.json");
+    let config_path = paths::from_ipc(&folder_path).join("config.json");
 
     let config_json = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
@@ -2576,7 +5748,7 @@ pub async fn delete_artifact_folder(
 ) -> Result<(), String> {
     use std::fs;
 
-    let path = PathBuf::from(&folder_path);
+    let path = paths::from_ipc(&folder_path);
 
     // Validate path is within .bluekit
     if !path.to_string_lossy().contains(".bluekit") {
@@ -2616,7 +5788,7 @@ pub async fn rename_artifact_folder(
 ) -> Result<String, String> {
     use std::fs;
 
-    let path = PathBuf::from(&folder_path);
+    let path = paths::from_ipc(&folder_path);
 
     // Validate path is within .bluekit
     if !path.to_string_lossy().contains(".bluekit") {
@@ -2661,71 +5833,734 @@ pub async fn rename_artifact_folder(
         }
     }
 
-    Ok(new_path.to_str().unwrap_or("").to_string())
+    Ok(paths::to_ipc(&new_path))
+}
+
+/// Moves an artifact file into a folder.
+///
+/// # Arguments
+///
+/// * `artifact_path` - Full path to the artifact file
+/// * `target_folder_path` - Full path to the target folder
+///
+/// # Returns
+///
+/// New path of the moved artifact
+#[tauri::command]
+pub async fn move_artifact_to_folder(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    artifact_path: String,
+    target_folder_path: String,
+) -> Result<String, String> {
+    use std::fs;
+
+    let source = paths::from_ipc(&artifact_path);
+    let target_folder = paths::from_ipc(&target_folder_path);
+
+    if !source.exists() {
+        return Err("Source artifact does not exist".to_string());
+    }
+
+    if !target_folder.exists() || !target_folder.is_dir() {
+        return Err("Target folder does not exist".to_string());
+    }
+
+    let file_name = source.file_name()
+        .ok_or_else(|| "Invalid source file name".to_string())?;
+    let destination = target_folder.join(file_name);
+
+    if destination.exists() {
+        return Err(format!("File already exists in target folder: {:?}", file_name));
+    }
+
+    // Move file
+    fs::rename(&source, &destination)
+        .map_err(|e| format!("Failed to move file: {}", e))?;
+
+    let destination_str = paths::to_ipc(&destination);
+
+    let is_walkthrough_path = |path: &PathBuf| {
+        let normalized = path.to_string_lossy().replace('\\', "/");
+        normalized.contains("/.bluekit/walkthroughs/")
+    };
+
+    if is_walkthrough_path(&source) && is_walkthrough_path(&destination) {
+        if let Err(err) = crate::db::walkthrough_operations::update_walkthrough_file_path(
+            db.inner(),
+            &artifact_path,
+            &destination_str,
+        )
+        .await
+        {
+            let _ = fs::rename(&destination, &source);
+            return Err(format!("Failed to update walkthrough path: {}", err));
+        }
+    }
+
+    Ok(destination_str)
+}
+
+/// Result of moving an artifact into another project.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveArtifactResult {
+    #[serde(rename = "newPath")]
+    pub new_path: String,
+    pub transferred: crate::db::move_operations::TransferredRecordsDto,
+}
+
+/// Moves an artifact file into another project's .bluekit directory.
+///
+/// The file keeps its artifact type directory (e.g. `kits/`, `walkthroughs/`)
+/// in the target project. Related DB records (walkthrough + takeaways/notes,
+/// agent records, library resources, kit usage, provenance) and bookmarks are
+/// transferred so nothing is lost, and `project-artifacts-changed-*` events are
+/// emitted for both projects.
+///
+/// # Arguments
+///
+/// * `source_path` - Full path to the artifact file (must be inside a `.bluekit` directory)
+/// * `target_project` - Root path of the destination project
+///
+/// # Returns
+///
+/// The new path of the artifact and counts of transferred records
+#[tauri::command]
+pub async fn move_artifact_between_projects(
+    app_handle: AppHandle,
+    db: State<'_, sea_orm::DatabaseConnection>,
+    cache: State<'_, ArtifactCache>,
+    source_path: String,
+    target_project: String,
+) -> Result<MoveArtifactResult, String> {
+    use std::fs;
+    use tauri::Manager;
+
+    let source = paths::from_ipc(&source_path);
+    if !source.is_file() {
+        return Err(format!("Source artifact does not exist: {}", source_path));
+    }
+
+    // Locate the source project root and the artifact's path inside .bluekit
+    let bluekit_dir = source
+        .ancestors()
+        .find(|p| p.file_name().map(|n| n == ".bluekit").unwrap_or(false))
+        .ok_or_else(|| "Source artifact is not inside a .bluekit directory".to_string())?;
+    let source_project = bluekit_dir
+        .parent()
+        .ok_or_else(|| "Invalid source project path".to_string())?
+        .to_path_buf();
+    let inner_path = source
+        .strip_prefix(bluekit_dir)
+        .map_err(|e| format!("Failed to compute artifact path: {}", e))?;
+
+    let target_root = paths::from_ipc(&target_project);
+    if !target_root.is_dir() {
+        return Err(format!("Target project directory does not exist: {}", target_project));
+    }
+    if target_root == source_project {
+        return Err("Artifact is already in the target project".to_string());
+    }
+
+    // Keep the artifact type directory, drop any source-specific folders
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| "Invalid source file name".to_string())?;
+    let target_dir = match inner_path.components().count() {
+        1 => target_root.join(".bluekit"),
+        _ => target_root.join(".bluekit").join(inner_path.components().next().unwrap()),
+    };
+    let destination = target_dir.join(file_name);
+    if destination.exists() {
+        return Err(format!("File already exists in target project: {}", destination.display()));
+    }
+
+    fs::create_dir_all(&target_dir)
+        .map_err(|e| format!("Failed to create target directory: {}", e))?;
+
+    // rename fails across filesystems; fall back to copy + delete
+    if fs::rename(&source, &destination).is_err() {
+        fs::copy(&source, &destination)
+            .map_err(|e| format!("Failed to move file: {}", e))?;
+        fs::remove_file(&source)
+            .map_err(|e| format!("Failed to remove source file after copy: {}", e))?;
+    }
+
+    let destination_str = destination.to_string_lossy().to_string();
+    let source_project_str = source_project.to_string_lossy().to_string();
+
+    let transferred = match crate::db::move_operations::transfer_artifact_records(
+        db.inner(),
+        &source_path,
+        &destination_str,
+        &source_project_str,
+        &target_project,
+    )
+    .await
+    {
+        Ok(transferred) => transferred,
+        Err(e) => {
+            // Put the file back so DB and disk stay consistent
+            if fs::rename(&destination, &source).is_err() {
+                let _ = fs::copy(&destination, &source).and_then(|_| fs::remove_file(&destination));
+            }
+            return Err(format!("Failed to transfer artifact records: {}", e));
+        }
+    };
+
+    // Carry the bookmark over to the target project (best-effort)
+    if let Ok(mut source_bookmarks) = get_bookmarks(source_project_str.clone()).await {
+        if let Some(BookmarkItem::File { ctime, title, .. }) = find_file_bookmark(&source_bookmarks.items, &source_path).cloned() {
+            remove_bookmark_recursive(&mut source_bookmarks.items, &source_path);
+            if let Err(e) = save_bookmarks(source_project_str.clone(), source_bookmarks).await {
+                tracing::warn!("Failed to update source bookmarks: {}", e);
+            }
+            let item = BookmarkItem::File { ctime, path: destination_str.clone(), title };
+            if let Err(e) = add_bookmark(target_project.clone(), item).await {
+                tracing::warn!("Failed to add target bookmark: {}", e);
+            }
+        }
+    }
+
+    cache.invalidate(&source).await;
+
+    let _ = app_handle.emit_all(&project_artifacts_event_name(&source_project_str), vec![source_path.clone()]);
+    let _ = app_handle.emit_all(&project_artifacts_event_name(&target_project), vec![destination_str.clone()]);
+
+    Ok(MoveArtifactResult {
+        new_path: destination_str,
+        transferred,
+    })
+}
+
+/// Moves a resource to another folder of the same project's `.bluekit`
+/// directory.
+///
+/// `target_directory` is relative to `.bluekit`, e.g. `kits`,
+/// `walkthroughs/onboarding` or a scrapbook folder. The front matter `type`
+/// is set to match the destination (`kits` -> `kit`, `walkthroughs` ->
+/// `walkthrough`, `agents` -> `agent`, `diagrams` -> `diagram`) and removed
+/// when the file lands in the scrapbook. DB records follow the file and a
+/// `project-artifacts-changed-*` event with both paths refreshes the lists.
+///
+/// # Arguments
+///
+/// * `file_path` - Full path to the resource (must be inside a `.bluekit` directory)
+/// * `target_directory` - Destination folder relative to `.bluekit` (created if missing)
+///
+/// # Returns
+///
+/// The new path of the resource
+#[tauri::command]
+pub async fn move_resource(
+    app_handle: AppHandle,
+    db: State<'_, sea_orm::DatabaseConnection>,
+    cache: State<'_, ArtifactCache>,
+    file_path: String,
+    target_directory: String,
+) -> Result<String, String> {
+    use std::fs;
+    use tauri::Manager;
+
+    let source = paths::from_ipc(&file_path);
+    if !source.is_file() {
+        return Err(format!("Resource does not exist: {}", file_path));
+    }
+
+    let bluekit_dir = source
+        .ancestors()
+        .find(|p| p.file_name().map(|n| n == ".bluekit").unwrap_or(false))
+        .ok_or_else(|| "Resource is not inside a .bluekit directory".to_string())?
+        .to_path_buf();
+    let project_root = bluekit_dir
+        .parent()
+        .ok_or_else(|| "Invalid project path".to_string())?
+        .to_path_buf();
+
+    let components: Vec<&str> = target_directory
+        .split(['/', '\\'])
+        .filter(|c| !c.is_empty())
+        .collect();
+    if components.is_empty() {
+        return Err("Target directory must be a folder inside .bluekit".to_string());
+    }
+    if components.iter().any(|c| *c == "." || *c == ".." || c.starts_with('.')) {
+        return Err(format!("Invalid target directory: {}", target_directory));
+    }
+    let artifact_type = match components[0] {
+        "kits" => Some("kit"),
+        "walkthroughs" => Some("walkthrough"),
+        "agents" => Some("agent"),
+        "diagrams" => Some("diagram"),
+        "blueprints" | "tasks" => {
+            return Err(format!("Resources can't be moved into {}", components[0]));
+        }
+        // Anything else is a scrapbook folder
+        _ => None,
+    };
+
+    let target_folder = components.iter().fold(bluekit_dir.clone(), |dir, c| dir.join(c));
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| "Invalid source file name".to_string())?;
+    let destination = target_folder.join(file_name);
+    if destination == source {
+        return Ok(file_path);
+    }
+    if destination.exists() {
+        return Err(format!("File already exists in target folder: {:?}", file_name));
+    }
+
+    fs::create_dir_all(&target_folder)
+        .map_err(|e| format!("Failed to create target folder: {}", e))?;
+    {
+        let _lock = crate::core::file_lock::lock(&source, "move_resource").await?;
+        fs::rename(&source, &destination)
+            .map_err(|e| format!("Failed to move file: {}", e))?;
+    }
+
+    let destination_str = paths::to_ipc(&destination);
+    let project_str = paths::to_ipc(&project_root);
+
+    if let Err(e) = crate::db::move_operations::transfer_artifact_records(
+        db.inner(),
+        &file_path,
+        &destination_str,
+        &project_str,
+        &project_str,
+    )
+    .await
+    {
+        let _ = fs::rename(&destination, &source);
+        return Err(format!("Failed to update artifact records: {}", e));
+    }
+
+    set_front_matter_type(&destination, artifact_type).await?;
+
+    if let Ok(mut bookmarks) = get_bookmarks(project_str.clone()).await {
+        if rename_bookmark_path(&mut bookmarks.items, &file_path, &destination_str) {
+            if let Err(e) = save_bookmarks(project_str.clone(), bookmarks).await {
+                tracing::warn!("Failed to update bookmarks: {}", e);
+            }
+        }
+    }
+
+    cache.invalidate(&source).await;
+    cache.invalidate(&destination).await;
+    let _ = app_handle.emit_all(&project_artifacts_event_name(&project_str), vec![file_path.clone(), destination_str.clone()]);
+
+    Ok(destination_str)
+}
+
+/// Sets (or with `None`, removes) the front matter `type` of a file. Files
+/// without front matter are left alone.
+async fn set_front_matter_type(path: &std::path::Path, artifact_type: Option<&str>) -> Result<(), String> {
+    use serde_yaml::Value;
+
+    let _lock = crate::core::file_lock::lock(path, "set_front_matter_type").await?;
+    edit_front_matter(path, &path.display().to_string(), |front_matter| {
+        if front_matter.is_empty() {
+            return;
+        }
+        let key = Value::String("type".to_string());
+        match artifact_type {
+            Some(artifact_type) => {
+                front_matter.insert(key, Value::String(artifact_type.to_string()));
+            }
+            None => {
+                front_matter.remove(&key);
+            }
+        }
+    })?;
+    Ok(())
+}
+
+/// Result of renaming a resource.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RenameResourceResult {
+    #[serde(rename = "newPath")]
+    pub new_path: String,
+    /// Artifacts and blueprint.json files whose references were rewritten
+    #[serde(rename = "updatedFiles")]
+    pub updated_files: Vec<String>,
+    /// Total number of references rewritten across `updated_files`
+    #[serde(rename = "referenceCount")]
+    pub reference_count: usize,
+}
+
+/// Renames a kit, walkthrough or diagram and updates everything pointing at it.
+///
+/// The file is renamed in place, its `alias` front matter is set to
+/// `new_alias` (or a title derived from the new name), and every other
+/// artifact and blueprint.json in the project has references to the old file
+/// name rewritten: relative links, `[[wikilinks]]` and blueprint task files.
+/// DB records and bookmarks for the file follow it to the new path.
+///
+/// # Arguments
+///
+/// * `file_path` - Full path to the resource (must be inside a `.bluekit` directory)
+/// * `new_name` - New file name; the old extension is kept if none is given
+/// * `new_alias` - Alias to write to the front matter (optional)
+///
+/// # Returns
+///
+/// The new path and the files whose references were updated
+#[tauri::command]
+pub async fn rename_resource(
+    app_handle: AppHandle,
+    db: State<'_, sea_orm::DatabaseConnection>,
+    cache: State<'_, ArtifactCache>,
+    file_path: String,
+    new_name: String,
+    new_alias: Option<String>,
+) -> Result<RenameResourceResult, String> {
+    use std::fs;
+    use tauri::Manager;
+
+    let source = paths::from_ipc(&file_path);
+    if !source.is_file() {
+        return Err(format!("Resource does not exist: {}", file_path));
+    }
+
+    let bluekit_dir = source
+        .ancestors()
+        .find(|p| p.file_name().map(|n| n == ".bluekit").unwrap_or(false))
+        .ok_or_else(|| "Resource is not inside a .bluekit directory".to_string())?
+        .to_path_buf();
+    let project_root = bluekit_dir
+        .parent()
+        .ok_or_else(|| "Invalid project path".to_string())?
+        .to_path_buf();
+
+    let new_name = new_name.trim();
+    if new_name.is_empty() || new_name.contains(['/', '\\']) || new_name == "." || new_name == ".." {
+        return Err(format!("Invalid file name: {}", new_name));
+    }
+    let old_file_name = paths::file_name_lossy(&source);
+    let new_file_name = match (std::path::Path::new(new_name).extension(), source.extension()) {
+        (None, Some(extension)) => format!("{}.{}", new_name, extension.to_string_lossy()),
+        _ => new_name.to_string(),
+    };
+    if new_file_name == old_file_name {
+        return Err("New name is the same as the current name".to_string());
+    }
+
+    let destination = source.with_file_name(&new_file_name);
+    if destination.exists() {
+        return Err(format!("A file named {} already exists", new_file_name));
+    }
+
+    {
+        let _lock = crate::core::file_lock::lock(&source, "rename_resource").await?;
+        fs::rename(&source, &destination)
+            .map_err(|e| format!("Failed to rename file: {}", e))?;
+    }
+
+    let destination_str = paths::to_ipc(&destination);
+    let project_str = paths::to_ipc(&project_root);
+
+    if let Err(e) = crate::db::move_operations::transfer_artifact_records(
+        db.inner(),
+        &file_path,
+        &destination_str,
+        &project_str,
+        &project_str,
+    )
+    .await
+    {
+        // Put the file back so DB and disk stay consistent
+        let _ = fs::rename(&destination, &source);
+        return Err(format!("Failed to update artifact records: {}", e));
+    }
+
+    // Front matter alias; files without front matter are left as they are
+    let content = fs::read_to_string(&destination).unwrap_or_default();
+    if parse_front_matter(&content).is_some() {
+        let alias = new_alias.unwrap_or_else(|| alias_from_file_name(&paths::file_stem_lossy(&destination)));
+        update_resource_metadata(destination_str.clone(), Some(alias), None, None).await?;
+    }
+
+    // Rewrite references in the rest of the project
+    let old_stem = paths::file_stem_lossy(&source);
+    let new_stem = paths::file_stem_lossy(&destination);
+    let mut candidates = Vec::new();
+    collect_reference_files(&bluekit_dir, &mut candidates);
+
+    let mut updated_files = Vec::new();
+    let mut reference_count = 0;
+    for candidate in candidates.into_iter().filter(|c| *c != destination) {
+        let _lock = crate::core::file_lock::lock(&candidate, "rename_resource").await?;
+        let Ok(content) = fs::read_to_string(&candidate) else {
+            continue;
+        };
+
+        let (content, file_refs) = replace_file_references(&content, &old_file_name, &new_file_name);
+        let (content, wiki_refs) = if candidate.extension().is_some_and(|e| e == "md") {
+            replace_wikilinks(&content, &old_stem, &new_stem)
+        } else {
+            (content, 0)
+        };
+        if file_refs + wiki_refs == 0 {
+            continue;
+        }
+
+        fs::write(&candidate, content)
+            .map_err(|e| format!("Failed to update references in {}: {}", candidate.display(), e))?;
+        cache.invalidate(&candidate).await;
+        reference_count += file_refs + wiki_refs;
+        updated_files.push(paths::to_ipc(&candidate));
+    }
+
+    // Bookmarks follow the file (best-effort)
+    if let Ok(mut bookmarks) = get_bookmarks(project_str.clone()).await {
+        if rename_bookmark_path(&mut bookmarks.items, &file_path, &destination_str) {
+            if let Err(e) = save_bookmarks(project_str.clone(), bookmarks).await {
+                tracing::warn!("Failed to update bookmarks: {}", e);
+            }
+        }
+    }
+
+    cache.invalidate(&source).await;
+    let _ = app_handle.emit_all(&project_artifacts_event_name(&project_str), vec![file_path.clone(), destination_str.clone()]);
+
+    Ok(RenameResourceResult {
+        new_path: destination_str,
+        updated_files,
+        reference_count,
+    })
+}
+
+/// Title-cased alias from a file stem ("api-client" -> "Api Client").
+fn alias_from_file_name(stem: &str) -> String {
+    stem.split(['-', '_', ' '])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Collects artifacts (.md, .mmd, .mermaid) and blueprint.json files that
+/// may reference other artifacts.
+fn collect_reference_files(dir: &std::path::Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            collect_reference_files(&path, files);
+        } else if file_type.is_file() {
+            let is_reference_file = path.file_name().is_some_and(|n| n == "blueprint.json")
+                || path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| matches!(e, "md" | "mmd" | "mermaid"));
+            if is_reference_file {
+                files.push(path);
+            }
+        }
+    }
+}
+
+/// Replaces whole-name occurrences of `old_name` (e.g. in links or
+/// blueprint task files), returning the new content and the count.
+///
+/// `kit.md` matches in `../kits/kit.md` and `"kit.md"` but not in
+/// `my-kit.md` or `kit.md.bak`.
+fn replace_file_references(content: &str, old_name: &str, new_name: &str) -> (String, usize) {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.');
+    let mut result = String::with_capacity(content.len());
+    let mut count = 0;
+    let mut last = 0;
+
+    for (start, _) in content.match_indices(old_name) {
+        let end = start + old_name.len();
+        let before_ok = !content[..start].chars().next_back().is_some_and(is_name_char);
+        let after = content[end..].chars().next();
+        // A trailing dot ends a sentence; only a dot followed by more of a name continues it
+        let after_ok = match after {
+            Some('.') => !content[end + 1..].chars().next().is_some_and(|c| c.is_alphanumeric()),
+            Some(c) => !is_name_char(c),
+            None => true,
+        };
+        if before_ok && after_ok && start >= last {
+            result.push_str(&content[last..start]);
+            result.push_str(new_name);
+            last = end;
+            count += 1;
+        }
+    }
+    result.push_str(&content[last..]);
+    (result, count)
+}
+
+/// Rewrites `[[old]]`, `[[old|label]]` and `[[old#heading]]` wikilinks.
+fn replace_wikilinks(content: &str, old_stem: &str, new_stem: &str) -> (String, usize) {
+    let mut result = content.to_string();
+    let mut count = 0;
+    for suffix in ["]]", "|", "#"] {
+        let old = format!("[[{}{}", old_stem, suffix);
+        let occurrences = result.matches(&old).count();
+        if occurrences > 0 {
+            result = result.replace(&old, &format!("[[{}{}", new_stem, suffix));
+            count += occurrences;
+        }
+    }
+    (result, count)
 }
 
-/// Moves an artifact file into a folder.
+/// Returns the artifact type directory (e.g. `kits`) of a file inside a
+/// `.bluekit` directory or the shared pool.
+fn artifact_type_dir(path: &std::path::Path) -> Option<std::ffi::OsString> {
+    let shared_pool = crate::core::links::shared_pool_dir().ok();
+    let root = path.ancestors().find(|p| {
+        p.file_name().map(|n| n == ".bluekit").unwrap_or(false)
+            || shared_pool.as_deref() == Some(*p)
+    })?;
+
+    let inner_path = path.strip_prefix(root).ok()?;
+    if inner_path.components().count() < 2 {
+        return None;
+    }
+    inner_path.components().next().map(|c| c.as_os_str().to_os_string())
+}
+
+/// Links an artifact into a project instead of copying it.
+///
+/// Writes a small stub into the target project's `.bluekit/<type>/` that
+/// points at one canonical file. `read_file`, `write_file` and the artifact
+/// listings resolve the stub, so every linked project shares the same content.
 ///
 /// # Arguments
 ///
-/// * `artifact_path` - Full path to the artifact file
-/// * `target_folder_path` - Full path to the target folder
+/// * `source_path` - Full path to the artifact to link (a stub links to its canonical file)
+/// * `target_project_path` - Root path of the project that should reference it
+/// * `use_shared_pool` - Copy the artifact into `~/.bluekit/shared/` and link to that copy
+///   instead of the source project's file
 ///
 /// # Returns
 ///
-/// New path of the moved artifact
+/// The path of the created stub
 #[tauri::command]
-pub async fn move_artifact_to_folder(
-    db: State<'_, sea_orm::DatabaseConnection>,
-    artifact_path: String,
-    target_folder_path: String,
+pub async fn link_artifact_to_project(
+    source_path: String,
+    target_project_path: String,
+    use_shared_pool: Option<bool>,
 ) -> Result<String, String> {
     use std::fs;
+    use crate::core::links;
 
-    let source = PathBuf::from(&artifact_path);
-    let target_folder = PathBuf::from(&target_folder_path);
+    let source = paths::from_ipc(&source_path);
+    if !source.is_file() {
+        return Err(format!("Source artifact does not exist: {}", source_path));
+    }
 
-    if !source.exists() {
-        return Err("Source artifact does not exist".to_string());
+    let target_project = paths::from_ipc(&target_project_path);
+    if !target_project.is_dir() {
+        return Err(format!("Target project directory does not exist: {}", target_project_path));
     }
 
-    if !target_folder.exists() || !target_folder.is_dir() {
-        return Err("Target folder does not exist".to_string());
+    // Always link to the canonical file, never to another stub
+    let mut canonical = links::resolve_path(&source)?.unwrap_or(source);
+
+    let type_dir = artifact_type_dir(&canonical)
+        .ok_or_else(|| "Artifact must be inside a .bluekit type directory or the shared pool".to_string())?;
+    let file_name = canonical
+        .file_name()
+        .ok_or_else(|| "Invalid source file name".to_string())?
+        .to_os_string();
+
+    let shared_pool = links::shared_pool_dir()?;
+
+    if use_shared_pool.unwrap_or(false) && !canonical.starts_with(&shared_pool) {
+        let pooled = shared_pool.join(&type_dir).join(&file_name);
+        let contents = fs::read_to_string(&canonical)
+            .map_err(|e| format!("Failed to read source file: {}", e))?;
+
+        if pooled.exists() {
+            let existing = fs::read_to_string(&pooled)
+                .map_err(|e| format!("Failed to read shared artifact: {}", e))?;
+            if existing != contents {
+                return Err(format!(
+                    "A different artifact already exists in the shared pool: {}",
+                    pooled.display()
+                ));
+            }
+        } else {
+            fs::create_dir_all(shared_pool.join(&type_dir))
+                .map_err(|e| format!("Failed to create shared pool directory: {}", e))?;
+            fs::write(&pooled, contents)
+                .map_err(|e| format!("Failed to write shared artifact: {}", e))?;
+        }
+
+        canonical = pooled;
     }
 
-    let file_name = source.file_name()
-        .ok_or_else(|| "Invalid source file name".to_string())?;
-    let destination = target_folder.join(file_name);
+    let link = match canonical.strip_prefix(&shared_pool) {
+        Ok(relative) => format!(
+            "{}{}",
+            links::SHARED_SCHEME,
+            relative.to_string_lossy().replace('\\', "/")
+        ),
+        Err(_) => canonical.to_string_lossy().to_string(),
+    };
 
-    if destination.exists() {
-        return Err(format!("File already exists in target folder: {:?}", file_name));
+    let stub_dir = target_project.join(".bluekit").join(&type_dir);
+    let stub_path = stub_dir.join(&file_name);
+    if stub_path.exists() {
+        return Err(format!("File already exists in target project: {}", stub_path.display()));
     }
 
-    // Move file
-    fs::rename(&source, &destination)
-        .map_err(|e| format!("Failed to move file: {}", e))?;
+    // Carry the type over so the stub is still classified without resolution
+    let artifact_type = fs::read_to_string(&canonical)
+        .ok()
+        .and_then(|content| parse_front_matter(&content))
+        .and_then(|fm| fm.get("type").and_then(|v| v.as_str()).map(|s| s.to_string()));
 
-    let destination_str = destination.to_str().unwrap_or("").to_string();
+    fs::create_dir_all(&stub_dir)
+        .map_err(|e| format!("Failed to create target directory: {}", e))?;
+    fs::write(&stub_path, links::build_stub(&link, artifact_type.as_deref()))
+        .map_err(|e| format!("Failed to write link stub: {}", e))?;
 
-    let is_walkthrough_path = |path: &PathBuf| {
-        let normalized = path.to_string_lossy().replace('\\', "/");
-        normalized.contains("/.bluekit/walkthroughs/")
-    };
+    Ok(stub_path.to_string_lossy().to_string())
+}
 
-    if is_walkthrough_path(&source) && is_walkthrough_path(&destination) {
-        if let Err(err) = crate::db::walkthrough_operations::update_walkthrough_file_path(
-            db.inner(),
-            &artifact_path,
-            &destination_str,
-        )
-        .await
-        {
-            let _ = fs::rename(&destination, &source);
-            return Err(format!("Failed to update walkthrough path: {}", err));
-        }
-    }
+/// Replaces a link stub with a standalone copy of the canonical artifact.
+///
+/// # Arguments
+///
+/// * `stub_path` - Full path to the link stub
+///
+/// # Returns
+///
+/// A `Result<(), String>` indicating success or failure
+#[tauri::command]
+pub async fn unlink_artifact(
+    cache: State<'_, ArtifactCache>,
+    stub_path: String,
+) -> Result<(), String> {
+    use std::fs;
 
-    Ok(destination_str)
+    let path = paths::from_ipc(&stub_path);
+    let target = crate::core::links::resolve_path(&path)?
+        .ok_or_else(|| format!("Not a linked artifact: {}", stub_path))?;
+
+    let contents = fs::read_to_string(&target)
+        .map_err(|e| format!("Failed to read linked file {}: {}", target.display(), e))?;
+    fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write file {}: {}", stub_path, e))?;
+
+    cache.invalidate(&path).await;
+
+    Ok(())
 }
 
 /// Moves a folder into another folder (creating nesting).
@@ -2745,8 +6580,8 @@ pub async fn move_folder_to_folder(
 ) -> Result<String, String> {
     use std::fs;
 
-    let source = PathBuf::from(&source_folder_path);
-    let target_folder = PathBuf::from(&target_folder_path);
+    let source = paths::from_ipc(&source_folder_path);
+    let target_folder = paths::from_ipc(&target_folder_path);
 
     // Prevent moving folder into itself or its descendants
     if target_folder.starts_with(&source) {
@@ -2773,7 +6608,7 @@ pub async fn move_folder_to_folder(
     fs::rename(&source, &destination)
         .map_err(|e| format!("Failed to move folder: {}", e))?;
 
-    Ok(destination.to_str().unwrap_or("").to_string())
+    Ok(paths::to_ipc(&destination))
 }
 
 /// Opens a project in the specified editor.
@@ -2807,7 +6642,7 @@ pub async fn open_project_in_editor(
 ) -> Result<(), String> {
     use std::process::Command;
 
-    let path = PathBuf::from(&project_path);
+    let path = paths::from_ipc(&project_path);
 
     // Verify the project path exists
     if !path.exists() {
@@ -2883,7 +6718,7 @@ pub async fn open_in_terminal(
 ) -> Result<(), String> {
     use std::process::Command;
 
-    let path_buf = PathBuf::from(&path);
+    let path_buf = paths::from_ipc(&path);
 
     // Verify the path exists and is a directory
     if !path_buf.exists() {
@@ -2922,92 +6757,393 @@ pub async fn open_in_terminal(
     Ok(())
 }
 
-/// Opens a file in the specified editor (Cursor or VSCode).
+/// Shows a file or folder in the system file manager (Finder, Explorer, or
+/// the default Linux file manager), selecting it where the platform allows.
+///
+/// Only paths inside a registered project or `~/.bluekit` can be revealed.
+/// On Linux the file is selected through the `org.freedesktop.FileManager1`
+/// D-Bus interface when a file manager provides it; otherwise its folder is
+/// opened with xdg-open.
+#[tauri::command]
+pub async fn reveal_in_file_manager(
+    db: State<'_, DatabaseConnection>,
+    path: String,
+) -> Result<(), String> {
+    use std::process::Command;
+
+    let target = paths::from_ipc(&path);
+    if !target.exists() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+    if !is_in_known_location(&db, &target).await? {
+        return Err(format!("Path is not inside a registered project or ~/.bluekit: {}", path));
+    }
+
+    #[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
+    if show_item_over_dbus(&target).await {
+        return Ok(());
+    }
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg("-R").arg(&target);
+        command
+    };
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        // Explorer wants "/select,<path>" as a single argument
+        let mut select = std::ffi::OsString::from("/select,");
+        select.push(target.as_os_str());
+        let mut command = Command::new("explorer");
+        command.arg(select);
+        command
+    };
+
+    // xdg-open can't select a file, so open the folder containing it
+    #[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
+    let mut command = {
+        let folder = if target.is_dir() {
+            target.clone()
+        } else {
+            target.parent().map(|p| p.to_path_buf()).unwrap_or(target.clone())
+        };
+        let mut command = Command::new("xdg-open");
+        command.arg(folder);
+        command
+    };
+
+    // Explorer exits non-zero even on success, so only spawn failures count
+    command
+        .spawn()
+        .map_err(|e| format!("Failed to open file manager: {}", e))?;
+
+    Ok(())
+}
+
+/// Whether `path` is inside `~/.bluekit`, the data directory or a project
+/// registered in the database, after resolving symlinks and `..`.
+async fn is_in_known_location(db: &DatabaseConnection, path: &std::path::Path) -> Result<bool, String> {
+    use sea_orm::EntityTrait;
+
+    let canonical = |p: &std::path::Path| std::fs::canonicalize(p).ok();
+    let Some(target) = canonical(path) else {
+        return Ok(false);
+    };
+
+    let bluekit_home = crate::core::settings::settings_path()?
+        .parent()
+        .and_then(canonical);
+    if bluekit_home.is_some_and(|home| target.starts_with(home)) {
+        return Ok(true);
+    }
+    let data_dir = crate::core::data_dir::data_dir().ok().and_then(|dir| canonical(&dir));
+    if data_dir.is_some_and(|dir| target.starts_with(dir)) {
+        return Ok(true);
+    }
+
+    let projects = crate::db::entities::project::Entity::find()
+        .all(db)
+        .await
+        .map_err(|e| format!("Failed to get projects: {}", e))?;
+    Ok(projects
+        .iter()
+        .filter_map(|project| canonical(&paths::from_ipc(&project.path)))
+        .any(|root| target.starts_with(root)))
+}
+
+/// How long to wait for a file manager to answer on D-Bus
+#[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
+const DBUS_REPLY_TIMEOUT_MS: u32 = 2000;
+
+/// Asks the desktop's file manager to show `path` selected. Returns false if
+/// no file manager answers on D-Bus within [`DBUS_REPLY_TIMEOUT_MS`].
+#[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
+async fn show_item_over_dbus(path: &std::path::Path) -> bool {
+    // file:// URI with everything but the separators percent-encoded
+    let uri = format!(
+        "file://{}",
+        urlencoding::encode(&path.to_string_lossy()).replace("%2F", "/")
+    );
+    tokio::process::Command::new("dbus-send")
+        .args([
+            "--session",
+            "--print-reply",
+            &format!("--reply-timeout={}", DBUS_REPLY_TIMEOUT_MS),
+            "--dest=org.freedesktop.FileManager1",
+            "--type=method_call",
+            "/org/freedesktop/FileManager1",
+            "org.freedesktop.FileManager1.ShowItems",
+        ])
+        .arg(format!("array:string:{}", uri))
+        .arg("string:")
+        .kill_on_drop(true)
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Opens a file in an editor, optionally at a line.
 ///
-/// Similar to `open_project_in_editor`, but opens a specific file instead of a directory.
+/// `editor` picks the editor for this call; without it the `editor.editor`
+/// setting is used. Editors are VS Code, Cursor, Claude, vim, the command in
+/// `$VISUAL`/`$EDITOR` ("env") or the custom command from the settings. If
+/// the editor can't be launched (e.g. VS Code's `code` shim isn't on the
+/// PATH of an app started from the Dock), platform fallbacks are tried: the
+/// app bundle via `open -a` on macOS, the installed `Code.exe`/`Cursor.exe`
+/// on Windows, and finally the system's default app for the file. Nothing is
+/// run through `cmd`, so paths can't inject shell commands.
 ///
 /// # Arguments
 /// * `file_path` - Absolute path to the file to open
-/// * `editor` - The editor to use: 'cursor' or 'vscode'
-///
-/// # Returns
-/// * `Ok(())` if the file was opened successfully
-/// * `Err(String)` if the file doesn't exist, is not a file, or the editor failed to open
+/// * `editor` - 'vscode', 'cursor', 'claude', 'vim', 'env' or 'custom'
+/// * `line` - Optional 1-based line number to jump to
 ///
 /// # Examples
 /// ```typescript
-/// await invoke('open_file_in_editor', {
-///   filePath: '/path/to/file.md',
-///   editor: 'cursor'
-/// });
+/// await invoke('open_file_in_editor', { filePath: '/path/to/kit.md', editor: 'cursor' });
+/// await invoke('open_file_in_editor', { filePath: '/path/to/kit.md', line: 12 });
 /// ```
 #[tauri::command]
 pub async fn open_file_in_editor(
     file_path: String,
-    editor: String,
+    editor: Option<String>,
+    line: Option<u32>,
 ) -> Result<(), String> {
+    use std::ffi::OsString;
     use std::process::Command;
 
-    let path = PathBuf::from(&file_path);
-
-    // Verify the file path exists
-    if !path.exists() {
+    let target = paths::from_ipc(&file_path);
+    if !target.exists() {
         return Err(format!("File path does not exist: {}", file_path));
     }
-
-    if !path.is_file() {
+    if !target.is_file() {
         return Err(format!("Path is not a file: {}", file_path));
     }
 
-    // Determine the command based on the editor
-    let (cmd, args) = match editor.as_str() {
-        "cursor" => {
-            #[cfg(target_os = "macos")]
-            {
-                ("open", vec!["-a", "Cursor", &file_path])
+    let mut editor_settings = crate::core::settings::load_settings().editor;
+    if let Some(editor) = editor.filter(|e| !e.trim().is_empty()) {
+        editor_settings.editor = editor;
+    }
+    let editor = editor_settings;
+    let target_os: OsString = target.clone().into_os_string();
+
+    // Builds "path:line" for editors that accept --goto
+    let goto_arg = |line: u32| {
+        let mut arg = target_os.clone();
+        arg.push(format!(":{}", line));
+        arg
+    };
+
+    let (program, args): (String, Vec<OsString>) = match editor.editor.as_str() {
+        "env" => {
+            let command = std::env::var("VISUAL")
+                .or_else(|_| std::env::var("EDITOR"))
+                .ok()
+                .filter(|c| !c.trim().is_empty())
+                .ok_or_else(|| "Neither $VISUAL nor $EDITOR is set".to_string())?;
+            let mut parts: Vec<OsString> = command.split_whitespace().map(OsString::from).collect();
+            let program = parts.remove(0).to_string_lossy().into_owned();
+            let program_name = paths::file_stem_lossy(std::path::Path::new(&program));
+
+            if matches!(program_name.as_str(), "code" | "cursor") {
+                // GUI editors take --goto and don't need a terminal
+                match line {
+                    Some(line) => parts.extend(["--goto".into(), goto_arg(line)]),
+                    None => parts.push(target_os.clone()),
+                }
+                (program, parts)
+            } else {
+                let mut terminal_args: Vec<OsString> = vec![program.into()];
+                terminal_args.extend(parts);
+                if let Some(line) = line {
+                    // vi, vim, nvim, nano, emacs and micro all take +LINE
+                    terminal_args.push(format!("+{}", line).into());
+                }
+                terminal_args.push(target_os.clone());
+                terminal_command(terminal_args)
             }
-            #[cfg(not(target_os = "macos"))]
-            {
-                ("cursor", vec![&file_path])
+        }
+        "vscode" | "code" | "cursor" => {
+            let program = if editor.editor == "cursor" { "cursor" } else { "code" };
+            match line {
+                Some(line) => (program.to_string(), vec!["--goto".into(), goto_arg(line)]),
+                None => (program.to_string(), vec![target_os.clone()]),
             }
         }
         "claude" => {
             #[cfg(target_os = "macos")]
             {
-                ("open", vec!["-a", "Claude", &file_path])
+                ("open".to_string(), vec!["-a".into(), "Claude".into(), target_os.clone()])
             }
             #[cfg(not(target_os = "macos"))]
             {
-                ("claude", vec![&file_path])
+                ("claude".to_string(), vec![target_os.clone()])
             }
         }
-        "vscode" | "code" => {
-            #[cfg(target_os = "macos")]
-            {
-                ("open", vec!["-a", "Visual Studio Code", &file_path])
-            }
-            #[cfg(not(target_os = "macos"))]
-            {
-                ("code", vec![&file_path])
+        "vim" => {
+            let mut vim_args: Vec<OsString> = vec!["vim".into()];
+            if let Some(line) = line {
+                vim_args.push(format!("+{}", line).into());
             }
+            vim_args.push(target_os.clone());
+            terminal_command(vim_args)
         }
-        _ => {
-            return Err(format!("Unknown editor: {}. Supported editors: 'cursor', 'claude', 'vscode'", editor));
+        "custom" => {
+            let template = editor
+                .custom_command
+                .as_deref()
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .ok_or_else(|| "No custom editor command configured".to_string())?;
+
+            let mut parts = template.split_whitespace().map(|part| {
+                let part = part.replace("{line}", &line.unwrap_or(1).to_string());
+                // Substitute the path as an OsString so non-UTF-8 paths survive
+                match part.split_once("{path}") {
+                    Some((before, after)) => {
+                        let mut arg = OsString::from(before);
+                        arg.push(&target_os);
+                        arg.push(after);
+                        arg
+                    }
+                    None => OsString::from(part),
+                }
+            });
+            let program = parts
+                .next()
+                .map(|p| p.to_string_lossy().into_owned())
+                .ok_or_else(|| "No custom editor command configured".to_string())?;
+            (program, parts.collect())
+        }
+        other => {
+            return Err(format!(
+                "Unknown editor: {}. Supported editors: 'vscode', 'cursor', 'claude', 'vim', 'env', 'custom'",
+                other
+            ));
         }
     };
 
-    // Execute the command
-    let output = Command::new(cmd)
-        .args(&args)
-        .output()
-        .map_err(|e| format!("Failed to execute command: {}", e))?;
+    let mut candidates = vec![(program, args)];
+    candidates.extend(editor_fallbacks(&editor.editor, &target_os, line));
+    candidates.push(default_opener(&target_os));
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to open file in {}: {}", editor, stderr));
+    // Terminal editors keep running, so don't wait for the process
+    let mut first_error = None;
+    for (program, args) in candidates {
+        let mut command = Command::new(&program);
+        command.args(&args);
+        #[cfg(target_os = "windows")]
+        {
+            // Give terminal editors a console of their own
+            use std::os::windows::process::CommandExt;
+            const CREATE_NEW_CONSOLE: u32 = 0x0000_0010;
+            command.creation_flags(CREATE_NEW_CONSOLE);
+        }
+        match command.spawn() {
+            Ok(_) => return Ok(()),
+            Err(e) => {
+                tracing::warn!("Failed to launch {}: {}", program, e);
+                first_error.get_or_insert(format!("Failed to launch {}: {}", program, e));
+            }
+        }
+    }
+    Err(first_error.unwrap_or_else(|| "Failed to launch editor".to_string()))
+}
+
+/// Other ways to launch VS Code or Cursor when their command isn't found.
+#[allow(unused_variables)]
+fn editor_fallbacks(
+    editor: &str,
+    target: &std::ffi::OsStr,
+    line: Option<u32>,
+) -> Vec<(String, Vec<std::ffi::OsString>)> {
+    if !matches!(editor, "vscode" | "code" | "cursor") {
+        return Vec::new();
     }
 
-    Ok(())
+    #[cfg(target_os = "macos")]
+    {
+        // The app bundle is there even if the shell command was never installed
+        let app = if editor == "cursor" { "Cursor" } else { "Visual Studio Code" };
+        vec![("open".to_string(), vec!["-a".into(), app.into(), target.to_os_string()])]
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // `code` and `cursor` are .cmd shims, which only cmd can run, so
+        // launch the executable from the per-user install instead
+        let Some(local_app_data) = std::env::var_os("LOCALAPPDATA") else {
+            return Vec::new();
+        };
+        let exe = if editor == "cursor" {
+            std::path::Path::new(&local_app_data).join(r"Programs\cursor\Cursor.exe")
+        } else {
+            std::path::Path::new(&local_app_data).join(r"Programs\Microsoft VS Code\Code.exe")
+        };
+        let mut args: Vec<std::ffi::OsString> = Vec::new();
+        match line {
+            Some(line) => {
+                let mut goto = target.to_os_string();
+                goto.push(format!(":{}", line));
+                args.extend(["--goto".into(), goto]);
+            }
+            None => args.push(target.to_os_string()),
+        }
+        vec![(exe.to_string_lossy().into_owned(), args)]
+    }
+    #[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
+    {
+        Vec::new()
+    }
+}
+
+/// Opens a file with the system's default app, the last resort for
+/// `open_file_in_editor`.
+fn default_opener(target: &std::ffi::OsStr) -> (String, Vec<std::ffi::OsString>) {
+    #[cfg(target_os = "macos")]
+    {
+        ("open".to_string(), vec![target.to_os_string()])
+    }
+    #[cfg(target_os = "windows")]
+    {
+        ("explorer".to_string(), vec![target.to_os_string()])
+    }
+    #[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
+    {
+        ("xdg-open".to_string(), vec![target.to_os_string()])
+    }
+}
+
+/// Wraps a command so it runs in a new terminal window (for terminal editors).
+fn terminal_command(command: Vec<std::ffi::OsString>) -> (String, Vec<std::ffi::OsString>) {
+    #[cfg(target_os = "macos")]
+    {
+        // Terminal.app only takes a script, so quote each argument for the shell
+        let script = command
+            .iter()
+            .map(|arg| format!("'{}'", arg.to_string_lossy().replace('\'', "'\\''")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let apple_script = format!(
+            "tell application \"Terminal\" to do script \"{}\"",
+            script.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        ("osascript".to_string(), vec!["-e".into(), apple_script.into()])
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // Run it directly; `open_file_in_editor` gives it a new console
+        let mut command = command.into_iter();
+        let program = command.next().unwrap_or_default().to_string_lossy().into_owned();
+        (program, command.collect())
+    }
+    #[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
+    {
+        let mut args: Vec<std::ffi::OsString> = vec!["-e".into()];
+        args.extend(command);
+        ("x-terminal-emulator".to_string(), args)
+    }
 }
 
 /// Opens HTML content in the default browser.
@@ -3069,7 +7205,8 @@ pub async fn open_html_in_browser(
 
     #[cfg(target_os = "windows")]
     let status = Command::new("cmd")
-        .args(&["/C", "start", "", temp_path.to_str().unwrap()])
+        .args(["/C", "start", ""])
+        .arg(&temp_path)
         .status()
         .map_err(|e| format!("Failed to open browser: {}", e))?;
 
@@ -3716,12 +7853,11 @@ pub async fn scan_project_resources(
     db: State<'_, DatabaseConnection>,
 ) -> Result<serde_json::Value, String> {
     use crate::library::resource_scanner;
-    use std::path::Path;
 
     let result = resource_scanner::scan_project_resources(
         &db,
         &project_id,
-        Path::new(&project_path),
+        &paths::from_ipc(&project_path),
     ).await?;
 
     Ok(serde_json::json!({
@@ -3841,13 +7977,59 @@ pub async fn publish_resource(
     serde_json::to_value(&result).map_err(|e| format!("Serialization error: {}", e))
 }
 
+/// Publish several resources to a workspace as new variations
+///
+/// Emits `operation-progress` events under `operation_id` (generated if not
+/// given) and returns one outcome per resource. Can be stopped with
+/// `cancel_operation`.
+#[tauri::command]
+pub async fn publish_resources(
+    app_handle: AppHandle,
+    resource_ids: Vec<String>,
+    workspace_id: String,
+    version_tag: Option<String>,
+    operation_id: Option<String>,
+    db: State<'_, DatabaseConnection>,
+) -> Result<Vec<crate::library::publishing::BulkPublishItem>, String> {
+    let progress = ProgressReporter::new(app_handle, "bulk_publish", operation_id);
+    let items = crate::library::publishing::publish_resources(
+        db.inner(),
+        resource_ids,
+        &workspace_id,
+        version_tag,
+        &progress,
+    )
+    .await;
+
+    if progress.is_cancelled() {
+        progress.fail(CANCELLED_ERROR.to_string());
+        return Ok(items);
+    }
+    let failed = items.iter().filter(|item| item.error.is_some()).count();
+    progress.complete(format!("Published {} of {} resource(s)", items.len() - failed, items.len()));
+    Ok(items)
+}
+
 /// Sync workspace catalog from GitHub
+///
+/// Emits `operation-progress` events under `operation_id` (generated if not
+/// given). Can be stopped with `cancel_operation`.
 #[tauri::command]
 pub async fn sync_workspace_catalog(
+    app_handle: AppHandle,
     workspace_id: String,
+    operation_id: Option<String>,
     db: State<'_, DatabaseConnection>,
 ) -> Result<serde_json::Value, String> {
-    let result = crate::library::sync::sync_workspace_catalog(db.inner(), &workspace_id).await?;
+    let progress = ProgressReporter::new(app_handle, "library_sync", operation_id);
+    let result = crate::library::sync::sync_workspace_catalog(db.inner(), &workspace_id, &progress)
+        .await
+        .map_err(|e| progress.fail(e))?;
+    progress.complete(format!(
+        "Synced {} catalog(s) and {} variation(s)",
+        result.catalogs_created + result.catalogs_updated,
+        result.variations_created + result.variations_updated
+    ));
     serde_json::to_value(&result).map_err(|e| format!("Serialization error: {}", e))
 }
 
@@ -3861,13 +8043,75 @@ pub async fn list_workspace_catalogs(
     crate::library::sync::list_workspace_catalogs(db.inner(), &workspace_id, artifact_type).await
 }
 
+/// Get a workspace's activity feed (publishes, catalog and folder changes)
+///
+/// `github_token` adds BlueKit-authored commits from the workspace repo,
+/// which are the only record of deletions and folder changes.
+#[tauri::command]
+pub async fn get_workspace_activity(
+    workspace_id: String,
+    range: Option<crate::library::activity::ActivityRange>,
+    github_token: Option<String>,
+    db: State<'_, DatabaseConnection>,
+) -> Result<crate::library::activity::WorkspaceActivity, String> {
+    crate::library::activity::get_workspace_activity(
+        db.inner(),
+        &workspace_id,
+        range.unwrap_or_default(),
+        github_token,
+    )
+    .await
+}
+
+/// Search GitHub for public BlueKit libraries and list their catalogs
+#[tauri::command]
+pub async fn browse_public_libraries(
+    access_token: String,
+    query: Option<String>,
+) -> Result<Vec<crate::library::discovery::PublicLibrary>, String> {
+    crate::library::discovery::browse_public_libraries(access_token, query.as_deref().unwrap_or(""))
+        .await
+}
+
+/// Pull an artifact from a public library into a project (no subscription)
+#[tauri::command]
+pub async fn pull_public_artifact(
+    access_token: String,
+    owner: String,
+    repo: String,
+    path: String,
+    target_project_path: String,
+    overwrite_if_exists: bool,
+) -> Result<String, String> {
+    crate::library::discovery::pull_public_artifact(
+        access_token,
+        &owner,
+        &repo,
+        &path,
+        &target_project_path,
+        overwrite_if_exists,
+    )
+    .await
+}
+
 /// Delete catalogs and their variations from workspace
+///
+/// With `dry_run`, returns the remote files and rows that would be deleted
+/// instead of the deleted count.
 #[tauri::command]
 pub async fn delete_catalogs(
     catalog_ids: Vec<String>,
+    dry_run: Option<bool>,
     db: State<'_, DatabaseConnection>,
-) -> Result<u32, String> {
-    crate::library::sync::delete_catalogs(db.inner(), catalog_ids).await
+) -> Result<DryRunOutcome<u32>, String> {
+    if dry_run.unwrap_or(false) {
+        return crate::library::sync::preview_delete_catalogs(db.inner(), &catalog_ids)
+            .await
+            .map(DryRunOutcome::DryRun);
+    }
+    crate::library::sync::delete_catalogs(db.inner(), catalog_ids)
+        .await
+        .map(DryRunOutcome::Applied)
 }
 
 /// Pull a variation to a local project
@@ -3877,6 +8121,7 @@ pub async fn pull_variation(
     target_project_id: String,
     target_project_path: String,
     overwrite_if_exists: bool,
+    resolved_content: Option<String>,
     db: State<'_, DatabaseConnection>,
 ) -> Result<serde_json::Value, String> {
     let options = crate::library::pull::PullOptions {
@@ -3884,12 +8129,49 @@ pub async fn pull_variation(
         target_project_id,
         target_project_path,
         overwrite_if_exists,
+        resolved_content,
     };
 
     let result = crate::library::pull::pull_variation(db.inner(), options).await?;
     serde_json::to_value(&result).map_err(|e| format!("Serialization error: {}", e))
 }
 
+/// Pull several variations into a local project
+///
+/// Emits `operation-progress` events under `operation_id` (generated if not
+/// given) and returns one outcome per variation. Conflicting merges are
+/// reported per variation and left for the single-variation pull to resolve.
+/// Can be stopped with `cancel_operation`.
+#[tauri::command]
+pub async fn pull_variations(
+    app_handle: AppHandle,
+    variation_ids: Vec<String>,
+    target_project_id: String,
+    target_project_path: String,
+    overwrite_if_exists: bool,
+    operation_id: Option<String>,
+    db: State<'_, DatabaseConnection>,
+) -> Result<Vec<crate::library::pull::BulkPullItem>, String> {
+    let progress = ProgressReporter::new(app_handle, "bulk_pull", operation_id);
+    let items = crate::library::pull::pull_variations(
+        db.inner(),
+        variation_ids,
+        &target_project_id,
+        &target_project_path,
+        overwrite_if_exists,
+        &progress,
+    )
+    .await;
+
+    if progress.is_cancelled() {
+        progress.fail(CANCELLED_ERROR.to_string());
+        return Ok(items);
+    }
+    let failed = items.iter().filter(|item| item.error.is_some()).count();
+    progress.complete(format!("Pulled {} of {} variation(s)", items.len() - failed, items.len()));
+    Ok(items)
+}
+
 /// Check resource status for unpublished changes and available updates
 #[tauri::command]
 pub async fn check_resource_status(
@@ -3924,18 +8206,152 @@ pub async fn migrate_projects_to_database(
         .map_err(|e| format!("Migration failed: {}", e))
 }
 
-/// Gets all projects from database
+/// Checks projectRegistry.json for malformed entries, missing project
+/// directories, duplicate ids and relative paths.
+///
+/// With `repair`, the registry is backed up and rewritten without the
+/// broken entries (relative paths that still resolve are made absolute).
 #[tauri::command]
-pub async fn db_get_projects(
+pub async fn validate_project_registry(
+    repair: Option<bool>,
+) -> Result<crate::core::registry::RegistryReport, String> {
+    crate::core::registry::validate_registry(repair.unwrap_or(false)).await
+}
+
+/// Removes a project from projectRegistry.json. A database project with the
+/// same id is archived, since the registry lists active projects and would
+/// otherwise get the entry back on the next sync.
+#[tauri::command]
+pub async fn remove_project_from_registry(
     db: State<'_, DatabaseConnection>,
-) -> Result<Vec<crate::db::entities::project::Model>, String> {
+    project_id: String,
+) -> Result<(), String> {
     use sea_orm::EntityTrait;
-    crate::db::entities::project::Entity::find()
-        .all(&*db)
+
+    crate::core::registry::remove_entry(&project_id).await?;
+
+    let in_db = crate::db::entities::project::Entity::find_by_id(&project_id)
+        .one(db.inner())
+        .await
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .is_some();
+    if in_db {
+        crate::db::project_operations::set_project_archived(db.inner(), &project_id, true)
+            .await
+            .map_err(|e| format!("Failed to archive project: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Updates a project's title, description and/or path in
+/// projectRegistry.json, and in the database project with the same id.
+/// Omitted fields are left unchanged.
+#[tauri::command]
+pub async fn update_project_entry(
+    db: State<'_, DatabaseConnection>,
+    project_id: String,
+    title: Option<String>,
+    description: Option<String>,
+    path: Option<String>,
+) -> Result<ProjectEntry, String> {
+    use sea_orm::*;
+
+    let entry = crate::core::registry::update_entry(&project_id, title.clone(), description.clone(), path.clone()).await?;
+
+    let project = crate::db::entities::project::Entity::find_by_id(&project_id)
+        .one(db.inner())
+        .await
+        .map_err(|e| format!("Failed to fetch project: {}", e))?;
+    if let Some(project) = project {
+        let mut active_model: crate::db::entities::project::ActiveModel = project.into();
+        if let Some(title) = title {
+            active_model.name = Set(title);
+        }
+        if let Some(description) = description {
+            active_model.description = Set(Some(description).filter(|d| !d.is_empty()));
+        }
+        if let Some(path) = path {
+            active_model.path = Set(path);
+        }
+        active_model.updated_at = Set(chrono::Utc::now().timestamp_millis());
+        active_model
+            .update(db.inner())
+            .await
+            .map_err(|e| format!("Failed to update project: {}", e))?;
+    }
+    Ok(entry)
+}
+
+/// Brings projectRegistry.json in line with the projects database, which
+/// is canonical. `get_projects` does this on every call.
+#[tauri::command]
+pub async fn sync_project_registry(
+    db: State<'_, DatabaseConnection>,
+) -> Result<crate::db::project_operations::RegistrySyncSummary, String> {
+    crate::db::project_operations::sync_registry(db.inner())
+        .await
+        .map_err(|e| format!("Failed to sync project registry: {}", e))
+}
+
+/// The canonical project list. Syncs projectRegistry.json with the
+/// database first, so projects registered either way are included, then
+/// returns them in their manual order. Archived projects are left out
+/// unless `include_archived` is set.
+#[tauri::command]
+pub async fn get_projects(
+    db: State<'_, DatabaseConnection>,
+    include_archived: Option<bool>,
+) -> Result<Vec<crate::db::entities::project::Model>, String> {
+    crate::db::project_operations::get_projects(db.inner(), include_archived.unwrap_or(false))
         .await
         .map_err(|e| format!("Failed to get projects: {}", e))
 }
 
+/// Gets all projects from database in their manual order. Same as
+/// `get_projects`, kept for existing callers.
+#[tauri::command]
+pub async fn db_get_projects(
+    db: State<'_, DatabaseConnection>,
+    include_archived: Option<bool>,
+) -> Result<Vec<crate::db::entities::project::Model>, String> {
+    get_projects(db, include_archived).await
+}
+
+/// Archives a project, hiding it (and tasks that only belong to it) from
+/// project and task listings without deleting anything.
+#[tauri::command]
+pub async fn archive_project(
+    db: State<'_, DatabaseConnection>,
+    project_id: String,
+) -> Result<crate::db::entities::project::Model, String> {
+    crate::db::project_operations::set_project_archived(&db, &project_id, true)
+        .await
+        .map_err(|e| format!("Failed to archive project: {}", e))
+}
+
+/// Restores an archived project to the project list.
+#[tauri::command]
+pub async fn unarchive_project(
+    db: State<'_, DatabaseConnection>,
+    project_id: String,
+) -> Result<crate::db::entities::project::Model, String> {
+    crate::db::project_operations::set_project_archived(&db, &project_id, false)
+        .await
+        .map_err(|e| format!("Failed to unarchive project: {}", e))
+}
+
+/// Stores the user's arrangement of the project list. Listed projects come
+/// first, in the given order; any others follow in their previous order.
+#[tauri::command]
+pub async fn reorder_projects(
+    db: State<'_, DatabaseConnection>,
+    project_ids: Vec<String>,
+) -> Result<Vec<crate::db::entities::project::Model>, String> {
+    crate::db::project_operations::reorder_projects(&db, &project_ids)
+        .await
+        .map_err(|e| format!("Failed to reorder projects: {}", e))
+}
+
 /// Creates a new project in database
 #[tauri::command]
 pub async fn db_create_project(
@@ -3971,6 +8387,8 @@ pub async fn db_create_project(
         updated_at: Set(now),
         last_opened_at: Set(None),
         is_vault: Set(is_vault.unwrap_or(false)),
+        sort_order: Set(None),
+        archived: Set(false),
     };
 
     project.insert(&*db).await
@@ -4017,7 +8435,7 @@ pub async fn db_update_project(
         .map_err(|e| format!("Failed to update project: {}", e))
 }
 
-/// Deletes a project from the database
+/// Deletes a project from the database and projectRegistry.json
 #[tauri::command]
 pub async fn db_delete_project(
     db: State<'_, DatabaseConnection>,
@@ -4025,11 +8443,14 @@ pub async fn db_delete_project(
 ) -> Result<(), String> {
     use sea_orm::*;
 
-    crate::db::entities::project::Entity::delete_by_id(project_id)
+    crate::db::entities::project::Entity::delete_by_id(&project_id)
         .exec(&*db)
         .await
-        .map_err(|e| format!("Failed to delete project: {}", e))
-        .map(|_| ())
+        .map_err(|e| format!("Failed to delete project: {}", e))?;
+
+    // Otherwise the next registry sync would import the project again
+    let removals = std::collections::HashSet::from([project_id]);
+    crate::core::registry::apply_changes(Vec::new(), &removals).await
 }
 
 /// Gets the vault project if it exists
@@ -4157,6 +8578,17 @@ pub async fn list_project_worktrees(
     crate::integrations::git::list_git_worktrees(&project.path)
 }
 
+/// Installs a pre-commit hook that validates staged `.bluekit` artifacts.
+///
+/// The hook runs BlueKit headlessly (`validate --staged`) and blocks the
+/// commit if front matter or blueprint files are broken.
+#[tauri::command]
+pub async fn install_git_hooks(
+    project_path: String,
+) -> Result<crate::integrations::git::GitHookInstallResult, String> {
+    crate::integrations::git::install_pre_commit_hook(&paths::from_ipc(&project_path))
+}
+
 /// Opens a worktree in a new window.
 /// 
 /// Creates a new Tauri window displaying the worktree as an ephemeral project.
@@ -4494,62 +8926,21 @@ pub async fn pin_checkpoint(
     git_url: Option<String>,
     tags: Option<Vec<String>>,
 ) -> Result<checkpoint::Model, String> {
-    use sea_orm::*;
+    use crate::db::checkpoint_operations::{self, NewCheckpoint};
 
-    // Validate checkpoint type
-    let valid_types = ["milestone", "experiment", "template", "backup"];
-    if !valid_types.contains(&checkpoint_type.as_str()) {
-        return Err(format!("Invalid checkpoint type: {}. Must be one of: milestone, experiment, template, backup", checkpoint_type));
-    }
-
-    // Check if checkpoint already exists for this commit
-    let existing = checkpoint::Entity::find()
-        .filter(checkpoint::Column::ProjectId.eq(&project_id))
-        .filter(checkpoint::Column::GitCommitSha.eq(&git_commit_sha))
-        .one(&*db)
-        .await
-        .map_err(|e| format!("Database error: {}", e))?;
-
-    if existing.is_some() {
-        return Err("This commit is already pinned as a checkpoint".to_string());
-    }
-
-    // Generate checkpoint ID
-    let checkpoint_id = format!("checkpoint-{}-{}", project_id, Utc::now().timestamp_millis());
-
-    // Serialize tags to JSON
-    let tags_json = tags.and_then(|t| {
-        if t.is_empty() {
-            None
-        } else {
-            serde_json::to_string(&t).ok()
-        }
-    });
-
-    let now = Utc::now().timestamp_millis();
-
-    let checkpoint = checkpoint::ActiveModel {
-        id: Set(checkpoint_id),
-        project_id: Set(project_id),
-        git_commit_sha: Set(git_commit_sha),
-        git_branch: Set(git_branch),
-        git_url: Set(git_url),
-        name: Set(name),
-        description: Set(description),
-        tags: Set(tags_json),
-        checkpoint_type: Set(checkpoint_type),
-        parent_checkpoint_id: Set(None), // Lineage tracking deferred to Phase 4
-        created_from_project_id: Set(None),
-        pinned_at: Set(now),
-        created_at: Set(now),
-        updated_at: Set(now),
+    let new = NewCheckpoint {
+        project_id,
+        git_commit_sha,
+        git_branch,
+        git_url,
+        name,
+        checkpoint_type,
+        description,
+        tags,
     };
-
-    let result = checkpoint.insert(&*db)
+    checkpoint_operations::pin_commit(db.inner(), new)
         .await
-        .map_err(|e| format!("Failed to create checkpoint: {}", e))?;
-
-    Ok(result)
+        .map_err(|e| format!("Failed to create checkpoint: {}", e))
 }
 
 /// Get all checkpoints for a project.
@@ -4558,16 +8949,9 @@ pub async fn get_project_checkpoints(
     db: State<'_, DatabaseConnection>,
     project_id: String,
 ) -> Result<Vec<checkpoint::Model>, String> {
-    use sea_orm::*;
-
-    let checkpoints = checkpoint::Entity::find()
-        .filter(checkpoint::Column::ProjectId.eq(&project_id))
-        .order_by_desc(checkpoint::Column::PinnedAt)
-        .all(&*db)
+    crate::db::checkpoint_operations::list_checkpoints(db.inner(), &project_id)
         .await
-        .map_err(|e| format!("Database error: {}", e))?;
-
-    Ok(checkpoints)
+        .map_err(|e| format!("Database error: {}", e))
 }
 
 /// Unpin a checkpoint (delete it).
@@ -4576,20 +8960,118 @@ pub async fn unpin_checkpoint(
     db: State<'_, DatabaseConnection>,
     checkpoint_id: String,
 ) -> Result<(), String> {
-    use sea_orm::*;
+    crate::db::checkpoint_operations::delete_checkpoint(db.inner(), &checkpoint_id)
+        .await
+        .map_err(|e| format!("Failed to delete checkpoint: {}", e))
+}
 
-    let checkpoint = checkpoint::Entity::find_by_id(&checkpoint_id)
-        .one(&*db)
+/// Pin the project's current commit as a checkpoint.
+#[tauri::command]
+pub async fn create_checkpoint(
+    db: State<'_, DatabaseConnection>,
+    project_id: String,
+    name: String,
+    checkpoint_type: String,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<checkpoint::Model, String> {
+    crate::db::checkpoint_operations::create_checkpoint(
+        db.inner(),
+        &project_id,
+        name,
+        checkpoint_type,
+        description,
+        tags,
+    )
+    .await
+    .map_err(|e| format!("Failed to create checkpoint: {}", e))
+}
+
+/// List a project's checkpoints, newest first.
+#[tauri::command]
+pub async fn list_checkpoints(
+    db: State<'_, DatabaseConnection>,
+    project_id: String,
+) -> Result<Vec<checkpoint::Model>, String> {
+    crate::db::checkpoint_operations::list_checkpoints(db.inner(), &project_id)
         .await
-        .map_err(|e| format!("Database error: {}", e))?
-        .ok_or_else(|| "Checkpoint not found".to_string())?;
+        .map_err(|e| format!("Failed to list checkpoints: {}", e))
+}
 
-    checkpoint::Entity::delete_by_id(&checkpoint_id)
-        .exec(&*db)
+/// Update a checkpoint's name, type, description or tags.
+#[tauri::command]
+pub async fn update_checkpoint(
+    db: State<'_, DatabaseConnection>,
+    checkpoint_id: String,
+    name: Option<String>,
+    checkpoint_type: Option<String>,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<checkpoint::Model, String> {
+    crate::db::checkpoint_operations::update_checkpoint(
+        db.inner(),
+        &checkpoint_id,
+        name,
+        checkpoint_type,
+        description,
+        tags,
+    )
+    .await
+    .map_err(|e| format!("Failed to update checkpoint: {}", e))
+}
+
+/// Delete a checkpoint.
+#[tauri::command]
+pub async fn delete_checkpoint(
+    db: State<'_, DatabaseConnection>,
+    checkpoint_id: String,
+) -> Result<(), String> {
+    crate::db::checkpoint_operations::delete_checkpoint(db.inner(), &checkpoint_id)
+        .await
+        .map_err(|e| format!("Failed to delete checkpoint: {}", e))
+}
+
+/// Files and line counts changed between two checkpoints, or between a
+/// checkpoint and the project's current HEAD when `to_checkpoint_id` is omitted.
+#[tauri::command]
+pub async fn diff_checkpoints(
+    db: State<'_, DatabaseConnection>,
+    from_checkpoint_id: String,
+    to_checkpoint_id: Option<String>,
+) -> Result<crate::integrations::git::GitDiffSummary, String> {
+    crate::db::checkpoint_operations::diff_checkpoints(db.inner(), &from_checkpoint_id, to_checkpoint_id.as_deref())
         .await
-        .map_err(|e| format!("Failed to delete checkpoint: {}", e))?;
+        .map_err(|e| format!("Failed to diff checkpoints: {}", e))
+}
 
-    Ok(())
+/// Restore a checkpoint into a new working copy and register it as a project.
+///
+/// `mode` is "clone" (default) or "worktree"; `branch` names a new branch to
+/// check out instead of a detached HEAD.
+#[tauri::command]
+pub async fn restore_checkpoint(
+    db: State<'_, DatabaseConnection>,
+    checkpoint_id: String,
+    target_path: String,
+    mode: Option<String>,
+    project_title: Option<String>,
+    branch: Option<String>,
+) -> Result<crate::db::checkpoint_operations::RestoredCheckpoint, String> {
+    let target = paths::from_ipc(&target_path);
+    if !target.is_absolute() {
+        return Err(format!("Target path must be absolute: {}", target_path));
+    }
+
+    crate::db::checkpoint_operations::restore_checkpoint(
+        db.inner(),
+        &checkpoint_id,
+        &target,
+        mode.as_deref().unwrap_or("clone"),
+        project_title,
+        branch.as_deref(),
+    )
+    .await
+    .map_err(|e| format!("Failed to restore checkpoint: {}", e))
 }
 
 /// Create a new project from a checkpoint (reuses clone logic).
@@ -4604,7 +9086,6 @@ pub async fn create_project_from_checkpoint(
     use sea_orm::*;
     use std::fs;
     use std::process::Command;
-    use std::path::PathBuf;
 
     // Get checkpoint
     let checkpoint = checkpoint::Entity::find_by_id(&checkpoint_id)
@@ -4617,7 +9098,7 @@ pub async fn create_project_from_checkpoint(
         .ok_or_else(|| "Checkpoint has no git URL".to_string())?;
 
     // Validate target path
-    let target = PathBuf::from(&target_path);
+    let target = paths::from_ipc(&target_path);
     if target.exists() {
         return Err(format!("Target path already exists: {}", target_path));
     }
@@ -4691,7 +9172,7 @@ pub async fn create_project_from_checkpoint(
         })?;
 
     // Copy all files except .git using existing helper
-    copy_directory_excluding(&temp_dir, &target, &[".git"])
+    copy_directory_excluding(&temp_dir, &target, &[".git"], &mut |_| Ok(()))
         .map_err(|e| {
             cleanup_temp();
             format!("Failed to copy files: {}", e)
@@ -4722,6 +9203,8 @@ pub async fn create_project_from_checkpoint(
             updated_at: Set(now),
             last_opened_at: Set(None),
             is_vault: Set(false),
+            sort_order: Set(None),
+            archived: Set(false),
         };
         
         let _ = project_model.insert(&*db)
@@ -4759,17 +9242,83 @@ pub async fn create_plan(
     .map_err(|e| format!("Failed to create plan: {}", e))
 }
 
-/// Get all plans for a project
+/// Create a plan from a Claude/Cursor markdown plan file: `##` headings
+/// become phases, checklist items milestones. The file is linked to the
+/// new plan.
+#[tauri::command]
+pub async fn import_plan_from_markdown(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    project_id: String,
+    project_path: String,
+    file_path: String,
+    name: Option<String>,
+    source: Option<String>,
+) -> Result<crate::db::plan_operations::PlanDetailsDto, String> {
+    crate::db::plan_operations::import_plan_from_markdown(
+        db.inner(),
+        project_id,
+        project_path,
+        file_path,
+        name,
+        source,
+    )
+    .await
+    .map_err(|e| format!("Failed to import plan: {}", e))
+}
+
+/// Get all plans for a project. Archived plans are left out unless
+/// `include_archived` is set.
 #[tauri::command]
 pub async fn get_project_plans(
     db: State<'_, sea_orm::DatabaseConnection>,
     project_id: String,
+    include_archived: Option<bool>,
 ) -> Result<Vec<crate::db::plan_operations::PlanDto>, String> {
-    crate::db::plan_operations::get_project_plans(db.inner(), project_id)
+    crate::db::plan_operations::get_project_plans(db.inner(), project_id, include_archived.unwrap_or(false))
         .await
         .map_err(|e| format!("Failed to get project plans: {}", e))
 }
 
+/// Generate a mermaid diagram of a plan in `.bluekit/diagrams/`
+///
+/// `format` is "flowchart" (default) or "gantt". Regenerating overwrites the
+/// previous diagram.
+#[tauri::command]
+pub async fn generate_plan_diagram(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    plan_id: String,
+    format: Option<String>,
+) -> Result<crate::db::plan_operations::PlanDiagramDto, String> {
+    crate::db::plan_operations::generate_plan_diagram(db.inner(), plan_id, format)
+        .await
+        .map_err(|e| format!("Failed to generate plan diagram: {}", e))
+}
+
+/// Write a plan (phases, milestones, linked plans and document text) into
+/// its folder as a single `<name>.export.md` for sharing outside the app.
+/// Exporting again overwrites the file.
+#[tauri::command]
+pub async fn export_plan_markdown(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    plan_id: String,
+) -> Result<crate::db::plan_operations::PlanExportDto, String> {
+    crate::db::plan_operations::export_plan_markdown(db.inner(), plan_id)
+        .await
+        .map_err(|e| format!("Failed to export plan: {}", e))
+}
+
+/// Phase windows and milestone completion trend of a plan, shaped for a
+/// timeline or Gantt chart.
+#[tauri::command]
+pub async fn get_plan_timeline(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    plan_id: String,
+) -> Result<crate::db::plan_operations::PlanTimelineDto, String> {
+    crate::db::plan_operations::get_plan_timeline(db.inner(), plan_id)
+        .await
+        .map_err(|e| format!("Failed to get plan timeline: {}", e))
+}
+
 /// Get plan details with phases, milestones, and documents
 #[tauri::command]
 pub async fn get_plan_details(
@@ -4781,29 +9330,83 @@ pub async fn get_plan_details(
         .map_err(|e| format!("Failed to get plan details: {}", e))
 }
 
-/// Update a plan
+/// Update a plan
+#[tauri::command]
+pub async fn update_plan(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    plan_id: String,
+    name: Option<String>,
+    description: Option<Option<String>>,
+    status: Option<String>,
+) -> Result<crate::db::plan_operations::PlanDto, String> {
+    crate::db::plan_operations::update_plan(db.inner(), plan_id, name, description, status)
+        .await
+        .map_err(|e| format!("Failed to update plan: {}", e))
+}
+
+/// Copy a plan, its folder contents and its phases, milestones, documents
+/// and links under a new name. Progress is reset unless `keep_progress`.
+#[tauri::command]
+pub async fn duplicate_plan(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    plan_id: String,
+    name: Option<String>,
+    keep_progress: Option<bool>,
+) -> Result<crate::db::plan_operations::PlanDetailsDto, String> {
+    crate::db::plan_operations::duplicate_plan(db.inner(), plan_id, name, keep_progress.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to duplicate plan: {}", e))
+}
+
+/// Delete a plan. It can be restored with `restore_plan` until the trash
+/// retention period passes; with `permanent`, its folder and database
+/// records are removed right away.
+///
+/// With `dry_run`, returns the files and rows a permanent delete would
+/// remove instead.
+#[tauri::command]
+pub async fn delete_plan(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    plan_id: String,
+    dry_run: Option<bool>,
+    permanent: Option<bool>,
+) -> Result<DryRunOutcome<()>, String> {
+    if dry_run.unwrap_or(false) {
+        return crate::db::plan_operations::preview_delete_plan(db.inner(), &plan_id)
+            .await
+            .map(DryRunOutcome::DryRun)
+            .map_err(|e| format!("Failed to preview plan deletion: {}", e));
+    }
+    if permanent.unwrap_or(false) {
+        crate::db::plan_operations::purge_plan(db.inner(), plan_id).await
+    } else {
+        crate::db::plan_operations::delete_plan(db.inner(), &plan_id).await
+    }
+    .map(DryRunOutcome::Applied)
+    .map_err(|e| format!("Failed to delete plan: {}", e))
+}
+
+/// Restore a deleted plan
 #[tauri::command]
-pub async fn update_plan(
+pub async fn restore_plan(
     db: State<'_, sea_orm::DatabaseConnection>,
     plan_id: String,
-    name: Option<String>,
-    description: Option<Option<String>>,
-    status: Option<String>,
 ) -> Result<crate::db::plan_operations::PlanDto, String> {
-    crate::db::plan_operations::update_plan(db.inner(), plan_id, name, description, status)
+    crate::db::plan_operations::restore_plan(db.inner(), &plan_id)
         .await
-        .map_err(|e| format!("Failed to update plan: {}", e))
+        .map_err(|e| format!("Failed to restore plan: {}", e))
 }
 
-/// Delete a plan (removes folder and database records)
+/// Deleted plans of a project that can still be restored, most recently
+/// deleted first
 #[tauri::command]
-pub async fn delete_plan(
+pub async fn get_deleted_plans(
     db: State<'_, sea_orm::DatabaseConnection>,
-    plan_id: String,
-) -> Result<(), String> {
-    crate::db::plan_operations::delete_plan(db.inner(), plan_id)
+    project_id: String,
+) -> Result<Vec<crate::db::plan_operations::PlanDto>, String> {
+    crate::db::plan_operations::get_deleted_plans(db.inner(), project_id)
         .await
-        .map_err(|e| format!("Failed to delete plan: {}", e))
+        .map_err(|e| format!("Failed to get deleted plans: {}", e))
 }
 
 /// Link brainstorm plan to a plan
@@ -4862,6 +9465,7 @@ pub async fn create_plan_phase(
     name: String,
     description: Option<String>,
     order_index: i32,
+    target_date: Option<i64>,
 ) -> Result<crate::db::plan_operations::PlanPhaseDto, String> {
     crate::db::plan_operations::create_plan_phase(
         db.inner(),
@@ -4869,12 +9473,16 @@ pub async fn create_plan_phase(
         name,
         description,
         order_index,
+        target_date,
     )
     .await
     .map_err(|e| format!("Failed to create phase: {}", e))
 }
 
 /// Update a plan phase
+///
+/// A `null` argument arrives the same as an omitted one, so
+/// `clear_target_date` removes the target date.
 #[tauri::command]
 pub async fn update_plan_phase(
     db: State<'_, sea_orm::DatabaseConnection>,
@@ -4883,7 +9491,10 @@ pub async fn update_plan_phase(
     description: Option<Option<String>>,
     status: Option<String>,
     order_index: Option<i32>,
+    target_date: Option<Option<i64>>,
+    clear_target_date: Option<bool>,
 ) -> Result<crate::db::plan_operations::PlanPhaseDto, String> {
+    let target_date = if clear_target_date.unwrap_or(false) { Some(None) } else { target_date };
     crate::db::plan_operations::update_plan_phase(
         db.inner(),
         phase_id,
@@ -4891,11 +9502,24 @@ pub async fn update_plan_phase(
         description,
         status,
         order_index,
+        target_date,
     )
     .await
     .map_err(|e| format!("Failed to update phase: {}", e))
 }
 
+/// Phases that aren't completed and are past their target date, across the
+/// active plans of a project (or all projects when `project_id` is omitted)
+#[tauri::command]
+pub async fn get_at_risk_phases(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    project_id: Option<String>,
+) -> Result<Vec<crate::db::plan_operations::AtRiskPhaseDto>, String> {
+    crate::db::plan_operations::get_at_risk_phases(db.inner(), project_id)
+        .await
+        .map_err(|e| format!("Failed to get at-risk phases: {}", e))
+}
+
 /// Delete a plan phase
 #[tauri::command]
 pub async fn delete_plan_phase(
@@ -4927,6 +9551,7 @@ pub async fn create_plan_milestone(
     name: String,
     description: Option<String>,
     order_index: i32,
+    due_at: Option<i64>,
 ) -> Result<crate::db::plan_operations::PlanMilestoneDto, String> {
     crate::db::plan_operations::create_plan_milestone(
         db.inner(),
@@ -4934,12 +9559,16 @@ pub async fn create_plan_milestone(
         name,
         description,
         order_index,
+        due_at,
     )
     .await
     .map_err(|e| format!("Failed to create milestone: {}", e))
 }
 
 /// Update a plan milestone
+///
+/// A `null` argument arrives the same as an omitted one, so `clear_due_at`
+/// removes the due date.
 #[tauri::command]
 pub async fn update_plan_milestone(
     db: State<'_, sea_orm::DatabaseConnection>,
@@ -4947,13 +9576,17 @@ pub async fn update_plan_milestone(
     name: Option<String>,
     description: Option<Option<String>>,
     completed: Option<bool>,
+    due_at: Option<Option<i64>>,
+    clear_due_at: Option<bool>,
 ) -> Result<crate::db::plan_operations::PlanMilestoneDto, String> {
+    let due_at = if clear_due_at.unwrap_or(false) { Some(None) } else { due_at };
     crate::db::plan_operations::update_plan_milestone(
         db.inner(),
         milestone_id,
         name,
         description,
         completed,
+        due_at,
     )
     .await
     .map_err(|e| format!("Failed to update milestone: {}", e))
@@ -4989,7 +9622,7 @@ pub async fn get_plan_documents(
 ) -> Result<Vec<crate::db::plan_operations::PlanDocumentDto>, String> {
     crate::db::plan_operations::get_plan_documents(db.inner(), plan_id)
         .await
-        .map_err(|e| format!("Failed to get plan documents: {}", e))
+        .map_err(|e| crate::core::file_lock::command_error(e, "Failed to get plan documents"))
 }
 
 /// Link document to phase
@@ -5004,6 +9637,21 @@ pub async fn link_document_to_phase(
         .map_err(|e| format!("Failed to link document to phase: {}", e))
 }
 
+/// Move a document to a phase (or out of any with no `phase_id`) at
+/// `position` among that phase's documents; last when omitted. Returns the
+/// phase's documents in their new order.
+#[tauri::command]
+pub async fn move_document_to_phase(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    document_id: String,
+    phase_id: Option<String>,
+    position: Option<usize>,
+) -> Result<Vec<crate::db::plan_operations::PlanDocumentDto>, String> {
+    crate::db::plan_operations::move_document_to_phase(db.inner(), document_id, phase_id, position)
+        .await
+        .map_err(|e| format!("Failed to move document: {}", e))
+}
+
 /// Reorders plan documents
 #[tauri::command]
 pub async fn reorder_plan_documents(
@@ -5030,6 +9678,39 @@ pub async fn watch_plan_folder(
     crate::core::watcher::watch_directory(app, path, event_name)
 }
 
+/// Watch the plan's linked `~/.claude` / `~/.cursor` plan files and emit
+/// `plan-link-changed-{plan_id}` with the changed files whenever one is
+/// edited, replaced or deleted (`deleted: true` marks the link stale).
+///
+/// Call again after linking or unlinking plans to watch the new set; the
+/// previous watcher is replaced. Returns the links, stale ones marked.
+#[tauri::command]
+pub async fn watch_plan_links(
+    app: AppHandle,
+    db: State<'_, sea_orm::DatabaseConnection>,
+    plan_id: String,
+) -> Result<Vec<crate::db::plan_operations::PlanLinkDto>, String> {
+    use crate::core::watcher;
+
+    let links = crate::db::plan_operations::get_plan_links(db.inner(), &plan_id)
+        .await
+        .map_err(|e| format!("Failed to get plan links: {}", e))?;
+
+    let event_name = format!("plan-link-changed-{}", plan_id);
+    if watcher::watcher_exists(&event_name).await {
+        watcher::stop_watcher(&event_name).await?;
+    }
+
+    // A deleted file is watched too, so relinking it by recreating the
+    // file is noticed, as long as its directory is still there
+    let files: Vec<PathBuf> = links.iter().map(|l| paths::from_ipc(&l.linked_plan_path)).collect();
+    if files.iter().any(|f| f.parent().is_some_and(std::path::Path::is_dir)) {
+        watcher::watch_files(app, files, event_name)?;
+    }
+
+    Ok(links)
+}
+
 // ============================================================================
 // WALKTHROUGH COMMANDS
 // ============================================================================
@@ -5065,7 +9746,7 @@ pub async fn get_project_walkthroughs(
 ) -> Result<Vec<crate::db::walkthrough_operations::WalkthroughDto>, String> {
     crate::db::walkthrough_operations::get_project_walkthroughs(db.inner(), project_id, project_path)
         .await
-        .map_err(|e| format!("Failed to get project walkthroughs: {}", e))
+        .map_err(|e| crate::core::file_lock::command_error(e, "Failed to get project walkthroughs"))
 }
 
 /// Get or create a walkthrough by file path (for file-based walkthroughs)
@@ -5105,15 +9786,44 @@ pub async fn update_walkthrough(
         .map_err(|e| format!("Failed to update walkthrough: {}", e))
 }
 
-/// Delete a walkthrough (removes file and database records)
+/// Delete a walkthrough. It can be restored with `restore_walkthrough`
+/// until the trash retention period passes; with `permanent`, its file and
+/// database records are removed right away.
 #[tauri::command]
 pub async fn delete_walkthrough(
     db: State<'_, sea_orm::DatabaseConnection>,
     walkthrough_id: String,
+    permanent: Option<bool>,
 ) -> Result<(), String> {
-    crate::db::walkthrough_operations::delete_walkthrough(db.inner(), walkthrough_id)
+    if permanent.unwrap_or(false) {
+        crate::db::walkthrough_operations::purge_walkthrough(db.inner(), walkthrough_id).await
+    } else {
+        crate::db::walkthrough_operations::delete_walkthrough(db.inner(), &walkthrough_id).await
+    }
+    .map_err(|e| format!("Failed to delete walkthrough: {}", e))
+}
+
+/// Restore a deleted walkthrough
+#[tauri::command]
+pub async fn restore_walkthrough(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+) -> Result<crate::db::walkthrough_operations::WalkthroughDto, String> {
+    crate::db::walkthrough_operations::restore_walkthrough(db.inner(), &walkthrough_id)
+        .await
+        .map_err(|e| format!("Failed to restore walkthrough: {}", e))
+}
+
+/// Deleted walkthroughs of a project that can still be restored, most
+/// recently deleted first
+#[tauri::command]
+pub async fn get_deleted_walkthroughs(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    project_id: String,
+) -> Result<Vec<crate::db::walkthrough_operations::WalkthroughDto>, String> {
+    crate::db::walkthrough_operations::get_deleted_walkthroughs(db.inner(), project_id)
         .await
-        .map_err(|e| format!("Failed to delete walkthrough: {}", e))
+        .map_err(|e| format!("Failed to get deleted walkthroughs: {}", e))
 }
 
 /// Add a takeaway to a walkthrough
@@ -5164,62 +9874,377 @@ pub async fn delete_walkthrough_takeaway(
         .map_err(|e| format!("Failed to delete takeaway: {}", e))
 }
 
-/// Reorder takeaways
+/// Reorder takeaways
+#[tauri::command]
+pub async fn reorder_walkthrough_takeaways(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+    takeaway_ids_in_order: Vec<String>,
+) -> Result<(), String> {
+    crate::db::walkthrough_operations::reorder_takeaways(db.inner(), walkthrough_id, takeaway_ids_in_order)
+        .await
+        .map_err(|e| format!("Failed to reorder takeaways: {}", e))
+}
+
+/// Create a task from a takeaway in the walkthrough's project and link them,
+/// so completing either completes the other. Returns the linked task if the
+/// takeaway already has one.
+#[tauri::command]
+pub async fn convert_takeaway_to_task(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    takeaway_id: String,
+    priority: Option<String>,
+) -> Result<crate::db::task_operations::TaskDto, String> {
+    crate::db::walkthrough_operations::convert_takeaway_to_task(db.inner(), takeaway_id, priority)
+        .await
+        .map_err(|e| format!("Failed to convert takeaway to task: {}", e))
+}
+
+/// Get walkthrough notes
+#[tauri::command]
+pub async fn get_walkthrough_notes(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+) -> Result<Vec<crate::db::walkthrough_operations::WalkthroughNoteDto>, String> {
+    crate::db::walkthrough_operations::get_walkthrough_notes(db.inner(), walkthrough_id)
+        .await
+        .map_err(|e| format!("Failed to get walkthrough notes: {}", e))
+}
+
+/// Add a note to a walkthrough. `format` is "text" (the default) or "markdown".
+#[tauri::command]
+pub async fn add_walkthrough_note(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+    content: String,
+    format: Option<String>,
+) -> Result<crate::db::walkthrough_operations::WalkthroughNoteDto, String> {
+    crate::db::walkthrough_operations::add_walkthrough_note(db.inner(), walkthrough_id, content, format)
+        .await
+        .map_err(|e| format!("Failed to add walkthrough note: {}", e))
+}
+
+/// Update a walkthrough note, and its format if given
+#[tauri::command]
+pub async fn update_walkthrough_note(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    note_id: String,
+    content: String,
+    format: Option<String>,
+) -> Result<crate::db::walkthrough_operations::WalkthroughNoteDto, String> {
+    crate::db::walkthrough_operations::update_walkthrough_note(db.inner(), note_id, content, format)
+        .await
+        .map_err(|e| format!("Failed to update walkthrough note: {}", e))
+}
+
+/// Delete a walkthrough note and its attachments
+#[tauri::command]
+pub async fn delete_walkthrough_note(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    note_id: String,
+) -> Result<(), String> {
+    crate::db::walkthrough_operations::delete_walkthrough_note(db.inner(), note_id)
+        .await
+        .map_err(|e| format!("Failed to delete walkthrough note: {}", e))
+}
+
+/// Attaches a file to a walkthrough note.
+///
+/// The file is copied into `.bluekit/assets/<walkthrough name>/notes/<note
+/// id>/` (renamed with a numeric suffix if that name is taken). The returned
+/// `markdown` link can be inserted into a markdown note.
+#[tauri::command]
+pub async fn attach_walkthrough_note_file(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    note_id: String,
+    source_path: String,
+) -> Result<crate::core::assets::NoteAttachment, String> {
+    crate::db::walkthrough_operations::attach_walkthrough_note_file(db.inner(), note_id, &paths::from_ipc(&source_path))
+        .await
+        .map_err(|e| format!("Failed to attach file to note: {}", e))
+}
+
+/// Deletes a note attachment by file name
+#[tauri::command]
+pub async fn delete_walkthrough_note_attachment(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    note_id: String,
+    name: String,
+) -> Result<(), String> {
+    crate::db::walkthrough_operations::delete_walkthrough_note_attachment(db.inner(), note_id, &name)
+        .await
+        .map_err(|e| format!("Failed to delete note attachment: {}", e))
+}
+
+/// Write a walkthrough's status and progress into its file's front matter.
+/// Done automatically on changes when `walkthroughs.syncProgressToFrontMatter`
+/// is on; this brings a file up to date on demand, e.g. after turning it on.
+/// Returns whether the file changed.
+#[tauri::command]
+pub async fn sync_walkthrough_front_matter(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+) -> Result<bool, String> {
+    crate::db::walkthrough_operations::sync_walkthrough_front_matter(db.inner(), &walkthrough_id)
+        .await
+        .map_err(|e| crate::core::file_lock::command_error(e, "Failed to sync walkthrough front matter"))
+}
+
+/// Mermaid sources in a walkthrough, to render before `export_walkthrough_html`
+#[tauri::command]
+pub async fn get_walkthrough_diagrams(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+) -> Result<Vec<String>, String> {
+    crate::db::walkthrough_operations::get_walkthrough_diagrams(db.inner(), walkthrough_id)
+        .await
+        .map_err(|e| format!("Failed to get walkthrough diagrams: {}", e))
+}
+
+/// Export a walkthrough as a self-contained HTML guide next to its markdown.
+/// `diagrams` maps mermaid sources to SVGs rendered by the frontend.
+#[tauri::command]
+pub async fn export_walkthrough_html(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+    diagrams: Option<HashMap<String, String>>,
+) -> Result<crate::db::walkthrough_operations::WalkthroughHtmlExportDto, String> {
+    crate::db::walkthrough_operations::export_walkthrough_html(db.inner(), walkthrough_id, diagrams.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to export walkthrough: {}", e))
+}
+
+/// Get walkthrough steps in order
+#[tauri::command]
+pub async fn get_walkthrough_steps(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+) -> Result<Vec<crate::db::walkthrough_operations::WalkthroughStepDto>, String> {
+    crate::db::walkthrough_operations::get_walkthrough_steps(db.inner(), walkthrough_id)
+        .await
+        .map_err(|e| format!("Failed to get walkthrough steps: {}", e))
+}
+
+/// Add a step anchored to a line range of a project file at the current commit
+#[tauri::command]
+pub async fn add_walkthrough_step(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+    title: String,
+    description: Option<String>,
+    file_path: String,
+    start_line: i32,
+    end_line: i32,
+) -> Result<crate::db::walkthrough_operations::WalkthroughStepDto, String> {
+    crate::db::walkthrough_operations::add_walkthrough_step(
+        db.inner(),
+        walkthrough_id,
+        title,
+        description,
+        file_path,
+        start_line,
+        end_line,
+    )
+    .await
+    .map_err(|e| format!("Failed to add walkthrough step: {}", e))
+}
+
+/// Update a walkthrough step. A new file or line range (both lines needed)
+/// anchors it to the current commit.
+#[tauri::command]
+pub async fn update_walkthrough_step(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    step_id: String,
+    title: Option<String>,
+    description: Option<Option<String>>,
+    file_path: Option<String>,
+    start_line: Option<i32>,
+    end_line: Option<i32>,
+) -> Result<crate::db::walkthrough_operations::WalkthroughStepDto, String> {
+    crate::db::walkthrough_operations::update_walkthrough_step(
+        db.inner(),
+        step_id,
+        title,
+        description,
+        file_path,
+        start_line.zip(end_line),
+    )
+    .await
+    .map_err(|e| format!("Failed to update walkthrough step: {}", e))
+}
+
+/// Delete a walkthrough step
+#[tauri::command]
+pub async fn delete_walkthrough_step(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    step_id: String,
+) -> Result<(), String> {
+    crate::db::walkthrough_operations::delete_walkthrough_step(db.inner(), step_id)
+        .await
+        .map_err(|e| format!("Failed to delete walkthrough step: {}", e))
+}
+
+/// Reorder walkthrough steps
+#[tauri::command]
+pub async fn reorder_walkthrough_steps(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+    step_ids: Vec<String>,
+) -> Result<(), String> {
+    crate::db::walkthrough_operations::reorder_walkthrough_steps(db.inner(), walkthrough_id, step_ids)
+        .await
+        .map_err(|e| format!("Failed to reorder walkthrough steps: {}", e))
+}
+
+/// Move each step of a walkthrough to where its lines are at the project's
+/// HEAD, following them with git blame. Reports what happened to each step.
+#[tauri::command]
+pub async fn reanchor_walkthrough_steps(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    walkthrough_id: String,
+) -> Result<Vec<crate::db::walkthrough_operations::StepReanchorDto>, String> {
+    crate::db::walkthrough_operations::reanchor_walkthrough_steps(db.inner(), walkthrough_id)
+        .await
+        .map_err(|e| format!("Failed to re-anchor walkthrough steps: {}", e))
+}
+
+// ============================================================================
+// ARTIFACT CREATION COMMANDS
+// ============================================================================
+
+/// Create a new kit with file and DB registration
+#[tauri::command]
+pub async fn create_kit(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    project_id: String,
+    project_path: String,
+    name: String,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<crate::db::artifact_operations::ArtifactDto, String> {
+    crate::db::artifact_operations::create_kit(
+        db.inner(),
+        project_id,
+        project_path,
+        name,
+        description,
+        tags.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| format!("Failed to create kit: {}", e))
+}
+
+/// Create a new agent with file and DB registration
+#[tauri::command]
+pub async fn create_agent(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    project_id: String,
+    project_path: String,
+    name: String,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    capabilities: Option<Vec<String>>,
+) -> Result<crate::db::artifact_operations::ArtifactDto, String> {
+    crate::db::artifact_operations::create_agent(
+        db.inner(),
+        project_id,
+        project_path,
+        name,
+        description,
+        tags.unwrap_or_default(),
+        capabilities.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| format!("Failed to create agent: {}", e))
+}
+
+/// Create a new mermaid diagram with file and DB registration
+#[tauri::command]
+pub async fn create_diagram(
+    db: State<'_, sea_orm::DatabaseConnection>,
+    project_id: String,
+    project_path: String,
+    name: String,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<crate::db::artifact_operations::ArtifactDto, String> {
+    crate::db::artifact_operations::create_diagram(
+        db.inner(),
+        project_id,
+        project_path,
+        name,
+        description,
+        tags.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| format!("Failed to create diagram: {}", e))
+}
+
+/// Get every project a kit has been copied into
+///
+/// Each entry flags whether the copy still exists and whether the source kit
+/// has changed since it was copied, so authors know which copies to update.
 #[tauri::command]
-pub async fn reorder_walkthrough_takeaways(
+pub async fn get_kit_usage(
     db: State<'_, sea_orm::DatabaseConnection>,
-    walkthrough_id: String,
-    takeaway_ids_in_order: Vec<String>,
-) -> Result<(), String> {
-    crate::db::walkthrough_operations::reorder_takeaways(db.inner(), walkthrough_id, takeaway_ids_in_order)
+    kit_path: String,
+) -> Result<Vec<crate::db::kit_usage_operations::KitUsageDto>, String> {
+    crate::db::kit_usage_operations::get_kit_usage(db.inner(), &kit_path)
         .await
-        .map_err(|e| format!("Failed to reorder takeaways: {}", e))
+        .map_err(|e| format!("Failed to get kit usage: {}", e))
 }
 
-/// Get walkthrough notes
+/// Check a project's copied/pulled artifacts against their origin
+///
+/// Returns the copies whose source file or library catalog has newer content
+/// than what was copied, plus copies whose origin no longer exists.
 #[tauri::command]
-pub async fn get_walkthrough_notes(
+pub async fn check_outdated_copies(
     db: State<'_, sea_orm::DatabaseConnection>,
-    walkthrough_id: String,
-) -> Result<Vec<crate::db::walkthrough_operations::WalkthroughNoteDto>, String> {
-    crate::db::walkthrough_operations::get_walkthrough_notes(db.inner(), walkthrough_id)
+    project_path: String,
+) -> Result<Vec<crate::db::provenance_operations::OutdatedCopyDto>, String> {
+    crate::db::provenance_operations::check_outdated_copies(db.inner(), &project_path)
         .await
-        .map_err(|e| format!("Failed to get walkthrough notes: {}", e))
+        .map_err(|e| format!("Failed to check outdated copies: {}", e))
 }
 
-/// Add a note to a walkthrough
+// ============================================================================
+// AGENT COMMANDS
+// ============================================================================
+
+/// Get all agents for a project (syncs with file system first)
 #[tauri::command]
-pub async fn add_walkthrough_note(
+pub async fn get_project_agents(
     db: State<'_, sea_orm::DatabaseConnection>,
-    walkthrough_id: String,
-    content: String,
-) -> Result<crate::db::walkthrough_operations::WalkthroughNoteDto, String> {
-    crate::db::walkthrough_operations::add_walkthrough_note(db.inner(), walkthrough_id, content)
+    project_id: String,
+    project_path: Option<String>,
+) -> Result<Vec<crate::db::agent_operations::AgentDto>, String> {
+    crate::db::agent_operations::get_project_agents(db.inner(), project_id, project_path)
         .await
-        .map_err(|e| format!("Failed to add walkthrough note: {}", e))
+        .map_err(|e| format!("Failed to get project agents: {}", e))
 }
 
-/// Update a walkthrough note
+/// Validate an agent's front matter (model, tools) and system prompt sections
 #[tauri::command]
-pub async fn update_walkthrough_note(
-    db: State<'_, sea_orm::DatabaseConnection>,
-    note_id: String,
-    content: String,
-) -> Result<crate::db::walkthrough_operations::WalkthroughNoteDto, String> {
-    crate::db::walkthrough_operations::update_walkthrough_note(db.inner(), note_id, content)
-        .await
-        .map_err(|e| format!("Failed to update walkthrough note: {}", e))
+pub async fn validate_agent(
+    file_path: String,
+) -> Result<crate::db::agent_operations::AgentValidationDto, String> {
+    crate::db::agent_operations::validate_agent_file(&file_path)
+        .map_err(|e| format!("Failed to validate agent: {}", e))
 }
 
-/// Delete a walkthrough note
+/// Export an agent as a Claude subagent (`claude`) or Cursor rule (`cursor`)
+///
+/// The exported file is written into `.claude/agents/` or `.cursor/rules/`
+/// of the given project.
 #[tauri::command]
-pub async fn delete_walkthrough_note(
-    db: State<'_, sea_orm::DatabaseConnection>,
-    note_id: String,
-) -> Result<(), String> {
-    crate::db::walkthrough_operations::delete_walkthrough_note(db.inner(), note_id)
-        .await
-        .map_err(|e| format!("Failed to delete walkthrough note: {}", e))
+pub async fn export_agent(
+    file_path: String,
+    project_path: String,
+    format: String,
+) -> Result<crate::db::agent_operations::AgentExportDto, String> {
+    crate::db::agent_operations::export_agent(&file_path, &project_path, &format)
+        .map_err(|e| format!("Failed to export agent: {}", e))
 }
 
 /// File tree node structure.
@@ -5245,7 +10270,7 @@ pub struct FileTreeNode {
 pub async fn get_bluekit_file_tree(project_path: String) -> Result<Vec<FileTreeNode>, String> {
     use std::fs;
 
-    let bluekit_path = PathBuf::from(&project_path).join(".bluekit");
+    let bluekit_path = paths::from_ipc(&project_path).join(".bluekit");
     if !bluekit_path.exists() {
         return Ok(Vec::new());
     }
@@ -5379,7 +10404,7 @@ pub async fn clone_from_github(
     
     // 2. Construct GitHub URL
     let git_url = format!("https://github.com/{}.git", owner_repo);
-    let path = PathBuf::from(&target_path);
+    let path = paths::from_ipc(&target_path);
     
     // 3. Validate target path doesn't exist
     if path.exists() {
@@ -5428,9 +10453,8 @@ pub async fn clone_from_github(
         // Extract project name from path if title not provided
         let name = project_title.unwrap_or_else(|| {
              path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("New Project")
-                .to_string()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "New Project".to_string())
         });
         
         // Create project entry
@@ -5636,6 +10660,46 @@ fn check_bookmark_exists(items: &[BookmarkItem], target_path: &str) -> bool {
     false
 }
 
+/// Helper function to recursively find a file bookmark by path.
+fn find_file_bookmark<'a>(items: &'a [BookmarkItem], target_path: &str) -> Option<&'a BookmarkItem> {
+    for item in items {
+        match item {
+            BookmarkItem::File { path, .. } => {
+                if path == target_path {
+                    return Some(item);
+                }
+            }
+            BookmarkItem::Group { items: nested_items, .. } => {
+                if let Some(found) = find_file_bookmark(nested_items, target_path) {
+                    return Some(found);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Helper function to recursively repoint a file bookmark at a new path.
+/// Returns true if a bookmark was updated.
+fn rename_bookmark_path(items: &mut [BookmarkItem], old_path: &str, new_path: &str) -> bool {
+    for item in items.iter_mut() {
+        match item {
+            BookmarkItem::File { path, .. } => {
+                if path == old_path {
+                    *path = new_path.to_string();
+                    return true;
+                }
+            }
+            BookmarkItem::Group { items: nested_items, .. } => {
+                if rename_bookmark_path(nested_items, old_path, new_path) {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
 /// Removes a bookmark by file path (recursively searches through groups).
 ///
 /// # Arguments
@@ -5745,6 +10809,68 @@ fn reconcile_bookmarks_recursive(items: &mut Vec<BookmarkItem>) {
     }
 }
 
+/// Collects file bookmarks (recursively) whose target no longer exists.
+fn missing_bookmark_paths(items: &[BookmarkItem], missing: &mut Vec<String>) {
+    for item in items {
+        match item {
+            BookmarkItem::File { path, .. } => {
+                if std::fs::metadata(path).is_err() {
+                    missing.push(path.clone());
+                }
+            }
+            BookmarkItem::Group { items, .. } => missing_bookmark_paths(items, missing),
+        }
+    }
+}
+
+/// Cross-checks a project's DB records and bookmarks against the filesystem.
+///
+/// Reports walkthroughs, plans, plan documents and library resources whose
+/// files are gone, files that have no record, and bookmarks pointing at
+/// missing files. With `fix`, each orphan is repaired: stale records and
+/// bookmarks are removed and untracked files are adopted.
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const report = await invoke<ReconcileReport>('reconcile_project', {
+///   projectPath: '/path/to/project',
+///   fix: false
+/// });
+/// ```
+#[tauri::command]
+pub async fn reconcile_project(
+    db: State<'_, DatabaseConnection>,
+    project_path: String,
+    fix: Option<bool>,
+) -> Result<crate::db::reconcile_operations::ReconcileReportDto, String> {
+    use crate::db::reconcile_operations::OrphanDto;
+
+    let fix = fix.unwrap_or(false);
+    let mut report = crate::db::reconcile_operations::reconcile_project(db.inner(), &project_path, fix)
+        .await
+        .map_err(|e| format!("Failed to reconcile project: {}", e))?;
+
+    let mut bookmarks = get_bookmarks(project_path.clone()).await?;
+    let mut missing = Vec::new();
+    missing_bookmark_paths(&bookmarks.items, &mut missing);
+    if fix && !missing.is_empty() {
+        reconcile_bookmarks_recursive(&mut bookmarks.items);
+        save_bookmarks(project_path, bookmarks).await?;
+    }
+
+    report.orphans.extend(missing.into_iter().map(|path| OrphanDto {
+        kind: "bookmark".to_string(),
+        issue: "missing_file".to_string(),
+        record_id: None,
+        path,
+        fixed: fix,
+    }));
+    report.fixed_count = report.orphans.iter().filter(|o| o.fixed).count();
+
+    Ok(report)
+}
+
 // ============================================================================
 // Supabase Auth Commands
 // ============================================================================
@@ -5802,3 +10928,324 @@ pub async fn stop_supabase_auth_server() -> Result<(), String> {
     }
     Ok(())
 }
+
+// ============================================================================
+// SETTINGS COMMANDS
+// ============================================================================
+
+/// Gets application settings (defaults are filled in for missing values)
+#[tauri::command]
+pub async fn get_app_settings() -> Result<crate::core::settings::AppSettings, String> {
+    Ok(crate::core::settings::load_settings())
+}
+
+/// Saves application settings to ~/.bluekit/settings.json
+///
+/// `storage` is kept as saved: the data directory only changes through
+/// `set_data_directory`, which also arranges moving the data.
+#[tauri::command]
+pub async fn update_app_settings(
+    mut settings: crate::core::settings::AppSettings,
+) -> Result<crate::core::settings::AppSettings, String> {
+    settings.storage = crate::core::settings::load_settings().storage;
+    crate::core::settings::save_settings(&settings)?;
+    Ok(settings)
+}
+
+/// Returns the data directory in use and the one set for the next launch
+#[tauri::command]
+pub async fn get_data_directory() -> Result<crate::core::data_dir::DataDirectory, String> {
+    crate::core::data_dir::status()
+}
+
+/// Lists what moving the data to `path` (the default `~/.bluekit` when
+/// none) would involve, and any reason it can't be done
+#[tauri::command]
+pub async fn preview_data_directory_change(
+    path: Option<String>,
+) -> Result<crate::core::data_dir::DataMigrationPlan, String> {
+    tokio::task::spawn_blocking(move || crate::core::data_dir::preview_change(path.as_deref()))
+        .await
+        .map_err(|e| format!("Failed to preview data directory change: {}", e))?
+}
+
+/// Sets the data directory (the default `~/.bluekit` when `path` is none)
+/// from the next launch. With `migrate`, the current data is moved there
+/// when the app restarts; without it, data already in `path` is used.
+#[tauri::command]
+pub async fn set_data_directory(
+    path: Option<String>,
+    migrate: bool,
+) -> Result<crate::core::data_dir::DataDirectory, String> {
+    tokio::task::spawn_blocking(move || crate::core::data_dir::set(path.as_deref(), migrate))
+        .await
+        .map_err(|e| format!("Failed to set data directory: {}", e))?
+}
+
+// ============================================================================
+// TERMINAL COMMANDS
+// ============================================================================
+
+/// Runs a shell command in a PTY inside the project directory.
+///
+/// Output streams as `terminal-output` events and completion as a
+/// `terminal-exit` event, both carrying the returned session id.
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const sessionId = await invoke<string>('run_command', {
+///   projectPath: '/path/to/project',
+///   command: 'npm test'
+/// });
+/// ```
+#[tauri::command]
+pub async fn run_command(
+    app_handle: AppHandle,
+    terminals: State<'_, crate::core::terminal::TerminalSessions>,
+    project_path: String,
+    command: String,
+) -> Result<String, String> {
+    if command.trim().is_empty() {
+        return Err("Command is empty".to_string());
+    }
+    terminals.run(app_handle, &paths::from_ipc(&project_path), &command)
+}
+
+/// Kills the process of a running terminal session
+#[tauri::command]
+pub async fn kill_command(
+    terminals: State<'_, crate::core::terminal::TerminalSessions>,
+    session_id: String,
+) -> Result<(), String> {
+    terminals.kill(&session_id)
+}
+
+/// Sends input to a running terminal session
+#[tauri::command]
+pub async fn write_terminal_input(
+    terminals: State<'_, crate::core::terminal::TerminalSessions>,
+    session_id: String,
+    data: String,
+) -> Result<(), String> {
+    terminals.write_input(&session_id, &data)
+}
+
+/// Lists running terminal sessions
+#[tauri::command]
+pub async fn list_terminal_sessions(
+    terminals: State<'_, crate::core::terminal::TerminalSessions>,
+) -> Result<Vec<crate::core::terminal::TerminalSessionInfo>, String> {
+    Ok(terminals.list())
+}
+
+// ============================================================================
+// DATABASE MAINTENANCE COMMANDS
+// ============================================================================
+
+/// Checks the database's integrity, then compacts it (`VACUUM`) and
+/// refreshes query planner statistics (`ANALYZE`).
+///
+/// Compaction is skipped when the integrity check fails; the report lists
+/// the problems found instead.
+#[tauri::command]
+pub async fn db_maintenance(
+    db: State<'_, DatabaseConnection>,
+) -> Result<crate::db::maintenance::DbHealthReport, String> {
+    crate::db::maintenance::run_maintenance(db.inner())
+        .await
+        .map_err(|e| format!("Database maintenance failed: {}", e))
+}
+
+/// Returns the database's schema version: the last applied migration and
+/// any this build hasn't applied yet
+#[tauri::command]
+pub async fn get_schema_version(
+    db: State<'_, DatabaseConnection>,
+) -> Result<crate::db::migrations::SchemaVersion, String> {
+    crate::db::migrations::schema_version(db.inner())
+        .await
+        .map_err(|e| format!("Failed to get schema version: {}", e))
+}
+
+/// Whether the database is encrypted, can be, or is locked
+#[tauri::command]
+pub async fn get_database_encryption_status(
+    db: State<'_, DatabaseConnection>,
+) -> Result<crate::db::encryption::EncryptionStatus, String> {
+    crate::db::encryption::status(db.inner())
+        .await
+        .map_err(|e| format!("Failed to get database encryption status: {}", e))
+}
+
+/// Encrypts the database with a key kept in the OS keychain on the next
+/// launch.
+#[tauri::command]
+pub async fn enable_database_encryption(
+    db: State<'_, DatabaseConnection>,
+) -> Result<crate::db::encryption::EncryptionStatus, String> {
+    crate::db::encryption::enable_encryption(db.inner())
+        .await
+        .map_err(|e| format!("Failed to encrypt database: {}", e))
+}
+
+/// Decrypts the database on the next launch.
+#[tauri::command]
+pub async fn disable_database_encryption(
+    db: State<'_, DatabaseConnection>,
+) -> Result<crate::db::encryption::EncryptionStatus, String> {
+    crate::db::encryption::disable_encryption(db.inner())
+        .await
+        .map_err(|e| format!("Failed to decrypt database: {}", e))
+}
+
+/// Closes the encrypted database until `unlock_database`
+#[tauri::command]
+pub async fn lock_database(db: State<'_, DatabaseConnection>) -> Result<(), String> {
+    crate::db::encryption::lock(db.inner())
+        .await
+        .map_err(|e| format!("Failed to lock database: {}", e))
+}
+
+/// Reopens a locked database by restarting the app
+#[tauri::command]
+pub async fn unlock_database(app_handle: AppHandle) -> Result<(), String> {
+    crate::db::encryption::unlock(&app_handle)
+        .map_err(|e| format!("Failed to unlock database: {}", e))
+}
+
+// ============================================================================
+// SCHEDULER COMMANDS
+// ============================================================================
+
+/// Lists scheduled jobs with their next and last run
+#[tauri::command]
+pub async fn list_scheduled_jobs(
+    db: State<'_, DatabaseConnection>,
+) -> Result<Vec<crate::db::scheduler_operations::ScheduledJobDto>, String> {
+    crate::db::scheduler_operations::list_scheduled_jobs(db.inner())
+        .await
+        .map_err(|e| format!("Failed to list scheduled jobs: {}", e))
+}
+
+/// Creates a scheduled job.
+///
+/// Give either `intervalSeconds` or a `cronExpression` (five fields, local
+/// time). `jobType` is one of `workspace_sync`, `backup`, `update_check` or
+/// `trash_purge`.
+///
+/// # Example Usage (from frontend)
+///
+/// ```typescript
+/// const job = await invoke<ScheduledJob>('create_scheduled_job', {
+///   name: 'Nightly backup',
+///   jobType: 'backup',
+///   cronExpression: '0 3 * * *',
+///   params: { keep: 14 }
+/// });
+/// ```
+#[tauri::command]
+pub async fn create_scheduled_job(
+    db: State<'_, DatabaseConnection>,
+    name: String,
+    job_type: String,
+    interval_seconds: Option<i64>,
+    cron_expression: Option<String>,
+    params: Option<serde_json::Value>,
+) -> Result<crate::db::scheduler_operations::ScheduledJobDto, String> {
+    crate::db::scheduler_operations::create_scheduled_job(
+        db.inner(),
+        name,
+        job_type,
+        interval_seconds,
+        cron_expression,
+        params,
+    )
+    .await
+    .map_err(|e| format!("Failed to create scheduled job: {}", e))
+}
+
+/// Updates a scheduled job's name, schedule, params or enabled state
+#[tauri::command]
+pub async fn update_scheduled_job(
+    db: State<'_, DatabaseConnection>,
+    job_id: String,
+    update: crate::db::scheduler_operations::ScheduledJobUpdate,
+) -> Result<crate::db::scheduler_operations::ScheduledJobDto, String> {
+    crate::db::scheduler_operations::update_scheduled_job(db.inner(), &job_id, update)
+        .await
+        .map_err(|e| format!("Failed to update scheduled job: {}", e))
+}
+
+/// Deletes a scheduled job
+#[tauri::command]
+pub async fn delete_scheduled_job(
+    db: State<'_, DatabaseConnection>,
+    job_id: String,
+) -> Result<(), String> {
+    crate::db::scheduler_operations::delete_scheduled_job(db.inner(), &job_id)
+        .await
+        .map_err(|e| format!("Failed to delete scheduled job: {}", e))
+}
+
+/// Runs a scheduled job immediately without changing its schedule.
+///
+/// Returns the job's result message; a failed run is returned as an error.
+#[tauri::command]
+pub async fn run_scheduled_job_now(
+    app_handle: AppHandle,
+    db: State<'_, DatabaseConnection>,
+    job_id: String,
+) -> Result<String, String> {
+    let job = crate::db::scheduler_operations::get_scheduled_job(db.inner(), &job_id)
+        .await
+        .map_err(|e| format!("Failed to load scheduled job: {}", e))?
+        .ok_or_else(|| format!("Scheduled job not found: {}", job_id))?;
+
+    crate::core::scheduler::run_job(&app_handle, db.inner(), job).await?
+}
+
+// ============================================================================
+// OPERATION COMMANDS
+// ============================================================================
+
+/// Cancels a running long operation by its `operation-progress` id
+///
+/// The operation stops at its next step, cleans up its partial output and
+/// fails with "Operation cancelled". Returns false if no operation with
+/// that id is running.
+#[tauri::command]
+pub async fn cancel_operation(operation_id: String) -> Result<bool, String> {
+    Ok(crate::core::cancellation::cancel(&operation_id))
+}
+
+// ============================================================================
+// SESSION COMMANDS
+// ============================================================================
+
+/// Saves where the user is (active project, open artifacts, selections)
+///
+/// The frontend calls this whenever the session changes; it replaces the
+/// previously saved session.
+#[tauri::command]
+pub async fn save_session_state(
+    db: State<'_, DatabaseConnection>,
+    state: crate::db::session_operations::SessionStateDto,
+) -> Result<(), String> {
+    crate::db::session_operations::save_session_state(db.inner(), state)
+        .await
+        .map_err(|e| format!("Failed to save session state: {}", e))
+}
+
+/// Returns the last saved session for restoring at startup
+///
+/// Artifacts that no longer exist are left out; with no saved session all
+/// fields are empty.
+#[tauri::command]
+pub async fn get_session_state(
+    db: State<'_, DatabaseConnection>,
+) -> Result<crate::db::session_operations::SessionStateDto, String> {
+    crate::db::session_operations::get_session_state(db.inner())
+        .await
+        .map_err(|e| format!("Failed to load session state: {}", e))
+}
@@ -0,0 +1,301 @@
+//! Zip archive helpers shared by export/import features.
+//!
+//! Archives carry a `bluekit-manifest.json` listing every file with its
+//! SHA-256 checksum. Extraction rejects entries that would escape the
+//! destination directory (absolute paths, `..`, symlinks) and verifies each
+//! file against the manifest, both as read from the archive and as written.
+
+use crate::core::progress::ProgressReporter;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Name of the manifest entry inside every archive
+pub const MANIFEST_NAME: &str = "bluekit-manifest.json";
+
+/// Current manifest format version
+const MANIFEST_VERSION: u32 = 1;
+
+/// One file stored in an archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    /// Path inside the archive, always `/`-separated
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Archive table of contents with checksums
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub version: u32,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    pub entries: Vec<ArchiveEntry>,
+}
+
+/// Zips every file under `source_dir` accepted by `include` into `dest`.
+///
+/// `include` is called with paths relative to `source_dir`; returning false
+/// for a directory skips everything beneath it. Each file added is reported
+/// to `progress`; if the operation is cancelled (or anything fails) the
+/// partial archive is removed.
+pub fn create_archive<F>(
+    source_dir: &Path,
+    dest: &Path,
+    include: F,
+    progress: &ProgressReporter,
+) -> Result<ArchiveManifest, String>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut files = Vec::new();
+    collect_files(source_dir, source_dir, &include, &mut files)?;
+    files.sort();
+
+    let file = File::create(dest)
+        .map_err(|e| format!("Failed to create archive {}: {}", dest.display(), e))?;
+    let result = write_archive(source_dir, file, files, progress);
+    if result.is_err() {
+        let _ = fs::remove_file(dest);
+    }
+    result
+}
+
+fn write_archive(
+    source_dir: &Path,
+    file: File,
+    files: Vec<PathBuf>,
+    progress: &ProgressReporter,
+) -> Result<ArchiveManifest, String> {
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let total = files.len() as u64;
+    let mut entries = Vec::new();
+    for (index, relative) in files.into_iter().enumerate() {
+        progress.check_cancelled()?;
+        let entry_name = archive_name(&relative)?;
+        progress.report("archiving", index as u64, Some(total), entry_name.as_str());
+        zip.start_file(entry_name.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to archive: {}", entry_name, e))?;
+
+        let mut source = File::open(source_dir.join(&relative))
+            .map_err(|e| format!("Failed to read {}: {}", relative.display(), e))?;
+        let (size, sha256) = copy_hashed(&mut source, &mut zip)
+            .map_err(|e| format!("Failed to add {} to archive: {}", entry_name, e))?;
+
+        entries.push(ArchiveEntry { path: entry_name, size, sha256 });
+    }
+
+    let manifest = ArchiveManifest {
+        version: MANIFEST_VERSION,
+        created_at: chrono::Utc::now().timestamp(),
+        entries,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize archive manifest: {}", e))?;
+    zip.start_file(MANIFEST_NAME, options)
+        .and_then(|_| zip.write_all(&manifest_json).map_err(Into::into))
+        .map_err(|e| format!("Failed to write archive manifest: {}", e))?;
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+
+    Ok(manifest)
+}
+
+/// Extracts `archive_path` into `dest_dir`, verifying checksums.
+///
+/// Existing files are only replaced when `overwrite` is set. Files already
+/// written are left in place if a later entry fails verification, so callers
+/// extracting into a live directory should extract to a staging directory
+/// first. Each file extracted is reported to `progress`; if the operation is
+/// cancelled, the files this call created are removed again.
+pub fn extract_archive(
+    archive_path: &Path,
+    dest_dir: &Path,
+    overwrite: bool,
+    progress: &ProgressReporter,
+) -> Result<ArchiveManifest, String> {
+    let mut archive = open_archive(archive_path)?;
+    let manifest = read_manifest_entry(&mut archive)?;
+
+    fs::create_dir_all(dest_dir)
+        .map_err(|e| format!("Failed to create {}: {}", dest_dir.display(), e))?;
+
+    let total = manifest.entries.len() as u64;
+    let mut created = Vec::new();
+    for (index, entry) in manifest.entries.iter().enumerate() {
+        if let Err(e) = progress.check_cancelled() {
+            for path in &created {
+                let _ = fs::remove_file(path);
+            }
+            return Err(e);
+        }
+        progress.report("extracting", index as u64, Some(total), entry.path.as_str());
+        let target = safe_join(dest_dir, &entry.path)?;
+        if target.exists() && !overwrite {
+            return Err(format!("File already exists: {}", target.display()));
+        }
+
+        let mut file = archive
+            .by_name(&entry.path)
+            .map_err(|e| format!("Archive is missing {}: {}", entry.path, e))?;
+        if file.is_dir() || is_symlink(file.unix_mode()) {
+            return Err(format!("Archive entry is not a regular file: {}", entry.path));
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        if !target.exists() {
+            created.push(target.clone());
+        }
+        let mut out = File::create(&target)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        let (size, sha256) = copy_hashed(&mut file, &mut out)
+            .map_err(|e| format!("Failed to extract {}: {}", entry.path, e))?;
+
+        if size != entry.size || sha256 != entry.sha256 {
+            drop(out);
+            let _ = fs::remove_file(&target);
+            return Err(format!("Checksum mismatch for {}; the archive is corrupt", entry.path));
+        }
+
+        // Re-read what landed on disk to catch failed or truncated writes
+        drop(out);
+        let written = crate::core::checksum::hash_file(&target)
+            .map_err(|e| format!("Failed to verify {}: {}", target.display(), e))?;
+        if written != entry.sha256 {
+            let _ = fs::remove_file(&target);
+            return Err(format!(
+                "Checksum mismatch for {} after writing {}; the file was not written correctly",
+                entry.path,
+                target.display()
+            ));
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Joins an archive entry name onto `base`, rejecting anything that could
+/// land outside it.
+pub fn safe_join(base: &Path, entry_name: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(entry_name);
+    let is_safe = !entry_name.is_empty()
+        && !entry_name.contains('\\')
+        && relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+
+    if !is_safe {
+        return Err(format!("Unsafe path in archive: {}", entry_name));
+    }
+    Ok(base.join(relative))
+}
+
+fn open_archive(archive_path: &Path) -> Result<ZipArchive<File>, String> {
+    let file = File::open(archive_path)
+        .map_err(|e| format!("Failed to open archive {}: {}", archive_path.display(), e))?;
+    ZipArchive::new(file)
+        .map_err(|e| format!("Invalid archive {}: {}", archive_path.display(), e))
+}
+
+fn read_manifest_entry(archive: &mut ZipArchive<File>) -> Result<ArchiveManifest, String> {
+    let mut manifest_json = String::new();
+    archive
+        .by_name(MANIFEST_NAME)
+        .map_err(|_| format!("Archive has no {}", MANIFEST_NAME))?
+        .read_to_string(&mut manifest_json)
+        .map_err(|e| format!("Failed to read archive manifest: {}", e))?;
+
+    let manifest: ArchiveManifest = serde_json::from_str(&manifest_json)
+        .map_err(|e| format!("Invalid archive manifest: {}", e))?;
+    if manifest.version > MANIFEST_VERSION {
+        return Err(format!(
+            "Archive was created by a newer version of BlueKit (manifest v{})",
+            manifest.version
+        ));
+    }
+    Ok(manifest)
+}
+
+// Recursively collects files (relative to root), skipping symlinks
+fn collect_files<F>(root: &Path, dir: &Path, include: &F, files: &mut Vec<PathBuf>) -> Result<(), String>
+where
+    F: Fn(&Path) -> bool,
+{
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = entry.path();
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let relative = path
+            .strip_prefix(root)
+            .map_err(|e| format!("Failed to compute archive path: {}", e))?
+            .to_path_buf();
+
+        // The manifest name is reserved for the archive's own manifest
+        if file_type.is_symlink() || relative == Path::new(MANIFEST_NAME) || !include(&relative) {
+            continue;
+        }
+        if file_type.is_dir() {
+            collect_files(root, &path, include, files)?;
+        } else if file_type.is_file() {
+            files.push(relative);
+        }
+    }
+
+    Ok(())
+}
+
+// Archive names are UTF-8 and `/`-separated on every platform
+fn archive_name(relative: &Path) -> Result<String, String> {
+    let parts = relative
+        .components()
+        .map(|c| match c {
+            Component::Normal(part) => part
+                .to_str()
+                .ok_or_else(|| format!("File name is not valid UTF-8: {}", relative.display())),
+            _ => Err(format!("Unexpected path component in {}", relative.display())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(parts.join("/"))
+}
+
+// Copies reader to writer in chunks, returning the byte count and SHA-256
+fn copy_hashed<R: Read, W: Write>(reader: &mut R, writer: &mut W) -> std::io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut size = 0u64;
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        size += read as u64;
+    }
+    writer.flush()?;
+
+    Ok((size, format!("{:x}", hasher.finalize())))
+}
+
+fn is_symlink(unix_mode: Option<u32>) -> bool {
+    const S_IFMT: u32 = 0o170000;
+    const S_IFLNK: u32 = 0o120000;
+    unix_mode.is_some_and(|mode| mode & S_IFMT == S_IFLNK)
+}
@@ -0,0 +1,388 @@
+//! Binary assets attached to artifacts.
+//!
+//! Files attached to an artifact are copied into `.bluekit/assets/<artifact
+//! stem>/` and linked from the artifact with a relative path. Loose files
+//! directly in `.bluekit/assets` are shared images and never treated as
+//! attachments. An attachment nothing in `.bluekit` links to any more is an
+//! orphan; [`orphaned_assets`] finds those so they can be trashed.
+//!
+//! Walkthrough notes live in the database rather than the artifact, so
+//! their attachments go one level deeper, in `notes/<note id>/` under the
+//! walkthrough's asset folder, and are left out of the orphan check. They
+//! are removed with their note.
+
+use crate::core::copy_conflict::{resolve_target, ConflictPolicy};
+use crate::core::paths;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Assets directory inside `.bluekit`
+pub const ASSETS_DIR: &str = "assets";
+
+/// Returned by `attach_asset`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachedAsset {
+    #[serde(rename = "assetPath")]
+    pub asset_path: String,
+    /// Path of the asset relative to the artifact, `/`-separated
+    #[serde(rename = "relativePath")]
+    pub relative_path: String,
+    /// Ready-to-insert markdown link (an image link for images)
+    pub markdown: String,
+    /// Links in the artifact that pointed at the source file and now point
+    /// at the attached copy
+    #[serde(rename = "linksRewritten")]
+    pub links_rewritten: usize,
+}
+
+/// One file attached to an artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactAsset {
+    pub path: String,
+    pub name: String,
+    pub size: u64,
+    #[serde(rename = "relativePath")]
+    pub relative_path: String,
+    /// Whether the artifact links to it
+    pub referenced: bool,
+}
+
+/// Folder in an artifact's asset folder holding walkthrough note attachments
+pub const NOTES_DIR: &str = "notes";
+
+/// A file attached to a walkthrough note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteAttachment {
+    pub path: String,
+    pub name: String,
+    pub size: u64,
+    /// Path relative to the walkthrough file, `/`-separated, for links in
+    /// the note's markdown
+    #[serde(rename = "relativePath")]
+    pub relative_path: String,
+    #[serde(rename = "isImage")]
+    pub is_image: bool,
+    /// Ready-to-insert markdown link (an image link for images)
+    pub markdown: String,
+}
+
+/// Copies `source` into the artifact's asset folder (as `name-2.ext` if the
+/// name is taken) and points links in the artifact that referenced `source`
+/// at the copy.
+pub async fn attach_asset(artifact: &Path, source: &Path) -> Result<AttachedAsset, String> {
+    if !artifact.is_file() {
+        return Err(format!("Artifact does not exist: {}", artifact.display()));
+    }
+    if !source.is_file() {
+        return Err(format!("Asset does not exist: {}", source.display()));
+    }
+
+    let asset_dir = asset_dir_for(artifact)?;
+    fs::create_dir_all(&asset_dir).map_err(|e| format!("Failed to create asset folder: {}", e))?;
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("Invalid asset file name: {}", source.display()))?;
+    let (target, _) = resolve_target(&asset_dir.join(file_name), ConflictPolicy::RenameWithSuffix)?;
+    fs::copy(source, &target).map_err(|e| format!("Failed to copy asset: {}", e))?;
+
+    let relative_path = relative_link(artifact, &target)?;
+    let links_rewritten = {
+        let _lock = crate::core::file_lock::lock(artifact, "attach_asset").await?;
+        rewrite_links(artifact, source, &relative_path)?
+    };
+
+    let name = paths::file_stem_lossy(&target);
+    let markdown = if is_image(&target) {
+        format!("![{}]({})", name, relative_path)
+    } else {
+        format!("[{}]({})", name, relative_path)
+    };
+
+    Ok(AttachedAsset {
+        asset_path: paths::to_ipc(&target),
+        relative_path,
+        markdown,
+        links_rewritten,
+    })
+}
+
+/// Files in the artifact's asset folder, by name.
+pub fn list_assets(artifact: &Path) -> Result<Vec<ArtifactAsset>, String> {
+    let asset_dir = asset_dir_for(artifact)?;
+    let content = fs::read_to_string(artifact).unwrap_or_default();
+
+    let mut assets = Vec::new();
+    for path in asset_files(&asset_dir) {
+        let relative_path = relative_link(artifact, &path)?;
+        assets.push(ArtifactAsset {
+            referenced: links_to(&content, &relative_path),
+            name: paths::file_name_lossy(&path),
+            size: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            path: paths::to_ipc(&path),
+            relative_path,
+        });
+    }
+    assets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(assets)
+}
+
+/// Copies `source` into the attachment folder of `note_id`, a note on the
+/// walkthrough at `walkthrough` (as `name-2.ext` if the name is taken).
+pub fn attach_to_note(walkthrough: &Path, note_id: &str, source: &Path) -> Result<NoteAttachment, String> {
+    if !source.is_file() {
+        return Err(format!("Attachment does not exist: {}", source.display()));
+    }
+    let note_dir = note_dir_for(walkthrough, note_id)?;
+    fs::create_dir_all(&note_dir).map_err(|e| format!("Failed to create attachment folder: {}", e))?;
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("Invalid attachment file name: {}", source.display()))?;
+    let (target, _) = resolve_target(&note_dir.join(file_name), ConflictPolicy::RenameWithSuffix)?;
+    fs::copy(source, &target).map_err(|e| format!("Failed to copy attachment: {}", e))?;
+    note_attachment(walkthrough, &target)
+}
+
+/// Files attached to a walkthrough note, by name
+pub fn note_attachments(walkthrough: &Path, note_id: &str) -> Result<Vec<NoteAttachment>, String> {
+    let mut attachments = asset_files(&note_dir_for(walkthrough, note_id)?)
+        .iter()
+        .map(|path| note_attachment(walkthrough, path))
+        .collect::<Result<Vec<_>, _>>()?;
+    attachments.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(attachments)
+}
+
+/// Deletes one attachment of a note by file name
+pub fn delete_note_attachment(walkthrough: &Path, note_id: &str, name: &str) -> Result<(), String> {
+    let note_dir = note_dir_for(walkthrough, note_id)?;
+    let path = note_dir.join(name);
+    // A bare file name, so nothing outside the note's folder can be named
+    if Path::new(name).file_name().is_none_or(|n| n != name) || !path.is_file() {
+        return Err(format!("Not an attachment of this note: {}", name));
+    }
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete attachment {}: {}", name, e))?;
+    remove_empty_note_dirs(&note_dir);
+    Ok(())
+}
+
+/// Deletes all of a note's attachments
+pub fn remove_note_attachments(walkthrough: &Path, note_id: &str) -> Result<(), String> {
+    let note_dir = note_dir_for(walkthrough, note_id)?;
+    if note_dir.exists() {
+        fs::remove_dir_all(&note_dir).map_err(|e| format!("Failed to delete note attachments: {}", e))?;
+    }
+    remove_empty_note_dirs(&note_dir);
+    Ok(())
+}
+
+// `.bluekit/assets/<walkthrough stem>/notes/<note id>`
+fn note_dir_for(walkthrough: &Path, note_id: &str) -> Result<PathBuf, String> {
+    Ok(asset_dir_for(walkthrough)?.join(NOTES_DIR).join(note_id))
+}
+
+// Removes the note's folder and the `notes` and asset folders above it,
+// as far as they are empty
+fn remove_empty_note_dirs(note_dir: &Path) {
+    for dir in note_dir.ancestors().take(3) {
+        if fs::remove_dir(dir).is_err() && dir.exists() {
+            break;
+        }
+    }
+}
+
+fn note_attachment(walkthrough: &Path, path: &Path) -> Result<NoteAttachment, String> {
+    let relative_path = relative_link(walkthrough, path)?;
+    let name = paths::file_name_lossy(path);
+    let is_image = is_image(path);
+    let link = relative_path.replace(' ', "%20");
+    let markdown = if is_image {
+        format!("![{}]({})", paths::file_stem_lossy(path), link)
+    } else {
+        format!("[{}]({})", name, link)
+    };
+    Ok(NoteAttachment {
+        path: paths::to_ipc(path),
+        size: fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        name,
+        relative_path,
+        is_image,
+        markdown,
+    })
+}
+
+/// Attachments in `.bluekit/assets/*/` that nothing links to. Every file in
+/// `.bluekit` outside the assets folder is scanned, ignored or not, so an
+/// attachment stays referenced when its artifact is renamed or another
+/// artifact links to it.
+pub fn orphaned_assets(bluekit_dir: &Path) -> Vec<PathBuf> {
+    let assets_dir = bluekit_dir.join(ASSETS_DIR);
+    let Ok(entries) = fs::read_dir(&assets_dir) else {
+        return Vec::new();
+    };
+    let attachments: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .flat_map(|asset_dir| asset_files(&asset_dir))
+        .collect();
+    if attachments.is_empty() {
+        return Vec::new();
+    }
+
+    let referenced = linked_assets(bluekit_dir, &assets_dir);
+    attachments
+        .into_iter()
+        .filter(|asset| !referenced.contains(&paths::canonical_lenient(asset)))
+        .collect()
+}
+
+// Canonical paths inside `assets_dir` that markdown links in the rest of
+// `.bluekit` point at
+fn linked_assets(bluekit_dir: &Path, assets_dir: &Path) -> HashSet<PathBuf> {
+    let link = Regex::new(r"(?m)\]\(\s*(?:<([^>]+)>|([^)\s]+))|^[ \t]*\[[^\]]+\]:[ \t]*<?([^>\s]+)")
+        .expect("valid link pattern");
+    let project_root = bluekit_dir.parent().unwrap_or(bluekit_dir);
+    let assets_dir = paths::canonical_lenient(assets_dir);
+
+    let mut linked = HashSet::new();
+    for file in paths::files_under(bluekit_dir) {
+        if paths::canonical_lenient(&file).starts_with(&assets_dir) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let file_dir = file.parent().unwrap_or(bluekit_dir);
+        for caps in link.captures_iter(&content) {
+            let Some(target) = caps.get(1).or_else(|| caps.get(2)).or_else(|| caps.get(3)) else {
+                continue;
+            };
+            let target = target.as_str().split(['#', '?']).next().unwrap_or_default();
+            let target = target.replace("%20", " ");
+            let target = target.strip_prefix("file://").unwrap_or(&target);
+            if target.is_empty() || target.contains("://") {
+                continue;
+            }
+
+            // A leading `/` may be project-root-relative or absolute
+            let mut candidates = vec![file_dir.join(target)];
+            if let Some(from_root) = target.strip_prefix('/') {
+                candidates.push(project_root.join(from_root));
+            }
+            linked.extend(
+                candidates
+                    .iter()
+                    .map(|path| paths::canonical_lenient(path))
+                    .filter(|path| path.starts_with(&assets_dir)),
+            );
+        }
+    }
+    linked
+}
+
+/// Whether `path` is an attachment (a file in a folder under
+/// `.bluekit/assets`).
+pub fn is_attachment(path: &Path) -> bool {
+    path.parent()
+        .and_then(Path::parent)
+        .is_some_and(|assets| {
+            assets.file_name().is_some_and(|n| n == ASSETS_DIR)
+                && assets.parent().and_then(Path::file_name).is_some_and(|n| n == ".bluekit")
+        })
+}
+
+// `.bluekit/assets/<artifact stem>` for an artifact inside `.bluekit`
+fn asset_dir_for(artifact: &Path) -> Result<PathBuf, String> {
+    let bluekit_dir = bluekit_dir_of(artifact)?;
+    Ok(bluekit_dir
+        .join(ASSETS_DIR)
+        .join(paths::file_stem_lossy(artifact)))
+}
+
+// `asset` relative to the artifact's folder, `/`-separated
+fn relative_link(artifact: &Path, asset: &Path) -> Result<String, String> {
+    let bluekit_dir = bluekit_dir_of(artifact)?;
+    let artifact_dir = artifact.parent().unwrap_or(&bluekit_dir);
+    let depth = artifact_dir
+        .strip_prefix(&bluekit_dir)
+        .map(|p| p.components().count())
+        .map_err(|_| format!("{} is not inside .bluekit", artifact.display()))?;
+    let from_root = asset
+        .strip_prefix(&bluekit_dir)
+        .map_err(|_| format!("{} is not inside .bluekit", asset.display()))?;
+
+    let mut link = "../".repeat(depth);
+    link.push_str(&from_root.to_string_lossy().replace('\\', "/"));
+    Ok(link)
+}
+
+// Points markdown links to `source` at `relative_path`; returns how many
+// changed
+fn rewrite_links(artifact: &Path, source: &Path, relative_path: &str) -> Result<usize, String> {
+    let content = fs::read_to_string(artifact)
+        .map_err(|e| format!("Failed to read artifact {}: {}", artifact.display(), e))?;
+    let artifact_dir = artifact.parent().unwrap_or(Path::new(""));
+    let source = fs::canonicalize(source).unwrap_or_else(|_| source.to_path_buf());
+
+    let link = Regex::new(r"\]\(\s*<?([^)<>\s]+)>?").expect("valid link pattern");
+    let mut rewritten = 0;
+    let updated = link.replace_all(&content, |caps: &regex::Captures| {
+        let target = caps[1].replace("%20", " ");
+        let target = target.strip_prefix("file://").unwrap_or(&target);
+        let resolved = artifact_dir.join(target);
+        let resolved = fs::canonicalize(&resolved).unwrap_or(resolved);
+        if resolved == source {
+            rewritten += 1;
+            format!("]({}", relative_path)
+        } else {
+            caps[0].to_string()
+        }
+    });
+
+    if rewritten > 0 {
+        // Write next to the artifact and rename over it, so a failed write
+        // never leaves a truncated artifact behind
+        let mut tmp_name = artifact.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = artifact.with_file_name(tmp_name);
+        fs::write(&tmp_path, updated.as_bytes())
+            .map_err(|e| format!("Failed to write artifact {}: {}", artifact.display(), e))?;
+        fs::rename(&tmp_path, artifact).map_err(|e| {
+            let _ = fs::remove_file(&tmp_path);
+            format!("Failed to write artifact {}: {}", artifact.display(), e)
+        })?;
+    }
+    Ok(rewritten)
+}
+
+// Whether markdown `content` links to `relative_path`
+fn links_to(content: &str, relative_path: &str) -> bool {
+    content.contains(relative_path) || content.contains(&relative_path.replace(' ', "%20"))
+}
+
+fn asset_files(asset_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(asset_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| crate::commands::IMAGE_ASSET_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+fn bluekit_dir_of(path: &Path) -> Result<PathBuf, String> {
+    path.ancestors()
+        .find(|p| p.file_name().is_some_and(|n| n == ".bluekit"))
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("Path is not within a .bluekit directory: {}", path.display()))
+}
@@ -0,0 +1,213 @@
+//! Links to files that no longer exist, for `check_broken_links`.
+//!
+//! Covers the link graph's links (relative links, reference definitions,
+//! link stubs and wikilinks) plus the `taskFile` of every blueprint task.
+//! Each broken link comes with suggested replacements: existing files whose
+//! names are closest to the missing one, already written the way the link
+//! needs them, so the frontend can offer one-click fixes.
+
+use crate::core::link_graph::ArtifactLink;
+use crate::core::paths;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Suggestions returned per broken link
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A possible fix for a broken link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkSuggestion {
+    /// The existing file
+    pub path: String,
+    /// Text to put in place of the broken `href`
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenLink {
+    /// The artifact (or blueprint.json) containing the link
+    pub source: String,
+    /// 1-based line; none for blueprint task files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// "inline", "reference", "stub", "wikilink" or "taskFile"
+    pub kind: String,
+    /// The link as written
+    pub href: String,
+    pub text: String,
+    /// Where the link pointed (empty for unresolved wikilinks)
+    pub target: String,
+    /// Nearest matches, best first
+    pub suggestions: Vec<LinkSuggestion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenLinkReport {
+    #[serde(rename = "filesChecked")]
+    pub files_checked: usize,
+    #[serde(rename = "linksChecked")]
+    pub links_checked: usize,
+    pub broken: Vec<BrokenLink>,
+}
+
+/// Checks `links` (from the link graph, over `artifacts`) and the project's
+/// blueprints for missing targets.
+pub fn check(project_root: &Path, links: Vec<(PathBuf, ArtifactLink)>, artifacts: &[PathBuf]) -> BrokenLinkReport {
+    let bluekit_dir = project_root.join(".bluekit");
+    let candidates = paths::files_under(&bluekit_dir);
+
+    let mut report = BrokenLinkReport {
+        files_checked: artifacts.len(),
+        links_checked: links.len(),
+        broken: Vec::new(),
+    };
+
+    for (source, link) in links {
+        // Targets can disappear after the source was graphed
+        if !link.target.is_empty() && paths::from_ipc(&link.target).exists() {
+            continue;
+        }
+        let suggestions = suggest(&source, &link, &candidates);
+        report.broken.push(BrokenLink {
+            source: paths::to_ipc(&source),
+            line: Some(link.line),
+            kind: link.kind,
+            href: link.href,
+            text: link.text,
+            target: link.target,
+            suggestions,
+        });
+    }
+
+    check_blueprints(&bluekit_dir, &mut report);
+
+    report.broken.sort_by(|a, b| a.source.cmp(&b.source).then(a.line.cmp(&b.line)));
+    report
+}
+
+// Adds every blueprint task whose task file is missing
+fn check_blueprints(bluekit_dir: &Path, report: &mut BrokenLinkReport) {
+    let Ok(entries) = fs::read_dir(bluekit_dir.join("blueprints")) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let blueprint_dir = entry.path();
+        let blueprint_json = blueprint_dir.join("blueprint.json");
+        let Ok(content) = fs::read_to_string(&blueprint_json) else {
+            continue;
+        };
+        let Ok(metadata) = serde_json::from_str::<crate::commands::BlueprintMetadata>(&content) else {
+            continue; // Invalid blueprints are reported by validation
+        };
+
+        let mut task_files = paths::files_under(&blueprint_dir);
+        task_files.retain(|path| path.extension().is_some_and(|ext| ext == "md"));
+
+        for task in metadata.layers.iter().flat_map(|layer| &layer.tasks) {
+            report.links_checked += 1;
+            let target = blueprint_dir.join(&task.task_file);
+            if target.exists() {
+                continue;
+            }
+            let suggestions = nearest(&target, &task_files)
+                .into_iter()
+                .map(|path| LinkSuggestion {
+                    replacement: relative_href(&blueprint_dir, &path),
+                    path: paths::to_ipc(&path),
+                })
+                .collect();
+            report.broken.push(BrokenLink {
+                source: paths::to_ipc(&blueprint_json),
+                line: None,
+                kind: "taskFile".to_string(),
+                href: task.task_file.clone(),
+                text: task.description.clone(),
+                target: paths::to_ipc(&target),
+                suggestions,
+            });
+        }
+    }
+}
+
+// Replacements for a broken graph link, written the way its kind needs
+fn suggest(source: &Path, link: &ArtifactLink, candidates: &[PathBuf]) -> Vec<LinkSuggestion> {
+    // An unresolved wikilink is missing a name rather than a path
+    let missing = if link.kind == "wikilink" {
+        let name = link.href.trim_start_matches("[[").trim_end_matches("]]");
+        PathBuf::from(format!("{}.md", name.rsplit('/').next().unwrap_or(name)))
+    } else {
+        paths::from_ipc(&link.target)
+    };
+    let fragment = link.href.find('#').map(|i| &link.href[i..]).unwrap_or("");
+    let source_dir = source.parent().unwrap_or(Path::new(""));
+
+    nearest(&missing, candidates)
+        .into_iter()
+        .map(|path| {
+            let replacement = match link.kind.as_str() {
+                "wikilink" => paths::file_stem_lossy(&path),
+                "stub" => paths::to_ipc(&path),
+                _ => format!("{}{}", relative_href(source_dir, &path), fragment),
+            };
+            LinkSuggestion {
+                path: paths::to_ipc(&path),
+                replacement,
+            }
+        })
+        .collect()
+}
+
+// Up to MAX_SUGGESTIONS candidates with the same extension as `missing`,
+// closest file name first; names too different to be a typo or rename are
+// left out
+fn nearest(missing: &Path, candidates: &[PathBuf]) -> Vec<PathBuf> {
+    let name = paths::file_stem_lossy(missing).to_lowercase();
+    let extension = missing.extension();
+
+    let mut scored: Vec<(usize, &PathBuf)> = candidates
+        .iter()
+        .filter(|path| path.extension() == extension)
+        .filter_map(|path| {
+            let stem = paths::file_stem_lossy(path).to_lowercase();
+            let distance = if stem.contains(&name) || name.contains(&stem) {
+                stem.len().abs_diff(name.len()).min(edit_distance(&stem, &name))
+            } else {
+                edit_distance(&stem, &name)
+            };
+            (distance <= (name.chars().count() / 3).max(2)).then_some((distance, path))
+        })
+        .collect();
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, path)| path.clone())
+        .collect()
+}
+
+// Levenshtein distance in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// `to` relative to `from_dir`, `/`-separated
+fn relative_href(from_dir: &Path, to: &Path) -> String {
+    let from: Vec<Component> = from_dir.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().map(|c| c.as_os_str().to_string_lossy().into_owned()));
+    parts.join("/")
+}
@@ -0,0 +1,89 @@
+//! Cancellation registry for long-running operations.
+//!
+//! Operations register a token under their operation id (the same id their
+//! progress events carry) and check it between steps. `cancel_operation`
+//! flips the token; the operation notices at its next check, cleans up its
+//! partial output and fails with [`CANCELLED_ERROR`].
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Error returned by operations that stopped because they were cancelled
+pub const CANCELLED_ERROR: &str = "Operation cancelled";
+
+static REGISTRY: once_cell::sync::Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Cancellation flag for one operation.
+///
+/// Registered tokens are removed from the registry when dropped.
+pub struct CancellationToken {
+    operation_id: Option<String>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Registers a token that `cancel` can reach by `operation_id`.
+    pub fn register(operation_id: &str) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        lock_registry().insert(operation_id.to_string(), cancelled.clone());
+        Self {
+            operation_id: Some(operation_id.to_string()),
+            cancelled,
+        }
+    }
+
+    /// A token nothing can cancel, for operations without a caller to ask.
+    pub fn none() -> Self {
+        Self {
+            operation_id: None,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Fails with [`CANCELLED_ERROR`] once the operation has been cancelled.
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED_ERROR.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Drop for CancellationToken {
+    fn drop(&mut self) {
+        if let Some(operation_id) = &self.operation_id {
+            let mut registry = lock_registry();
+            // Only remove our own entry; the id may have been reused since
+            if registry
+                .get(operation_id)
+                .is_some_and(|flag| Arc::ptr_eq(flag, &self.cancelled))
+            {
+                registry.remove(operation_id);
+            }
+        }
+    }
+}
+
+/// Requests cancellation of a running operation.
+///
+/// Returns false if no operation with that id is running.
+pub fn cancel(operation_id: &str) -> bool {
+    match lock_registry().get(operation_id) {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+fn lock_registry() -> std::sync::MutexGuard<'static, HashMap<String, Arc<AtomicBool>>> {
+    REGISTRY.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,71 @@
+//! Checksum-verified file copies.
+//!
+//! Copies hash the source while streaming it and then re-read the written
+//! file, so a short write or a corrupted destination is reported instead of
+//! passing as success. Files are copied as bytes, so binary assets survive
+//! unchanged.
+
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// SHA-256 of a file's bytes as lowercase hex.
+pub fn hash_file(path: &Path) -> io::Result<String> {
+    let mut reader = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Copies `source` to `target` and checks the written file against the
+/// source's checksum.
+///
+/// I/O failures are errors; a checksum mismatch returns `Ok(false)` so
+/// directory copies can collect every bad file before failing.
+pub fn copy_verified(source: &Path, target: &Path) -> Result<bool, String> {
+    let source_hash = crate::library::utils::copy_file_with_hash(source, target).map_err(|e| {
+        format!("Failed to copy {} to {}: {}", source.display(), target.display(), e)
+    })?;
+    let destination_hash = hash_file(target)
+        .map_err(|e| format!("Failed to verify {}: {}", target.display(), e))?;
+
+    if source_hash != destination_hash {
+        tracing::warn!(
+            "Checksum mismatch copying {} to {} (source {}, destination {})",
+            source.display(),
+            target.display(),
+            source_hash,
+            destination_hash
+        );
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Error listing the files whose copies failed verification, or `Ok` if
+/// there were none.
+pub fn check_mismatches(mismatches: &[PathBuf]) -> Result<(), String> {
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+    let files = mismatches
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(format!(
+        "Copy verification failed: {} file(s) differ from the source after copying: {}",
+        mismatches.len(),
+        files
+    ))
+}
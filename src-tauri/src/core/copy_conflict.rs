@@ -0,0 +1,73 @@
+//! What the copy commands do when the target already exists.
+//!
+//! `copy_resources_to_project` and `copy_blueprint_to_project` take an
+//! `onConflict` policy and report a [`CopyAction`] for what happened to each
+//! file. Without a policy, existing targets are overwritten as before.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// How to handle a copy whose target already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictPolicy {
+    /// Replace the existing target
+    #[default]
+    Overwrite,
+    /// Leave the existing target alone and don't copy
+    Skip,
+    /// Copy next to it as `name-2.ext`, `name-3.ext`, ...
+    RenameWithSuffix,
+    /// Fail the copy
+    Fail,
+}
+
+/// What a copy actually did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyAction {
+    /// Copied to a target that didn't exist
+    Copied,
+    /// Replaced an existing target
+    Overwritten,
+    /// Target existed, nothing was copied
+    Skipped,
+    /// Target existed, copied under a suffixed name instead
+    Renamed,
+}
+
+/// Applies `policy` to `target`, returning where to copy and what that
+/// copy will be. For [`CopyAction::Skipped`] the returned path is the
+/// existing target and nothing should be written.
+pub fn resolve_target(target: &Path, policy: ConflictPolicy) -> Result<(PathBuf, CopyAction), String> {
+    if !target.exists() {
+        return Ok((target.to_path_buf(), CopyAction::Copied));
+    }
+
+    match policy {
+        ConflictPolicy::Overwrite => Ok((target.to_path_buf(), CopyAction::Overwritten)),
+        ConflictPolicy::Skip => Ok((target.to_path_buf(), CopyAction::Skipped)),
+        ConflictPolicy::RenameWithSuffix => Ok((suffixed_path(target), CopyAction::Renamed)),
+        ConflictPolicy::Fail => Err(format!("Target already exists: {}", target.display())),
+    }
+}
+
+// First free `stem-N.ext` next to `target`, starting at 2
+fn suffixed_path(target: &Path) -> PathBuf {
+    let stem = crate::core::paths::file_stem_lossy(target);
+    let extension = target.extension().map(|e| e.to_string_lossy().into_owned());
+    let parent = target.parent().unwrap_or_else(|| Path::new(""));
+
+    let mut suffix = 2;
+    loop {
+        let name = match &extension {
+            Some(extension) => format!("{}-{}.{}", stem, suffix, extension),
+            None => format!("{}-{}", stem, suffix),
+        };
+        let candidate = parent.join(name);
+        if !candidate.exists() {
+            return candidate;
+        }
+        suffix += 1;
+    }
+}
@@ -0,0 +1,326 @@
+//! Where BlueKit keeps its data: the database, the project registry, the
+//! shared artifact pool and backups.
+//!
+//! The directory is `$BLUEKIT_DATA_DIR` when set, else `storage.dataDirectory`
+//! from the settings, else `~/.bluekit`. The settings file itself always
+//! stays in `~/.bluekit`, since that is where the setting is read from.
+//!
+//! The directory is resolved once per session. [`set`] changes the setting
+//! for the next launch and, to bring the data along, leaves a marker in the
+//! new directory naming the old one. [`apply_pending_migration`] moves the
+//! data at startup, before the database is opened.
+
+use crate::core::paths;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable that overrides the configured data directory
+pub const ENV_VAR: &str = "BLUEKIT_DATA_DIR";
+
+/// File in the new directory naming the directory to move data from
+const MIGRATION_MARKER: &str = ".migrate-from";
+
+/// Entries of the data directory that are never moved: the settings file
+/// lives in `~/.bluekit` regardless, SQLite recreates `-shm` files, and the
+/// starter project is registered by its absolute path, so it stays where
+/// the registry points
+const NOT_MOVED: &[&str] = &[
+    "settings.json",
+    "bluekit.db-shm",
+    MIGRATION_MARKER,
+    crate::core::starter_content::STARTER_DIR,
+];
+
+static SESSION_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDirectory {
+    /// The directory in use this session
+    pub path: String,
+    /// The directory used from the next launch
+    #[serde(rename = "configuredPath")]
+    pub configured_path: String,
+    /// "env", "settings" or "default", for the configured directory
+    pub source: String,
+    #[serde(rename = "defaultPath")]
+    pub default_path: String,
+    /// Data moved into the configured directory on the next launch
+    #[serde(rename = "pendingMigrationFrom")]
+    pub pending_migration_from: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataEntry {
+    pub name: String,
+    #[serde(rename = "sizeBytes")]
+    pub size_bytes: u64,
+    /// The target already has an entry with this name
+    pub conflict: bool,
+}
+
+/// What moving the data to a new directory involves, for the frontend to
+/// show before calling `set_data_directory`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataMigrationPlan {
+    pub from: String,
+    pub to: String,
+    pub entries: Vec<DataEntry>,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    /// Free space on the target's volume, if it could be read
+    #[serde(rename = "availableBytes")]
+    pub available_bytes: Option<u64>,
+    /// Reasons the move would be refused; empty when it can go ahead
+    pub problems: Vec<String>,
+}
+
+/// `~/.bluekit`
+pub fn default_dir() -> Result<PathBuf, String> {
+    let home_dir = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Could not determine home directory".to_string())?;
+
+    Ok(PathBuf::from(home_dir).join(".bluekit"))
+}
+
+/// The data directory for this session, created if needed.
+///
+/// A configured directory whose parent is missing (an unmounted drive, say)
+/// is an error rather than being created in its place.
+pub fn data_dir() -> Result<PathBuf, String> {
+    let dir = match SESSION_DIR.get() {
+        Some(dir) => dir.clone(),
+        None => {
+            let (dir, _) = configured()?;
+            SESSION_DIR.get_or_init(|| dir).clone()
+        }
+    };
+
+    if !dir.exists() {
+        if dir != default_dir()? && !dir.parent().is_some_and(Path::exists) {
+            return Err(format!("Data directory is not available: {}", dir.display()));
+        }
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create data directory {}: {}", dir.display(), e))?;
+    }
+    Ok(dir)
+}
+
+/// The data directory in use and the one configured for the next launch
+pub fn status() -> Result<DataDirectory, String> {
+    let path = data_dir()?;
+    let (configured_path, source) = configured()?;
+    Ok(DataDirectory {
+        path: paths::to_ipc(&path),
+        pending_migration_from: fs::read_to_string(configured_path.join(MIGRATION_MARKER))
+            .ok()
+            .map(|from| from.trim().to_string()),
+        configured_path: paths::to_ipc(&configured_path),
+        source: source.to_string(),
+        default_path: paths::to_ipc(&default_dir()?),
+    })
+}
+
+/// Lists what moving the data to `target` (the default directory when none)
+/// would move, and why it would be refused.
+pub fn preview_change(target: Option<&str>) -> Result<DataMigrationPlan, String> {
+    let from = data_dir()?;
+    let to = target_dir(target)?;
+
+    let mut problems = Vec::new();
+    if env_override().is_some() {
+        problems.push(format!("{} is set and overrides the setting", ENV_VAR));
+    }
+    if to == from {
+        problems.push("This is already the data directory".to_string());
+    } else if to.starts_with(&from) || from.starts_with(&to) {
+        problems.push("The directories can't be inside one another".to_string());
+    }
+
+    let mut entries = Vec::new();
+    for path in movable_entries(&from)? {
+        let name = paths::file_name_lossy(&path);
+        entries.push(DataEntry {
+            conflict: to.join(&name).exists(),
+            size_bytes: size_of(&path),
+            name,
+        });
+    }
+    let conflicts: Vec<&str> = entries
+        .iter()
+        .filter(|entry| entry.conflict)
+        .map(|entry| entry.name.as_str())
+        .collect();
+    if !conflicts.is_empty() {
+        problems.push(format!(
+            "The target already contains {}; move them away, or switch without moving to use the data there",
+            conflicts.join(", ")
+        ));
+    }
+
+    let total_bytes = entries.iter().map(|entry| entry.size_bytes).sum();
+    // The target may not exist yet; its nearest existing ancestor is on the same volume
+    let available_bytes = to
+        .ancestors()
+        .find(|dir| dir.exists())
+        .and_then(|dir| fs2::available_space(dir).ok());
+    if available_bytes.is_some_and(|available| available < total_bytes) {
+        problems.push("Not enough free space on the target volume".to_string());
+    }
+
+    Ok(DataMigrationPlan {
+        from: paths::to_ipc(&from),
+        to: paths::to_ipc(&to),
+        entries,
+        total_bytes,
+        available_bytes,
+        problems,
+    })
+}
+
+/// Makes `target` (the default directory when none) the data directory
+/// from the next launch. With `migrate`, the current data is moved there
+/// at that launch; without it, the data already in `target` is used.
+pub fn set(target: Option<&str>, migrate: bool) -> Result<DataDirectory, String> {
+    let to = target_dir(target)?;
+    if migrate {
+        let plan = preview_change(target)?;
+        if !plan.problems.is_empty() {
+            return Err(plan.problems.join("; "));
+        }
+    } else if env_override().is_some() {
+        return Err(format!("{} is set and overrides the setting", ENV_VAR));
+    }
+
+    fs::create_dir_all(&to)
+        .map_err(|e| format!("Failed to create data directory {}: {}", to.display(), e))?;
+    // A move planned into another directory no longer applies
+    let (previous, _) = configured()?;
+    if previous != to {
+        let _ = fs::remove_file(previous.join(MIGRATION_MARKER));
+    }
+    let marker = to.join(MIGRATION_MARKER);
+    if migrate {
+        fs::write(&marker, paths::to_ipc(&data_dir()?))
+            .map_err(|e| format!("Failed to prepare the data migration: {}", e))?;
+    } else if marker.exists() {
+        fs::remove_file(&marker).map_err(|e| format!("Failed to cancel the data migration: {}", e))?;
+    }
+
+    let mut settings = crate::core::settings::load_settings();
+    settings.storage.data_directory = (to != default_dir()?).then(|| paths::to_ipc(&to));
+    if let Err(e) = crate::core::settings::save_settings(&settings) {
+        let _ = fs::remove_file(&marker);
+        return Err(e);
+    }
+
+    tracing::info!("Data directory set to {} from the next launch", to.display());
+    status()
+}
+
+/// Moves the data named by a pending migration into the data directory.
+/// Call at startup, before anything in the directory is opened.
+///
+/// Everything is copied before anything is removed, so a failure leaves
+/// the old directory complete; the marker stays and the move is retried
+/// on the next launch.
+pub fn apply_pending_migration() -> Result<(), String> {
+    let to = data_dir()?;
+    let marker = to.join(MIGRATION_MARKER);
+    let Ok(from) = fs::read_to_string(&marker) else {
+        return Ok(());
+    };
+    let from = paths::from_ipc(from.trim());
+
+    if from.exists() {
+        let entries = movable_entries(&from)?;
+        for entry in &entries {
+            copy_recursive(entry, &to.join(paths::file_name_lossy(entry)))?;
+        }
+        for entry in &entries {
+            let removed = if entry.is_dir() {
+                fs::remove_dir_all(entry)
+            } else {
+                fs::remove_file(entry)
+            };
+            if let Err(e) = removed {
+                tracing::warn!("Failed to remove {} after moving it: {}", entry.display(), e);
+            }
+        }
+        tracing::info!("Moved {} entries from {} to {}", entries.len(), from.display(), to.display());
+    } else {
+        tracing::warn!("Data to migrate is gone: {}", from.display());
+    }
+
+    fs::remove_file(&marker).map_err(|e| format!("Failed to finish the data migration: {}", e))
+}
+
+// The configured directory and where its setting came from
+fn configured() -> Result<(PathBuf, &'static str), String> {
+    if let Some(dir) = env_override() {
+        return Ok((dir, "env"));
+    }
+    match crate::core::settings::load_settings().storage.data_directory {
+        Some(dir) if !dir.trim().is_empty() => Ok((paths::from_ipc(dir.trim()), "settings")),
+        _ => Ok((default_dir()?, "default")),
+    }
+}
+
+fn env_override() -> Option<PathBuf> {
+    std::env::var_os(ENV_VAR)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+fn target_dir(target: Option<&str>) -> Result<PathBuf, String> {
+    let dir = match target.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(target) => paths::from_ipc(target),
+        None => return default_dir(),
+    };
+    if !dir.is_absolute() {
+        return Err(format!("Data directory must be an absolute path: {}", dir.display()));
+    }
+    if dir.is_file() {
+        return Err(format!("Not a directory: {}", dir.display()));
+    }
+    Ok(dir)
+}
+
+// Entries of `dir` that belong to the data, in name order
+fn movable_entries(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| !NOT_MOVED.contains(&paths::file_name_lossy(path).as_str()))
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> Result<(), String> {
+    if from.is_dir() {
+        fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+        let entries = fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+        for entry in entries.flatten() {
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy {} to {}: {}", from.display(), to.display(), e))
+    }
+}
+
+fn size_of(path: &Path) -> u64 {
+    if path.is_dir() {
+        fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|entry| size_of(&entry.path())).sum())
+            .unwrap_or(0)
+    } else {
+        fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+    }
+}
@@ -0,0 +1,212 @@
+//! Line diffs between two texts, for `diff_files`.
+//!
+//! A diff comes back both as unified diff text and as hunks of typed lines,
+//! so the frontend can render it without a diff library of its own.
+
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+
+/// Unchanged lines shown around each change by default
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// One line of a hunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    /// "context", "added" or "removed"
+    pub kind: String,
+    /// 1-based line number in the old text (none for added lines)
+    #[serde(rename = "oldLine")]
+    pub old_line: Option<usize>,
+    /// 1-based line number in the new text (none for removed lines)
+    #[serde(rename = "newLine")]
+    pub new_line: Option<usize>,
+    /// The line without its line ending
+    pub content: String,
+}
+
+/// A run of changes with the context around them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffHunk {
+    #[serde(rename = "oldStart")]
+    pub old_start: usize,
+    #[serde(rename = "oldLines")]
+    pub old_lines: usize,
+    #[serde(rename = "newStart")]
+    pub new_start: usize,
+    #[serde(rename = "newLines")]
+    pub new_lines: usize,
+    /// The `@@ -a,b +c,d @@` line
+    pub header: String,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiff {
+    #[serde(rename = "oldLabel")]
+    pub old_label: String,
+    #[serde(rename = "newLabel")]
+    pub new_label: String,
+    pub identical: bool,
+    pub additions: usize,
+    pub deletions: usize,
+    /// Unified diff text; empty when identical
+    pub unified: String,
+    pub hunks: Vec<DiffHunk>,
+}
+
+/// Diffs `old` against `new` line by line. The labels name the two sides
+/// in the unified diff header.
+pub fn diff_text(old: &str, new: &str, old_label: &str, new_label: &str, context_lines: usize) -> FileDiff {
+    let diff = TextDiff::from_lines(old, new);
+
+    let mut additions = 0;
+    let mut deletions = 0;
+    let mut hunks = Vec::new();
+    for group in diff.grouped_ops(context_lines) {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+
+        let mut lines = Vec::new();
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let kind = match change.tag() {
+                    ChangeTag::Equal => "context",
+                    ChangeTag::Insert => {
+                        additions += 1;
+                        "added"
+                    }
+                    ChangeTag::Delete => {
+                        deletions += 1;
+                        "removed"
+                    }
+                };
+                lines.push(DiffLine {
+                    kind: kind.to_string(),
+                    old_line: change.old_index().map(|i| i + 1),
+                    new_line: change.new_index().map(|i| i + 1),
+                    content: change.value().trim_end_matches(['\n', '\r']).to_string(),
+                });
+            }
+        }
+
+        let old_start = hunk_start(old_range.start, old_range.len());
+        let new_start = hunk_start(new_range.start, new_range.len());
+        hunks.push(DiffHunk {
+            old_start,
+            old_lines: old_range.len(),
+            new_start,
+            new_lines: new_range.len(),
+            header: format!(
+                "@@ -{},{} +{},{} @@",
+                old_start,
+                old_range.len(),
+                new_start,
+                new_range.len()
+            ),
+            lines,
+        });
+    }
+
+    let identical = hunks.is_empty();
+    let unified = if identical {
+        String::new()
+    } else {
+        diff.unified_diff()
+            .context_radius(context_lines)
+            .header(old_label, new_label)
+            .to_string()
+    };
+
+    FileDiff {
+        old_label: old_label.to_string(),
+        new_label: new_label.to_string(),
+        identical,
+        additions,
+        deletions,
+        unified,
+        hunks,
+    }
+}
+
+// Unified diffs number an empty range by the line before it
+fn hunk_start(start: usize, len: usize) -> usize {
+    if len == 0 {
+        start
+    } else {
+        start + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(diff: &FileDiff) -> Vec<&str> {
+        diff.hunks.iter().map(|hunk| hunk.header.as_str()).collect()
+    }
+
+    // Ten lines, with the given ones changed
+    fn numbered(changed: &[usize]) -> String {
+        (1..=10)
+            .map(|n| match changed.contains(&n) {
+                true => format!("changed {}\n", n),
+                false => format!("line {}\n", n),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_identical_texts() {
+        let diff = diff_text("a\nb\n", "a\nb\n", "old", "new", 3);
+        assert!(diff.identical);
+        assert!(diff.hunks.is_empty());
+        assert_eq!(diff.unified, "");
+        assert_eq!((diff.additions, diff.deletions), (0, 0));
+    }
+
+    #[test]
+    fn test_diff_changed_line() {
+        let diff = diff_text("a\nb\nc\n", "a\nB\nc\n", "old", "new", 3);
+        assert!(!diff.identical);
+        assert_eq!(headers(&diff), vec!["@@ -1,3 +1,3 @@"]);
+        assert_eq!((diff.additions, diff.deletions), (1, 1));
+        assert_eq!(diff.unified.matches("\n@@ ").count(), 1);
+        assert!(diff.hunks[0].lines.iter().all(|line| !line.content.ends_with('\n')));
+    }
+
+    #[test]
+    fn test_diff_empty_ranges_use_the_line_before() {
+        let added = diff_text("", "x\n", "old", "new", 3);
+        assert_eq!(headers(&added), vec!["@@ -0,0 +1,1 @@"]);
+        assert_eq!((added.additions, added.deletions), (1, 0));
+
+        let removed = diff_text("x\n", "", "old", "new", 3);
+        assert_eq!(headers(&removed), vec!["@@ -1,1 +0,0 @@"]);
+        assert_eq!((removed.additions, removed.deletions), (0, 1));
+
+        let inserted = diff_text("a\nb\n", "a\nx\nb\n", "old", "new", 0);
+        assert_eq!(headers(&inserted), vec!["@@ -1,0 +2,1 @@"]);
+    }
+
+    #[test]
+    fn test_diff_context_lines_split_and_merge_hunks() {
+        let split = diff_text(&numbered(&[]), &numbered(&[2, 9]), "old", "new", 1);
+        assert_eq!(headers(&split), vec!["@@ -1,3 +1,3 @@", "@@ -8,3 +8,3 @@"]);
+        assert_eq!((split.additions, split.deletions), (2, 2));
+        assert_eq!(split.unified.matches("\n@@ ").count(), 2);
+
+        let merged = diff_text(&numbered(&[]), &numbered(&[2, 9]), "old", "new", 3);
+        assert_eq!(headers(&merged), vec!["@@ -1,10 +1,10 @@"]);
+    }
+
+    #[test]
+    fn test_diff_without_trailing_newline() {
+        let diff = diff_text("a\nb", "a\nc", "old", "new", 3);
+        assert_eq!(headers(&diff), vec!["@@ -1,2 +1,2 @@"]);
+        assert_eq!((diff.additions, diff.deletions), (1, 1));
+        assert!(diff.hunks[0].lines.iter().all(|line| !line.content.ends_with('\n')));
+    }
+}
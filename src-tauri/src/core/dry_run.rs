@@ -0,0 +1,61 @@
+//! Previews for destructive commands.
+//!
+//! Commands that delete or overwrite data accept a `dry_run` flag. When set,
+//! they return a [`DryRunReport`] listing every file, database row and remote
+//! file the real call would touch, so the UI can confirm with the actual
+//! consequences. Without the flag they return their usual result unchanged.
+
+use serde::{Deserialize, Serialize};
+
+/// A database row that would be deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunRow {
+    pub table: String,
+    pub id: String,
+    /// Human-readable description (name or path)
+    pub label: String,
+}
+
+/// A file in a GitHub repository that would be deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunRemoteFile {
+    /// `owner/repo`
+    pub repository: String,
+    pub path: String,
+}
+
+/// Everything a destructive command would do
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DryRunReport {
+    #[serde(rename = "deletedFiles")]
+    pub deleted_files: Vec<String>,
+    #[serde(rename = "createdFiles")]
+    pub created_files: Vec<String>,
+    #[serde(rename = "overwrittenFiles")]
+    pub overwritten_files: Vec<String>,
+    #[serde(rename = "deletedRows")]
+    pub deleted_rows: Vec<DryRunRow>,
+    #[serde(rename = "deletedRemoteFiles")]
+    pub deleted_remote_files: Vec<DryRunRemoteFile>,
+}
+
+impl DryRunReport {
+    pub fn delete_row(&mut self, table: &str, id: &str, label: &str) {
+        self.deleted_rows.push(DryRunRow {
+            table: table.to_string(),
+            id: id.to_string(),
+            label: label.to_string(),
+        });
+    }
+}
+
+/// Result of a command that supports `dry_run`.
+///
+/// Serialized untagged, so `Applied` looks exactly like the command's
+/// result before dry runs existed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum DryRunOutcome<T> {
+    DryRun(DryRunReport),
+    Applied(T),
+}
@@ -0,0 +1,88 @@
+//! Text decoding for files that may not be UTF-8.
+//!
+//! Markdown exported from Windows tools or pasted from elsewhere is often
+//! UTF-16 or a legacy code page. Rather than failing, the encoding is
+//! detected (BOM first, then UTF-8 validity, then a UTF-16 heuristic, falling
+//! back to Windows-1252) and the bytes are decoded lossily, with a warning
+//! the frontend can surface.
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Decoded file contents plus how they were decoded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedText {
+    pub content: String,
+    /// WHATWG encoding label, e.g. "UTF-8", "UTF-16LE", "windows-1252"
+    pub encoding: String,
+    /// Set when the file was not clean UTF-8
+    pub warning: Option<String>,
+}
+
+/// Reads and decodes a text file.
+///
+/// With `strict`, anything other than valid UTF-8 is an error.
+pub fn read_text(path: &Path, strict: bool) -> Result<DecodedText, String> {
+    let bytes = fs::read(path)
+        .map_err(|e| format!("Failed to read file {}: {}", path.display(), e))?;
+
+    decode(&bytes, strict).map_err(|e| format!("{}: {}", e, path.display()))
+}
+
+/// Decodes `bytes`, detecting the encoding.
+pub fn decode(bytes: &[u8], strict: bool) -> Result<DecodedText, String> {
+    let (encoding, body) = match Encoding::for_bom(bytes) {
+        Some((encoding, bom_len)) => (encoding, &bytes[bom_len..]),
+        None => (detect(bytes), bytes),
+    };
+
+    if encoding == UTF_8 {
+        if let Ok(content) = std::str::from_utf8(body) {
+            return Ok(DecodedText {
+                content: content.to_string(),
+                encoding: UTF_8.name().to_string(),
+                warning: None,
+            });
+        }
+    }
+
+    if strict {
+        return Err(format!("File is not valid UTF-8 (looks like {})", encoding.name()));
+    }
+
+    let (content, had_errors) = encoding.decode_without_bom_handling(body);
+    let mut warning = format!("File is not UTF-8; decoded as {}", encoding.name());
+    if had_errors {
+        warning.push_str(" with some characters replaced");
+    }
+
+    Ok(DecodedText {
+        content: content.into_owned(),
+        encoding: encoding.name().to_string(),
+        warning: Some(warning),
+    })
+}
+
+// Guesses the encoding of BOM-less bytes
+fn detect(bytes: &[u8]) -> &'static Encoding {
+    if std::str::from_utf8(bytes).is_ok() {
+        return UTF_8;
+    }
+
+    // UTF-16 text that is mostly ASCII has a NUL in every other byte
+    if bytes.len() >= 4 && bytes.len().is_multiple_of(2) {
+        let pairs = bytes.len() / 2;
+        let even_nuls = bytes.iter().step_by(2).filter(|&&b| b == 0).count();
+        let odd_nuls = bytes.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+        if odd_nuls * 2 > pairs && even_nuls * 10 < pairs {
+            return UTF_16LE;
+        }
+        if even_nuls * 2 > pairs && odd_nuls * 10 < pairs {
+            return UTF_16BE;
+        }
+    }
+
+    WINDOWS_1252
+}
@@ -0,0 +1,162 @@
+//! Advisory in-process locks on artifact paths.
+//!
+//! Read-modify-write operations on the same file (front matter updates,
+//! editor saves) and reconciliations of the same folder (walkthrough sync,
+//! plan document scans) take a lock on that path first so they run one at a
+//! time instead of interleaving. Locks are advisory: they only coordinate
+//! code in this process, not other programs editing the files.
+//!
+//! A caller that can't get a lock within [`LOCK_TIMEOUT`] gets a
+//! [`LockConflict`], which serializes to a JSON error string the frontend can
+//! recognize by its `code` of [`LOCK_CONFLICT`]. Writes refused because the
+//! file changed on disk use the same shape with [`WRITE_CONFLICT`].
+
+use sea_orm::DbErr;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::OwnedMutexGuard;
+
+/// How long to wait for a lock before reporting a conflict
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `code` of a [`LockConflict`]
+pub const LOCK_CONFLICT: &str = "lock_conflict";
+
+/// `code` of a write refused because the file changed since it was read
+pub const WRITE_CONFLICT: &str = "write_conflict";
+
+/// Start of every serialized [`LockConflict`]
+const CONFLICT_PREFIX: &str = r#"{"code":"lock_conflict""#;
+
+struct LockEntry {
+    mutex: Arc<tokio::sync::Mutex<()>>,
+    /// Operation currently holding the lock, for conflict messages
+    holder: Mutex<Option<String>>,
+}
+
+static LOCKS: once_cell::sync::Lazy<Mutex<HashMap<PathBuf, Arc<LockEntry>>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Another operation held the lock for longer than [`LOCK_TIMEOUT`]
+#[derive(Debug, Clone, Serialize)]
+pub struct LockConflict {
+    /// Always [`LOCK_CONFLICT`]
+    pub code: String,
+    pub path: String,
+    /// Operation that held the lock, if known
+    #[serde(rename = "heldBy")]
+    pub held_by: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for LockConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // JSON so the frontend can tell conflicts apart from other failures
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl From<LockConflict> for String {
+    fn from(conflict: LockConflict) -> Self {
+        conflict.to_string()
+    }
+}
+
+impl From<LockConflict> for DbErr {
+    fn from(conflict: LockConflict) -> Self {
+        DbErr::Custom(conflict.to_string())
+    }
+}
+
+/// Formats a DB error for a command, passing lock conflicts through
+/// unprefixed so they stay parseable.
+pub fn command_error(error: DbErr, context: &str) -> String {
+    match error {
+        DbErr::Custom(message) if message.starts_with(CONFLICT_PREFIX) => message,
+        other => format!("{}: {}", context, other),
+    }
+}
+
+/// Held lock on a path; released when dropped.
+pub struct FileLock {
+    path: PathBuf,
+    entry: Arc<LockEntry>,
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        *lock_holder(&self.entry) = None;
+
+        // Forget the entry once nobody else is waiting on it
+        let mut locks = lock_registry();
+        if Arc::strong_count(&self.entry) == 2 {
+            locks.remove(&self.path);
+        }
+    }
+}
+
+/// Locks `path` for `operation`, waiting up to [`LOCK_TIMEOUT`].
+///
+/// Locks are not reentrant: taking a lock already held by the same task
+/// waits for the timeout and fails.
+pub async fn lock(path: &Path, operation: &str) -> Result<FileLock, LockConflict> {
+    let path = normalize(path);
+    let entry = lock_registry()
+        .entry(path.clone())
+        .or_insert_with(|| {
+            Arc::new(LockEntry {
+                mutex: Arc::new(tokio::sync::Mutex::new(())),
+                holder: Mutex::new(None),
+            })
+        })
+        .clone();
+
+    match tokio::time::timeout(LOCK_TIMEOUT, entry.mutex.clone().lock_owned()).await {
+        Ok(guard) => {
+            *lock_holder(&entry) = Some(operation.to_string());
+            Ok(FileLock { path, entry, _guard: guard })
+        }
+        Err(_) => {
+            let held_by = lock_holder(&entry).clone();
+            Err(LockConflict {
+                code: LOCK_CONFLICT.to_string(),
+                path: path.to_string_lossy().to_string(),
+                message: match &held_by {
+                    Some(holder) => format!(
+                        "{} is busy ({}); try again in a moment",
+                        path.display(),
+                        holder
+                    ),
+                    None => format!("{} is busy; try again in a moment", path.display()),
+                },
+                held_by,
+            })
+        }
+    }
+}
+
+// Same file through different spellings (relative, `..`, symlinked dirs)
+// must map to one lock
+fn normalize(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    // New files: canonicalize the parent and keep the name
+    match (path.parent().and_then(|p| p.canonicalize().ok()), path.file_name()) {
+        (Some(parent), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn lock_registry() -> std::sync::MutexGuard<'static, HashMap<PathBuf, Arc<LockEntry>>> {
+    LOCKS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn lock_holder(entry: &LockEntry) -> std::sync::MutexGuard<'_, Option<String>> {
+    entry.holder.lock().unwrap_or_else(|e| e.into_inner())
+}
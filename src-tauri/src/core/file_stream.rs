@@ -0,0 +1,166 @@
+//! Chunked reads of large text files.
+//!
+//! `read_file_stream` returns at once and sends the file as a series of
+//! `file-stream-chunk` events, so the UI can render a multi-megabyte
+//! artifact progressively instead of waiting on one huge IPC payload.
+//! Chunks always end on a UTF-8 character boundary. A stream is registered
+//! for cancellation under its id, so `cancel_operation` stops it.
+
+use crate::core::cancellation::CancellationToken;
+use serde::Serialize;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// Event carrying a [`FileStreamChunk`]
+pub const FILE_STREAM_EVENT: &str = "file-stream-chunk";
+
+/// Chunk size when the caller doesn't pick one
+pub const DEFAULT_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Smallest accepted chunk size (a chunk must fit any UTF-8 character)
+const MIN_CHUNK_BYTES: usize = 4 * 1024;
+
+/// One piece of a streamed file. The last event of a stream has `done` set,
+/// and carries `error` if the read failed or was cancelled.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileStreamChunk {
+    #[serde(rename = "streamId")]
+    pub stream_id: String,
+    /// 0-based chunk number
+    pub index: u64,
+    pub chunk: String,
+    /// Byte offset of this chunk in the file
+    pub offset: u64,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+/// Reads `path` in chunks of about `chunk_size` bytes on a background task,
+/// emitting one [`FILE_STREAM_EVENT`] per chunk.
+pub fn spawn_stream(
+    app_handle: AppHandle,
+    path: PathBuf,
+    stream_id: String,
+    total_bytes: u64,
+    chunk_size: usize,
+) {
+    let chunk_size = chunk_size.max(MIN_CHUNK_BYTES);
+    // Registered before the task starts so a cancel right after the
+    // command returns isn't lost
+    let cancellation = CancellationToken::register(&stream_id);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let emit = |chunk: FileStreamChunk| {
+            if let Err(e) = app_handle.emit_all(FILE_STREAM_EVENT, chunk) {
+                tracing::warn!("Failed to emit file stream chunk: {}", e);
+            }
+        };
+        let last = |index: u64, offset: u64, error: Option<String>| FileStreamChunk {
+            stream_id: stream_id.clone(),
+            index,
+            chunk: String::new(),
+            offset,
+            total_bytes,
+            done: true,
+            error,
+        };
+
+        let mut file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                emit(last(0, 0, Some(format!("Failed to open {}: {}", path.display(), e))));
+                return;
+            }
+        };
+
+        let mut buffer = vec![0u8; chunk_size];
+        // Bytes of a character split across two reads
+        let mut carry: Vec<u8> = Vec::new();
+        let mut index = 0;
+        let mut offset = 0;
+
+        loop {
+            if let Err(e) = cancellation.check() {
+                emit(last(index, offset, Some(e)));
+                return;
+            }
+
+            let read = match file.read(&mut buffer) {
+                Ok(read) => read,
+                Err(e) => {
+                    emit(last(index, offset, Some(format!("Failed to read {}: {}", path.display(), e))));
+                    return;
+                }
+            };
+            if read == 0 {
+                break;
+            }
+
+            carry.extend_from_slice(&buffer[..read]);
+            let (text, rest) = split_utf8(&carry);
+            let chunk_bytes = carry.len() - rest;
+            let chunk = FileStreamChunk {
+                stream_id: stream_id.clone(),
+                index,
+                chunk: text,
+                offset,
+                total_bytes,
+                done: false,
+                error: None,
+            };
+            emit(chunk);
+            carry.drain(..chunk_bytes);
+            index += 1;
+            offset += chunk_bytes as u64;
+        }
+
+        // Whatever is left is an incomplete character at the end of the file
+        let tail = FileStreamChunk {
+            chunk: String::from_utf8_lossy(&carry).to_string(),
+            ..last(index, offset, None)
+        };
+        emit(tail);
+    });
+}
+
+/// Decodes the longest prefix of `bytes` that doesn't end inside a
+/// character, returning the text and the number of trailing bytes left
+/// over. Invalid sequences in the middle are replaced, as in `read_file`'s
+/// lossy mode.
+fn split_utf8(bytes: &[u8]) -> (String, usize) {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => (text.to_string(), 0),
+        // `error_len() == None` means the input ended mid-character
+        Err(e) if e.error_len().is_none() => {
+            let complete = e.valid_up_to();
+            (String::from_utf8_lossy(&bytes[..complete]).to_string(), bytes.len() - complete)
+        }
+        Err(_) => {
+            // Keep a possibly split character at the very end for the next read
+            let keep = incomplete_suffix_len(bytes);
+            (String::from_utf8_lossy(&bytes[..bytes.len() - keep]).to_string(), keep)
+        }
+    }
+}
+
+// Length of a trailing, unfinished UTF-8 sequence (0 to 3 bytes)
+fn incomplete_suffix_len(bytes: &[u8]) -> usize {
+    for back in 1..=3.min(bytes.len()) {
+        let byte = bytes[bytes.len() - back];
+        if byte & 0b1100_0000 == 0b1000_0000 {
+            continue; // continuation byte; keep looking for the lead byte
+        }
+        let needed = match byte {
+            b if b & 0b1110_0000 == 0b1100_0000 => 2,
+            b if b & 0b1111_0000 == 0b1110_0000 => 3,
+            b if b & 0b1111_1000 == 0b1111_0000 => 4,
+            _ => 1,
+        };
+        return if needed > back { back } else { 0 };
+    }
+    0
+}
@@ -0,0 +1,404 @@
+//! One search over tasks, plans, milestones, walkthroughs and artifact front
+//! matter, for the command palette.
+//!
+//! Tasks are found through their FTS index. Plans, milestones and
+//! walkthroughs are few enough to narrow down with `LIKE`, and artifacts are
+//! matched on their front matter (alias, description, tags, type) and file
+//! name as kept by the artifact [`SearchIndex`]. Every hit is then scored the same way so the kinds rank against
+//! each other: each query word has to start a word of the hit's title or
+//! other text, title words count most, and a title that equals or starts
+//! with the whole query ranks higher still.
+
+use crate::core::search_index::SearchIndex;
+use crate::db::entities::{plan, plan_milestone, plan_phase, project, walkthrough};
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Kinds of records `global_search` looks through
+pub const KINDS: &[&str] = &["task", "plan", "milestone", "walkthrough", "artifact"];
+
+/// Default number of hits returned
+pub const DEFAULT_LIMIT: usize = 30;
+
+/// One search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalSearchHit {
+    /// One of [`KINDS`]
+    pub kind: String,
+    /// Record ID; the file path for artifacts
+    pub id: String,
+    pub title: String,
+    /// HTML-escaped title with matched words wrapped in `<mark>`
+    #[serde(rename = "titleHighlight")]
+    pub title_highlight: String,
+    /// Where the hit lives, e.g. "Plan › Phase" for a milestone
+    pub context: Option<String>,
+    #[serde(rename = "projectId")]
+    pub project_id: Option<String>,
+    /// ID of the plan a milestone belongs to
+    #[serde(rename = "planId", skip_serializing_if = "Option::is_none")]
+    pub plan_id: Option<String>,
+    /// File (or plan folder) to open
+    pub path: Option<String>,
+    /// Higher is better; comparable across kinds
+    pub score: f64,
+}
+
+/// Searches every kind in `kinds` (all of them by default), best hits first.
+///
+/// `project_ids` limits the search to those projects; by default archived
+/// projects are left out. Tasks without a project are only found when no
+/// projects are given.
+pub async fn global_search(
+    db: &DatabaseConnection,
+    index: &SearchIndex,
+    query: &str,
+    kinds: Option<Vec<String>>,
+    project_ids: Option<Vec<String>>,
+    limit: usize,
+) -> Result<Vec<GlobalSearchHit>, DbErr> {
+    let terms = words(query);
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let kinds = kinds.unwrap_or_else(|| KINDS.iter().map(|k| String::from(*k)).collect());
+    if let Some(kind) = kinds.iter().find(|k| !KINDS.contains(&k.as_str())) {
+        return Err(DbErr::Custom(format!(
+            "Invalid search kind: {}. Must be one of: {}",
+            kind,
+            KINDS.join(", ")
+        )));
+    }
+    let searches = |kind: &str| kinds.iter().any(|k| k == kind);
+
+    let projects: Vec<project::Model> = crate::db::project_operations::get_all_projects(db)
+        .await?
+        .into_iter()
+        .filter(|p| match &project_ids {
+            Some(ids) => ids.contains(&p.id),
+            None => !p.archived,
+        })
+        .collect();
+    let scope: Vec<String> = projects.iter().map(|p| p.id.clone()).collect();
+
+    let mut hits = Vec::new();
+    if searches("task") {
+        hits.extend(search_tasks(db, query, &terms, project_ids.is_some().then(|| scope.clone()), limit).await?);
+    }
+    if searches("plan") {
+        hits.extend(search_plans(db, &terms, &scope).await?);
+    }
+    if searches("milestone") {
+        hits.extend(search_milestones(db, &terms, &scope).await?);
+    }
+    if searches("walkthrough") {
+        hits.extend(search_walkthroughs(db, &terms, &scope).await?);
+    }
+    if searches("artifact") {
+        // Walkthrough files are already found as walkthroughs
+        let walkthrough_paths: HashSet<String> = hits
+            .iter()
+            .filter(|hit| hit.kind == "walkthrough")
+            .filter_map(|hit| hit.path.clone())
+            .collect();
+        let artifacts = search_artifacts(index, &terms, &projects).await;
+        hits.extend(
+            artifacts
+                .into_iter()
+                .filter(|hit| !hit.path.as_ref().is_some_and(|p| walkthrough_paths.contains(p))),
+        );
+    }
+
+    hits.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
+    });
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+async fn search_tasks(
+    db: &DatabaseConnection,
+    query: &str,
+    terms: &[String],
+    project_ids: Option<Vec<String>>,
+    limit: usize,
+) -> Result<Vec<GlobalSearchHit>, DbErr> {
+    let scoped = project_ids.is_some();
+    let found = crate::db::task_operations::search_tasks(db, query, project_ids, limit as u64).await?;
+    let archived = if scoped {
+        HashSet::new()
+    } else {
+        crate::db::project_operations::archived_project_ids(db).await?
+    };
+
+    let count = found.len();
+    Ok(found
+        .into_iter()
+        .enumerate()
+        .filter(|(_, hit)| {
+            hit.task.project_ids.is_empty() || !hit.task.project_ids.iter().all(|id| archived.contains(id))
+        })
+        .map(|(rank, hit)| {
+            let task = hit.task;
+            let tags = task.tags.join(" ");
+            let other = [task.description.as_deref().unwrap_or(""), tags.as_str()];
+            // The FTS index tokenizes a little differently; whatever it
+            // matched still counts, ordered as it ranked them
+            let score = score(terms, &task.title, &other).unwrap_or(1.0) + (count - rank) as f64 * 0.01;
+            GlobalSearchHit {
+                kind: "task".to_string(),
+                title_highlight: highlight(&task.title, terms),
+                context: Some(task.status.replace('_', " ")),
+                project_id: task.project_ids.first().cloned(),
+                plan_id: None,
+                path: None,
+                score,
+                id: task.id,
+                title: task.title,
+            }
+        })
+        .collect())
+}
+
+// Every term in `name` or `description`, to narrow down before scoring
+fn name_or_description_contains<C: ColumnTrait>(terms: &[String], name: C, description: C) -> Condition {
+    terms.iter().fold(Condition::all(), |condition, term| {
+        condition.add(
+            Condition::any()
+                .add(name.contains(term.as_str()))
+                .add(description.contains(term.as_str())),
+        )
+    })
+}
+
+async fn search_plans(db: &DatabaseConnection, terms: &[String], scope: &[String]) -> Result<Vec<GlobalSearchHit>, DbErr> {
+    let plans = plan::Entity::find()
+        .filter(plan::Column::DeletedAt.is_null())
+        .filter(plan::Column::ProjectId.is_in(scope.iter().cloned()))
+        .filter(name_or_description_contains(terms, plan::Column::Name, plan::Column::Description))
+        .all(db)
+        .await?;
+
+    Ok(plans
+        .into_iter()
+        .filter_map(|p| {
+            let score = score(terms, &p.name, &[p.description.as_deref().unwrap_or("")])?;
+            Some(GlobalSearchHit {
+                kind: "plan".to_string(),
+                title_highlight: highlight(&p.name, terms),
+                context: Some(p.status),
+                project_id: Some(p.project_id),
+                plan_id: None,
+                path: Some(p.folder_path),
+                score,
+                id: p.id,
+                title: p.name,
+            })
+        })
+        .collect())
+}
+
+async fn search_milestones(
+    db: &DatabaseConnection,
+    terms: &[String],
+    scope: &[String],
+) -> Result<Vec<GlobalSearchHit>, DbErr> {
+    let milestones = plan_milestone::Entity::find()
+        .filter(name_or_description_contains(
+            terms,
+            plan_milestone::Column::Name,
+            plan_milestone::Column::Description,
+        ))
+        .all(db)
+        .await?;
+    if milestones.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let phases: HashMap<String, plan_phase::Model> = plan_phase::Entity::find()
+        .filter(plan_phase::Column::Id.is_in(milestones.iter().map(|m| m.phase_id.clone())))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|phase| (phase.id.clone(), phase))
+        .collect();
+    let plans: HashMap<String, plan::Model> = plan::Entity::find()
+        .filter(plan::Column::Id.is_in(phases.values().map(|phase| phase.plan_id.clone())))
+        .filter(plan::Column::DeletedAt.is_null())
+        .filter(plan::Column::ProjectId.is_in(scope.iter().cloned()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id.clone(), p))
+        .collect();
+
+    Ok(milestones
+        .into_iter()
+        .filter_map(|m| {
+            let phase = phases.get(&m.phase_id)?;
+            let plan = plans.get(&phase.plan_id)?;
+            let score = score(terms, &m.name, &[m.description.as_deref().unwrap_or("")])?;
+            Some(GlobalSearchHit {
+                kind: "milestone".to_string(),
+                title_highlight: highlight(&m.name, terms),
+                context: Some(format!("{} › {}", plan.name, phase.name)),
+                project_id: Some(plan.project_id.clone()),
+                plan_id: Some(plan.id.clone()),
+                path: None,
+                score,
+                id: m.id,
+                title: m.name,
+            })
+        })
+        .collect())
+}
+
+async fn search_walkthroughs(
+    db: &DatabaseConnection,
+    terms: &[String],
+    scope: &[String],
+) -> Result<Vec<GlobalSearchHit>, DbErr> {
+    let walkthroughs = walkthrough::Entity::find()
+        .filter(walkthrough::Column::DeletedAt.is_null())
+        .filter(walkthrough::Column::ProjectId.is_in(scope.iter().cloned()))
+        .filter(name_or_description_contains(
+            terms,
+            walkthrough::Column::Name,
+            walkthrough::Column::Description,
+        ))
+        .all(db)
+        .await?;
+
+    Ok(walkthroughs
+        .into_iter()
+        .filter_map(|w| {
+            let score = score(terms, &w.name, &[w.description.as_deref().unwrap_or("")])?;
+            Some(GlobalSearchHit {
+                kind: "walkthrough".to_string(),
+                title_highlight: highlight(&w.name, terms),
+                context: Some(w.status.replace('_', " ")),
+                project_id: Some(w.project_id),
+                plan_id: None,
+                path: Some(w.file_path),
+                score,
+                id: w.id,
+                title: w.name,
+            })
+        })
+        .collect())
+}
+
+// Matches artifact front matter in each project's `.bluekit`
+async fn search_artifacts(index: &SearchIndex, terms: &[String], projects: &[project::Model]) -> Vec<GlobalSearchHit> {
+    let mut hits = Vec::new();
+    for project in projects {
+        let root = crate::core::paths::from_ipc(&project.path);
+        let event_name = crate::commands::project_artifacts_event_name(&project.path);
+        let watched = crate::core::watcher::watcher_exists(&event_name).await;
+        let artifacts = match index.artifacts(&root, watched).await {
+            Ok(artifacts) => artifacts,
+            Err(e) => {
+                tracing::debug!("Skipping artifacts of {} in search: {}", root.display(), e);
+                continue;
+            }
+        };
+        for artifact in artifacts {
+            let metadata = artifact.metadata;
+            let title = metadata.alias.clone().unwrap_or_else(|| artifact.name.clone());
+            let tags = metadata.tags.join(" ");
+            let other = [
+                artifact.name.as_str(),
+                metadata.description.as_deref().unwrap_or(""),
+                tags.as_str(),
+                metadata.artifact_type.as_deref().unwrap_or(""),
+            ];
+            let Some(score) = score(terms, &title, &other) else {
+                continue;
+            };
+            let path = crate::core::paths::to_ipc(&artifact.path);
+            hits.push(GlobalSearchHit {
+                kind: "artifact".to_string(),
+                title_highlight: highlight(&title, terms),
+                context: metadata.artifact_type,
+                project_id: Some(project.id.clone()),
+                plan_id: None,
+                path: Some(path.clone()),
+                score,
+                id: path,
+                title,
+            });
+        }
+    }
+    hits
+}
+
+// Lowercased words: runs of letters and digits
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// None unless every term starts a word of `title` or `other`
+fn score(terms: &[String], title: &str, other: &[&str]) -> Option<f64> {
+    let title_words = words(title);
+    let other_words: Vec<String> = other.iter().flat_map(|text| words(text)).collect();
+
+    let mut score = 0.0;
+    for term in terms {
+        if title_words.iter().any(|word| word == term) {
+            score += 12.0;
+        } else if title_words.iter().any(|word| word.starts_with(term.as_str())) {
+            score += 10.0;
+        } else if other_words.iter().any(|word| word.starts_with(term.as_str())) {
+            score += 3.0;
+        } else {
+            return None;
+        }
+    }
+
+    let phrase = terms.join(" ");
+    let title = title_words.join(" ");
+    if title == phrase {
+        score += 20.0;
+    } else if title.starts_with(&phrase) {
+        score += 8.0;
+    }
+    // Of otherwise equal titles, the shorter one is the closer match
+    Some(score - title_words.len() as f64 * 0.1)
+}
+
+// HTML-escapes `title`, wrapping words that start with a term in <mark>
+fn highlight(title: &str, terms: &[String]) -> String {
+    fn flush(word: &mut String, terms: &[String], out: &mut String) {
+        if word.is_empty() {
+            return;
+        }
+        let lower = word.to_lowercase();
+        let escaped = crate::core::html_export::escape(word);
+        if terms.iter().any(|term| lower.starts_with(term.as_str())) {
+            out.push_str(&format!("<mark>{}</mark>", escaped));
+        } else {
+            out.push_str(&escaped);
+        }
+        word.clear();
+    }
+
+    let mut out = String::with_capacity(title.len());
+    let mut word = String::new();
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            word.push(c);
+        } else {
+            flush(&mut word, terms, &mut out);
+            out.push_str(&crate::core::html_export::escape(c.encode_utf8(&mut [0; 4])));
+        }
+    }
+    flush(&mut word, terms, &mut out);
+    out
+}
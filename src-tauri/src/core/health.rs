@@ -0,0 +1,319 @@
+//! Aggregate health diagnostics for the diagnostics screen.
+//!
+//! Each subsystem is checked independently and reported as a [`HealthCheck`];
+//! one failing check never prevents the others from running. Auth tokens
+//! live in the frontend, so GitHub and Supabase are only checked when their
+//! credentials are passed in.
+
+use crate::db::entities::scheduled_job;
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// Free space below which the disk check reports a warning
+const LOW_DISK_SPACE_BYTES: u64 = 500 * 1024 * 1024;
+
+/// Timeout for each network check, so an offline machine gets a quick report
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheck {
+    /// "ok", "warning", "error" or "skipped"
+    pub status: String,
+    pub message: String,
+}
+
+impl HealthCheck {
+    fn ok(message: impl Into<String>) -> Self {
+        Self { status: "ok".to_string(), message: message.into() }
+    }
+
+    fn warning(message: impl Into<String>) -> Self {
+        Self { status: "warning".to_string(), message: message.into() }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self { status: "error".to_string(), message: message.into() }
+    }
+
+    fn skipped(message: impl Into<String>) -> Self {
+        Self { status: "skipped".to_string(), message: message.into() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseHealth {
+    pub check: HealthCheck,
+    pub path: String,
+    /// Database file plus its WAL/shared-memory files
+    #[serde(rename = "sizeBytes")]
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherHealth {
+    pub check: HealthCheck,
+    pub total: usize,
+    pub active: usize,
+    /// Event names of watchers that have stopped
+    pub inactive: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskHealth {
+    pub check: HealthCheck,
+    /// Total size of `~/.bluekit`
+    #[serde(rename = "bluekitDirBytes")]
+    pub bluekit_dir_bytes: u64,
+    /// Free space on the volume holding `~/.bluekit`
+    #[serde(rename = "availableBytes")]
+    pub available_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobQueueHealth {
+    pub check: HealthCheck,
+    /// Enabled jobs whose next run is already due
+    pub pending: u64,
+    pub running: usize,
+    pub enabled: u64,
+}
+
+/// Supabase session to verify
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupabaseCredentials {
+    pub url: String,
+    #[serde(rename = "anonKey")]
+    pub anon_key: String,
+    #[serde(rename = "accessToken")]
+    pub access_token: String,
+}
+
+/// Full diagnostics report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppHealthReport {
+    /// Worst status across all checks ("ok", "warning" or "error")
+    pub status: String,
+    #[serde(rename = "checkedAt")]
+    pub checked_at: i64,
+    pub database: DatabaseHealth,
+    pub watchers: WatcherHealth,
+    pub github: HealthCheck,
+    pub supabase: HealthCheck,
+    pub disk: DiskHealth,
+    pub jobs: JobQueueHealth,
+}
+
+/// Runs every check and aggregates the results.
+pub async fn get_app_health(
+    db: &DatabaseConnection,
+    github_token: Option<String>,
+    supabase: Option<SupabaseCredentials>,
+) -> AppHealthReport {
+    let (database, watchers, github, supabase, disk, jobs) = tokio::join!(
+        check_database(db),
+        check_watchers(),
+        check_github(github_token),
+        check_supabase(supabase),
+        check_disk(),
+        check_jobs(db),
+    );
+
+    let status = [
+        &database.check,
+        &watchers.check,
+        &github,
+        &supabase,
+        &disk.check,
+        &jobs.check,
+    ]
+    .iter()
+    .map(|check| check.status.as_str())
+    .fold("ok", |worst, status| match (worst, status) {
+        ("error", _) | (_, "error") => "error",
+        ("warning", _) | (_, "warning") => "warning",
+        _ => "ok",
+    })
+    .to_string();
+
+    AppHealthReport {
+        status,
+        checked_at: chrono::Utc::now().timestamp(),
+        database,
+        watchers,
+        github,
+        supabase,
+        disk,
+        jobs,
+    }
+}
+
+async fn check_database(db: &DatabaseConnection) -> DatabaseHealth {
+    let path = crate::db::get_db_path().unwrap_or_default();
+    let size_bytes = ["", "-wal", "-shm"]
+        .iter()
+        .filter_map(|suffix| {
+            let mut file = path.clone().into_os_string();
+            file.push(suffix);
+            std::fs::metadata(file).ok()
+        })
+        .map(|meta| meta.len())
+        .sum();
+
+    let check = match db.ping().await {
+        Ok(()) => HealthCheck::ok("Database is reachable"),
+        Err(e) => HealthCheck::error(format!("Database is unreachable: {}", e)),
+    };
+
+    DatabaseHealth {
+        check,
+        path: path.to_string_lossy().to_string(),
+        size_bytes,
+    }
+}
+
+async fn check_watchers() -> WatcherHealth {
+    let watchers = crate::core::watcher::get_watcher_health().await;
+    let mut inactive: Vec<String> = watchers
+        .iter()
+        .filter(|(_, active)| !**active)
+        .map(|(name, _)| name.clone())
+        .collect();
+    inactive.sort();
+
+    let total = watchers.len();
+    let active = total - inactive.len();
+    let check = if inactive.is_empty() {
+        HealthCheck::ok(format!("{} watcher(s) running", active))
+    } else {
+        HealthCheck::warning(format!("{} of {} watcher(s) have stopped", inactive.len(), total))
+    };
+
+    WatcherHealth { check, total, active, inactive }
+}
+
+async fn check_github(token: Option<String>) -> HealthCheck {
+    let Some(token) = token.filter(|t| !t.is_empty()) else {
+        return HealthCheck::skipped("Not signed in to GitHub");
+    };
+
+    let client = crate::integrations::github::GitHubClient::new(token);
+    match tokio::time::timeout(NETWORK_TIMEOUT, client.get_user()).await {
+        Ok(Ok(user)) => HealthCheck::ok(format!("Signed in as {}", user.login)),
+        Ok(Err(e)) if e.contains("(401)") => {
+            HealthCheck::error("GitHub token is invalid or expired")
+        }
+        Ok(Err(e)) => HealthCheck::warning(format!("Could not verify GitHub token: {}", e)),
+        Err(_) => HealthCheck::warning("Timed out contacting GitHub"),
+    }
+}
+
+async fn check_supabase(credentials: Option<SupabaseCredentials>) -> HealthCheck {
+    let Some(credentials) = credentials else {
+        return HealthCheck::skipped("Not signed in to Supabase");
+    };
+
+    let url = format!("{}/auth/v1/user", credentials.url.trim_end_matches('/'));
+    let request = reqwest::Client::new()
+        .get(&url)
+        .header("apikey", &credentials.anon_key)
+        .bearer_auth(&credentials.access_token)
+        .timeout(NETWORK_TIMEOUT)
+        .send();
+
+    match request.await {
+        Ok(response) if response.status().is_success() => {
+            HealthCheck::ok("Supabase session is valid")
+        }
+        Ok(response) if response.status() == reqwest::StatusCode::UNAUTHORIZED => {
+            HealthCheck::error("Supabase session is invalid or expired")
+        }
+        Ok(response) => HealthCheck::warning(format!(
+            "Supabase returned an unexpected status: {}",
+            response.status()
+        )),
+        Err(e) => HealthCheck::warning(format!("Could not reach Supabase: {}", e)),
+    }
+}
+
+async fn check_disk() -> DiskHealth {
+    let bluekit_dir = match crate::db::get_db_path() {
+        Ok(path) => path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        Err(e) => {
+            return DiskHealth {
+                check: HealthCheck::error(e),
+                bluekit_dir_bytes: 0,
+                available_bytes: None,
+            }
+        }
+    };
+
+    // Walking the directory can take a while with many backups
+    let dir = bluekit_dir.clone();
+    let bluekit_dir_bytes = tokio::task::spawn_blocking(move || dir_size(&dir))
+        .await
+        .unwrap_or(0);
+    let available_bytes = fs2::available_space(&bluekit_dir).ok();
+
+    let check = match available_bytes {
+        Some(available) if available < LOW_DISK_SPACE_BYTES => HealthCheck::warning(format!(
+            "Only {} MB free on the volume holding {}",
+            available / (1024 * 1024),
+            bluekit_dir.display()
+        )),
+        Some(_) => HealthCheck::ok("Enough free disk space"),
+        None => HealthCheck::warning("Could not determine free disk space"),
+    };
+
+    DiskHealth { check, bluekit_dir_bytes, available_bytes }
+}
+
+// Total size of regular files under `dir`; symlinks are not followed
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+async fn check_jobs(db: &DatabaseConnection) -> JobQueueHealth {
+    let now = chrono::Utc::now().timestamp();
+    let enabled = scheduled_job::Entity::find()
+        .filter(scheduled_job::Column::Enabled.eq(1))
+        .count(db)
+        .await;
+    let pending = scheduled_job::Entity::find()
+        .filter(scheduled_job::Column::Enabled.eq(1))
+        .filter(scheduled_job::Column::NextRunAt.lte(now))
+        .count(db)
+        .await;
+    let running = crate::core::scheduler::running_job_count();
+
+    match (enabled, pending) {
+        (Ok(enabled), Ok(pending)) => JobQueueHealth {
+            check: HealthCheck::ok(format!(
+                "{} running, {} pending of {} enabled job(s)",
+                running, pending, enabled
+            )),
+            pending,
+            running,
+            enabled,
+        },
+        (Err(e), _) | (_, Err(e)) => JobQueueHealth {
+            check: HealthCheck::error(format!("Failed to read scheduled jobs: {}", e)),
+            pending: 0,
+            running,
+            enabled: 0,
+        },
+    }
+}
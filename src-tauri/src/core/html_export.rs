@@ -0,0 +1,213 @@
+//! Self-contained HTML documents, for sharing artifacts outside the app.
+//!
+//! Markdown is rendered to HTML and wrapped in a page with inline CSS.
+//! Mermaid diagrams (```` ```mermaid ```` blocks, and images or links
+//! pointing at `.mmd` / `.mermaid` files) are replaced by SVGs rendered
+//! beforehand: [`diagram_sources`] lists them so the frontend's mermaid can
+//! render each, keyed by source. A diagram without an SVG is shown as its
+//! source. Local images are embedded as data URIs.
+
+use base64::prelude::*;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Markdown rendered to an HTML fragment
+#[derive(Debug, Clone, Default)]
+pub struct RenderedMarkdown {
+    pub html: String,
+    /// Diagrams shown as source because no SVG was given for them
+    pub unrendered_diagrams: usize,
+}
+
+/// The mermaid sources in `markdown`, each once, in order. Diagram files are
+/// resolved against `base_dir`.
+pub fn diagram_sources(markdown: &str, base_dir: &Path) -> Vec<String> {
+    let mut sources = Vec::new();
+    render(markdown, base_dir, &HashMap::new(), &mut sources);
+    let mut seen = std::collections::HashSet::new();
+    sources.retain(|source| seen.insert(source.clone()));
+    sources
+}
+
+/// Renders `markdown` (without front matter) to HTML, putting in the SVG
+/// from `svgs` for each diagram. Relative links and images are resolved
+/// against `base_dir`.
+pub fn render_markdown(markdown: &str, base_dir: &Path, svgs: &HashMap<String, String>) -> RenderedMarkdown {
+    render(markdown, base_dir, svgs, &mut Vec::new())
+}
+
+/// A complete HTML page around `body`
+pub fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<main>\n{}\n</main>\n</body>\n</html>\n",
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+/// Escapes text for HTML content and attribute values
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = r#"
+:root { color-scheme: light; --accent: #3b6fd8; --muted: #6b7280; --border: #e5e7eb; }
+body { margin: 0; background: #f7f8fa; color: #1f2937; font: 16px/1.6 -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; }
+main { max-width: 860px; margin: 2rem auto; padding: 2rem 2.5rem; background: #fff; border: 1px solid var(--border); border-radius: 12px; }
+h1, h2, h3, h4 { line-height: 1.25; margin: 1.6em 0 0.6em; }
+h1 { margin-top: 0; }
+a { color: var(--accent); }
+code { font: 0.9em ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; background: #f1f3f5; padding: 0.1em 0.3em; border-radius: 4px; }
+pre { background: #f1f3f5; padding: 1em; border-radius: 8px; overflow-x: auto; }
+pre code { background: none; padding: 0; }
+blockquote { margin: 1em 0; padding: 0 1em; color: var(--muted); border-left: 4px solid var(--border); }
+table { border-collapse: collapse; }
+th, td { border: 1px solid var(--border); padding: 0.4em 0.7em; }
+img { max-width: 100%; }
+figure.diagram { margin: 1.5em 0; text-align: center; }
+figure.diagram svg { max-width: 100%; height: auto; }
+.meta { color: var(--muted); font-size: 0.9em; }
+.progress { height: 8px; background: var(--border); border-radius: 4px; overflow: hidden; margin: 0.5em 0 1.5em; }
+.progress > div { height: 100%; background: var(--accent); }
+ul.checklist { list-style: none; padding-left: 0; }
+ul.checklist li { margin: 0.4em 0; }
+.done { color: var(--muted); text-decoration: line-through; }
+.note { border: 1px solid var(--border); border-radius: 8px; padding: 0 1em; margin: 1em 0; }
+.plain { white-space: pre-wrap; }
+.step-location { font-size: 0.85em; color: var(--muted); }
+"#;
+
+#[derive(Default)]
+struct RenderState {
+    sources: Vec<String>,
+    unrendered: usize,
+}
+
+fn render(
+    markdown: &str,
+    base_dir: &Path,
+    svgs: &HashMap<String, String>,
+    sources: &mut Vec<String>,
+) -> RenderedMarkdown {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut state = RenderState::default();
+    let mut events = Vec::new();
+    // Text of the ```mermaid block being read
+    let mut mermaid: Option<String> = None;
+    // The end of the link or image replaced by a diagram
+    let mut skip_until: Option<TagEnd> = None;
+
+    for event in Parser::new_ext(markdown, options) {
+        if let Some(end) = &skip_until {
+            if event == Event::End(*end) {
+                skip_until = None;
+            }
+            continue;
+        }
+        if let Some(source) = mermaid.as_mut() {
+            match event {
+                Event::Text(text) => source.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => {
+                    let source = mermaid.take().unwrap_or_default();
+                    events.push(diagram(source, svgs, &mut state));
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(ref lang))) if lang.trim() == "mermaid" => {
+                mermaid = Some(String::new());
+            }
+            Event::Start(Tag::Image { ref dest_url, .. }) | Event::Start(Tag::Link { ref dest_url, .. })
+                if diagram_file(base_dir, dest_url).is_some() =>
+            {
+                let end = match event {
+                    Event::Start(Tag::Image { .. }) => TagEnd::Image,
+                    _ => TagEnd::Link,
+                };
+                let source = diagram_file(base_dir, dest_url)
+                    .and_then(|path| std::fs::read_to_string(path).ok())
+                    .unwrap_or_default();
+                events.push(diagram(source, svgs, &mut state));
+                skip_until = Some(end);
+            }
+            Event::Start(Tag::Image { link_type, dest_url, title, id }) => {
+                let dest_url = local_file(base_dir, &dest_url)
+                    .and_then(|path| data_uri(&path))
+                    .map(CowStr::from)
+                    .unwrap_or(dest_url);
+                events.push(Event::Start(Tag::Image { link_type, dest_url, title, id }));
+            }
+            event => events.push(event),
+        }
+    }
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events.into_iter());
+    sources.append(&mut state.sources);
+    RenderedMarkdown {
+        html,
+        unrendered_diagrams: state.unrendered,
+    }
+}
+
+// The SVG for `source`, or the source itself when there is none
+fn diagram<'a>(source: String, svgs: &HashMap<String, String>, state: &mut RenderState) -> Event<'a> {
+    let source = source.trim().to_string();
+    let html = match svgs.get(&source) {
+        Some(svg) => format!("<figure class=\"diagram\">{}</figure>\n", svg),
+        None => {
+            state.unrendered += 1;
+            format!("<pre class=\"diagram-source\"><code>{}</code></pre>\n", escape(&source))
+        }
+    };
+    state.sources.push(source);
+    Event::Html(html.into())
+}
+
+// A relative link to an existing file, resolved against `base_dir`
+fn local_file(base_dir: &Path, dest_url: &str) -> Option<std::path::PathBuf> {
+    if dest_url.contains("://") || dest_url.starts_with("data:") || dest_url.starts_with('#') {
+        return None;
+    }
+    let path = dest_url.split(['#', '?']).next().unwrap_or_default();
+    let path = urlencoding::decode(path).ok()?;
+    let path = base_dir.join(path.as_ref());
+    path.is_file().then_some(path)
+}
+
+fn diagram_file(base_dir: &Path, dest_url: &str) -> Option<std::path::PathBuf> {
+    local_file(base_dir, dest_url)
+        .filter(|path| path.extension().is_some_and(|ext| ext == "mmd" || ext == "mermaid"))
+}
+
+fn data_uri(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    let mime = match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        _ => return None,
+    };
+    let bytes = std::fs::read(path).ok()?;
+    Some(format!("data:{};base64,{}", mime, BASE64_STANDARD.encode(bytes)))
+}
@@ -0,0 +1,86 @@
+//! `.bluekit/.bluekitignore` support.
+//!
+//! The file holds gitignore-style patterns, relative to `.bluekit`, for
+//! generated or vendored markdown that shouldn't show up as artifacts.
+//! Artifact listings, the scrapbook and the directory watcher skip whatever
+//! it matches. A missing or unreadable file ignores nothing.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+/// Name of the ignore file inside `.bluekit`
+pub const IGNORE_FILE: &str = ".bluekitignore";
+
+/// Compiled `.bluekitignore` patterns for one `.bluekit` directory
+pub struct IgnoreRules {
+    root: PathBuf,
+    matcher: Option<Gitignore>,
+}
+
+impl IgnoreRules {
+    /// Loads `<bluekit_dir>/.bluekitignore`. Invalid patterns are logged and
+    /// skipped; the valid ones still apply.
+    pub fn load(bluekit_dir: &Path) -> Self {
+        let ignore_path = bluekit_dir.join(IGNORE_FILE);
+        if !ignore_path.is_file() {
+            return Self::none(bluekit_dir);
+        }
+
+        let mut builder = GitignoreBuilder::new(bluekit_dir);
+        if let Some(e) = builder.add(&ignore_path) {
+            tracing::warn!("Problem reading {}: {}", ignore_path.display(), e);
+        }
+        let matcher = match builder.build() {
+            Ok(matcher) => Some(matcher),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid {}: {}", ignore_path.display(), e);
+                None
+            }
+        };
+
+        Self {
+            root: bluekit_dir.to_path_buf(),
+            matcher,
+        }
+    }
+
+    /// Rules for the `.bluekit` directory `path` is in (or is, or contains).
+    pub fn for_path(path: &Path) -> Self {
+        match bluekit_dir_of(path) {
+            Some(bluekit_dir) => Self::load(&bluekit_dir),
+            None => Self::none(path),
+        }
+    }
+
+    fn none(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            matcher: None,
+        }
+    }
+
+    /// Whether `path` (or a folder above it) matches an ignore pattern.
+    /// Paths outside the `.bluekit` directory are never ignored.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(matcher) = &self.matcher else {
+            return false;
+        };
+        // `matched_path_or_any_parents` panics on paths outside its root
+        if !path.starts_with(&self.root) || path == self.root {
+            return false;
+        }
+        matcher.matched_path_or_any_parents(path, is_dir).is_ignore()
+    }
+}
+
+// The nearest `.bluekit` at or above `path`, or `path/.bluekit`
+fn bluekit_dir_of(path: &Path) -> Option<PathBuf> {
+    if let Some(dir) = path
+        .ancestors()
+        .find(|ancestor| ancestor.file_name().is_some_and(|name| name == ".bluekit"))
+    {
+        return Some(dir.to_path_buf());
+    }
+    let child = path.join(".bluekit");
+    child.is_dir().then_some(child)
+}
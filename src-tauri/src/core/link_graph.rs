@@ -0,0 +1,399 @@
+//! Links between `.bluekit` artifacts, for outgoing links and backlinks.
+//!
+//! Each project gets an in-memory graph of the local links in its Markdown
+//! files: inline links (`[API client](../kits/api-client.md)`), reference
+//! definitions (`[api]: ../kits/api-client.md`), link stubs and
+//! `[[api-client]]` wikilinks. Wikilinks are resolved by name when the graph
+//! is read (see [`crate::core::wikilinks`]), so renaming or re-aliasing an
+//! artifact re-points them without re-reading the files that use them. Like the
+//! search index, the graph is built on first use and then kept current from
+//! directory watcher events through [`LinkGraph::mark_changed`]; without a
+//! watcher it falls back to comparing modification times.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use crate::core::wikilinks::{WikilinkRef, WikilinkResolution, WikilinkResolver};
+
+/// `[text](href)` and `![alt](href "title")`; group 1 is `!` for images
+pub(crate) static INLINE_LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(!?)\[([^\]]*)\]\(\s*<?([^)<>\s]+)>?(?:\s+"[^"]*")?\s*\)"#).expect("valid link pattern")
+});
+/// `[label]: href`
+pub(crate) static REFERENCE_DEFINITION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s{0,3}\[([^\]]+)\]:\s*<?([^>\s]+)>?").expect("valid reference pattern")
+});
+
+/// A link from one artifact to a local file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactLink {
+    /// The file linked to, resolved against the linking artifact (empty for
+    /// a wikilink that matches no artifact)
+    pub target: String,
+    /// The link as written, including any `#fragment`
+    pub href: String,
+    /// Link text or reference label (empty for link stubs)
+    pub text: String,
+    /// 1-based line of the link
+    pub line: usize,
+    /// "inline", "reference", "stub" or "wikilink"
+    pub kind: String,
+    /// Whether the target exists
+    pub exists: bool,
+}
+
+/// An artifact linking to another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backlink {
+    pub source: String,
+    #[serde(rename = "sourceName")]
+    pub source_name: String,
+    pub text: String,
+    pub line: usize,
+    pub kind: String,
+}
+
+struct GraphedFile {
+    modified: SystemTime,
+    links: Vec<ArtifactLink>,
+    /// Lexically normalized targets of `links`
+    targets: Vec<PathBuf>,
+    /// `[[...]]` links, resolved when read
+    wikilinks: Vec<WikilinkRef>,
+    /// `alias` front matter, for resolving other files' wikilinks
+    alias: Option<String>,
+}
+
+#[derive(Default)]
+struct ProjectGraph {
+    files: HashMap<PathBuf, GraphedFile>,
+    /// Paths reported by the watcher since the graph was last read
+    changed: HashSet<PathBuf>,
+    built: bool,
+}
+
+/// Thread-safe link graph shared by all projects, registered as Tauri state
+/// next to the search index.
+pub struct LinkGraph {
+    projects: Arc<RwLock<HashMap<PathBuf, Arc<Mutex<ProjectGraph>>>>>,
+}
+
+impl LinkGraph {
+    pub fn new() -> Self {
+        Self {
+            projects: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Records paths reported by a directory watcher. Paths outside any
+    /// graphed project are ignored.
+    pub async fn mark_changed(&self, paths: &[PathBuf]) {
+        let projects = self.projects.read().await;
+        for (root, graph) in projects.iter() {
+            let bluekit_dir = root.join(".bluekit");
+            lock_graph(graph)
+                .changed
+                .extend(paths.iter().filter(|p| p.starts_with(&bluekit_dir)).cloned());
+        }
+    }
+
+    /// Links in `file`, in order. `watched` says whether a watcher is
+    /// reporting changes for the project; if not, the graph is checked
+    /// against the filesystem first.
+    pub async fn links(&self, project_root: &Path, file: &Path, watched: bool) -> Vec<ArtifactLink> {
+        let graph = self.project_graph(project_root).await;
+        let mut graph = lock_graph(&graph);
+        refresh(project_root, &mut graph, watched);
+        let Some(graphed) = graph.files.get(&normalize(file)) else {
+            return Vec::new();
+        };
+
+        let resolver = resolver(project_root, &graph);
+        let mut links = graphed.links.clone();
+        links.extend(graphed.wikilinks.iter().map(|wikilink| wikilink_link(&resolver, wikilink)));
+        links.sort_by_key(|link| link.line);
+        links
+    }
+
+    /// Artifacts in the project that link to `file`, by path.
+    pub async fn backlinks(&self, project_root: &Path, file: &Path, watched: bool) -> Vec<Backlink> {
+        let graph = self.project_graph(project_root).await;
+        let mut graph = lock_graph(&graph);
+        refresh(project_root, &mut graph, watched);
+
+        let file = normalize(file);
+        let resolver = resolver(project_root, &graph);
+        let mut backlinks = Vec::new();
+        for (source, graphed) in &graph.files {
+            if *source == file {
+                continue;
+            }
+            let new_backlink = |text: &str, line: usize, kind: &str| Backlink {
+                source: crate::core::paths::to_ipc(source),
+                source_name: crate::core::paths::file_stem_lossy(source),
+                text: text.to_string(),
+                line,
+                kind: kind.to_string(),
+            };
+            for (link, target) in graphed.links.iter().zip(&graphed.targets) {
+                if *target == file {
+                    backlinks.push(new_backlink(&link.text, link.line, &link.kind));
+                }
+            }
+            for wikilink in &graphed.wikilinks {
+                if resolver.resolve(&wikilink.name) == Some(file.as_path()) {
+                    backlinks.push(new_backlink(&wikilink.label, wikilink.line, "wikilink"));
+                }
+            }
+        }
+        backlinks.sort_by(|a, b| a.source.cmp(&b.source).then(a.line.cmp(&b.line)));
+        backlinks
+    }
+
+    /// Every link in the project with the file it's in, wikilinks resolved,
+    /// plus the graphed files themselves.
+    pub async fn all_links(&self, project_root: &Path, watched: bool) -> (Vec<(PathBuf, ArtifactLink)>, Vec<PathBuf>) {
+        let graph = self.project_graph(project_root).await;
+        let mut graph = lock_graph(&graph);
+        refresh(project_root, &mut graph, watched);
+
+        let resolver = resolver(project_root, &graph);
+        let mut links = Vec::new();
+        for (source, graphed) in &graph.files {
+            links.extend(graphed.links.iter().map(|link| (source.clone(), link.clone())));
+            links.extend(
+                graphed
+                    .wikilinks
+                    .iter()
+                    .map(|wikilink| (source.clone(), wikilink_link(&resolver, wikilink))),
+            );
+        }
+        (links, graph.files.keys().cloned().collect())
+    }
+
+    /// Resolves wikilink names against the project's artifacts, in order.
+    pub async fn resolve_wikilinks(&self, project_root: &Path, texts: &[String], watched: bool) -> Vec<WikilinkResolution> {
+        let graph = self.project_graph(project_root).await;
+        let mut graph = lock_graph(&graph);
+        refresh(project_root, &mut graph, watched);
+        resolver(project_root, &graph).resolve_all(texts)
+    }
+
+    // The project's graph, created empty on first use
+    async fn project_graph(&self, project_root: &Path) -> Arc<Mutex<ProjectGraph>> {
+        let mut projects = self.projects.write().await;
+        Arc::clone(projects.entry(project_root.to_path_buf()).or_default())
+    }
+}
+
+fn lock_graph(graph: &Mutex<ProjectGraph>) -> MutexGuard<'_, ProjectGraph> {
+    graph.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Wikilink resolver over every graphed file
+fn resolver(project_root: &Path, graph: &ProjectGraph) -> WikilinkResolver {
+    WikilinkResolver::new(
+        &project_root.join(".bluekit"),
+        graph.files.iter().map(|(path, file)| (path.as_path(), file.alias.as_deref())),
+    )
+}
+
+fn wikilink_link(resolver: &WikilinkResolver, wikilink: &WikilinkRef) -> ArtifactLink {
+    let target = resolver.resolve(&wikilink.name);
+    ArtifactLink {
+        target: target.map(crate::core::paths::to_ipc).unwrap_or_default(),
+        href: format!("[[{}]]", wikilink.name),
+        text: wikilink.label.clone(),
+        line: wikilink.line,
+        kind: "wikilink".to_string(),
+        exists: target.is_some(),
+    }
+}
+
+// Brings the graph up to date: a full scan the first time (or without a
+// watcher), otherwise only the paths the watcher reported
+fn refresh(project_root: &Path, graph: &mut ProjectGraph, watched: bool) {
+    let bluekit_dir = project_root.join(".bluekit");
+    if !bluekit_dir.is_dir() {
+        graph.files.clear();
+        return;
+    }
+    let limit = crate::core::settings::load_settings()
+        .file_size_limits
+        .list_content_max_bytes;
+
+    if graph.built && watched {
+        let changed: Vec<PathBuf> = graph.changed.drain().collect();
+        debug!("Updating link graph for {} changed path(s)", changed.len());
+        for path in changed {
+            if path.is_dir() {
+                for file in crate::core::paths::files_under(&path) {
+                    update_file(graph, &file, limit);
+                }
+            } else {
+                update_file(graph, &path, limit);
+            }
+        }
+        // A removed folder only reports its own path
+        graph.files.retain(|path, _| path.exists());
+        return;
+    }
+
+    let files: Vec<PathBuf> = crate::core::paths::files_under(&bluekit_dir)
+        .into_iter()
+        .filter(|path| is_markdown(path))
+        .collect();
+    let present: HashSet<PathBuf> = files.iter().map(|f| normalize(f)).collect();
+    graph.files.retain(|path, _| present.contains(path));
+    for file in &files {
+        update_file(graph, file, limit);
+    }
+    graph.changed.clear();
+    graph.built = true;
+}
+
+// Re-reads one file's links if it changed, or drops it if it's gone
+fn update_file(graph: &mut ProjectGraph, path: &Path, limit: u64) {
+    let path = normalize(path);
+    let metadata = match fs::metadata(&path) {
+        Ok(metadata) if metadata.is_file() && is_markdown(&path) && metadata.len() <= limit => metadata,
+        _ => {
+            graph.files.remove(&path);
+            return;
+        }
+    };
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    if graph.files.get(&path).is_some_and(|file| file.modified == modified) {
+        return;
+    }
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) => {
+            debug!("Not graphing {}: {}", path.display(), e);
+            graph.files.remove(&path);
+            return;
+        }
+    };
+
+    let (links, targets) = parse_links(&path, &content).into_iter().unzip();
+    let alias = crate::core::wikilinks::front_matter_alias(&content);
+    graph.files.insert(
+        path,
+        GraphedFile {
+            modified,
+            links,
+            targets,
+            wikilinks: crate::core::wikilinks::parse_wikilinks(&content),
+            alias,
+        },
+    );
+}
+
+/// Local links in a Markdown file, each with its normalized target. Links
+/// inside fenced code blocks, images and external URLs are left out.
+pub fn parse_links(file: &Path, content: &str) -> Vec<(ArtifactLink, PathBuf)> {
+    let dir = file.parent().unwrap_or(Path::new(""));
+    let project_root = project_root_of(file);
+    let mut links = Vec::new();
+
+    if let Some(target) = crate::core::links::link_target(content) {
+        if let Ok(resolved) = crate::core::links::resolve_link(&target) {
+            links.push(new_link(normalize(&resolved), target, String::new(), 1, "stub"));
+        }
+    }
+
+    let mut in_code_block = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        if let Some(caps) = REFERENCE_DEFINITION.captures(line) {
+            if let Some(target) = resolve_href(project_root, dir, &caps[2]) {
+                links.push(new_link(target, caps[2].to_string(), caps[1].to_string(), index + 1, "reference"));
+            }
+            continue;
+        }
+        for caps in INLINE_LINK.captures_iter(line) {
+            if !caps[1].is_empty() {
+                continue; // image
+            }
+            if let Some(target) = resolve_href(project_root, dir, &caps[3]) {
+                links.push(new_link(target, caps[3].to_string(), caps[2].to_string(), index + 1, "inline"));
+            }
+        }
+    }
+    links
+}
+
+fn new_link(target: PathBuf, href: String, text: String, line: usize, kind: &str) -> (ArtifactLink, PathBuf) {
+    let link = ArtifactLink {
+        target: crate::core::paths::to_ipc(&target),
+        href,
+        text,
+        line,
+        kind: kind.to_string(),
+        exists: target.exists(),
+    };
+    (link, target)
+}
+
+/// The local file `href` points at from a file in `dir`, or `None` for
+/// URLs and in-page anchors. An `href` starting with `/` is relative to
+/// `project_root`.
+pub(crate) fn resolve_href(project_root: &Path, dir: &Path, href: &str) -> Option<PathBuf> {
+    if href.starts_with('#') || href.contains("://") || href.starts_with("mailto:") {
+        return None;
+    }
+    let path = href.split(['#', '?']).next().unwrap_or("").replace("%20", " ");
+    if path.is_empty() {
+        return None;
+    }
+    Some(normalize(&match path.strip_prefix('/') {
+        Some(rooted) => project_root.join(rooted),
+        None => dir.join(path),
+    }))
+}
+
+// The project a file in `.bluekit` belongs to; its own folder otherwise
+fn project_root_of(file: &Path) -> &Path {
+    file.ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == ".bluekit"))
+        .and_then(Path::parent)
+        .or_else(|| file.parent())
+        .unwrap_or(Path::new(""))
+}
+
+/// Resolves `.` and `..` without touching the filesystem, so links to
+/// missing files still compare equal to their targets.
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+pub(crate) fn is_markdown(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "md")
+}
@@ -0,0 +1,126 @@
+//! Linked (reference) artifact module.
+//!
+//! A linked artifact is a small stub file inside a project's `.bluekit`
+//! directory that points at one canonical copy, either in the shared pool
+//! (`shared/` in the data directory) or in another project:
+//!
+//! ```yaml
+//! ---
+//! bluekit_link: shared://kits/api-client.md
+//! ---
+//! ```
+//!
+//! Readers resolve the stub to the canonical file so every linked project
+//! sees (and edits) the same content.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Front matter key that marks a file as a link stub
+pub const LINK_KEY: &str = "bluekit_link";
+
+/// Prefix for link targets inside the shared pool
+pub const SHARED_SCHEME: &str = "shared://";
+
+/// Maximum number of stubs followed before giving up (guards against cycles)
+const MAX_LINK_DEPTH: usize = 8;
+
+/// Returns the shared artifact pool directory (`shared` in the data
+/// directory, `~/.bluekit` by default).
+pub fn shared_pool_dir() -> Result<PathBuf, String> {
+    Ok(crate::core::data_dir::data_dir()?.join("shared"))
+}
+
+/// Returns the link target if `content` is a link stub.
+pub fn link_target(content: &str) -> Option<String> {
+    // Cheap check before parsing YAML for every artifact
+    if !content.contains(LINK_KEY) || !content.trim_start().starts_with("---") {
+        return None;
+    }
+
+    let after_first_delim = &content[content.find("---")? + 3..];
+    let end_pos = after_first_delim.find("\n---")?;
+    let front_matter: serde_yaml::Value =
+        serde_yaml::from_str(&after_first_delim[..end_pos]).ok()?;
+
+    front_matter
+        .get(LINK_KEY)
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Converts a link target (`shared://...` or an absolute path) into a file path.
+pub fn resolve_link(target: &str) -> Result<PathBuf, String> {
+    if let Some(relative) = target.strip_prefix(SHARED_SCHEME) {
+        let relative = Path::new(relative);
+        // Keep shared links inside the pool
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err(format!("Invalid shared link target: {}", target));
+        }
+        return Ok(shared_pool_dir()?.join(relative));
+    }
+
+    let path = PathBuf::from(target);
+    if !path.is_absolute() {
+        return Err(format!("Link target must be absolute or shared://: {}", target));
+    }
+    Ok(path)
+}
+
+/// Follows the link stub whose content is `content` to the canonical file.
+///
+/// Returns `Ok(None)` if `content` is not a stub. Chains of stubs are
+/// followed up to a fixed depth; cycles and dangling links are errors.
+pub fn resolve_from_content(path: &Path, content: &str) -> Result<Option<PathBuf>, String> {
+    let Some(mut target) = link_target(content) else {
+        return Ok(None);
+    };
+
+    let mut visited = HashSet::new();
+    visited.insert(path.to_path_buf());
+
+    for _ in 0..MAX_LINK_DEPTH {
+        let resolved = resolve_link(&target)?;
+        if !visited.insert(resolved.clone()) {
+            return Err(format!("Link cycle detected at {}", resolved.display()));
+        }
+
+        let resolved_content = fs::read_to_string(&resolved).map_err(|e| {
+            format!("Linked artifact {} is unavailable: {}", resolved.display(), e)
+        })?;
+
+        match link_target(&resolved_content) {
+            Some(next) => target = next,
+            None => return Ok(Some(resolved)),
+        }
+    }
+
+    Err(format!("Too many nested links starting at {}", path.display()))
+}
+
+/// Resolves `path` to its canonical file if it is a link stub.
+pub fn resolve_path(path: &Path) -> Result<Option<PathBuf>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read file {}: {}", path.display(), e))?;
+    resolve_from_content(path, &content)
+}
+
+/// Builds the content of a link stub pointing at `target`.
+///
+/// `artifact_type` is copied into the stub so type-based filters still work
+/// on tools that don't resolve links.
+pub fn build_stub(target: &str, artifact_type: Option<&str>) -> String {
+    let mut front_matter = serde_yaml::Mapping::new();
+    front_matter.insert(LINK_KEY.into(), target.into());
+    if let Some(artifact_type) = artifact_type {
+        front_matter.insert("type".into(), artifact_type.into());
+    }
+
+    let yaml = serde_yaml::to_string(&front_matter).unwrap_or_default();
+    format!("---\n{}---\n", yaml)
+}
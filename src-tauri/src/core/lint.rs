@@ -0,0 +1,214 @@
+//! Markdown lint for a project's `.bluekit` artifacts.
+//!
+//! Checks every Markdown file for relative links and images that point at
+//! missing files, `[[wikilinks]]` that don't match any artifact, link stubs
+//! whose target is gone, and front matter problems (the same checks the
+//! headless `validate` mode runs). Links and wikilinks are resolved the way
+//! the link graph resolves them. Findings are grouped per file for the
+//! project health tab.
+
+use crate::core::link_graph::{self, INLINE_LINK, REFERENCE_DEFINITION};
+use crate::core::wikilinks::{self, WikilinkResolver};
+use crate::core::{links, paths, validation};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories whose artifacts are expected to carry front matter
+const FRONT_MATTER_DIRS: [&str; 3] = ["kits", "agents", "walkthroughs"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintFinding {
+    /// "error" or "warning"
+    pub severity: String,
+    /// "broken_link", "missing_asset", "missing_diagram", "dangling_wikilink",
+    /// "broken_link_stub", "front_matter" or "missing_front_matter"
+    pub rule: String,
+    pub message: String,
+    /// 1-based line, when the finding points at one
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileLintResult {
+    /// Relative to the project root, `/`-separated
+    pub path: String,
+    pub findings: Vec<LintFinding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintReport {
+    #[serde(rename = "filesChecked")]
+    pub files_checked: usize,
+    #[serde(rename = "errorCount")]
+    pub error_count: usize,
+    #[serde(rename = "warningCount")]
+    pub warning_count: usize,
+    /// Only files with findings
+    pub files: Vec<FileLintResult>,
+}
+
+impl LintFinding {
+    fn error(rule: &str, message: String, line: Option<usize>) -> Self {
+        Self { severity: "error".to_string(), rule: rule.to_string(), message, line }
+    }
+
+    fn warning(rule: &str, message: String, line: Option<usize>) -> Self {
+        Self { severity: "warning".to_string(), rule: rule.to_string(), message, line }
+    }
+}
+
+/// Lints every Markdown file under `<project_root>/.bluekit`.
+pub fn lint_artifacts(project_root: &Path) -> Result<LintReport, String> {
+    let bluekit_dir = project_root.join(".bluekit");
+    if !bluekit_dir.is_dir() {
+        return Err(format!("No .bluekit directory in {}", project_root.display()));
+    }
+
+    let mut files: Vec<PathBuf> = paths::files_under(&bluekit_dir)
+        .into_iter()
+        .filter(|path| link_graph::is_markdown(path))
+        .collect();
+    files.sort();
+    let contents: Vec<Result<String, String>> = files
+        .iter()
+        .map(|file| fs::read_to_string(file).map_err(|e| e.to_string()))
+        .collect();
+    let aliases: Vec<Option<String>> = contents
+        .iter()
+        .map(|content| content.as_ref().ok().and_then(|content| wikilinks::front_matter_alias(content)))
+        .collect();
+    let resolver = WikilinkResolver::new(
+        &bluekit_dir,
+        files.iter().map(PathBuf::as_path).zip(aliases.iter().map(Option::as_deref)),
+    );
+
+    let mut report = LintReport {
+        files_checked: files.len(),
+        error_count: 0,
+        warning_count: 0,
+        files: Vec::new(),
+    };
+
+    for (file, content) in files.iter().zip(&contents) {
+        let relative = file
+            .strip_prefix(project_root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| file.to_string_lossy().to_string());
+
+        let findings = match content {
+            Ok(content) => lint_file(project_root, file, &relative, content, &resolver),
+            Err(e) => vec![LintFinding::error("unreadable", format!("Failed to read file: {}", e), None)],
+        };
+        if findings.is_empty() {
+            continue;
+        }
+
+        for finding in &findings {
+            if finding.severity == "error" {
+                report.error_count += 1;
+            } else {
+                report.warning_count += 1;
+            }
+        }
+        report.files.push(FileLintResult { path: relative, findings });
+    }
+
+    Ok(report)
+}
+
+fn lint_file(
+    project_root: &Path,
+    file: &Path,
+    relative: &str,
+    content: &str,
+    resolver: &WikilinkResolver,
+) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+
+    // Stubs have no body of their own; only their target matters
+    if let Some(target) = links::link_target(content) {
+        if let Err(e) = links::resolve_from_content(file, content) {
+            findings.push(LintFinding::error(
+                "broken_link_stub",
+                format!("Link target {} can't be resolved: {}", target, e),
+                None,
+            ));
+        }
+        return findings;
+    }
+
+    findings.extend(
+        validation::validate_artifact(relative, content, project_root)
+            .into_iter()
+            .map(|message| LintFinding::error("front_matter", message, None)),
+    );
+    let in_front_matter_dir = FRONT_MATTER_DIRS
+        .iter()
+        .any(|dir| relative.starts_with(&format!(".bluekit/{}/", dir)));
+    if in_front_matter_dir && !content.trim_start().starts_with("---") {
+        findings.push(LintFinding::warning(
+            "missing_front_matter",
+            "Artifact has no front matter".to_string(),
+            None,
+        ));
+    }
+
+    let base_dir = file.parent().unwrap_or(project_root);
+    let mut in_code_block = false;
+    for (index, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let line_number = Some(index + 1);
+
+        let hrefs: Vec<(bool, &str)> = match REFERENCE_DEFINITION.captures(line) {
+            Some(captures) => vec![(false, captures.get(2).map_or("", |m| m.as_str()))],
+            None => INLINE_LINK
+                .captures_iter(line)
+                .map(|captures| (!captures[1].is_empty(), captures.get(3).map_or("", |m| m.as_str())))
+                .collect(),
+        };
+        for (is_image, target) in hrefs {
+            let Some(path) = link_graph::resolve_href(project_root, base_dir, target) else {
+                continue;
+            };
+            if path.exists() {
+                continue;
+            }
+
+            let finding = if is_diagram(&path) {
+                LintFinding::error("missing_diagram", format!("Diagram not found: {}", target), line_number)
+            } else if is_image {
+                LintFinding::error("missing_asset", format!("Asset not found: {}", target), line_number)
+            } else {
+                LintFinding::error("broken_link", format!("Link target not found: {}", target), line_number)
+            };
+            findings.push(finding);
+        }
+    }
+
+    for wikilink in wikilinks::parse_wikilinks(content) {
+        if resolver.resolve(&wikilink.name).is_none() {
+            findings.push(LintFinding::warning(
+                "dangling_wikilink",
+                format!("No artifact named [[{}]]", wikilink.name),
+                Some(wikilink.line),
+            ));
+        }
+    }
+    findings.sort_by_key(|finding| finding.line);
+
+    findings
+}
+
+fn is_diagram(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("mmd") | Some("mermaid")
+    )
+}
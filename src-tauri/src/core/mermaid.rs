@@ -0,0 +1,393 @@
+//! Syntax checks for mermaid diagram sources.
+//!
+//! This is not a full mermaid parser; it catches the mistakes that make the
+//! renderer show a blank diagram: an unknown or missing diagram type, an
+//! invalid flowchart direction, unbalanced brackets and quotes (in
+//! flowcharts and class, state and ER diagrams), and blocks (`subgraph`,
+//! `loop`, `alt`, ...) without a matching `end`. Every error carries a
+//! 1-based line and column so the editor can mark it inline.
+
+use serde::{Deserialize, Serialize};
+
+/// Diagram keywords accepted on the first line
+const DIAGRAM_TYPES: [&str; 24] = [
+    "graph",
+    "flowchart",
+    "flowchart-elk",
+    "sequenceDiagram",
+    "classDiagram",
+    "classDiagram-v2",
+    "stateDiagram",
+    "stateDiagram-v2",
+    "erDiagram",
+    "gantt",
+    "pie",
+    "journey",
+    "gitGraph",
+    "mindmap",
+    "timeline",
+    "quadrantChart",
+    "requirementDiagram",
+    "C4Context",
+    "C4Container",
+    "C4Component",
+    "C4Dynamic",
+    "sankey-beta",
+    "xychart-beta",
+    "block-beta",
+];
+
+const FLOWCHART_DIRECTIONS: [&str; 5] = ["TB", "TD", "BT", "RL", "LR"];
+
+/// Sequence diagram keywords that open a block closed by `end`
+const SEQUENCE_BLOCKS: [&str; 7] = ["loop", "alt", "opt", "par", "critical", "break", "rect"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagramError {
+    /// 1-based
+    pub line: usize,
+    /// 1-based, in characters
+    pub column: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagramValidation {
+    pub valid: bool,
+    /// Keyword from the first line, e.g. "flowchart" or "sequenceDiagram"
+    #[serde(rename = "diagramType")]
+    pub diagram_type: Option<String>,
+    pub errors: Vec<DiagramError>,
+}
+
+/// Checks a mermaid source and lists its syntax errors.
+pub fn validate(source: &str) -> DiagramValidation {
+    let mut errors = Vec::new();
+    let lines: Vec<&str> = source.lines().collect();
+    let body_start = skip_front_matter(&lines, &mut errors);
+
+    let mut diagram_type: Option<String> = None;
+    // Open blocks: (keyword, line, column)
+    let mut blocks: Vec<(String, usize, usize)> = Vec::new();
+    // Open `{` bodies: (line, column)
+    let mut braces: Vec<(usize, usize)> = Vec::new();
+
+    for (index, raw_line) in lines.iter().enumerate().skip(body_start) {
+        let line_number = index + 1;
+        let line = strip_comment(raw_line);
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let column = line[..indent].chars().count() + 1;
+
+        let Some(kind) = diagram_type.clone() else {
+            match check_header(trimmed) {
+                Ok(kind) => diagram_type = Some(kind),
+                Err(message) => {
+                    errors.push(DiagramError { line: line_number, column, message });
+                    break;
+                }
+            }
+            continue;
+        };
+
+        match kind.as_str() {
+            "graph" | "flowchart" | "flowchart-elk" => {
+                check_brackets(line, line_number, true, &mut braces, &mut errors);
+            }
+            // Labels after `:` are free text; entity relationships use braces
+            // as cardinality markers (`||--o{`)
+            "classDiagram" | "classDiagram-v2" | "stateDiagram" | "stateDiagram-v2" | "erDiagram" => {
+                let structure = line.split(':').next().unwrap_or(line);
+                check_brackets(structure, line_number, kind != "erDiagram", &mut braces, &mut errors);
+            }
+            // Other diagram types are mostly free text
+            _ => {}
+        }
+
+        let keyword = trimmed.split_whitespace().next().unwrap_or("");
+        let opens_block = match kind.as_str() {
+            "graph" | "flowchart" | "flowchart-elk" => keyword == "subgraph",
+            "sequenceDiagram" => SEQUENCE_BLOCKS.contains(&keyword),
+            _ => false,
+        };
+        if opens_block {
+            blocks.push((keyword.to_string(), line_number, column));
+        } else if trimmed == "end" && blocks.pop().is_none() {
+            errors.push(DiagramError {
+                line: line_number,
+                column,
+                message: "`end` without an open block".to_string(),
+            });
+        }
+    }
+
+    if diagram_type.is_none() && errors.is_empty() {
+        errors.push(DiagramError {
+            line: body_start.min(lines.len()) + 1,
+            column: 1,
+            message: "Diagram is empty; expected a diagram type such as `flowchart TD`".to_string(),
+        });
+    }
+    for (line, column) in braces {
+        errors.push(DiagramError {
+            line,
+            column,
+            message: "`{` is never closed".to_string(),
+        });
+    }
+    for (keyword, line, column) in blocks {
+        errors.push(DiagramError {
+            line,
+            column,
+            message: format!("`{}` block is never closed with `end`", keyword),
+        });
+    }
+
+    errors.sort_by_key(|error| (error.line, error.column));
+    DiagramValidation {
+        valid: errors.is_empty(),
+        diagram_type,
+        errors,
+    }
+}
+
+/// Index of the first line after an optional `---` front matter block.
+fn skip_front_matter(lines: &[&str], errors: &mut Vec<DiagramError>) -> usize {
+    let Some(first) = lines.iter().position(|line| !line.trim().is_empty()) else {
+        return lines.len();
+    };
+    if lines[first].trim() != "---" {
+        return 0;
+    }
+    match lines[first + 1..].iter().position(|line| line.trim() == "---") {
+        Some(offset) => first + offset + 2,
+        None => {
+            errors.push(DiagramError {
+                line: first + 1,
+                column: 1,
+                message: "Front matter is never closed with `---`".to_string(),
+            });
+            lines.len()
+        }
+    }
+}
+
+// `%%` starts a comment, including `%%{init: ...}%%` directives
+fn strip_comment(line: &str) -> &str {
+    match line.find("%%") {
+        Some(position) => &line[..position],
+        None => line,
+    }
+}
+
+/// Diagram type of the header line, or why it isn't one.
+fn check_header(line: &str) -> Result<String, String> {
+    let mut words = line.split_whitespace();
+    let keyword = words.next().unwrap_or("");
+    if !DIAGRAM_TYPES.contains(&keyword) {
+        return Err(format!(
+            "Unknown diagram type `{}`; expected one of: {}",
+            keyword,
+            DIAGRAM_TYPES.join(", ")
+        ));
+    }
+
+    if matches!(keyword, "graph" | "flowchart" | "flowchart-elk") {
+        if let Some(direction) = words.next() {
+            let direction = direction.trim_end_matches(';');
+            if !FLOWCHART_DIRECTIONS.contains(&direction) {
+                return Err(format!(
+                    "Invalid flowchart direction `{}`; expected one of: {}",
+                    direction,
+                    FLOWCHART_DIRECTIONS.join(", ")
+                ));
+            }
+        }
+    }
+    Ok(keyword.to_string())
+}
+
+/// Reports unbalanced `()`, `[]` and unterminated `"` on one line, and
+/// tracks `{}` in `braces` (unless `brace_bodies` is false) since class and
+/// state bodies span lines. Text inside quotes is not checked, so labels can
+/// contain brackets. The flowchart shape `id>text]` is accepted.
+fn check_brackets(
+    line: &str,
+    line_number: usize,
+    brace_bodies: bool,
+    braces: &mut Vec<(usize, usize)>,
+    errors: &mut Vec<DiagramError>,
+) {
+    let mut stack: Vec<(char, usize)> = Vec::new();
+    let mut quote_start: Option<usize> = None;
+
+    let mut previous = ' ';
+    for (index, c) in line.chars().enumerate() {
+        let column = index + 1;
+        let after = std::mem::replace(&mut previous, c);
+        if quote_start.is_some() {
+            if c == '"' {
+                quote_start = None;
+            }
+            continue;
+        }
+        match c {
+            '"' => quote_start = Some(column),
+            '{' | '}' if !brace_bodies => {}
+            // Asymmetric node: an id directly followed by `>`
+            '>' if after.is_alphanumeric() || after == '_' => stack.push((c, column)),
+            '(' | '[' | '{' => stack.push((c, column)),
+            ')' | ']' | '}' => {
+                let expected = match c {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                match stack.pop() {
+                    Some((open, _)) if open == expected || (open == '>' && c == ']') => {}
+                    Some((open, open_column)) => {
+                        errors.push(DiagramError {
+                            line: line_number,
+                            column,
+                            message: format!(
+                                "`{}` doesn't match `{}` opened at column {}",
+                                c, open, open_column
+                            ),
+                        });
+                        return;
+                    }
+                    // Closes a body opened on an earlier line
+                    None if c == '}' && braces.pop().is_some() => {}
+                    None => {
+                        errors.push(DiagramError {
+                            line: line_number,
+                            column,
+                            message: format!("Unmatched `{}`", c),
+                        });
+                        return;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(start) = quote_start {
+        errors.push(DiagramError {
+            line: line_number,
+            column: start,
+            message: "Unterminated string".to_string(),
+        });
+    }
+    for (open, column) in stack {
+        if open == '{' {
+            braces.push((line_number, column));
+        } else if open != '>' {
+            errors.push(DiagramError {
+                line: line_number,
+                column,
+                message: format!("`{}` is never closed", open),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (line, column) of each error
+    fn error_positions(source: &str) -> Vec<(usize, usize)> {
+        validate(source).errors.iter().map(|error| (error.line, error.column)).collect()
+    }
+
+    #[test]
+    fn test_valid_flowchart() {
+        let validation = validate("flowchart TD\n  A[Start] --> B(End)\n");
+        assert!(validation.valid);
+        assert_eq!(validation.diagram_type.as_deref(), Some("flowchart"));
+        assert!(validation.errors.is_empty());
+    }
+
+    #[test]
+    fn test_front_matter_is_skipped() {
+        let validation = validate("---\ntitle: Flow\n---\nflowchart LR\n  A --> B\n");
+        assert!(validation.valid);
+        assert_eq!(validation.diagram_type.as_deref(), Some("flowchart"));
+    }
+
+    #[test]
+    fn test_brackets_in_quotes_and_comments_are_ignored() {
+        assert_eq!(error_positions("flowchart LR\n  A[\"a [label\"] --> B\n"), vec![]);
+        assert_eq!(error_positions("flowchart LR\n  A --> B %% comment (\n"), vec![]);
+    }
+
+    #[test]
+    fn test_flag_shape_is_valid() {
+        assert_eq!(error_positions("flowchart LR\n  A>flag] --> B\n"), vec![]);
+    }
+
+    #[test]
+    fn test_valid_er_diagram() {
+        let validation = validate("erDiagram\n  CUSTOMER ||--o{ ORDER : places\n");
+        assert!(validation.valid);
+        assert_eq!(validation.diagram_type.as_deref(), Some("erDiagram"));
+    }
+
+    #[test]
+    fn test_missing_or_unknown_diagram_type() {
+        for source in ["", "sequence\n  A->>B: hi\n", "flowchart XY\n"] {
+            let validation = validate(source);
+            assert!(!validation.valid);
+            assert_eq!(validation.diagram_type, None);
+            assert_eq!(error_positions(source), vec![(1, 1)]);
+        }
+    }
+
+    #[test]
+    fn test_unclosed_front_matter() {
+        assert_eq!(validate("---\ntitle: Flow\n").diagram_type, None);
+        assert_eq!(error_positions("---\ntitle: Flow\n"), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_unclosed_bracket() {
+        let validation = validate("flowchart LR\n  A[Start --> B\n");
+        assert!(!validation.valid);
+        assert_eq!(validation.diagram_type.as_deref(), Some("flowchart"));
+        assert_eq!(error_positions("flowchart LR\n  A[Start --> B\n"), vec![(2, 4)]);
+    }
+
+    #[test]
+    fn test_mismatched_bracket() {
+        assert_eq!(error_positions("flowchart LR\n  A(Start] --> B\n"), vec![(2, 10)]);
+    }
+
+    #[test]
+    fn test_unterminated_quote() {
+        // The quote swallows the `]`, so the `[` stays open too
+        assert_eq!(error_positions("flowchart LR\n  A[\"label] --> B\n"), vec![(2, 4), (2, 5)]);
+    }
+
+    #[test]
+    fn test_unclosed_subgraph() {
+        assert_eq!(error_positions("flowchart LR\n  subgraph one\n    A --> B\n"), vec![(2, 3)]);
+    }
+
+    #[test]
+    fn test_unmatched_end() {
+        let source = "sequenceDiagram\n  loop Every minute\n    A->>B: ping\n  end\nend\n";
+        assert_eq!(validate(source).diagram_type.as_deref(), Some("sequenceDiagram"));
+        assert_eq!(error_positions(source), vec![(5, 1)]);
+    }
+
+    #[test]
+    fn test_unclosed_class_body() {
+        let source = "classDiagram\n  class Animal {\n    +name\n";
+        assert_eq!(validate(source).diagram_type.as_deref(), Some("classDiagram"));
+        assert_eq!(error_positions(source), vec![(2, 16)]);
+    }
+}
@@ -0,0 +1,229 @@
+//! Front matter schemas for kits, walkthroughs, agents and diagrams.
+//!
+//! Each artifact type lists the fields it requires and the YAML type every
+//! known field must have. Validation returns structured diagnostics (errors
+//! block publishing, warnings are advice) for the metadata editor and for
+//! the library publish path, which rejects artifacts with errors before
+//! anything is pushed.
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// Values accepted in the `type` field
+const KNOWN_TYPES: [&str; 5] = ["kit", "walkthrough", "agent", "diagram", "task"];
+
+/// Longest tag accepted without a warning
+const MAX_TAG_LENGTH: usize = 40;
+
+#[derive(Clone, Copy)]
+enum FieldKind {
+    String,
+    Bool,
+    Number,
+    StringList,
+}
+
+/// Fields every schema knows and the type they must have
+const FIELD_KINDS: [(&str, FieldKind); 11] = [
+    ("id", FieldKind::String),
+    ("alias", FieldKind::String),
+    ("title", FieldKind::String),
+    ("description", FieldKind::String),
+    ("type", FieldKind::String),
+    ("tags", FieldKind::StringList),
+    ("is_base", FieldKind::Bool),
+    ("version", FieldKind::Number),
+    ("capabilities", FieldKind::StringList),
+    ("tools", FieldKind::StringList),
+    ("model", FieldKind::String),
+];
+
+/// Required fields per artifact type
+fn required_fields(artifact_type: &str) -> &'static [&'static str] {
+    match artifact_type {
+        "kit" | "walkthrough" | "agent" => &["alias", "description"],
+        "diagram" => &["alias"],
+        _ => &[],
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataDiagnostic {
+    /// "error" or "warning"
+    pub severity: String,
+    /// Front matter field the diagnostic is about, if any
+    pub field: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataValidation {
+    /// True when there are no errors (warnings are allowed)
+    pub valid: bool,
+    /// Type the artifact was validated as
+    #[serde(rename = "artifactType")]
+    pub artifact_type: Option<String>,
+    pub diagnostics: Vec<MetadataDiagnostic>,
+}
+
+impl MetadataValidation {
+    /// Error messages joined into one line, for commands that refuse
+    /// invalid artifacts.
+    pub fn error_summary(&self) -> String {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == "error")
+            .map(|d| d.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Artifact type implied by a path's directory (`kits/` -> "kit", ...).
+/// Accepts full paths and paths relative to `.bluekit`.
+pub fn type_from_path(path: &str) -> Option<&'static str> {
+    let normalized = path.replace('\\', "/");
+    let relative = match normalized.rfind(".bluekit/") {
+        Some(position) => &normalized[position + ".bluekit/".len()..],
+        None => normalized.as_str(),
+    };
+    match relative.split('/').next()? {
+        "kits" => Some("kit"),
+        "walkthroughs" => Some("walkthrough"),
+        "agents" => Some("agent"),
+        "diagrams" => Some("diagram"),
+        _ => None,
+    }
+}
+
+/// Validates an artifact's front matter against the schema for its type.
+///
+/// `expected_type` (usually from [`type_from_path`]) is used when the front
+/// matter has no `type` and must match it when it does.
+pub fn validate_metadata(content: &str, expected_type: Option<&str>) -> MetadataValidation {
+    let mut diagnostics = Vec::new();
+
+    let front_matter = match crate::core::validation::parse_front_matter_strict(content) {
+        Ok(Some(Value::Mapping(mapping))) => mapping,
+        Ok(Some(_)) => serde_yaml::Mapping::new(),
+        Ok(None) => {
+            diagnostics.push(error(None, "Artifact has no front matter".to_string()));
+            return finish(expected_type.map(str::to_string), diagnostics);
+        }
+        Err(e) => {
+            diagnostics.push(error(None, e));
+            return finish(expected_type.map(str::to_string), diagnostics);
+        }
+    };
+    let get = |field: &str| front_matter.get(field);
+
+    // Type
+    let declared = get("type").and_then(Value::as_str);
+    let artifact_type = match (declared, expected_type) {
+        (Some(declared), _) if !KNOWN_TYPES.contains(&declared) => {
+            diagnostics.push(error(
+                Some("type"),
+                format!("Unknown type '{}' (expected one of: {})", declared, KNOWN_TYPES.join(", ")),
+            ));
+            None
+        }
+        (Some(declared), Some(expected)) if declared != expected => {
+            diagnostics.push(error(
+                Some("type"),
+                format!("Type '{}' doesn't match its folder, which holds '{}' artifacts", declared, expected),
+            ));
+            Some(declared)
+        }
+        (Some(declared), _) => Some(declared),
+        (None, Some(expected)) => {
+            diagnostics.push(warning(Some("type"), format!("Missing `type`; expected '{}'", expected)));
+            Some(expected)
+        }
+        (None, None) => None,
+    };
+
+    // Required fields
+    for field in artifact_type.map(required_fields).unwrap_or_default() {
+        let present = get(field).is_some_and(|value| match value {
+            Value::String(s) => !s.trim().is_empty(),
+            Value::Null => false,
+            _ => true,
+        });
+        if !present {
+            diagnostics.push(error(Some(field), format!("Missing required field `{}`", field)));
+        }
+    }
+
+    // Field types
+    for (field, kind) in FIELD_KINDS {
+        let Some(value) = get(field) else {
+            continue;
+        };
+        let matches = match kind {
+            FieldKind::String => value.is_string() || value.is_null(),
+            FieldKind::Bool => value.is_bool(),
+            FieldKind::Number => value.is_number(),
+            FieldKind::StringList => value
+                .as_sequence()
+                .is_some_and(|items| items.iter().all(Value::is_string)),
+        };
+        if !matches {
+            let expected = match kind {
+                FieldKind::String => "a string",
+                FieldKind::Bool => "true or false",
+                FieldKind::Number => "a number",
+                FieldKind::StringList => "a list of strings",
+            };
+            diagnostics.push(error(Some(field), format!("`{}` must be {}", field, expected)));
+        }
+    }
+
+    // Tag format
+    if let Some(tags) = get("tags").and_then(Value::as_sequence) {
+        for tag in tags.iter().filter_map(Value::as_str) {
+            if !is_valid_tag(tag) {
+                diagnostics.push(warning(
+                    Some("tags"),
+                    format!(
+                        "Tag '{}' should be lowercase letters, digits and hyphens (at most {} characters)",
+                        tag, MAX_TAG_LENGTH
+                    ),
+                ));
+            }
+        }
+    }
+
+    finish(artifact_type.map(str::to_string), diagnostics)
+}
+
+fn is_valid_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag.len() <= MAX_TAG_LENGTH
+        && !tag.starts_with('-')
+        && !tag.ends_with('-')
+        && tag.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+fn finish(artifact_type: Option<String>, diagnostics: Vec<MetadataDiagnostic>) -> MetadataValidation {
+    MetadataValidation {
+        valid: !diagnostics.iter().any(|d| d.severity == "error"),
+        artifact_type,
+        diagnostics,
+    }
+}
+
+fn error(field: Option<&str>, message: String) -> MetadataDiagnostic {
+    MetadataDiagnostic {
+        severity: "error".to_string(),
+        field: field.map(str::to_string),
+        message,
+    }
+}
+
+fn warning(field: Option<&str>, message: String) -> MetadataDiagnostic {
+    MetadataDiagnostic {
+        severity: "warning".to_string(),
+        field: field.map(str::to_string),
+        message,
+    }
+}
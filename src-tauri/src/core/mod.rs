@@ -1,15 +1,85 @@
 /// Core application functionality module.
 /// 
 /// This module contains core functionality used throughout the application:
+/// - Zip archives with checksums
+/// - Assets attached to artifacts
+/// - Broken link reports with suggested fixes
 /// - File content caching
+/// - Cancellation of long-running operations
+/// - Checksum-verified file copies
+/// - Conflict policies for copy commands
+/// - Data directory location and migration
+/// - Line diffs between artifacts
+/// - Dry-run previews for destructive commands
+/// - Text encoding detection
+/// - Advisory file locks
+/// - Chunked streaming reads of large files
+/// - Search across tasks, plans, walkthroughs and artifacts
+/// - Health diagnostics
+/// - Self-contained HTML exports
+/// - `.bluekitignore` patterns
+/// - Link graph and backlinks between artifacts
+/// - Linked (reference) artifact resolution
+/// - Markdown lint for the project health tab
+/// - Front matter schemas per artifact type
+/// - Mermaid diagram syntax checks
+/// - Path conversion for IPC
+/// - Application settings
+/// - Progress events for long-running operations
+/// - Periodic progress reports
+/// - Word count and reading time
+/// - Project registry validation and repair
+/// - Task reminders
+/// - Recurring job scheduler
+/// - Full-text search index for artifacts
+/// - First-run starter content
 /// - Application state management
+/// - Terminal (PTY) sessions
+/// - Trash for deleted artifacts
 /// - Utility functions
+/// - Artifact validation (and the headless `validate` mode)
 /// - File watching
+/// - `[[wikilink]]` parsing and resolution
 
+pub mod archive;
+pub mod assets;
+pub mod broken_links;
 pub mod cache;
+pub mod cancellation;
+pub mod checksum;
+pub mod copy_conflict;
+pub mod data_dir;
+pub mod diff;
+pub mod dry_run;
+pub mod encoding;
+pub mod file_lock;
+pub mod file_stream;
+pub mod global_search;
+pub mod health;
+pub mod html_export;
+pub mod ignore_rules;
+pub mod link_graph;
+pub mod links;
+pub mod lint;
+pub mod mermaid;
+pub mod metadata_schema;
+pub mod paths;
+pub mod progress;
+pub mod progress_report;
+pub mod read_stats;
+pub mod registry;
+pub mod reminders;
+pub mod scheduler;
+pub mod search_index;
+pub mod settings;
+pub mod starter_content;
 pub mod state;
+pub mod terminal;
+pub mod trash;
 pub mod utils;
+pub mod validation;
 pub mod watcher;
+pub mod wikilinks;
 
 // Re-export commonly used types
 pub use state::AppState;
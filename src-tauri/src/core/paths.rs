@@ -0,0 +1,195 @@
+//! Path conversion helpers for IPC.
+//!
+//! Paths cross the IPC boundary as JSON strings. Valid UTF-8 paths are sent
+//! as-is; anything else (non-UTF-8 bytes on Unix, unpaired surrogates on
+//! Windows) is hex-encoded behind `ENCODED_PREFIX` so it survives the round
+//! trip instead of being rejected or silently mangled.
+//!
+//! Commands should build paths from frontend strings with `from_ipc` and hand
+//! paths back with `to_ipc`.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// Marks a path string that carries hex-encoded OS bytes
+pub const ENCODED_PREFIX: &str = "bluekit-os-path:";
+
+/// Converts a path into a string that `from_ipc` turns back into the same path.
+pub fn to_ipc(path: &Path) -> String {
+    let path = strip_long_path_prefix(path);
+    match path.to_str() {
+        Some(s) => s.to_string(),
+        None => format!("{}{}", ENCODED_PREFIX, encode_os_str(path.as_os_str())),
+    }
+}
+
+/// Converts a path string received over IPC back into a path.
+///
+/// Strings that look encoded but fail to decode are treated as literal paths.
+pub fn from_ipc(s: &str) -> PathBuf {
+    let path = s
+        .strip_prefix(ENCODED_PREFIX)
+        .and_then(decode_os_str)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(s));
+
+    long_path(&path)
+}
+
+/// File name of `path`, with invalid UTF-8 replaced (for display and matching).
+pub fn file_name_lossy(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// File stem of `path`, with invalid UTF-8 replaced (for display and matching).
+pub fn file_stem_lossy(path: &Path) -> String {
+    path.file_stem()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// `path` with symlinks and `..` resolved, for comparing paths from different
+/// sources (file watchers on macOS report `/private/var/...` for
+/// `/var/...`). The path need not exist: its nearest existing ancestor is
+/// resolved and the rest appended. Falls back to `path` as given.
+pub fn canonical_lenient(path: &Path) -> PathBuf {
+    let mut missing = Vec::new();
+    let mut current = path;
+    loop {
+        if let Ok(resolved) = std::fs::canonicalize(current) {
+            return missing.iter().rev().fold(resolved, |acc, name| acc.join(name));
+        }
+        match (current.parent(), current.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                current = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+/// Every file under `dir`, skipping hidden (`.`-prefixed) entries.
+/// Symlinked files are included; symlinked directories aren't followed, so a
+/// link back up the tree can't loop.
+pub fn files_under(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files);
+    files
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if file_name_lossy(&path).starts_with('.') {
+            continue;
+        }
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => collect_files(&path, files),
+            Ok(_) if path.is_file() => files.push(path),
+            _ => {}
+        }
+    }
+}
+
+/// Maximum path length the classic Win32 APIs accept without the `\\?\` prefix
+#[cfg(windows)]
+const MAX_PATH: usize = 260;
+
+/// Opts long absolute paths into Windows extended-length handling.
+///
+/// Verbatim (`\\?\`) paths bypass normalization, so the path must already be
+/// absolute and free of `.`/`..` components; anything else is returned as-is.
+/// On other platforms this is a no-op.
+#[cfg(windows)]
+pub fn long_path(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    let raw = path.as_os_str();
+    if raw.len() < MAX_PATH
+        || !path.is_absolute()
+        || raw.to_string_lossy().starts_with(r"\\?\")
+        || path
+            .components()
+            .any(|c| matches!(c, Component::CurDir | Component::ParentDir))
+    {
+        return path.to_path_buf();
+    }
+
+    // Verbatim paths only accept backslashes
+    let normalized: PathBuf = path.components().collect();
+    let normalized = normalized.as_os_str().to_string_lossy().replace('/', r"\");
+    match normalized.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!(r"\\?\UNC\{}", unc)),
+        None => PathBuf::from(format!(r"\\?\{}", normalized)),
+    }
+}
+
+#[cfg(not(windows))]
+pub fn long_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+// Drops a `\\?\` prefix added by `long_path` so the frontend sees ordinary paths
+#[cfg(windows)]
+fn strip_long_path_prefix(path: &Path) -> PathBuf {
+    let raw = path.to_string_lossy();
+    if let Some(unc) = raw.strip_prefix(r"\\?\UNC\") {
+        if path.to_str().is_some() {
+            return PathBuf::from(format!(r"\\{}", unc));
+        }
+    } else if let Some(rest) = raw.strip_prefix(r"\\?\") {
+        if path.to_str().is_some() {
+            return PathBuf::from(rest);
+        }
+    }
+    path.to_path_buf()
+}
+
+#[cfg(not(windows))]
+fn strip_long_path_prefix(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+#[cfg(unix)]
+fn encode_os_str(s: &OsStr) -> String {
+    use std::os::unix::ffi::OsStrExt;
+    s.as_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(unix)]
+fn decode_os_str(hex: &str) -> Option<OsString> {
+    use std::os::unix::ffi::OsStringExt;
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    Some(OsString::from_vec(bytes))
+}
+
+#[cfg(windows)]
+fn encode_os_str(s: &OsStr) -> String {
+    use std::os::windows::ffi::OsStrExt;
+    s.encode_wide().map(|unit| format!("{:04x}", unit)).collect()
+}
+
+#[cfg(windows)]
+fn decode_os_str(hex: &str) -> Option<OsString> {
+    use std::os::windows::ffi::OsStringExt;
+    if !hex.len().is_multiple_of(4) {
+        return None;
+    }
+    let units = (0..hex.len())
+        .step_by(4)
+        .map(|i| u16::from_str_radix(hex.get(i..i + 4)?, 16).ok())
+        .collect::<Option<Vec<u16>>>()?;
+    Some(OsString::from_wide(&units))
+}
@@ -0,0 +1,130 @@
+//! Common progress protocol for long-running operations.
+//!
+//! Every long operation (library sync, bulk publish, bulk pull, clone and
+//! project creation, archive export/import) reports through a [`ProgressReporter`],
+//! which emits `operation-progress` events with the same payload shape. The
+//! frontend can pass its own operation id to a command so it can subscribe
+//! before the first event arrives; otherwise one is generated. The same id
+//! can be passed to `cancel_operation` to stop the operation. Each update is
+//! also sent on the operation's own channel ([`operation_event_name`]), so a
+//! listener for one job doesn't have to filter everyone else's.
+
+use crate::core::cancellation::{CancellationToken, CANCELLED_ERROR};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+/// Event carrying a [`ProgressUpdate`]
+pub const PROGRESS_EVENT: &str = "operation-progress";
+
+/// Per-operation channel: `operation-progress/<operation id>`
+pub fn operation_event_name(operation_id: &str) -> String {
+    format!("{}/{}", PROGRESS_EVENT, operation_id)
+}
+
+/// One progress update
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgressUpdate {
+    #[serde(rename = "operationId")]
+    pub operation_id: String,
+    /// Operation kind, e.g. "library_sync" or "archive_export"
+    pub kind: String,
+    /// Current step, e.g. "cloning" or "copying"
+    pub phase: String,
+    pub current: u64,
+    /// None while the amount of work is still unknown
+    pub total: Option<u64>,
+    pub message: String,
+    /// "running", "completed", "failed" or "cancelled"
+    pub status: String,
+}
+
+/// Emits progress updates for one operation and carries its cancellation
+/// token.
+///
+/// A reporter created with [`ProgressReporter::silent`] drops updates and
+/// can't be cancelled, for callers without a window such as background jobs.
+pub struct ProgressReporter {
+    app_handle: Option<AppHandle>,
+    operation_id: String,
+    kind: String,
+    cancellation: CancellationToken,
+}
+
+impl ProgressReporter {
+    /// Creates a reporter, generating an operation id if none is given, and
+    /// registers the operation for cancellation.
+    pub fn new(app_handle: AppHandle, kind: &str, operation_id: Option<String>) -> Self {
+        let operation_id = operation_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+        Self {
+            app_handle: Some(app_handle),
+            cancellation: CancellationToken::register(&operation_id),
+            operation_id,
+            kind: kind.to_string(),
+        }
+    }
+
+    /// Creates a reporter that emits nothing.
+    pub fn silent(kind: &str) -> Self {
+        Self {
+            app_handle: None,
+            operation_id: Uuid::new_v4().to_string(),
+            kind: kind.to_string(),
+            cancellation: CancellationToken::none(),
+        }
+    }
+
+    pub fn operation_id(&self) -> &str {
+        &self.operation_id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Fails with the cancellation error once the operation has been
+    /// cancelled; call between steps.
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        self.cancellation.check()
+    }
+
+    /// Reports progress within a phase.
+    pub fn report(&self, phase: &str, current: u64, total: Option<u64>, message: impl Into<String>) {
+        self.emit(phase, current, total, message.into(), "running");
+    }
+
+    /// Reports successful completion.
+    pub fn complete(&self, message: impl Into<String>) {
+        self.emit("done", 1, Some(1), message.into(), "completed");
+    }
+
+    /// Reports failure (or cancellation); returns the error so it can be
+    /// propagated with `?`.
+    pub fn fail(&self, error: String) -> String {
+        let status = if error == CANCELLED_ERROR { "cancelled" } else { "failed" };
+        self.emit("done", 0, None, error.clone(), status);
+        error
+    }
+
+    fn emit(&self, phase: &str, current: u64, total: Option<u64>, message: String, status: &str) {
+        let Some(app_handle) = &self.app_handle else {
+            return;
+        };
+
+        let update = ProgressUpdate {
+            operation_id: self.operation_id.clone(),
+            kind: self.kind.clone(),
+            phase: phase.to_string(),
+            current,
+            total,
+            message,
+            status: status.to_string(),
+        };
+        let channel = operation_event_name(&self.operation_id);
+        for event in [PROGRESS_EVENT, channel.as_str()] {
+            if let Err(e) = app_handle.emit_all(event, update.clone()) {
+                tracing::warn!("Failed to emit progress: {}", e);
+            }
+        }
+    }
+}
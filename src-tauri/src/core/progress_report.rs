@@ -0,0 +1,248 @@
+//! Progress reports for standups and stakeholder updates.
+//!
+//! A report covers one period (the last seven days by default) and lists
+//! milestones completed in the project's plans, finished tasks, artifacts
+//! added under `.bluekit` and checkpoints pinned. It is written as Markdown
+//! to `.bluekit/reports/` so it can be shared or committed like any other
+//! artifact.
+
+use crate::core::paths;
+use crate::db::entities::{checkpoint, plan, plan_milestone, plan_phase, project, task, task_project};
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Length of the default (weekly) period, in seconds
+const DEFAULT_PERIOD_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Report period in Unix seconds (both ends inclusive); missing ends default
+/// to the seven days up to now
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportRange {
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressReport {
+    /// Where the Markdown was written
+    pub path: String,
+    pub markdown: String,
+    pub since: i64,
+    pub until: i64,
+    #[serde(rename = "completedMilestones")]
+    pub completed_milestones: usize,
+    #[serde(rename = "finishedTasks")]
+    pub finished_tasks: usize,
+    #[serde(rename = "newArtifacts")]
+    pub new_artifacts: usize,
+    pub checkpoints: usize,
+}
+
+/// Compiles the report for `project_id` and saves it to
+/// `.bluekit/reports/progress-<since>-to-<until>.md`, replacing an earlier
+/// report for the same period.
+pub async fn generate_progress_report(
+    db: &DatabaseConnection,
+    project_id: &str,
+    range: ReportRange,
+) -> Result<ProgressReport, String> {
+    let project = project::Entity::find_by_id(project_id)
+        .one(db)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    let until = range.until.unwrap_or_else(|| Utc::now().timestamp());
+    let since = range.since.unwrap_or(until - DEFAULT_PERIOD_SECS);
+    if since > until {
+        return Err("Report range starts after it ends".to_string());
+    }
+    let in_range = |timestamp: i64| timestamp >= since && timestamp <= until;
+
+    let milestones = completed_milestones(db, project_id, &in_range).await?;
+    let tasks = finished_tasks(db, project_id, &in_range).await?;
+    let project_root = paths::from_ipc(&project.path);
+    let artifacts = new_artifacts(&project_root, &in_range);
+
+    // Checkpoint timestamps are in milliseconds
+    let checkpoints: Vec<checkpoint::Model> = checkpoint::Entity::find()
+        .filter(checkpoint::Column::ProjectId.eq(project_id))
+        .order_by_asc(checkpoint::Column::PinnedAt)
+        .all(db)
+        .await
+        .map_err(|e| format!("Failed to load checkpoints: {}", e))?
+        .into_iter()
+        .filter(|c| in_range(c.pinned_at / 1000))
+        .collect();
+
+    let mut markdown = String::new();
+    let _ = writeln!(markdown, "# Progress Report: {}", project.name);
+    let _ = writeln!(markdown);
+    let _ = writeln!(markdown, "{} to {}", format_date(since), format_date(until));
+
+    let _ = writeln!(markdown, "\n## Completed Milestones ({})\n", milestones.len());
+    if milestones.is_empty() {
+        let _ = writeln!(markdown, "_None_");
+    }
+    for (plan_name, phase_name, milestone) in &milestones {
+        let _ = writeln!(
+            markdown,
+            "- **{}** ({} / {}), {}",
+            milestone.name,
+            plan_name,
+            phase_name,
+            format_date(milestone.completed_at.unwrap_or_default())
+        );
+    }
+
+    let _ = writeln!(markdown, "\n## Finished Tasks ({})\n", tasks.len());
+    if tasks.is_empty() {
+        let _ = writeln!(markdown, "_None_");
+    }
+    for task in &tasks {
+        let _ = writeln!(markdown, "- {} ({})", task.title, task.priority);
+    }
+
+    let _ = writeln!(markdown, "\n## New Artifacts ({})\n", artifacts.len());
+    if artifacts.is_empty() {
+        let _ = writeln!(markdown, "_None_");
+    }
+    for artifact in &artifacts {
+        let _ = writeln!(markdown, "- `{}`", artifact);
+    }
+
+    let _ = writeln!(markdown, "\n## Checkpoints ({})\n", checkpoints.len());
+    if checkpoints.is_empty() {
+        let _ = writeln!(markdown, "_None_");
+    }
+    for checkpoint in &checkpoints {
+        let short_sha = checkpoint.git_commit_sha.get(..7).unwrap_or(&checkpoint.git_commit_sha);
+        let _ = writeln!(
+            markdown,
+            "- **{}** ({}, `{}`), {}",
+            checkpoint.name,
+            checkpoint.checkpoint_type,
+            short_sha,
+            format_date(checkpoint.pinned_at / 1000)
+        );
+    }
+
+    let reports_dir = project_root.join(".bluekit").join("reports");
+    fs::create_dir_all(&reports_dir)
+        .map_err(|e| format!("Failed to create reports directory: {}", e))?;
+    let report_path = reports_dir.join(format!(
+        "progress-{}-to-{}.md",
+        format_date(since),
+        format_date(until)
+    ));
+    fs::write(&report_path, &markdown).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    Ok(ProgressReport {
+        path: paths::to_ipc(&report_path),
+        markdown,
+        since,
+        until,
+        completed_milestones: milestones.len(),
+        finished_tasks: tasks.len(),
+        new_artifacts: artifacts.len(),
+        checkpoints: checkpoints.len(),
+    })
+}
+
+/// Milestones completed in range with their plan and phase names, oldest first.
+async fn completed_milestones(
+    db: &DatabaseConnection,
+    project_id: &str,
+    in_range: &impl Fn(i64) -> bool,
+) -> Result<Vec<(String, String, plan_milestone::Model)>, String> {
+    let plans = plan::Entity::find()
+        .filter(plan::Column::ProjectId.eq(project_id))
+        .filter(plan::Column::DeletedAt.is_null())
+        .all(db)
+        .await
+        .map_err(|e| format!("Failed to load plans: {}", e))?;
+
+    let mut milestones = Vec::new();
+    for plan in plans {
+        let phases = plan_phase::Entity::find()
+            .filter(plan_phase::Column::PlanId.eq(&plan.id))
+            .find_with_related(plan_milestone::Entity)
+            .all(db)
+            .await
+            .map_err(|e| format!("Failed to load plan phases: {}", e))?;
+
+        for (phase, phase_milestones) in phases {
+            for milestone in phase_milestones {
+                if milestone.completed != 0 && milestone.completed_at.is_some_and(in_range) {
+                    milestones.push((plan.name.clone(), phase.name.clone(), milestone));
+                }
+            }
+        }
+    }
+
+    milestones.sort_by_key(|(_, _, milestone)| milestone.completed_at);
+    Ok(milestones)
+}
+
+/// Tasks linked to the project that are completed and were last updated in
+/// range (tasks don't record when they were completed).
+async fn finished_tasks(
+    db: &DatabaseConnection,
+    project_id: &str,
+    in_range: &impl Fn(i64) -> bool,
+) -> Result<Vec<task::Model>, String> {
+    let links = task_project::Entity::find()
+        .filter(task_project::Column::ProjectId.eq(project_id))
+        .find_also_related(task::Entity)
+        .all(db)
+        .await
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+
+    let mut tasks: Vec<task::Model> = links
+        .into_iter()
+        .filter_map(|(_, task)| task)
+        .filter(|task| task.status == "completed" && task.deleted_at.is_none())
+        .filter(|task| {
+            DateTime::parse_from_rfc3339(&task.updated_at)
+                .is_ok_and(|updated| in_range(updated.timestamp()))
+        })
+        .collect();
+    tasks.sort_by(|a, b| a.updated_at.cmp(&b.updated_at));
+    Ok(tasks)
+}
+
+/// `.bluekit` files created in range, relative to the project root. Earlier
+/// reports are left out.
+fn new_artifacts(project_root: &Path, in_range: &impl Fn(i64) -> bool) -> Vec<String> {
+    let bluekit_dir = project_root.join(".bluekit");
+    let mut artifacts: Vec<String> = paths::files_under(&bluekit_dir)
+        .into_iter()
+        .filter(|path| !path.starts_with(bluekit_dir.join("reports")))
+        .filter(|path| created_at(path).is_some_and(in_range))
+        .filter_map(|path| {
+            path.strip_prefix(project_root)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    artifacts.sort();
+    artifacts
+}
+
+// Creation time where the filesystem records it, modification time otherwise
+fn created_at(path: &Path) -> Option<i64> {
+    let metadata = fs::metadata(path).ok()?;
+    let time = metadata.created().or_else(|_| metadata.modified()).ok()?;
+    Some(time.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64)
+}
+
+fn format_date(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
@@ -0,0 +1,93 @@
+//! Word count and reading time for markdown artifacts.
+//!
+//! Stats cover the markdown body (front matter stripped by the caller).
+//! Words inside fenced code blocks aren't counted as prose; the blocks are
+//! counted separately.
+
+use serde::{Deserialize, Serialize};
+
+/// Reading speed used for `reading_time_minutes`
+pub const WORDS_PER_MINUTE: usize = 200;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadStats {
+    /// Words outside code blocks
+    #[serde(rename = "wordCount")]
+    pub word_count: usize,
+    /// ATX headings (`#` to `######`)
+    #[serde(rename = "headingCount")]
+    pub heading_count: usize,
+    /// Fenced code blocks (``` or ~~~)
+    #[serde(rename = "codeBlockCount")]
+    pub code_block_count: usize,
+    /// Estimated minutes to read the prose, rounded up; 0 when empty
+    #[serde(rename = "readingTimeMinutes")]
+    pub reading_time_minutes: usize,
+}
+
+/// Computes stats for a markdown body.
+pub fn read_stats(body: &str) -> ReadStats {
+    let mut stats = ReadStats::default();
+    // The fence that opened the current code block, if inside one
+    let mut open_fence: Option<String> = None;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some(fence) = &open_fence {
+            if closes_fence(trimmed, fence) {
+                open_fence = None;
+            }
+            continue;
+        }
+        if let Some(fence) = fence_of(trimmed) {
+            stats.code_block_count += 1;
+            open_fence = Some(fence);
+            continue;
+        }
+
+        let text = match heading_text(trimmed) {
+            Some(text) => {
+                stats.heading_count += 1;
+                text
+            }
+            None => trimmed,
+        };
+        stats.word_count += text
+            .split_whitespace()
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .count();
+    }
+
+    stats.reading_time_minutes = stats.word_count.div_ceil(WORDS_PER_MINUTE);
+    stats
+}
+
+// The opening fence (three or more backticks or tildes) if `line` starts a
+// code block
+fn fence_of(line: &str) -> Option<String> {
+    let marker = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = line.chars().take_while(|c| *c == marker).count();
+    (length >= 3).then(|| marker.to_string().repeat(length))
+}
+
+// Whether `line` is a closing fence for `fence`: the same character, at
+// least as many times, and nothing else
+fn closes_fence(line: &str, fence: &str) -> bool {
+    let line = line.trim_end();
+    line.len() >= fence.len() && line.chars().all(|c| fence.starts_with(c))
+}
+
+// Heading text if `line` is an ATX heading
+fn heading_text(line: &str) -> Option<&str> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if rest.is_empty() || rest.starts_with([' ', '\t']) {
+        Some(rest.trim())
+    } else {
+        None
+    }
+}
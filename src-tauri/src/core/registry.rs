@@ -0,0 +1,272 @@
+//! The JSON project registry (`projectRegistry.json` in the data directory).
+//!
+//! Entries are read one by one, so a malformed entry is skipped (and
+//! reported by [`validate_registry`]) instead of failing the whole file.
+//! `validate_registry` can also repair the file: malformed entries, entries
+//! whose directory is gone and duplicate ids are dropped, and relative paths
+//! that resolve against the home directory are made absolute. The original
+//! is backed up next to it before a repair is written.
+//!
+//! Every read-modify-write of the file holds a [`file_lock`] on it, so two
+//! commands editing the registry at once can't drop each other's entries.
+//!
+//! The `projects` table is the canonical project list; the registry is kept
+//! in step with it by [`sync_registry`], which uses [`apply_changes`].
+//!
+//! [`file_lock`]: crate::core::file_lock
+//! [`sync_registry`]: crate::db::project_operations::sync_registry
+
+use crate::commands::ProjectEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One problem found in the registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryIssue {
+    /// "malformed", "missing_path", "duplicate_id" or "relative_path"
+    pub kind: String,
+    /// Position of the entry in the file
+    pub index: usize,
+    pub id: Option<String>,
+    pub path: Option<String>,
+    pub message: String,
+    /// What a repair does (or did) about it: "removed" or "fixed"
+    pub action: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryReport {
+    #[serde(rename = "registryPath")]
+    pub registry_path: String,
+    /// Entries in the file as found
+    #[serde(rename = "entryCount")]
+    pub entry_count: usize,
+    pub issues: Vec<RegistryIssue>,
+    /// Whether the repaired registry was written
+    pub repaired: bool,
+    #[serde(rename = "backupPath")]
+    pub backup_path: Option<String>,
+}
+
+/// Returns the registry file path (`projectRegistry.json` in the data
+/// directory, `~/.bluekit` by default).
+pub fn registry_path() -> Result<PathBuf, String> {
+    Ok(crate::core::data_dir::data_dir()?.join("projectRegistry.json"))
+}
+
+/// Reads the registry, skipping entries that can't be parsed. A missing or
+/// empty file is an empty registry.
+pub fn read_entries() -> Result<Vec<ProjectEntry>, String> {
+    Ok(read_raw()?
+        .into_iter()
+        .filter_map(|value| serde_json::from_value(value).ok())
+        .collect())
+}
+
+/// Removes every entry with `id` from the registry.
+pub async fn remove_entry(id: &str) -> Result<(), String> {
+    let path = registry_path()?;
+    let _lock = crate::core::file_lock::lock(&path, "remove_project_from_registry").await?;
+
+    let mut raw = read_raw()?;
+    let before = raw.len();
+    raw.retain(|value| entry_id(value) != Some(id));
+    if raw.len() == before {
+        return Err(format!("Project not found in registry: {}", id));
+    }
+    write_raw(&path, &raw)
+}
+
+/// Updates the title, description and/or path of the entry with `id`.
+/// Fields of the entry that aren't given are left as they are.
+pub async fn update_entry(
+    id: &str,
+    title: Option<String>,
+    description: Option<String>,
+    project_path: Option<String>,
+) -> Result<ProjectEntry, String> {
+    if let Some(project_path) = &project_path {
+        let directory = crate::core::paths::from_ipc(project_path);
+        if !directory.is_absolute() {
+            return Err(format!("Project path must be absolute: {}", project_path));
+        }
+        if !directory.is_dir() {
+            return Err(format!("Project directory does not exist: {}", project_path));
+        }
+    }
+
+    let path = registry_path()?;
+    let _lock = crate::core::file_lock::lock(&path, "update_project_entry").await?;
+
+    let mut raw = read_raw()?;
+    let value = raw
+        .iter_mut()
+        .find(|value| entry_id(value) == Some(id))
+        .ok_or_else(|| format!("Project not found in registry: {}", id))?;
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| format!("Registry entry {} is not an object", id))?;
+    for (field, new_value) in [("title", title), ("description", description), ("path", project_path)] {
+        if let Some(new_value) = new_value {
+            object.insert(field.to_string(), serde_json::Value::String(new_value));
+        }
+    }
+
+    let entry: ProjectEntry = serde_json::from_value(value.clone())
+        .map_err(|e| format!("Registry entry {} is still malformed: {}", id, e))?;
+    write_raw(&path, &raw)?;
+    Ok(entry)
+}
+
+/// Replaces or appends each of `upserts` (matched by id) and drops every
+/// entry whose id is in `removals`. Entries that can't be parsed are kept
+/// as they are. The file is only written if something changed.
+pub async fn apply_changes(upserts: Vec<ProjectEntry>, removals: &HashSet<String>) -> Result<(), String> {
+    if upserts.is_empty() && removals.is_empty() {
+        return Ok(());
+    }
+    let path = registry_path()?;
+    let _lock = crate::core::file_lock::lock(&path, "sync_project_registry").await?;
+
+    let mut raw = read_raw()?;
+    raw.retain(|value| !entry_id(value).is_some_and(|id| removals.contains(id)));
+    for entry in upserts {
+        let value = serde_json::to_value(&entry)
+            .map_err(|e| format!("Failed to serialize registry entry {}: {}", entry.id, e))?;
+        match raw.iter_mut().find(|existing| entry_id(existing) == Some(entry.id.as_str())) {
+            Some(existing) => *existing = value,
+            None => raw.push(value),
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create registry directory: {}", e))?;
+    }
+    write_raw(&path, &raw)
+}
+
+/// Checks every registry entry and, with `repair`, rewrites the file
+/// without the broken ones.
+pub async fn validate_registry(repair: bool) -> Result<RegistryReport, String> {
+    let path = registry_path()?;
+    let _lock = if repair {
+        Some(crate::core::file_lock::lock(&path, "validate_project_registry").await?)
+    } else {
+        None
+    };
+    let raw = read_raw()?;
+    let home = home_dir()?;
+
+    let mut issues = Vec::new();
+    let mut kept: Vec<ProjectEntry> = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for (index, value) in raw.iter().enumerate() {
+        let mut entry: ProjectEntry = match serde_json::from_value(value.clone()) {
+            Ok(entry) => entry,
+            Err(e) => {
+                issues.push(RegistryIssue {
+                    kind: "malformed".to_string(),
+                    index,
+                    id: entry_id(value).map(str::to_string),
+                    path: value.get("path").and_then(|v| v.as_str()).map(str::to_string),
+                    message: format!("Entry can't be read: {}", e),
+                    action: "removed".to_string(),
+                });
+                continue;
+            }
+        };
+
+        if !seen_ids.insert(entry.id.clone()) {
+            let message = format!("Id {} is used by an earlier entry", entry.id);
+            issues.push(entry_issue("duplicate_id", index, &entry, message, "removed"));
+            continue;
+        }
+
+        if !Path::new(&entry.path).is_absolute() {
+            let resolved = home.join(&entry.path);
+            if resolved.is_dir() {
+                let message = format!("Path is not absolute; resolves to {}", resolved.display());
+                issues.push(entry_issue("relative_path", index, &entry, message, "fixed"));
+                entry.path = crate::core::paths::to_ipc(&resolved);
+            } else {
+                let message = "Path is not absolute".to_string();
+                issues.push(entry_issue("relative_path", index, &entry, message, "removed"));
+                continue;
+            }
+        }
+
+        if !crate::core::paths::from_ipc(&entry.path).is_dir() {
+            let message = "Project directory does not exist".to_string();
+            issues.push(entry_issue("missing_path", index, &entry, message, "removed"));
+            continue;
+        }
+
+        kept.push(entry);
+    }
+
+    let mut report = RegistryReport {
+        registry_path: path.display().to_string(),
+        entry_count: raw.len(),
+        issues,
+        repaired: false,
+        backup_path: None,
+    };
+    if !repair || report.issues.is_empty() {
+        return Ok(report);
+    }
+
+    let backup_path = path.with_extension("json.bak");
+    fs::copy(&path, &backup_path).map_err(|e| format!("Failed to back up project registry: {}", e))?;
+    write_raw(&path, &kept)?;
+
+    report.repaired = true;
+    report.backup_path = Some(backup_path.display().to_string());
+    Ok(report)
+}
+
+fn entry_issue(kind: &str, index: usize, entry: &ProjectEntry, message: String, action: &str) -> RegistryIssue {
+    RegistryIssue {
+        kind: kind.to_string(),
+        index,
+        id: Some(entry.id.clone()),
+        path: Some(entry.path.clone()),
+        message,
+        action: action.to_string(),
+    }
+}
+
+// The registry's entries as untyped JSON values
+fn read_raw() -> Result<Vec<serde_json::Value>, String> {
+    let path = registry_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read project registry: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse project registry: {}", e))
+}
+
+fn write_raw<T: Serialize>(path: &Path, entries: &[T]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize project registry: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write project registry: {}", e))
+}
+
+fn entry_id(value: &serde_json::Value) -> Option<&str> {
+    value.get("id").and_then(|v| v.as_str())
+}
+
+fn home_dir() -> Result<PathBuf, String> {
+    env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .map(PathBuf::from)
+        .map_err(|_| "Could not determine home directory".to_string())
+}
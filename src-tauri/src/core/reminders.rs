@@ -0,0 +1,105 @@
+//! Task reminders.
+//!
+//! A task with a `remind_at` time gets a [`REMINDER_EVENT`] carrying the
+//! task once that time passes, and a system notification unless
+//! `reminders.nativeNotifications` is off. Each reminder fires once; its
+//! `remind_at` is cleared when it does. Reminders that came due while the
+//! app was closed fire on the first check after startup.
+
+use crate::db::task_operations;
+use sea_orm::DatabaseConnection;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Event sent with the task when its reminder fires
+pub const REMINDER_EVENT: &str = "task-reminder";
+
+/// How often due reminders are checked
+const TICK_SECONDS: u64 = 30;
+
+/// Starts the reminder loop. Call once during app setup.
+pub fn start(app_handle: AppHandle, db: DatabaseConnection) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(TICK_SECONDS));
+        loop {
+            ticker.tick().await;
+            if crate::db::encryption::is_locked() {
+                continue;
+            }
+
+            let due = match task_operations::claim_due_reminders(&db, chrono::Utc::now()).await {
+                Ok(due) => due,
+                Err(e) => {
+                    tracing::warn!("Failed to load due task reminders: {}", e);
+                    continue;
+                }
+            };
+            if due.is_empty() {
+                continue;
+            }
+
+            let native = crate::core::settings::load_settings().reminders.native_notifications;
+            for task in due {
+                tracing::info!("Task reminder: {}", task.title);
+                if native {
+                    let body = match &task.due_at {
+                        Some(due_at) => format!("Due {}", due_at),
+                        None => "Task reminder".to_string(),
+                    };
+                    let title = task.title.clone();
+                    tokio::task::spawn_blocking(move || {
+                        if let Err(e) = notify(&title, &body) {
+                            tracing::debug!("Failed to show reminder notification: {}", e);
+                        }
+                    });
+                }
+                if let Err(e) = app_handle.emit_all(REMINDER_EVENT, &task) {
+                    tracing::warn!("Failed to emit task reminder: {}", e);
+                }
+            }
+        }
+    });
+}
+
+// Shows a system notification with the platform's own tool. Blocks until
+// the tool exits
+fn notify(title: &str, body: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let script = format!("display notification {} with title {}", quote(body), quote(title));
+        let mut command = std::process::Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    };
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        // Text goes through the environment so it never needs escaping
+        let script = "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null; \
+            $template = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+            $text = $template.GetElementsByTagName('text'); \
+            $text.Item(0).AppendChild($template.CreateTextNode($env:BLUEKIT_TITLE)) | Out-Null; \
+            $text.Item(1).AppendChild($template.CreateTextNode($env:BLUEKIT_BODY)) | Out-Null; \
+            [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('BlueKit').Show([Windows.UI.Notifications.ToastNotification]::new($template))";
+        let mut command = std::process::Command::new("powershell");
+        command
+            .args(["-NoProfile", "-NonInteractive", "-Command", script])
+            .env("BLUEKIT_TITLE", title)
+            .env("BLUEKIT_BODY", body);
+        command
+    };
+    #[cfg(all(not(target_os = "macos"), not(target_os = "windows")))]
+    let mut command = {
+        let mut command = std::process::Command::new("notify-send");
+        command.args(["--app-name=BlueKit", "--", title, body]);
+        command
+    };
+
+    let status = command
+        .status()
+        .map_err(|e| format!("Failed to run notifier: {}", e))?;
+    if !status.success() {
+        return Err(format!("Notifier exited with {}", status));
+    }
+    Ok(())
+}
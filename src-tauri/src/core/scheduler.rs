@@ -0,0 +1,331 @@
+//! Background scheduler for recurring jobs.
+//!
+//! Jobs and their schedules (fixed interval or cron expression) live in the
+//! `scheduled_jobs` table, so they survive restarts. A background loop wakes
+//! up every [`TICK_SECONDS`], runs whatever is due and records the outcome.
+//! Every finished run is announced with a `scheduled-job-finished` event.
+
+use crate::db::entities::{library_resource, library_subscription, library_workspace, scheduled_job};
+use crate::db::scheduler_operations;
+use sea_orm::*;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Event sent after each job run (scheduled or manual)
+pub const FINISHED_EVENT: &str = "scheduled-job-finished";
+
+/// How often the scheduler checks for due jobs
+const TICK_SECONDS: u64 = 30;
+
+/// Backups kept by default when a backup job has no `keep` param
+const DEFAULT_BACKUPS_KEPT: usize = 7;
+
+// Jobs currently running, so a manual run can't overlap a scheduled one
+static RUNNING_JOBS: once_cell::sync::Lazy<Mutex<HashSet<String>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Debug, Clone, Serialize)]
+struct FinishedPayload {
+    #[serde(rename = "jobId")]
+    job_id: String,
+    #[serde(rename = "jobType")]
+    job_type: String,
+    success: bool,
+    message: String,
+}
+
+/// Starts the scheduler loop. Call once during app setup.
+pub fn start(app_handle: AppHandle, db: DatabaseConnection) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(TICK_SECONDS));
+        loop {
+            ticker.tick().await;
+            if crate::db::encryption::is_locked() {
+                continue;
+            }
+
+            let now = chrono::Utc::now().timestamp();
+            let due = match scheduler_operations::claim_due_jobs(&db, now).await {
+                Ok(due) => due,
+                Err(e) => {
+                    tracing::warn!("Failed to load due scheduled jobs: {}", e);
+                    continue;
+                }
+            };
+
+            for job in due {
+                let app_handle = app_handle.clone();
+                let db = db.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = run_job(&app_handle, &db, job).await {
+                        tracing::debug!("Skipped scheduled job: {}", e);
+                    }
+                });
+            }
+        }
+    });
+}
+
+/// Runs a job now, records the result and emits [`FINISHED_EVENT`].
+///
+/// Fails only if the job is already running; the job's own outcome is
+/// returned inside the `Ok`.
+pub async fn run_job(
+    app_handle: &AppHandle,
+    db: &DatabaseConnection,
+    job: scheduled_job::Model,
+) -> Result<Result<String, String>, String> {
+    if !lock_running().insert(job.id.clone()) {
+        return Err(format!("Job '{}' is already running", job.name));
+    }
+
+    let started_at = chrono::Utc::now().timestamp();
+    let params: serde_json::Value = job
+        .params
+        .as_deref()
+        .and_then(|p| serde_json::from_str(p).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+
+    let result = match job.job_type.as_str() {
+        "workspace_sync" => run_workspace_sync(db, &params).await,
+        "backup" => run_backup(db, &params).await,
+        "update_check" => run_update_check(db).await,
+        "trash_purge" => run_trash_purge(db, &params).await,
+        other => Err(format!("Unknown job type: {}", other)),
+    };
+    lock_running().remove(&job.id);
+
+    match &result {
+        Ok(message) => tracing::info!("Scheduled job '{}' succeeded: {}", job.name, message),
+        Err(error) => tracing::warn!("Scheduled job '{}' failed: {}", job.name, error),
+    }
+
+    if let Err(e) = scheduler_operations::record_job_run(db, &job.id, started_at, &result).await {
+        tracing::warn!("Failed to record scheduled job run: {}", e);
+    }
+
+    let payload = FinishedPayload {
+        job_id: job.id,
+        job_type: job.job_type,
+        success: result.is_ok(),
+        message: match &result {
+            Ok(message) | Err(message) => message.clone(),
+        },
+    };
+    if let Err(e) = app_handle.emit_all(FINISHED_EVENT, payload) {
+        tracing::warn!("Failed to emit scheduled job event: {}", e);
+    }
+
+    Ok(result)
+}
+
+/// Number of jobs currently running
+pub fn running_job_count() -> usize {
+    lock_running().len()
+}
+
+fn lock_running() -> std::sync::MutexGuard<'static, HashSet<String>> {
+    RUNNING_JOBS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Params: `workspaceId` (optional; all workspaces when absent)
+async fn run_workspace_sync(db: &DatabaseConnection, params: &serde_json::Value) -> Result<String, String> {
+    let workspace_ids: Vec<String> = match params.get("workspaceId").and_then(|v| v.as_str()) {
+        Some(id) => vec![id.to_string()],
+        None => library_workspace::Entity::find()
+            .all(db)
+            .await
+            .map_err(|e| format!("Failed to list workspaces: {}", e))?
+            .into_iter()
+            .map(|w| w.id)
+            .collect(),
+    };
+
+    // Background runs are announced by FINISHED_EVENT instead of progress events
+    let progress = crate::core::progress::ProgressReporter::silent("library_sync");
+    let mut failures = Vec::new();
+    let (mut created, mut updated) = (0, 0);
+    for workspace_id in &workspace_ids {
+        match crate::library::sync::sync_workspace_catalog(db, workspace_id, &progress).await {
+            Ok(result) => {
+                created += result.catalogs_created + result.variations_created;
+                updated += result.catalogs_updated + result.variations_updated;
+            }
+            Err(e) => failures.push(format!("{}: {}", workspace_id, e)),
+        }
+    }
+
+    if !failures.is_empty() {
+        return Err(format!("Sync failed for {}", failures.join("; ")));
+    }
+    Ok(format!(
+        "Synced {} workspace(s): {} created, {} updated",
+        workspace_ids.len(),
+        created,
+        updated
+    ))
+}
+
+// Params: `keep` (number of backups to retain)
+async fn run_backup(db: &DatabaseConnection, params: &serde_json::Value) -> Result<String, String> {
+    let keep = params
+        .get("keep")
+        .and_then(|v| v.as_u64())
+        .map(|k| k.max(1) as usize)
+        .unwrap_or(DEFAULT_BACKUPS_KEPT);
+
+    let backups_dir = backups_dir()?;
+    std::fs::create_dir_all(&backups_dir)
+        .map_err(|e| format!("Failed to create backups directory: {}", e))?;
+
+    let backup_path = backups_dir.join(format!(
+        "bluekit-{}.db",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    // VACUUM INTO produces a consistent copy even while the app is writing
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "VACUUM INTO ?",
+        [backup_path.to_string_lossy().to_string().into()],
+    ))
+    .await
+    .map_err(|e| format!("Failed to back up database: {}", e))?;
+
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(&backups_dir)
+        .map_err(|e| format!("Failed to read backups directory: {}", e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            let name = crate::core::paths::file_name_lossy(path);
+            name.starts_with("bluekit-") && name.ends_with(".db")
+        })
+        .collect();
+    // Timestamped names sort chronologically
+    backups.sort();
+    let excess = backups.len().saturating_sub(keep);
+    for old in &backups[..excess] {
+        if let Err(e) = std::fs::remove_file(old) {
+            tracing::warn!("Failed to remove old backup {}: {}", old.display(), e);
+        }
+    }
+
+    Ok(format!("Backed up database to {}", backup_path.display()))
+}
+
+fn backups_dir() -> Result<PathBuf, String> {
+    let db_path = crate::db::get_db_path()?;
+    Ok(db_path
+        .parent()
+        .map(|dir| dir.join("backups"))
+        .unwrap_or_else(|| PathBuf::from("backups")))
+}
+
+async fn run_update_check(db: &DatabaseConnection) -> Result<String, String> {
+    let projects = crate::db::project_operations::get_all_projects(db)
+        .await
+        .map_err(|e| format!("Failed to list projects: {}", e))?;
+
+    let (mut unpublished, mut updates) = (0, 0);
+    for project in &projects {
+        match crate::library::updates::check_project_for_updates(db, &project.id, &project.path).await {
+            Ok(statuses) => {
+                unpublished += statuses.iter().filter(|s| s.has_unpublished_changes).count();
+                updates += statuses
+                    .iter()
+                    .filter(|s| s.subscription.as_ref().is_some_and(|sub| sub.has_updates))
+                    .count();
+            }
+            Err(e) => tracing::warn!("Update check failed for {}: {}", project.path, e),
+        }
+    }
+
+    Ok(format!(
+        "Checked {} project(s): {} update(s) available, {} unpublished change(s)",
+        projects.len(),
+        updates,
+        unpublished
+    ))
+}
+
+// Params: `olderThanDays` (how long deleted resources and trashed
+// artifacts are kept; defaults to the `trash.retentionDays` setting, where
+// 0 keeps them forever)
+async fn run_trash_purge(db: &DatabaseConnection, params: &serde_json::Value) -> Result<String, String> {
+    let days = match params.get("olderThanDays").and_then(|v| v.as_u64()) {
+        Some(days) => days.min(u64::from(u32::MAX)) as u32,
+        None => match crate::core::settings::load_settings().trash.retention_days {
+            0 => return Ok("Trash retention is unlimited; nothing purged".to_string()),
+            days => days,
+        },
+    };
+    let cutoff = chrono::Utc::now().timestamp() - i64::from(days) * 24 * 60 * 60;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let purged_ids: Vec<String> = library_resource::Entity::find()
+        .filter(library_resource::Column::IsDeleted.eq(1))
+        .filter(library_resource::Column::UpdatedAt.lt(cutoff))
+        .all(&txn)
+        .await
+        .map_err(|e| format!("Failed to find deleted resources: {}", e))?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+    if !purged_ids.is_empty() {
+        library_subscription::Entity::delete_many()
+            .filter(library_subscription::Column::ResourceId.is_in(purged_ids.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|e| format!("Failed to delete subscriptions: {}", e))?;
+        library_resource::Entity::delete_many()
+            .filter(library_resource::Column::Id.is_in(purged_ids.clone()))
+            .exec(&txn)
+            .await
+            .map_err(|e| format!("Failed to delete resources: {}", e))?;
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| format!("Failed to commit purge: {}", e))?;
+
+    // Soft-deleted tasks, plans and walkthroughs
+    let cutoff_time = chrono::DateTime::from_timestamp(cutoff, 0).unwrap_or_default();
+    let purged_rows = crate::db::task_operations::purge_deleted_tasks(db, cutoff_time)
+        .await
+        .map_err(|e| format!("Failed to purge deleted tasks: {}", e))?
+        + crate::db::plan_operations::purge_deleted_plans(db, cutoff)
+            .await
+            .map_err(|e| format!("Failed to purge deleted plans: {}", e))?
+        + crate::db::walkthrough_operations::purge_deleted_walkthroughs(db, cutoff)
+            .await
+            .map_err(|e| format!("Failed to purge deleted walkthroughs: {}", e))?;
+
+    // Trashed artifact files in each project's .bluekit/.trash
+    let projects = crate::db::project_operations::get_all_projects(db)
+        .await
+        .map_err(|e| format!("Failed to list projects: {}", e))?;
+    let mut purged_files = 0;
+    for project in &projects {
+        let project_root = crate::core::paths::from_ipc(&project.path);
+        match crate::core::trash::empty_trash(&project_root, Some(days)).await {
+            Ok(count) => purged_files += count,
+            Err(e) => tracing::warn!("Failed to purge trash for {}: {}", project.path, e),
+        }
+    }
+
+    Ok(format!(
+        "Purged {} resource(s), {} task(s)/plan(s)/walkthrough(s) and {} trashed file(s) deleted more than {} day(s) ago",
+        purged_ids.len(),
+        purged_rows,
+        purged_files,
+        days
+    ))
+}
@@ -0,0 +1,497 @@
+//! Full-text search index for `.bluekit` artifacts.
+//!
+//! Each searched project gets an in-memory index of its Markdown and mermaid
+//! files, built on the first search. Directory watchers report changed paths
+//! through [`SearchIndex::mark_changed`], so while a project's watcher is
+//! running a search only re-reads those files. Without a watcher the index
+//! falls back to comparing modification times, which still skips unchanged
+//! files. Each file's front matter fields are kept too, for searches that
+//! match on those rather than the content.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+/// Extensions of indexed files
+const INDEXED_EXTENSIONS: [&str; 3] = ["md", "mmd", "mermaid"];
+
+/// Matching lines returned per file
+const MAX_LINES_PER_FILE: usize = 5;
+
+/// Snippets longer than this are cut down around the first match
+const MAX_SNIPPET_CHARS: usize = 160;
+
+/// Default number of files returned by a search
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+struct IndexedFile {
+    modified: SystemTime,
+    name: String,
+    metadata: crate::commands::ArtifactMetadata,
+    lines: Vec<String>,
+    /// Lowercased `lines`, for case-insensitive matching
+    lower_lines: Vec<String>,
+}
+
+#[derive(Default)]
+struct ProjectIndex {
+    files: HashMap<PathBuf, IndexedFile>,
+    /// Paths reported by the watcher since the last search
+    changed: HashSet<PathBuf>,
+    built: bool,
+}
+
+/// One matching line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchLineMatch {
+    /// 1-based line number
+    pub line: usize,
+    pub snippet: String,
+}
+
+/// A file matching a search, with its best lines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub path: String,
+    pub name: String,
+    pub score: f64,
+    #[serde(rename = "matchCount")]
+    pub match_count: usize,
+    pub matches: Vec<SearchLineMatch>,
+}
+
+/// An indexed file with its front matter fields
+#[derive(Debug, Clone)]
+pub struct IndexedArtifact {
+    pub path: PathBuf,
+    /// File name without the extension
+    pub name: String,
+    pub metadata: crate::commands::ArtifactMetadata,
+}
+
+/// Thread-safe search index shared by all projects.
+///
+/// Registered as Tauri state next to the artifact cache. Each project's
+/// index has its own lock, so searches of different projects don't wait on
+/// each other.
+pub struct SearchIndex {
+    projects: Arc<RwLock<HashMap<PathBuf, Arc<Mutex<ProjectIndex>>>>>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self {
+            projects: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Records paths reported by a directory watcher. Paths outside any
+    /// indexed project are ignored.
+    pub async fn mark_changed(&self, paths: &[PathBuf]) {
+        let projects = self.projects.read().await;
+        for (root, index) in projects.iter() {
+            let bluekit_dir = root.join(".bluekit");
+            lock_index(index)
+                .changed
+                .extend(paths.iter().filter(|p| p.starts_with(&bluekit_dir)).cloned());
+        }
+    }
+
+    /// Searches a project's artifacts for `query`, best matches first.
+    ///
+    /// All query terms must appear in a file for it to match. Files are
+    /// ranked by term frequency, with extra weight for matches in the file
+    /// name and for the whole query appearing as a phrase. `watched` says
+    /// whether a watcher is reporting changes for the project; if not, the
+    /// index is checked against the filesystem first.
+    pub async fn search(
+        &self,
+        project_root: &Path,
+        query: &str,
+        limit: usize,
+        watched: bool,
+    ) -> Result<Vec<SearchResult>, String> {
+        let Some(query) = Query::parse(query) else {
+            return Ok(Vec::new());
+        };
+        let index = self.project_index(project_root).await;
+        search_project(project_root, &index, &query, limit, watched)
+    }
+
+    /// Every indexed file of a project with its front matter, refreshed
+    /// the same way as for [`SearchIndex::search`].
+    pub async fn artifacts(&self, project_root: &Path, watched: bool) -> Result<Vec<IndexedArtifact>, String> {
+        let index = self.project_index(project_root).await;
+        let mut index = lock_index(&index);
+        refresh(project_root, &mut index, watched)?;
+        Ok(index
+            .files
+            .iter()
+            .map(|(path, file)| IndexedArtifact {
+                path: path.clone(),
+                name: file.name.clone(),
+                metadata: file.metadata.clone(),
+            })
+            .collect())
+    }
+
+    /// Searches several projects at once, each on a blocking thread.
+    ///
+    /// `projects` pairs each project root with whether it is watched (see
+    /// [`SearchIndex::search`]). Results come back in the same order, with
+    /// at most `limit` files per project.
+    pub async fn search_many(
+        &self,
+        projects: Vec<(PathBuf, bool)>,
+        query: &str,
+        limit: usize,
+    ) -> Vec<Result<Vec<SearchResult>, String>> {
+        let Some(query) = Query::parse(query) else {
+            return projects.iter().map(|_| Ok(Vec::new())).collect();
+        };
+        let query = Arc::new(query);
+
+        let mut tasks = Vec::with_capacity(projects.len());
+        for (project_root, watched) in projects {
+            let index = self.project_index(&project_root).await;
+            let query = Arc::clone(&query);
+            tasks.push(tokio::task::spawn_blocking(move || {
+                search_project(&project_root, &index, &query, limit, watched)
+            }));
+        }
+
+        futures::future::join_all(tasks)
+            .await
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|e| Err(format!("Search task failed: {}", e))))
+            .collect()
+    }
+
+    // The project's index, created empty on first use
+    async fn project_index(&self, project_root: &Path) -> Arc<Mutex<ProjectIndex>> {
+        let mut projects = self.projects.write().await;
+        Arc::clone(projects.entry(project_root.to_path_buf()).or_default())
+    }
+}
+
+// Lowercased query terms, plus the whole query for phrase matches
+struct Query {
+    terms: Vec<String>,
+    phrase: String,
+}
+
+impl Query {
+    fn parse(query: &str) -> Option<Self> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| term.to_lowercase())
+            .collect();
+        if terms.is_empty() {
+            return None;
+        }
+        let phrase = terms.join(" ");
+        Some(Self { terms, phrase })
+    }
+}
+
+fn search_project(
+    project_root: &Path,
+    index: &Mutex<ProjectIndex>,
+    query: &Query,
+    limit: usize,
+    watched: bool,
+) -> Result<Vec<SearchResult>, String> {
+    let mut index = lock_index(index);
+    refresh(project_root, &mut index, watched)?;
+
+    let mut results: Vec<SearchResult> = index
+        .files
+        .iter()
+        .filter_map(|(path, file)| score_file(path, file, &query.terms, &query.phrase))
+        .collect();
+    drop(index);
+
+    results.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    results.truncate(limit);
+    Ok(results)
+}
+
+fn lock_index(index: &Mutex<ProjectIndex>) -> MutexGuard<'_, ProjectIndex> {
+    index.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Brings the index up to date: a full scan the first time (or without a
+// watcher), otherwise only the paths the watcher reported
+fn refresh(project_root: &Path, index: &mut ProjectIndex, watched: bool) -> Result<(), String> {
+    let bluekit_dir = project_root.join(".bluekit");
+    if !bluekit_dir.is_dir() {
+        index.files.clear();
+        return Ok(());
+    }
+    // Huge files are indexed by name only
+    let limit = crate::core::settings::load_settings()
+        .file_size_limits
+        .list_content_max_bytes;
+
+    if index.built && watched {
+        let changed: Vec<PathBuf> = index.changed.drain().collect();
+        debug!("Updating search index for {} changed path(s)", changed.len());
+        for path in changed {
+            if path.is_dir() {
+                // A folder was added or moved in; index what's inside it
+                for file in crate::core::paths::files_under(&path) {
+                    update_file(index, &file, limit);
+                }
+            } else {
+                update_file(index, &path, limit);
+            }
+        }
+        // A removed folder only reports its own path
+        index.files.retain(|path, _| path.exists());
+        return Ok(());
+    }
+
+    let files: Vec<PathBuf> = crate::core::paths::files_under(&bluekit_dir)
+        .into_iter()
+        .filter(|path| is_indexed(path))
+        .collect();
+    let present: HashSet<&PathBuf> = files.iter().collect();
+    index.files.retain(|path, _| present.contains(path));
+    for file in &files {
+        update_file(index, file, limit);
+    }
+    index.changed.clear();
+    index.built = true;
+    Ok(())
+}
+
+// (Re)indexes one file if it changed, or drops it if it's gone
+fn update_file(index: &mut ProjectIndex, path: &Path, limit: u64) {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() && is_indexed(path) => metadata,
+        _ => {
+            index.files.remove(path);
+            return;
+        }
+    };
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    if index.files.get(path).is_some_and(|file| file.modified == modified) {
+        return;
+    }
+
+    let content = if metadata.len() > limit {
+        String::new()
+    } else {
+        match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                debug!("Not indexing {}: {}", path.display(), e);
+                index.files.remove(path);
+                return;
+            }
+        }
+    };
+
+    // Huge files are skipped above, but their front matter is still read
+    let front_matter = if content.is_empty() {
+        crate::commands::read_front_matter_only(path)
+    } else {
+        crate::core::validation::parse_front_matter_strict(&content).ok().flatten()
+    };
+    let metadata = front_matter
+        .as_ref()
+        .map(crate::commands::artifact_metadata)
+        .unwrap_or_default();
+
+    let lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let lower_lines = lines.iter().map(|line| line.to_lowercase()).collect();
+    index.files.insert(
+        path.to_path_buf(),
+        IndexedFile {
+            modified,
+            name: crate::core::paths::file_stem_lossy(path),
+            metadata,
+            lines,
+            lower_lines,
+        },
+    );
+}
+
+fn score_file(path: &Path, file: &IndexedFile, terms: &[String], phrase: &str) -> Option<SearchResult> {
+    let lower_name = file.name.to_lowercase();
+    let mut score = 0.0;
+
+    for term in terms {
+        let body_hits: usize = file.lower_lines.iter().map(|line| line.matches(term.as_str()).count()).sum();
+        let name_hit = lower_name.contains(term.as_str());
+        if body_hits == 0 && !name_hit {
+            return None;
+        }
+        // Diminishing returns so one long file doesn't drown out the rest
+        score += (1.0 + body_hits as f64).ln();
+        if name_hit {
+            score += 5.0;
+        }
+    }
+
+    let mut matches = Vec::new();
+    let mut match_count = 0;
+    for (index, lower_line) in file.lower_lines.iter().enumerate() {
+        let Some(first) = terms.iter().filter_map(|term| lower_line.find(term.as_str())).min() else {
+            continue;
+        };
+        match_count += 1;
+        if terms.len() > 1 && lower_line.contains(phrase) {
+            score += 3.0;
+        }
+        if matches.len() < MAX_LINES_PER_FILE {
+            matches.push(SearchLineMatch {
+                line: index + 1,
+                // Lowercasing rarely changes character counts, so this
+                // position is close enough for the original line
+                snippet: snippet(&file.lines[index], lower_line[..first].chars().count()),
+            });
+        }
+    }
+
+    Some(SearchResult {
+        path: crate::core::paths::to_ipc(path),
+        name: file.name.clone(),
+        score,
+        match_count,
+        matches,
+    })
+}
+
+// Trimmed line, cut to a window around the match starting at character `at`
+fn snippet(line: &str, at: usize) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    if line.trim().chars().count() <= MAX_SNIPPET_CHARS {
+        return line.trim().to_string();
+    }
+
+    let start = at.saturating_sub(MAX_SNIPPET_CHARS / 3);
+    let end = (start + MAX_SNIPPET_CHARS).min(chars.len());
+    let start = end.saturating_sub(MAX_SNIPPET_CHARS);
+
+    let mut snippet = chars[start..end].iter().collect::<String>().trim().to_string();
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+fn is_indexed(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| INDEXED_EXTENSIONS.contains(&ext))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indexed(name: &str, content: &str) -> IndexedFile {
+        let lines: Vec<String> = content.lines().map(str::to_string).collect();
+        IndexedFile {
+            modified: SystemTime::UNIX_EPOCH,
+            name: name.to_string(),
+            metadata: Default::default(),
+            lower_lines: lines.iter().map(|line| line.to_lowercase()).collect(),
+            lines,
+        }
+    }
+
+    fn score(name: &str, content: &str, query: &str) -> Option<SearchResult> {
+        let query = Query::parse(query)?;
+        score_file(Path::new("/p/.bluekit/kits/a.md"), &indexed(name, content), &query.terms, &query.phrase)
+    }
+
+    fn matched_lines(result: &SearchResult) -> Vec<usize> {
+        result.matches.iter().map(|m| m.line).collect()
+    }
+
+    #[test]
+    fn test_score_file_ignores_case() {
+        let result = score("notes", "Alpha beta\ngamma\nALPHA", "alpha").unwrap();
+        assert_eq!(result.match_count, 2);
+        assert_eq!(matched_lines(&result), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_score_file_terms_may_be_on_different_lines() {
+        let result = score("notes", "alpha\nbeta", "alpha beta").unwrap();
+        assert_eq!(result.match_count, 2);
+        assert_eq!(matched_lines(&result), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_score_file_requires_every_term() {
+        assert!(score("notes", "alpha", "alpha beta").is_none());
+    }
+
+    #[test]
+    fn test_score_file_matches_the_name() {
+        let result = score("beta-guide", "alpha", "alpha beta").unwrap();
+        assert_eq!(matched_lines(&result), vec![1]);
+
+        let result = score("notes", "", "NOTES").unwrap();
+        assert_eq!(result.match_count, 0);
+        assert!(result.matches.is_empty());
+    }
+
+    #[test]
+    fn test_score_file_empty_query() {
+        assert!(score("notes", "alpha", "").is_none());
+        assert!(score("notes", "alpha", "   ").is_none());
+    }
+
+    #[test]
+    fn test_score_file_returns_a_few_lines() {
+        // Every matching line is counted, but only a few are returned
+        let result = score("notes", &"alpha\n".repeat(7), "alpha").unwrap();
+        assert_eq!(result.match_count, 7);
+        assert_eq!(matched_lines(&result), (1..=MAX_LINES_PER_FILE).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_score_file_ranking() {
+        let score_of = |name: &str, content: &str| {
+            score(name, content, "alpha beta").map(|result| result.score).unwrap_or(0.0)
+        };
+        // A name match outranks a match in the body
+        assert!(score_of("alpha-guide", "beta") > score_of("notes", "alpha\nbeta"));
+        // The whole query as a phrase outranks scattered terms
+        assert!(score_of("notes", "alpha beta") > score_of("notes", "alpha\nbeta"));
+        // More hits rank higher, with diminishing returns
+        assert!(score_of("notes", "alpha beta alpha") > score_of("notes", "alpha beta"));
+    }
+
+    #[test]
+    fn test_snippet() {
+        assert_eq!(snippet("   short line  ", 3), "short line");
+
+        let long = format!("{}needle{}", "x".repeat(300), "y".repeat(300));
+        let cut = snippet(&long, 300);
+        assert!(cut.contains("needle"));
+        assert!(cut.starts_with('…') && cut.ends_with('…'));
+        assert_eq!(cut.chars().count(), MAX_SNIPPET_CHARS + 2);
+
+        // A match near the start keeps the start of the line
+        let cut = snippet(&long, 0);
+        assert!(cut.starts_with('x') && cut.ends_with('…'));
+    }
+}
@@ -0,0 +1,241 @@
+//! Application settings stored in `~/.bluekit/settings.json`.
+//!
+//! Every field has a default, so a missing or partial settings file is
+//! fine; unknown keys written by newer versions are ignored.
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const MIB: u64 = 1024 * 1024;
+
+/// Size thresholds that keep huge files from being pushed over IPC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSizeLimits {
+    /// Artifacts larger than this are listed without their content
+    #[serde(rename = "listContentMaxBytes", default = "default_list_content_max_bytes")]
+    pub list_content_max_bytes: u64,
+    /// `read_file` refuses files larger than this
+    #[serde(rename = "readMaxBytes", default = "default_read_max_bytes")]
+    pub read_max_bytes: u64,
+}
+
+fn default_list_content_max_bytes() -> u64 {
+    2 * MIB
+}
+
+fn default_read_max_bytes() -> u64 {
+    25 * MIB
+}
+
+impl Default for FileSizeLimits {
+    fn default() -> Self {
+        Self {
+            list_content_max_bytes: default_list_content_max_bytes(),
+            read_max_bytes: default_read_max_bytes(),
+        }
+    }
+}
+
+/// External editor used by `open_in_editor`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorSettings {
+    /// "vscode", "cursor", "vim", "env" (the `$VISUAL` or `$EDITOR`
+    /// command) or "custom"
+    #[serde(default = "default_editor")]
+    pub editor: String,
+    /// Command for the "custom" editor, split on whitespace, with `{path}`
+    /// and `{line}` placeholders (e.g. `subl {path}:{line}`)
+    #[serde(rename = "customCommand", default)]
+    pub custom_command: Option<String>,
+}
+
+fn default_editor() -> String {
+    "vscode".to_string()
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            editor: default_editor(),
+            custom_command: None,
+        }
+    }
+}
+
+/// Trash for deleted artifacts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashSettings {
+    /// Trashed files older than this are purged; 0 keeps them forever
+    #[serde(rename = "retentionDays", default = "default_trash_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        Self {
+            retention_days: default_trash_retention_days(),
+        }
+    }
+}
+
+/// How artifact scans treat symbolic links
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSettings {
+    /// Follow symlinked files and folders inside `.bluekit` (cycles are
+    /// detected and skipped); when false, symlinks are ignored
+    #[serde(rename = "followSymlinks", default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+}
+
+fn default_follow_symlinks() -> bool {
+    true
+}
+
+impl Default for ScanSettings {
+    fn default() -> Self {
+        Self {
+            follow_symlinks: default_follow_symlinks(),
+        }
+    }
+}
+
+/// Task reminders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReminderSettings {
+    /// Also show a system notification when a reminder fires (the
+    /// `task-reminder` event is always sent)
+    #[serde(rename = "nativeNotifications", default = "default_native_notifications")]
+    pub native_notifications: bool,
+}
+
+fn default_native_notifications() -> bool {
+    true
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        Self {
+            native_notifications: default_native_notifications(),
+        }
+    }
+}
+
+/// Walkthrough files
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalkthroughSettings {
+    /// Mirror each walkthrough's status and takeaway progress into its
+    /// file's front matter whenever they change, so the file carries them
+    /// when shared
+    #[serde(rename = "syncProgressToFrontMatter", default)]
+    pub sync_progress_to_front_matter: bool,
+}
+
+/// SQLite connection tuning, applied when the database is opened at startup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseSettings {
+    /// Use write-ahead logging, so readers don't block the writer
+    #[serde(rename = "walMode", default = "default_wal_mode")]
+    pub wal_mode: bool,
+    /// How long a connection waits on a locked database before failing
+    #[serde(rename = "busyTimeoutMs", default = "default_busy_timeout_ms")]
+    pub busy_timeout_ms: u64,
+    /// Connection pool size
+    #[serde(rename = "maxConnections", default = "default_max_connections")]
+    pub max_connections: u32,
+}
+
+fn default_wal_mode() -> bool {
+    true
+}
+
+fn default_busy_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_max_connections() -> u32 {
+    8
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            wal_mode: default_wal_mode(),
+            busy_timeout_ms: default_busy_timeout_ms(),
+            max_connections: default_max_connections(),
+        }
+    }
+}
+
+/// Where data is kept; see `core::data_dir`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageSettings {
+    /// Directory for the database, project registry and shared pool, used
+    /// from the next launch; none for `~/.bluekit`. Change it with
+    /// `set_data_directory` so the data can be moved along.
+    #[serde(rename = "dataDirectory", default)]
+    pub data_directory: Option<String>,
+}
+
+/// User-configurable application settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppSettings {
+    #[serde(rename = "fileSizeLimits", default)]
+    pub file_size_limits: FileSizeLimits,
+    #[serde(default)]
+    pub editor: EditorSettings,
+    #[serde(default)]
+    pub trash: TrashSettings,
+    #[serde(default)]
+    pub scan: ScanSettings,
+    #[serde(default)]
+    pub reminders: ReminderSettings,
+    #[serde(default)]
+    pub database: DatabaseSettings,
+    #[serde(default)]
+    pub walkthroughs: WalkthroughSettings,
+    #[serde(default)]
+    pub storage: StorageSettings,
+}
+
+/// Returns the settings file path (`~/.bluekit/settings.json`).
+pub fn settings_path() -> Result<PathBuf, String> {
+    let home_dir = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .map_err(|_| "Could not determine home directory".to_string())?;
+
+    Ok(PathBuf::from(home_dir).join(".bluekit").join("settings.json"))
+}
+
+/// Loads settings, falling back to defaults if the file is missing or invalid.
+pub fn load_settings() -> AppSettings {
+    let Ok(path) = settings_path() else {
+        return AppSettings::default();
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring invalid settings file {}: {}", path.display(), e);
+            AppSettings::default()
+        }),
+        Err(_) => AppSettings::default(),
+    }
+}
+
+/// Writes settings to disk, creating `~/.bluekit` if needed.
+pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+    }
+
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write settings: {}", e))
+}
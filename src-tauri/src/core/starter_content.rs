@@ -0,0 +1,333 @@
+//! First-run starter content.
+//!
+//! On a fresh install (no legacy project registry and no projects in the
+//! database) `bootstrap_starter_content` creates an example project under
+//! `~/.bluekit/starter-project` with sample kits, a blueprint, a walkthrough
+//! with takeaways and a demo plan, so new users can see how the pieces fit
+//! together before creating their own.
+
+use crate::core::paths;
+use crate::db::entities::project;
+use crate::db::{plan_operations, project_operations, walkthrough_operations};
+use sea_orm::{DatabaseConnection, EntityTrait, PaginatorTrait};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory name of the starter project inside `~/.bluekit`
+pub const STARTER_DIR: &str = "starter-project";
+
+const STARTER_PROJECT_NAME: &str = "BlueKit Starter";
+
+/// Marker left in `~/.bluekit` once seeded, so deleting the starter project
+/// doesn't bring it back
+const SEEDED_MARKER: &str = ".starter-content-seeded";
+
+/// Sample files written relative to the project root
+const STARTER_FILES: [(&str, &str); 5] = [
+    (".bluekit/kits/getting-started.md", GETTING_STARTED_KIT),
+    (".bluekit/kits/error-handling.md", ERROR_HANDLING_KIT),
+    (".bluekit/blueprints/todo-app/blueprint.json", BLUEPRINT_JSON),
+    (".bluekit/blueprints/todo-app/setup-project.md", BLUEPRINT_SETUP_TASK),
+    (".bluekit/blueprints/todo-app/add-persistence.md", BLUEPRINT_PERSISTENCE_TASK),
+];
+
+/// Outcome of a bootstrap request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapResult {
+    /// False if this isn't a first run and nothing was created
+    pub seeded: bool,
+    #[serde(rename = "projectId")]
+    pub project_id: Option<String>,
+    #[serde(rename = "projectPath")]
+    pub project_path: Option<String>,
+}
+
+/// True if starter content was never seeded and there is neither a legacy
+/// project registry nor a project in the DB.
+pub async fn is_first_run(db: &DatabaseConnection) -> Result<bool, String> {
+    let bluekit_dir = bluekit_dir()?;
+    if bluekit_dir.join(SEEDED_MARKER).exists() || bluekit_dir.join("projectRegistry.json").exists() {
+        return Ok(false);
+    }
+
+    // Vaults count too: a user with only a library isn't new
+    let projects = project::Entity::find()
+        .count(db)
+        .await
+        .map_err(|e| format!("Failed to count projects: {}", e))?;
+    Ok(projects == 0)
+}
+
+/// Seeds the starter project if this is a first run.
+///
+/// Sample files that already exist in the starter directory are left
+/// untouched.
+pub async fn bootstrap_starter_content(db: &DatabaseConnection) -> Result<BootstrapResult, String> {
+    if !is_first_run(db).await? {
+        return Ok(BootstrapResult {
+            seeded: false,
+            project_id: None,
+            project_path: None,
+        });
+    }
+
+    let project_dir = bluekit_dir()?.join(STARTER_DIR);
+    for (relative, content) in STARTER_FILES {
+        write_if_missing(&project_dir.join(relative), content)?;
+    }
+
+    let project_path = paths::to_ipc(&project_dir);
+    let project = project_operations::create_project(
+        db,
+        STARTER_PROJECT_NAME,
+        &project_path,
+        Some("Example project showing kits, blueprints, walkthroughs and plans".to_string()),
+        Some(vec!["starter".to_string()]),
+        None,
+    )
+    .await
+    .map_err(|e| format!("Failed to register starter project: {}", e))?;
+
+    seed_walkthrough(db, &project.id, &project_path).await?;
+    seed_plan(db, &project.id, &project_path).await?;
+    write_if_missing(&bluekit_dir()?.join(SEEDED_MARKER), "")?;
+
+    Ok(BootstrapResult {
+        seeded: true,
+        project_id: Some(project.id),
+        project_path: Some(project_path),
+    })
+}
+
+async fn seed_walkthrough(db: &DatabaseConnection, project_id: &str, project_path: &str) -> Result<(), String> {
+    let takeaways = [
+        ("Kits are reusable instructions", "Open the kits in .bluekit/kits and read their front matter."),
+        ("Blueprints chain tasks into layers", "Each layer in blueprint.json points at a task file."),
+        ("Plans track work in phases", "Tick off a milestone in the demo plan to see progress update."),
+    ];
+
+    let walkthrough = walkthrough_operations::create_walkthrough(
+        db,
+        project_id.to_string(),
+        project_path.to_string(),
+        "Tour of BlueKit".to_string(),
+        Some("A short guided tour of the starter project".to_string()),
+        takeaways
+            .iter()
+            .map(|(title, description)| (title.to_string(), Some(description.to_string())))
+            .collect(),
+    )
+    .await
+    .map_err(|e| format!("Failed to create starter walkthrough: {}", e))?;
+
+    // Replace the placeholder body with the tour
+    fs::write(&walkthrough.file_path, TOUR_WALKTHROUGH)
+        .map_err(|e| format!("Failed to write starter walkthrough: {}", e))
+}
+
+async fn seed_plan(db: &DatabaseConnection, project_id: &str, project_path: &str) -> Result<(), String> {
+    let plan = plan_operations::create_plan(
+        db,
+        project_id.to_string(),
+        project_path.to_string(),
+        "Demo Plan".to_string(),
+        Some("An example plan with phases, milestones and a document".to_string()),
+    )
+    .await
+    .map_err(|e| format!("Failed to create demo plan: {}", e))?;
+
+    let phases = [
+        ("Explore", vec!["Read the getting started kit", "Finish the tour walkthrough"]),
+        ("Build", vec!["Run the todo-app blueprint", "Publish a kit to a library"]),
+    ];
+    for (phase_index, (phase_name, milestones)) in phases.iter().enumerate() {
+        let phase = plan_operations::create_plan_phase(
+            db,
+            plan.id.clone(),
+            phase_name.to_string(),
+            None,
+            phase_index as i32,
+            None,
+        )
+        .await
+        .map_err(|e| format!("Failed to create demo plan phase: {}", e))?;
+
+        for (milestone_index, milestone) in milestones.iter().enumerate() {
+            plan_operations::create_plan_milestone(
+                db,
+                phase.id.clone(),
+                milestone.to_string(),
+                None,
+                milestone_index as i32,
+                None,
+            )
+            .await
+            .map_err(|e| format!("Failed to create demo plan milestone: {}", e))?;
+        }
+    }
+
+    write_if_missing(&Path::new(&plan.folder_path).join("overview.md"), PLAN_OVERVIEW)?;
+    // Registers the document written above
+    plan_operations::get_plan_documents(db, plan.id)
+        .await
+        .map_err(|e| format!("Failed to register demo plan document: {}", e))?;
+
+    Ok(())
+}
+
+fn bluekit_dir() -> Result<PathBuf, String> {
+    let db_path = crate::db::get_db_path()?;
+    db_path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "Could not determine the BlueKit directory".to_string())
+}
+
+fn write_if_missing(path: &Path, content: &str) -> Result<(), String> {
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+const GETTING_STARTED_KIT: &str = r#"---
+id: getting-started
+alias: Getting Started with BlueKit
+type: kit
+is_base: false
+version: 1
+tags:
+  - starter
+description: What kits are and how to write your own
+---
+# Getting Started with BlueKit
+
+A kit is a reusable set of instructions you can hand to an AI assistant or a
+teammate. Kits live in `.bluekit/kits` as Markdown with YAML front matter.
+
+## Writing a kit
+
+1. Create a `.md` file in `.bluekit/kits`.
+2. Add front matter with `type: kit`, an `alias` and a `description`.
+3. Describe the goal, the steps and how to verify the result.
+
+Publish a kit to a library to share it across projects.
+"#;
+
+const ERROR_HANDLING_KIT: &str = r#"---
+id: error-handling
+alias: Consistent Error Handling
+type: kit
+is_base: false
+version: 1
+tags:
+  - starter
+  - errors
+description: Example kit describing an error-handling convention
+---
+# Consistent Error Handling
+
+Use this kit when adding a new backend command.
+
+- Return `Result<T, String>` from commands.
+- Add context to every error: `format!("Failed to read file: {}", e)`.
+- Log unexpected failures once, where they are handled.
+"#;
+
+const BLUEPRINT_JSON: &str = r#"{
+  "id": "todo-app",
+  "name": "Todo App",
+  "version": 1,
+  "description": "A small blueprint that builds a todo app in two layers",
+  "createdAt": "2025-01-01T00:00:00Z",
+  "layers": [
+    {
+      "id": "layer-1",
+      "order": 1,
+      "name": "Foundation",
+      "tasks": [
+        {
+          "id": "setup-project",
+          "taskFile": "setup-project.md",
+          "description": "Scaffold the project"
+        }
+      ]
+    },
+    {
+      "id": "layer-2",
+      "order": 2,
+      "name": "Features",
+      "tasks": [
+        {
+          "id": "add-persistence",
+          "taskFile": "add-persistence.md",
+          "description": "Store todos between sessions"
+        }
+      ]
+    }
+  ]
+}
+"#;
+
+const BLUEPRINT_SETUP_TASK: &str = r#"---
+id: setup-project
+alias: Set Up the Project
+type: task
+description: Scaffold the todo app
+---
+# Set Up the Project
+
+Create a new app with a single page listing todos and a form to add one.
+"#;
+
+const BLUEPRINT_PERSISTENCE_TASK: &str = r#"---
+id: add-persistence
+alias: Add Persistence
+type: task
+description: Store todos between sessions
+---
+# Add Persistence
+
+Save todos to local storage whenever they change and load them on start.
+"#;
+
+const TOUR_WALKTHROUGH: &str = r#"---
+type: walkthrough
+alias: Tour of BlueKit
+description: A short guided tour of the starter project
+---
+
+# Tour of BlueKit
+
+This project was created on first launch to show how BlueKit organizes work.
+
+## Kits
+
+`.bluekit/kits` holds reusable instructions. Open *Getting Started with
+BlueKit* to see the front matter every kit carries.
+
+## Blueprints
+
+`.bluekit/blueprints/todo-app` chains task files into ordered layers.
+
+## Plans
+
+*Demo Plan* splits work into phases and milestones. Completing milestones
+updates the plan's progress.
+
+Mark the takeaways on the right as you go. Delete this project whenever you
+like; it won't be recreated.
+"#;
+
+const PLAN_OVERVIEW: &str = r#"# Demo Plan
+
+Plans group documents like this one with phases and milestones.
+
+- **Explore**: get to know kits and walkthroughs.
+- **Build**: run a blueprint and publish something of your own.
+"#;
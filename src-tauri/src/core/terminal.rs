@@ -0,0 +1,250 @@
+//! PTY-backed terminal sessions.
+//!
+//! Each `run_command` call starts a shell command in a pseudo-terminal inside
+//! the project directory. Output is streamed to the frontend as
+//! `terminal-output` events and completion as a `terminal-exit` event, both
+//! tagged with the session id. Because the command runs in a real terminal,
+//! stdout and stderr arrive interleaved exactly as a user would see them.
+
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtySize};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+/// Event carrying a chunk of terminal output
+pub const OUTPUT_EVENT: &str = "terminal-output";
+
+/// Event sent once when a session's process exits
+pub const EXIT_EVENT: &str = "terminal-exit";
+
+/// Summary of a running session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalSessionInfo {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    pub command: String,
+    #[serde(rename = "startedAt")]
+    pub started_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OutputPayload<'a> {
+    #[serde(rename = "sessionId")]
+    session_id: &'a str,
+    data: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ExitPayload<'a> {
+    #[serde(rename = "sessionId")]
+    session_id: &'a str,
+    #[serde(rename = "exitCode")]
+    exit_code: Option<u32>,
+    success: bool,
+    killed: bool,
+}
+
+struct TerminalSession {
+    info: TerminalSessionInfo,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    writer: Box<dyn Write + Send>,
+    killed: bool,
+    // Keeps the PTY open for as long as the session runs
+    _master: Box<dyn MasterPty + Send>,
+}
+
+/// Registry of running terminal sessions (managed as Tauri state)
+#[derive(Clone, Default)]
+pub struct TerminalSessions {
+    sessions: Arc<Mutex<HashMap<String, TerminalSession>>>,
+}
+
+impl TerminalSessions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts `command` through the platform shell in `project_path`.
+    ///
+    /// Returns the session id used in output/exit events.
+    pub fn run(
+        &self,
+        app_handle: AppHandle,
+        project_path: &Path,
+        command: &str,
+    ) -> Result<String, String> {
+        if !project_path.is_dir() {
+            return Err(format!("Project directory does not exist: {}", project_path.display()));
+        }
+
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows: 24,
+                cols: 120,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| format!("Failed to open terminal: {}", e))?;
+
+        let mut builder = shell_command(command);
+        builder.cwd(project_path);
+
+        let mut child = pair
+            .slave
+            .spawn_command(builder)
+            .map_err(|e| format!("Failed to start command: {}", e))?;
+        // The child holds its own handle; ours would keep the PTY from closing
+        drop(pair.slave);
+
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| format!("Failed to read terminal output: {}", e))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| format!("Failed to open terminal input: {}", e))?;
+
+        let session_id = Uuid::new_v4().to_string();
+        let info = TerminalSessionInfo {
+            session_id: session_id.clone(),
+            project_path: project_path.to_string_lossy().to_string(),
+            command: command.to_string(),
+            started_at: chrono::Utc::now().timestamp(),
+        };
+
+        self.lock().insert(
+            session_id.clone(),
+            TerminalSession {
+                info,
+                killer: child.clone_killer(),
+                writer,
+                killed: false,
+                _master: pair.master,
+            },
+        );
+
+        // Stream output until the PTY closes
+        let output_handle = app_handle.clone();
+        let output_session_id = session_id.clone();
+        let output_thread = std::thread::spawn(move || {
+            let mut buffer = [0u8; 4096];
+            loop {
+                match reader.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => {
+                        let payload = OutputPayload {
+                            session_id: &output_session_id,
+                            data: String::from_utf8_lossy(&buffer[..read]).into_owned(),
+                        };
+                        if let Err(e) = output_handle.emit_all(OUTPUT_EVENT, payload) {
+                            tracing::warn!("Failed to emit terminal output: {}", e);
+                        }
+                    }
+                }
+            }
+        });
+
+        // Wait for the process, then report the exit once output is flushed
+        let sessions = self.clone();
+        let exit_session_id = session_id.clone();
+        std::thread::spawn(move || {
+            let status = child.wait();
+
+            // Dropping the session closes the PTY, which ends the output thread
+            let killed = sessions
+                .lock()
+                .remove(&exit_session_id)
+                .map(|s| s.killed)
+                .unwrap_or(false);
+            let _ = output_thread.join();
+
+            let payload = match &status {
+                Ok(status) => ExitPayload {
+                    session_id: &exit_session_id,
+                    exit_code: Some(status.exit_code()),
+                    success: status.success(),
+                    killed,
+                },
+                Err(e) => {
+                    tracing::warn!("Failed to wait for terminal command: {}", e);
+                    ExitPayload {
+                        session_id: &exit_session_id,
+                        exit_code: None,
+                        success: false,
+                        killed,
+                    }
+                }
+            };
+            if let Err(e) = app_handle.emit_all(EXIT_EVENT, payload) {
+                tracing::warn!("Failed to emit terminal exit: {}", e);
+            }
+        });
+
+        Ok(session_id)
+    }
+
+    /// Kills a running session's process.
+    pub fn kill(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.lock();
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("No running terminal session: {}", session_id))?;
+
+        session.killed = true;
+        session
+            .killer
+            .kill()
+            .map_err(|e| format!("Failed to kill command: {}", e))
+    }
+
+    /// Sends input (e.g. an answer to a prompt) to a running session.
+    pub fn write_input(&self, session_id: &str, data: &str) -> Result<(), String> {
+        let mut sessions = self.lock();
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("No running terminal session: {}", session_id))?;
+
+        session
+            .writer
+            .write_all(data.as_bytes())
+            .and_then(|_| session.writer.flush())
+            .map_err(|e| format!("Failed to write to terminal: {}", e))
+    }
+
+    /// Lists running sessions, oldest first.
+    pub fn list(&self) -> Vec<TerminalSessionInfo> {
+        let mut sessions: Vec<TerminalSessionInfo> =
+            self.lock().values().map(|s| s.info.clone()).collect();
+        sessions.sort_by_key(|s| s.started_at);
+        sessions
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, TerminalSession>> {
+        // A panic while holding the lock can't leave the map inconsistent
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// Runs `command` through the user's shell so pipes, globs, etc. work
+fn shell_command(command: &str) -> CommandBuilder {
+    #[cfg(windows)]
+    {
+        let mut builder = CommandBuilder::new("cmd");
+        builder.args(["/C", command]);
+        builder
+    }
+    #[cfg(not(windows))]
+    {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+        let mut builder = CommandBuilder::new(shell);
+        builder.args(["-c", command]);
+        builder
+    }
+}
@@ -0,0 +1,260 @@
+//! Per-project trash for deleted artifacts.
+//!
+//! `delete_resources` moves files (and `delete_scrapbook_folder` whole
+//! folders) into `.bluekit/.trash` instead of removing them. Each trashed
+//! item is stored under a unique name and described by a tombstone in
+//! `.bluekit/.trash/manifest.json`, which records where it came from so it
+//! can be restored. Entries older than the retention period from
+//! the settings (`trash.retentionDays`, where 0 keeps them forever) are
+//! purged when something new is trashed and by the `trash_purge` scheduled
+//! job.
+
+use crate::core::{file_lock, paths};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Trash directory inside `.bluekit` (hidden, so artifact scans skip it)
+pub const TRASH_DIR: &str = ".trash";
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Tombstone for one trashed file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    /// Where the file was, relative to `.bluekit` and `/`-separated
+    #[serde(rename = "originalPath")]
+    pub original_path: String,
+    /// File name inside the trash directory
+    #[serde(rename = "trashedName")]
+    pub trashed_name: String,
+    /// Unix seconds
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: i64,
+    /// Bytes, summed over the files of a folder
+    pub size: u64,
+    /// A whole folder was trashed (a scrapbook folder)
+    #[serde(rename = "isFolder", default)]
+    pub is_folder: bool,
+}
+
+/// Moves `path` (a file or folder inside a `.bluekit` directory) to that
+/// project's trash, first purging entries past the retention period.
+pub async fn move_to_trash(path: &Path) -> Result<TrashEntry, String> {
+    let bluekit_dir = bluekit_dir_of(path)?;
+    let original_path = path
+        .strip_prefix(&bluekit_dir)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .map_err(|_| format!("{} is not inside .bluekit", path.display()))?;
+    if original_path.starts_with(&format!("{}/", TRASH_DIR)) {
+        return Err("File is already in the trash".to_string());
+    }
+
+    let retention_days = crate::core::settings::load_settings().trash.retention_days;
+    if retention_days > 0 {
+        if let Err(e) = purge_older_than(&bluekit_dir, retention_days).await {
+            tracing::warn!("Failed to purge expired trash: {}", e);
+        }
+    }
+
+    let trash_dir = bluekit_dir.join(TRASH_DIR);
+    fs::create_dir_all(&trash_dir).map_err(|e| format!("Failed to create trash directory: {}", e))?;
+
+    let id = Uuid::new_v4().to_string();
+    let trashed_name = format!("{}-{}", id, paths::file_name_lossy(path));
+    let is_folder = path.is_dir();
+    let size = if is_folder {
+        paths::files_under(path)
+            .iter()
+            .filter_map(|file| fs::metadata(file).ok())
+            .map(|m| m.len())
+            .sum()
+    } else {
+        fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+    };
+
+    let manifest_path = trash_dir.join(MANIFEST_FILE);
+    let _lock = file_lock::lock(&manifest_path, "move_to_trash").await?;
+    let mut entries = read_manifest(&manifest_path)?;
+    fs::rename(path, trash_dir.join(&trashed_name))
+        .map_err(|e| format!("Failed to move {} to trash: {}", path.display(), e))?;
+
+    let entry = TrashEntry {
+        id,
+        original_path,
+        trashed_name,
+        deleted_at: chrono::Utc::now().timestamp(),
+        size,
+        is_folder,
+    };
+    entries.push(entry.clone());
+    write_manifest(&manifest_path, &entries)?;
+
+    Ok(entry)
+}
+
+/// Trashed files and folders of a project, most recently deleted first.
+/// Tombstones whose file has gone missing are left out.
+pub fn list_trash(project_root: &Path) -> Result<Vec<TrashEntry>, String> {
+    let trash_dir = project_root.join(".bluekit").join(TRASH_DIR);
+    let mut entries: Vec<TrashEntry> = read_manifest(&trash_dir.join(MANIFEST_FILE))?
+        .into_iter()
+        .filter(|entry| trash_dir.join(&entry.trashed_name).exists())
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.deleted_at));
+    Ok(entries)
+}
+
+/// Moves a trashed file or folder back to where it was, recreating missing
+/// folders.
+/// Fails if something else now occupies the original path.
+pub async fn restore(project_root: &Path, id: &str) -> Result<PathBuf, String> {
+    let bluekit_dir = project_root.join(".bluekit");
+    let trash_dir = bluekit_dir.join(TRASH_DIR);
+    let manifest_path = trash_dir.join(MANIFEST_FILE);
+    let _lock = file_lock::lock(&manifest_path, "restore_resource").await?;
+
+    let mut entries = read_manifest(&manifest_path)?;
+    let position = entries
+        .iter()
+        .position(|entry| entry.id == id)
+        .ok_or_else(|| format!("Trash entry not found: {}", id))?;
+    let entry = &entries[position];
+
+    let destination = entry
+        .original_path
+        .split('/')
+        .fold(bluekit_dir.clone(), |dir, component| dir.join(component));
+    if destination.exists() {
+        return Err(format!(
+            "Can't restore {}: a file already exists at that path",
+            entry.original_path
+        ));
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+    fs::rename(trash_dir.join(&entry.trashed_name), &destination)
+        .map_err(|e| format!("Failed to restore {}: {}", entry.original_path, e))?;
+
+    entries.remove(position);
+    write_manifest(&manifest_path, &entries)?;
+    Ok(destination)
+}
+
+/// Permanently deletes trashed files and folders older than
+/// `older_than_days`, or all of them when `None`. Returns the number of
+/// entries removed; an entry that can't be removed keeps its tombstone.
+pub async fn empty_trash(project_root: &Path, older_than_days: Option<u32>) -> Result<usize, String> {
+    let bluekit_dir = project_root.join(".bluekit");
+    purge_older_than(&bluekit_dir, older_than_days.unwrap_or(0)).await
+}
+
+async fn purge_older_than(bluekit_dir: &Path, days: u32) -> Result<usize, String> {
+    let trash_dir = bluekit_dir.join(TRASH_DIR);
+    let manifest_path = trash_dir.join(MANIFEST_FILE);
+    if !manifest_path.exists() {
+        return Ok(0);
+    }
+    let _lock = file_lock::lock(&manifest_path, "empty_trash").await?;
+
+    let cutoff = chrono::Utc::now().timestamp() - i64::from(days) * SECONDS_PER_DAY;
+    let mut kept = Vec::new();
+    let mut purged = 0;
+    for entry in read_manifest(&manifest_path)? {
+        if days != 0 && entry.deleted_at >= cutoff {
+            kept.push(entry);
+            continue;
+        }
+        let path = trash_dir.join(&entry.trashed_name);
+        let removed = if path.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        match removed {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                tracing::warn!("Failed to remove trashed file {}: {}", path.display(), e);
+                kept.push(entry);
+            }
+            _ => purged += 1,
+        }
+    }
+    write_manifest(&manifest_path, &kept)?;
+    Ok(purged)
+}
+
+fn bluekit_dir_of(path: &Path) -> Result<PathBuf, String> {
+    path.ancestors()
+        .find(|p| p.file_name().is_some_and(|n| n == ".bluekit"))
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("Path is not within a .bluekit directory: {}", path.display()))
+}
+
+// A missing manifest is an empty trash. One that can't be parsed is an
+// error, so the next write doesn't replace it and lose every tombstone; a
+// copy is kept next to it for recovery
+fn read_manifest(path: &Path) -> Result<Vec<TrashEntry>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read trash manifest {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&content).map_err(|e| {
+        let backup = path.with_extension("json.corrupt");
+        match fs::copy(path, &backup) {
+            Ok(_) => format!(
+                "Trash manifest {} is corrupt ({}); a copy was saved to {}",
+                path.display(),
+                e,
+                backup.display()
+            ),
+            Err(_) => format!("Trash manifest {} is corrupt: {}", path.display(), e),
+        }
+    })
+}
+
+fn write_manifest(path: &Path, entries: &[TrashEntry]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize trash manifest: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write trash manifest: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_trash_folder_round_trip() {
+        let project_root = std::env::temp_dir().join(format!("bluekit-trash-{}", Uuid::new_v4()));
+        let folder = project_root.join(".bluekit").join("ideas");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::write(folder.join("a.md"), "abc").unwrap();
+        fs::write(folder.join("nested").join("b.md"), "de").unwrap();
+
+        let entry = move_to_trash(&folder).await.unwrap();
+        assert!(!folder.exists());
+        assert!(entry.is_folder);
+        assert_eq!(entry.size, 5);
+
+        let listed = list_trash(&project_root).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].original_path, "ideas");
+
+        restore(&project_root, &entry.id).await.unwrap();
+        assert_eq!(fs::read_to_string(folder.join("nested").join("b.md")).unwrap(), "de");
+        assert!(list_trash(&project_root).unwrap().is_empty());
+
+        let entry = move_to_trash(&folder).await.unwrap();
+        assert_eq!(empty_trash(&project_root, None).await.unwrap(), 1);
+        let trash_dir = project_root.join(".bluekit").join(TRASH_DIR);
+        assert!(!trash_dir.join(&entry.trashed_name).exists());
+        assert!(list_trash(&project_root).unwrap().is_empty());
+
+        fs::remove_dir_all(&project_root).unwrap();
+    }
+}
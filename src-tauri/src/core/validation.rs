@@ -0,0 +1,185 @@
+//! Artifact validation and the headless `validate` mode.
+//!
+//! `bluekit-app validate [--staged] [FILES...]` checks `.bluekit` artifacts
+//! without starting the UI, printing problems to stderr and exiting non-zero
+//! if any were found. The pre-commit hook installed by `install_git_hooks`
+//! runs it with `--staged` so broken artifacts can't be committed.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Validates one artifact.
+///
+/// `relative_path` decides which checks apply; `project_root` is used to
+/// resolve blueprint task files. Returns a list of problems (empty if valid).
+pub fn validate_artifact(relative_path: &str, content: &str, project_root: &Path) -> Vec<String> {
+    let normalized = relative_path.replace('\\', "/");
+    let mut errors = Vec::new();
+
+    if normalized.ends_with("/blueprint.json") || normalized == "blueprint.json" {
+        validate_blueprint(&normalized, content, project_root, &mut errors);
+        return errors;
+    }
+
+    let is_artifact = [".md", ".mmd", ".mermaid"]
+        .iter()
+        .any(|ext| normalized.ends_with(ext));
+    if !is_artifact {
+        return errors;
+    }
+
+    if let Err(e) = parse_front_matter_strict(content) {
+        errors.push(e);
+    }
+
+    if normalized.contains(".bluekit/agents/") && normalized.ends_with(".md") {
+        errors.extend(crate::db::agent_operations::validate_agent_content(content).errors);
+    }
+
+    errors
+}
+
+// Unlike the lenient parser used for listing, reports why front matter is
+// invalid. `Ok(None)` if the content has no front matter at all.
+pub(crate) fn parse_front_matter_strict(content: &str) -> Result<Option<serde_yaml::Value>, String> {
+    let Some(after_first_delim) = content.trim_start().strip_prefix("---") else {
+        return Ok(None);
+    };
+    let end_pos = after_first_delim
+        .find("\n---")
+        .ok_or_else(|| "Front matter is not closed with `---`".to_string())?;
+
+    let value: serde_yaml::Value = serde_yaml::from_str(&after_first_delim[..end_pos])
+        .map_err(|e| format!("Invalid front matter YAML: {}", e))?;
+    if !value.is_mapping() && !value.is_null() {
+        return Err("Front matter must be a YAML mapping".to_string());
+    }
+    Ok(Some(value))
+}
+
+fn validate_blueprint(relative_path: &str, content: &str, project_root: &Path, errors: &mut Vec<String>) {
+    let metadata: crate::commands::BlueprintMetadata = match serde_json::from_str(content) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            errors.push(format!("Invalid blueprint.json: {}", e));
+            return;
+        }
+    };
+
+    let blueprint_dir = Path::new(relative_path).parent().unwrap_or(Path::new(""));
+    for layer in &metadata.layers {
+        for task in &layer.tasks {
+            if !project_root.join(blueprint_dir).join(&task.task_file).exists() {
+                errors.push(format!(
+                    "Layer '{}' references missing task file: {}",
+                    layer.name, task.task_file
+                ));
+            }
+        }
+    }
+}
+
+/// Runs the headless CLI if the arguments ask for it.
+///
+/// Returns the process exit code, or `None` to start the app normally.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    if args.get(1).map(String::as_str) != Some("validate") {
+        return None;
+    }
+
+    let staged = args[2..].iter().any(|a| a == "--staged");
+    let files: Vec<&String> = args[2..].iter().filter(|a| !a.starts_with("--")).collect();
+
+    let project_root = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            eprintln!("bluekit: cannot determine working directory: {}", e);
+            return Some(2);
+        }
+    };
+
+    // (relative path, content) pairs to check
+    let artifacts: Vec<(String, Result<String, String>)> = if staged {
+        match staged_bluekit_files() {
+            Ok(paths) => paths
+                .into_iter()
+                .map(|path| {
+                    let content = staged_content(&path);
+                    (path, content)
+                })
+                .collect(),
+            Err(e) => {
+                eprintln!("bluekit: {}", e);
+                return Some(2);
+            }
+        }
+    } else {
+        files
+            .into_iter()
+            .map(|path| {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| format!("Failed to read file: {}", e));
+                (path.clone(), content)
+            })
+            .collect()
+    };
+
+    let mut problem_count = 0;
+    for (path, content) in &artifacts {
+        let errors = match content {
+            Ok(content) => validate_artifact(path, content, &project_root),
+            Err(e) => vec![e.clone()],
+        };
+        for error in errors {
+            eprintln!("{}: {}", path, error);
+            problem_count += 1;
+        }
+    }
+
+    if problem_count > 0 {
+        eprintln!(
+            "bluekit: {} problem(s) found in {} artifact(s)",
+            problem_count,
+            artifacts.len()
+        );
+        Some(1)
+    } else {
+        Some(0)
+    }
+}
+
+// Staged (added/copied/modified) files under a .bluekit directory
+fn staged_bluekit_files() -> Result<Vec<String>, String> {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "--name-only", "--diff-filter=ACM", "-z"])
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|p| p.starts_with(".bluekit/") || p.contains("/.bluekit/"))
+        .map(|p| p.to_string())
+        .collect())
+}
+
+// Validate what is being committed, not the working tree copy
+fn staged_content(path: &str) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("show")
+        .arg(format!(":{}", path))
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to read staged content: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|_| "Staged content is not valid UTF-8".to_string())
+}
@@ -7,10 +7,11 @@
 /// - Auto-recovery with exponential backoff
 /// - Proper error propagation to frontend
 /// - Task lifecycle management
-/// - Extended file type support (.md, .mmd, .mermaid, .json)
+/// - Extended file type support (.md, .mmd, .mermaid, .json, image assets)
+/// - Paths matched by `.bluekit/.bluekitignore` are skipped
 
 use notify::{Watcher, RecommendedWatcher, RecursiveMode};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -21,6 +22,7 @@ use tauri::{AppHandle, Manager};
 use std::env;
 use std::fs;
 use tracing::{info, warn, error, debug};
+use crate::core::ignore_rules::{IgnoreRules, IGNORE_FILE};
 
 // Configuration constants
 const CHANNEL_BUFFER_SIZE: usize = 100;   // Bounded channel prevents OOM
@@ -62,12 +64,17 @@ struct DebouncerState {
 /// Checks if a file extension matches watched types
 fn is_watched_file(path: &PathBuf) -> bool {
     if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        matches!(ext, "md" | "mmd" | "mermaid" | "json")
+        matches!(ext, "md" | "mmd" | "mermaid" | "json" | "png" | "svg" | "jpg" | "jpeg")
     } else {
         false
     }
 }
 
+/// Checks if a path is a `.bluekitignore` file
+fn is_ignore_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == IGNORE_FILE)
+}
+
 /// Checks if a JSON file is one we specifically care about
 fn is_watched_json(path: &PathBuf) -> bool {
     if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
@@ -124,9 +131,8 @@ pub fn watch_file(
         .map_err(|e| format!("Failed to start watching directory: {}", e))?;
 
     let file_name = file_path.file_name()
-        .and_then(|n| n.to_str())
         .ok_or_else(|| "Invalid file name".to_string())?
-        .to_string();
+        .to_os_string();
 
     let event_name_for_task = event_name.clone();
 
@@ -154,10 +160,7 @@ pub fn watch_file(
                     match event_result {
                         Some(Ok(event)) => {
                             if let Some(path) = event.paths.iter().find(|p| {
-                                p.file_name()
-                                    .and_then(|n| n.to_str())
-                                    .map(|n| n == file_name)
-                                    .unwrap_or(false)
+                                p.file_name() == Some(file_name.as_os_str())
                             }) {
                                 // Debounce: collect events (insert deduplicates automatically)
                                 debounce_state.pending_paths.insert(path.clone());
@@ -218,6 +221,122 @@ pub fn watch_file(
     Ok(())
 }
 
+/// A change to one of the files given to [`watch_files`]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WatchedFileChange {
+    pub path: String,
+    /// The file no longer exists
+    pub deleted: bool,
+}
+
+/// Starts watching a set of files, possibly in different directories, and
+/// emits one Tauri event per debounced batch of changes.
+///
+/// The payload is the changed files as [`WatchedFileChange`]s, so deleted
+/// files can be told apart. Files whose directory doesn't exist are skipped;
+/// at least one must be watchable. Event paths are compared canonicalized,
+/// since the OS may report them through a different (symlinked) prefix.
+///
+/// # Arguments
+///
+/// * `app_handle` - Tauri application handle for emitting events
+/// * `files` - Paths of the files to watch
+/// * `event_name` - Name of the Tauri event to emit when files change
+///
+/// # Returns
+///
+/// A `Result<(), String>` indicating success or failure
+pub fn watch_files(
+    app_handle: AppHandle,
+    files: Vec<PathBuf>,
+    event_name: String,
+) -> Result<(), String> {
+    let (tx, mut rx) = mpsc::channel(CHANNEL_BUFFER_SIZE);
+
+    // Create cancellation channel for graceful shutdown
+    let (cancel_tx, mut cancel_rx) = oneshot::channel::<()>();
+
+    let mut watcher: RecommendedWatcher = Watcher::new(
+        move |res| {
+            if tx.blocking_send(res).is_err() {
+                warn!("Watcher channel full, dropping event");
+            }
+        },
+        notify::Config::default()
+    ).map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+    // Each directory is watched once, non-recursively
+    let watch_dirs: std::collections::HashSet<PathBuf> = files
+        .iter()
+        .filter_map(|file| file.parent())
+        .filter(|dir| dir.is_dir())
+        .map(Path::to_path_buf)
+        .collect();
+    if watch_dirs.is_empty() {
+        return Err("None of the files are in an existing directory".to_string());
+    }
+    for dir in &watch_dirs {
+        watcher.watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to start watching directory: {}", e))?;
+    }
+
+    // Canonical path -> the path as given, which is what gets reported
+    let watched: HashMap<PathBuf, PathBuf> = files
+        .iter()
+        .map(|file| (crate::core::paths::canonical_lenient(file), file.clone()))
+        .collect();
+    let event_name_for_task = event_name.clone();
+
+    let task_handle = tauri::async_runtime::spawn(async move {
+        let _watcher = watcher; // Keep watcher alive
+
+        info!("File set watcher started for: {}", event_name_for_task);
+
+        let select = |event: &notify::Event| -> Vec<PathBuf> {
+            event.paths
+                .iter()
+                .filter_map(|p| watched.get(&crate::core::paths::canonical_lenient(p)).cloned())
+                .collect()
+        };
+        let flush = |paths: Vec<PathBuf>| {
+            let changes: Vec<WatchedFileChange> = paths
+                .into_iter()
+                .map(|path| WatchedFileChange {
+                    deleted: !path.exists(),
+                    path: crate::core::paths::to_ipc(&path),
+                })
+                .collect();
+            debug!("Debounced {} watched file changes, emitting event", changes.len());
+
+            if let Err(e) = app_handle.emit_all(&event_name_for_task, changes) {
+                error!("Failed to emit file change event: {}", e);
+            }
+            std::future::ready(())
+        };
+        debounce_loop(&app_handle, &event_name_for_task, &mut rx, &mut cancel_rx, select, flush).await;
+
+        info!("File set watcher task exiting: {}", event_name_for_task);
+    });
+
+    // Store task handle for lifecycle management
+    let event_name_clone = event_name.clone();
+    let first_file = files.first().cloned().unwrap_or_default();
+
+    tauri::async_runtime::spawn(async move {
+        let mut registry = WATCHER_REGISTRY.write().await;
+        registry.insert(event_name_clone, WatcherTask {
+            path: first_file,
+            event_name,
+            restart_count: 0,
+            is_active: true,
+            task_handle,
+            cancel_tx: Some(cancel_tx),
+        });
+    });
+
+    Ok(())
+}
+
 /// Starts watching a directory for file changes and emits Tauri events.
 ///
 /// Watches recursively with auto-recovery on failure.
@@ -235,19 +354,34 @@ pub fn watch_directory(
     app_handle: AppHandle,
     directory_path: PathBuf,
     event_name: String,
+) -> Result<(), String> {
+    watch_directory_with_hook(app_handle, directory_path, event_name, None)
+}
+
+/// Called with the changed paths each time a directory watcher emits its
+/// event, for work that follows from the change (like events of its own)
+pub type ChangeHook = Arc<dyn Fn(&AppHandle, &[String]) + Send + Sync>;
+
+/// [`watch_directory`], calling `hook` after each emitted change event.
+pub fn watch_directory_with_hook(
+    app_handle: AppHandle,
+    directory_path: PathBuf,
+    event_name: String,
+    hook: Option<ChangeHook>,
 ) -> Result<(), String> {
     if !directory_path.exists() {
         fs::create_dir_all(&directory_path)
             .map_err(|e| format!("Failed to create directory: {}", e))?;
     }
 
-    start_directory_watcher_with_recovery(app_handle, directory_path, event_name, 0)
+    start_directory_watcher_with_recovery(app_handle, directory_path, event_name, hook, 0)
 }
 
 fn start_directory_watcher_with_recovery(
     app_handle: AppHandle,
     directory_path: PathBuf,
     event_name: String,
+    hook: Option<ChangeHook>,
     restart_count: u32,
 ) -> Result<(), String> {
     let (tx, mut rx) = mpsc::channel(CHANNEL_BUFFER_SIZE);
@@ -275,111 +409,63 @@ fn start_directory_watcher_with_recovery(
     let task_handle = tauri::async_runtime::spawn(async move {
         let _watcher = watcher;
 
-        let mut debounce_state = DebouncerState {
-            last_event_time: Instant::now(),
-            pending_paths: std::collections::HashSet::new(), // HashSet for automatic deduplication
-        };
-
-        let mut consecutive_errors = 0u32;
-        const MAX_CONSECUTIVE_ERRORS: u32 = 10;
+        // Paths matched by .bluekit/.bluekitignore are not reported; the
+        // rules are reloaded whenever the ignore file itself changes
+        let mut ignore_rules = IgnoreRules::for_path(&dir_path_for_restart);
 
         info!("Directory watcher started for: {}", event_name_for_task);
 
-        // Track why the watcher exited
-        enum ExitReason {
-            Cancelled,  // Intentional shutdown
-            Error,      // Crash/error that should trigger restart
-        }
-
-        let exit_reason = loop {
-            tokio::select! {
-                // Cancellation signal received
-                _ = &mut cancel_rx => {
-                    info!("Directory watcher cancelled: {}", event_name_for_task);
-                    break ExitReason::Cancelled;
-                }
-
-                event_result = rx.recv() => {
-                    match event_result {
-                        Some(Ok(event)) => {
-                            consecutive_errors = 0; // Reset error counter
-
-                            // Check if any relevant files changed
-                            let has_relevant_change = event.paths.iter().any(|p| {
-                                if is_watched_file(p) {
-                                    // For JSON files, only watch specific ones
-                                    if p.extension().and_then(|e| e.to_str()) == Some("json") {
-                                        is_watched_json(p)
-                                    } else {
-                                        true // All .md, .mmd, .mermaid files
-                                    }
-                                } else {
-                                    false
-                                }
-                            });
-
-                            if has_relevant_change {
-                                for path in &event.paths {
-                                    debounce_state.pending_paths.insert(path.clone()); // Insert into HashSet (auto-deduplicates)
-                                }
-                                debounce_state.last_event_time = Instant::now();
-                            }
-                        }
-                        Some(Err(e)) => {
-                            consecutive_errors += 1;
-                            error!("Directory watcher error (#{}/{}): {}",
-                                consecutive_errors, MAX_CONSECUTIVE_ERRORS, e);
-
-                            // Emit error event
-                            let _ = app_handle.emit_all(&format!("{}-error", event_name_for_task),
-                                format!("Watcher error: {}", e));
-
-                            // Too many errors - trigger restart
-                            if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
-                                error!("Too many consecutive errors, attempting restart");
-                                break ExitReason::Error;
-                            }
-                        }
-                        None => {
-                            warn!("Directory watcher channel closed");
-                            break ExitReason::Error;
+        let select = |event: &notify::Event| -> Vec<PathBuf> {
+            // An edited ignore file changes what the lists show
+            if event.paths.iter().any(|p| is_ignore_file(p)) {
+                ignore_rules = IgnoreRules::for_path(&dir_path_for_restart);
+            }
+            event.paths
+                .iter()
+                .filter(|p| {
+                    if is_ignore_file(p) {
+                        true
+                    } else if ignore_rules.is_ignored(p, p.is_dir()) {
+                        false
+                    } else if is_watched_file(p) {
+                        // For JSON files, only watch specific ones
+                        if p.extension().and_then(|e| e.to_str()) == Some("json") {
+                            is_watched_json(p)
+                        } else {
+                            true // All .md, .mmd, .mermaid and image files
                         }
+                    } else {
+                        false
                     }
+                })
+                .cloned()
+                .collect()
+        };
+        let flush = |paths: Vec<PathBuf>| {
+            let app_handle = app_handle.clone();
+            let event_name = event_name_for_task.clone();
+            let hook = hook.clone();
+            async move {
+                debug!("Debounced {} directory changes, emitting event", paths.len());
+
+                // Keep the search index and link graph in step before the frontend reacts
+                if let Some(index) = app_handle.try_state::<crate::core::search_index::SearchIndex>() {
+                    index.mark_changed(&paths).await;
+                }
+                if let Some(graph) = app_handle.try_state::<crate::core::link_graph::LinkGraph>() {
+                    graph.mark_changed(&paths).await;
                 }
 
-                _ = sleep(Duration::from_millis(DEBOUNCE_DURATION_MS)) => {
-                    if !debounce_state.pending_paths.is_empty() &&
-                       debounce_state.last_event_time.elapsed() >= Duration::from_millis(DEBOUNCE_DURATION_MS) {
-                        debug!("Debounced {} directory changes, emitting event",
-                            debounce_state.pending_paths.len());
-
-                        // Filter to only watched file types and convert to strings
-                        let changed_paths: Vec<String> = debounce_state.pending_paths
-                            .iter()
-                            .filter(|p| {
-                                if is_watched_file(p) {
-                                    // For JSON files, only watch specific ones
-                                    if p.extension().and_then(|e| e.to_str()) == Some("json") {
-                                        is_watched_json(p)
-                                    } else {
-                                        true // All .md, .mmd, .mermaid files
-                                    }
-                                } else {
-                                    false
-                                }
-                            })
-                            .map(|p| p.to_string_lossy().to_string())
-                            .collect();
-
-                        if let Err(e) = app_handle.emit_all(&event_name_for_task, changed_paths) {
-                            error!("Failed to emit directory change event: {}", e);
-                        }
-
-                        debounce_state.pending_paths.clear();
-                    }
+                let changed_paths: Vec<String> = paths.iter().map(|p| crate::core::paths::to_ipc(p)).collect();
+                if let Err(e) = app_handle.emit_all(&event_name, &changed_paths) {
+                    error!("Failed to emit directory change event: {}", e);
+                }
+                if let Some(hook) = hook {
+                    hook(&app_handle, &changed_paths);
                 }
             }
         };
+        let exit_reason = debounce_loop(&app_handle, &event_name_for_task, &mut rx, &mut cancel_rx, select, flush).await;
 
         // Only auto-restart if watcher crashed (not intentionally cancelled)
         if matches!(exit_reason, ExitReason::Error) &&
@@ -397,6 +483,7 @@ fn start_directory_watcher_with_recovery(
                 app_handle_for_restart,
                 dir_path_for_restart,
                 event_name_for_restart,
+                hook,
                 next_restart,
             ) {
                 error!("Failed to restart directory watcher: {}", e);
@@ -437,6 +524,91 @@ fn start_directory_watcher_with_recovery(
     Ok(())
 }
 
+/// Why a watcher's debounce loop exited
+enum ExitReason {
+    Cancelled,  // Intentional shutdown
+    Error,      // Crash/error that should trigger restart
+}
+
+/// The debounce loop shared by the multi-file watchers.
+///
+/// Each event's paths go through `select`; what it keeps is collected (deduplicated)
+/// and handed to `flush` once no relevant event has arrived for the debounce
+/// window. Watcher errors are emitted as `{event_name}-error`; too many in a
+/// row, or a closed channel, end the loop with `ExitReason::Error`.
+async fn debounce_loop<S, F, Fut>(
+    app_handle: &AppHandle,
+    event_name: &str,
+    rx: &mut mpsc::Receiver<notify::Result<notify::Event>>,
+    cancel_rx: &mut oneshot::Receiver<()>,
+    mut select: S,
+    mut flush: F,
+) -> ExitReason
+where
+    S: FnMut(&notify::Event) -> Vec<PathBuf>,
+    F: FnMut(Vec<PathBuf>) -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    const MAX_CONSECUTIVE_ERRORS: u32 = 10;
+
+    let mut debounce_state = DebouncerState {
+        last_event_time: Instant::now(),
+        pending_paths: std::collections::HashSet::new(), // HashSet for automatic deduplication
+    };
+    let mut consecutive_errors = 0u32;
+
+    loop {
+        tokio::select! {
+            // Cancellation signal received
+            _ = &mut *cancel_rx => {
+                info!("Watcher cancelled: {}", event_name);
+                return ExitReason::Cancelled;
+            }
+
+            event_result = rx.recv() => {
+                match event_result {
+                    Some(Ok(event)) => {
+                        consecutive_errors = 0; // Reset error counter
+
+                        let relevant = select(&event);
+                        if !relevant.is_empty() {
+                            debounce_state.pending_paths.extend(relevant);
+                            debounce_state.last_event_time = Instant::now();
+                        }
+                    }
+                    Some(Err(e)) => {
+                        consecutive_errors += 1;
+                        error!("Watcher error (#{}/{}): {}",
+                            consecutive_errors, MAX_CONSECUTIVE_ERRORS, e);
+
+                        // Emit error event
+                        let _ = app_handle.emit_all(&format!("{}-error", event_name),
+                            format!("Watcher error: {}", e));
+
+                        // Too many errors - trigger restart
+                        if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
+                            error!("Too many consecutive errors, stopping watcher");
+                            return ExitReason::Error;
+                        }
+                    }
+                    None => {
+                        warn!("Watcher channel closed: {}", event_name);
+                        return ExitReason::Error;
+                    }
+                }
+            }
+
+            // Debounce timer - flush after quiet period
+            _ = sleep(Duration::from_millis(DEBOUNCE_DURATION_MS)) => {
+                if !debounce_state.pending_paths.is_empty() &&
+                   debounce_state.last_event_time.elapsed() >= Duration::from_millis(DEBOUNCE_DURATION_MS) {
+                    flush(debounce_state.pending_paths.drain().collect()).await;
+                }
+            }
+        }
+    }
+}
+
 /// Checks if a watcher exists by event name
 pub async fn watcher_exists(event_name: &str) -> bool {
     let registry = WATCHER_REGISTRY.read().await;
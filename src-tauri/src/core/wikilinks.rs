@@ -0,0 +1,175 @@
+//! `[[artifact-name]]` links between artifacts.
+//!
+//! A wikilink names an artifact instead of giving its path:
+//! `[[api-client]]`, `[[API Client|the client]]` or `[[kits/api-client#setup]]`.
+//! The name is matched against artifact file names first and `alias` front
+//! matter second, ignoring case and treating spaces, `_` and `-` alike. A
+//! name containing `/` is matched against the path below `.bluekit`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A `[[...]]` occurrence in an artifact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WikilinkRef {
+    /// The artifact name, without `|label` or `#heading`
+    pub name: String,
+    /// The label after `|`, or the name
+    pub label: String,
+    /// 1-based line
+    pub line: usize,
+}
+
+/// What a wikilink name resolved to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WikilinkResolution {
+    pub text: String,
+    /// The artifact it resolved to; the first by path if several match
+    pub path: Option<String>,
+    /// "name", "alias" or "path", when resolved
+    #[serde(rename = "matchedBy")]
+    pub matched_by: Option<String>,
+    /// Every artifact that matched, when more than one did
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ambiguous: Vec<String>,
+}
+
+/// Finds the wikilinks in Markdown `content`, skipping fenced code blocks
+/// and inline code.
+pub fn parse_wikilinks(content: &str) -> Vec<WikilinkRef> {
+    let mut links = Vec::new();
+    let mut in_code_block = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        // Odd-numbered pieces between backticks are inline code
+        for (piece_index, piece) in line.split('`').enumerate() {
+            if piece_index % 2 == 1 {
+                continue;
+            }
+            let mut rest = piece;
+            while let Some(start) = rest.find("[[") {
+                let after = &rest[start + 2..];
+                let Some(end) = after.find("]]") else {
+                    break;
+                };
+                let inner = &after[..end];
+                rest = &after[end + 2..];
+
+                let (target, label) = match inner.split_once('|') {
+                    Some((target, label)) => (target, Some(label.trim())),
+                    None => (inner, None),
+                };
+                let name = target.split('#').next().unwrap_or("").trim();
+                if name.is_empty() || name.contains('[') {
+                    continue;
+                }
+                links.push(WikilinkRef {
+                    name: name.to_string(),
+                    label: label.filter(|l| !l.is_empty()).unwrap_or(name).to_string(),
+                    line: index + 1,
+                });
+            }
+        }
+    }
+    links
+}
+
+/// Maps wikilink names to artifacts. Built from every artifact in a
+/// project, then used for any number of lookups.
+#[derive(Debug, Default)]
+pub struct WikilinkResolver {
+    by_name: HashMap<String, Vec<PathBuf>>,
+    by_alias: HashMap<String, Vec<PathBuf>>,
+    by_path: HashMap<String, Vec<PathBuf>>,
+}
+
+impl WikilinkResolver {
+    /// `artifacts` pairs each artifact path with its `alias`, if any.
+    pub fn new<'a>(bluekit_dir: &Path, artifacts: impl IntoIterator<Item = (&'a Path, Option<&'a str>)>) -> Self {
+        let mut resolver = Self::default();
+        for (path, alias) in artifacts {
+            let stem = crate::core::paths::file_stem_lossy(path);
+            resolver.by_name.entry(key(&stem)).or_default().push(path.to_path_buf());
+            if let Some(alias) = alias.filter(|a| !a.trim().is_empty()) {
+                resolver.by_alias.entry(key(alias)).or_default().push(path.to_path_buf());
+            }
+            if let Ok(relative) = path.with_extension("").strip_prefix(bluekit_dir) {
+                let relative = relative.to_string_lossy().replace('\\', "/");
+                resolver.by_path.entry(key(&relative)).or_default().push(path.to_path_buf());
+            }
+        }
+        for paths in resolver
+            .by_name
+            .values_mut()
+            .chain(resolver.by_alias.values_mut())
+            .chain(resolver.by_path.values_mut())
+        {
+            paths.sort();
+        }
+        resolver
+    }
+
+    /// The artifact `name` refers to, if any.
+    pub fn resolve(&self, name: &str) -> Option<&Path> {
+        self.candidates(name).and_then(|(paths, _)| paths.first()).map(PathBuf::as_path)
+    }
+
+    /// Resolves one name, reporting how it matched.
+    pub fn resolution(&self, text: &str) -> WikilinkResolution {
+        let candidates = self.candidates(text);
+        WikilinkResolution {
+            text: text.to_string(),
+            path: candidates
+                .and_then(|(paths, _)| paths.first())
+                .map(|p| crate::core::paths::to_ipc(p)),
+            matched_by: candidates.map(|(_, matched_by)| matched_by.to_string()),
+            ambiguous: match candidates {
+                Some((paths, _)) if paths.len() > 1 => paths.iter().map(|p| crate::core::paths::to_ipc(p)).collect(),
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    /// Resolves many names at once, in order.
+    pub fn resolve_all(&self, texts: &[String]) -> Vec<WikilinkResolution> {
+        texts.iter().map(|text| self.resolution(text)).collect()
+    }
+
+    fn candidates(&self, text: &str) -> Option<(&Vec<PathBuf>, &'static str)> {
+        let name = text.split(['|', '#']).next().unwrap_or("").trim();
+        let name = name.trim_end_matches(".md");
+        let key = key(name);
+        if name.contains('/') {
+            return self.by_path.get(key.trim_start_matches('/')).map(|paths| (paths, "path"));
+        }
+        self.by_name
+            .get(&key)
+            .map(|paths| (paths, "name"))
+            .or_else(|| self.by_alias.get(&key).map(|paths| (paths, "alias")))
+    }
+}
+
+/// The `alias` in `content`'s front matter, for [`WikilinkResolver::new`]
+pub fn front_matter_alias(content: &str) -> Option<String> {
+    let front_matter = crate::core::validation::parse_front_matter_strict(content).ok()??;
+    front_matter.get("alias")?.as_str().map(str::to_string)
+}
+
+// Lowercase, with spaces and `_` as `-`
+fn key(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c == ' ' || c == '_' { '-' } else { c })
+        .collect()
+}
@@ -0,0 +1,423 @@
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::agent;
+use crate::db::walkthrough_operations::slugify;
+use crate::library::utils::compute_content_hash;
+use chrono::Utc;
+use uuid::Uuid;
+use std::path::{Path, PathBuf};
+use std::fs;
+
+/// Models accepted by Claude custom agent configs (full `claude-*` ids are also accepted)
+const KNOWN_MODELS: &[&str] = &["sonnet", "opus", "haiku", "inherit"];
+
+/// Built-in tools agents can be granted (`mcp__*` tools are also accepted)
+const KNOWN_TOOLS: &[&str] = &[
+    "Read", "Write", "Edit", "MultiEdit", "Glob", "Grep", "Bash", "WebFetch",
+    "WebSearch", "Task", "TodoWrite", "NotebookEdit",
+];
+
+/// Agent DTO for frontend communication
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentDto {
+    pub id: String,
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub model: Option<String>,
+    pub tools: Vec<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+/// Result of validating an agent file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentValidationDto {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Result of exporting an agent to an external tool's format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentExportDto {
+    pub format: String,
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    pub content: String,
+}
+
+/// Parsed agent file: front matter fields plus the system prompt body
+struct ParsedAgent {
+    id: Option<String>,
+    name: Option<String>,
+    description: Option<String>,
+    model: Option<String>,
+    tools: Vec<String>,
+    body: String,
+}
+
+impl From<agent::Model> for AgentDto {
+    fn from(a: agent::Model) -> Self {
+        AgentDto {
+            id: a.id,
+            project_id: a.project_id,
+            file_path: a.file_path,
+            name: a.name,
+            description: a.description,
+            model: a.model,
+            tools: serde_json::from_str(&a.tools).unwrap_or_default(),
+            created_at: a.created_at,
+            updated_at: a.updated_at,
+        }
+    }
+}
+
+/// Get all agents for a project (syncs with file system first)
+pub async fn get_project_agents(
+    db: &DatabaseConnection,
+    project_id: String,
+    project_path: Option<String>,
+) -> Result<Vec<AgentDto>, DbErr> {
+    if let Some(path) = project_path {
+        sync_project_agents(db, &project_id, &path).await?;
+    }
+
+    let agents: Vec<agent::Model> = agent::Entity::find()
+        .filter(agent::Column::ProjectId.eq(&project_id))
+        .order_by_asc(agent::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(agents.into_iter().map(AgentDto::from).collect())
+}
+
+/// Sync database with agent files in the project's agents folder
+/// This ensures DB reflects file system (file system is SOT)
+pub async fn sync_project_agents(
+    db: &DatabaseConnection,
+    project_id: &str,
+    project_path: &str,
+) -> Result<(), DbErr> {
+    let agents_dir = PathBuf::from(project_path).join(".bluekit").join("agents");
+
+    let mut files = Vec::new();
+    if agents_dir.exists() {
+        collect_agent_files(&agents_dir, &mut files)?;
+    }
+
+    for path in &files {
+        // Files that aren't agents (or can't be read) are skipped, not fatal
+        let _ = register_agent_file(db, project_id, &path.to_string_lossy()).await;
+    }
+
+    // Clean up DB records for files that no longer exist
+    let existing: Vec<agent::Model> = agent::Entity::find()
+        .filter(agent::Column::ProjectId.eq(project_id))
+        .all(db)
+        .await?;
+
+    for a in existing {
+        if !Path::new(&a.file_path).exists() {
+            agent::Entity::delete_by_id(&a.id).exec(db).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create or refresh the DB record for an agent file
+pub async fn register_agent_file(
+    db: &DatabaseConnection,
+    project_id: &str,
+    file_path: &str,
+) -> Result<AgentDto, DbErr> {
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| DbErr::Custom(format!("Failed to read agent file: {}", e)))?;
+
+    let parsed = parse_agent(&content).map_err(DbErr::Custom)?;
+    let content_hash = compute_content_hash(&content);
+    let name = parsed.name.clone().or(parsed.id.clone()).unwrap_or_else(|| {
+        Path::new(file_path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Untitled Agent".to_string())
+    });
+    let tools_json = serde_json::to_string(&parsed.tools)
+        .map_err(|e| DbErr::Custom(format!("Failed to serialize tools: {}", e)))?;
+    let now = Utc::now().timestamp();
+
+    let existing = agent::Entity::find()
+        .filter(agent::Column::ProjectId.eq(project_id))
+        .filter(agent::Column::FilePath.eq(file_path))
+        .one(db)
+        .await?;
+
+    let model = match existing {
+        Some(existing) if existing.content_hash.as_deref() == Some(content_hash.as_str()) => existing,
+        Some(existing) => {
+            let mut agent_active: agent::ActiveModel = existing.into();
+            agent_active.name = Set(name);
+            agent_active.description = Set(parsed.description);
+            agent_active.model = Set(parsed.model);
+            agent_active.tools = Set(tools_json);
+            agent_active.content_hash = Set(Some(content_hash));
+            agent_active.updated_at = Set(now);
+            agent_active.update(db).await?
+        }
+        None => {
+            let agent_active = agent::ActiveModel {
+                id: Set(Uuid::new_v4().to_string()),
+                project_id: Set(project_id.to_string()),
+                file_path: Set(file_path.to_string()),
+                name: Set(name),
+                description: Set(parsed.description),
+                model: Set(parsed.model),
+                tools: Set(tools_json),
+                content_hash: Set(Some(content_hash)),
+                created_at: Set(now),
+                updated_at: Set(now),
+            };
+            agent_active.insert(db).await?
+        }
+    };
+
+    Ok(model.into())
+}
+
+/// Validate agent-specific front matter and system prompt structure
+pub fn validate_agent_file(file_path: &str) -> Result<AgentValidationDto, DbErr> {
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| DbErr::Custom(format!("Failed to read agent file: {}", e)))?;
+
+    Ok(validate_agent_content(&content))
+}
+
+/// Validate agent content without touching the file system
+pub fn validate_agent_content(content: &str) -> AgentValidationDto {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let parsed = match parse_agent(content) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return AgentValidationDto {
+                valid: false,
+                errors: vec![e],
+                warnings,
+            };
+        }
+    };
+
+    if parsed.id.is_none() && parsed.name.is_none() {
+        errors.push("Agent must define an `id` or `alias`".to_string());
+    }
+
+    if parsed.description.as_deref().map(str::trim).unwrap_or("").is_empty() {
+        errors.push("Agent must have a `description` explaining when it should be used".to_string());
+    }
+
+    if let Some(model) = &parsed.model {
+        if !KNOWN_MODELS.contains(&model.as_str()) && !model.starts_with("claude-") {
+            warnings.push(format!(
+                "Unknown model '{}' (expected one of: {})",
+                model,
+                KNOWN_MODELS.join(", ")
+            ));
+        }
+    }
+
+    for tool in &parsed.tools {
+        if !KNOWN_TOOLS.contains(&tool.as_str()) && !tool.starts_with("mcp__") {
+            warnings.push(format!("Unknown tool '{}'", tool));
+        }
+    }
+
+    // System prompt sections
+    let body = parsed.body.trim();
+    if body.is_empty() {
+        errors.push("Agent has no system prompt (body is empty)".to_string());
+    } else {
+        if !body.lines().any(|line| line.trim_start().starts_with('#')) {
+            warnings.push("System prompt has no markdown sections (e.g. `## Responsibilities`)".to_string());
+        }
+        if body.contains("[Agent instructions go here]") {
+            warnings.push("System prompt still contains the scaffold placeholder".to_string());
+        }
+    }
+
+    AgentValidationDto {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+    }
+}
+
+/// Export an agent into `.claude/agents/` or `.cursor/rules/` of the target project
+///
+/// Supported formats: `claude` (Claude Code subagent markdown) and `cursor` (Cursor `.mdc` rule).
+pub fn export_agent(
+    file_path: &str,
+    project_path: &str,
+    format: &str,
+) -> Result<AgentExportDto, DbErr> {
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| DbErr::Custom(format!("Failed to read agent file: {}", e)))?;
+
+    let validation = validate_agent_content(&content);
+    if !validation.valid {
+        return Err(DbErr::Custom(format!(
+            "Agent is invalid: {}",
+            validation.errors.join("; ")
+        )));
+    }
+
+    let parsed = parse_agent(&content).map_err(DbErr::Custom)?;
+    let slug = slugify(parsed.id.as_deref().or(parsed.name.as_deref()).unwrap_or_default());
+    let description = parsed.description.clone().unwrap_or_default();
+    let body = parsed.body.trim();
+
+    let (target_path, exported) = match format {
+        "claude" => {
+            let mut front_matter = serde_yaml::Mapping::new();
+            front_matter.insert("name".into(), slug.clone().into());
+            front_matter.insert("description".into(), description.into());
+            // Omitting tools/model makes Claude inherit them from the main session
+            if !parsed.tools.is_empty() {
+                front_matter.insert("tools".into(), parsed.tools.join(", ").into());
+            }
+            if let Some(model) = &parsed.model {
+                front_matter.insert("model".into(), model.as_str().into());
+            }
+
+            let yaml = serde_yaml::to_string(&front_matter)
+                .map_err(|e| DbErr::Custom(format!("Failed to serialize front matter: {}", e)))?;
+
+            (
+                PathBuf::from(project_path).join(".claude").join("agents").join(format!("{}.md", slug)),
+                format!("---\n{}---\n\n{}\n", yaml, body),
+            )
+        }
+        "cursor" => {
+            let description_yaml = serde_yaml::to_string(&serde_yaml::Value::String(description))
+                .map_err(|e| DbErr::Custom(format!("Failed to serialize front matter: {}", e)))?;
+
+            (
+                PathBuf::from(project_path).join(".cursor").join("rules").join(format!("{}.mdc", slug)),
+                format!(
+                    "---\ndescription: {}\nglobs:\nalwaysApply: false\n---\n\n{}\n",
+                    description_yaml.trim_end(),
+                    body
+                ),
+            )
+        }
+        other => {
+            return Err(DbErr::Custom(format!(
+                "Unsupported export format '{}' (expected 'claude' or 'cursor')",
+                other
+            )));
+        }
+    };
+
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| DbErr::Custom(format!("Failed to create export directory: {}", e)))?;
+    }
+
+    fs::write(&target_path, &exported)
+        .map_err(|e| DbErr::Custom(format!("Failed to write exported agent: {}", e)))?;
+
+    Ok(AgentExportDto {
+        format: format.to_string(),
+        file_path: target_path.to_string_lossy().to_string(),
+        content: exported,
+    })
+}
+
+// Helper to recursively collect .md files under the agents folder
+fn collect_agent_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), DbErr> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| DbErr::Custom(format!("Failed to read agents directory: {}", e)))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_agent_files(&path, files)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+// Helper to split and parse agent front matter; errors if the file isn't an agent
+fn parse_agent(content: &str) -> Result<ParsedAgent, String> {
+    let trimmed = content.trim_start();
+    if !trimmed.starts_with("---") {
+        return Err("Agent file has no front matter".to_string());
+    }
+
+    let after_first_delim = &trimmed[3..];
+    let end_pos = after_first_delim
+        .find("\n---")
+        .ok_or_else(|| "Agent front matter is not closed".to_string())?;
+    let front_matter_str = &after_first_delim[..end_pos];
+    let body = after_first_delim[end_pos + 4..]
+        .trim_start_matches('-')
+        .to_string();
+
+    let front_matter: serde_yaml::Value = serde_yaml::from_str(front_matter_str)
+        .map_err(|e| format!("Invalid front matter YAML: {}", e))?;
+
+    let get_str = |key: &str| -> Option<String> {
+        front_matter
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    if get_str("type").as_deref() != Some("agent") {
+        return Err("Front matter `type` must be `agent`".to_string());
+    }
+
+    // Tools may be a YAML list or a comma-separated string (Claude format)
+    let tools = match front_matter.get("tools") {
+        None | Some(serde_yaml::Value::Null) => Vec::new(),
+        Some(serde_yaml::Value::String(s)) => s
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        Some(serde_yaml::Value::Sequence(seq)) => {
+            let mut tools = Vec::new();
+            for item in seq {
+                match item.as_str() {
+                    Some(tool) => tools.push(tool.trim().to_string()),
+                    None => return Err("Every entry in `tools` must be a string".to_string()),
+                }
+            }
+            tools
+        }
+        Some(_) => return Err("`tools` must be a list or a comma-separated string".to_string()),
+    };
+
+    if front_matter.get("model").is_some_and(|v| !v.is_null() && !v.is_string()) {
+        return Err("`model` must be a string".to_string());
+    }
+
+    Ok(ParsedAgent {
+        id: get_str("id"),
+        name: get_str("alias"),
+        description: get_str("description"),
+        model: get_str("model"),
+        tools,
+        body,
+    })
+}
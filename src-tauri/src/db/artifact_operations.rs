@@ -0,0 +1,201 @@
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::library_resource;
+use crate::db::walkthrough_operations::slugify;
+use crate::library::utils::compute_content_hash;
+use chrono::Utc;
+use uuid::Uuid;
+use std::path::PathBuf;
+use std::fs;
+
+/// Artifact DTO returned by the kit/agent/diagram creation flows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactDto {
+    pub id: String,
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    pub name: String,
+    #[serde(rename = "artifactType")]
+    pub artifact_type: String,
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    #[serde(rename = "relativePath")]
+    pub relative_path: String,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+/// Create a new kit with file and DB registration
+pub async fn create_kit(
+    db: &DatabaseConnection,
+    project_id: String,
+    project_path: String,
+    name: String,
+    description: Option<String>,
+    tags: Vec<String>,
+) -> Result<ArtifactDto, DbErr> {
+    let mut front_matter = base_front_matter("kit", &name, description.as_deref(), &tags);
+    front_matter.insert("is_base".into(), false.into());
+    front_matter.insert("version".into(), 1.into());
+
+    let body = format!("# {}\n\n[Kit content goes here]\n", name);
+
+    create_artifact(db, project_id, project_path, "kit", "kits", "md", name, description, tags, front_matter, body).await
+}
+
+/// Create a new agent with file and DB registration
+pub async fn create_agent(
+    db: &DatabaseConnection,
+    project_id: String,
+    project_path: String,
+    name: String,
+    description: Option<String>,
+    tags: Vec<String>,
+    capabilities: Vec<String>,
+) -> Result<ArtifactDto, DbErr> {
+    let mut front_matter = base_front_matter("agent", &name, description.as_deref(), &tags);
+    front_matter.insert("version".into(), 1.into());
+    front_matter.insert(
+        "capabilities".into(),
+        serde_yaml::Value::Sequence(capabilities.into_iter().map(Into::into).collect()),
+    );
+
+    let body = format!("# {} Agent\n\n[Agent instructions go here]\n", name);
+
+    let artifact = create_artifact(db, project_id, project_path, "agent", "agents", "md", name, description, tags, front_matter, body).await?;
+
+    // Agents also get a typed record for validation/export
+    crate::db::agent_operations::register_agent_file(db, &artifact.project_id, &artifact.file_path).await?;
+
+    Ok(artifact)
+}
+
+/// Create a new mermaid diagram with file and DB registration
+pub async fn create_diagram(
+    db: &DatabaseConnection,
+    project_id: String,
+    project_path: String,
+    name: String,
+    description: Option<String>,
+    tags: Vec<String>,
+) -> Result<ArtifactDto, DbErr> {
+    let front_matter = base_front_matter("diagram", &name, description.as_deref(), &tags);
+
+    let body = "```mermaid\ngraph TD\n    A[Start] --> B[End]\n```\n".to_string();
+
+    create_artifact(db, project_id, project_path, "diagram", "diagrams", "mmd", name, description, tags, front_matter, body).await
+}
+
+// Helper to build the front matter fields shared by every artifact type
+pub(crate) fn base_front_matter(
+    artifact_type: &str,
+    name: &str,
+    description: Option<&str>,
+    tags: &[String],
+) -> serde_yaml::Mapping {
+    let mut front_matter = serde_yaml::Mapping::new();
+    front_matter.insert("id".into(), slugify(name).into());
+    front_matter.insert("alias".into(), name.into());
+    front_matter.insert("type".into(), artifact_type.into());
+    front_matter.insert(
+        "tags".into(),
+        serde_yaml::Value::Sequence(tags.iter().map(|t| t.as_str().into()).collect()),
+    );
+    front_matter.insert("description".into(), description.unwrap_or_default().into());
+    front_matter
+}
+
+/// Scaffold the artifact file under `.bluekit/{subdir}` and register it in library_resources
+#[allow(clippy::too_many_arguments)]
+async fn create_artifact(
+    db: &DatabaseConnection,
+    project_id: String,
+    project_path: String,
+    artifact_type: &str,
+    subdir: &str,
+    extension: &str,
+    name: String,
+    description: Option<String>,
+    tags: Vec<String>,
+    front_matter: serde_yaml::Mapping,
+    body: String,
+) -> Result<ArtifactDto, DbErr> {
+    let slug = slugify(&name);
+    if slug.is_empty() {
+        return Err(DbErr::Custom(format!("Invalid {} name: {}", artifact_type, name)));
+    }
+
+    let now = Utc::now().timestamp();
+    let file_name = format!("{}.{}", slug, extension);
+    let relative_path = PathBuf::from(".bluekit").join(subdir).join(&file_name);
+
+    // Create file path: {project_path}/.bluekit/{subdir}/{file_name}
+    let file_path = PathBuf::from(&project_path).join(&relative_path);
+
+    // Never clobber an existing artifact
+    if file_path.exists() {
+        return Err(DbErr::Custom(format!(
+            "A {} named '{}' already exists at {}",
+            artifact_type,
+            name,
+            file_path.display()
+        )));
+    }
+
+    // Ensure directory exists
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| DbErr::Custom(format!("Failed to create {} directory: {}", subdir, e)))?;
+    }
+
+    let yaml = serde_yaml::to_string(&front_matter)
+        .map_err(|e| DbErr::Custom(format!("Failed to serialize front matter: {}", e)))?;
+    let content = format!("---\n{}---\n\n{}", yaml, body);
+
+    fs::write(&file_path, &content)
+        .map_err(|e| DbErr::Custom(format!("Failed to create {} file: {}", artifact_type, e)))?;
+
+    let yaml_metadata = serde_json::to_string(&front_matter).ok();
+    let relative_path_str = relative_path.to_string_lossy().to_string();
+
+    // Create resource record
+    let resource_active_model = library_resource::ActiveModel {
+        id: Set(Uuid::new_v4().to_string()),
+        project_id: Set(project_id),
+        relative_path: Set(relative_path_str.clone()),
+        file_name: Set(file_name),
+        artifact_type: Set(artifact_type.to_string()),
+        content_hash: Set(Some(compute_content_hash(&content))),
+        yaml_metadata: Set(yaml_metadata),
+        created_at: Set(now),
+        updated_at: Set(now),
+        last_modified_at: Set(Some(now)),
+        is_deleted: Set(0),
+    };
+
+    let resource_model = match resource_active_model.insert(db).await {
+        Ok(model) => model,
+        Err(e) => {
+            // Roll back the scaffolded file so a retry doesn't hit "already exists"
+            let _ = fs::remove_file(&file_path);
+            return Err(e);
+        }
+    };
+
+    Ok(ArtifactDto {
+        id: resource_model.id,
+        project_id: resource_model.project_id,
+        name,
+        artifact_type: resource_model.artifact_type,
+        file_path: file_path.to_string_lossy().to_string(),
+        relative_path: relative_path_str,
+        description,
+        tags,
+        created_at: resource_model.created_at,
+        updated_at: resource_model.updated_at,
+    })
+}
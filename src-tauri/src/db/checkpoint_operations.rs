@@ -0,0 +1,297 @@
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::{checkpoint, project};
+use chrono::Utc;
+use std::path::Path;
+
+/// Valid values for `checkpoint_type`
+pub const CHECKPOINT_TYPES: &[&str] = &["milestone", "experiment", "template", "backup"];
+
+/// How `restore_checkpoint` creates the working copy: a fresh clone, or a
+/// worktree of the checkpoint's own project
+pub const RESTORE_MODES: &[&str] = &["clone", "worktree"];
+
+/// A commit to pin as a checkpoint
+#[derive(Debug, Clone)]
+pub struct NewCheckpoint {
+    pub project_id: String,
+    pub git_commit_sha: String,
+    pub git_branch: Option<String>,
+    pub git_url: Option<String>,
+    pub name: String,
+    pub checkpoint_type: String,
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+fn validate_type(checkpoint_type: &str) -> Result<(), DbErr> {
+    if !CHECKPOINT_TYPES.contains(&checkpoint_type) {
+        return Err(DbErr::Custom(format!(
+            "Invalid checkpoint type: {}. Must be one of: {}",
+            checkpoint_type,
+            CHECKPOINT_TYPES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<(), DbErr> {
+    if name.trim().is_empty() {
+        return Err(DbErr::Custom("Checkpoint name cannot be empty".to_string()));
+    }
+    Ok(())
+}
+
+// Tags are stored as a JSON array; no tags is stored as NULL
+fn tags_json(tags: Option<Vec<String>>) -> Option<String> {
+    tags.filter(|t| !t.is_empty())
+        .and_then(|t| serde_json::to_string(&t).ok())
+}
+
+/// Pin the project's current commit, as reported by git
+pub async fn create_checkpoint(
+    db: &DatabaseConnection,
+    project_id: &str,
+    name: String,
+    checkpoint_type: String,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<checkpoint::Model, DbErr> {
+    let project = project::Entity::find_by_id(project_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Project not found: {}", project_id)))?;
+
+    let git = crate::integrations::git::detect_git_metadata(&project.path)
+        .map_err(DbErr::Custom)?;
+
+    pin_commit(
+        db,
+        NewCheckpoint {
+            project_id: project.id,
+            git_commit_sha: git.latest_commit_sha,
+            git_branch: Some(git.current_branch),
+            git_url: Some(git.remote_url),
+            name,
+            checkpoint_type,
+            description,
+            tags,
+        },
+    )
+    .await
+}
+
+/// Pin a given commit. A commit can only be pinned once per project.
+pub async fn pin_commit(db: &DatabaseConnection, new: NewCheckpoint) -> Result<checkpoint::Model, DbErr> {
+    validate_name(&new.name)?;
+    validate_type(&new.checkpoint_type)?;
+
+    let existing = checkpoint::Entity::find()
+        .filter(checkpoint::Column::ProjectId.eq(&new.project_id))
+        .filter(checkpoint::Column::GitCommitSha.eq(&new.git_commit_sha))
+        .one(db)
+        .await?;
+    if existing.is_some() {
+        return Err(DbErr::Custom("This commit is already pinned as a checkpoint".to_string()));
+    }
+
+    let now = Utc::now().timestamp_millis();
+    let checkpoint = checkpoint::ActiveModel {
+        id: Set(format!("checkpoint-{}-{}", new.project_id, now)),
+        project_id: Set(new.project_id),
+        git_commit_sha: Set(new.git_commit_sha),
+        git_branch: Set(new.git_branch),
+        git_url: Set(new.git_url),
+        name: Set(new.name.trim().to_string()),
+        description: Set(new.description),
+        tags: Set(tags_json(new.tags)),
+        checkpoint_type: Set(new.checkpoint_type),
+        parent_checkpoint_id: Set(None), // Lineage tracking deferred to Phase 4
+        created_from_project_id: Set(None),
+        pinned_at: Set(now),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    checkpoint.insert(db).await
+}
+
+/// List a project's checkpoints, newest pin first
+pub async fn list_checkpoints(db: &DatabaseConnection, project_id: &str) -> Result<Vec<checkpoint::Model>, DbErr> {
+    checkpoint::Entity::find()
+        .filter(checkpoint::Column::ProjectId.eq(project_id))
+        .order_by_desc(checkpoint::Column::PinnedAt)
+        .all(db)
+        .await
+}
+
+/// Update a checkpoint's metadata. An empty description or tag list clears it.
+pub async fn update_checkpoint(
+    db: &DatabaseConnection,
+    checkpoint_id: &str,
+    name: Option<String>,
+    checkpoint_type: Option<String>,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<checkpoint::Model, DbErr> {
+    let checkpoint = checkpoint::Entity::find_by_id(checkpoint_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Checkpoint not found: {}", checkpoint_id)))?;
+
+    let mut active_model: checkpoint::ActiveModel = checkpoint.into();
+    if let Some(name) = name {
+        validate_name(&name)?;
+        active_model.name = Set(name.trim().to_string());
+    }
+    if let Some(checkpoint_type) = checkpoint_type {
+        validate_type(&checkpoint_type)?;
+        active_model.checkpoint_type = Set(checkpoint_type);
+    }
+    if let Some(description) = description {
+        active_model.description = Set(Some(description).filter(|d| !d.trim().is_empty()));
+    }
+    if let Some(tags) = tags {
+        active_model.tags = Set(tags_json(Some(tags)));
+    }
+    active_model.updated_at = Set(Utc::now().timestamp_millis());
+
+    active_model.update(db).await
+}
+
+/// Delete a checkpoint. The commit itself is untouched.
+pub async fn delete_checkpoint(db: &DatabaseConnection, checkpoint_id: &str) -> Result<(), DbErr> {
+    let result = checkpoint::Entity::delete_by_id(checkpoint_id).exec(db).await?;
+    if result.rows_affected == 0 {
+        return Err(DbErr::RecordNotFound(format!("Checkpoint not found: {}", checkpoint_id)));
+    }
+    Ok(())
+}
+
+/// A checkpoint restored into a new working copy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoredCheckpoint {
+    /// The project registered for the working copy
+    pub project: project::Model,
+    /// The new project's checkpoint at the restored commit
+    pub checkpoint: checkpoint::Model,
+}
+
+/// Check out a checkpoint's commit in `target` and register it as a project.
+///
+/// "clone" clones the checkpoint's git URL (or its project's directory when
+/// there is none); "worktree" adds a worktree to the project's repository.
+/// The new project gets its own checkpoint at the same commit, with the
+/// original as `parent_checkpoint_id` and the original project as
+/// `created_from_project_id`.
+pub async fn restore_checkpoint(
+    db: &DatabaseConnection,
+    checkpoint_id: &str,
+    target: &Path,
+    mode: &str,
+    project_name: Option<String>,
+    branch: Option<&str>,
+) -> Result<RestoredCheckpoint, DbErr> {
+    if !RESTORE_MODES.contains(&mode) {
+        return Err(DbErr::Custom(format!(
+            "Invalid restore mode: {}. Must be one of: {}",
+            mode,
+            RESTORE_MODES.join(", ")
+        )));
+    }
+    if target.exists() {
+        return Err(DbErr::Custom(format!("Target path already exists: {}", target.display())));
+    }
+
+    let source = checkpoint::Entity::find_by_id(checkpoint_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Checkpoint not found: {}", checkpoint_id)))?;
+    let source_project = project::Entity::find_by_id(&source.project_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Project not found: {}", source.project_id)))?;
+
+    let branch = branch.map(str::trim).filter(|b| !b.is_empty());
+    let git_result = if mode == "worktree" {
+        crate::integrations::git::add_worktree_at_commit(&source_project.path, target, &source.git_commit_sha, branch)
+    } else {
+        let origin = source.git_url.as_deref().unwrap_or(&source_project.path);
+        crate::integrations::git::clone_at_commit(origin, target, &source.git_commit_sha, branch)
+    };
+    git_result.map_err(DbErr::Custom)?;
+
+    let path = crate::core::paths::to_ipc(target);
+    let name = project_name
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| format!("{} ({})", source_project.name, source.name));
+    let created = crate::db::project_operations::create_project(
+        db,
+        &name,
+        &path,
+        Some(format!("Restored from checkpoint: {}", source.name)),
+        None,
+        None,
+    )
+    .await?;
+    let project = crate::db::project_operations::update_project_git_info(db, &created.id).await?;
+
+    let now = Utc::now().timestamp_millis();
+    let checkpoint = checkpoint::ActiveModel {
+        id: Set(format!("checkpoint-{}-{}", project.id, now)),
+        project_id: Set(project.id.clone()),
+        git_commit_sha: Set(source.git_commit_sha.clone()),
+        git_branch: Set(branch.map(str::to_string).or(source.git_branch.clone())),
+        git_url: Set(source.git_url.clone()),
+        name: Set(source.name.clone()),
+        description: Set(source.description.clone()),
+        tags: Set(source.tags.clone()),
+        checkpoint_type: Set(source.checkpoint_type.clone()),
+        parent_checkpoint_id: Set(Some(source.id.clone())),
+        created_from_project_id: Set(Some(source.project_id.clone())),
+        pinned_at: Set(now),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(RestoredCheckpoint { project, checkpoint })
+}
+
+/// Files changed between two checkpoints of the same project, or between a
+/// checkpoint and the project's current HEAD when `to_checkpoint_id` is none
+pub async fn diff_checkpoints(
+    db: &DatabaseConnection,
+    from_checkpoint_id: &str,
+    to_checkpoint_id: Option<&str>,
+) -> Result<crate::integrations::git::GitDiffSummary, DbErr> {
+    let find = |id: &str| {
+        let id = id.to_string();
+        async move {
+            checkpoint::Entity::find_by_id(&id)
+                .one(db)
+                .await?
+                .ok_or_else(|| DbErr::RecordNotFound(format!("Checkpoint not found: {}", id)))
+        }
+    };
+
+    let from = find(from_checkpoint_id).await?;
+    let to_sha = match to_checkpoint_id {
+        Some(to_id) => {
+            let to = find(to_id).await?;
+            if to.project_id != from.project_id {
+                return Err(DbErr::Custom("Checkpoints belong to different projects".to_string()));
+            }
+            to.git_commit_sha
+        }
+        None => "HEAD".to_string(),
+    };
+
+    let project = project::Entity::find_by_id(&from.project_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Project not found: {}", from.project_id)))?;
+
+    crate::integrations::git::diff_commits(&project.path, &from.git_commit_sha, &to_sha).map_err(DbErr::Custom)
+}
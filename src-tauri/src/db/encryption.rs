@@ -0,0 +1,318 @@
+//! Opt-in SQLCipher encryption of the database.
+//!
+//! An encrypted database is told apart from a plain one by its file header,
+//! so there is no setting that can disagree with the file. The key is
+//! random, kept in the OS keychain, and applied to every pooled connection
+//! when the database is opened.
+//!
+//! The file can't be converted under the open pool, so enabling (or
+//! disabling) encryption only records the request; [`apply_pending`]
+//! converts the database on the next launch, before the pool opens, so
+//! nothing written in between is lost. Locking closes the pool; unlocking
+//! reads the key from the keychain again and restarts the app.
+//!
+//! Encryption needs a build with the `sqlcipher` feature, which links
+//! SQLCipher in place of SQLite.
+
+use crate::integrations::github::keychain::KeychainManager;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, Statement};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+/// First bytes of an unencrypted SQLite file
+const PLAIN_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Name the converted copy is attached under while it's written
+const EXPORT_SCHEMA: &str = "converted";
+
+/// Contents of the request file
+const ENCRYPT: &str = "encrypt";
+const DECRYPT: &str = "decrypt";
+
+static LOCKED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptionStatus {
+    /// Whether this build's SQLite is SQLCipher
+    pub available: bool,
+    /// Whether the database file is encrypted
+    pub encrypted: bool,
+    /// The database is converted on the next launch
+    #[serde(rename = "pendingRestart")]
+    pub pending_restart: bool,
+    /// The database is closed until it is unlocked
+    pub locked: bool,
+}
+
+/// Whether the file at `db_path` is encrypted. A missing or empty file is
+/// created unencrypted.
+pub fn is_encrypted(db_path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    match std::fs::File::open(db_path).and_then(|mut file| file.read_exact(&mut header)) {
+        Ok(()) => &header != PLAIN_HEADER,
+        Err(_) => false,
+    }
+}
+
+/// Whether the database was locked this session
+pub fn is_locked() -> bool {
+    LOCKED.load(Ordering::SeqCst)
+}
+
+/// The database key from the keychain
+pub fn database_key() -> Result<String, String> {
+    KeychainManager::new()
+        .and_then(|keychain| keychain.retrieve_database_key())
+        .map_err(|e| format!("Failed to read the database key from the keychain: {}", e))
+}
+
+/// `key` written as a raw key for `PRAGMA key` and `ATTACH ... KEY`
+pub fn key_pragma(key: &str) -> String {
+    format!("\"x'{}'\"", key)
+}
+
+/// Carries out a conversion requested by [`enable_encryption`] or
+/// [`disable_encryption`]. Call before opening the database.
+///
+/// The database is checkpointed, exported into a converted copy and the
+/// copy renamed over it. On failure the database is left as it was and the
+/// conversion is retried on the next launch.
+pub async fn apply_pending(db_path: &Path) {
+    let request = request_path(db_path);
+    let Ok(wanted) = std::fs::read_to_string(&request) else {
+        return;
+    };
+    let encrypt = wanted.trim() == ENCRYPT;
+    if encrypt == is_encrypted(db_path) {
+        let _ = std::fs::remove_file(&request);
+        return;
+    }
+
+    if let Err(e) = convert(db_path, encrypt).await {
+        warn!("Failed to {} the database; retrying on the next launch: {}", wanted.trim(), e);
+        let _ = std::fs::remove_file(pending_path(db_path));
+        return;
+    }
+    let _ = std::fs::remove_file(&request);
+
+    if encrypt {
+        info!("Database is now encrypted");
+    } else {
+        info!("Database is now decrypted");
+        forget_key();
+    }
+}
+
+/// Whether the linked SQLite is SQLCipher
+pub async fn cipher_available(db: &DatabaseConnection) -> Result<bool, DbErr> {
+    // Plain SQLite ignores the unknown pragma and returns no rows
+    let row = db
+        .query_one(Statement::from_string(
+            db.get_database_backend(),
+            "PRAGMA cipher_version".to_string(),
+        ))
+        .await?;
+    match row {
+        Some(row) => Ok(!row.try_get_by_index::<String>(0)?.is_empty()),
+        None => Ok(false),
+    }
+}
+
+pub async fn status(db: &DatabaseConnection) -> Result<EncryptionStatus, DbErr> {
+    let db_path = super::get_db_path().map_err(DbErr::Custom)?;
+    // Only an encrypted database can be locked, and its pool is closed
+    let locked = is_locked();
+    Ok(EncryptionStatus {
+        available: locked || cipher_available(db).await?,
+        encrypted: is_encrypted(&db_path),
+        pending_restart: request_path(&db_path).exists(),
+        locked,
+    })
+}
+
+/// Stores a new key in the keychain; the database is encrypted with it on
+/// the next launch. Cancels a pending decryption.
+pub async fn enable_encryption(db: &DatabaseConnection) -> Result<EncryptionStatus, DbErr> {
+    let db_path = super::get_db_path().map_err(DbErr::Custom)?;
+    if is_encrypted(&db_path) {
+        if !cancel_request(&db_path)? {
+            return Err(DbErr::Custom("Database is already encrypted".to_string()));
+        }
+        return status(db).await;
+    }
+    if !cipher_available(db).await? {
+        return Err(DbErr::Custom(
+            "Database encryption needs a build with SQLCipher (the `sqlcipher` feature)".to_string(),
+        ));
+    }
+    if request_path(&db_path).exists() {
+        return status(db).await;
+    }
+
+    let key = new_key();
+    KeychainManager::new()
+        .and_then(|keychain| keychain.store_database_key(&key))
+        .map_err(|e| DbErr::Custom(format!("Failed to store the database key in the keychain: {}", e)))?;
+    request(&db_path, ENCRYPT)?;
+
+    info!("Database will be encrypted on the next launch");
+    status(db).await
+}
+
+/// Requests decryption on the next launch; the key is removed from the
+/// keychain then. Cancels a pending encryption.
+pub async fn disable_encryption(db: &DatabaseConnection) -> Result<EncryptionStatus, DbErr> {
+    let db_path = super::get_db_path().map_err(DbErr::Custom)?;
+    if !is_encrypted(&db_path) {
+        if !cancel_request(&db_path)? {
+            return Err(DbErr::Custom("Database is not encrypted".to_string()));
+        }
+        forget_key();
+        return status(db).await;
+    }
+
+    request(&db_path, DECRYPT)?;
+
+    info!("Database will be decrypted on the next launch");
+    status(db).await
+}
+
+/// Closes the database. Every command that needs it fails until
+/// [`unlock`] is called.
+pub async fn lock(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let db_path = super::get_db_path().map_err(DbErr::Custom)?;
+    if !is_encrypted(&db_path) {
+        return Err(DbErr::Custom("Only an encrypted database can be locked".to_string()));
+    }
+    if LOCKED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    db.close_by_ref().await?;
+    info!("Database locked");
+    Ok(())
+}
+
+/// Reads the key from the keychain, which may ask the user to allow it,
+/// and restarts the app to reopen the database with it.
+pub fn unlock(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    if !is_locked() {
+        return Err("Database is not locked".to_string());
+    }
+    database_key()?;
+    info!("Database unlocked; restarting");
+    app_handle.restart();
+    Ok(())
+}
+
+// Converts the closed database at `db_path`: checkpoints it, exports it
+// into the pending copy (encrypted with the keychain key, or not) and
+// renames the copy over it
+async fn convert(db_path: &Path, encrypt: bool) -> Result<(), String> {
+    use sqlx::{ConnectOptions, Connection};
+
+    let key = key_pragma(&database_key()?);
+    let (source_key, target_key) = if encrypt {
+        (None, key.as_str())
+    } else {
+        (Some(key.clone()), "''")
+    };
+
+    let mut options = sqlx::sqlite::SqliteConnectOptions::new().filename(db_path);
+    if let Some(source_key) = source_key {
+        options = options.pragma("key", source_key);
+    }
+    let mut conn = options
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to open the database: {}", e))?;
+
+    let pending = pending_path(db_path);
+    if pending.exists() {
+        std::fs::remove_file(&pending)
+            .map_err(|e| format!("Failed to remove {}: {}", pending.display(), e))?;
+    }
+
+    // Committed transactions still in the WAL go into the file first
+    let statements = [
+        "PRAGMA wal_checkpoint(TRUNCATE)".to_string(),
+        format!(
+            "ATTACH DATABASE '{}' AS {} KEY {}",
+            pending.to_string_lossy().replace('\'', "''"),
+            EXPORT_SCHEMA,
+            target_key
+        ),
+        format!("SELECT sqlcipher_export('{}')", EXPORT_SCHEMA),
+        format!("DETACH DATABASE {}", EXPORT_SCHEMA),
+    ];
+    for statement in &statements {
+        if let Err(e) = sqlx::query(statement).execute(&mut conn).await {
+            let _ = conn.close().await;
+            return Err(format!("Failed to export the database: {}", e));
+        }
+    }
+    conn.close()
+        .await
+        .map_err(|e| format!("Failed to close the database: {}", e))?;
+
+    std::fs::rename(&pending, db_path)
+        .map_err(|e| format!("Failed to replace the database with its converted copy: {}", e))?;
+
+    // The old file's journal would otherwise be replayed into the new one;
+    // it was checkpointed, so nothing is lost
+    for suffix in ["-wal", "-shm", "-journal"] {
+        let sidecar = with_suffix(db_path, suffix);
+        if sidecar.exists() {
+            if let Err(e) = std::fs::remove_file(&sidecar) {
+                warn!("Failed to remove {}: {}", sidecar.display(), e);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Records that the database should be converted on the next launch
+fn request(db_path: &Path, wanted: &str) -> Result<(), DbErr> {
+    std::fs::write(request_path(db_path), wanted)
+        .map_err(|e| DbErr::Custom(format!("Failed to record the encryption change: {}", e)))
+}
+
+// Removes a pending conversion request; true if there was one
+fn cancel_request(db_path: &Path) -> Result<bool, DbErr> {
+    let request = request_path(db_path);
+    if !request.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_file(&request)
+        .map_err(|e| DbErr::Custom(format!("Failed to remove {}: {}", request.display(), e)))?;
+    Ok(true)
+}
+
+// Removes the key once nothing is encrypted with it
+fn forget_key() {
+    if let Err(e) = KeychainManager::new().and_then(|keychain| keychain.delete_database_key()) {
+        warn!("Failed to remove the database key from the keychain: {}", e);
+    }
+}
+
+// A random 256-bit key, hex-encoded
+fn new_key() -> String {
+    let bytes: [u8; 32] = rand::random();
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn pending_path(db_path: &Path) -> PathBuf {
+    with_suffix(db_path, ".pending")
+}
+
+fn request_path(db_path: &Path) -> PathBuf {
+    with_suffix(db_path, ".convert")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
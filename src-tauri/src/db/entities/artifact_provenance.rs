@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "artifact_provenance")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    #[serde(rename = "localPath")]
+    pub local_path: String,
+    #[serde(rename = "sourceKind")]
+    pub source_kind: String, // 'local' (copied from a file) or 'library' (pulled variation)
+    #[serde(rename = "sourcePath")]
+    pub source_path: Option<String>,
+    #[serde(rename = "workspaceId")]
+    pub workspace_id: Option<String>,
+    #[serde(rename = "catalogId")]
+    pub catalog_id: Option<String>,
+    #[serde(rename = "variationId")]
+    pub variation_id: Option<String>,
+    #[serde(rename = "contentHash")]
+    pub content_hash: String, // Hash of the content at copy/pull time
+    #[serde(rename = "copiedAt")]
+    pub copied_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
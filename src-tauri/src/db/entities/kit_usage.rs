@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "kit_usage")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    #[serde(rename = "kitPath")]
+    pub kit_path: String,
+    #[serde(rename = "targetProjectPath")]
+    pub target_project_path: String,
+    #[serde(rename = "targetProjectId")]
+    pub target_project_id: Option<String>,
+    #[serde(rename = "targetFilePath")]
+    pub target_file_path: String,
+    #[serde(rename = "contentHash")]
+    pub content_hash: String, // Hash of the kit content at copy time
+    #[serde(rename = "copiedAt")]
+    pub copied_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
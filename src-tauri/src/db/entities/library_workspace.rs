@@ -16,13 +16,13 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
-    #[sea_orm(has_many = "super::library_artifact::Entity")]
-    LibraryArtifacts,
+    #[sea_orm(has_many = "super::library_catalog::Entity")]
+    LibraryCatalogs,
 }
 
-impl Related<super::library_artifact::Entity> for Entity {
+impl Related<super::library_catalog::Entity> for Entity {
     fn to() -> RelationDef {
-        Relation::LibraryArtifacts.def()
+        Relation::LibraryCatalogs.def()
     }
 }
 
@@ -3,8 +3,9 @@
 
 pub mod task;
 pub mod task_project;
+pub mod task_activity;
+pub mod saved_view;
 pub mod library_workspace;
-pub mod library_artifact;
 pub mod library_catalog;
 pub mod library_variation;
 pub mod library_subscription;
@@ -20,3 +21,10 @@ pub mod plan_link;
 pub mod walkthrough;
 pub mod walkthrough_takeaway;
 pub mod walkthrough_note;
+pub mod walkthrough_step;
+pub mod walkthrough_takeaway_task;
+pub mod agent;
+pub mod kit_usage;
+pub mod artifact_provenance;
+pub mod scheduled_job;
+pub mod session_state;
@@ -19,6 +19,8 @@ pub struct Model {
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
     pub updated_at: i64,
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: Option<i64>, // Set when soft-deleted
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
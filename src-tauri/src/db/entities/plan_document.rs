@@ -20,6 +20,8 @@ pub struct Model {
     pub updated_at: i64,
     #[serde(rename = "orderIndex")]
     pub order_index: i32,
+    #[serde(rename = "phaseOrderIndex")]
+    pub phase_order_index: i32, // Position among the documents of the same phase
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
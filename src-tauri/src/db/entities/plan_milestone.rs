@@ -15,6 +15,8 @@ pub struct Model {
     pub completed: i32, // SQLite boolean (0 or 1)
     #[serde(rename = "completedAt")]
     pub completed_at: Option<i64>,
+    #[serde(rename = "dueAt")]
+    pub due_at: Option<i64>, // Unix seconds
     #[serde(rename = "createdAt")]
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
@@ -17,6 +17,8 @@ pub struct Model {
     pub started_at: Option<i64>,
     #[serde(rename = "completedAt")]
     pub completed_at: Option<i64>,
+    #[serde(rename = "targetDate")]
+    pub target_date: Option<i64>, // When the phase should be done (Unix seconds)
     #[serde(rename = "createdAt")]
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
@@ -37,6 +37,15 @@ pub struct Model {
     #[serde(rename = "isVault")]
     #[sea_orm(default_value = "false")]
     pub is_vault: bool,
+
+    // Position in the user's manual ordering; None sorts after ordered
+    // projects, oldest first
+    #[serde(rename = "order")]
+    pub sort_order: Option<i64>,
+
+    // Archived projects are hidden from pickers but keep their history
+    #[sea_orm(default_value = "false")]
+    pub archived: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,20 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "session_state")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String, // Always "current"; the table holds a single row
+    pub active_project_id: Option<String>,
+    pub open_artifact_paths: String, // JSON array of absolute paths, in tab order
+    pub active_artifact_path: Option<String>,
+    pub selected_plan_id: Option<String>,
+    pub selected_walkthrough_id: Option<String>,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -17,6 +17,9 @@ pub struct Model {
     #[sea_orm(column_name = "type")]
     #[serde(rename = "type")]
     pub type_: Option<String>, // Optional: "bug", "investigation", "feature", "cleanup", "optimization", "chore"
+    pub due_at: Option<String>,    // Optional RFC 3339 UTC timestamp
+    pub remind_at: Option<String>, // Optional RFC 3339 UTC timestamp, cleared once the reminder fires
+    pub deleted_at: Option<String>, // RFC 3339 UTC timestamp, set when soft-deleted
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -9,6 +9,7 @@ pub struct Model {
     pub id: i32,
     pub task_id: String,
     pub project_id: String,
+    pub order_index: Option<i32>, // Manual position within the task's status column on this project's board
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -17,6 +17,8 @@ pub struct Model {
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
     pub updated_at: i64,
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: Option<i64>, // Set when soft-deleted
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -25,6 +27,8 @@ pub enum Relation {
     Takeaways,
     #[sea_orm(has_many = "super::walkthrough_note::Entity")]
     Notes,
+    #[sea_orm(has_many = "super::walkthrough_step::Entity")]
+    Steps,
 }
 
 impl Related<super::walkthrough_takeaway::Entity> for Entity {
@@ -39,4 +43,10 @@ impl Related<super::walkthrough_note::Entity> for Entity {
     }
 }
 
+impl Related<super::walkthrough_step::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Steps.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
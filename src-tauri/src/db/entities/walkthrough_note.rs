@@ -9,6 +9,8 @@ pub struct Model {
     #[serde(rename = "walkthroughId")]
     pub walkthrough_id: String,
     pub content: String,
+    /// "text" or "markdown"
+    pub format: String,
     #[serde(rename = "createdAt")]
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "walkthrough_steps")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    #[serde(rename = "walkthroughId")]
+    pub walkthrough_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(rename = "filePath")]
+    pub file_path: String, // Relative to the project root, `/`-separated
+    #[serde(rename = "startLine")]
+    pub start_line: i32, // 1-based, inclusive
+    #[serde(rename = "endLine")]
+    pub end_line: i32,
+    #[serde(rename = "commitSha")]
+    pub commit_sha: Option<String>, // Commit the line range refers to
+    #[serde(rename = "sortOrder")]
+    pub sort_order: i32,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::walkthrough::Entity",
+        from = "Column::WalkthroughId",
+        to = "super::walkthrough::Column::Id"
+    )]
+    Walkthrough,
+}
+
+impl Related<super::walkthrough::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Walkthrough.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Junction table linking a takeaway to the task made from it
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "walkthrough_takeaway_tasks")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    #[serde(rename = "takeawayId")]
+    pub takeaway_id: String,
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::walkthrough_takeaway::Entity",
+        from = "Column::TakeawayId",
+        to = "super::walkthrough_takeaway::Column::Id"
+    )]
+    Takeaway,
+    #[sea_orm(
+        belongs_to = "super::task::Entity",
+        from = "Column::TaskId",
+        to = "super::task::Column::Id"
+    )]
+    Task,
+}
+
+impl Related<super::walkthrough_takeaway::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Takeaway.def()
+    }
+}
+
+impl Related<super::task::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Task.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
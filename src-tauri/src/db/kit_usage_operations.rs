@@ -0,0 +1,107 @@
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::{kit_usage, project};
+use crate::library::utils::compute_content_hash;
+use chrono::Utc;
+use uuid::Uuid;
+use std::path::Path;
+use std::fs;
+
+/// Kit usage DTO: one copy of a kit in a target project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KitUsageDto {
+    pub id: String,
+    #[serde(rename = "kitPath")]
+    pub kit_path: String,
+    #[serde(rename = "targetProjectPath")]
+    pub target_project_path: String,
+    #[serde(rename = "targetProjectId")]
+    pub target_project_id: Option<String>,
+    #[serde(rename = "targetFilePath")]
+    pub target_file_path: String,
+    #[serde(rename = "contentHash")]
+    pub content_hash: String,
+    #[serde(rename = "copiedAt")]
+    pub copied_at: i64,
+    /// The copy no longer exists on disk
+    #[serde(rename = "targetMissing")]
+    pub target_missing: bool,
+    /// The source kit has been edited since this copy was made
+    #[serde(rename = "sourceChanged")]
+    pub source_changed: bool,
+}
+
+/// Record that a kit was copied into a project (re-copies refresh the existing row)
+pub async fn record_kit_usage(
+    db: &DatabaseConnection,
+    kit_path: &str,
+    target_project_path: &str,
+    target_file_path: &str,
+    content_hash: &str,
+) -> Result<(), DbErr> {
+    let now = Utc::now().timestamp();
+    let content_hash = content_hash.to_string();
+
+    // Link to the registered project when there is one
+    let target_project_id = project::Entity::find()
+        .filter(project::Column::Path.eq(target_project_path))
+        .one(db)
+        .await?
+        .map(|p| p.id);
+
+    let existing = kit_usage::Entity::find()
+        .filter(kit_usage::Column::KitPath.eq(kit_path))
+        .filter(kit_usage::Column::TargetFilePath.eq(target_file_path))
+        .one(db)
+        .await?;
+
+    if let Some(existing) = existing {
+        let mut usage_active: kit_usage::ActiveModel = existing.into();
+        usage_active.target_project_id = Set(target_project_id);
+        usage_active.content_hash = Set(content_hash);
+        usage_active.copied_at = Set(now);
+        usage_active.update(db).await?;
+    } else {
+        let usage_active = kit_usage::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            kit_path: Set(kit_path.to_string()),
+            target_project_path: Set(target_project_path.to_string()),
+            target_project_id: Set(target_project_id),
+            target_file_path: Set(target_file_path.to_string()),
+            content_hash: Set(content_hash),
+            copied_at: Set(now),
+        };
+        usage_active.insert(db).await?;
+    }
+
+    Ok(())
+}
+
+/// Get every recorded copy of a kit, most recent first
+pub async fn get_kit_usage(
+    db: &DatabaseConnection,
+    kit_path: &str,
+) -> Result<Vec<KitUsageDto>, DbErr> {
+    let usages: Vec<kit_usage::Model> = kit_usage::Entity::find()
+        .filter(kit_usage::Column::KitPath.eq(kit_path))
+        .order_by_desc(kit_usage::Column::CopiedAt)
+        .all(db)
+        .await?;
+
+    // If the kit itself can't be read, we can't tell whether copies are stale
+    let current_hash = fs::read_to_string(kit_path)
+        .ok()
+        .map(|content| compute_content_hash(&content));
+
+    Ok(usages.into_iter().map(|u| KitUsageDto {
+        target_missing: !Path::new(&u.target_file_path).exists(),
+        source_changed: current_hash.as_deref().is_some_and(|h| h != u.content_hash),
+        id: u.id,
+        kit_path: u.kit_path,
+        target_project_path: u.target_project_path,
+        target_project_id: u.target_project_id,
+        target_file_path: u.target_file_path,
+        content_hash: u.content_hash,
+        copied_at: u.copied_at,
+    }).collect())
+}
@@ -0,0 +1,163 @@
+//! Database upkeep: integrity check, `VACUUM` and `ANALYZE`.
+//!
+//! Runs on demand through `db_maintenance`, and once at startup when the
+//! database has grown past [`AUTO_MAINTENANCE_MIN_BYTES`] with enough free
+//! pages for a vacuum to be worth the wait.
+
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Size above which startup maintenance is considered
+pub const AUTO_MAINTENANCE_MIN_BYTES: i64 = 50 * 1024 * 1024;
+
+/// Share of free pages (in percent) that makes startup maintenance run
+const AUTO_MAINTENANCE_MIN_FREE_PERCENT: i64 = 10;
+
+/// Delay before startup maintenance, so it doesn't compete with app launch
+const STARTUP_DELAY_SECONDS: u64 = 60;
+
+/// How often startup maintenance checks again while the database is locked
+const LOCKED_TICK_SECONDS: u64 = 30;
+
+/// Integrity problems included in a report
+const MAX_INTEGRITY_MESSAGES: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbHealthReport {
+    /// Whether `PRAGMA integrity_check` reported "ok"
+    #[serde(rename = "integrityOk")]
+    pub integrity_ok: bool,
+    /// Problems found by the integrity check (empty when ok)
+    #[serde(rename = "integrityMessages")]
+    pub integrity_messages: Vec<String>,
+    /// Skipped when the integrity check fails, so a damaged file isn't rewritten
+    pub vacuumed: bool,
+    pub analyzed: bool,
+    #[serde(rename = "sizeBeforeBytes")]
+    pub size_before_bytes: i64,
+    #[serde(rename = "sizeAfterBytes")]
+    pub size_after_bytes: i64,
+    /// Unused pages before the vacuum
+    #[serde(rename = "freePagesBefore")]
+    pub free_pages_before: i64,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u128,
+}
+
+/// Database size and unused space, from SQLite's page counts
+#[derive(Debug, Clone, Copy)]
+pub struct DbSize {
+    pub bytes: i64,
+    pub free_pages: i64,
+    pub page_count: i64,
+}
+
+/// Current size of the database
+pub async fn db_size(db: &DatabaseConnection) -> Result<DbSize, DbErr> {
+    let page_count = pragma_i64(db, "page_count").await?;
+    let page_size = pragma_i64(db, "page_size").await?;
+    let free_pages = pragma_i64(db, "freelist_count").await?;
+    Ok(DbSize {
+        bytes: page_count * page_size,
+        free_pages,
+        page_count,
+    })
+}
+
+/// Runs the integrity check, then `VACUUM` and `ANALYZE` if it passed.
+pub async fn run_maintenance(db: &DatabaseConnection) -> Result<DbHealthReport, DbErr> {
+    let started = Instant::now();
+    let before = db_size(db).await?;
+
+    let rows = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            format!("PRAGMA integrity_check({})", MAX_INTEGRITY_MESSAGES),
+        ))
+        .await?;
+    let mut integrity_messages = Vec::new();
+    for row in rows {
+        integrity_messages.push(row.try_get_by_index::<String>(0)?);
+    }
+    let integrity_ok = integrity_messages.len() == 1 && integrity_messages[0] == "ok";
+    if integrity_ok {
+        integrity_messages.clear();
+    } else {
+        warn!("Database integrity check failed: {}", integrity_messages.join("; "));
+    }
+
+    let mut report = DbHealthReport {
+        integrity_ok,
+        integrity_messages,
+        vacuumed: false,
+        analyzed: false,
+        size_before_bytes: before.bytes,
+        size_after_bytes: before.bytes,
+        free_pages_before: before.free_pages,
+        duration_ms: 0,
+    };
+
+    if integrity_ok {
+        execute(db, "VACUUM").await?;
+        report.vacuumed = true;
+        execute(db, "ANALYZE").await?;
+        report.analyzed = true;
+        report.size_after_bytes = db_size(db).await?.bytes;
+    }
+
+    report.duration_ms = started.elapsed().as_millis();
+    info!(
+        "Database maintenance finished in {} ms: {} -> {} bytes",
+        report.duration_ms, report.size_before_bytes, report.size_after_bytes
+    );
+    Ok(report)
+}
+
+/// Schedules maintenance shortly after startup if the database is large
+/// and has enough free space to reclaim. Waits for an encrypted database to
+/// be unlocked first. Call once during app setup.
+pub fn start_if_needed(db: DatabaseConnection) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(STARTUP_DELAY_SECONDS)).await;
+        let mut ticker = tokio::time::interval(Duration::from_secs(LOCKED_TICK_SECONDS));
+        loop {
+            ticker.tick().await;
+            if !crate::db::encryption::is_locked() {
+                break;
+            }
+        }
+
+        let size = match db_size(&db).await {
+            Ok(size) => size,
+            Err(e) => {
+                warn!("Failed to read database size: {}", e);
+                return;
+            }
+        };
+        if size.bytes < AUTO_MAINTENANCE_MIN_BYTES
+            || size.free_pages * 100 < size.page_count * AUTO_MAINTENANCE_MIN_FREE_PERCENT
+        {
+            return;
+        }
+
+        info!("Database is {} bytes with {} free pages; running maintenance", size.bytes, size.free_pages);
+        if let Err(e) = run_maintenance(&db).await {
+            warn!("Startup database maintenance failed: {}", e);
+        }
+    });
+}
+
+async fn pragma_i64(db: &DatabaseConnection, pragma: &str) -> Result<i64, DbErr> {
+    db.query_one(Statement::from_string(db.get_database_backend(), format!("PRAGMA {}", pragma)))
+        .await?
+        .ok_or_else(|| DbErr::Custom(format!("PRAGMA {} returned no rows", pragma)))?
+        .try_get_by_index(0)
+}
+
+async fn execute(db: &DatabaseConnection, sql: &str) -> Result<(), DbErr> {
+    db.execute(Statement::from_string(db.get_database_backend(), sql.to_string()))
+        .await?;
+    Ok(())
+}
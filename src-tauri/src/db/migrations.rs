@@ -1,63 +1,470 @@
-use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, Statement};
+//! Versioned schema migrations.
+//!
+//! Migrations run in order through `sea-orm-migration`, which records the
+//! applied ones in `seaql_migrations`. Add schema changes as a new
+//! migration at the end of [`Migrator::migrations`]; never edit one that
+//! has shipped.
+//!
+//! The first migrations predate versioning and may find their tables
+//! already in place, so their steps are idempotent (`IF NOT EXISTS`,
+//! `pragma_table_info` checks). Later migrations can assume the schema left
+//! by the ones before them.
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, Statement, TransactionTrait};
+use sea_orm_migration::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::library::utils::compute_content_hash;
+use uuid::Uuid;
 use tracing::info;
 
+/// Applies any pending migrations
 pub async fn run_migrations(db: &DatabaseConnection) -> Result<(), DbErr> {
-    // Create tasks table
-    create_tasks_table(db).await?;
+    let pending = Migrator::get_pending_migrations(db).await?.len();
+    if pending > 0 {
+        info!("Applying {} database migration(s)", pending);
+    }
+    Migrator::up(db, None).await
+}
 
-    // Create task_projects junction table
-    create_task_projects_table(db).await?;
+/// Schema version DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaVersion {
+    /// Name of the last applied migration
+    pub version: Option<String>,
+    /// Number of applied migrations
+    pub applied: usize,
+    /// Migrations this build knows about that haven't run
+    pub pending: Vec<String>,
+    /// Name of the newest migration in this build
+    pub latest: Option<String>,
+}
 
-    // Add status and complexity columns to tasks table
-    add_task_status_and_complexity_columns(db).await?;
+/// The database's current schema version
+pub async fn schema_version(db: &DatabaseConnection) -> Result<SchemaVersion, DbErr> {
+    let applied = Migrator::get_applied_migrations(db).await?;
+    let pending = Migrator::get_pending_migrations(db).await?;
+    Ok(SchemaVersion {
+        version: applied.last().map(|m| m.name().to_string()),
+        applied: applied.len(),
+        pending: pending.iter().map(|m| m.name().to_string()).collect(),
+        latest: Migrator::migrations().last().map(|m| m.name().to_string()),
+    })
+}
 
-    // Add type column to tasks table
-    add_task_type_column(db).await?;
+pub struct Migrator;
+
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(CreateTasks),
+            Box::new(CreateLibrary),
+            Box::new(CreateProjects),
+            Box::new(CreatePlans),
+            Box::new(CreateWalkthroughs),
+            Box::new(CreateAgents),
+            Box::new(CreateKitUsage),
+            Box::new(CreateArtifactProvenance),
+            Box::new(CreateScheduledJobs),
+            Box::new(CreateSessionState),
+            Box::new(CreateTasksFts),
+            Box::new(AddTaskDueDates),
+            Box::new(CreateTaskActivity),
+            Box::new(AddTaskBoardOrder),
+            Box::new(CreateSavedViews),
+            Box::new(AddSoftDelete),
+            Box::new(AddPlanSchedule),
+            Box::new(AddPlanDocumentPhaseOrder),
+            Box::new(CreateWalkthroughSteps),
+            Box::new(AddWalkthroughNoteFormat),
+            Box::new(CreateWalkthroughTakeawayTasks),
+            Box::new(KeyTasksFtsByTaskId),
+        ]
+    }
+}
 
-    // Create library tables
-    create_library_workspaces_table(db).await?;
-    create_library_artifacts_table(db).await?;
-    create_library_resources_table(db).await?;
+// Runs each statement in `sql`
+async fn execute<C: ConnectionTrait>(db: &C, sql: &str) -> Result<(), DbErr> {
+    db.execute(Statement::from_string(db.get_database_backend(), sql.to_string()))
+        .await?;
+    Ok(())
+}
 
-    // Migrate library schema (Phase 2)
-    migrate_library_artifacts_to_catalogs(db).await?;
-    create_library_variations_table(db).await?;
-    create_library_subscriptions_table(db).await?;
+// Drops `tables` in order
+async fn drop_tables<C: ConnectionTrait>(db: &C, tables: &[&str]) -> Result<(), DbErr> {
+    for table in tables {
+        execute(db, &format!("DROP TABLE IF EXISTS {}", table)).await?;
+    }
+    Ok(())
+}
 
-    // Library collections (Phase 3)
-    create_library_collections_table(db).await?;
-    create_library_collection_catalogs_table(db).await?;
-    add_collection_description_and_tags(db).await?;
+struct CreateTasks;
 
-    // Add pinned field to library_workspaces
-    add_library_workspaces_pinned_field(db).await?;
+impl MigrationName for CreateTasks {
+    fn name(&self) -> &str {
+        "m0001_create_tasks"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateTasks {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        create_tasks_table(db).await?;
+        create_task_projects_table(db).await?;
+        add_task_status_and_complexity_columns(db).await?;
+        add_task_type_column(db).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["task_projects", "tasks"]).await
+    }
+}
 
-    // Create projects and checkpoints tables
-    create_projects_table(db).await?;
-    create_checkpoints_table(db).await?;
+struct CreateLibrary;
 
-    // Create plans tables
-    create_plans_table(db).await?;
-    create_plan_phases_table(db).await?;
-    create_plan_milestones_table(db).await?;
-    create_plan_documents_table(db).await?;
-    create_plan_links_table(db).await?;
+impl MigrationName for CreateLibrary {
+    fn name(&self) -> &str {
+        "m0002_create_library"
+    }
+}
 
-    // Add order_index to plan_documents
-    add_plan_documents_order_index(db).await?;
+#[async_trait::async_trait]
+impl MigrationTrait for CreateLibrary {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        create_library_workspaces_table(db).await?;
+        create_library_resources_table(db).await?;
 
-    // Create walkthrough tables
-    create_walkthroughs_table(db).await?;
-    create_walkthrough_takeaways_table(db).await?;
-    create_walkthrough_notes_table(db).await?;
+        // Catalogs, variations and subscriptions (Phase 2)
+        create_library_catalogs_table(db).await?;
+        create_library_variations_table(db).await?;
+        create_library_subscriptions_table(db).await?;
 
-    // Add is_vault to projects
-    add_project_is_vault_column(db).await?;
+        // Convert legacy library_artifacts rows into catalogs/variations
+        migrate_legacy_library_artifacts(db).await?;
 
-    Ok(())
+        // Collections (Phase 3)
+        create_library_collections_table(db).await?;
+        create_library_collection_catalogs_table(db).await?;
+        add_collection_description_and_tags(db).await?;
+
+        add_library_workspaces_pinned_field(db).await
+    }
+
+    // Legacy library_artifacts rows are left as they are
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(
+            manager.get_connection(),
+            &[
+                "library_collection_catalogs",
+                "library_collections",
+                "library_subscriptions",
+                "library_variations",
+                "library_catalogs",
+                "library_resources",
+                "library_workspaces",
+            ],
+        )
+        .await
+    }
+}
+
+struct CreateProjects;
+
+impl MigrationName for CreateProjects {
+    fn name(&self) -> &str {
+        "m0003_create_projects"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateProjects {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        create_projects_table(db).await?;
+        create_checkpoints_table(db).await?;
+        add_project_is_vault_column(db).await?;
+        add_project_sort_order_column(db).await?;
+        add_project_archived_column(db).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["checkpoints", "projects"]).await
+    }
+}
+
+struct CreatePlans;
+
+impl MigrationName for CreatePlans {
+    fn name(&self) -> &str {
+        "m0004_create_plans"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreatePlans {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        create_plans_table(db).await?;
+        create_plan_phases_table(db).await?;
+        create_plan_milestones_table(db).await?;
+        create_plan_documents_table(db).await?;
+        create_plan_links_table(db).await?;
+        add_plan_documents_order_index(db).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(
+            manager.get_connection(),
+            &["plan_links", "plan_documents", "plan_milestones", "plan_phases", "plans"],
+        )
+        .await
+    }
+}
+
+struct CreateWalkthroughs;
+
+impl MigrationName for CreateWalkthroughs {
+    fn name(&self) -> &str {
+        "m0005_create_walkthroughs"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateWalkthroughs {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        create_walkthroughs_table(db).await?;
+        create_walkthrough_takeaways_table(db).await?;
+        create_walkthrough_notes_table(db).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(
+            manager.get_connection(),
+            &["walkthrough_notes", "walkthrough_takeaways", "walkthroughs"],
+        )
+        .await
+    }
+}
+
+struct CreateAgents;
+
+impl MigrationName for CreateAgents {
+    fn name(&self) -> &str {
+        "m0006_create_agents"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateAgents {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_agents_table(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["agents"]).await
+    }
+}
+
+struct CreateKitUsage;
+
+impl MigrationName for CreateKitUsage {
+    fn name(&self) -> &str {
+        "m0007_create_kit_usage"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateKitUsage {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_kit_usage_table(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["kit_usage"]).await
+    }
+}
+
+struct CreateArtifactProvenance;
+
+impl MigrationName for CreateArtifactProvenance {
+    fn name(&self) -> &str {
+        "m0008_create_artifact_provenance"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateArtifactProvenance {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_artifact_provenance_table(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["artifact_provenance"]).await
+    }
+}
+
+struct CreateScheduledJobs;
+
+impl MigrationName for CreateScheduledJobs {
+    fn name(&self) -> &str {
+        "m0009_create_scheduled_jobs"
+    }
 }
 
-async fn create_tasks_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+#[async_trait::async_trait]
+impl MigrationTrait for CreateScheduledJobs {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_scheduled_jobs_table(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["scheduled_jobs"]).await
+    }
+}
+
+struct CreateSessionState;
+
+impl MigrationName for CreateSessionState {
+    fn name(&self) -> &str {
+        "m0010_create_session_state"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateSessionState {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_session_state_table(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["session_state"]).await
+    }
+}
+
+struct CreateTasksFts;
+
+impl MigrationName for CreateTasksFts {
+    fn name(&self) -> &str {
+        "m0011_create_tasks_fts"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateTasksFts {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_tasks_fts_table(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        execute(
+            db,
+            r#"
+            DROP TRIGGER IF EXISTS tasks_fts_insert;
+            DROP TRIGGER IF EXISTS tasks_fts_delete;
+            DROP TRIGGER IF EXISTS tasks_fts_update;
+        "#,
+        )
+        .await?;
+        drop_tables(db, &["tasks_fts"]).await
+    }
+}
+
+struct AddTaskDueDates;
+
+impl MigrationName for AddTaskDueDates {
+    fn name(&self) -> &str {
+        "m0012_add_task_due_dates"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddTaskDueDates {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_task_due_and_remind_columns(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(
+            manager.get_connection(),
+            r#"
+            DROP INDEX IF EXISTS idx_tasks_due_at;
+            DROP INDEX IF EXISTS idx_tasks_remind_at;
+            ALTER TABLE tasks DROP COLUMN due_at;
+            ALTER TABLE tasks DROP COLUMN remind_at;
+        "#,
+        )
+        .await
+    }
+}
+
+struct CreateTaskActivity;
+
+impl MigrationName for CreateTaskActivity {
+    fn name(&self) -> &str {
+        "m0013_create_task_activity"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateTaskActivity {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_task_activity_table(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["task_activity"]).await
+    }
+}
+
+struct AddTaskBoardOrder;
+
+impl MigrationName for AddTaskBoardOrder {
+    fn name(&self) -> &str {
+        "m0014_add_task_board_order"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddTaskBoardOrder {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_task_projects_order_index(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(
+            manager.get_connection(),
+            r#"
+            DROP INDEX IF EXISTS idx_task_projects_order;
+            ALTER TABLE task_projects DROP COLUMN order_index;
+        "#,
+        )
+        .await
+    }
+}
+
+struct CreateSavedViews;
+
+impl MigrationName for CreateSavedViews {
+    fn name(&self) -> &str {
+        "m0015_create_saved_views"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateSavedViews {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_saved_views_table(manager.get_connection()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["saved_views"]).await
+    }
+}
+
+async fn create_tasks_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS tasks (
             id TEXT PRIMARY KEY NOT NULL,
@@ -81,7 +488,7 @@ async fn create_tasks_table(db: &DatabaseConnection) -> Result<(), DbErr> {
     Ok(())
 }
 
-async fn create_task_projects_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_task_projects_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS task_projects (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -115,7 +522,7 @@ async fn create_task_projects_table(db: &DatabaseConnection) -> Result<(), DbErr
     Ok(())
 }
 
-async fn add_task_status_and_complexity_columns(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn add_task_status_and_complexity_columns<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     // Check if status column exists
     let check_status_sql = r#"
         SELECT COUNT(*) as count
@@ -187,7 +594,7 @@ async fn add_task_status_and_complexity_columns(db: &DatabaseConnection) -> Resu
     Ok(())
 }
 
-async fn add_task_type_column(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn add_task_type_column<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     // Check if type column exists
     let check_type_sql = r#"
         SELECT COUNT(*) as count
@@ -225,15 +632,98 @@ async fn add_task_type_column(db: &DatabaseConnection) -> Result<(), DbErr> {
     Ok(())
 }
 
-async fn create_library_workspaces_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn add_task_due_and_remind_columns<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    for column in ["due_at", "remind_at"] {
+        let check_sql = format!(
+            "SELECT COUNT(*) as count FROM pragma_table_info('tasks') WHERE name='{}'",
+            column
+        );
+
+        let result = db.query_one(Statement::from_string(
+            db.get_database_backend(),
+            check_sql,
+        )).await?;
+
+        let column_exists = if let Some(row) = result {
+            row.try_get::<i32>("", "count").unwrap_or(0) > 0
+        } else {
+            false
+        };
+
+        if !column_exists {
+            db.execute(Statement::from_string(
+                db.get_database_backend(),
+                format!("ALTER TABLE tasks ADD COLUMN {} TEXT", column),
+            )).await?;
+
+            info!("Added {} column to tasks table", column);
+        } else {
+            info!("{} column already exists in tasks table", column);
+        }
+    }
+
+    let index_sql = r#"
+        CREATE INDEX IF NOT EXISTS idx_tasks_due_at ON tasks(due_at);
+        CREATE INDEX IF NOT EXISTS idx_tasks_remind_at ON tasks(remind_at);
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        index_sql.to_string(),
+    ))
+    .await?;
+
+    Ok(())
+}
+
+async fn add_task_projects_order_index<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let check_sql = r#"
+        SELECT COUNT(*) as count
+        FROM pragma_table_info('task_projects')
+        WHERE name='order_index'
+    "#;
+
+    let result = db.query_one(Statement::from_string(
+        db.get_database_backend(),
+        check_sql.to_string(),
+    )).await?;
+
+    let column_exists = if let Some(row) = result {
+        row.try_get::<i32>("", "count").unwrap_or(0) > 0
+    } else {
+        false
+    };
+
+    if !column_exists {
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "ALTER TABLE task_projects ADD COLUMN order_index INTEGER".to_string(),
+        )).await?;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "CREATE INDEX IF NOT EXISTS idx_task_projects_order ON task_projects(project_id, order_index)".to_string(),
+        )).await?;
+
+        info!("Added order_index column to task_projects table");
+    } else {
+        info!("order_index column already exists in task_projects table");
+    }
+
+    Ok(())
+}
+
+async fn create_task_activity_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
-        CREATE TABLE IF NOT EXISTS library_workspaces (
-            id TEXT PRIMARY KEY NOT NULL,
-            name TEXT NOT NULL,
-            github_owner TEXT NOT NULL,
-            github_repo TEXT NOT NULL,
-            created_at INTEGER NOT NULL,
-            updated_at INTEGER NOT NULL
+        CREATE TABLE IF NOT EXISTS task_activity (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            action TEXT NOT NULL,
+            field TEXT,
+            old_value TEXT,
+            new_value TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
         )
     "#;
 
@@ -243,22 +733,29 @@ async fn create_library_workspaces_table(db: &DatabaseConnection) -> Result<(),
     ))
     .await?;
 
-    info!("Library workspaces table created or already exists");
+    let index_sql = r#"
+        CREATE INDEX IF NOT EXISTS idx_task_activity_task_id ON task_activity(task_id, created_at);
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        index_sql.to_string(),
+    ))
+    .await?;
+
+    info!("Task activity table and indexes created or already exist");
 
     Ok(())
 }
 
-async fn create_library_artifacts_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_saved_views_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
-        CREATE TABLE IF NOT EXISTS library_artifacts (
+        CREATE TABLE IF NOT EXISTS saved_views (
             id TEXT PRIMARY KEY NOT NULL,
-            workspace_id TEXT NOT NULL,
-            local_path TEXT NOT NULL,
-            library_path TEXT NOT NULL,
-            artifact_type TEXT NOT NULL,
-            published_at INTEGER NOT NULL,
-            last_synced_at INTEGER NOT NULL,
-            FOREIGN KEY (workspace_id) REFERENCES library_workspaces(id) ON DELETE CASCADE
+            name TEXT NOT NULL,
+            filter TEXT NOT NULL DEFAULT '{}',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
         )
     "#;
 
@@ -268,24 +765,35 @@ async fn create_library_artifacts_table(db: &DatabaseConnection) -> Result<(), D
     ))
     .await?;
 
-    // Create indexes for better query performance
-    let index_sql = r#"
-        CREATE INDEX IF NOT EXISTS idx_library_artifacts_workspace_id ON library_artifacts(workspace_id);
-        CREATE INDEX IF NOT EXISTS idx_library_artifacts_local_path ON library_artifacts(local_path);
+    info!("Saved views table created or already exists");
+
+    Ok(())
+}
+
+async fn create_library_workspaces_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS library_workspaces (
+            id TEXT PRIMARY KEY NOT NULL,
+            name TEXT NOT NULL,
+            github_owner TEXT NOT NULL,
+            github_repo TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )
     "#;
 
     db.execute(Statement::from_string(
         db.get_database_backend(),
-        index_sql.to_string(),
+        sql.to_string(),
     ))
     .await?;
 
-    info!("Library artifacts table and indexes created or already exist");
+    info!("Library workspaces table created or already exists");
 
     Ok(())
 }
 
-async fn create_projects_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_projects_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS projects (
             id TEXT PRIMARY KEY NOT NULL,
@@ -328,7 +836,7 @@ async fn create_projects_table(db: &DatabaseConnection) -> Result<(), DbErr> {
     Ok(())
 }
 
-async fn add_project_is_vault_column(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn add_project_is_vault_column<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     // Check if is_vault column exists
     let check_column_sql = r#"
         SELECT COUNT(*) as count
@@ -375,7 +883,80 @@ async fn add_project_is_vault_column(db: &DatabaseConnection) -> Result<(), DbEr
     Ok(())
 }
 
-async fn create_checkpoints_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn add_project_sort_order_column<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let check_column_sql = r#"
+        SELECT COUNT(*) as count
+        FROM pragma_table_info('projects')
+        WHERE name='sort_order'
+    "#;
+
+    let result = db.query_one(Statement::from_string(
+        db.get_database_backend(),
+        check_column_sql.to_string(),
+    )).await?;
+
+    let column_exists = if let Some(row) = result {
+        row.try_get::<i32>("", "count").unwrap_or(0) > 0
+    } else {
+        false
+    };
+
+    if !column_exists {
+        // NULL until the user arranges the list
+        let add_column_sql = r#"
+            ALTER TABLE projects ADD COLUMN sort_order INTEGER
+        "#;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            add_column_sql.to_string(),
+        )).await?;
+
+        info!("Added sort_order column to projects table");
+    } else {
+        info!("sort_order column already exists in projects table");
+    }
+
+    Ok(())
+}
+
+async fn add_project_archived_column<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let check_column_sql = r#"
+        SELECT COUNT(*) as count
+        FROM pragma_table_info('projects')
+        WHERE name='archived'
+    "#;
+
+    let result = db.query_one(Statement::from_string(
+        db.get_database_backend(),
+        check_column_sql.to_string(),
+    )).await?;
+
+    let column_exists = if let Some(row) = result {
+        row.try_get::<i32>("", "count").unwrap_or(0) > 0
+    } else {
+        false
+    };
+
+    if !column_exists {
+        let add_column_sql = r#"
+            ALTER TABLE projects ADD COLUMN archived INTEGER NOT NULL DEFAULT 0
+        "#;
+
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            add_column_sql.to_string(),
+        )).await?;
+
+        info!("Added archived column to projects table");
+    } else {
+        info!("archived column already exists in projects table");
+    }
+
+    Ok(())
+}
+
+async fn create_checkpoints_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS checkpoints (
             id TEXT PRIMARY KEY NOT NULL,
@@ -422,7 +1003,7 @@ async fn create_checkpoints_table(db: &DatabaseConnection) -> Result<(), DbErr>
     Ok(())
 }
 
-async fn create_plans_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_plans_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS plans (
             id TEXT PRIMARY KEY NOT NULL,
@@ -461,7 +1042,7 @@ async fn create_plans_table(db: &DatabaseConnection) -> Result<(), DbErr> {
     Ok(())
 }
 
-async fn create_plan_phases_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_plan_phases_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS plan_phases (
             id TEXT PRIMARY KEY NOT NULL,
@@ -501,7 +1082,7 @@ async fn create_plan_phases_table(db: &DatabaseConnection) -> Result<(), DbErr>
     Ok(())
 }
 
-async fn create_plan_milestones_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_plan_milestones_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS plan_milestones (
             id TEXT PRIMARY KEY NOT NULL,
@@ -540,7 +1121,7 @@ async fn create_plan_milestones_table(db: &DatabaseConnection) -> Result<(), DbE
     Ok(())
 }
 
-async fn create_plan_documents_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_plan_documents_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS plan_documents (
             id TEXT PRIMARY KEY NOT NULL,
@@ -578,7 +1159,7 @@ async fn create_plan_documents_table(db: &DatabaseConnection) -> Result<(), DbEr
     Ok(())
 }
 
-async fn add_plan_documents_order_index(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn add_plan_documents_order_index<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     // Check if order_index column exists
     let check_column_sql = r#"
         SELECT COUNT(*) as count
@@ -625,7 +1206,7 @@ async fn add_plan_documents_order_index(db: &DatabaseConnection) -> Result<(), D
     Ok(())
 }
 
-async fn create_plan_links_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_plan_links_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS plan_links (
             id TEXT PRIMARY KEY NOT NULL,
@@ -662,7 +1243,7 @@ async fn create_plan_links_table(db: &DatabaseConnection) -> Result<(), DbErr> {
     Ok(())
 }
 
-async fn create_library_resources_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_library_resources_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS library_resources (
             id TEXT PRIMARY KEY NOT NULL,
@@ -706,83 +1287,139 @@ async fn create_library_resources_table(db: &DatabaseConnection) -> Result<(), D
     Ok(())
 }
 
-async fn migrate_library_artifacts_to_catalogs(db: &DatabaseConnection) -> Result<(), DbErr> {
-    // Check if library_catalogs already exists
-    let check_catalogs_sql = r#"
+/// Converts rows from the legacy `library_artifacts` table (one row per
+/// published file) into catalogs and variations, then drops the table.
+///
+/// Runs on every startup but only does work while the legacy table exists.
+/// Each row becomes a catalog keyed by workspace and remote path (reusing an
+/// existing catalog if sync already created one) plus a variation for the
+/// published content. The legacy schema stored no content hash, so it is
+/// computed from the original local file when that still exists.
+async fn migrate_legacy_library_artifacts<C: ConnectionTrait + TransactionTrait>(db: &C) -> Result<(), DbErr> {
+    let check_artifacts_sql = r#"
         SELECT name FROM sqlite_master
-        WHERE type='table' AND name='library_catalogs'
+        WHERE type='table' AND name='library_artifacts'
     "#;
 
-    let catalogs_exists = db.query_one(Statement::from_string(
+    let artifacts_exists = db.query_one(Statement::from_string(
         db.get_database_backend(),
-        check_catalogs_sql.to_string(),
+        check_artifacts_sql.to_string(),
     )).await?.is_some();
 
-    if catalogs_exists {
-        info!("Library catalogs table already exists, skipping migration");
+    if !artifacts_exists {
         return Ok(());
     }
 
-    // Check if old library_artifacts table exists
-    let check_artifacts_sql = r#"
-        SELECT name FROM sqlite_master
-        WHERE type='table' AND name='library_artifacts'
-    "#;
-
-    let artifacts_exists = db.query_one(Statement::from_string(
+    let rows = db.query_all(Statement::from_string(
         db.get_database_backend(),
-        check_artifacts_sql.to_string(),
-    )).await?.is_some();
+        "SELECT id, workspace_id, local_path, library_path, artifact_type, published_at, last_synced_at FROM library_artifacts".to_string(),
+    )).await?;
+
+    info!("Migrating {} legacy library artifacts to catalogs...", rows.len());
+
+    let txn = db.begin().await?;
+    let (mut catalogs_created, mut variations_created) = (0, 0);
+
+    for row in rows {
+        let id: String = row.try_get("", "id")?;
+        let workspace_id: String = row.try_get("", "workspace_id")?;
+        let local_path: String = row.try_get("", "local_path")?;
+        let library_path: String = row.try_get("", "library_path")?;
+        let artifact_type: String = row.try_get("", "artifact_type")?;
+        let published_at: i64 = row.try_get("", "published_at")?;
+        let last_synced_at: i64 = row.try_get("", "last_synced_at")?;
+
+        let existing_catalog = txn.query_one(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "SELECT id FROM library_catalogs WHERE workspace_id = ? AND remote_path = ?",
+            [workspace_id.clone().into(), library_path.clone().into()],
+        )).await?;
+
+        let catalog_id = match existing_catalog {
+            Some(catalog) => catalog.try_get::<String>("", "id")?,
+            None => {
+                let name = std::path::Path::new(&library_path)
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_else(|| library_path.clone());
+
+                // Keep the legacy id unless sync already took it for another catalog
+                let id_taken = txn.query_one(Statement::from_sql_and_values(
+                    txn.get_database_backend(),
+                    "SELECT id FROM library_catalogs WHERE id = ?",
+                    [id.clone().into()],
+                )).await?.is_some();
+                let catalog_id = if id_taken { Uuid::new_v4().to_string() } else { id };
+
+                txn.execute(Statement::from_sql_and_values(
+                    txn.get_database_backend(),
+                    r#"INSERT INTO library_catalogs
+                        (id, workspace_id, name, description, artifact_type, tags, remote_path, created_at, updated_at)
+                        VALUES (?, ?, ?, NULL, ?, '[]', ?, ?, ?)"#,
+                    [
+                        catalog_id.clone().into(),
+                        workspace_id.clone().into(),
+                        name.into(),
+                        artifact_type.into(),
+                        library_path.clone().into(),
+                        published_at.into(),
+                        last_synced_at.into(),
+                    ],
+                )).await?;
+                catalogs_created += 1;
+                catalog_id
+            }
+        };
 
-    if !artifacts_exists {
-        // Neither table exists, create catalogs table directly
-        info!("No existing library_artifacts table, creating library_catalogs directly");
-        return create_library_catalogs_table(db).await;
-    }
-
-    // Table exists - migrate data
-    info!("Migrating library_artifacts to library_catalogs...");
-
-    // Create new catalogs table
-    create_library_catalogs_table(db).await?;
-
-    // Migrate existing data
-    let migrate_data_sql = r#"
-        INSERT INTO library_catalogs (id, workspace_id, name, description, artifact_type, tags, remote_path, created_at, updated_at)
-        SELECT
-            id,
-            workspace_id,
-            COALESCE(
-                substr(library_path, instr(library_path, '/') + 1),
-                library_path
-            ) as name,
-            NULL as description,
-            artifact_type,
-            '[]' as tags,
-            library_path as remote_path,
-            published_at as created_at,
-            last_synced_at as updated_at
-        FROM library_artifacts
-    "#;
+        // An empty hash never matches, so the next update check offers a pull
+        let content_hash = std::fs::read_to_string(&local_path)
+            .map(|content| compute_content_hash(&content))
+            .unwrap_or_default();
 
-    db.execute(Statement::from_string(
-        db.get_database_backend(),
-        migrate_data_sql.to_string(),
-    )).await?;
+        let existing_variation = txn.query_one(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "SELECT id FROM library_variations WHERE catalog_id = ? AND remote_path = ? AND published_at = ?",
+            [catalog_id.clone().into(), library_path.clone().into(), published_at.into()],
+        )).await?;
 
-    // Drop old table
-    let drop_sql = "DROP TABLE IF EXISTS library_artifacts";
-    db.execute(Statement::from_string(
-        db.get_database_backend(),
-        drop_sql.to_string(),
+        if existing_variation.is_none() {
+            txn.execute(Statement::from_sql_and_values(
+                txn.get_database_backend(),
+                r#"INSERT INTO library_variations
+                    (id, catalog_id, workspace_id, remote_path, content_hash, github_commit_sha, published_at, publisher_name, version_tag, created_at, updated_at)
+                    VALUES (?, ?, ?, ?, ?, NULL, ?, NULL, NULL, ?, ?)"#,
+                [
+                    Uuid::new_v4().to_string().into(),
+                    catalog_id.into(),
+                    workspace_id.into(),
+                    library_path.into(),
+                    content_hash.into(),
+                    published_at.into(),
+                    published_at.into(),
+                    last_synced_at.into(),
+                ],
+            )).await?;
+            variations_created += 1;
+        }
+    }
+
+    // Retire the legacy table
+    txn.execute(Statement::from_string(
+        txn.get_database_backend(),
+        "DROP TABLE IF EXISTS library_artifacts".to_string(),
     )).await?;
 
-    info!("Migration complete: library_artifacts → library_catalogs");
+    txn.commit().await?;
+
+    info!(
+        "Migration complete: library_artifacts → {} catalogs, {} variations",
+        catalogs_created, variations_created
+    );
 
     Ok(())
 }
 
-async fn create_library_catalogs_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_library_catalogs_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS library_catalogs (
             id TEXT PRIMARY KEY NOT NULL,
@@ -818,7 +1455,7 @@ async fn create_library_catalogs_table(db: &DatabaseConnection) -> Result<(), Db
     Ok(())
 }
 
-async fn create_library_variations_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_library_variations_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS library_variations (
             id TEXT PRIMARY KEY NOT NULL,
@@ -858,7 +1495,7 @@ async fn create_library_variations_table(db: &DatabaseConnection) -> Result<(),
     Ok(())
 }
 
-async fn create_library_subscriptions_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_library_subscriptions_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS library_subscriptions (
             id TEXT PRIMARY KEY NOT NULL,
@@ -898,7 +1535,7 @@ async fn create_library_subscriptions_table(db: &DatabaseConnection) -> Result<(
     Ok(())
 }
 
-async fn create_library_collections_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_library_collections_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS library_collections (
             id TEXT PRIMARY KEY NOT NULL,
@@ -932,7 +1569,7 @@ async fn create_library_collections_table(db: &DatabaseConnection) -> Result<(),
     Ok(())
 }
 
-async fn create_library_collection_catalogs_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_library_collection_catalogs_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS library_collection_catalogs (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -975,7 +1612,7 @@ async fn create_library_collection_catalogs_table(db: &DatabaseConnection) -> Re
     Ok(())
 }
 
-async fn add_library_workspaces_pinned_field(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn add_library_workspaces_pinned_field<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     // Check if the column already exists by trying to add it (SQLite will error if it exists)
     // We'll use a more robust approach: try to alter the table
     let sql = r#"
@@ -1017,7 +1654,7 @@ async fn add_library_workspaces_pinned_field(db: &DatabaseConnection) -> Result<
     Ok(())
 }
 
-async fn add_collection_description_and_tags(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn add_collection_description_and_tags<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     // Check if description column exists
     let check_description_sql = r#"
         SELECT COUNT(*) as count
@@ -1089,7 +1726,7 @@ async fn add_collection_description_and_tags(db: &DatabaseConnection) -> Result<
     Ok(())
 }
 
-async fn create_walkthroughs_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_walkthroughs_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS walkthroughs (
             id TEXT PRIMARY KEY NOT NULL,
@@ -1128,7 +1765,7 @@ async fn create_walkthroughs_table(db: &DatabaseConnection) -> Result<(), DbErr>
     Ok(())
 }
 
-async fn create_walkthrough_takeaways_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_walkthrough_takeaways_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS walkthrough_takeaways (
             id TEXT PRIMARY KEY NOT NULL,
@@ -1166,7 +1803,7 @@ async fn create_walkthrough_takeaways_table(db: &DatabaseConnection) -> Result<(
     Ok(())
 }
 
-async fn create_walkthrough_notes_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+async fn create_walkthrough_notes_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
     let sql = r#"
         CREATE TABLE IF NOT EXISTS walkthrough_notes (
             id TEXT PRIMARY KEY NOT NULL,
@@ -1199,3 +1836,616 @@ async fn create_walkthrough_notes_table(db: &DatabaseConnection) -> Result<(), D
 
     Ok(())
 }
+
+async fn create_agents_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS agents (
+            id TEXT PRIMARY KEY NOT NULL,
+            project_id TEXT NOT NULL,
+            file_path TEXT NOT NULL,
+            name TEXT NOT NULL,
+            description TEXT,
+            model TEXT,
+            tools TEXT NOT NULL DEFAULT '[]',
+            content_hash TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+        )
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        sql.to_string(),
+    ))
+    .await?;
+
+    // Create indexes
+    let index_sql = r#"
+        CREATE INDEX IF NOT EXISTS idx_agents_project_id ON agents(project_id);
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_agents_file_path ON agents(project_id, file_path);
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        index_sql.to_string(),
+    ))
+    .await?;
+
+    info!("Agents table and indexes created or already exist");
+
+    Ok(())
+}
+
+async fn create_kit_usage_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS kit_usage (
+            id TEXT PRIMARY KEY NOT NULL,
+            kit_path TEXT NOT NULL,
+            target_project_path TEXT NOT NULL,
+            target_project_id TEXT,
+            target_file_path TEXT NOT NULL,
+            content_hash TEXT NOT NULL,
+            copied_at INTEGER NOT NULL,
+            UNIQUE(kit_path, target_file_path)
+        )
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        sql.to_string(),
+    ))
+    .await?;
+
+    // Create indexes
+    let index_sql = r#"
+        CREATE INDEX IF NOT EXISTS idx_kit_usage_kit_path ON kit_usage(kit_path);
+        CREATE INDEX IF NOT EXISTS idx_kit_usage_target_project ON kit_usage(target_project_path);
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        index_sql.to_string(),
+    ))
+    .await?;
+
+    info!("Kit usage table and indexes created or already exist");
+
+    Ok(())
+}
+
+async fn create_artifact_provenance_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS artifact_provenance (
+            id TEXT PRIMARY KEY NOT NULL,
+            project_path TEXT NOT NULL,
+            local_path TEXT NOT NULL UNIQUE,
+            source_kind TEXT NOT NULL,
+            source_path TEXT,
+            workspace_id TEXT,
+            catalog_id TEXT,
+            variation_id TEXT,
+            content_hash TEXT NOT NULL,
+            copied_at INTEGER NOT NULL
+        )
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        sql.to_string(),
+    ))
+    .await?;
+
+    // Create indexes
+    let index_sql = r#"
+        CREATE INDEX IF NOT EXISTS idx_artifact_provenance_project ON artifact_provenance(project_path);
+        CREATE INDEX IF NOT EXISTS idx_artifact_provenance_source ON artifact_provenance(source_path);
+        CREATE INDEX IF NOT EXISTS idx_artifact_provenance_catalog ON artifact_provenance(catalog_id);
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        index_sql.to_string(),
+    ))
+    .await?;
+
+    info!("Artifact provenance table and indexes created or already exist");
+
+    Ok(())
+}
+
+async fn create_scheduled_jobs_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS scheduled_jobs (
+            id TEXT PRIMARY KEY NOT NULL,
+            name TEXT NOT NULL,
+            job_type TEXT NOT NULL,
+            interval_seconds INTEGER,
+            cron_expression TEXT,
+            params TEXT,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            next_run_at INTEGER,
+            last_run_at INTEGER,
+            last_status TEXT,
+            last_message TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        sql.to_string(),
+    ))
+    .await?;
+
+    // Create indexes
+    let index_sql = r#"
+        CREATE INDEX IF NOT EXISTS idx_scheduled_jobs_next_run ON scheduled_jobs(enabled, next_run_at);
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        index_sql.to_string(),
+    ))
+    .await?;
+
+    info!("Scheduled jobs table and indexes created or already exist");
+
+    Ok(())
+}
+
+async fn create_session_state_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    // Deleting a project, plan or walkthrough just clears the selection
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS session_state (
+            id TEXT PRIMARY KEY NOT NULL,
+            active_project_id TEXT,
+            open_artifact_paths TEXT NOT NULL DEFAULT '[]',
+            active_artifact_path TEXT,
+            selected_plan_id TEXT,
+            selected_walkthrough_id TEXT,
+            updated_at INTEGER NOT NULL,
+            FOREIGN KEY (active_project_id) REFERENCES projects(id) ON DELETE SET NULL,
+            FOREIGN KEY (selected_plan_id) REFERENCES plans(id) ON DELETE SET NULL,
+            FOREIGN KEY (selected_walkthrough_id) REFERENCES walkthroughs(id) ON DELETE SET NULL
+        )
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        sql.to_string(),
+    ))
+    .await?;
+
+    info!("Session state table created or already exists");
+
+    Ok(())
+}
+
+async fn create_tasks_fts_table<C: ConnectionTrait>(db: &C) -> Result<(), DbErr> {
+    let check_sql = r#"
+        SELECT COUNT(*) as count
+        FROM sqlite_master
+        WHERE type='table' AND name='tasks_fts'
+    "#;
+
+    let result = db.query_one(Statement::from_string(
+        db.get_database_backend(),
+        check_sql.to_string(),
+    )).await?;
+
+    let fts_exists = if let Some(row) = result {
+        row.try_get::<i32>("", "count").unwrap_or(0) > 0
+    } else {
+        false
+    };
+
+    // External-content index over tasks, kept in sync by triggers
+    let sql = r#"
+        CREATE VIRTUAL TABLE IF NOT EXISTS tasks_fts USING fts5(
+            title,
+            description,
+            tags,
+            content='tasks',
+            content_rowid='rowid',
+            tokenize='unicode61 remove_diacritics 2'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS tasks_fts_insert AFTER INSERT ON tasks BEGIN
+            INSERT INTO tasks_fts(rowid, title, description, tags)
+            VALUES (new.rowid, new.title, new.description, new.tags);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS tasks_fts_delete AFTER DELETE ON tasks BEGIN
+            INSERT INTO tasks_fts(tasks_fts, rowid, title, description, tags)
+            VALUES ('delete', old.rowid, old.title, old.description, old.tags);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS tasks_fts_update AFTER UPDATE ON tasks BEGIN
+            INSERT INTO tasks_fts(tasks_fts, rowid, title, description, tags)
+            VALUES ('delete', old.rowid, old.title, old.description, old.tags);
+            INSERT INTO tasks_fts(rowid, title, description, tags)
+            VALUES (new.rowid, new.title, new.description, new.tags);
+        END;
+    "#;
+
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        sql.to_string(),
+    ))
+    .await?;
+
+    // Index the tasks that existed before the table did
+    if !fts_exists {
+        db.execute(Statement::from_string(
+            db.get_database_backend(),
+            "INSERT INTO tasks_fts(tasks_fts) VALUES ('rebuild')".to_string(),
+        ))
+        .await?;
+
+        info!("Created tasks_fts table and indexed existing tasks");
+    } else {
+        info!("Tasks_fts table already exists");
+    }
+
+    Ok(())
+}
+struct AddSoftDelete;
+
+impl MigrationName for AddSoftDelete {
+    fn name(&self) -> &str {
+        "m0016_add_soft_delete"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddSoftDelete {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(
+            manager.get_connection(),
+            r#"
+            ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+            ALTER TABLE plans ADD COLUMN deleted_at INTEGER;
+            ALTER TABLE walkthroughs ADD COLUMN deleted_at INTEGER;
+            CREATE INDEX IF NOT EXISTS idx_tasks_deleted_at ON tasks(deleted_at);
+            CREATE INDEX IF NOT EXISTS idx_plans_deleted_at ON plans(deleted_at);
+            CREATE INDEX IF NOT EXISTS idx_walkthroughs_deleted_at ON walkthroughs(deleted_at);
+        "#,
+        )
+        .await?;
+        info!("Added deleted_at columns to tasks, plans and walkthroughs");
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(
+            manager.get_connection(),
+            r#"
+            DROP INDEX IF EXISTS idx_tasks_deleted_at;
+            DROP INDEX IF EXISTS idx_plans_deleted_at;
+            DROP INDEX IF EXISTS idx_walkthroughs_deleted_at;
+            ALTER TABLE tasks DROP COLUMN deleted_at;
+            ALTER TABLE plans DROP COLUMN deleted_at;
+            ALTER TABLE walkthroughs DROP COLUMN deleted_at;
+        "#,
+        )
+        .await
+    }
+}
+
+struct AddPlanSchedule;
+
+impl MigrationName for AddPlanSchedule {
+    fn name(&self) -> &str {
+        "m0017_add_plan_schedule"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddPlanSchedule {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(
+            manager.get_connection(),
+            r#"
+            ALTER TABLE plan_phases ADD COLUMN target_date INTEGER;
+            ALTER TABLE plan_milestones ADD COLUMN due_at INTEGER;
+            CREATE INDEX IF NOT EXISTS idx_plan_phases_target_date ON plan_phases(target_date);
+        "#,
+        )
+        .await?;
+        info!("Added plan phase target dates and milestone due dates");
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(
+            manager.get_connection(),
+            r#"
+            DROP INDEX IF EXISTS idx_plan_phases_target_date;
+            ALTER TABLE plan_phases DROP COLUMN target_date;
+            ALTER TABLE plan_milestones DROP COLUMN due_at;
+        "#,
+        )
+        .await
+    }
+}
+
+struct AddPlanDocumentPhaseOrder;
+
+impl MigrationName for AddPlanDocumentPhaseOrder {
+    fn name(&self) -> &str {
+        "m0018_add_plan_document_phase_order"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddPlanDocumentPhaseOrder {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Each phase's documents (and each plan's unassigned ones) are
+        // numbered from 0 in their current global order
+        execute(
+            manager.get_connection(),
+            r#"
+            ALTER TABLE plan_documents ADD COLUMN phase_order_index INTEGER NOT NULL DEFAULT 0;
+            UPDATE plan_documents SET phase_order_index = (
+                SELECT COUNT(*) FROM plan_documents AS other
+                WHERE other.plan_id = plan_documents.plan_id
+                  AND other.phase_id IS plan_documents.phase_id
+                  AND (other.order_index < plan_documents.order_index
+                       OR (other.order_index = plan_documents.order_index AND other.id < plan_documents.id))
+            );
+            CREATE INDEX IF NOT EXISTS idx_plan_documents_phase_order ON plan_documents(phase_id, phase_order_index);
+        "#,
+        )
+        .await?;
+        info!("Added per-phase ordering to plan documents");
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(
+            manager.get_connection(),
+            r#"
+            DROP INDEX IF EXISTS idx_plan_documents_phase_order;
+            ALTER TABLE plan_documents DROP COLUMN phase_order_index;
+        "#,
+        )
+        .await
+    }
+}
+
+struct CreateWalkthroughSteps;
+
+impl MigrationName for CreateWalkthroughSteps {
+    fn name(&self) -> &str {
+        "m0019_create_walkthrough_steps"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateWalkthroughSteps {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(
+            manager.get_connection(),
+            r#"
+            CREATE TABLE IF NOT EXISTS walkthrough_steps (
+                id TEXT PRIMARY KEY NOT NULL,
+                walkthrough_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT,
+                file_path TEXT NOT NULL,
+                start_line INTEGER NOT NULL,
+                end_line INTEGER NOT NULL,
+                commit_sha TEXT,
+                sort_order INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                FOREIGN KEY (walkthrough_id) REFERENCES walkthroughs(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_walkthrough_steps_walkthrough_id ON walkthrough_steps(walkthrough_id);
+        "#,
+        )
+        .await?;
+        info!("Created walkthrough_steps table");
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["walkthrough_steps"]).await
+    }
+}
+
+struct AddWalkthroughNoteFormat;
+
+impl MigrationName for AddWalkthroughNoteFormat {
+    fn name(&self) -> &str {
+        "m0020_add_walkthrough_note_format"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddWalkthroughNoteFormat {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing notes were written as plain text
+        execute(
+            manager.get_connection(),
+            r#"
+            ALTER TABLE walkthrough_notes ADD COLUMN format TEXT NOT NULL DEFAULT 'text';
+        "#,
+        )
+        .await?;
+        info!("Added format to walkthrough notes");
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(
+            manager.get_connection(),
+            r#"
+            ALTER TABLE walkthrough_notes DROP COLUMN format;
+        "#,
+        )
+        .await
+    }
+}
+
+struct CreateWalkthroughTakeawayTasks;
+
+impl MigrationName for CreateWalkthroughTakeawayTasks {
+    fn name(&self) -> &str {
+        "m0021_create_walkthrough_takeaway_tasks"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateWalkthroughTakeawayTasks {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One task per takeaway
+        execute(
+            manager.get_connection(),
+            r#"
+            CREATE TABLE IF NOT EXISTS walkthrough_takeaway_tasks (
+                takeaway_id TEXT PRIMARY KEY NOT NULL,
+                task_id TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (takeaway_id) REFERENCES walkthrough_takeaways(id) ON DELETE CASCADE,
+                FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_walkthrough_takeaway_tasks_task_id ON walkthrough_takeaway_tasks(task_id);
+        "#,
+        )
+        .await?;
+        info!("Created walkthrough_takeaway_tasks table");
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_tables(manager.get_connection(), &["walkthrough_takeaway_tasks"]).await
+    }
+}
+
+struct KeyTasksFtsByTaskId;
+
+impl MigrationName for KeyTasksFtsByTaskId {
+    fn name(&self) -> &str {
+        "m0022_key_tasks_fts_by_task_id"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for KeyTasksFtsByTaskId {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // tasks has no INTEGER PRIMARY KEY, so its rowids can change on
+        // VACUUM and an external-content index keyed on them goes stale.
+        // tasks_fts_keys gives each task a stable rowid in the index, so
+        // the triggers can find a task's entry without scanning it.
+        execute(
+            manager.get_connection(),
+            r#"
+            DROP TRIGGER IF EXISTS tasks_fts_insert;
+            DROP TRIGGER IF EXISTS tasks_fts_delete;
+            DROP TRIGGER IF EXISTS tasks_fts_update;
+            DROP TABLE IF EXISTS tasks_fts;
+
+            CREATE TABLE tasks_fts_keys (
+                fts_rowid INTEGER PRIMARY KEY,
+                task_id TEXT NOT NULL UNIQUE
+            );
+
+            CREATE VIRTUAL TABLE tasks_fts USING fts5(
+                title,
+                description,
+                tags,
+                tokenize='unicode61 remove_diacritics 2'
+            );
+
+            CREATE TRIGGER tasks_fts_insert AFTER INSERT ON tasks BEGIN
+                INSERT INTO tasks_fts_keys(task_id) VALUES (new.id);
+                INSERT INTO tasks_fts(rowid, title, description, tags)
+                VALUES (
+                    (SELECT fts_rowid FROM tasks_fts_keys WHERE task_id = new.id),
+                    new.title, new.description, new.tags
+                );
+            END;
+
+            CREATE TRIGGER tasks_fts_delete AFTER DELETE ON tasks BEGIN
+                DELETE FROM tasks_fts
+                WHERE rowid = (SELECT fts_rowid FROM tasks_fts_keys WHERE task_id = old.id);
+                DELETE FROM tasks_fts_keys WHERE task_id = old.id;
+            END;
+
+            CREATE TRIGGER tasks_fts_update AFTER UPDATE OF id, title, description, tags ON tasks BEGIN
+                DELETE FROM tasks_fts
+                WHERE rowid = (SELECT fts_rowid FROM tasks_fts_keys WHERE task_id = old.id);
+                UPDATE tasks_fts_keys SET task_id = new.id WHERE task_id = old.id;
+                INSERT INTO tasks_fts(rowid, title, description, tags)
+                VALUES (
+                    (SELECT fts_rowid FROM tasks_fts_keys WHERE task_id = new.id),
+                    new.title, new.description, new.tags
+                );
+            END;
+
+            INSERT INTO tasks_fts_keys(task_id) SELECT id FROM tasks;
+            INSERT INTO tasks_fts(rowid, title, description, tags)
+            SELECT k.fts_rowid, t.title, t.description, t.tags
+            FROM tasks t
+            JOIN tasks_fts_keys k ON k.task_id = t.id;
+        "#,
+        )
+        .await?;
+        info!("Re-keyed tasks_fts on task ids");
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        execute(
+            db,
+            r#"
+            DROP TRIGGER IF EXISTS tasks_fts_insert;
+            DROP TRIGGER IF EXISTS tasks_fts_delete;
+            DROP TRIGGER IF EXISTS tasks_fts_update;
+        "#,
+        )
+        .await?;
+        drop_tables(db, &["tasks_fts", "tasks_fts_keys"]).await?;
+        create_tasks_fts_table(db).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{ConnectOptions, Database};
+
+    // A single connection, since each in-memory connection is its own database
+    async fn memory_db() -> DatabaseConnection {
+        let mut options = ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1).min_connections(1);
+        Database::connect(options).await.expect("Failed to open in-memory database")
+    }
+
+    #[tokio::test]
+    async fn test_migrations_apply_and_revert() {
+        let db = memory_db().await;
+
+        run_migrations(&db).await.unwrap();
+        let version = schema_version(&db).await.unwrap();
+        assert!(version.pending.is_empty());
+        assert_eq!(version.version, version.latest);
+        assert_eq!(version.applied, Migrator::migrations().len());
+
+        // Running again is a no-op
+        run_migrations(&db).await.unwrap();
+
+        Migrator::down(&db, None).await.unwrap();
+        let version = schema_version(&db).await.unwrap();
+        assert_eq!(version.applied, 0);
+        assert_eq!(version.pending.len(), Migrator::migrations().len());
+
+        // And the schema can be rebuilt from scratch
+        run_migrations(&db).await.unwrap();
+        assert!(schema_version(&db).await.unwrap().pending.is_empty());
+    }
+}
@@ -1,31 +1,33 @@
-use sea_orm::{Database, DbErr};
+use sea_orm::{ConnectOptions, Database, DbErr};
+use sqlx::sqlite::{SqliteJournalMode, SqliteSynchronous};
 pub use sea_orm::DatabaseConnection;
-use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 use tracing::info;
 
 pub mod entities;
 pub mod migrations;
+pub mod maintenance;
+pub mod encryption;
 pub mod task_operations;
+pub mod saved_view_operations;
+pub mod checkpoint_operations;
 pub mod project_operations;
 pub mod plan_operations;
 pub mod walkthrough_operations;
+pub mod artifact_operations;
+pub mod agent_operations;
+pub mod kit_usage_operations;
+pub mod provenance_operations;
+pub mod move_operations;
+pub mod reconcile_operations;
+pub mod scheduler_operations;
+pub mod session_operations;
 
-/// Get the path to the SQLite database file
+/// Get the path to the SQLite database file, in the data directory
+/// (`~/.bluekit` unless configured; see `core::data_dir`)
 pub fn get_db_path() -> Result<PathBuf, String> {
-    let home_dir = env::var("HOME")
-        .or_else(|_| env::var("USERPROFILE"))
-        .map_err(|_| "Could not determine home directory".to_string())?;
-
-    let bluekit_dir = PathBuf::from(&home_dir).join(".bluekit");
-
-    // Ensure .bluekit directory exists
-    if !bluekit_dir.exists() {
-        std::fs::create_dir_all(&bluekit_dir)
-            .map_err(|e| format!("Failed to create .bluekit directory: {}", e))?;
-    }
-
-    Ok(bluekit_dir.join("bluekit.db"))
+    Ok(crate::core::data_dir::data_dir()?.join("bluekit.db"))
 }
 
 /// Initialize the database connection and run migrations
@@ -33,12 +35,41 @@ pub async fn initialize_database() -> Result<DatabaseConnection, DbErr> {
     let db_path = get_db_path()
         .map_err(|e| DbErr::Custom(format!("Failed to get database path: {}", e)))?;
 
+    // Data directory and encryption changes made last session take effect now
+    crate::core::data_dir::apply_pending_migration().map_err(DbErr::Custom)?;
+    encryption::apply_pending(&db_path).await;
+    let key = if encryption::is_encrypted(&db_path) {
+        Some(encryption::key_pragma(&encryption::database_key().map_err(DbErr::Custom)?))
+    } else {
+        None
+    };
+
     let db_url = format!("sqlite://{}?mode=rwc", db_path.display());
 
     info!("Connecting to database at: {}", db_url);
 
-    // Create database connection
-    let db = Database::connect(&db_url).await?;
+    // Create database connection. WAL and a busy timeout keep concurrent
+    // commands from failing with "database is locked"; both are set on
+    // every pooled connection. Changes take effect on the next launch.
+    let settings = crate::core::settings::load_settings().database;
+    let mut options = ConnectOptions::new(db_url);
+    options
+        .max_connections(settings.max_connections.max(1))
+        .min_connections(1)
+        .map_sqlx_sqlite_opts(move |opts| {
+            let opts = match &key {
+                Some(key) => opts.pragma("key", key.clone()),
+                None => opts,
+            };
+            let opts = opts.busy_timeout(Duration::from_millis(settings.busy_timeout_ms));
+            if settings.wal_mode {
+                opts.journal_mode(SqliteJournalMode::Wal)
+                    .synchronous(SqliteSynchronous::Normal)
+            } else {
+                opts.journal_mode(SqliteJournalMode::Delete)
+            }
+        });
+    let db = Database::connect(options).await?;
 
     // Run migrations
     info!("Running database migrations...");
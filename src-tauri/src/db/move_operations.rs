@@ -0,0 +1,144 @@
+use sea_orm::*;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::{agent, artifact_provenance, kit_usage, library_resource, project, walkthrough};
+use chrono::Utc;
+use std::path::Path;
+
+/// Counts of DB records carried over by a cross-project move
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferredRecordsDto {
+    pub walkthroughs: u64,
+    pub agents: u64,
+    pub resources: u64,
+    #[serde(rename = "kitUsage")]
+    pub kit_usage: u64,
+    pub provenance: u64,
+}
+
+/// Re-point every DB record tied to `old_path` at `new_path` in the target project
+///
+/// Runs in a single transaction so a failure leaves the records untouched.
+pub async fn transfer_artifact_records(
+    db: &DatabaseConnection,
+    old_path: &str,
+    new_path: &str,
+    source_project_path: &str,
+    target_project_path: &str,
+) -> Result<TransferredRecordsDto, DbErr> {
+    let txn = db.begin().await?;
+    let now = Utc::now().timestamp();
+    let mut transferred = TransferredRecordsDto::default();
+
+    let target_project = project::Entity::find()
+        .filter(project::Column::Path.eq(target_project_path))
+        .one(&txn)
+        .await?;
+
+    // Project-scoped records need the target project to be registered
+    let target_project_id = |kind: &str| -> Result<String, DbErr> {
+        target_project
+            .as_ref()
+            .map(|p| p.id.clone())
+            .ok_or_else(|| DbErr::RecordNotFound(format!(
+                "Target project is not registered, cannot transfer {}: {}",
+                kind, target_project_path
+            )))
+    };
+
+    // Walkthroughs (takeaways and notes follow via walkthrough_id)
+    if let Some(model) = walkthrough::Entity::find()
+        .filter(walkthrough::Column::FilePath.eq(old_path))
+        .one(&txn)
+        .await?
+    {
+        let mut walkthrough_active: walkthrough::ActiveModel = model.into();
+        walkthrough_active.project_id = Set(target_project_id("walkthrough")?);
+        walkthrough_active.file_path = Set(new_path.to_string());
+        walkthrough_active.updated_at = Set(now);
+        walkthrough_active.update(&txn).await?;
+        transferred.walkthroughs += 1;
+    }
+
+    // Agents
+    if let Some(model) = agent::Entity::find()
+        .filter(agent::Column::FilePath.eq(old_path))
+        .one(&txn)
+        .await?
+    {
+        let mut agent_active: agent::ActiveModel = model.into();
+        agent_active.project_id = Set(target_project_id("agent")?);
+        agent_active.file_path = Set(new_path.to_string());
+        agent_active.updated_at = Set(now);
+        agent_active.update(&txn).await?;
+        transferred.agents += 1;
+    }
+
+    // Library resources are keyed by project + relative path
+    let source_project = project::Entity::find()
+        .filter(project::Column::Path.eq(source_project_path))
+        .one(&txn)
+        .await?;
+    let source_resource = match (&source_project, Path::new(old_path).strip_prefix(source_project_path)) {
+        (Some(source_project), Ok(relative_path)) => library_resource::Entity::find()
+            .filter(library_resource::Column::ProjectId.eq(&source_project.id))
+            .filter(library_resource::Column::RelativePath.eq(relative_path.to_string_lossy().to_string()))
+            .one(&txn)
+            .await?,
+        _ => None,
+    };
+    if let Some(resource) = source_resource {
+        let relative_path = Path::new(new_path)
+            .strip_prefix(target_project_path)
+            .map_err(|e| DbErr::Custom(format!("Failed to compute relative path: {}", e)))?
+            .to_string_lossy()
+            .to_string();
+
+        let mut resource_active: library_resource::ActiveModel = resource.into();
+        resource_active.project_id = Set(target_project_id("resource")?);
+        resource_active.relative_path = Set(relative_path);
+        resource_active.updated_at = Set(now);
+        resource_active.update(&txn).await?;
+        transferred.resources += 1;
+    }
+
+    // Kit usage: the moved file may be a kit source or a copy of one
+    let result = kit_usage::Entity::update_many()
+        .col_expr(kit_usage::Column::KitPath, Expr::value(new_path))
+        .filter(kit_usage::Column::KitPath.eq(old_path))
+        .exec(&txn)
+        .await?;
+    transferred.kit_usage += result.rows_affected;
+
+    let result = kit_usage::Entity::update_many()
+        .col_expr(kit_usage::Column::TargetFilePath, Expr::value(new_path))
+        .col_expr(kit_usage::Column::TargetProjectPath, Expr::value(target_project_path))
+        .col_expr(
+            kit_usage::Column::TargetProjectId,
+            Expr::value(target_project.as_ref().map(|p| p.id.clone())),
+        )
+        .filter(kit_usage::Column::TargetFilePath.eq(old_path))
+        .exec(&txn)
+        .await?;
+    transferred.kit_usage += result.rows_affected;
+
+    // Provenance: the moved file may be an origin or a copy
+    let result = artifact_provenance::Entity::update_many()
+        .col_expr(artifact_provenance::Column::SourcePath, Expr::value(new_path))
+        .filter(artifact_provenance::Column::SourcePath.eq(old_path))
+        .exec(&txn)
+        .await?;
+    transferred.provenance += result.rows_affected;
+
+    let result = artifact_provenance::Entity::update_many()
+        .col_expr(artifact_provenance::Column::LocalPath, Expr::value(new_path))
+        .col_expr(artifact_provenance::Column::ProjectPath, Expr::value(target_project_path))
+        .filter(artifact_provenance::Column::LocalPath.eq(old_path))
+        .exec(&txn)
+        .await?;
+    transferred.provenance += result.rows_affected;
+
+    txn.commit().await?;
+
+    Ok(transferred)
+}
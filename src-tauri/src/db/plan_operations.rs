@@ -2,11 +2,16 @@ use sea_orm::*;
 use sea_orm::sea_query::{Expr, Value};
 use serde::{Deserialize, Serialize};
 use crate::db::entities::{plan, plan_phase, plan_milestone, plan_document, plan_link};
+use crate::core::dry_run::DryRunReport;
 use chrono::Utc;
 use uuid::Uuid;
 use std::path::{Path, PathBuf};
 use std::fs;
 
+/// Valid values for a plan's `status`. Archived plans are kept for
+/// reference but left out of `get_project_plans` unless asked for.
+pub const PLAN_STATUSES: &[&str] = &["active", "completed", "archived"];
+
 /// Plan DTO for frontend communication
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlanDto {
@@ -25,6 +30,8 @@ pub struct PlanDto {
     #[serde(rename = "updatedAt")]
     pub updated_at: i64,
     pub progress: f32, // 0-100 based on milestone completion
+    #[serde(rename = "deletedAt", skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<i64>,
 }
 
 /// Plan Phase DTO
@@ -42,6 +49,11 @@ pub struct PlanPhaseDto {
     pub started_at: Option<i64>,
     #[serde(rename = "completedAt")]
     pub completed_at: Option<i64>,
+    #[serde(rename = "targetDate")]
+    pub target_date: Option<i64>,
+    /// Not completed and past its target date
+    #[serde(rename = "atRisk")]
+    pub at_risk: bool,
     #[serde(rename = "createdAt")]
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
@@ -62,12 +74,56 @@ pub struct PlanMilestoneDto {
     pub completed: bool,
     #[serde(rename = "completedAt")]
     pub completed_at: Option<i64>,
+    #[serde(rename = "dueAt")]
+    pub due_at: Option<i64>,
     #[serde(rename = "createdAt")]
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
     pub updated_at: i64,
 }
 
+impl PlanPhaseDto {
+    fn from_model(phase: plan_phase::Model, milestones: Vec<PlanMilestoneDto>) -> Self {
+        Self {
+            at_risk: phase_at_risk(&phase, Utc::now().timestamp()),
+            id: phase.id,
+            plan_id: phase.plan_id,
+            name: phase.name,
+            description: phase.description,
+            order_index: phase.order_index,
+            status: phase.status,
+            started_at: phase.started_at,
+            completed_at: phase.completed_at,
+            target_date: phase.target_date,
+            created_at: phase.created_at,
+            updated_at: phase.updated_at,
+            milestones,
+        }
+    }
+}
+
+impl From<plan_milestone::Model> for PlanMilestoneDto {
+    fn from(m: plan_milestone::Model) -> Self {
+        Self {
+            id: m.id,
+            phase_id: m.phase_id,
+            name: m.name,
+            description: m.description,
+            order_index: m.order_index,
+            completed: m.completed != 0,
+            completed_at: m.completed_at,
+            due_at: m.due_at,
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+// Not completed and its target date has passed
+fn phase_at_risk(phase: &plan_phase::Model, now: i64) -> bool {
+    phase.status != "completed" && phase.target_date.is_some_and(|target| target < now)
+}
+
 /// Plan Document DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlanDocumentDto {
@@ -86,6 +142,26 @@ pub struct PlanDocumentDto {
     pub updated_at: i64,
     #[serde(rename = "orderIndex")]
     pub order_index: i32,
+    /// Position among the documents of the same phase (or among those
+    /// without one)
+    #[serde(rename = "phaseOrderIndex")]
+    pub phase_order_index: i32,
+}
+
+impl From<plan_document::Model> for PlanDocumentDto {
+    fn from(d: plan_document::Model) -> Self {
+        Self {
+            id: d.id,
+            plan_id: d.plan_id,
+            phase_id: d.phase_id,
+            file_path: d.file_path,
+            file_name: d.file_name,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+            order_index: d.order_index,
+            phase_order_index: d.phase_order_index,
+        }
+    }
 }
 
 /// Plan Link DTO
@@ -101,6 +177,8 @@ pub struct PlanLinkDto {
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
     pub updated_at: i64,
+    /// The linked file no longer exists
+    pub stale: bool,
 }
 
 /// Plan Details DTO (includes phases with milestones and documents)
@@ -125,6 +203,9 @@ pub struct PlanDetailsDto {
     #[serde(rename = "linkedPlans")]
     pub linked_plans: Vec<PlanLinkDto>,
     pub progress: f32, // 0-100 based on milestone completion
+    /// Phases past their target date and not completed
+    #[serde(rename = "atRiskPhases")]
+    pub at_risk_phases: usize,
 }
 
 // Helper function to slugify plan name
@@ -140,8 +221,8 @@ fn slugify(name: &str) -> String {
 }
 
 /// Create a new plan with folder structure
-pub async fn create_plan(
-    db: &DatabaseConnection,
+pub async fn create_plan<C: ConnectionTrait>(
+    db: &C,
     project_id: String,
     project_path: String,
     name: String,
@@ -174,6 +255,7 @@ pub async fn create_plan(
         brainstorm_link: Set(None),
         created_at: Set(now),
         updated_at: Set(now),
+        deleted_at: Set(None),
     };
 
     let plan_model = plan_active_model.insert(db).await?;
@@ -190,20 +272,223 @@ pub async fn create_plan(
         created_at: plan_model.created_at,
         updated_at: plan_model.updated_at,
         progress: 0.0,
+        deleted_at: None,
     })
 }
 
-/// Get all plans for a project
+/// Phases and milestones read from a markdown plan
+#[derive(Debug, Default)]
+struct PlanOutline {
+    title: Option<String>,
+    description: Vec<String>,
+    phases: Vec<OutlinePhase>,
+}
+
+#[derive(Debug, Default)]
+struct OutlinePhase {
+    name: String,
+    description: Vec<String>,
+    /// (name, checked)
+    milestones: Vec<(String, bool)>,
+}
+
+// `# Title`, then one phase per `## Heading` with its `- [ ]` / `- [x]`
+// items (at any depth) as milestones. Other text before the first item of a
+// section becomes its description; items before the first `##` go into a
+// "Tasks" phase. Fenced code is skipped.
+fn parse_plan_outline(markdown: &str) -> PlanOutline {
+    let mut outline = PlanOutline::default();
+    let mut in_code = false;
+
+    for line in crate::commands::strip_front_matter(markdown).lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(title) = trimmed.strip_prefix("# ") {
+            if outline.title.is_none() {
+                outline.title = Some(title.trim().to_string());
+            }
+            continue;
+        }
+        if let Some(name) = trimmed.strip_prefix("## ") {
+            outline.phases.push(OutlinePhase {
+                name: name.trim().trim_end_matches('#').trim().to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+
+        let item = ["- [", "* [", "+ ["].iter().find_map(|marker| trimmed.strip_prefix(marker));
+        if let Some((mark, name)) = item.and_then(|rest| rest.split_once(']')) {
+            let checked = match mark {
+                "x" | "X" => true,
+                " " => false,
+                _ => continue,
+            };
+            if outline.phases.is_empty() {
+                outline.phases.push(OutlinePhase {
+                    name: "Tasks".to_string(),
+                    ..Default::default()
+                });
+            }
+            if let Some(phase) = outline.phases.last_mut() {
+                phase.milestones.push((name.trim().to_string(), checked));
+            }
+            continue;
+        }
+
+        if trimmed.starts_with('#') {
+            continue; // Deeper headings only structure the text
+        }
+        let description = match outline.phases.last_mut() {
+            Some(phase) if phase.milestones.is_empty() => &mut phase.description,
+            Some(_) => continue,
+            None => &mut outline.description,
+        };
+        description.push(trimmed.to_string());
+    }
+    outline
+}
+
+/// Create a plan from a Claude/Cursor markdown plan: each `##` heading
+/// becomes a phase and each checklist item a milestone (checked items are
+/// completed). The source file is linked to the new plan.
+///
+/// The plan is named after the file's `#` title unless `name` is given.
+/// `source` ("claude" or "cursor") is worked out from the path if omitted.
+pub async fn import_plan_from_markdown(
+    db: &DatabaseConnection,
+    project_id: String,
+    project_path: String,
+    file_path: String,
+    name: Option<String>,
+    source: Option<String>,
+) -> Result<PlanDetailsDto, DbErr> {
+    let markdown = fs::read_to_string(&file_path)
+        .map_err(|e| DbErr::Custom(format!("Failed to read plan file: {}", e)))?;
+    let outline = parse_plan_outline(&markdown);
+    if outline.phases.is_empty() {
+        return Err(DbErr::Custom(
+            "No phases (## headings) or checklist items found in plan file".to_string(),
+        ));
+    }
+
+    let name = name
+        .filter(|n| !n.trim().is_empty())
+        .or(outline.title.clone())
+        .unwrap_or_else(|| crate::core::paths::file_stem_lossy(Path::new(&file_path)));
+    let source = source.unwrap_or_else(|| {
+        if file_path.replace('\\', "/").contains("/.cursor/") { "cursor" } else { "claude" }.to_string()
+    });
+    let join = |lines: &[String]| Some(lines.join(" ")).filter(|d| !d.is_empty());
+
+    let txn = db.begin().await?;
+    let plan = create_plan(&txn, project_id, project_path, name, join(&outline.description)).await?;
+
+    let now = Utc::now().timestamp();
+    for (phase_index, phase) in outline.phases.iter().enumerate() {
+        let done = phase.milestones.iter().filter(|(_, checked)| *checked).count();
+        let status = if !phase.milestones.is_empty() && done == phase.milestones.len() {
+            "completed"
+        } else if done > 0 {
+            "in_progress"
+        } else {
+            "pending"
+        };
+        let phase_model = plan_phase::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            plan_id: Set(plan.id.clone()),
+            name: Set(phase.name.clone()),
+            description: Set(join(&phase.description)),
+            order_index: Set(phase_index as i32),
+            status: Set(status.to_string()),
+            started_at: Set((status != "pending").then_some(now)),
+            completed_at: Set((status == "completed").then_some(now)),
+            target_date: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&txn)
+        .await?;
+
+        for (milestone_index, (milestone, checked)) in phase.milestones.iter().enumerate() {
+            plan_milestone::ActiveModel {
+                id: Set(Uuid::new_v4().to_string()),
+                phase_id: Set(phase_model.id.clone()),
+                name: Set(milestone.clone()),
+                description: Set(None),
+                order_index: Set(milestone_index as i32),
+                completed: Set(i32::from(*checked)),
+                completed_at: Set(checked.then_some(now)),
+                due_at: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+            }
+            .insert(&txn)
+            .await?;
+        }
+    }
+
+    plan_link::ActiveModel {
+        id: Set(Uuid::new_v4().to_string()),
+        plan_id: Set(plan.id.clone()),
+        linked_plan_path: Set(file_path),
+        source: Set(source),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+
+    get_plan_details(db, plan.id).await
+}
+
+/// Get all plans for a project. Archived plans are only included with
+/// `include_archived`, after the others.
 pub async fn get_project_plans(
     db: &DatabaseConnection,
     project_id: String,
+    include_archived: bool,
 ) -> Result<Vec<PlanDto>, DbErr> {
-    let plans: Vec<plan::Model> = plan::Entity::find()
+    let mut query = plan::Entity::find()
         .filter(plan::Column::ProjectId.eq(project_id))
+        .filter(plan::Column::DeletedAt.is_null());
+    if !include_archived {
+        query = query.filter(plan::Column::Status.ne("archived"));
+    }
+    let mut plans: Vec<plan::Model> = query
         .order_by_desc(plan::Column::CreatedAt)
         .all(db)
         .await?;
+    // Stable, so each group stays newest first
+    plans.sort_by_key(|p| p.status == "archived");
+
+    plans_to_dtos(db, plans).await
+}
 
+/// Soft-deleted plans of a project, most recently deleted first
+pub async fn get_deleted_plans(
+    db: &DatabaseConnection,
+    project_id: String,
+) -> Result<Vec<PlanDto>, DbErr> {
+    let plans: Vec<plan::Model> = plan::Entity::find()
+        .filter(plan::Column::ProjectId.eq(project_id))
+        .filter(plan::Column::DeletedAt.is_not_null())
+        .order_by_desc(plan::Column::DeletedAt)
+        .all(db)
+        .await?;
+
+    plans_to_dtos(db, plans).await
+}
+
+async fn plans_to_dtos(db: &DatabaseConnection, plans: Vec<plan::Model>) -> Result<Vec<PlanDto>, DbErr> {
     let mut plan_dtos = Vec::new();
     for p in plans {
         let progress = calculate_plan_progress(db, &p.id).await?;
@@ -218,6 +503,7 @@ pub async fn get_project_plans(
             created_at: p.created_at,
             updated_at: p.updated_at,
             progress,
+            deleted_at: p.deleted_at,
         });
     }
 
@@ -258,6 +544,8 @@ pub async fn get_plan_details(
         0.0
     };
 
+    let at_risk_phases = phases.iter().filter(|p| p.at_risk).count();
+
     Ok(PlanDetailsDto {
         id: plan_model.id,
         name: plan_model.name,
@@ -272,6 +560,7 @@ pub async fn get_plan_details(
         documents,
         linked_plans,
         progress,
+        at_risk_phases,
     })
 }
 
@@ -312,19 +601,7 @@ async fn get_plan_phases_with_milestones(
     let mut phase_dtos = Vec::new();
     for phase in phases {
         let milestones = get_phase_milestones(db, &phase.id).await?;
-        phase_dtos.push(PlanPhaseDto {
-            id: phase.id,
-            plan_id: phase.plan_id,
-            name: phase.name,
-            description: phase.description,
-            order_index: phase.order_index,
-            status: phase.status,
-            started_at: phase.started_at,
-            completed_at: phase.completed_at,
-            created_at: phase.created_at,
-            updated_at: phase.updated_at,
-            milestones,
-        });
+        phase_dtos.push(PlanPhaseDto::from_model(phase, milestones));
     }
 
     Ok(phase_dtos)
@@ -341,17 +618,68 @@ async fn get_phase_milestones(
         .all(db)
         .await?;
 
-    Ok(milestones.into_iter().map(|m| PlanMilestoneDto {
-        id: m.id,
-        phase_id: m.phase_id,
-        name: m.name,
-        description: m.description,
-        order_index: m.order_index,
-        completed: m.completed != 0,
-        completed_at: m.completed_at,
-        created_at: m.created_at,
-        updated_at: m.updated_at,
-    }).collect())
+    Ok(milestones.into_iter().map(PlanMilestoneDto::from).collect())
+}
+
+/// An at-risk phase with the plan it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtRiskPhaseDto {
+    #[serde(rename = "planId")]
+    pub plan_id: String,
+    #[serde(rename = "planName")]
+    pub plan_name: String,
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    pub phase: PlanPhaseDto,
+    /// Whole days since the target date
+    #[serde(rename = "daysOverdue")]
+    pub days_overdue: i64,
+}
+
+/// Phases that aren't completed and whose target date has passed, across
+/// the active plans of a project (or of every project), most overdue first
+pub async fn get_at_risk_phases(
+    db: &DatabaseConnection,
+    project_id: Option<String>,
+) -> Result<Vec<AtRiskPhaseDto>, DbErr> {
+    let now = Utc::now().timestamp();
+
+    let mut plans = plan::Entity::find()
+        .filter(plan::Column::DeletedAt.is_null())
+        .filter(plan::Column::Status.eq("active"));
+    if let Some(project_id) = project_id {
+        plans = plans.filter(plan::Column::ProjectId.eq(project_id));
+    }
+    let plans: std::collections::HashMap<String, plan::Model> =
+        plans.all(db).await?.into_iter().map(|p| (p.id.clone(), p)).collect();
+    if plans.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let phases = plan_phase::Entity::find()
+        .filter(plan_phase::Column::PlanId.is_in(plans.keys().cloned()))
+        .filter(plan_phase::Column::Status.ne("completed"))
+        .filter(plan_phase::Column::TargetDate.lt(now))
+        .order_by_asc(plan_phase::Column::TargetDate)
+        .all(db)
+        .await?;
+
+    let mut at_risk = Vec::with_capacity(phases.len());
+    for phase in phases {
+        let Some(plan) = plans.get(&phase.plan_id) else {
+            continue;
+        };
+        let days_overdue = (now - phase.target_date.unwrap_or(now)) / 86_400;
+        let milestones = get_phase_milestones(db, &phase.id).await?;
+        at_risk.push(AtRiskPhaseDto {
+            plan_id: plan.id.clone(),
+            plan_name: plan.name.clone(),
+            project_id: plan.project_id.clone(),
+            phase: PlanPhaseDto::from_model(phase, milestones),
+            days_overdue,
+        });
+    }
+    Ok(at_risk)
 }
 
 // Helper to get documents for a plan
@@ -364,16 +692,31 @@ async fn get_plan_documents_internal(
         .all(db)
         .await?;
 
-    Ok(documents.into_iter().map(|d| PlanDocumentDto {
-        id: d.id,
-        plan_id: d.plan_id,
-        phase_id: d.phase_id,
-        file_path: d.file_path,
-        file_name: d.file_name,
-        created_at: d.created_at,
-        updated_at: d.updated_at,
-        order_index: d.order_index,
-    }).collect())
+    Ok(documents.into_iter().map(PlanDocumentDto::from).collect())
+}
+
+/// IDs of the plans linked to any of `paths`, each once. Paths are compared
+/// canonicalized, since watchers may report them through another prefix.
+pub async fn plan_ids_linked_to(
+    db: &DatabaseConnection,
+    paths: &[String],
+) -> Result<Vec<String>, DbErr> {
+    use crate::core::paths::{canonical_lenient, from_ipc};
+
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+    let changed: std::collections::HashSet<_> = paths.iter().map(|p| canonical_lenient(&from_ipc(p))).collect();
+    let links: Vec<plan_link::Model> = plan_link::Entity::find().all(db).await?;
+
+    let mut plan_ids: Vec<String> = links
+        .into_iter()
+        .filter(|l| changed.contains(&canonical_lenient(&from_ipc(&l.linked_plan_path))))
+        .map(|l| l.plan_id)
+        .collect();
+    plan_ids.sort();
+    plan_ids.dedup();
+    Ok(plan_ids)
 }
 
 // Helper to get linked plans for a plan
@@ -388,6 +731,7 @@ async fn get_plan_links_internal(
         .await?;
 
     Ok(links.into_iter().map(|l| PlanLinkDto {
+        stale: !Path::new(&l.linked_plan_path).is_file(),
         id: l.id,
         plan_id: l.plan_id,
         linked_plan_path: l.linked_plan_path,
@@ -397,6 +741,11 @@ async fn get_plan_links_internal(
     }).collect())
 }
 
+/// The plan's linked external plan files, with missing ones marked stale
+pub async fn get_plan_links(db: &DatabaseConnection, plan_id: &str) -> Result<Vec<PlanLinkDto>, DbErr> {
+    get_plan_links_internal(db, plan_id).await
+}
+
 /// Update a plan
 pub async fn update_plan(
     db: &DatabaseConnection,
@@ -438,6 +787,13 @@ pub async fn update_plan(
     }
 
     if let Some(s) = status {
+        if !PLAN_STATUSES.contains(&s.as_str()) {
+            return Err(DbErr::Custom(format!(
+                "Invalid plan status: {}. Must be one of: {}",
+                s,
+                PLAN_STATUSES.join(", ")
+            )));
+        }
         plan_active_model.status = Set(s);
     }
 
@@ -459,11 +815,267 @@ pub async fn update_plan(
         created_at: updated_plan.created_at,
         updated_at: updated_plan.updated_at,
         progress,
+        deleted_at: updated_plan.deleted_at,
     })
 }
 
-/// Delete a plan (removes folder and database records)
-pub async fn delete_plan(
+/// Copy a plan: a new folder (with the original's files) and new phases,
+/// milestones, document records and links.
+///
+/// The copy is named `name`, or "<original> (copy)" numbered until its folder
+/// is free. Unless `keep_progress` is set, phases start out pending,
+/// milestones unchecked and target and due dates cleared, so the copy can be
+/// reused as a fresh plan.
+pub async fn duplicate_plan(
+    db: &DatabaseConnection,
+    plan_id: String,
+    name: Option<String>,
+    keep_progress: bool,
+) -> Result<PlanDetailsDto, DbErr> {
+    let source = plan::Entity::find_by_id(&plan_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Plan not found: {}", plan_id)))?;
+    let source_folder = PathBuf::from(&source.folder_path);
+    let plans_dir = source_folder
+        .parent()
+        .ok_or_else(|| DbErr::Custom(format!("Invalid plan folder: {}", source.folder_path)))?;
+    // create_plan puts the folder at <project>/.bluekit/plans/<slug>
+    let project_path = plans_dir
+        .parent()
+        .and_then(Path::parent)
+        .ok_or_else(|| DbErr::Custom(format!("Invalid plan folder: {}", source.folder_path)))?;
+
+    let name = match name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) {
+        Some(name) => {
+            if plans_dir.join(slugify(&name)).exists() {
+                return Err(DbErr::Custom(format!("A plan folder for \"{}\" already exists", name)));
+            }
+            name
+        }
+        None => (1..)
+            .map(|n| match n {
+                1 => format!("{} (copy)", source.name),
+                n => format!("{} (copy {})", source.name, n),
+            })
+            .find(|candidate| !plans_dir.join(slugify(candidate)).exists())
+            .unwrap_or_default(),
+    };
+
+    let txn = db.begin().await?;
+    let plan = create_plan(
+        &txn,
+        source.project_id.clone(),
+        project_path.to_string_lossy().to_string(),
+        name,
+        source.description.clone(),
+    )
+    .await?;
+    let target_folder = PathBuf::from(&plan.folder_path);
+
+    let copied = async {
+        copy_plan_folder(&source_folder, &target_folder)?;
+        copy_plan_records(&txn, &source, &plan.id, &target_folder, keep_progress).await?;
+        txn.commit().await
+    }
+    .await;
+    if let Err(e) = copied {
+        let _ = fs::remove_dir_all(&target_folder);
+        return Err(e);
+    }
+
+    get_plan_details(db, plan.id).await
+}
+
+// Copies a plan folder's files and subfolders, leaving out exports (they
+// are named after the original plan)
+fn copy_plan_folder(source: &Path, target: &Path) -> Result<(), DbErr> {
+    let Ok(entries) = fs::read_dir(source) else {
+        return Ok(()); // Nothing to copy if the folder went missing
+    };
+    fs::create_dir_all(target)
+        .map_err(|e| DbErr::Custom(format!("Failed to create plan folder: {}", e)))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let destination = target.join(entry.file_name());
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => copy_plan_folder(&path, &destination)?,
+            Ok(file_type) if file_type.is_file() && !path.to_string_lossy().ends_with(EXPORT_SUFFIX) => {
+                fs::copy(&path, &destination).map_err(|e| {
+                    DbErr::Custom(format!("Failed to copy {}: {}", path.display(), e))
+                })?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// Copies phases, milestones, document records and links of `source` to the
+// plan `plan_id`, whose folder is `target_folder`
+async fn copy_plan_records<C: ConnectionTrait>(
+    db: &C,
+    source: &plan::Model,
+    plan_id: &str,
+    target_folder: &Path,
+    keep_progress: bool,
+) -> Result<(), DbErr> {
+    let now = Utc::now().timestamp();
+
+    if source.brainstorm_link.is_some() {
+        plan::ActiveModel {
+            id: Set(plan_id.to_string()),
+            brainstorm_link: Set(source.brainstorm_link.clone()),
+            ..Default::default()
+        }
+        .update(db)
+        .await?;
+    }
+
+    let phases = plan_phase::Entity::find()
+        .filter(plan_phase::Column::PlanId.eq(&source.id))
+        .order_by_asc(plan_phase::Column::OrderIndex)
+        .all(db)
+        .await?;
+    let mut phase_ids = std::collections::HashMap::new();
+    for phase in phases {
+        let new_id = Uuid::new_v4().to_string();
+        phase_ids.insert(phase.id.clone(), new_id.clone());
+        let milestones = plan_milestone::Entity::find()
+            .filter(plan_milestone::Column::PhaseId.eq(&phase.id))
+            .order_by_asc(plan_milestone::Column::OrderIndex)
+            .all(db)
+            .await?;
+
+        plan_phase::ActiveModel {
+            id: Set(new_id.clone()),
+            plan_id: Set(plan_id.to_string()),
+            name: Set(phase.name),
+            description: Set(phase.description),
+            order_index: Set(phase.order_index),
+            status: Set(if keep_progress { phase.status } else { "pending".to_string() }),
+            started_at: Set(phase.started_at.filter(|_| keep_progress)),
+            completed_at: Set(phase.completed_at.filter(|_| keep_progress)),
+            target_date: Set(phase.target_date.filter(|_| keep_progress)),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(db)
+        .await?;
+
+        for milestone in milestones {
+            plan_milestone::ActiveModel {
+                id: Set(Uuid::new_v4().to_string()),
+                phase_id: Set(new_id.clone()),
+                name: Set(milestone.name),
+                description: Set(milestone.description),
+                order_index: Set(milestone.order_index),
+                completed: Set(if keep_progress { milestone.completed } else { 0 }),
+                completed_at: Set(milestone.completed_at.filter(|_| keep_progress)),
+                due_at: Set(milestone.due_at.filter(|_| keep_progress)),
+                created_at: Set(now),
+                updated_at: Set(now),
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+
+    let documents = plan_document::Entity::find()
+        .filter(plan_document::Column::PlanId.eq(&source.id))
+        .all(db)
+        .await?;
+    for document in documents {
+        // Files outside the plan folder weren't copied
+        let Ok(relative) = Path::new(&document.file_path).strip_prefix(&source.folder_path) else {
+            continue;
+        };
+        let file_path = target_folder.join(relative);
+        if !file_path.exists() {
+            continue;
+        }
+        plan_document::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            plan_id: Set(plan_id.to_string()),
+            phase_id: Set(document.phase_id.and_then(|id| phase_ids.get(&id).cloned())),
+            file_path: Set(file_path.to_string_lossy().to_string()),
+            file_name: Set(document.file_name),
+            created_at: Set(now),
+            updated_at: Set(now),
+            order_index: Set(document.order_index),
+            phase_order_index: Set(document.phase_order_index),
+        }
+        .insert(db)
+        .await?;
+    }
+
+    let links = plan_link::Entity::find()
+        .filter(plan_link::Column::PlanId.eq(&source.id))
+        .all(db)
+        .await?;
+    for link in links {
+        plan_link::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            plan_id: Set(plan_id.to_string()),
+            linked_plan_path: Set(link.linked_plan_path),
+            source: Set(link.source),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Soft-deletes a plan. Its folder and records stay in place so
+/// `restore_plan` can bring it back until it's purged.
+pub async fn delete_plan(db: &DatabaseConnection, plan_id: &str) -> Result<(), DbErr> {
+    set_plan_deleted_at(db, plan_id, Some(Utc::now().timestamp())).await?;
+    Ok(())
+}
+
+/// Brings back a soft-deleted plan
+pub async fn restore_plan(db: &DatabaseConnection, plan_id: &str) -> Result<PlanDto, DbErr> {
+    let restored = set_plan_deleted_at(db, plan_id, None).await?;
+    let mut dtos = plans_to_dtos(db, vec![restored]).await?;
+    Ok(dtos.remove(0))
+}
+
+async fn set_plan_deleted_at(
+    db: &DatabaseConnection,
+    plan_id: &str,
+    deleted_at: Option<i64>,
+) -> Result<plan::Model, DbErr> {
+    let plan_model = plan::Entity::find_by_id(plan_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Plan not found: {}", plan_id)))?;
+    if plan_model.deleted_at.is_some() == deleted_at.is_some() {
+        return Ok(plan_model);
+    }
+
+    let mut plan_active_model: plan::ActiveModel = plan_model.into();
+    plan_active_model.deleted_at = Set(deleted_at);
+    plan_active_model.updated_at = Set(Utc::now().timestamp());
+    plan_active_model.update(db).await
+}
+
+/// Permanently deletes plans soft-deleted before `before` (Unix seconds),
+/// folders included. Returns how many were removed.
+pub async fn purge_deleted_plans(db: &DatabaseConnection, before: i64) -> Result<usize, DbErr> {
+    let expired = plan::Entity::find()
+        .filter(plan::Column::DeletedAt.lt(before))
+        .all(db)
+        .await?;
+    for plan_model in &expired {
+        purge_plan(db, plan_model.id.clone()).await?;
+    }
+    Ok(expired.len())
+}
+
+/// Permanently delete a plan (removes folder and database records)
+pub async fn purge_plan(
     db: &DatabaseConnection,
     plan_id: String,
 ) -> Result<(), DbErr> {
@@ -485,6 +1097,73 @@ pub async fn delete_plan(
     Ok(())
 }
 
+/// List what `purge_plan` would delete (folder contents and cascaded rows)
+pub async fn preview_delete_plan(
+    db: &DatabaseConnection,
+    plan_id: &str,
+) -> Result<DryRunReport, DbErr> {
+    let plan_model = plan::Entity::find_by_id(plan_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Plan not found: {}", plan_id)))?;
+
+    let mut report = DryRunReport::default();
+    collect_files_recursive(Path::new(&plan_model.folder_path), &mut report.deleted_files);
+    report.deleted_files.sort();
+
+    report.delete_row("plans", &plan_model.id, &plan_model.name);
+
+    let phases = plan_phase::Entity::find()
+        .filter(plan_phase::Column::PlanId.eq(plan_id))
+        .all(db)
+        .await?;
+    for phase in &phases {
+        report.delete_row("plan_phases", &phase.id, &phase.name);
+    }
+
+    let phase_ids: Vec<String> = phases.into_iter().map(|p| p.id).collect();
+    let milestones = plan_milestone::Entity::find()
+        .filter(plan_milestone::Column::PhaseId.is_in(phase_ids))
+        .all(db)
+        .await?;
+    for milestone in milestones {
+        report.delete_row("plan_milestones", &milestone.id, &milestone.name);
+    }
+
+    let documents = plan_document::Entity::find()
+        .filter(plan_document::Column::PlanId.eq(plan_id))
+        .all(db)
+        .await?;
+    for document in documents {
+        report.delete_row("plan_documents", &document.id, &document.file_path);
+    }
+
+    let links = plan_link::Entity::find()
+        .filter(plan_link::Column::PlanId.eq(plan_id))
+        .all(db)
+        .await?;
+    for link in links {
+        report.delete_row("plan_links", &link.id, &link.linked_plan_path);
+    }
+
+    Ok(report)
+}
+
+fn collect_files_recursive(dir: &Path, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => collect_files_recursive(&path, files),
+            Ok(_) => files.push(path.to_string_lossy().to_string()),
+            Err(_) => {}
+        }
+    }
+}
+
 /// Link brainstorm plan to a plan (legacy - maintains backward compatibility)
 pub async fn link_brainstorm_to_plan(
     db: &DatabaseConnection,
@@ -605,6 +1284,7 @@ pub async fn create_plan_phase(
     name: String,
     description: Option<String>,
     order_index: i32,
+    target_date: Option<i64>,
 ) -> Result<PlanPhaseDto, DbErr> {
     let now = Utc::now().timestamp();
     let phase_id = Uuid::new_v4().to_string();
@@ -618,25 +1298,14 @@ pub async fn create_plan_phase(
         status: Set("pending".to_string()),
         started_at: Set(None),
         completed_at: Set(None),
+        target_date: Set(target_date),
         created_at: Set(now),
         updated_at: Set(now),
     };
 
     let phase_model = phase_active_model.insert(db).await?;
 
-    Ok(PlanPhaseDto {
-        id: phase_model.id,
-        plan_id: phase_model.plan_id,
-        name: phase_model.name,
-        description: phase_model.description,
-        order_index: phase_model.order_index,
-        status: phase_model.status,
-        started_at: phase_model.started_at,
-        completed_at: phase_model.completed_at,
-        created_at: phase_model.created_at,
-        updated_at: phase_model.updated_at,
-        milestones: vec![],
-    })
+    Ok(PlanPhaseDto::from_model(phase_model, Vec::new()))
 }
 
 /// Update a plan phase
@@ -647,6 +1316,7 @@ pub async fn update_plan_phase(
     description: Option<Option<String>>,
     status: Option<String>,
     order_index: Option<i32>,
+    target_date: Option<Option<i64>>,
 ) -> Result<PlanPhaseDto, DbErr> {
     let now = Utc::now().timestamp();
 
@@ -669,6 +1339,10 @@ pub async fn update_plan_phase(
         phase_active_model.order_index = Set(o);
     }
 
+    if let Some(t) = target_date {
+        phase_active_model.target_date = Set(t);
+    }
+
     // Handle status change and auto-complete milestones if status changed to 'completed'
     if let Some(s) = status {
         let old_status = phase_model.status.clone();
@@ -703,19 +1377,7 @@ pub async fn update_plan_phase(
     // Get milestones
     let milestones = get_phase_milestones(db, &updated_phase.id).await?;
 
-    Ok(PlanPhaseDto {
-        id: updated_phase.id,
-        plan_id: updated_phase.plan_id,
-        name: updated_phase.name,
-        description: updated_phase.description,
-        order_index: updated_phase.order_index,
-        status: updated_phase.status,
-        started_at: updated_phase.started_at,
-        completed_at: updated_phase.completed_at,
-        created_at: updated_phase.created_at,
-        updated_at: updated_phase.updated_at,
-        milestones,
-    })
+    Ok(PlanPhaseDto::from_model(updated_phase, milestones))
 }
 
 /// Delete a plan phase
@@ -771,6 +1433,7 @@ pub async fn create_plan_milestone(
     name: String,
     description: Option<String>,
     order_index: i32,
+    due_at: Option<i64>,
 ) -> Result<PlanMilestoneDto, DbErr> {
     let now = Utc::now().timestamp();
     let milestone_id = Uuid::new_v4().to_string();
@@ -783,23 +1446,14 @@ pub async fn create_plan_milestone(
         order_index: Set(order_index),
         completed: Set(0),
         completed_at: Set(None),
+        due_at: Set(due_at),
         created_at: Set(now),
         updated_at: Set(now),
     };
 
     let milestone_model = milestone_active_model.insert(db).await?;
 
-    Ok(PlanMilestoneDto {
-        id: milestone_model.id,
-        phase_id: milestone_model.phase_id,
-        name: milestone_model.name,
-        description: milestone_model.description,
-        order_index: milestone_model.order_index,
-        completed: milestone_model.completed != 0,
-        completed_at: milestone_model.completed_at,
-        created_at: milestone_model.created_at,
-        updated_at: milestone_model.updated_at,
-    })
+    Ok(PlanMilestoneDto::from(milestone_model))
 }
 
 /// Update a plan milestone
@@ -809,6 +1463,7 @@ pub async fn update_plan_milestone(
     name: Option<String>,
     description: Option<Option<String>>,
     completed: Option<bool>,
+    due_at: Option<Option<i64>>,
 ) -> Result<PlanMilestoneDto, DbErr> {
     let now = Utc::now().timestamp();
 
@@ -832,21 +1487,15 @@ pub async fn update_plan_milestone(
         milestone_active.completed_at = Set(if c { Some(now) } else { None });
     }
 
+    if let Some(d) = due_at {
+        milestone_active.due_at = Set(d);
+    }
+
     milestone_active.updated_at = Set(now);
 
     let updated_milestone = milestone_active.update(db).await?;
 
-    Ok(PlanMilestoneDto {
-        id: updated_milestone.id,
-        phase_id: updated_milestone.phase_id,
-        name: updated_milestone.name,
-        description: updated_milestone.description,
-        order_index: updated_milestone.order_index,
-        completed: updated_milestone.completed != 0,
-        completed_at: updated_milestone.completed_at,
-        created_at: updated_milestone.created_at,
-        updated_at: updated_milestone.updated_at,
-    })
+    Ok(PlanMilestoneDto::from(updated_milestone))
 }
 
 /// Delete a plan milestone
@@ -879,17 +1528,7 @@ pub async fn toggle_milestone_completion(
 
     let updated_milestone = milestone_active.update(db).await?;
 
-    Ok(PlanMilestoneDto {
-        id: updated_milestone.id,
-        phase_id: updated_milestone.phase_id,
-        name: updated_milestone.name,
-        description: updated_milestone.description,
-        order_index: updated_milestone.order_index,
-        completed: updated_milestone.completed != 0,
-        completed_at: updated_milestone.completed_at,
-        created_at: updated_milestone.created_at,
-        updated_at: updated_milestone.updated_at,
-    })
+    Ok(PlanMilestoneDto::from(updated_milestone))
 }
 
 /// Get plan documents (scans folder and reconciles with DB)
@@ -905,6 +1544,9 @@ pub async fn get_plan_documents(
 
     let folder_path = Path::new(&plan_model.folder_path);
 
+    // Scans of the same folder would both insert records for new files
+    let _lock = crate::core::file_lock::lock(folder_path, "plan document scan").await?;
+
     // Get existing documents from DB sorted by order_index
     let existing_docs: Vec<plan_document::Model> = plan_document::Entity::find()
         .filter(plan_document::Column::PlanId.eq(&plan_id))
@@ -920,6 +1562,12 @@ pub async fn get_plan_documents(
         .map(|d| d.order_index)
         .max()
         .unwrap_or(-1) + 1;
+    // New documents go after the others without a phase
+    let mut next_phase_order_index = existing_docs.iter()
+        .filter(|d| d.phase_id.is_none())
+        .map(|d| d.phase_order_index)
+        .max()
+        .unwrap_or(-1) + 1;
 
     // Scan folder for .md files
     let mut documents = Vec::new();
@@ -931,23 +1579,15 @@ pub async fn get_plan_documents(
             let entry = entry.map_err(|e| DbErr::Custom(format!("Failed to read entry: {}", e)))?;
             let path = entry.path();
 
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
+            let is_export = path.to_string_lossy().ends_with(EXPORT_SUFFIX);
+            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") && !is_export {
                 let file_path_str = path.to_string_lossy().to_string();
                 let file_name = path.file_name().unwrap().to_string_lossy().to_string();
 
                 // Check if document exists in DB
                 if let Some(doc) = existing_paths.remove(&file_path_str) {
                     // Exists, use DB record
-                    documents.push(PlanDocumentDto {
-                        id: doc.id,
-                        plan_id: doc.plan_id,
-                        phase_id: doc.phase_id,
-                        file_path: doc.file_path,
-                        file_name: doc.file_name,
-                        created_at: doc.created_at,
-                        updated_at: doc.updated_at,
-                        order_index: doc.order_index,
-                    });
+                    documents.push(PlanDocumentDto::from(doc));
                 } else {
                     // New file, create DB record
                     let now = Utc::now().timestamp();
@@ -962,22 +1602,15 @@ pub async fn get_plan_documents(
                         created_at: Set(now),
                         updated_at: Set(now),
                         order_index: Set(next_order_index),
+                        phase_order_index: Set(next_phase_order_index),
                     };
 
                     next_order_index += 1;
+                    next_phase_order_index += 1;
 
                     let doc_model = doc_active.insert(db).await?;
 
-                    documents.push(PlanDocumentDto {
-                        id: doc_model.id,
-                        plan_id: doc_model.plan_id,
-                        phase_id: doc_model.phase_id,
-                        file_path: doc_model.file_path,
-                        file_name: doc_model.file_name,
-                        created_at: doc_model.created_at,
-                        updated_at: doc_model.updated_at,
-                        order_index: doc_model.order_index,
-                    });
+                    documents.push(PlanDocumentDto::from(doc_model));
                 }
             }
         }
@@ -994,26 +1627,109 @@ pub async fn get_plan_documents(
     Ok(documents)
 }
 
-/// Link document to phase
+/// Link document to phase, as the last of its documents
 pub async fn link_document_to_phase(
     db: &DatabaseConnection,
     document_id: String,
     phase_id: Option<String>,
 ) -> Result<(), DbErr> {
-    let now = Utc::now().timestamp();
+    move_document_to_phase(db, document_id, phase_id, None).await?;
+    Ok(())
+}
+
+/// Move a document to `position` (0-based) among the documents of
+/// `phase_id`, or among those without a phase when it's none. Without a
+/// position, or past the end, the document goes last.
+///
+/// Both the old and the new phase are renumbered in one transaction.
+/// Returns the documents of the new phase in order.
+pub async fn move_document_to_phase(
+    db: &DatabaseConnection,
+    document_id: String,
+    phase_id: Option<String>,
+    position: Option<usize>,
+) -> Result<Vec<PlanDocumentDto>, DbErr> {
+    let txn = db.begin().await?;
 
     let doc_model = plan_document::Entity::find_by_id(&document_id)
-        .one(db)
+        .one(&txn)
         .await?
         .ok_or_else(|| DbErr::RecordNotFound(format!("Document not found: {}", document_id)))?;
 
+    if let Some(phase_id) = &phase_id {
+        let phase_model = plan_phase::Entity::find_by_id(phase_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Phase not found: {}", phase_id)))?;
+        if phase_model.plan_id != doc_model.plan_id {
+            return Err(DbErr::Custom("Phase does not belong to the document's plan".to_string()));
+        }
+    }
+
+    let now = Utc::now().timestamp();
+    let plan_id = doc_model.plan_id.clone();
+    let old_phase_id = doc_model.phase_id.clone();
+
+    if old_phase_id != phase_id {
+        let remaining = documents_of_phase(&txn, &plan_id, old_phase_id.as_deref(), &document_id).await?;
+        renumber_phase_documents(&txn, remaining, now).await?;
+    }
+
+    let mut siblings = documents_of_phase(&txn, &plan_id, phase_id.as_deref(), &document_id).await?;
+    let position = position.unwrap_or(siblings.len()).min(siblings.len());
+
     let mut doc_active: plan_document::ActiveModel = doc_model.into();
     doc_active.phase_id = Set(phase_id);
     doc_active.updated_at = Set(now);
+    siblings.insert(position, doc_active.update(&txn).await?);
 
-    doc_active.update(db).await?;
+    let documents = renumber_phase_documents(&txn, siblings, now).await?;
+    txn.commit().await?;
 
-    Ok(())
+    Ok(documents.into_iter().map(PlanDocumentDto::from).collect())
+}
+
+// Documents of one phase of a plan (or those without a phase) in order,
+// leaving out `except`
+async fn documents_of_phase<C: ConnectionTrait>(
+    db: &C,
+    plan_id: &str,
+    phase_id: Option<&str>,
+    except: &str,
+) -> Result<Vec<plan_document::Model>, DbErr> {
+    let query = plan_document::Entity::find()
+        .filter(plan_document::Column::PlanId.eq(plan_id))
+        .filter(plan_document::Column::Id.ne(except));
+    let query = match phase_id {
+        Some(phase_id) => query.filter(plan_document::Column::PhaseId.eq(phase_id)),
+        None => query.filter(plan_document::Column::PhaseId.is_null()),
+    };
+    query
+        .order_by_asc(plan_document::Column::PhaseOrderIndex)
+        .order_by_asc(plan_document::Column::OrderIndex)
+        .all(db)
+        .await
+}
+
+// Gives `documents` consecutive phase positions in the order given,
+// updating only the ones that change
+async fn renumber_phase_documents<C: ConnectionTrait>(
+    db: &C,
+    documents: Vec<plan_document::Model>,
+    now: i64,
+) -> Result<Vec<plan_document::Model>, DbErr> {
+    let mut renumbered = Vec::with_capacity(documents.len());
+    for (index, doc) in documents.into_iter().enumerate() {
+        if doc.phase_order_index == index as i32 {
+            renumbered.push(doc);
+            continue;
+        }
+        let mut doc_active: plan_document::ActiveModel = doc.into();
+        doc_active.phase_order_index = Set(index as i32);
+        doc_active.updated_at = Set(now);
+        renumbered.push(doc_active.update(db).await?);
+    }
+    Ok(renumbered)
 }
 
 /// Reorder plan documents
@@ -1043,3 +1759,504 @@ pub async fn reorder_plan_documents(
 
     Ok(())
 }
+
+/// Suffix of the file `export_plan_markdown` writes into the plan folder.
+/// Files with it aren't plan documents.
+pub const EXPORT_SUFFIX: &str = ".export.md";
+
+/// Plan exported as a single markdown document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanExportDto {
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    pub content: String,
+}
+
+/// Render a plan as one standalone markdown document and write it into the
+/// plan folder as `<name>.export.md`.
+///
+/// The document has the plan's status and progress, each phase with its
+/// milestones as checkboxes, the linked plans, and the full text of every
+/// plan document (front matter removed, headings nested under the
+/// document's own). It is overwritten on every export.
+pub async fn export_plan_markdown(db: &DatabaseConnection, plan_id: String) -> Result<PlanExportDto, DbErr> {
+    let details = get_plan_details(db, plan_id).await?;
+    let content = render_plan_markdown(&details);
+
+    let file_path = Path::new(&details.folder_path).join(format!("{}{}", slugify(&details.name), EXPORT_SUFFIX));
+    fs::write(&file_path, &content)
+        .map_err(|e| DbErr::Custom(format!("Failed to write plan export: {}", e)))?;
+
+    Ok(PlanExportDto {
+        file_path: file_path.to_string_lossy().to_string(),
+        content,
+    })
+}
+
+fn render_plan_markdown(plan: &PlanDetailsDto) -> String {
+    let date = |timestamp: i64| {
+        chrono::DateTime::from_timestamp(timestamp, 0)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    };
+    let total: usize = plan.phases.iter().map(|p| p.milestones.len()).sum();
+    let done: usize = plan
+        .phases
+        .iter()
+        .map(|p| p.milestones.iter().filter(|m| m.completed).count())
+        .sum();
+
+    let mut out = format!("# {}\n\n", plan.name);
+    out.push_str(&format!(
+        "**Status:** {} · **Progress:** {:.0}% ({}/{} milestones) · **Exported:** {}\n\n",
+        plan.status,
+        plan.progress,
+        done,
+        total,
+        Utc::now().format("%Y-%m-%d")
+    ));
+    if let Some(description) = plan.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        out.push_str(description.trim());
+        out.push_str("\n\n");
+    }
+
+    if !plan.phases.is_empty() {
+        out.push_str("## Phases\n\n");
+    }
+    for (index, phase) in plan.phases.iter().enumerate() {
+        out.push_str(&format!("### {}. {} ({})\n\n", index + 1, phase.name, phase.status.replace('_', " ")));
+        if let Some(description) = phase.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            out.push_str(description.trim());
+            out.push_str("\n\n");
+        }
+        for milestone in &phase.milestones {
+            let mut line = format!("- [{}] {}", if milestone.completed { "x" } else { " " }, milestone.name);
+            if let Some(description) = milestone.description.as_deref().filter(|d| !d.trim().is_empty()) {
+                line.push_str(&format!(" — {}", description.trim()));
+            }
+            if let Some(completed_at) = milestone.completed_at.filter(|_| milestone.completed) {
+                line.push_str(&format!(" _(done {})_", date(completed_at)));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        let mut phase_documents: Vec<&PlanDocumentDto> = plan
+            .documents
+            .iter()
+            .filter(|d| d.phase_id.as_deref() == Some(phase.id.as_str()))
+            .collect();
+        phase_documents.sort_by_key(|d| d.phase_order_index);
+        let phase_documents: Vec<&str> = phase_documents.iter().map(|d| d.file_name.as_str()).collect();
+        if !phase_documents.is_empty() {
+            out.push_str(&format!("\nDocuments: {}\n", phase_documents.join(", ")));
+        }
+        out.push('\n');
+    }
+
+    if !plan.linked_plans.is_empty() {
+        out.push_str("## Linked plans\n\n");
+        for link in &plan.linked_plans {
+            let name = Path::new(&link.linked_plan_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| link.linked_plan_path.clone());
+            out.push_str(&format!("- {} ({})\n", name, link.source));
+        }
+        out.push('\n');
+    }
+
+    if !plan.documents.is_empty() {
+        out.push_str("## Documents\n\n");
+    }
+    for document in &plan.documents {
+        out.push_str(&format!("### {}\n\n", document.file_name.trim_end_matches(".md")));
+        match fs::read_to_string(&document.file_path) {
+            Ok(text) => {
+                let body = nest_headings(crate::commands::strip_front_matter(&text), 3);
+                out.push_str(body.trim());
+                out.push_str("\n\n");
+            }
+            Err(e) => out.push_str(&format!("_Could not read this document: {}_\n\n", e)),
+        }
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+// Pushes ATX headings down `levels` levels (at most to `######`), leaving
+// fenced code alone
+fn nest_headings(markdown: &str, levels: usize) -> String {
+    let mut in_code = false;
+    let mut lines = Vec::new();
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+        }
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        let is_heading = (1..=6).contains(&hashes) && trimmed[hashes..].starts_with([' ', '\t']);
+        if in_code || !is_heading {
+            lines.push(line.to_string());
+        } else {
+            let level = (hashes + levels).min(6);
+            lines.push(format!("{}{}", "#".repeat(level), &trimmed[hashes..]));
+        }
+    }
+    lines.join("\n")
+}
+
+/// A plan laid out over time, for timeline and Gantt charts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanTimelineDto {
+    #[serde(rename = "planId")]
+    pub plan_id: String,
+    /// Earliest phase start (or plan creation)
+    pub start: i64,
+    /// Latest phase end
+    pub end: i64,
+    /// When the timeline was computed
+    pub now: i64,
+    pub phases: Vec<TimelinePhaseDto>,
+    /// Cumulative milestone completions, one point per day with any
+    pub trend: Vec<TimelineTrendPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelinePhaseDto {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub start: i64,
+    /// No `started_at` yet; the start follows the previous phase
+    #[serde(rename = "startEstimated")]
+    pub start_estimated: bool,
+    pub end: i64,
+    /// "completed", "target" (the target date) or "ongoing" (now)
+    #[serde(rename = "endKind")]
+    pub end_kind: String,
+    #[serde(rename = "targetDate")]
+    pub target_date: Option<i64>,
+    #[serde(rename = "atRisk")]
+    pub at_risk: bool,
+    pub milestones: Vec<TimelineMilestoneDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineMilestoneDto {
+    pub id: String,
+    pub name: String,
+    pub completed: bool,
+    /// Completion time, else the due date, if either is known
+    pub at: Option<i64>,
+    #[serde(rename = "dueAt")]
+    pub due_at: Option<i64>,
+    /// Not completed and past its due date
+    pub overdue: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineTrendPoint {
+    /// YYYY-MM-DD (UTC)
+    pub date: String,
+    /// Milestones completed by the end of the day
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Phase windows and milestone trend for a plan.
+///
+/// A phase runs from `started_at` (or, before it starts, the end of the
+/// phase before it) to `completed_at`, else its target date, else now.
+/// Phases that would end before they start end at their start.
+pub async fn get_plan_timeline(db: &DatabaseConnection, plan_id: String) -> Result<PlanTimelineDto, DbErr> {
+    let plan_model = plan::Entity::find_by_id(&plan_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Plan not found: {}", plan_id)))?;
+    let phases = get_plan_phases_with_milestones(db, &plan_id).await?;
+    let now = Utc::now().timestamp();
+
+    let mut previous_end = plan_model.created_at;
+    let mut timeline_phases = Vec::with_capacity(phases.len());
+    for phase in &phases {
+        let start = phase.started_at.unwrap_or(previous_end);
+        let (end, end_kind) = match (phase.completed_at, phase.target_date) {
+            (Some(completed_at), _) if phase.status == "completed" => (completed_at, "completed"),
+            (_, Some(target)) => (target, "target"),
+            _ => (now, "ongoing"),
+        };
+        let end = end.max(start);
+        previous_end = end;
+
+        let milestones = phase
+            .milestones
+            .iter()
+            .map(|m| TimelineMilestoneDto {
+                id: m.id.clone(),
+                name: m.name.clone(),
+                completed: m.completed,
+                at: m.completed_at.filter(|_| m.completed).or(m.due_at),
+                due_at: m.due_at,
+                overdue: !m.completed && m.due_at.is_some_and(|due| due < now),
+            })
+            .collect();
+
+        timeline_phases.push(TimelinePhaseDto {
+            id: phase.id.clone(),
+            name: phase.name.clone(),
+            status: phase.status.clone(),
+            start,
+            start_estimated: phase.started_at.is_none(),
+            end,
+            end_kind: end_kind.to_string(),
+            target_date: phase.target_date,
+            at_risk: phase.at_risk,
+            milestones,
+        });
+    }
+
+    let start = timeline_phases.iter().map(|p| p.start).min().unwrap_or(plan_model.created_at);
+    let end = timeline_phases.iter().map(|p| p.end).max().unwrap_or(now).max(start);
+
+    Ok(PlanTimelineDto {
+        plan_id,
+        start,
+        end,
+        now,
+        trend: completion_trend(&phases),
+        phases: timeline_phases,
+    })
+}
+
+fn completion_trend(phases: &[PlanPhaseDto]) -> Vec<TimelineTrendPoint> {
+    let milestones: Vec<&PlanMilestoneDto> = phases.iter().flat_map(|p| &p.milestones).collect();
+    let mut completions: Vec<i64> = milestones
+        .iter()
+        .filter(|m| m.completed)
+        .filter_map(|m| m.completed_at)
+        .collect();
+    completions.sort_unstable();
+
+    let mut trend: Vec<TimelineTrendPoint> = Vec::new();
+    for (index, completed_at) in completions.iter().enumerate() {
+        let date = chrono::DateTime::from_timestamp(*completed_at, 0)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        match trend.last_mut() {
+            Some(point) if point.date == date => point.completed = index + 1,
+            _ => trend.push(TimelineTrendPoint {
+                date,
+                completed: index + 1,
+                total: milestones.len(),
+            }),
+        }
+    }
+    trend
+}
+
+/// Generated plan diagram
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanDiagramDto {
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    /// "flowchart" or "gantt"
+    pub format: String,
+    pub content: String,
+}
+
+/// Render a plan's phases, milestones and linked plans as a mermaid diagram
+/// and write it to the project's `.bluekit/diagrams/`.
+///
+/// The file (`plan-<name>.mmd`, or `plan-<name>-gantt.mmd`) is overwritten
+/// each time, so it always reflects the plan's current state.
+pub async fn generate_plan_diagram(
+    db: &DatabaseConnection,
+    plan_id: String,
+    format: Option<String>,
+) -> Result<PlanDiagramDto, DbErr> {
+    let format = format.unwrap_or_else(|| "flowchart".to_string());
+    if format != "flowchart" && format != "gantt" {
+        return Err(DbErr::Custom(format!("Unknown diagram format: {}", format)));
+    }
+
+    let plan_model = plan::Entity::find_by_id(&plan_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Plan not found: {}", plan_id)))?;
+    let phases = get_plan_phases_with_milestones(db, &plan_id).await?;
+    let linked_plans = get_plan_links_internal(db, &plan_id).await?;
+
+    let (alias, diagram) = if format == "gantt" {
+        (format!("Plan {} Gantt", plan_model.name), render_plan_gantt(&plan_model, &phases))
+    } else {
+        (format!("Plan {}", plan_model.name), render_plan_flowchart(&plan_model, &phases, &linked_plans))
+    };
+
+    // The same front matter as diagrams created in the app, so schema and
+    // publish checks accept the file
+    let description = format!("Phases and milestones of the \"{}\" plan", plan_model.name);
+    let front_matter = crate::db::artifact_operations::base_front_matter(
+        "diagram",
+        &alias,
+        Some(&description),
+        &["plan".to_string()],
+    );
+    let yaml = serde_yaml::to_string(&front_matter)
+        .map_err(|e| DbErr::Custom(format!("Failed to serialize front matter: {}", e)))?;
+    let content = format!("---\n{}---\n\n{}", yaml, diagram);
+
+    // Plan folders live at {project}/.bluekit/plans/{name}
+    let bluekit_dir = Path::new(&plan_model.folder_path)
+        .parent()
+        .and_then(Path::parent)
+        .ok_or_else(|| DbErr::Custom("Plan folder is not inside .bluekit/plans".to_string()))?;
+    let diagrams_dir = bluekit_dir.join("diagrams");
+    fs::create_dir_all(&diagrams_dir)
+        .map_err(|e| DbErr::Custom(format!("Failed to create diagrams folder: {}", e)))?;
+
+    let file_name = if format == "gantt" {
+        format!("plan-{}-gantt.mmd", slugify(&plan_model.name))
+    } else {
+        format!("plan-{}.mmd", slugify(&plan_model.name))
+    };
+    let file_path = diagrams_dir.join(file_name);
+    fs::write(&file_path, &content)
+        .map_err(|e| DbErr::Custom(format!("Failed to write plan diagram: {}", e)))?;
+
+    Ok(PlanDiagramDto {
+        file_path: file_path.to_string_lossy().to_string(),
+        format,
+        content,
+    })
+}
+
+// Phases run in order, each depending on the one before; milestones hang
+// off their phase and linked plans feed into the plan
+fn render_plan_flowchart(plan: &plan::Model, phases: &[PlanPhaseDto], linked_plans: &[PlanLinkDto]) -> String {
+    let total: usize = phases.iter().map(|p| p.milestones.len()).sum();
+    let done: usize = phases
+        .iter()
+        .map(|p| p.milestones.iter().filter(|m| m.completed).count())
+        .sum();
+
+    let mut lines = vec![
+        format!("%% Generated by BlueKit from plan \"{}\"; regenerate instead of editing", plan.name),
+        "flowchart TD".to_string(),
+        "    classDef completed fill:#d3f9d8,stroke:#2b8a3e".to_string(),
+        "    classDef in_progress fill:#fff3bf,stroke:#e67700".to_string(),
+        "    classDef pending fill:#f1f3f5,stroke:#868e96".to_string(),
+        format!(
+            "    plan[\"{} ({}/{} milestones)\"]",
+            mermaid_label(&plan.name),
+            done,
+            total
+        ),
+    ];
+
+    let mut previous = "plan".to_string();
+    for (phase_index, phase) in phases.iter().enumerate() {
+        let phase_node = format!("phase{}", phase_index);
+        lines.push(format!(
+            "    {}[\"{}. {}\"]:::{}",
+            phase_node,
+            phase_index + 1,
+            mermaid_label(&phase.name),
+            status_class(&phase.status)
+        ));
+        lines.push(format!("    {} --> {}", previous, phase_node));
+
+        for (milestone_index, milestone) in phase.milestones.iter().enumerate() {
+            let (marker, class) = if milestone.completed { ("✓", "completed") } else { ("○", "pending") };
+            let milestone_node = format!("{}_m{}", phase_node, milestone_index);
+            lines.push(format!(
+                "    {}(\"{} {}\"):::{}",
+                milestone_node,
+                marker,
+                mermaid_label(&milestone.name),
+                class
+            ));
+            lines.push(format!("    {} -.- {}", phase_node, milestone_node));
+        }
+        previous = phase_node;
+    }
+
+    for (index, link) in linked_plans.iter().enumerate() {
+        let name = Path::new(&link.linked_plan_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| link.linked_plan_path.clone());
+        lines.push(format!("    linked{}[[\"{}\"]] -.-> plan", index, mermaid_label(&name)));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+// One section per phase; phases without dates start when they were created
+// and run until now
+fn render_plan_gantt(plan: &plan::Model, phases: &[PlanPhaseDto]) -> String {
+    let now = Utc::now().timestamp();
+    let date = |timestamp: i64| {
+        chrono::DateTime::from_timestamp(timestamp, 0)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    };
+
+    let mut lines = vec![
+        format!("%% Generated by BlueKit from plan \"{}\"; regenerate instead of editing", plan.name),
+        "gantt".to_string(),
+        format!("    title {}", gantt_text(&plan.name)),
+        "    dateFormat YYYY-MM-DD".to_string(),
+    ];
+
+    for (phase_index, phase) in phases.iter().enumerate() {
+        let start = phase.started_at.unwrap_or(phase.created_at);
+        let end = phase.completed_at.unwrap_or(now).max(start);
+        let tag = match phase.status.as_str() {
+            "completed" => "done, ",
+            "in_progress" => "active, ",
+            _ => "",
+        };
+
+        lines.push(format!("    section {}", gantt_text(&phase.name)));
+        lines.push(format!(
+            "    {} :{}phase{}, {}, {}",
+            gantt_text(&phase.name),
+            tag,
+            phase_index,
+            date(start),
+            date(end)
+        ));
+        for milestone in &phase.milestones {
+            let (tag, when) = match milestone.completed_at {
+                Some(completed_at) if milestone.completed => ("done, ", completed_at),
+                _ => ("", end),
+            };
+            lines.push(format!(
+                "    {} :{}milestone, {}, 0d",
+                gantt_text(&milestone.name),
+                tag,
+                date(when)
+            ));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn status_class(status: &str) -> &'static str {
+    match status {
+        "completed" => "completed",
+        "in_progress" => "in_progress",
+        _ => "pending",
+    }
+}
+
+// Quoted mermaid labels can't contain raw double quotes
+fn mermaid_label(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+// Gantt task names end at the first `:` and comments start with `#`
+fn gantt_text(text: &str) -> String {
+    text.replace([':', '#', ';'], " ")
+}
@@ -52,13 +52,7 @@ pub async fn migrate_json_to_database(
     let now = Utc::now().timestamp_millis();
 
     // 1. Migrate projectRegistry.json
-    let home_dir = std::env::var("HOME")
-        .or_else(|_| std::env::var("USERPROFILE"))
-        .map_err(|_| DbErr::Custom("Could not find home directory".to_string()))?;
-
-    let registry_path = PathBuf::from(&home_dir)
-        .join(".bluekit")
-        .join("projectRegistry.json");
+    let registry_path = crate::core::registry::registry_path().map_err(DbErr::Custom)?;
 
     if !registry_path.exists() {
         return Ok(summary); // Nothing to migrate
@@ -74,10 +68,18 @@ pub async fn migrate_json_to_database(
     let content = fs::read_to_string(&registry_path)
         .map_err(|e| DbErr::Custom(format!("Failed to read registry: {}", e)))?;
 
-    let legacy_projects: Vec<LegacyProjectEntry> = serde_json::from_str(&content)
+    let raw_entries: Vec<serde_json::Value> = serde_json::from_str(&content)
         .map_err(|e| DbErr::Custom(format!("Failed to parse registry: {}", e)))?;
 
-    for legacy_project in legacy_projects {
+    // Parse entries one by one so a malformed entry doesn't block the rest
+    for (index, raw_entry) in raw_entries.into_iter().enumerate() {
+        let legacy_project: LegacyProjectEntry = match serde_json::from_value(raw_entry) {
+            Ok(entry) => entry,
+            Err(e) => {
+                summary.errors.push(format!("Skipped malformed registry entry {}: {}", index, e));
+                continue;
+            }
+        };
         // Check if already exists (idempotent migration)
         let exists = project::Entity::find_by_id(&legacy_project.id)
             .one(db)
@@ -112,6 +114,8 @@ pub async fn migrate_json_to_database(
             updated_at: Set(now),
             last_opened_at: Set(None),
             is_vault: Set(false),
+            sort_order: Set(None),
+            archived: Set(false),
         };
 
         match project_model.insert(db).await {
@@ -209,6 +213,8 @@ pub async fn create_project(
         updated_at: Set(now),
         last_opened_at: Set(Some(now)),
         is_vault: Set(is_vault.unwrap_or(false)),
+        sort_order: Set(None),
+        archived: Set(false),
     };
 
     let res = project.insert(db).await?;
@@ -242,6 +248,211 @@ pub async fn get_all_projects(
         .await
 }
 
+/// Sorts projects by their manual order. Projects that were never placed
+/// come after the ordered ones, oldest first.
+pub fn sort_by_manual_order(projects: &mut [project::Model]) {
+    projects.sort_by_key(|p| (p.sort_order.is_none(), p.sort_order, p.created_at));
+}
+
+/// Stores a manual project order. `project_ids` are placed first, in the
+/// given order; projects not listed keep their relative order after them.
+pub async fn reorder_projects(
+    db: &DatabaseConnection,
+    project_ids: &[String],
+) -> Result<Vec<project::Model>, DbErr> {
+    let mut projects = project::Entity::find().all(db).await?;
+    sort_by_manual_order(&mut projects);
+
+    let mut ordered = Vec::with_capacity(projects.len());
+    for id in project_ids {
+        let position = projects
+            .iter()
+            .position(|p| &p.id == id)
+            .ok_or_else(|| DbErr::Custom(format!("Project not found: {}", id)))?;
+        ordered.push(projects.remove(position));
+    }
+    ordered.append(&mut projects);
+
+    let txn = db.begin().await?;
+    let now = Utc::now().timestamp_millis();
+    for (index, project) in ordered.iter_mut().enumerate() {
+        let position = index as i64;
+        if project.sort_order == Some(position) {
+            continue;
+        }
+        let mut active_model: project::ActiveModel = project.clone().into();
+        active_model.sort_order = Set(Some(position));
+        active_model.updated_at = Set(now);
+        *project = active_model.update(&txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok(ordered)
+}
+
+/// Archives or unarchives a project. Archived projects are left out of
+/// project and task listings unless asked for; nothing is deleted.
+pub async fn set_project_archived(
+    db: &DatabaseConnection,
+    project_id: &str,
+    archived: bool,
+) -> Result<project::Model, DbErr> {
+    let project = project::Entity::find_by_id(project_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::Custom("Project not found".to_string()))?;
+
+    let mut active_model: project::ActiveModel = project.into();
+    active_model.archived = Set(archived);
+    active_model.updated_at = Set(Utc::now().timestamp_millis());
+    active_model.update(db).await
+}
+
+/// IDs of all archived projects.
+pub async fn archived_project_ids(
+    db: &DatabaseConnection,
+) -> Result<std::collections::HashSet<String>, DbErr> {
+    Ok(project::Entity::find()
+        .filter(project::Column::Archived.eq(true))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| p.id)
+        .collect())
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct RegistrySyncSummary {
+    /// Registry entries added to the database
+    pub imported: usize,
+    /// Database projects added to the registry
+    pub exported: usize,
+    /// Registry entries rewritten to match the database
+    pub updated: usize,
+    /// Registry entries dropped because their project is archived
+    pub removed: usize,
+}
+
+/// Brings `projectRegistry.json` and the `projects` table into line.
+///
+/// The database is canonical: registry entries with no project (by id or
+/// path) are imported when their directory exists, and every active,
+/// non-vault project is written back to the registry under its database id
+/// with its current name, description and path. Archived projects are
+/// dropped from the registry.
+pub async fn sync_registry(db: &DatabaseConnection) -> Result<RegistrySyncSummary, DbErr> {
+    use crate::commands::ProjectEntry;
+    use crate::core::paths;
+
+    let mut summary = RegistrySyncSummary::default();
+    let entries = crate::core::registry::read_entries().map_err(DbErr::Custom)?;
+    let mut projects = project::Entity::find().all(db).await?;
+
+    let same_path = |a: &str, b: &str| paths::from_ipc(a) == paths::from_ipc(b);
+    let now = Utc::now().timestamp_millis();
+    for entry in &entries {
+        let known = projects
+            .iter()
+            .any(|p| p.id == entry.id || same_path(&p.path, &entry.path));
+        if known || !paths::from_ipc(&entry.path).is_dir() {
+            continue;
+        }
+        let imported = project::ActiveModel {
+            id: Set(entry.id.clone()),
+            name: Set(entry.title.clone()),
+            path: Set(entry.path.clone()),
+            description: Set(Some(entry.description.clone()).filter(|d| !d.is_empty())),
+            tags: Set(None),
+            git_connected: Set(false),
+            git_url: Set(None),
+            git_branch: Set(None),
+            git_remote: Set(None),
+            last_commit_sha: Set(None),
+            last_synced_at: Set(None),
+            created_at: Set(entry.id.parse::<i64>().unwrap_or(now)),
+            updated_at: Set(now),
+            last_opened_at: Set(None),
+            is_vault: Set(false),
+            sort_order: Set(None),
+            archived: Set(false),
+        }
+        .insert(db)
+        .await?;
+        projects.push(imported);
+        summary.imported += 1;
+    }
+
+    let mut upserts = Vec::new();
+    let mut removals = std::collections::HashSet::new();
+    for project in projects.iter().filter(|p| !p.is_vault) {
+        let matching: Vec<&ProjectEntry> = entries
+            .iter()
+            .filter(|e| e.id == project.id || same_path(&e.path, &project.path))
+            .collect();
+
+        if project.archived {
+            for entry in matching {
+                removals.insert(entry.id.clone());
+                summary.removed += 1;
+            }
+            continue;
+        }
+
+        let canonical = ProjectEntry {
+            id: project.id.clone(),
+            title: project.name.clone(),
+            description: project.description.clone().unwrap_or_default(),
+            path: project.path.clone(),
+        };
+        let in_sync = |e: &&ProjectEntry| {
+            e.id == canonical.id
+                && e.title == canonical.title
+                && e.description == canonical.description
+                && e.path == canonical.path
+        };
+        if matching.iter().any(in_sync) {
+            continue;
+        }
+        // Entries found by path under another id are replaced by one under
+        // the project's id
+        for entry in matching.iter().filter(|e| e.id != project.id) {
+            removals.insert(entry.id.clone());
+        }
+        if matching.is_empty() {
+            summary.exported += 1;
+        } else {
+            summary.updated += 1;
+        }
+        upserts.push(canonical);
+    }
+
+    crate::core::registry::apply_changes(upserts, &removals)
+        .await
+        .map_err(DbErr::Custom)?;
+    Ok(summary)
+}
+
+/// The canonical project list: syncs the registry, then returns projects
+/// in their manual order. Archived projects are left out unless
+/// `include_archived` is set.
+pub async fn get_projects(
+    db: &DatabaseConnection,
+    include_archived: bool,
+) -> Result<Vec<project::Model>, DbErr> {
+    // A registry that can't be read or written shouldn't hide the projects
+    if let Err(e) = sync_registry(db).await {
+        tracing::warn!("Failed to sync project registry: {}", e);
+    }
+
+    let mut query = project::Entity::find();
+    if !include_archived {
+        query = query.filter(project::Column::Archived.eq(false));
+    }
+    let mut projects = query.all(db).await?;
+    sort_by_manual_order(&mut projects);
+    Ok(projects)
+}
+
 /// Updates project git info by detecting git metadata from the project path.
 pub async fn update_project_git_info(
     db: &DatabaseConnection,
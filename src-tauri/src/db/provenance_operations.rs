@@ -0,0 +1,167 @@
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::{artifact_provenance, library_variation};
+use crate::library::utils::compute_content_hash;
+use chrono::Utc;
+use uuid::Uuid;
+use std::fs;
+
+/// Where a local copy came from
+pub enum ProvenanceSource<'a> {
+    /// Copied from another file on disk
+    Local { source_path: &'a str },
+    /// Pulled from a library variation
+    Library {
+        workspace_id: &'a str,
+        catalog_id: &'a str,
+        variation_id: &'a str,
+    },
+}
+
+/// A local copy whose origin has moved on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutdatedCopyDto {
+    #[serde(rename = "localPath")]
+    pub local_path: String,
+    #[serde(rename = "sourceKind")]
+    pub source_kind: String,
+    #[serde(rename = "sourcePath")]
+    pub source_path: Option<String>,
+    #[serde(rename = "catalogId")]
+    pub catalog_id: Option<String>,
+    #[serde(rename = "variationId")]
+    pub variation_id: Option<String>,
+    #[serde(rename = "latestVariationId")]
+    pub latest_variation_id: Option<String>,
+    #[serde(rename = "copiedHash")]
+    pub copied_hash: String,
+    #[serde(rename = "sourceHash")]
+    pub source_hash: Option<String>,
+    #[serde(rename = "copiedAt")]
+    pub copied_at: i64,
+    /// The origin no longer exists (file deleted or catalog removed)
+    #[serde(rename = "sourceMissing")]
+    pub source_missing: bool,
+    /// The local copy has been edited since it was copied
+    #[serde(rename = "locallyModified")]
+    pub locally_modified: bool,
+}
+
+/// Record (or refresh) where a local artifact copy came from
+pub async fn record_provenance(
+    db: &DatabaseConnection,
+    project_path: &str,
+    local_path: &str,
+    source: ProvenanceSource<'_>,
+    content_hash: &str,
+) -> Result<(), DbErr> {
+    let now = Utc::now().timestamp();
+
+    let (source_kind, source_path, workspace_id, catalog_id, variation_id) = match source {
+        ProvenanceSource::Local { source_path } => {
+            ("local", Some(source_path.to_string()), None, None, None)
+        }
+        ProvenanceSource::Library { workspace_id, catalog_id, variation_id } => (
+            "library",
+            None,
+            Some(workspace_id.to_string()),
+            Some(catalog_id.to_string()),
+            Some(variation_id.to_string()),
+        ),
+    };
+
+    // A re-copy replaces the previous origin of this file
+    artifact_provenance::Entity::delete_many()
+        .filter(artifact_provenance::Column::LocalPath.eq(local_path))
+        .exec(db)
+        .await?;
+
+    let provenance_active = artifact_provenance::ActiveModel {
+        id: Set(Uuid::new_v4().to_string()),
+        project_path: Set(project_path.to_string()),
+        local_path: Set(local_path.to_string()),
+        source_kind: Set(source_kind.to_string()),
+        source_path: Set(source_path),
+        workspace_id: Set(workspace_id),
+        catalog_id: Set(catalog_id),
+        variation_id: Set(variation_id),
+        content_hash: Set(content_hash.to_string()),
+        copied_at: Set(now),
+    };
+    provenance_active.insert(db).await?;
+
+    Ok(())
+}
+
+/// Flag copies in a project whose origin has newer content than what was copied
+///
+/// Copies whose local file was deleted are dropped from the provenance table.
+pub async fn check_outdated_copies(
+    db: &DatabaseConnection,
+    project_path: &str,
+) -> Result<Vec<OutdatedCopyDto>, DbErr> {
+    let records: Vec<artifact_provenance::Model> = artifact_provenance::Entity::find()
+        .filter(artifact_provenance::Column::ProjectPath.eq(project_path))
+        .order_by_asc(artifact_provenance::Column::LocalPath)
+        .all(db)
+        .await?;
+
+    let mut outdated = Vec::new();
+
+    for record in records {
+        let local_hash = match fs::read_to_string(&record.local_path) {
+            Ok(content) => compute_content_hash(&content),
+            Err(_) => {
+                // Local copy is gone; nothing left to keep up to date
+                artifact_provenance::Entity::delete_by_id(&record.id).exec(db).await?;
+                continue;
+            }
+        };
+
+        let (source_hash, latest_variation_id) = match record.source_kind.as_str() {
+            "library" => {
+                let catalog_id = record.catalog_id.clone().unwrap_or_default();
+                let latest = library_variation::Entity::find()
+                    .filter(library_variation::Column::CatalogId.eq(&catalog_id))
+                    .order_by_desc(library_variation::Column::PublishedAt)
+                    .one(db)
+                    .await?;
+                match latest {
+                    Some(v) => (Some(v.content_hash), Some(v.id)),
+                    None => (None, None),
+                }
+            }
+            _ => {
+                let source_hash = record
+                    .source_path
+                    .as_deref()
+                    .and_then(|p| fs::read_to_string(p).ok())
+                    .map(|content| compute_content_hash(&content));
+                (source_hash, None)
+            }
+        };
+
+        let source_missing = source_hash.is_none();
+        let source_changed = source_hash
+            .as_deref()
+            .is_some_and(|h| h != record.content_hash);
+
+        if source_missing || source_changed {
+            outdated.push(OutdatedCopyDto {
+                locally_modified: local_hash != record.content_hash,
+                local_path: record.local_path,
+                source_kind: record.source_kind,
+                source_path: record.source_path,
+                catalog_id: record.catalog_id,
+                variation_id: record.variation_id,
+                latest_variation_id,
+                copied_hash: record.content_hash,
+                source_hash,
+                copied_at: record.copied_at,
+                source_missing,
+            });
+        }
+    }
+
+    Ok(outdated)
+}
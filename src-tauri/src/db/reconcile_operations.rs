@@ -0,0 +1,306 @@
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::{library_resource, plan, plan_document, project, walkthrough};
+use crate::db::{plan_operations, walkthrough_operations};
+use chrono::Utc;
+use uuid::Uuid;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A DB record or file that is out of sync with the other side
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanDto {
+    /// "walkthrough" | "plan" | "plan_document" | "library_resource" | "bookmark"
+    pub kind: String,
+    /// "missing_file" (record points at a file that is gone) or
+    /// "untracked_file" (file exists but has no record)
+    pub issue: String,
+    #[serde(rename = "recordId")]
+    pub record_id: Option<String>,
+    pub path: String,
+    pub fixed: bool,
+}
+
+impl OrphanDto {
+    fn missing(kind: &str, record_id: &str, path: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            issue: "missing_file".to_string(),
+            record_id: Some(record_id.to_string()),
+            path: path.to_string(),
+            fixed: false,
+        }
+    }
+
+    fn untracked(kind: &str, path: &Path) -> Self {
+        Self {
+            kind: kind.to_string(),
+            issue: "untracked_file".to_string(),
+            record_id: None,
+            path: path.to_string_lossy().to_string(),
+            fixed: false,
+        }
+    }
+}
+
+/// Result of reconciling a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconcileReportDto {
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    /// None if the project isn't registered (only file-based data is checked)
+    #[serde(rename = "projectId")]
+    pub project_id: Option<String>,
+    pub orphans: Vec<OrphanDto>,
+    #[serde(rename = "fixedCount")]
+    pub fixed_count: usize,
+}
+
+/// Cross-check a project's DB records against its files in both directions.
+///
+/// With `fix`, records whose files are gone are removed (library resources
+/// are soft-deleted, like a rescan would) and untracked files get records.
+/// Untracked walkthroughs without valid front matter are reported but can't
+/// be adopted.
+pub async fn reconcile_project(
+    db: &DatabaseConnection,
+    project_path: &str,
+    fix: bool,
+) -> Result<ReconcileReportDto, DbErr> {
+    let project = project::Entity::find()
+        .filter(project::Column::Path.eq(project_path))
+        .one(db)
+        .await?;
+
+    let mut report = ReconcileReportDto {
+        project_path: project_path.to_string(),
+        project_id: project.as_ref().map(|p| p.id.clone()),
+        orphans: Vec::new(),
+        fixed_count: 0,
+    };
+
+    if let Some(project) = project {
+        reconcile_walkthroughs(db, &project.id, project_path, fix, &mut report.orphans).await?;
+        reconcile_plans(db, &project.id, project_path, fix, &mut report.orphans).await?;
+        reconcile_library_resources(db, &project.id, project_path, fix, &mut report.orphans).await?;
+    }
+
+    report.fixed_count = report.orphans.iter().filter(|o| o.fixed).count();
+    Ok(report)
+}
+
+async fn reconcile_walkthroughs(
+    db: &DatabaseConnection,
+    project_id: &str,
+    project_path: &str,
+    fix: bool,
+    orphans: &mut Vec<OrphanDto>,
+) -> Result<(), DbErr> {
+    let records = walkthrough::Entity::find()
+        .filter(walkthrough::Column::ProjectId.eq(project_id))
+        .all(db)
+        .await?;
+
+    for record in &records {
+        if !Path::new(&record.file_path).exists() {
+            let mut orphan = OrphanDto::missing("walkthrough", &record.id, &record.file_path);
+            if fix {
+                // Cascade removes takeaways and notes
+                walkthrough::Entity::delete_by_id(&record.id).exec(db).await?;
+                orphan.fixed = true;
+            }
+            orphans.push(orphan);
+        }
+    }
+
+    let tracked: HashSet<&str> = records.iter().map(|w| w.file_path.as_str()).collect();
+    let walkthroughs_dir = Path::new(project_path).join(".bluekit").join("walkthroughs");
+    let untracked: Vec<PathBuf> = markdown_files(&walkthroughs_dir)
+        .into_iter()
+        .filter(|path| !tracked.contains(path.to_string_lossy().as_ref()))
+        .collect();
+    if untracked.is_empty() {
+        return Ok(());
+    }
+
+    let adopted: HashSet<String> = if fix {
+        walkthrough_operations::sync_project_walkthroughs(db, project_id, project_path).await?;
+        walkthrough::Entity::find()
+            .filter(walkthrough::Column::ProjectId.eq(project_id))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|w| w.file_path)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    for path in untracked {
+        let mut orphan = OrphanDto::untracked("walkthrough", &path);
+        orphan.fixed = adopted.contains(&orphan.path);
+        orphans.push(orphan);
+    }
+
+    Ok(())
+}
+
+async fn reconcile_plans(
+    db: &DatabaseConnection,
+    project_id: &str,
+    project_path: &str,
+    fix: bool,
+    orphans: &mut Vec<OrphanDto>,
+) -> Result<(), DbErr> {
+    let plans = plan::Entity::find()
+        .filter(plan::Column::ProjectId.eq(project_id))
+        .all(db)
+        .await?;
+
+    let mut tracked_folders = HashSet::new();
+    for plan_model in &plans {
+        tracked_folders.insert(PathBuf::from(&plan_model.folder_path));
+
+        if !Path::new(&plan_model.folder_path).exists() {
+            let mut orphan = OrphanDto::missing("plan", &plan_model.id, &plan_model.folder_path);
+            if fix {
+                // Cascade removes phases, milestones and documents
+                plan::Entity::delete_by_id(&plan_model.id).exec(db).await?;
+                orphan.fixed = true;
+            }
+            orphans.push(orphan);
+            continue;
+        }
+
+        let first_new = orphans.len();
+        let documents = plan_document::Entity::find()
+            .filter(plan_document::Column::PlanId.eq(&plan_model.id))
+            .all(db)
+            .await?;
+        for document in &documents {
+            if !Path::new(&document.file_path).exists() {
+                orphans.push(OrphanDto::missing("plan_document", &document.id, &document.file_path));
+            }
+        }
+
+        let tracked: HashSet<&str> = documents.iter().map(|d| d.file_path.as_str()).collect();
+        for path in markdown_files(Path::new(&plan_model.folder_path)) {
+            if !tracked.contains(path.to_string_lossy().as_ref()) {
+                orphans.push(OrphanDto::untracked("plan_document", &path));
+            }
+        }
+
+        if fix && orphans.len() > first_new {
+            // Rescanning the folder adds new files and drops missing ones
+            plan_operations::get_plan_documents(db, plan_model.id.clone()).await?;
+            for orphan in &mut orphans[first_new..] {
+                orphan.fixed = true;
+            }
+        }
+    }
+
+    // Plan folders created outside the app (or left behind by a lost DB)
+    let plans_dir = Path::new(project_path).join(".bluekit").join("plans");
+    let Ok(entries) = fs::read_dir(&plans_dir) else {
+        return Ok(());
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || tracked_folders.contains(&path) {
+            continue;
+        }
+
+        let mut orphan = OrphanDto::untracked("plan", &path);
+        if fix {
+            let now = Utc::now().timestamp();
+            let name = crate::core::paths::file_name_lossy(&path).replace(['-', '_'], " ");
+            let plan_id = Uuid::new_v4().to_string();
+            plan::ActiveModel {
+                id: Set(plan_id.clone()),
+                name: Set(name),
+                project_id: Set(project_id.to_string()),
+                folder_path: Set(orphan.path.clone()),
+                description: Set(None),
+                status: Set("active".to_string()),
+                brainstorm_link: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+                deleted_at: Set(None),
+            }
+            .insert(db)
+            .await?;
+            plan_operations::get_plan_documents(db, plan_id).await?;
+            orphan.fixed = true;
+        }
+        orphans.push(orphan);
+    }
+
+    Ok(())
+}
+
+async fn reconcile_library_resources(
+    db: &DatabaseConnection,
+    project_id: &str,
+    project_path: &str,
+    fix: bool,
+    orphans: &mut Vec<OrphanDto>,
+) -> Result<(), DbErr> {
+    let project_root = Path::new(project_path);
+    let resources = library_resource::Entity::find()
+        .filter(library_resource::Column::ProjectId.eq(project_id))
+        .filter(library_resource::Column::IsDeleted.eq(0))
+        .all(db)
+        .await?;
+
+    let first_new = orphans.len();
+    for resource in &resources {
+        let path = project_root.join(&resource.relative_path);
+        if !path.exists() {
+            orphans.push(OrphanDto::missing(
+                "library_resource",
+                &resource.id,
+                &path.to_string_lossy(),
+            ));
+        }
+    }
+
+    let tracked: HashSet<&str> = resources.iter().map(|r| r.relative_path.as_str()).collect();
+    let mut artifact_files = Vec::new();
+    crate::library::resource_scanner::collect_artifact_files(
+        &project_root.join(".bluekit"),
+        project_root,
+        &mut artifact_files,
+    )
+    .map_err(DbErr::Custom)?;
+    for (relative_path, absolute_path) in artifact_files {
+        if !tracked.contains(relative_path.as_str()) {
+            orphans.push(OrphanDto::untracked("library_resource", &absolute_path));
+        }
+    }
+
+    if fix && orphans.len() > first_new {
+        // A rescan creates records for new files and soft-deletes missing ones
+        crate::library::resource_scanner::scan_project_resources(db, project_id, project_root)
+            .await
+            .map_err(DbErr::Custom)?;
+        for orphan in &mut orphans[first_new..] {
+            orphan.fixed = true;
+        }
+    }
+
+    Ok(())
+}
+
+// Markdown files directly inside `dir` (not recursive, matching the per-feature syncs)
+fn markdown_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().and_then(|e| e.to_str()) == Some("md"))
+        .collect()
+}
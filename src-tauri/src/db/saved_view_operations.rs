@@ -0,0 +1,234 @@
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::saved_view;
+use crate::db::task_operations::{self, TaskDto};
+use chrono::Utc;
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+/// Fields a view can sort by
+pub const SORT_FIELDS: &[&str] = &["createdAt", "updatedAt", "dueAt", "priority", "status", "title", "order"];
+
+/// Priorities from most to least urgent, for sorting
+const PRIORITY_ORDER: &[&str] = &["pinned", "high", "standard", "long term", "nit"];
+
+/// Statuses in board order, for sorting
+const STATUS_ORDER: &[&str] = &["backlog", "in_progress", "blocked", "completed"];
+
+/// Which tasks a view shows. Empty lists don't filter; within a list any
+/// value matches, except tags with `match_all_tags`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskFilter {
+    #[serde(default)]
+    pub statuses: Vec<String>,
+    #[serde(default)]
+    pub priorities: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Require every tag instead of any
+    #[serde(rename = "matchAllTags", default)]
+    pub match_all_tags: bool,
+    #[serde(rename = "projectIds", default)]
+    pub project_ids: Vec<String>,
+    /// Keep tasks that only belong to archived projects
+    #[serde(rename = "includeArchived", default)]
+    pub include_archived: bool,
+    pub sort: Option<TaskSort>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSort {
+    /// One of `SORT_FIELDS`; "order" is the board order of a single-project view
+    pub field: String,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// Saved view DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedViewDto {
+    pub id: String,
+    pub name: String,
+    pub filter: TaskFilter,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+impl From<saved_view::Model> for SavedViewDto {
+    fn from(model: saved_view::Model) -> Self {
+        Self {
+            filter: serde_json::from_str(&model.filter).unwrap_or_default(),
+            id: model.id,
+            name: model.name,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+fn validate(name: &str, filter: &TaskFilter) -> Result<(), DbErr> {
+    if name.trim().is_empty() {
+        return Err(DbErr::Custom("View name cannot be empty".to_string()));
+    }
+    if let Some(sort) = &filter.sort {
+        if !SORT_FIELDS.contains(&sort.field.as_str()) {
+            return Err(DbErr::Custom(format!(
+                "Unknown sort field '{}' (expected one of: {})",
+                sort.field,
+                SORT_FIELDS.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn filter_json(filter: &TaskFilter) -> Result<String, DbErr> {
+    serde_json::to_string(filter).map_err(|e| DbErr::Custom(format!("Invalid filter: {}", e)))
+}
+
+/// List all saved views by name
+pub async fn list_saved_views(db: &DatabaseConnection) -> Result<Vec<SavedViewDto>, DbErr> {
+    let views = saved_view::Entity::find()
+        .order_by_asc(saved_view::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(views.into_iter().map(Into::into).collect())
+}
+
+/// Create a saved view
+pub async fn create_saved_view(
+    db: &DatabaseConnection,
+    name: String,
+    filter: TaskFilter,
+) -> Result<SavedViewDto, DbErr> {
+    validate(&name, &filter)?;
+
+    let now = Utc::now().to_rfc3339();
+    let view = saved_view::ActiveModel {
+        id: Set(Uuid::new_v4().to_string()),
+        name: Set(name.trim().to_string()),
+        filter: Set(filter_json(&filter)?),
+        created_at: Set(now.clone()),
+        updated_at: Set(now),
+    };
+
+    Ok(view.insert(db).await?.into())
+}
+
+/// Rename a saved view and/or replace its filter
+pub async fn update_saved_view(
+    db: &DatabaseConnection,
+    view_id: &str,
+    name: Option<String>,
+    filter: Option<TaskFilter>,
+) -> Result<SavedViewDto, DbErr> {
+    let view = saved_view::Entity::find_by_id(view_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Saved view not found: {}", view_id)))?;
+
+    let current: SavedViewDto = view.clone().into();
+    validate(name.as_deref().unwrap_or(&current.name), filter.as_ref().unwrap_or(&current.filter))?;
+
+    let mut active_model: saved_view::ActiveModel = view.into();
+    if let Some(name) = name {
+        active_model.name = Set(name.trim().to_string());
+    }
+    if let Some(filter) = filter {
+        active_model.filter = Set(filter_json(&filter)?);
+    }
+    active_model.updated_at = Set(Utc::now().to_rfc3339());
+
+    Ok(active_model.update(db).await?.into())
+}
+
+/// Delete a saved view
+pub async fn delete_saved_view(db: &DatabaseConnection, view_id: &str) -> Result<(), DbErr> {
+    saved_view::Entity::delete_by_id(view_id).exec(db).await?;
+    Ok(())
+}
+
+/// Runs a saved view's filter and sort
+pub async fn get_tasks_for_view(db: &DatabaseConnection, view_id: &str) -> Result<Vec<TaskDto>, DbErr> {
+    let view: SavedViewDto = saved_view::Entity::find_by_id(view_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Saved view not found: {}", view_id)))?
+        .into();
+
+    get_filtered_tasks(db, &view.filter).await
+}
+
+/// Tasks matching `filter`, sorted by its sort (or board order, then
+/// creation, when it has none)
+pub async fn get_filtered_tasks(db: &DatabaseConnection, filter: &TaskFilter) -> Result<Vec<TaskDto>, DbErr> {
+    let project_ids = (!filter.project_ids.is_empty()).then(|| filter.project_ids.clone());
+    let mut tasks = task_operations::get_tasks(db, project_ids).await?;
+
+    if !filter.include_archived {
+        let archived = crate::db::project_operations::archived_project_ids(db).await?;
+        if !archived.is_empty() {
+            tasks.retain(|task| {
+                task.project_ids.is_empty() || !task.project_ids.iter().all(|id| archived.contains(id))
+            });
+        }
+    }
+
+    tasks.retain(|task| matches(task, filter));
+    if let Some(sort) = &filter.sort {
+        sort_tasks(&mut tasks, sort);
+    }
+    Ok(tasks)
+}
+
+fn matches(task: &TaskDto, filter: &TaskFilter) -> bool {
+    if !filter.statuses.is_empty() && !filter.statuses.contains(&task.status) {
+        return false;
+    }
+    if !filter.priorities.is_empty() && !filter.priorities.contains(&task.priority) {
+        return false;
+    }
+    if !filter.tags.is_empty() {
+        let has_tag = |tag: &String| task.tags.contains(tag);
+        let tags_match = if filter.match_all_tags {
+            filter.tags.iter().all(has_tag)
+        } else {
+            filter.tags.iter().any(has_tag)
+        };
+        if !tags_match {
+            return false;
+        }
+    }
+    true
+}
+
+// Stable sort, so ties keep the board/creation order from get_tasks. Tasks
+// without a due date or board position go last either way.
+fn sort_tasks(tasks: &mut [TaskDto], sort: &TaskSort) {
+    let rank = |order: &[&str], value: &str| order.iter().position(|v| *v == value).unwrap_or(order.len());
+    tasks.sort_by(|a, b| {
+        let ordering = match sort.field.as_str() {
+            "updatedAt" => a.updated_at.cmp(&b.updated_at),
+            "dueAt" => return missing_last(a.due_at.as_ref(), b.due_at.as_ref(), sort.descending),
+            "order" => return missing_last(a.order_index.as_ref(), b.order_index.as_ref(), sort.descending),
+            "priority" => rank(PRIORITY_ORDER, &a.priority).cmp(&rank(PRIORITY_ORDER, &b.priority)),
+            "status" => rank(STATUS_ORDER, &a.status).cmp(&rank(STATUS_ORDER, &b.status)),
+            "title" => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            _ => a.created_at.cmp(&b.created_at),
+        };
+        if sort.descending { ordering.reverse() } else { ordering }
+    });
+}
+
+fn missing_last<T: Ord>(a: Option<&T>, b: Option<&T>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if descending => b.cmp(a),
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
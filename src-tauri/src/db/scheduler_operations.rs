@@ -0,0 +1,409 @@
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::scheduled_job;
+use chrono::{Local, TimeZone, Utc};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Job types the scheduler knows how to run
+pub const JOB_TYPES: &[&str] = &["workspace_sync", "backup", "update_check", "trash_purge"];
+
+/// Shortest allowed interval, so a typo can't hammer GitHub or the disk
+const MIN_INTERVAL_SECONDS: i64 = 60;
+
+/// Scheduled job DTO
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJobDto {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "jobType")]
+    pub job_type: String,
+    #[serde(rename = "intervalSeconds")]
+    pub interval_seconds: Option<i64>,
+    #[serde(rename = "cronExpression")]
+    pub cron_expression: Option<String>,
+    pub params: serde_json::Value,
+    pub enabled: bool,
+    #[serde(rename = "nextRunAt")]
+    pub next_run_at: Option<i64>,
+    #[serde(rename = "lastRunAt")]
+    pub last_run_at: Option<i64>,
+    #[serde(rename = "lastStatus")]
+    pub last_status: Option<String>,
+    #[serde(rename = "lastMessage")]
+    pub last_message: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+impl From<scheduled_job::Model> for ScheduledJobDto {
+    fn from(model: scheduled_job::Model) -> Self {
+        Self {
+            params: model
+                .params
+                .as_deref()
+                .and_then(|p| serde_json::from_str(p).ok())
+                .unwrap_or_else(|| serde_json::json!({})),
+            id: model.id,
+            name: model.name,
+            job_type: model.job_type,
+            interval_seconds: model.interval_seconds,
+            cron_expression: model.cron_expression,
+            enabled: model.enabled != 0,
+            next_run_at: model.next_run_at,
+            last_run_at: model.last_run_at,
+            last_status: model.last_status,
+            last_message: model.last_message,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// Fields that can be changed on an existing job (absent fields are kept)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduledJobUpdate {
+    pub name: Option<String>,
+    pub enabled: Option<bool>,
+    /// Setting an interval replaces any cron expression, and vice versa
+    #[serde(rename = "intervalSeconds")]
+    pub interval_seconds: Option<i64>,
+    #[serde(rename = "cronExpression")]
+    pub cron_expression: Option<String>,
+    pub params: Option<serde_json::Value>,
+}
+
+/// Computes the next run time (Unix seconds) after `after`.
+///
+/// Cron expressions use local time and accept the usual five fields
+/// (`min hour day month weekday`) or six with a leading seconds field.
+/// Numeric weekdays in five-field expressions follow crontab (0 or 7 =
+/// Sunday); six-field expressions follow the `cron` crate, where 1 = Sunday.
+/// Weekday names (`MON-FRI`) mean the same in both.
+pub fn next_run_after(
+    interval_seconds: Option<i64>,
+    cron_expression: Option<&str>,
+    after: i64,
+) -> Result<i64, String> {
+    match (interval_seconds, cron_expression) {
+        (Some(interval), None) => {
+            if interval < MIN_INTERVAL_SECONDS {
+                return Err(format!(
+                    "Interval must be at least {} seconds",
+                    MIN_INTERVAL_SECONDS
+                ));
+            }
+            Ok(after + interval)
+        }
+        (None, Some(expression)) => {
+            let schedule = parse_cron(expression)?;
+            let after = Local
+                .timestamp_opt(after, 0)
+                .single()
+                .ok_or_else(|| format!("Invalid timestamp: {}", after))?;
+            schedule
+                .after(&after)
+                .next()
+                .map(|next| next.timestamp())
+                .ok_or_else(|| format!("Cron expression never fires: {}", expression))
+        }
+        _ => Err("Specify exactly one of an interval or a cron expression".to_string()),
+    }
+}
+
+fn parse_cron(expression: &str) -> Result<cron::Schedule, String> {
+    let expression = expression.trim();
+    let invalid = |e: String| format!("Invalid cron expression '{}': {}", expression, e);
+    // The cron crate wants a seconds field; accept standard five-field syntax too
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let normalized = if fields.len() == 5 {
+        let weekdays = crontab_weekdays(fields[4]).map_err(invalid)?;
+        format!("0 {} {}", fields[..4].join(" "), weekdays)
+    } else {
+        expression.to_string()
+    };
+    cron::Schedule::from_str(&normalized).map_err(|e| invalid(e.to_string()))
+}
+
+// Rewrites a crontab weekday field (0 or 7 = Sunday) for the cron crate
+// (1 = Sunday). Numeric items are expanded into a list of days; names,
+// `*` and `*/n` (which picks the same days in both) are kept as they are.
+fn crontab_weekdays(field: &str) -> Result<String, String> {
+    let mut items = Vec::new();
+    for item in field.split(',') {
+        if !item.starts_with(|c: char| c.is_ascii_digit()) {
+            items.push(item.to_string());
+            continue;
+        }
+
+        let number = |value: &str| {
+            value
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid weekday '{}'", item))
+        };
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, number(step)?),
+            None => (item, 1),
+        };
+        let (start, end) = match range.split_once('-') {
+            Some((start, end)) => (number(start)?, number(end)?),
+            // `n/step` runs to the end of the week
+            None if item.contains('/') => (number(range)?, 6),
+            None => (number(range)?, number(range)?),
+        };
+        if end > 7 || start > end || step == 0 {
+            return Err(format!("Invalid weekday '{}'", item));
+        }
+
+        let mut days: Vec<u32> = (start..=end)
+            .step_by(step as usize)
+            .map(|day| day % 7 + 1)
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+        items.extend(days.iter().map(u32::to_string));
+    }
+    Ok(items.join(","))
+}
+
+fn validate_job_type(job_type: &str) -> Result<(), DbErr> {
+    if JOB_TYPES.contains(&job_type) {
+        Ok(())
+    } else {
+        Err(DbErr::Custom(format!(
+            "Unknown job type '{}' (expected one of: {})",
+            job_type,
+            JOB_TYPES.join(", ")
+        )))
+    }
+}
+
+/// Create a scheduled job; its first run is one schedule step from now
+pub async fn create_scheduled_job(
+    db: &DatabaseConnection,
+    name: String,
+    job_type: String,
+    interval_seconds: Option<i64>,
+    cron_expression: Option<String>,
+    params: Option<serde_json::Value>,
+) -> Result<ScheduledJobDto, DbErr> {
+    validate_job_type(&job_type)?;
+
+    let now = Utc::now().timestamp();
+    let next_run_at = next_run_after(interval_seconds, cron_expression.as_deref(), now)
+        .map_err(DbErr::Custom)?;
+
+    let job = scheduled_job::ActiveModel {
+        id: Set(Uuid::new_v4().to_string()),
+        name: Set(name),
+        job_type: Set(job_type),
+        interval_seconds: Set(interval_seconds),
+        cron_expression: Set(cron_expression),
+        params: Set(params.map(|p| p.to_string())),
+        enabled: Set(1),
+        next_run_at: Set(Some(next_run_at)),
+        last_run_at: Set(None),
+        last_status: Set(None),
+        last_message: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    Ok(job.insert(db).await?.into())
+}
+
+/// List all scheduled jobs, soonest first (paused jobs last)
+pub async fn list_scheduled_jobs(db: &DatabaseConnection) -> Result<Vec<ScheduledJobDto>, DbErr> {
+    let jobs = scheduled_job::Entity::find()
+        .order_by_desc(scheduled_job::Column::Enabled)
+        .order_by_asc(scheduled_job::Column::NextRunAt)
+        .all(db)
+        .await?;
+
+    Ok(jobs.into_iter().map(Into::into).collect())
+}
+
+/// Get a scheduled job by ID
+pub async fn get_scheduled_job(
+    db: &DatabaseConnection,
+    job_id: &str,
+) -> Result<Option<scheduled_job::Model>, DbErr> {
+    scheduled_job::Entity::find_by_id(job_id).one(db).await
+}
+
+/// Update a scheduled job, recomputing its next run if the schedule changed
+/// or it was re-enabled
+pub async fn update_scheduled_job(
+    db: &DatabaseConnection,
+    job_id: &str,
+    update: ScheduledJobUpdate,
+) -> Result<ScheduledJobDto, DbErr> {
+    let job = get_scheduled_job(db, job_id)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Scheduled job not found: {}", job_id)))?;
+
+    let was_enabled = job.enabled != 0;
+    let (mut interval_seconds, mut cron_expression) =
+        (job.interval_seconds, job.cron_expression.clone());
+    let schedule_changed = update.interval_seconds.is_some() || update.cron_expression.is_some();
+    if update.interval_seconds.is_some() && update.cron_expression.is_some() {
+        return Err(DbErr::Custom(
+            "Specify exactly one of an interval or a cron expression".to_string(),
+        ));
+    }
+    if let Some(interval) = update.interval_seconds {
+        interval_seconds = Some(interval);
+        cron_expression = None;
+    }
+    if let Some(expression) = update.cron_expression {
+        cron_expression = Some(expression);
+        interval_seconds = None;
+    }
+
+    let enabled = update.enabled.unwrap_or(was_enabled);
+    let now = Utc::now().timestamp();
+
+    let mut active_model: scheduled_job::ActiveModel = job.into();
+    if let Some(name) = update.name {
+        active_model.name = Set(name);
+    }
+    if let Some(params) = update.params {
+        active_model.params = Set(Some(params.to_string()));
+    }
+    if schedule_changed || (enabled && !was_enabled) {
+        let next_run_at = next_run_after(interval_seconds, cron_expression.as_deref(), now)
+            .map_err(DbErr::Custom)?;
+        active_model.next_run_at = Set(Some(next_run_at));
+    }
+    active_model.interval_seconds = Set(interval_seconds);
+    active_model.cron_expression = Set(cron_expression);
+    active_model.enabled = Set(if enabled { 1 } else { 0 });
+    active_model.updated_at = Set(now);
+
+    Ok(active_model.update(db).await?.into())
+}
+
+/// Delete a scheduled job
+pub async fn delete_scheduled_job(db: &DatabaseConnection, job_id: &str) -> Result<(), DbErr> {
+    scheduled_job::Entity::delete_by_id(job_id).exec(db).await?;
+    Ok(())
+}
+
+/// Returns enabled jobs that are due at `now` and moves each one's next run
+/// forward, so a slow or failing job is not picked up again on the next tick.
+///
+/// Runs missed while the app was closed are coalesced into one.
+pub async fn claim_due_jobs(
+    db: &DatabaseConnection,
+    now: i64,
+) -> Result<Vec<scheduled_job::Model>, DbErr> {
+    let due = scheduled_job::Entity::find()
+        .filter(scheduled_job::Column::Enabled.eq(1))
+        .filter(scheduled_job::Column::NextRunAt.lte(now))
+        .all(db)
+        .await?;
+
+    let mut claimed = Vec::with_capacity(due.len());
+    for job in due {
+        let next_run_at =
+            match next_run_after(job.interval_seconds, job.cron_expression.as_deref(), now) {
+                Ok(next) => Some(next),
+                Err(e) => {
+                    // A stored schedule that no longer parses would otherwise fire every tick
+                    tracing::warn!("Pausing scheduled job {} with invalid schedule: {}", job.id, e);
+                    None
+                }
+            };
+
+        let mut active_model: scheduled_job::ActiveModel = job.clone().into();
+        active_model.next_run_at = Set(next_run_at);
+        if next_run_at.is_none() {
+            active_model.enabled = Set(0);
+        }
+        active_model.update(db).await?;
+
+        if next_run_at.is_some() {
+            claimed.push(job);
+        }
+    }
+
+    Ok(claimed)
+}
+
+/// Record the outcome of a job run
+pub async fn record_job_run(
+    db: &DatabaseConnection,
+    job_id: &str,
+    ran_at: i64,
+    result: &Result<String, String>,
+) -> Result<(), DbErr> {
+    let Some(job) = get_scheduled_job(db, job_id).await? else {
+        // Deleted while it was running
+        return Ok(());
+    };
+
+    let (status, message) = match result {
+        Ok(message) => ("success", message.clone()),
+        Err(error) => ("failed", error.clone()),
+    };
+
+    let mut active_model: scheduled_job::ActiveModel = job.into();
+    active_model.last_run_at = Set(Some(ran_at));
+    active_model.last_status = Set(Some(status.to_string()));
+    active_model.last_message = Set(Some(message));
+    active_model.update(db).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weekdays(field: &str) -> String {
+        crontab_weekdays(field).unwrap()
+    }
+
+    #[test]
+    fn test_crontab_weekdays_keeps_wildcards_and_names() {
+        assert_eq!(weekdays("*"), "*");
+        assert_eq!(weekdays("*/2"), "*/2");
+        assert_eq!(weekdays("MON-FRI"), "MON-FRI");
+    }
+
+    #[test]
+    fn test_crontab_weekdays_count_from_sunday_zero() {
+        assert_eq!(weekdays("0"), "1");
+        assert_eq!(weekdays("7"), "1");
+        assert_eq!(weekdays("1"), "2");
+        assert_eq!(weekdays("6"), "7");
+    }
+
+    #[test]
+    fn test_crontab_weekdays_expands_ranges() {
+        assert_eq!(weekdays("1-5"), "2,3,4,5,6");
+        // Sunday written as 7 sorts to the front of the list
+        assert_eq!(weekdays("5-7"), "1,6,7");
+    }
+
+    #[test]
+    fn test_crontab_weekdays_expands_steps() {
+        assert_eq!(weekdays("1-5/2"), "2,4,6");
+        assert_eq!(weekdays("4/1"), "5,6,7");
+    }
+
+    #[test]
+    fn test_crontab_weekdays_lists_mix_numbers_and_names() {
+        assert_eq!(weekdays("0,3,SAT"), "1,4,SAT");
+    }
+
+    #[test]
+    fn test_crontab_weekdays_rejects_invalid_days() {
+        assert!(crontab_weekdays("8").is_err());
+        assert!(crontab_weekdays("5-2").is_err());
+        assert!(crontab_weekdays("1-5/0").is_err());
+        assert!(crontab_weekdays("1-x").is_err());
+    }
+}
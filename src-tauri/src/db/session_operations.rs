@@ -0,0 +1,83 @@
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use crate::db::entities::session_state;
+use chrono::Utc;
+use std::path::Path;
+
+/// Id of the single session row
+const SESSION_ID: &str = "current";
+
+/// Where the user left off
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionStateDto {
+    #[serde(rename = "activeProjectId")]
+    pub active_project_id: Option<String>,
+    /// Open artifacts in tab order
+    #[serde(rename = "openArtifactPaths", default)]
+    pub open_artifact_paths: Vec<String>,
+    #[serde(rename = "activeArtifactPath")]
+    pub active_artifact_path: Option<String>,
+    #[serde(rename = "selectedPlanId")]
+    pub selected_plan_id: Option<String>,
+    #[serde(rename = "selectedWalkthroughId")]
+    pub selected_walkthrough_id: Option<String>,
+    /// None until a session has been saved
+    #[serde(rename = "updatedAt", default)]
+    pub updated_at: Option<i64>,
+}
+
+/// Save the current session, replacing the previous one
+pub async fn save_session_state(
+    db: &DatabaseConnection,
+    state: SessionStateDto,
+) -> Result<(), DbErr> {
+    let open_artifact_paths = serde_json::to_string(&state.open_artifact_paths)
+        .map_err(|e| DbErr::Custom(format!("Failed to serialize open artifacts: {}", e)))?;
+
+    let active_model = session_state::ActiveModel {
+        id: Set(SESSION_ID.to_string()),
+        active_project_id: Set(state.active_project_id),
+        open_artifact_paths: Set(open_artifact_paths),
+        active_artifact_path: Set(state.active_artifact_path),
+        selected_plan_id: Set(state.selected_plan_id),
+        selected_walkthrough_id: Set(state.selected_walkthrough_id),
+        updated_at: Set(Utc::now().timestamp()),
+    };
+
+    if session_state::Entity::find_by_id(SESSION_ID).one(db).await?.is_some() {
+        active_model.update(db).await?;
+    } else {
+        active_model.insert(db).await?;
+    }
+
+    Ok(())
+}
+
+/// Load the saved session for restoring at startup.
+///
+/// Artifacts whose files have been deleted or moved since are dropped.
+/// Deleted projects, plans and walkthroughs are already cleared by the
+/// database.
+pub async fn get_session_state(db: &DatabaseConnection) -> Result<SessionStateDto, DbErr> {
+    let Some(model) = session_state::Entity::find_by_id(SESSION_ID).one(db).await? else {
+        return Ok(SessionStateDto::default());
+    };
+
+    let open_artifact_paths: Vec<String> = serde_json::from_str::<Vec<String>>(&model.open_artifact_paths)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| Path::new(path).exists())
+        .collect();
+    let active_artifact_path = model
+        .active_artifact_path
+        .filter(|path| Path::new(path).exists());
+
+    Ok(SessionStateDto {
+        active_project_id: model.active_project_id,
+        open_artifact_paths,
+        active_artifact_path,
+        selected_plan_id: model.selected_plan_id,
+        selected_walkthrough_id: model.selected_walkthrough_id,
+        updated_at: Some(model.updated_at),
+    })
+}
@@ -1,7 +1,7 @@
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
-use crate::db::entities::{task, task_project};
-use chrono::Utc;
+use crate::db::entities::{task, task_activity, task_project, walkthrough_takeaway_task};
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 /// Task DTO for frontend communication
@@ -22,6 +22,57 @@ pub struct TaskDto {
     pub complexity: Option<String>,
     #[serde(rename = "type")]
     pub type_: Option<String>,
+    #[serde(rename = "dueAt")]
+    pub due_at: Option<String>,
+    #[serde(rename = "remindAt")]
+    pub remind_at: Option<String>,
+    /// Manual board position within its status column; only set when tasks
+    /// are listed for a single project
+    #[serde(rename = "orderIndex", skip_serializing_if = "Option::is_none")]
+    pub order_index: Option<i32>,
+    /// When the task was soft-deleted; deleted tasks are left out of every
+    /// listing except `get_deleted_tasks`
+    #[serde(rename = "deletedAt", skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
+}
+
+/// One entry in a task's history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskActivityDto {
+    pub id: i32,
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+    /// "created", "updated", "deleted" or "restored"
+    pub action: String,
+    /// The changed field for "updated" (e.g. "status", "priority", "projects")
+    pub field: Option<String>,
+    #[serde(rename = "oldValue")]
+    pub old_value: Option<String>,
+    #[serde(rename = "newValue")]
+    pub new_value: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+}
+
+/// Changes applied to every task in a bulk update. Unset fields are left
+/// alone; `add_*`/`remove_*` apply after the matching replacement list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskPatch {
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    /// Replaces each task's tags
+    pub tags: Option<Vec<String>>,
+    #[serde(rename = "addTags", default)]
+    pub add_tags: Vec<String>,
+    #[serde(rename = "removeTags", default)]
+    pub remove_tags: Vec<String>,
+    /// Replaces each task's projects
+    #[serde(rename = "projectIds")]
+    pub project_ids: Option<Vec<String>>,
+    #[serde(rename = "addProjectIds", default)]
+    pub add_project_ids: Vec<String>,
+    #[serde(rename = "removeProjectIds", default)]
+    pub remove_project_ids: Vec<String>,
 }
 
 /// Get all tasks (optionally filtered by project IDs)
@@ -32,11 +83,17 @@ pub async fn get_tasks(
     let mut tasks: Vec<TaskDto> = Vec::new();
 
     if let Some(proj_ids) = project_ids {
+        let single_project = proj_ids.len() == 1;
+
         // Get tasks associated with specific projects
         let task_project_links: Vec<task_project::Model> = task_project::Entity::find()
             .filter(task_project::Column::ProjectId.is_in(proj_ids))
             .all(db)
             .await?;
+        let order: std::collections::HashMap<&str, i32> = task_project_links
+            .iter()
+            .filter_map(|tp| Some((tp.task_id.as_str(), tp.order_index?)))
+            .collect();
 
         // Get unique task IDs
         let task_ids: Vec<String> = task_project_links
@@ -49,18 +106,33 @@ pub async fn get_tasks(
         if !task_ids.is_empty() {
             let task_models: Vec<task::Model> = task::Entity::find()
                 .filter(task::Column::Id.is_in(task_ids))
+                .filter(task::Column::DeletedAt.is_null())
                 .all(db)
                 .await?;
 
             for task_model in task_models {
                 // Get project IDs for this task
                 let project_ids = get_task_project_ids(db, &task_model.id).await?;
-                tasks.push(model_to_dto(task_model, project_ids));
+                let order_index = if single_project { order.get(task_model.id.as_str()).copied() } else { None };
+                let mut task = model_to_dto(task_model, project_ids);
+                task.order_index = order_index;
+                tasks.push(task);
             }
+
+            // Board order first, then unordered tasks by creation
+            tasks.sort_by(|a, b| match (a.order_index, b.order_index) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.created_at.cmp(&b.created_at),
+            });
         }
     } else {
         // Get all tasks
-        let task_models: Vec<task::Model> = task::Entity::find().all(db).await?;
+        let task_models: Vec<task::Model> = task::Entity::find()
+            .filter(task::Column::DeletedAt.is_null())
+            .all(db)
+            .await?;
 
         for task_model in task_models {
             let project_ids = get_task_project_ids(db, &task_model.id).await?;
@@ -71,9 +143,13 @@ pub async fn get_tasks(
     Ok(tasks)
 }
 
-/// Get a single task by ID
+/// Get a single task by ID (None if it's deleted)
 pub async fn get_task(db: &DatabaseConnection, task_id: &str) -> Result<Option<TaskDto>, DbErr> {
-    if let Some(task_model) = task::Entity::find_by_id(task_id).one(db).await? {
+    if let Some(task_model) = task::Entity::find_by_id(task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(db)
+        .await?
+    {
         let project_ids = get_task_project_ids(db, task_id).await?;
         Ok(Some(model_to_dto(task_model, project_ids)))
     } else {
@@ -92,7 +168,11 @@ pub async fn create_task(
     status: Option<String>,
     complexity: Option<String>,
     type_: Option<String>,
+    due_at: Option<String>,
+    remind_at: Option<String>,
 ) -> Result<TaskDto, DbErr> {
+    let due_at = due_at.map(|t| normalize_timestamp(&t)).transpose()?;
+    let remind_at = remind_at.map(|t| normalize_timestamp(&t)).transpose()?;
     let now = Utc::now().to_rfc3339();
     let task_id = Uuid::new_v4().to_string();
 
@@ -111,9 +191,13 @@ pub async fn create_task(
         status: Set(status.unwrap_or_else(|| "backlog".to_string())),
         complexity: Set(complexity),
         type_: Set(type_),
+        due_at: Set(due_at),
+        remind_at: Set(remind_at),
+        deleted_at: Set(None),
     };
 
     let task_model = task_active_model.insert(db).await?;
+    record_activity(db, &task_id, "created", None, None, None).await?;
 
     // Create task-project associations
     for project_id in &project_ids {
@@ -121,6 +205,7 @@ pub async fn create_task(
             id: NotSet,
             task_id: Set(task_id.clone()),
             project_id: Set(project_id.clone()),
+            order_index: Set(None),
         };
         task_project_model.insert(db).await?;
     }
@@ -129,8 +214,8 @@ pub async fn create_task(
 }
 
 /// Update an existing task
-pub async fn update_task(
-    db: &DatabaseConnection,
+pub async fn update_task<C: ConnectionTrait>(
+    db: &C,
     task_id: String,
     title: Option<String>,
     description: Option<Option<String>>,
@@ -140,6 +225,8 @@ pub async fn update_task(
     status: Option<String>,
     complexity: Option<Option<String>>,
     type_: Option<Option<String>>,
+    due_at: Option<Option<String>>,
+    remind_at: Option<Option<String>>,
 ) -> Result<TaskDto, DbErr> {
     // Find existing task
     let task_model = task::Entity::find_by_id(&task_id)
@@ -147,6 +234,7 @@ pub async fn update_task(
         .await?
         .ok_or_else(|| DbErr::RecordNotFound(format!("Task not found: {}", task_id)))?;
 
+    let before = task_model.clone();
     let mut task_active_model: task::ActiveModel = task_model.into();
 
     // Update fields if provided
@@ -175,29 +263,67 @@ pub async fn update_task(
         let tags_json = serde_json::to_string(&t).unwrap_or_else(|_| "[]".to_string());
         task_active_model.tags = Set(tags_json);
     }
+    if let Some(d) = due_at {
+        task_active_model.due_at = Set(d.map(|t| normalize_timestamp(&t)).transpose()?);
+    }
+    if let Some(r) = remind_at {
+        task_active_model.remind_at = Set(r.map(|t| normalize_timestamp(&t)).transpose()?);
+    }
 
     task_active_model.updated_at = Set(Utc::now().to_rfc3339());
 
     let updated_task = task_active_model.update(db).await?;
+    record_changes(db, &before, &updated_task).await?;
+
+    // Keep a takeaway the task was made from in step
+    let completed = updated_task.status == "completed";
+    if completed != (before.status == "completed") {
+        if let Err(e) = crate::db::walkthrough_operations::sync_takeaway_from_task(db, &task_id, completed).await {
+            tracing::warn!("Failed to sync takeaway with task {}: {}", task_id, e);
+        }
+    }
 
     // Update project associations if provided
     let final_project_ids = if let Some(new_project_ids) = project_ids {
+        let old_links = task_project::Entity::find()
+            .filter(task_project::Column::TaskId.eq(&task_id))
+            .all(db)
+            .await?;
+        let old_project_ids: Vec<String> = old_links.iter().map(|tp| tp.project_id.clone()).collect();
+
         // Delete existing associations
         task_project::Entity::delete_many()
             .filter(task_project::Column::TaskId.eq(&task_id))
             .exec(db)
             .await?;
 
-        // Create new associations
+        // Create new associations, keeping the board position in projects the task stays in
         for project_id in &new_project_ids {
+            let order_index = old_links
+                .iter()
+                .find(|tp| &tp.project_id == project_id)
+                .and_then(|tp| tp.order_index);
             let task_project_model = task_project::ActiveModel {
                 id: NotSet,
                 task_id: Set(task_id.clone()),
                 project_id: Set(project_id.clone()),
+                order_index: Set(order_index),
             };
             task_project_model.insert(db).await?;
         }
 
+        if sorted(&old_project_ids) != sorted(&new_project_ids) {
+            record_activity(
+                db,
+                &task_id,
+                "updated",
+                Some("projects"),
+                Some(json_list(&old_project_ids)),
+                Some(json_list(&new_project_ids)),
+            )
+            .await?;
+        }
+
         new_project_ids
     } else {
         get_task_project_ids(db, &task_id).await?
@@ -206,13 +332,88 @@ pub async fn update_task(
     Ok(model_to_dto(updated_task, final_project_ids))
 }
 
-/// Delete a task
-pub async fn delete_task(db: &DatabaseConnection, task_id: &str) -> Result<(), DbErr> {
+/// Soft-deletes a task: it keeps its projects and history and can be
+/// brought back with `restore_task` until it's purged
+pub async fn delete_task<C: ConnectionTrait>(db: &C, task_id: &str) -> Result<(), DbErr> {
+    let task_model = task::Entity::find_by_id(task_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Task not found: {}", task_id)))?;
+    if task_model.deleted_at.is_some() {
+        return Ok(());
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let mut task_active_model: task::ActiveModel = task_model.into();
+    task_active_model.deleted_at = Set(Some(now.clone()));
+    task_active_model.updated_at = Set(now);
+    task_active_model.update(db).await?;
+    record_activity(db, task_id, "deleted", None, None, None).await
+}
+
+/// Brings back a soft-deleted task
+pub async fn restore_task(db: &DatabaseConnection, task_id: &str) -> Result<TaskDto, DbErr> {
+    let task_model = task::Entity::find_by_id(task_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Task not found: {}", task_id)))?;
+
+    let restored = if task_model.deleted_at.is_some() {
+        let mut task_active_model: task::ActiveModel = task_model.into();
+        task_active_model.deleted_at = Set(None);
+        task_active_model.updated_at = Set(Utc::now().to_rfc3339());
+        let restored = task_active_model.update(db).await?;
+        record_activity(db, task_id, "restored", None, None, None).await?;
+        restored
+    } else {
+        task_model
+    };
+
+    let project_ids = get_task_project_ids(db, task_id).await?;
+    Ok(model_to_dto(restored, project_ids))
+}
+
+/// Soft-deleted tasks, most recently deleted first
+pub async fn get_deleted_tasks(
+    db: &DatabaseConnection,
+    project_ids: Option<Vec<String>>,
+) -> Result<Vec<TaskDto>, DbErr> {
+    let query = task::Entity::find()
+        .filter(task::Column::DeletedAt.is_not_null())
+        .order_by_desc(task::Column::DeletedAt);
+    tasks_for_query(db, query, project_ids).await
+}
+
+/// Permanently deletes tasks soft-deleted before `before`. Returns how many
+/// were removed.
+pub async fn purge_deleted_tasks(db: &DatabaseConnection, before: DateTime<Utc>) -> Result<usize, DbErr> {
+    let txn = db.begin().await?;
+    let expired = task::Entity::find()
+        .filter(task::Column::DeletedAt.lt(before.to_rfc3339()))
+        .all(&txn)
+        .await?;
+    for task_model in &expired {
+        purge_task(&txn, &task_model.id).await?;
+    }
+    txn.commit().await?;
+    Ok(expired.len())
+}
+
+/// Permanently deletes a task with its project links and history
+pub async fn purge_task<C: ConnectionTrait>(db: &C, task_id: &str) -> Result<(), DbErr> {
     // Delete task-project associations (CASCADE should handle this, but being explicit)
     task_project::Entity::delete_many()
         .filter(task_project::Column::TaskId.eq(task_id))
         .exec(db)
         .await?;
+    task_activity::Entity::delete_many()
+        .filter(task_activity::Column::TaskId.eq(task_id))
+        .exec(db)
+        .await?;
+    walkthrough_takeaway_task::Entity::delete_many()
+        .filter(walkthrough_takeaway_task::Column::TaskId.eq(task_id))
+        .exec(db)
+        .await?;
 
     // Delete task
     task::Entity::delete_by_id(task_id).exec(db).await?;
@@ -220,8 +421,502 @@ pub async fn delete_task(db: &DatabaseConnection, task_id: &str) -> Result<(), D
     Ok(())
 }
 
+/// Applies `patch` to each of `task_ids` in a single transaction; if any
+/// task is missing or fails to update, none are changed.
+pub async fn bulk_update_tasks(
+    db: &DatabaseConnection,
+    task_ids: &[String],
+    patch: &TaskPatch,
+) -> Result<Vec<TaskDto>, DbErr> {
+    let txn = db.begin().await?;
+    let mut updated = Vec::with_capacity(task_ids.len());
+
+    for task_id in task_ids {
+        let task_model = task::Entity::find_by_id(task_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Task not found: {}", task_id)))?;
+
+        let tags = if patch.tags.is_some() || !patch.add_tags.is_empty() || !patch.remove_tags.is_empty() {
+            let current: Vec<String> = serde_json::from_str(&task_model.tags).unwrap_or_default();
+            Some(apply_list_patch(
+                patch.tags.clone().unwrap_or(current),
+                &patch.add_tags,
+                &patch.remove_tags,
+            ))
+        } else {
+            None
+        };
+        let project_ids = if patch.project_ids.is_some()
+            || !patch.add_project_ids.is_empty()
+            || !patch.remove_project_ids.is_empty()
+        {
+            let current = match &patch.project_ids {
+                Some(ids) => ids.clone(),
+                None => get_task_project_ids(&txn, task_id).await?,
+            };
+            Some(apply_list_patch(current, &patch.add_project_ids, &patch.remove_project_ids))
+        } else {
+            None
+        };
+
+        updated.push(
+            update_task(
+                &txn,
+                task_id.clone(),
+                None,
+                None,
+                patch.priority.clone(),
+                tags,
+                project_ids,
+                patch.status.clone(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?,
+        );
+    }
+
+    txn.commit().await?;
+    Ok(updated)
+}
+
+/// Deletes each of `task_ids` in a single transaction, soft unless
+/// `permanent`. IDs that don't exist are ignored; returns how many tasks
+/// were deleted.
+pub async fn bulk_delete_tasks(
+    db: &DatabaseConnection,
+    task_ids: &[String],
+    permanent: bool,
+) -> Result<usize, DbErr> {
+    let txn = db.begin().await?;
+    let mut query = task::Entity::find().filter(task::Column::Id.is_in(task_ids.iter().cloned()));
+    if !permanent {
+        query = query.filter(task::Column::DeletedAt.is_null());
+    }
+    let existing = query.all(&txn).await?;
+    for task_model in &existing {
+        if permanent {
+            purge_task(&txn, &task_model.id).await?;
+        } else {
+            delete_task(&txn, &task_model.id).await?;
+        }
+    }
+    txn.commit().await?;
+    Ok(existing.len())
+}
+
+// `list` with `add` appended (skipping ones already present) and `remove` taken out
+fn apply_list_patch(mut list: Vec<String>, add: &[String], remove: &[String]) -> Vec<String> {
+    for value in add {
+        if !list.contains(value) {
+            list.push(value.clone());
+        }
+    }
+    list.retain(|value| !remove.contains(value));
+    list
+}
+
+/// Replaces `tag` with `replacement` on every task linked to `project_id`,
+/// or removes it when `replacement` is None. Returns how many tasks changed.
+pub async fn replace_task_tag(
+    db: &DatabaseConnection,
+    project_id: &str,
+    tag: &str,
+    replacement: Option<&str>,
+) -> Result<usize, DbErr> {
+    let tasks = get_tasks(db, Some(vec![project_id.to_string()])).await?;
+
+    let txn = db.begin().await?;
+    let now = Utc::now().to_rfc3339();
+    let mut changed = 0;
+    for task_dto in tasks {
+        if !task_dto.tags.iter().any(|t| t == tag) {
+            continue;
+        }
+        let Some(task_model) = task::Entity::find_by_id(&task_dto.id).one(&txn).await? else {
+            continue;
+        };
+        let tags = replace_tag(&task_dto.tags, tag, replacement);
+        let before = task_model.clone();
+        let mut task_active_model: task::ActiveModel = task_model.into();
+        task_active_model.tags = Set(serde_json::to_string(&tags).unwrap_or_else(|_| "[]".to_string()));
+        task_active_model.updated_at = Set(now.clone());
+        let updated_task = task_active_model.update(&txn).await?;
+        record_changes(&txn, &before, &updated_task).await?;
+        changed += 1;
+    }
+    txn.commit().await?;
+
+    Ok(changed)
+}
+
+/// Replaces `tag` in `tags`, keeping its position, or removes it when
+/// `replacement` is None. A replacement that's already present isn't added
+/// twice.
+pub fn replace_tag(tags: &[String], tag: &str, replacement: Option<&str>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::with_capacity(tags.len());
+    for existing in tags {
+        let next = if existing == tag { replacement } else { Some(existing.as_str()) };
+        if let Some(next) = next {
+            if !result.iter().any(|t| t == next) {
+                result.push(next.to_string());
+            }
+        }
+    }
+    result
+}
+
+/// Saves the board order of one status column in a project: `task_ids`
+/// in their new order. Tasks not already in `status` are moved to it (a
+/// drag between columns). Runs in a single transaction; fails without
+/// changing anything if a task isn't in the project.
+pub async fn reorder_tasks(
+    db: &DatabaseConnection,
+    project_id: &str,
+    status: &str,
+    task_ids: &[String],
+) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    let now = Utc::now().to_rfc3339();
+
+    for (index, task_id) in task_ids.iter().enumerate() {
+        let link = task_project::Entity::find()
+            .filter(task_project::Column::TaskId.eq(task_id))
+            .filter(task_project::Column::ProjectId.eq(project_id))
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Task {} is not in project {}", task_id, project_id)))?;
+        let mut link: task_project::ActiveModel = link.into();
+        link.order_index = Set(Some(index as i32));
+        link.update(&txn).await?;
+
+        let task_model = task::Entity::find_by_id(task_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Task not found: {}", task_id)))?;
+        if task_model.status != status {
+            let before = task_model.clone();
+            let mut task_active_model: task::ActiveModel = task_model.into();
+            task_active_model.status = Set(status.to_string());
+            task_active_model.updated_at = Set(now.clone());
+            let updated_task = task_active_model.update(&txn).await?;
+            record_changes(&txn, &before, &updated_task).await?;
+        }
+    }
+
+    txn.commit().await
+}
+
+/// Tasks due before `now` that aren't completed, most overdue first
+pub async fn get_overdue_tasks(
+    db: &DatabaseConnection,
+    now: DateTime<Utc>,
+    project_ids: Option<Vec<String>>,
+) -> Result<Vec<TaskDto>, DbErr> {
+    let query = task::Entity::find()
+        .filter(task::Column::DueAt.lt(now.to_rfc3339()))
+        .filter(task::Column::Status.ne("completed"))
+        .filter(task::Column::DeletedAt.is_null())
+        .order_by_asc(task::Column::DueAt);
+    tasks_for_query(db, query, project_ids).await
+}
+
+/// Tasks due between `now` and `until` that aren't completed, soonest first
+pub async fn get_upcoming_tasks(
+    db: &DatabaseConnection,
+    now: DateTime<Utc>,
+    until: DateTime<Utc>,
+    project_ids: Option<Vec<String>>,
+) -> Result<Vec<TaskDto>, DbErr> {
+    let query = task::Entity::find()
+        .filter(task::Column::DueAt.gte(now.to_rfc3339()))
+        .filter(task::Column::DueAt.lte(until.to_rfc3339()))
+        .filter(task::Column::Status.ne("completed"))
+        .filter(task::Column::DeletedAt.is_null())
+        .order_by_asc(task::Column::DueAt);
+    tasks_for_query(db, query, project_ids).await
+}
+
+/// Tasks whose reminder is due at `now`. Each reminder fires once: its
+/// `remind_at` is cleared in the same transaction.
+pub async fn claim_due_reminders(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<Vec<TaskDto>, DbErr> {
+    let txn = db.begin().await?;
+    let models = task::Entity::find()
+        .filter(task::Column::RemindAt.lte(now.to_rfc3339()))
+        .filter(task::Column::DeletedAt.is_null())
+        .all(&txn)
+        .await?;
+
+    let mut tasks = Vec::with_capacity(models.len());
+    for model in models {
+        task::Entity::update_many()
+            .col_expr(task::Column::RemindAt, sea_query::Expr::value(Option::<String>::None))
+            .filter(task::Column::Id.eq(&model.id))
+            .exec(&txn)
+            .await?;
+        let project_ids = task_project::Entity::find()
+            .filter(task_project::Column::TaskId.eq(&model.id))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|tp| tp.project_id)
+            .collect();
+        tasks.push(model_to_dto(model, project_ids));
+    }
+    txn.commit().await?;
+    Ok(tasks)
+}
+
+// Runs a task query, limited to `project_ids`
+async fn tasks_for_query(
+    db: &DatabaseConnection,
+    mut query: Select<task::Entity>,
+    project_ids: Option<Vec<String>>,
+) -> Result<Vec<TaskDto>, DbErr> {
+    if let Some(project_ids) = project_ids {
+        let task_ids: Vec<String> = task_project::Entity::find()
+            .filter(task_project::Column::ProjectId.is_in(project_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|tp| tp.task_id)
+            .collect();
+        query = query.filter(task::Column::Id.is_in(task_ids));
+    }
+
+    let mut tasks = Vec::new();
+    for model in query.all(db).await? {
+        let project_ids = get_task_project_ids(db, &model.id).await?;
+        tasks.push(model_to_dto(model, project_ids));
+    }
+    Ok(tasks)
+}
+
+// Stores timestamps as RFC 3339 UTC so they compare as strings
+fn normalize_timestamp(timestamp: &str) -> Result<String, DbErr> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| t.with_timezone(&Utc).to_rfc3339())
+        .map_err(|e| DbErr::Custom(format!("Invalid timestamp '{}': {}", timestamp, e)))
+}
+
+/// A task matching a full-text search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSearchHit {
+    pub task: TaskDto,
+    /// Higher is better (negated bm25)
+    pub score: f64,
+    /// HTML-escaped title with matches wrapped in `<mark>`
+    #[serde(rename = "titleHighlight")]
+    pub title_highlight: String,
+    /// HTML-escaped excerpt of the description around the matches, wrapped
+    /// the same way
+    #[serde(rename = "descriptionSnippet")]
+    pub description_snippet: Option<String>,
+}
+
+/// Default number of tasks returned by `search_tasks`
+pub const DEFAULT_TASK_SEARCH_LIMIT: u64 = 50;
+
+// Match markers from highlight()/snippet(), swapped for <mark> after escaping
+const MATCH_START: char = '\u{E000}';
+const MATCH_END: char = '\u{E001}';
+
+/// Searches task titles, descriptions and tags through the `tasks_fts`
+/// index, best matches first.
+///
+/// Every word in `query` must match, as a prefix (`auth` finds
+/// "authentication"). Title matches weigh most, then tags, then the
+/// description. `project_ids` limits the search to tasks in those projects.
+pub async fn search_tasks(
+    db: &DatabaseConnection,
+    query: &str,
+    project_ids: Option<Vec<String>>,
+    limit: u64,
+) -> Result<Vec<TaskSearchHit>, DbErr> {
+    let Some(match_query) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+
+    let mut sql = format!(
+        r#"
+        SELECT t.id AS id,
+               bm25(tasks_fts, 10.0, 1.0, 5.0) AS rank,
+               highlight(tasks_fts, 0, '{start}', '{end}') AS title_highlight,
+               snippet(tasks_fts, 1, '{start}', '{end}', '…', 16) AS description_snippet
+        FROM tasks_fts
+        JOIN tasks_fts_keys k ON k.fts_rowid = tasks_fts.rowid
+        JOIN tasks t ON t.id = k.task_id
+        WHERE tasks_fts MATCH ? AND t.deleted_at IS NULL
+        "#,
+        start = MATCH_START,
+        end = MATCH_END,
+    );
+    let mut values: Vec<Value> = vec![match_query.into()];
+    if let Some(project_ids) = project_ids {
+        if project_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders = vec!["?"; project_ids.len()].join(", ");
+        sql.push_str(&format!(
+            " AND t.id IN (SELECT task_id FROM task_projects WHERE project_id IN ({}))",
+            placeholders
+        ));
+        values.extend(project_ids.into_iter().map(Value::from));
+    }
+    sql.push_str(" ORDER BY rank LIMIT ?");
+    values.push(limit.into());
+
+    let rows = db
+        .query_all(Statement::from_sql_and_values(db.get_database_backend(), sql, values))
+        .await?;
+
+    let mut matches = Vec::with_capacity(rows.len());
+    for row in rows {
+        let id: String = row.try_get("", "id")?;
+        let rank: f64 = row.try_get("", "rank")?;
+        let title_highlight: String = row.try_get("", "title_highlight")?;
+        let description_snippet: Option<String> = row.try_get("", "description_snippet")?;
+        matches.push((id, rank, title_highlight, description_snippet));
+    }
+
+    let ids: Vec<String> = matches.iter().map(|(id, ..)| id.clone()).collect();
+    let mut models: std::collections::HashMap<String, task::Model> = task::Entity::find()
+        .filter(task::Column::Id.is_in(ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|model| (model.id.clone(), model))
+        .collect();
+
+    let mut hits = Vec::with_capacity(matches.len());
+    for (id, rank, title_highlight, description_snippet) in matches {
+        let Some(model) = models.remove(&id) else {
+            continue;
+        };
+        let project_ids = get_task_project_ids(db, &id).await?;
+        hits.push(TaskSearchHit {
+            task: model_to_dto(model, project_ids),
+            score: -rank,
+            title_highlight: mark_matches(&title_highlight),
+            description_snippet: description_snippet
+                .filter(|snippet| snippet.contains(MATCH_START))
+                .map(|snippet| mark_matches(&snippet)),
+        });
+    }
+
+    Ok(hits)
+}
+
+// Each word as a quoted FTS5 prefix query; none if there are no words
+fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+// HTML-escapes `text` and turns the match markers into <mark> tags
+fn mark_matches(text: &str) -> String {
+    let mut marked = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            MATCH_START => marked.push_str("<mark>"),
+            MATCH_END => marked.push_str("</mark>"),
+            '&' => marked.push_str("&amp;"),
+            '<' => marked.push_str("&lt;"),
+            '>' => marked.push_str("&gt;"),
+            '"' => marked.push_str("&quot;"),
+            '\'' => marked.push_str("&#39;"),
+            c => marked.push(c),
+        }
+    }
+    marked
+}
+
+/// A task's history, newest first
+pub async fn get_task_activity(db: &DatabaseConnection, task_id: &str) -> Result<Vec<TaskActivityDto>, DbErr> {
+    let entries = task_activity::Entity::find()
+        .filter(task_activity::Column::TaskId.eq(task_id))
+        .order_by_desc(task_activity::Column::CreatedAt)
+        .order_by_desc(task_activity::Column::Id)
+        .all(db)
+        .await?;
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| TaskActivityDto {
+            id: entry.id,
+            task_id: entry.task_id,
+            action: entry.action,
+            field: entry.field,
+            old_value: entry.old_value,
+            new_value: entry.new_value,
+            created_at: entry.created_at,
+        })
+        .collect())
+}
+
+/// Helper: Record one task_activity row
+async fn record_activity<C: ConnectionTrait>(
+    db: &C,
+    task_id: &str,
+    action: &str,
+    field: Option<&str>,
+    old_value: Option<String>,
+    new_value: Option<String>,
+) -> Result<(), DbErr> {
+    task_activity::ActiveModel {
+        id: NotSet,
+        task_id: Set(task_id.to_string()),
+        action: Set(action.to_string()),
+        field: Set(field.map(str::to_string)),
+        old_value: Set(old_value),
+        new_value: Set(new_value),
+        created_at: Set(Utc::now().to_rfc3339()),
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
+/// Helper: Record an "updated" row for each field that differs between two
+/// versions of a task
+async fn record_changes<C: ConnectionTrait>(db: &C, before: &task::Model, after: &task::Model) -> Result<(), DbErr> {
+    let changes = [
+        ("title", Some(before.title.clone()), Some(after.title.clone())),
+        ("description", before.description.clone(), after.description.clone()),
+        ("priority", Some(before.priority.clone()), Some(after.priority.clone())),
+        ("status", Some(before.status.clone()), Some(after.status.clone())),
+        ("complexity", before.complexity.clone(), after.complexity.clone()),
+        ("type", before.type_.clone(), after.type_.clone()),
+        ("tags", Some(before.tags.clone()), Some(after.tags.clone())),
+        ("dueAt", before.due_at.clone(), after.due_at.clone()),
+        ("remindAt", before.remind_at.clone(), after.remind_at.clone()),
+    ];
+    for (field, old_value, new_value) in changes {
+        if old_value != new_value {
+            record_activity(db, &after.id, "updated", Some(field), old_value, new_value).await?;
+        }
+    }
+    Ok(())
+}
+
+fn sorted(ids: &[String]) -> Vec<&String> {
+    let mut ids: Vec<&String> = ids.iter().collect();
+    ids.sort();
+    ids
+}
+
+fn json_list(values: &[String]) -> String {
+    serde_json::to_string(values).unwrap_or_else(|_| "[]".to_string())
+}
+
 /// Helper: Get project IDs for a task
-async fn get_task_project_ids(db: &DatabaseConnection, task_id: &str) -> Result<Vec<String>, DbErr> {
+async fn get_task_project_ids<C: ConnectionTrait>(db: &C, task_id: &str) -> Result<Vec<String>, DbErr> {
     let task_projects: Vec<task_project::Model> = task_project::Entity::find()
         .filter(task_project::Column::TaskId.eq(task_id))
         .all(db)
@@ -246,5 +941,9 @@ fn model_to_dto(model: task::Model, project_ids: Vec<String>) -> TaskDto {
         status: model.status,
         complexity: model.complexity,
         type_: model.type_,
+        due_at: model.due_at,
+        remind_at: model.remind_at,
+        order_index: None,
+        deleted_at: model.deleted_at,
     }
 }
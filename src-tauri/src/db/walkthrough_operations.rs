@@ -1,9 +1,9 @@
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
-use crate::db::entities::{walkthrough, walkthrough_takeaway, walkthrough_note};
+use crate::db::entities::{project, task, walkthrough, walkthrough_takeaway, walkthrough_takeaway_task, walkthrough_note, walkthrough_step};
 use chrono::Utc;
 use uuid::Uuid;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 
 /// Walkthrough DTO for frontend communication
@@ -22,6 +22,8 @@ pub struct WalkthroughDto {
     #[serde(rename = "updatedAt")]
     pub updated_at: i64,
     pub progress: f32, // 0-100 based on takeaway completion
+    #[serde(rename = "deletedAt", skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<i64>,
 }
 
 /// Takeaway DTO
@@ -39,8 +41,30 @@ pub struct TakeawayDto {
     pub completed_at: Option<i64>,
     #[serde(rename = "createdAt")]
     pub created_at: i64,
+    /// The task made from this takeaway with `convert_takeaway_to_task`
+    #[serde(rename = "taskId")]
+    pub task_id: Option<String>,
 }
 
+impl TakeawayDto {
+    fn new(takeaway: walkthrough_takeaway::Model, task_id: Option<String>) -> Self {
+        Self {
+            id: takeaway.id,
+            walkthrough_id: takeaway.walkthrough_id,
+            title: takeaway.title,
+            description: takeaway.description,
+            sort_order: takeaway.sort_order,
+            completed: takeaway.completed != 0,
+            completed_at: takeaway.completed_at,
+            created_at: takeaway.created_at,
+            task_id,
+        }
+    }
+}
+
+/// Valid values for a note's `format`
+pub const NOTE_FORMATS: &[&str] = &["text", "markdown"];
+
 /// Walkthrough Note DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalkthroughNoteDto {
@@ -48,12 +72,88 @@ pub struct WalkthroughNoteDto {
     #[serde(rename = "walkthroughId")]
     pub walkthrough_id: String,
     pub content: String,
+    /// How to render `content`: "text" or "markdown"
+    pub format: String,
+    /// Files attached to the note; markdown links to them use their
+    /// `relativePath`, which resolves from the walkthrough file
+    pub attachments: Vec<crate::core::assets::NoteAttachment>,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+impl WalkthroughNoteDto {
+    // `walkthrough_file` is the note's walkthrough, where its attachments
+    // are found
+    fn new(note: walkthrough_note::Model, walkthrough_file: &Path) -> Self {
+        let attachments = crate::core::assets::note_attachments(walkthrough_file, &note.id).unwrap_or_default();
+        Self {
+            id: note.id,
+            walkthrough_id: note.walkthrough_id,
+            content: note.content,
+            format: note.format,
+            attachments,
+            created_at: note.created_at,
+            updated_at: note.updated_at,
+        }
+    }
+}
+
+/// Walkthrough Step DTO: a stop in the walkthrough at a range of lines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkthroughStepDto {
+    pub id: String,
+    #[serde(rename = "walkthroughId")]
+    pub walkthrough_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// Relative to the project root
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    #[serde(rename = "startLine")]
+    pub start_line: i32,
+    #[serde(rename = "endLine")]
+    pub end_line: i32,
+    /// The commit the lines refer to; none outside a git repository
+    #[serde(rename = "commitSha")]
+    pub commit_sha: Option<String>,
+    #[serde(rename = "sortOrder")]
+    pub sort_order: i32,
     #[serde(rename = "createdAt")]
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
     pub updated_at: i64,
 }
 
+impl From<walkthrough_step::Model> for WalkthroughStepDto {
+    fn from(s: walkthrough_step::Model) -> Self {
+        Self {
+            id: s.id,
+            walkthrough_id: s.walkthrough_id,
+            title: s.title,
+            description: s.description,
+            file_path: s.file_path,
+            start_line: s.start_line,
+            end_line: s.end_line,
+            commit_sha: s.commit_sha,
+            sort_order: s.sort_order,
+            created_at: s.created_at,
+            updated_at: s.updated_at,
+        }
+    }
+}
+
+/// Outcome of re-anchoring one step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepReanchorDto {
+    pub step: WalkthroughStepDto,
+    /// "unchanged", "moved" (same lines, new place), "changed" (some lines
+    /// were edited or removed), "lost" (none of the lines are left; the step
+    /// is left as it was) or "unanchored" (no commit to follow from)
+    pub status: String,
+}
+
 /// Walkthrough Details DTO (includes takeaways and notes)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalkthroughDetailsDto {
@@ -71,11 +171,12 @@ pub struct WalkthroughDetailsDto {
     pub updated_at: i64,
     pub takeaways: Vec<TakeawayDto>,
     pub notes: Vec<WalkthroughNoteDto>,
+    pub steps: Vec<WalkthroughStepDto>,
     pub progress: f32,
 }
 
 // Helper function to slugify walkthrough name
-fn slugify(name: &str) -> String {
+pub(crate) fn slugify(name: &str) -> String {
     name.to_lowercase()
         .chars()
         .map(|c| if c.is_alphanumeric() { c } else { '-' })
@@ -143,6 +244,7 @@ description: {}
         status: Set("not_started".to_string()),
         created_at: Set(now),
         updated_at: Set(now),
+        deleted_at: Set(None),
     };
 
     let walkthrough_model = walkthrough_active_model.insert(db).await?;
@@ -173,6 +275,7 @@ description: {}
         created_at: walkthrough_model.created_at,
         updated_at: walkthrough_model.updated_at,
         progress: 0.0,
+        deleted_at: None,
     })
 }
 
@@ -189,10 +292,33 @@ pub async fn get_project_walkthroughs(
 
     let walkthroughs: Vec<walkthrough::Model> = walkthrough::Entity::find()
         .filter(walkthrough::Column::ProjectId.eq(&project_id))
+        .filter(walkthrough::Column::DeletedAt.is_null())
         .order_by_desc(walkthrough::Column::CreatedAt)
         .all(db)
         .await?;
 
+    walkthroughs_to_dtos(db, walkthroughs).await
+}
+
+/// Soft-deleted walkthroughs of a project, most recently deleted first
+pub async fn get_deleted_walkthroughs(
+    db: &DatabaseConnection,
+    project_id: String,
+) -> Result<Vec<WalkthroughDto>, DbErr> {
+    let walkthroughs: Vec<walkthrough::Model> = walkthrough::Entity::find()
+        .filter(walkthrough::Column::ProjectId.eq(&project_id))
+        .filter(walkthrough::Column::DeletedAt.is_not_null())
+        .order_by_desc(walkthrough::Column::DeletedAt)
+        .all(db)
+        .await?;
+
+    walkthroughs_to_dtos(db, walkthroughs).await
+}
+
+async fn walkthroughs_to_dtos(
+    db: &DatabaseConnection,
+    walkthroughs: Vec<walkthrough::Model>,
+) -> Result<Vec<WalkthroughDto>, DbErr> {
     let mut walkthrough_dtos = Vec::new();
     for w in walkthroughs {
         let progress = calculate_walkthrough_progress(db, &w.id).await?;
@@ -206,6 +332,7 @@ pub async fn get_project_walkthroughs(
             created_at: w.created_at,
             updated_at: w.updated_at,
             progress,
+            deleted_at: w.deleted_at,
         });
     }
 
@@ -228,6 +355,10 @@ pub async fn sync_project_walkthroughs(
         return Ok(());
     }
 
+    // Overlapping syncs (watcher events arrive in bursts) would both insert
+    // records for new files
+    let _lock = crate::core::file_lock::lock(&walkthroughs_dir, "walkthrough sync").await?;
+
     // Get all existing DB records for this project
     let existing_walkthroughs: Vec<walkthrough::Model> = walkthrough::Entity::find()
         .filter(walkthrough::Column::ProjectId.eq(project_id))
@@ -279,6 +410,7 @@ pub async fn sync_project_walkthroughs(
                 status: Set("not_started".to_string()),
                 created_at: Set(now),
                 updated_at: Set(now),
+                deleted_at: Set(None),
             };
 
             let _ = walkthrough_active.insert(db).await;
@@ -320,6 +452,7 @@ pub async fn get_or_create_walkthrough_by_path(
             created_at: w.created_at,
             updated_at: w.updated_at,
             progress,
+            deleted_at: w.deleted_at,
         });
     }
 
@@ -342,6 +475,7 @@ pub async fn get_or_create_walkthrough_by_path(
         status: Set("not_started".to_string()),
         created_at: Set(now),
         updated_at: Set(now),
+        deleted_at: Set(None),
     };
 
     let model = walkthrough_active.insert(db).await?;
@@ -356,6 +490,7 @@ pub async fn get_or_create_walkthrough_by_path(
         created_at: model.created_at,
         updated_at: model.updated_at,
         progress: 0.0,
+        deleted_at: None,
     })
 }
 
@@ -420,6 +555,8 @@ pub async fn get_walkthrough_details(
     // Get notes
     let notes = get_walkthrough_notes_internal(db, &walkthrough_id).await?;
 
+    let steps = get_walkthrough_steps(db, walkthrough_id.clone()).await?;
+
     // Calculate progress
     let total = takeaways.len();
     let completed = takeaways.iter().filter(|t| t.completed).count();
@@ -440,6 +577,7 @@ pub async fn get_walkthrough_details(
         updated_at: walkthrough_model.updated_at,
         takeaways,
         notes,
+        steps,
         progress,
     })
 }
@@ -473,8 +611,8 @@ pub async fn update_walkthrough_file_path(
 }
 
 // Helper to calculate walkthrough progress from takeaways
-async fn calculate_walkthrough_progress(
-    db: &DatabaseConnection,
+async fn calculate_walkthrough_progress<C: ConnectionTrait>(
+    db: &C,
     walkthrough_id: &str,
 ) -> Result<f32, DbErr> {
     let takeaways = get_walkthrough_takeaways(db, walkthrough_id).await?;
@@ -491,9 +629,44 @@ async fn calculate_walkthrough_progress(
     Ok(progress)
 }
 
+/// Writes a walkthrough's `status` and `progress` (whole percent) into its
+/// file's front matter. Returns whether the file changed.
+pub async fn sync_walkthrough_front_matter<C: ConnectionTrait>(db: &C, walkthrough_id: &str) -> Result<bool, DbErr> {
+    use serde_yaml::Value;
+
+    let walkthrough_model = walkthrough::Entity::find_by_id(walkthrough_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Walkthrough not found: {}", walkthrough_id)))?;
+    let progress = calculate_walkthrough_progress(db, walkthrough_id).await?;
+
+    let path = Path::new(&walkthrough_model.file_path);
+    if !path.is_file() {
+        return Err(DbErr::Custom(format!("Walkthrough file not found: {}", walkthrough_model.file_path)));
+    }
+    let _lock = crate::core::file_lock::lock(path, "sync_walkthrough_front_matter").await?;
+    crate::commands::edit_front_matter(path, &walkthrough_model.file_path, |front_matter| {
+        front_matter.insert(Value::String("status".to_string()), Value::String(walkthrough_model.status.clone()));
+        front_matter.insert(Value::String("progress".to_string()), Value::from(progress.round() as u64));
+    })
+    .map_err(DbErr::Custom)
+}
+
+// Mirrors status and progress into the walkthrough's file when the
+// `walkthroughs.syncProgressToFrontMatter` setting is on. The database
+// change is already made, so a failure here is only logged.
+async fn mirror_progress<C: ConnectionTrait>(db: &C, walkthrough_id: &str) {
+    if !crate::core::settings::load_settings().walkthroughs.sync_progress_to_front_matter {
+        return;
+    }
+    if let Err(e) = sync_walkthrough_front_matter(db, walkthrough_id).await {
+        tracing::warn!("Failed to sync walkthrough {} progress to front matter: {}", walkthrough_id, e);
+    }
+}
+
 // Helper to get takeaways
-async fn get_walkthrough_takeaways(
-    db: &DatabaseConnection,
+async fn get_walkthrough_takeaways<C: ConnectionTrait>(
+    db: &C,
     walkthrough_id: &str,
 ) -> Result<Vec<TakeawayDto>, DbErr> {
     let takeaways: Vec<walkthrough_takeaway::Model> = walkthrough_takeaway::Entity::find()
@@ -502,16 +675,29 @@ async fn get_walkthrough_takeaways(
         .all(db)
         .await?;
 
-    Ok(takeaways.into_iter().map(|t| TakeawayDto {
-        id: t.id,
-        walkthrough_id: t.walkthrough_id,
-        title: t.title,
-        description: t.description,
-        sort_order: t.sort_order,
-        completed: t.completed != 0,
-        completed_at: t.completed_at,
-        created_at: t.created_at,
-    }).collect())
+    let mut task_ids: std::collections::HashMap<String, String> = walkthrough_takeaway_task::Entity::find()
+        .filter(walkthrough_takeaway_task::Column::TakeawayId.is_in(takeaways.iter().map(|t| t.id.clone())))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|link| (link.takeaway_id, link.task_id))
+        .collect();
+
+    Ok(takeaways
+        .into_iter()
+        .map(|t| {
+            let task_id = task_ids.remove(&t.id);
+            TakeawayDto::new(t, task_id)
+        })
+        .collect())
+}
+
+// The task linked to a takeaway, if any
+async fn takeaway_task_id<C: ConnectionTrait>(db: &C, takeaway_id: &str) -> Result<Option<String>, DbErr> {
+    Ok(walkthrough_takeaway_task::Entity::find_by_id(takeaway_id)
+        .one(db)
+        .await?
+        .map(|link| link.task_id))
 }
 
 // Helper to get notes
@@ -519,19 +705,41 @@ async fn get_walkthrough_notes_internal(
     db: &DatabaseConnection,
     walkthrough_id: &str,
 ) -> Result<Vec<WalkthroughNoteDto>, DbErr> {
+    let walkthrough_file = walkthrough_file_path(db, walkthrough_id).await?;
     let notes: Vec<walkthrough_note::Model> = walkthrough_note::Entity::find()
         .filter(walkthrough_note::Column::WalkthroughId.eq(walkthrough_id))
         .order_by_desc(walkthrough_note::Column::CreatedAt)
         .all(db)
         .await?;
 
-    Ok(notes.into_iter().map(|n| WalkthroughNoteDto {
-        id: n.id,
-        walkthrough_id: n.walkthrough_id,
-        content: n.content,
-        created_at: n.created_at,
-        updated_at: n.updated_at,
-    }).collect())
+    Ok(notes.into_iter().map(|n| WalkthroughNoteDto::new(n, &walkthrough_file)).collect())
+}
+
+// The walkthrough's markdown file
+async fn walkthrough_file_path(db: &DatabaseConnection, walkthrough_id: &str) -> Result<PathBuf, DbErr> {
+    let walkthrough_model = walkthrough::Entity::find_by_id(walkthrough_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Walkthrough not found: {}", walkthrough_id)))?;
+    Ok(PathBuf::from(walkthrough_model.file_path))
+}
+
+async fn find_note(db: &DatabaseConnection, note_id: &str) -> Result<walkthrough_note::Model, DbErr> {
+    walkthrough_note::Entity::find_by_id(note_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Note not found: {}", note_id)))
+}
+
+fn validate_note_format(format: &str) -> Result<(), DbErr> {
+    if !NOTE_FORMATS.contains(&format) {
+        return Err(DbErr::Custom(format!(
+            "Invalid note format: {}. Must be one of: {}",
+            format,
+            NOTE_FORMATS.join(", ")
+        )));
+    }
+    Ok(())
 }
 
 /// Update a walkthrough
@@ -560,6 +768,7 @@ pub async fn update_walkthrough(
         walkthrough_active.description = Set(desc);
     }
 
+    let status_changed = status.as_ref().is_some_and(|s| *s != walkthrough_model.status);
     if let Some(s) = status {
         walkthrough_active.status = Set(s);
     }
@@ -567,6 +776,9 @@ pub async fn update_walkthrough(
     walkthrough_active.updated_at = Set(now);
 
     let updated_walkthrough = walkthrough_active.update(db).await?;
+    if status_changed {
+        mirror_progress(db, &walkthrough_id).await;
+    }
 
     let progress = calculate_walkthrough_progress(db, &walkthrough_id).await?;
 
@@ -580,11 +792,58 @@ pub async fn update_walkthrough(
         created_at: updated_walkthrough.created_at,
         updated_at: updated_walkthrough.updated_at,
         progress,
+        deleted_at: updated_walkthrough.deleted_at,
     })
 }
 
-/// Delete a walkthrough (removes file and database records)
-pub async fn delete_walkthrough(
+/// Soft-deletes a walkthrough. Its file and records stay in place so
+/// `restore_walkthrough` can bring it back until it's purged.
+pub async fn delete_walkthrough(db: &DatabaseConnection, walkthrough_id: &str) -> Result<(), DbErr> {
+    set_walkthrough_deleted_at(db, walkthrough_id, Some(Utc::now().timestamp())).await?;
+    Ok(())
+}
+
+/// Brings back a soft-deleted walkthrough
+pub async fn restore_walkthrough(db: &DatabaseConnection, walkthrough_id: &str) -> Result<WalkthroughDto, DbErr> {
+    let restored = set_walkthrough_deleted_at(db, walkthrough_id, None).await?;
+    let mut dtos = walkthroughs_to_dtos(db, vec![restored]).await?;
+    Ok(dtos.remove(0))
+}
+
+async fn set_walkthrough_deleted_at(
+    db: &DatabaseConnection,
+    walkthrough_id: &str,
+    deleted_at: Option<i64>,
+) -> Result<walkthrough::Model, DbErr> {
+    let walkthrough_model = walkthrough::Entity::find_by_id(walkthrough_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Walkthrough not found: {}", walkthrough_id)))?;
+    if walkthrough_model.deleted_at.is_some() == deleted_at.is_some() {
+        return Ok(walkthrough_model);
+    }
+
+    let mut walkthrough_active: walkthrough::ActiveModel = walkthrough_model.into();
+    walkthrough_active.deleted_at = Set(deleted_at);
+    walkthrough_active.updated_at = Set(Utc::now().timestamp());
+    walkthrough_active.update(db).await
+}
+
+/// Permanently deletes walkthroughs soft-deleted before `before` (Unix
+/// seconds), files included. Returns how many were removed.
+pub async fn purge_deleted_walkthroughs(db: &DatabaseConnection, before: i64) -> Result<usize, DbErr> {
+    let expired = walkthrough::Entity::find()
+        .filter(walkthrough::Column::DeletedAt.lt(before))
+        .all(db)
+        .await?;
+    for walkthrough_model in &expired {
+        purge_walkthrough(db, walkthrough_model.id.clone()).await?;
+    }
+    Ok(expired.len())
+}
+
+/// Permanently delete a walkthrough (removes file and database records)
+pub async fn purge_walkthrough(
     db: &DatabaseConnection,
     walkthrough_id: String,
 ) -> Result<(), DbErr> {
@@ -594,6 +853,17 @@ pub async fn delete_walkthrough(
         .await?
         .ok_or_else(|| DbErr::RecordNotFound(format!("Walkthrough not found: {}", walkthrough_id)))?;
 
+    // Note attachments go with their notes
+    let notes = walkthrough_note::Entity::find()
+        .filter(walkthrough_note::Column::WalkthroughId.eq(&walkthrough_id))
+        .all(db)
+        .await?;
+    for note in &notes {
+        if let Err(e) = crate::core::assets::remove_note_attachments(Path::new(&walkthrough_model.file_path), &note.id) {
+            tracing::warn!("Failed to delete attachments of note {}: {}", note.id, e);
+        }
+    }
+
     // Delete file if exists
     if std::path::Path::new(&walkthrough_model.file_path).exists() {
         fs::remove_file(&walkthrough_model.file_path)
@@ -631,7 +901,7 @@ pub async fn add_takeaway(
 
     let takeaway_active = walkthrough_takeaway::ActiveModel {
         id: Set(takeaway_id),
-        walkthrough_id: Set(walkthrough_id),
+        walkthrough_id: Set(walkthrough_id.clone()),
         title: Set(title),
         description: Set(description),
         sort_order: Set(max_order),
@@ -641,17 +911,9 @@ pub async fn add_takeaway(
     };
 
     let takeaway_model = takeaway_active.insert(db).await?;
+    mirror_progress(db, &walkthrough_id).await;
 
-    Ok(TakeawayDto {
-        id: takeaway_model.id,
-        walkthrough_id: takeaway_model.walkthrough_id,
-        title: takeaway_model.title,
-        description: takeaway_model.description,
-        sort_order: takeaway_model.sort_order,
-        completed: takeaway_model.completed != 0,
-        completed_at: takeaway_model.completed_at,
-        created_at: takeaway_model.created_at,
-    })
+    Ok(TakeawayDto::new(takeaway_model, None))
 }
 
 /// Toggle takeaway completion
@@ -674,17 +936,16 @@ pub async fn toggle_takeaway_complete(
     takeaway_active.completed_at = Set(new_completed_at);
 
     let updated = takeaway_active.update(db).await?;
+    mirror_progress(db, &updated.walkthrough_id).await;
 
-    Ok(TakeawayDto {
-        id: updated.id,
-        walkthrough_id: updated.walkthrough_id,
-        title: updated.title,
-        description: updated.description,
-        sort_order: updated.sort_order,
-        completed: updated.completed != 0,
-        completed_at: updated.completed_at,
-        created_at: updated.created_at,
-    })
+    let task_id = takeaway_task_id(db, &updated.id).await?;
+    if let Some(task_id) = &task_id {
+        if let Err(e) = sync_task_from_takeaway(db, task_id, new_completed == 1).await {
+            tracing::warn!("Failed to sync task {} with takeaway {}: {}", task_id, updated.id, e);
+        }
+    }
+
+    Ok(TakeawayDto::new(updated, task_id))
 }
 
 /// Update a takeaway
@@ -710,17 +971,9 @@ pub async fn update_takeaway(
     }
 
     let updated = takeaway_active.update(db).await?;
+    let task_id = takeaway_task_id(db, &updated.id).await?;
 
-    Ok(TakeawayDto {
-        id: updated.id,
-        walkthrough_id: updated.walkthrough_id,
-        title: updated.title,
-        description: updated.description,
-        sort_order: updated.sort_order,
-        completed: updated.completed != 0,
-        completed_at: updated.completed_at,
-        created_at: updated.created_at,
-    })
+    Ok(TakeawayDto::new(updated, task_id))
 }
 
 /// Delete a takeaway
@@ -728,7 +981,11 @@ pub async fn delete_takeaway(
     db: &DatabaseConnection,
     takeaway_id: String,
 ) -> Result<(), DbErr> {
+    let Some(takeaway_model) = walkthrough_takeaway::Entity::find_by_id(&takeaway_id).one(db).await? else {
+        return Ok(());
+    };
     walkthrough_takeaway::Entity::delete_by_id(takeaway_id).exec(db).await?;
+    mirror_progress(db, &takeaway_model.walkthrough_id).await;
     Ok(())
 }
 
@@ -757,6 +1014,126 @@ pub async fn reorder_takeaways(
     Ok(())
 }
 
+/// Task status a linked task goes back to when its takeaway is unchecked
+const REOPENED_TASK_STATUS: &str = "backlog";
+
+/// Create a task from a takeaway, with its title and description, in the
+/// walkthrough's project, and link the two. From then on, checking or
+/// unchecking either one completes or reopens the other.
+///
+/// A takeaway gets one task: converting it again returns the linked task,
+/// unless that task was deleted, in which case a new one replaces it.
+pub async fn convert_takeaway_to_task(
+    db: &DatabaseConnection,
+    takeaway_id: String,
+    priority: Option<String>,
+) -> Result<crate::db::task_operations::TaskDto, DbErr> {
+    let takeaway_model = walkthrough_takeaway::Entity::find_by_id(&takeaway_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Takeaway not found: {}", takeaway_id)))?;
+
+    // `get_task` leaves out deleted tasks
+    if let Some(task_id) = takeaway_task_id(db, &takeaway_id).await? {
+        if let Some(existing) = crate::db::task_operations::get_task(db, &task_id).await? {
+            return Ok(existing);
+        }
+    }
+
+    let walkthrough_model = walkthrough::Entity::find_by_id(&takeaway_model.walkthrough_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Walkthrough not found: {}", takeaway_model.walkthrough_id)))?;
+
+    let created = crate::db::task_operations::create_task(
+        db,
+        takeaway_model.title.clone(),
+        takeaway_model.description.clone(),
+        priority.unwrap_or_else(|| "standard".to_string()),
+        Vec::new(),
+        vec![walkthrough_model.project_id],
+        Some(if takeaway_model.completed != 0 { "completed" } else { "backlog" }.to_string()),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    let link = walkthrough_takeaway_task::ActiveModel {
+        takeaway_id: Set(takeaway_id.clone()),
+        task_id: Set(created.id.clone()),
+        created_at: Set(Utc::now().timestamp()),
+    };
+    let linked = async {
+        walkthrough_takeaway_task::Entity::delete_by_id(takeaway_id.clone()).exec(db).await?;
+        link.insert(db).await
+    }
+    .await;
+    if let Err(e) = linked {
+        // Don't leave a task behind that the takeaway doesn't know about
+        crate::db::task_operations::purge_task(db, &created.id).await?;
+        return Err(e);
+    }
+
+    Ok(created)
+}
+
+// Completes or reopens a takeaway's task to match the takeaway. A task that
+// is already in a matching state, or deleted, is left alone.
+async fn sync_task_from_takeaway(db: &DatabaseConnection, task_id: &str, completed: bool) -> Result<(), DbErr> {
+    let Some(task_model) = task::Entity::find_by_id(task_id).one(db).await? else {
+        return Ok(());
+    };
+    if task_model.deleted_at.is_some() || (task_model.status == "completed") == completed {
+        return Ok(());
+    }
+
+    let status = if completed { "completed" } else { REOPENED_TASK_STATUS };
+    crate::db::task_operations::update_task(
+        db,
+        task_id.to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(status.to_string()),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Checks or unchecks the takeaway linked to a task, after the task's
+/// status moved into or out of "completed"
+pub async fn sync_takeaway_from_task<C: ConnectionTrait>(db: &C, task_id: &str, completed: bool) -> Result<(), DbErr> {
+    let links = walkthrough_takeaway_task::Entity::find()
+        .filter(walkthrough_takeaway_task::Column::TaskId.eq(task_id))
+        .all(db)
+        .await?;
+
+    for link in links {
+        let Some(takeaway_model) = walkthrough_takeaway::Entity::find_by_id(&link.takeaway_id).one(db).await? else {
+            continue;
+        };
+        if (takeaway_model.completed != 0) == completed {
+            continue;
+        }
+
+        let walkthrough_id = takeaway_model.walkthrough_id.clone();
+        let mut takeaway_active: walkthrough_takeaway::ActiveModel = takeaway_model.into();
+        takeaway_active.completed = Set(i32::from(completed));
+        takeaway_active.completed_at = Set(completed.then(|| Utc::now().timestamp()));
+        takeaway_active.update(db).await?;
+        mirror_progress(db, &walkthrough_id).await;
+    }
+    Ok(())
+}
+
 // ============================================================================
 // NOTE OPERATIONS
 // ============================================================================
@@ -769,67 +1146,560 @@ pub async fn get_walkthrough_notes(
     get_walkthrough_notes_internal(db, &walkthrough_id).await
 }
 
-/// Add a note to a walkthrough
+/// Add a note to a walkthrough. `format` defaults to "text".
 pub async fn add_walkthrough_note(
     db: &DatabaseConnection,
     walkthrough_id: String,
     content: String,
+    format: Option<String>,
 ) -> Result<WalkthroughNoteDto, DbErr> {
     let now = Utc::now().timestamp();
     let note_id = Uuid::new_v4().to_string();
+    let format = format.unwrap_or_else(|| "text".to_string());
+    validate_note_format(&format)?;
+    let walkthrough_file = walkthrough_file_path(db, &walkthrough_id).await?;
 
     let note_active = walkthrough_note::ActiveModel {
         id: Set(note_id),
         walkthrough_id: Set(walkthrough_id),
         content: Set(content),
+        format: Set(format),
         created_at: Set(now),
         updated_at: Set(now),
     };
 
     let note_model = note_active.insert(db).await?;
 
-    Ok(WalkthroughNoteDto {
-        id: note_model.id,
-        walkthrough_id: note_model.walkthrough_id,
-        content: note_model.content,
-        created_at: note_model.created_at,
-        updated_at: note_model.updated_at,
-    })
+    Ok(WalkthroughNoteDto::new(note_model, &walkthrough_file))
 }
 
-/// Update a walkthrough note
+/// Update a walkthrough note's content and, if given, its format
 pub async fn update_walkthrough_note(
     db: &DatabaseConnection,
     note_id: String,
     content: String,
+    format: Option<String>,
 ) -> Result<WalkthroughNoteDto, DbErr> {
     let now = Utc::now().timestamp();
 
-    let note_model = walkthrough_note::Entity::find_by_id(&note_id)
-        .one(db)
-        .await?
-        .ok_or_else(|| DbErr::RecordNotFound(format!("Note not found: {}", note_id)))?;
+    let note_model = find_note(db, &note_id).await?;
+    let walkthrough_file = walkthrough_file_path(db, &note_model.walkthrough_id).await?;
 
     let mut note_active: walkthrough_note::ActiveModel = note_model.into();
     note_active.content = Set(content);
+    if let Some(format) = format {
+        validate_note_format(&format)?;
+        note_active.format = Set(format);
+    }
     note_active.updated_at = Set(now);
 
     let updated = note_active.update(db).await?;
 
-    Ok(WalkthroughNoteDto {
-        id: updated.id,
-        walkthrough_id: updated.walkthrough_id,
-        content: updated.content,
-        created_at: updated.created_at,
-        updated_at: updated.updated_at,
-    })
+    Ok(WalkthroughNoteDto::new(updated, &walkthrough_file))
 }
 
-/// Delete a walkthrough note
+/// Delete a walkthrough note and its attachments
 pub async fn delete_walkthrough_note(
     db: &DatabaseConnection,
     note_id: String,
 ) -> Result<(), DbErr> {
+    let Some(note_model) = walkthrough_note::Entity::find_by_id(&note_id).one(db).await? else {
+        return Ok(());
+    };
+    let walkthrough_file = walkthrough_file_path(db, &note_model.walkthrough_id).await?;
     walkthrough_note::Entity::delete_by_id(note_id).exec(db).await?;
+
+    if let Err(e) = crate::core::assets::remove_note_attachments(&walkthrough_file, &note_model.id) {
+        tracing::warn!("Failed to delete attachments of note {}: {}", note_model.id, e);
+    }
+    Ok(())
+}
+
+/// Copy a file into a note's attachment folder (under the walkthrough's
+/// asset folder). Insert the returned `markdown` into a markdown note to
+/// show it inline.
+pub async fn attach_walkthrough_note_file(
+    db: &DatabaseConnection,
+    note_id: String,
+    source_path: &Path,
+) -> Result<crate::core::assets::NoteAttachment, DbErr> {
+    let note_model = find_note(db, &note_id).await?;
+    let walkthrough_file = walkthrough_file_path(db, &note_model.walkthrough_id).await?;
+    let attachment = crate::core::assets::attach_to_note(&walkthrough_file, &note_model.id, source_path)
+        .map_err(DbErr::Custom)?;
+
+    let mut note_active: walkthrough_note::ActiveModel = note_model.into();
+    note_active.updated_at = Set(Utc::now().timestamp());
+    note_active.update(db).await?;
+
+    Ok(attachment)
+}
+
+/// Delete one of a note's attachments by file name. Links to it in the
+/// note's content are left for the user to remove.
+pub async fn delete_walkthrough_note_attachment(
+    db: &DatabaseConnection,
+    note_id: String,
+    name: &str,
+) -> Result<(), DbErr> {
+    let note_model = find_note(db, &note_id).await?;
+    let walkthrough_file = walkthrough_file_path(db, &note_model.walkthrough_id).await?;
+    crate::core::assets::delete_note_attachment(&walkthrough_file, &note_model.id, name).map_err(DbErr::Custom)?;
+
+    let mut note_active: walkthrough_note::ActiveModel = note_model.into();
+    note_active.updated_at = Set(Utc::now().timestamp());
+    note_active.update(db).await?;
+    Ok(())
+}
+
+// ============================================================================
+// STEP OPERATIONS
+// ============================================================================
+
+/// Get walkthrough steps in order
+pub async fn get_walkthrough_steps(
+    db: &DatabaseConnection,
+    walkthrough_id: String,
+) -> Result<Vec<WalkthroughStepDto>, DbErr> {
+    let steps = walkthrough_step::Entity::find()
+        .filter(walkthrough_step::Column::WalkthroughId.eq(&walkthrough_id))
+        .order_by_asc(walkthrough_step::Column::SortOrder)
+        .all(db)
+        .await?;
+
+    Ok(steps.into_iter().map(WalkthroughStepDto::from).collect())
+}
+
+// Root of the project a walkthrough belongs to
+async fn walkthrough_project_root(db: &DatabaseConnection, walkthrough_id: &str) -> Result<PathBuf, DbErr> {
+    let walkthrough_model = walkthrough::Entity::find_by_id(walkthrough_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Walkthrough not found: {}", walkthrough_id)))?;
+    let project_model = project::Entity::find_by_id(&walkthrough_model.project_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Project not found: {}", walkthrough_model.project_id)))?;
+    Ok(crate::core::paths::from_ipc(&project_model.path))
+}
+
+// `file_path` relative to `root`, `/`-separated. Absolute paths must be
+// inside the project.
+fn step_file_path(root: &Path, file_path: &str) -> Result<String, DbErr> {
+    let path = crate::core::paths::from_ipc(file_path);
+    let relative = if path.is_absolute() {
+        path.strip_prefix(root)
+            .map_err(|_| DbErr::Custom(format!("File is outside the project: {}", file_path)))?
+            .to_path_buf()
+    } else {
+        path
+    };
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    if relative.is_empty() || relative.split('/').any(|part| part == "..") {
+        return Err(DbErr::Custom(format!("Invalid step file path: {}", file_path)));
+    }
+    Ok(relative)
+}
+
+fn validate_line_range(start_line: i32, end_line: i32) -> Result<(), DbErr> {
+    if start_line < 1 || end_line < start_line {
+        return Err(DbErr::Custom(format!("Invalid line range: {}-{}", start_line, end_line)));
+    }
+    Ok(())
+}
+
+// HEAD of the project's repository, if it is one
+fn head_commit(root: &Path) -> Option<String> {
+    crate::integrations::git::resolve_commit(&root.to_string_lossy(), "HEAD").ok()
+}
+
+/// Add a step anchored to lines `start_line`..=`end_line` of `file_path`
+/// (absolute, or relative to the project root). The range is taken to refer
+/// to the project's current commit, which is recorded for re-anchoring.
+pub async fn add_walkthrough_step(
+    db: &DatabaseConnection,
+    walkthrough_id: String,
+    title: String,
+    description: Option<String>,
+    file_path: String,
+    start_line: i32,
+    end_line: i32,
+) -> Result<WalkthroughStepDto, DbErr> {
+    validate_line_range(start_line, end_line)?;
+    let root = walkthrough_project_root(db, &walkthrough_id).await?;
+    let file_path = step_file_path(&root, &file_path)?;
+    let now = Utc::now().timestamp();
+
+    let sort_order = walkthrough_step::Entity::find()
+        .filter(walkthrough_step::Column::WalkthroughId.eq(&walkthrough_id))
+        .order_by_desc(walkthrough_step::Column::SortOrder)
+        .one(db)
+        .await?
+        .map(|s| s.sort_order + 1)
+        .unwrap_or(0);
+
+    let step = walkthrough_step::ActiveModel {
+        id: Set(Uuid::new_v4().to_string()),
+        walkthrough_id: Set(walkthrough_id),
+        title: Set(title),
+        description: Set(description),
+        file_path: Set(file_path),
+        start_line: Set(start_line),
+        end_line: Set(end_line),
+        commit_sha: Set(head_commit(&root)),
+        sort_order: Set(sort_order),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(step.into())
+}
+
+/// Update a step. Moving it to another file or line range anchors it to the
+/// project's current commit again.
+pub async fn update_walkthrough_step(
+    db: &DatabaseConnection,
+    step_id: String,
+    title: Option<String>,
+    description: Option<Option<String>>,
+    file_path: Option<String>,
+    lines: Option<(i32, i32)>,
+) -> Result<WalkthroughStepDto, DbErr> {
+    let step_model = walkthrough_step::Entity::find_by_id(&step_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("Step not found: {}", step_id)))?;
+    let walkthrough_id = step_model.walkthrough_id.clone();
+
+    let mut step_active: walkthrough_step::ActiveModel = step_model.into();
+    if let Some(t) = title {
+        step_active.title = Set(t);
+    }
+    if let Some(d) = description {
+        step_active.description = Set(d);
+    }
+    if file_path.is_some() || lines.is_some() {
+        let root = walkthrough_project_root(db, &walkthrough_id).await?;
+        if let Some(file_path) = file_path {
+            step_active.file_path = Set(step_file_path(&root, &file_path)?);
+        }
+        if let Some((start_line, end_line)) = lines {
+            validate_line_range(start_line, end_line)?;
+            step_active.start_line = Set(start_line);
+            step_active.end_line = Set(end_line);
+        }
+        step_active.commit_sha = Set(head_commit(&root));
+    }
+    step_active.updated_at = Set(Utc::now().timestamp());
+
+    Ok(step_active.update(db).await?.into())
+}
+
+/// Delete a step
+pub async fn delete_walkthrough_step(
+    db: &DatabaseConnection,
+    step_id: String,
+) -> Result<(), DbErr> {
+    walkthrough_step::Entity::delete_by_id(step_id).exec(db).await?;
     Ok(())
 }
+
+/// Reorder steps
+pub async fn reorder_walkthrough_steps(
+    db: &DatabaseConnection,
+    walkthrough_id: String,
+    step_ids_in_order: Vec<String>,
+) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    for (index, step_id) in step_ids_in_order.iter().enumerate() {
+        let step_model = walkthrough_step::Entity::find_by_id(step_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Step not found: {}", step_id)))?;
+
+        // Verify step belongs to this walkthrough
+        if step_model.walkthrough_id != walkthrough_id {
+            return Err(DbErr::Custom("Step does not belong to this walkthrough".to_string()));
+        }
+
+        let mut step_active: walkthrough_step::ActiveModel = step_model.into();
+        step_active.sort_order = Set(index as i32);
+        step_active.update(&txn).await?;
+    }
+    txn.commit().await
+}
+
+/// Follow each step's lines from its commit to the project's HEAD with git
+/// blame, and move the step to where they are now.
+///
+/// A line is identified by the commit that introduced it and its place in
+/// that commit, so it is found however far it moved within its file; a
+/// renamed file is followed too. Uncommitted edits aren't seen. A step whose
+/// lines are all gone is left unchanged and reported as "lost".
+pub async fn reanchor_walkthrough_steps(
+    db: &DatabaseConnection,
+    walkthrough_id: String,
+) -> Result<Vec<StepReanchorDto>, DbErr> {
+    let root = walkthrough_project_root(db, &walkthrough_id).await?;
+    let repo = root.to_string_lossy().to_string();
+    let head = crate::integrations::git::resolve_commit(&repo, "HEAD").map_err(DbErr::Custom)?;
+
+    let steps = walkthrough_step::Entity::find()
+        .filter(walkthrough_step::Column::WalkthroughId.eq(&walkthrough_id))
+        .order_by_asc(walkthrough_step::Column::SortOrder)
+        .all(db)
+        .await?;
+
+    // Current blame of each file, keyed by line origin
+    let mut current: std::collections::HashMap<String, Option<BlameIndex>> = std::collections::HashMap::new();
+    let now = Utc::now().timestamp();
+    let mut results = Vec::with_capacity(steps.len());
+
+    for step in steps {
+        let Some(commit_sha) = step.commit_sha.clone() else {
+            results.push(StepReanchorDto { step: step.into(), status: "unanchored".to_string() });
+            continue;
+        };
+        let anchor = reanchor_step(&repo, &head, &commit_sha, &step, &mut current);
+        let Some((file_path, start_line, end_line, complete)) = anchor else {
+            results.push(StepReanchorDto { step: step.into(), status: "lost".to_string() });
+            continue;
+        };
+
+        let moved = file_path != step.file_path || start_line != step.start_line || end_line != step.end_line;
+        let status = if !complete {
+            "changed"
+        } else if moved {
+            "moved"
+        } else {
+            "unchanged"
+        };
+        if !moved && commit_sha == head {
+            results.push(StepReanchorDto { step: step.into(), status: status.to_string() });
+            continue;
+        }
+
+        let mut step_active: walkthrough_step::ActiveModel = step.into();
+        step_active.file_path = Set(file_path);
+        step_active.start_line = Set(start_line);
+        step_active.end_line = Set(end_line);
+        step_active.commit_sha = Set(Some(head.clone()));
+        step_active.updated_at = Set(now);
+        results.push(StepReanchorDto {
+            step: step_active.update(db).await?.into(),
+            status: status.to_string(),
+        });
+    }
+
+    Ok(results)
+}
+
+// Lines of a file by origin: (commit, original line, original path)
+type BlameIndex = std::collections::HashMap<(String, usize, String), usize>;
+
+// The step's new (file, start, end, every line found), or none if none of
+// its lines are left at `head`
+fn reanchor_step(
+    repo: &str,
+    head: &str,
+    commit_sha: &str,
+    step: &walkthrough_step::Model,
+    current: &mut std::collections::HashMap<String, Option<BlameIndex>>,
+) -> Option<(String, i32, i32, bool)> {
+    let range = (step.start_line as usize, step.end_line as usize);
+    let origins = crate::integrations::git::blame_lines(repo, commit_sha, &step.file_path, Some(range)).ok()?;
+    if origins.is_empty() {
+        return None;
+    }
+
+    // The file may have been renamed since
+    let file_path = if commit_sha == head || Path::new(repo).join(&step.file_path).exists() {
+        step.file_path.clone()
+    } else {
+        crate::integrations::git::diff_commits(repo, commit_sha, head)
+            .ok()?
+            .files
+            .into_iter()
+            .find(|f| f.status == "renamed" && f.old_path.as_deref() == Some(step.file_path.as_str()))?
+            .path
+    };
+
+    let index = current
+        .entry(file_path.clone())
+        .or_insert_with(|| {
+            crate::integrations::git::blame_lines(repo, head, &file_path, None).ok().map(|lines| {
+                lines
+                    .into_iter()
+                    .map(|l| ((l.commit, l.original_line, l.original_path), l.line))
+                    .collect()
+            })
+        })
+        .as_ref()?;
+
+    let found: Vec<usize> = origins
+        .iter()
+        .filter_map(|o| index.get(&(o.commit.clone(), o.original_line, o.original_path.clone())).copied())
+        .collect();
+    let start = *found.iter().min()?;
+    let end = *found.iter().max()?;
+    Some((file_path, start as i32, end as i32, found.len() == origins.len()))
+}
+
+// ============================================================================
+// HTML EXPORT
+// ============================================================================
+
+/// Walkthrough exported as a standalone HTML guide
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkthroughHtmlExportDto {
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    /// Diagrams included as source because no SVG was given for them
+    #[serde(rename = "unrenderedDiagrams")]
+    pub unrendered_diagrams: usize,
+}
+
+/// The mermaid sources in a walkthrough (its markdown and notes), for the
+/// frontend to render before `export_walkthrough_html`
+pub async fn get_walkthrough_diagrams(
+    db: &DatabaseConnection,
+    walkthrough_id: String,
+) -> Result<Vec<String>, DbErr> {
+    let details = get_walkthrough_details(db, walkthrough_id).await?;
+    let (markdown, base_dir) = walkthrough_markdown(&details)?;
+
+    let mut sources = crate::core::html_export::diagram_sources(&markdown, &base_dir);
+    for note in details.notes.iter().filter(|note| note.format == "markdown") {
+        for source in crate::core::html_export::diagram_sources(&note.content, &base_dir) {
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+    }
+    Ok(sources)
+}
+
+// The walkthrough file without front matter, and its directory
+fn walkthrough_markdown(details: &WalkthroughDetailsDto) -> Result<(String, PathBuf), DbErr> {
+    let content = fs::read_to_string(&details.file_path)
+        .map_err(|e| DbErr::Custom(format!("Failed to read walkthrough file: {}", e)))?;
+    let base_dir = Path::new(&details.file_path).parent().map(Path::to_path_buf).unwrap_or_default();
+    Ok((crate::commands::strip_front_matter(&content).to_string(), base_dir))
+}
+
+/// Render a walkthrough as one self-contained HTML file next to its
+/// markdown (same name, `.html`): the walkthrough text, its progress,
+/// steps, takeaways and notes, with inline CSS and images.
+///
+/// `diagrams` maps mermaid sources (from `get_walkthrough_diagrams`) to
+/// rendered SVGs; any diagram left out is included as source. Exporting
+/// again overwrites the file.
+pub async fn export_walkthrough_html(
+    db: &DatabaseConnection,
+    walkthrough_id: String,
+    diagrams: std::collections::HashMap<String, String>,
+) -> Result<WalkthroughHtmlExportDto, DbErr> {
+    use crate::core::html_export::{escape, page, render_markdown};
+
+    let details = get_walkthrough_details(db, walkthrough_id).await?;
+    let (markdown, base_dir) = walkthrough_markdown(&details)?;
+    let diagrams: std::collections::HashMap<String, String> =
+        diagrams.into_iter().map(|(source, svg)| (source.trim().to_string(), svg)).collect();
+    let date = |timestamp: i64| {
+        chrono::DateTime::from_timestamp(timestamp, 0)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    };
+
+    let mut body = String::new();
+    let mut unrendered_diagrams = 0;
+
+    // The walkthrough file usually starts with its own title
+    if !markdown.trim_start().starts_with("# ") {
+        body.push_str(&format!("<h1>{}</h1>\n", escape(&details.name)));
+    }
+    let completed = details.takeaways.iter().filter(|t| t.completed).count();
+    body.push_str(&format!(
+        "<p class=\"meta\">Status: {} &middot; {} of {} takeaways done &middot; Exported {}</p>\n\
+         <div class=\"progress\"><div style=\"width: {:.0}%\"></div></div>\n",
+        escape(&details.status.replace('_', " ")),
+        completed,
+        details.takeaways.len(),
+        date(Utc::now().timestamp()),
+        details.progress
+    ));
+    if let Some(description) = details.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        body.push_str(&format!("<p>{}</p>\n", escape(description)));
+    }
+
+    let rendered = render_markdown(&markdown, &base_dir, &diagrams);
+    unrendered_diagrams += rendered.unrendered_diagrams;
+    body.push_str(&rendered.html);
+
+    if !details.steps.is_empty() {
+        body.push_str("<h2>Steps</h2>\n<ol class=\"steps\">\n");
+        for step in &details.steps {
+            let location = if step.start_line == step.end_line {
+                format!("{}:{}", step.file_path, step.start_line)
+            } else {
+                format!("{}:{}-{}", step.file_path, step.start_line, step.end_line)
+            };
+            body.push_str(&format!(
+                "<li><strong>{}</strong> <span class=\"step-location\"><code>{}</code></span>",
+                escape(&step.title),
+                escape(&location)
+            ));
+            if let Some(description) = &step.description {
+                body.push_str(&format!("<br>{}", escape(description)));
+            }
+            body.push_str("</li>\n");
+        }
+        body.push_str("</ol>\n");
+    }
+
+    if !details.takeaways.is_empty() {
+        body.push_str("<h2>Takeaways</h2>\n<ul class=\"checklist\">\n");
+        for takeaway in &details.takeaways {
+            body.push_str(&format!(
+                "<li{}><input type=\"checkbox\" disabled{}> {}",
+                if takeaway.completed { " class=\"done\"" } else { "" },
+                if takeaway.completed { " checked" } else { "" },
+                escape(&takeaway.title)
+            ));
+            if let Some(description) = &takeaway.description {
+                body.push_str(&format!("<br><span class=\"meta\">{}</span>", escape(description)));
+            }
+            body.push_str("</li>\n");
+        }
+        body.push_str("</ul>\n");
+    }
+
+    if !details.notes.is_empty() {
+        body.push_str("<h2>Notes</h2>\n");
+        for note in &details.notes {
+            let content = if note.format == "markdown" {
+                let rendered = render_markdown(&note.content, &base_dir, &diagrams);
+                unrendered_diagrams += rendered.unrendered_diagrams;
+                rendered.html
+            } else {
+                format!("<p class=\"plain\">{}</p>\n", escape(&note.content))
+            };
+            body.push_str(&format!(
+                "<section class=\"note\">\n<p class=\"meta\">{}</p>\n{}</section>\n",
+                date(note.created_at),
+                content
+            ));
+        }
+    }
+
+    let file_path = Path::new(&details.file_path).with_extension("html");
+    fs::write(&file_path, page(&details.name, &body))
+        .map_err(|e| DbErr::Custom(format!("Failed to write walkthrough export: {}", e)))?;
+
+    Ok(WalkthroughHtmlExportDto {
+        file_path: file_path.to_string_lossy().to_string(),
+        unrendered_diagrams,
+    })
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+
+/// Marker line identifying hooks written by BlueKit
+const HOOK_MARKER: &str = "# Installed by BlueKit";
+
+/// Result of installing the pre-commit hook
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHookInstallResult {
+    #[serde(rename = "hookPath")]
+    pub hook_path: String,
+    /// An existing non-BlueKit hook was moved here and is still run first
+    #[serde(rename = "previousHookPath")]
+    pub previous_hook_path: Option<String>,
+}
+
+/// Installs a pre-commit hook that validates staged `.bluekit` artifacts.
+///
+/// The hook runs this executable in headless `validate --staged` mode.
+/// An existing hook that BlueKit didn't write is kept as `pre-commit.local`
+/// and chained before validation.
+pub fn install_pre_commit_hook(project_path: &Path) -> Result<GitHookInstallResult, String> {
+    let hooks_dir = hooks_dir(project_path)?;
+    fs::create_dir_all(&hooks_dir)
+        .map_err(|e| format!("Failed to create hooks directory: {}", e))?;
+
+    let hook_path = hooks_dir.join("pre-commit");
+    let local_hook_path = hooks_dir.join("pre-commit.local");
+
+    let mut previous_hook_path = None;
+    if let Ok(existing) = fs::read_to_string(&hook_path) {
+        if !existing.contains(HOOK_MARKER) {
+            if local_hook_path.exists() {
+                return Err(format!(
+                    "Both {} and {} exist; merge them manually before installing",
+                    hook_path.display(),
+                    local_hook_path.display()
+                ));
+            }
+            fs::rename(&hook_path, &local_hook_path)
+                .map_err(|e| format!("Failed to move existing hook: {}", e))?;
+            previous_hook_path = Some(local_hook_path.to_string_lossy().to_string());
+        }
+    }
+
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate BlueKit executable: {}", e))?;
+
+    fs::write(&hook_path, hook_script(&exe))
+        .map_err(|e| format!("Failed to write hook: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to make hook executable: {}", e))?;
+    }
+
+    Ok(GitHookInstallResult {
+        hook_path: hook_path.to_string_lossy().to_string(),
+        previous_hook_path,
+    })
+}
+
+// Respects core.hooksPath and linked worktrees
+fn hooks_dir(project_path: &Path) -> Result<PathBuf, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(project_path)
+        .args(["rev-parse", "--git-path", "hooks"])
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Not a git repository: {}", project_path.display()));
+    }
+
+    let hooks = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(if hooks.is_absolute() { hooks } else { project_path.join(hooks) })
+}
+
+fn hook_script(exe: &Path) -> String {
+    // Single-quote for sh; git runs hooks through sh on every platform
+    let exe = exe.to_string_lossy().replace('\\', "/").replace('\'', "'\\''");
+
+    format!(
+        r#"#!/bin/sh
+{marker}
+# Validates staged .bluekit artifacts before each commit.
+
+HOOK_DIR=$(dirname "$0")
+if [ -x "$HOOK_DIR/pre-commit.local" ]; then
+    "$HOOK_DIR/pre-commit.local" "$@" || exit $?
+fi
+
+BLUEKIT='{exe}'
+if [ ! -x "$BLUEKIT" ]; then
+    echo "BlueKit not found at $BLUEKIT; skipping artifact validation" >&2
+    exit 0
+fi
+
+exec "$BLUEKIT" validate --staged
+"#,
+        marker = HOOK_MARKER,
+        exe = exe
+    )
+}
@@ -2,8 +2,10 @@
 //!
 //! This module provides git operations using git CLI commands.
 
+pub mod hooks;
 pub mod operations;
-pub use operations::{GitMetadata, detect_git_metadata, GitWorktree, list_git_worktrees};
+pub use hooks::{GitHookInstallResult, install_pre_commit_hook};
+pub use operations::{GitMetadata, detect_git_metadata, GitWorktree, list_git_worktrees, clone_at_commit, add_worktree_at_commit, GitDiffSummary, GitFileChange, diff_commits, resolve_commit, BlameLine, blame_lines};
 
 
 
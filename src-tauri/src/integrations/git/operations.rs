@@ -195,3 +195,369 @@ pub fn list_git_worktrees(project_path: &str) -> Result<Vec<GitWorktree>, String
     
     Ok(worktrees)
 }
+
+/// Clones `source` (a remote URL or local repository path) into `target`
+/// and checks out `commit_sha`, on a new `branch` if given or detached
+/// otherwise. The commit is fetched explicitly if the clone doesn't include
+/// it. `target` is removed again if anything fails.
+pub fn clone_at_commit(source: &str, target: &Path, commit_sha: &str, branch: Option<&str>) -> Result<(), String> {
+    let result = clone_and_checkout(source, target, commit_sha, branch);
+    if result.is_err() && target.exists() {
+        let _ = std::fs::remove_dir_all(target);
+    }
+    result
+}
+
+fn clone_and_checkout(source: &str, target: &Path, commit_sha: &str, branch: Option<&str>) -> Result<(), String> {
+    run_git(Command::new("git").arg("clone").arg("--quiet").arg(source).arg(target), "clone")?;
+
+    let has_commit = Command::new("git")
+        .arg("-C")
+        .arg(target)
+        .args(["cat-file", "-e", &format!("{}^{{commit}}", commit_sha)])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+    if !has_commit {
+        run_git(Command::new("git").arg("-C").arg(target).args(["fetch", "--quiet", "origin", commit_sha]), "fetch")?;
+    }
+
+    let mut checkout = Command::new("git");
+    checkout.arg("-C").arg(target).arg("checkout").arg("--quiet");
+    match branch {
+        Some(branch) => checkout.args(["-b", branch, commit_sha]),
+        None => checkout.args(["--detach", commit_sha]),
+    };
+    run_git(&mut checkout, "checkout")?;
+    Ok(())
+}
+
+/// Adds a worktree of the repository at `repo_path` in `target`, checked
+/// out at `commit_sha` on a new `branch` if given or detached otherwise.
+pub fn add_worktree_at_commit(repo_path: &str, target: &Path, commit_sha: &str, branch: Option<&str>) -> Result<(), String> {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(repo_path).args(["worktree", "add", "--quiet"]);
+    match branch {
+        Some(branch) => command.args(["-b", branch]),
+        None => command.arg("--detach"),
+    };
+    command.arg(target).arg(commit_sha);
+    run_git(&mut command, "worktree add")?;
+    Ok(())
+}
+
+/// One file changed between two commits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitFileChange {
+    /// Path in the newer commit (the old path for deletions)
+    pub path: String,
+    /// Path in the older commit, for renames and copies
+    #[serde(rename = "oldPath", skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    /// "added", "modified", "deleted", "renamed", "copied" or "typeChanged"
+    pub status: String,
+    pub additions: u64,
+    pub deletions: u64,
+    /// Binary files have no line counts
+    pub binary: bool,
+}
+
+/// Changed files and line totals between two commits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitDiffSummary {
+    #[serde(rename = "fromSha")]
+    pub from_sha: String,
+    #[serde(rename = "toSha")]
+    pub to_sha: String,
+    pub files: Vec<GitFileChange>,
+    #[serde(rename = "filesChanged")]
+    pub files_changed: usize,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+/// Diffs two commits of the repository at `repo_path`, with rename
+/// detection. Either side can be any revision git understands (e.g. "HEAD");
+/// the summary reports both resolved to full SHAs.
+pub fn diff_commits(repo_path: &str, from: &str, to: &str) -> Result<GitDiffSummary, String> {
+    let from_sha = resolve_commit(repo_path, from)?;
+    let to_sha = resolve_commit(repo_path, to)?;
+
+    let diff = |format: &str| {
+        run_git(
+            Command::new("git")
+                .arg("-C")
+                .arg(repo_path)
+                .args(["diff", "-M", "-z", format, &from_sha, &to_sha]),
+            "diff",
+        )
+    };
+    let statuses = parse_name_status(&diff("--name-status")?);
+    let counts = parse_numstat(&diff("--numstat")?);
+
+    // Both listings come from the same diff, so they're in the same order
+    let files: Vec<GitFileChange> = statuses
+        .into_iter()
+        .zip(counts)
+        .map(|((status, path, old_path), counts)| GitFileChange {
+            path,
+            old_path,
+            status,
+            additions: counts.unwrap_or((0, 0)).0,
+            deletions: counts.unwrap_or((0, 0)).1,
+            binary: counts.is_none(),
+        })
+        .collect();
+
+    Ok(GitDiffSummary {
+        from_sha,
+        to_sha,
+        files_changed: files.len(),
+        additions: files.iter().map(|f| f.additions).sum(),
+        deletions: files.iter().map(|f| f.deletions).sum(),
+        files,
+    })
+}
+
+/// The full SHA of a revision (e.g. "HEAD" or a short SHA) of the
+/// repository at `repo_path`
+pub fn resolve_commit(repo_path: &str, rev: &str) -> Result<String, String> {
+    let sha = run_git(
+        Command::new("git")
+            .arg("-C")
+            .arg(repo_path)
+            .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", rev)]),
+        "rev-parse",
+    )
+    .map_err(|_| format!("Commit not found in repository: {}", rev))?;
+    Ok(sha.trim().to_string())
+}
+
+/// Where a line of a file came from, according to `git blame`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    /// 1-based line in the blamed revision
+    pub line: usize,
+    /// The commit that introduced the line
+    pub commit: String,
+    /// The line's number and file in that commit
+    pub original_line: usize,
+    pub original_path: String,
+}
+
+/// Blames `path` (relative to the repository root) as of `rev`, optionally
+/// only the 1-based inclusive `lines`. Lines moved within the file are
+/// followed (`-M`).
+///
+/// A line's (commit, original line, original path) stays the same however
+/// much the code around it moves, so it identifies the line across revisions.
+pub fn blame_lines(
+    repo_path: &str,
+    rev: &str,
+    path: &str,
+    lines: Option<(usize, usize)>,
+) -> Result<Vec<BlameLine>, String> {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(repo_path).args(["blame", "--porcelain", "-M"]);
+    if let Some((start, end)) = lines {
+        command.arg(format!("-L{},{}", start, end));
+    }
+    command.args([rev, "--", path]);
+    Ok(parse_blame_porcelain(&run_git(&mut command, "blame")?))
+}
+
+// `blame --porcelain` output. Each line starts with
+// "<sha> <original line> <final line>[ <group size>]"; a commit's `filename`
+// is only given the first time it appears (or when it changes)
+fn parse_blame_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut filenames: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    let mut pending: Option<(String, usize, usize)> = None;
+    let mut lines = Vec::new();
+
+    for record in output.lines() {
+        if record.starts_with('\t') {
+            // The line's content ends its entry
+            if let Some((commit, original_line, line)) = pending.take() {
+                lines.push(BlameLine {
+                    original_path: filenames.get(&commit).cloned().unwrap_or_default(),
+                    line,
+                    commit,
+                    original_line,
+                });
+            }
+        } else if let Some(filename) = record.strip_prefix("filename ") {
+            if let Some((commit, _, _)) = &pending {
+                filenames.insert(commit.clone(), filename.to_string());
+            }
+        } else {
+            let mut fields = record.split(' ');
+            let commit = fields.next().unwrap_or_default();
+            let is_header = matches!(commit.len(), 40 | 64) && commit.chars().all(|c| c.is_ascii_hexdigit());
+            let numbers = fields.next().and_then(|n| n.parse().ok()).zip(fields.next().and_then(|n| n.parse().ok()));
+            if let (true, Some((original_line, line))) = (is_header, numbers) {
+                pending = Some((commit.to_string(), original_line, line));
+            }
+        }
+    }
+    lines
+}
+
+// `--name-status -z` output as (status, path, old path)
+fn parse_name_status(output: &str) -> Vec<(String, String, Option<String>)> {
+    let mut fields = output.split('\0').filter(|f| !f.is_empty());
+    let mut entries = Vec::new();
+    while let Some(code) = fields.next() {
+        let status = match code.chars().next() {
+            Some('A') => "added",
+            Some('D') => "deleted",
+            Some('R') => "renamed",
+            Some('C') => "copied",
+            Some('T') => "typeChanged",
+            _ => "modified",
+        };
+        let first = fields.next().unwrap_or_default().to_string();
+        if matches!(status, "renamed" | "copied") {
+            let second = fields.next().unwrap_or_default().to_string();
+            entries.push((status.to_string(), second, Some(first)));
+        } else {
+            entries.push((status.to_string(), first, None));
+        }
+    }
+    entries
+}
+
+// `--numstat -z` output as (additions, deletions), none for binary files
+fn parse_numstat(output: &str) -> Vec<Option<(u64, u64)>> {
+    let mut fields = output.split('\0');
+    let mut entries = Vec::new();
+    while let Some(record) = fields.next() {
+        if record.is_empty() {
+            continue;
+        }
+        let mut parts = record.splitn(3, '\t');
+        let additions = parts.next().and_then(|n| n.parse().ok());
+        let deletions = parts.next().and_then(|n| n.parse().ok());
+        // Renames leave the path empty and give the old and new paths as
+        // separate fields
+        if parts.next().unwrap_or_default().is_empty() {
+            fields.next();
+            fields.next();
+        }
+        entries.push(additions.zip(deletions));
+    }
+    entries
+}
+
+// Runs a git command and returns its stdout, turning a non-zero exit into
+// an error with its stderr
+fn run_git(command: &mut Command, what: &str) -> Result<String, String> {
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run git {}: {}", what, e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", what, stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const SHA_B: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    fn blame(line: usize, commit: &str, original_line: usize, original_path: &str) -> BlameLine {
+        BlameLine {
+            line,
+            commit: commit.to_string(),
+            original_line,
+            original_path: original_path.to_string(),
+        }
+    }
+
+    fn entry(status: &str, path: &str, old_path: Option<&str>) -> (String, String, Option<String>) {
+        (status.to_string(), path.to_string(), old_path.map(str::to_string))
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        // A commit's filename is only given the first time it appears
+        let output = format!(
+            "{a} 1 1 2\nauthor A\nfilename src/old.rs\n\tfirst\n\
+             {a} 2 2\n\tsecond\n\
+             {b} 7 3 1\nauthor B\nprevious {a} src/old.rs\nfilename src/new.rs\n\tthird\n\
+             {a} 5 4 1\n\tfourth\n",
+            a = SHA_A,
+            b = SHA_B
+        );
+        assert_eq!(
+            parse_blame_porcelain(&output),
+            vec![
+                blame(1, SHA_A, 1, "src/old.rs"),
+                blame(2, SHA_A, 2, "src/old.rs"),
+                blame(3, SHA_B, 7, "src/new.rs"),
+                blame(4, SHA_A, 5, "src/old.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain_header_like_content() {
+        let output = format!("{a} 3 1 1\nfilename a.md\n\t{a} 9 9\n", a = SHA_A);
+        assert_eq!(parse_blame_porcelain(&output), vec![blame(1, SHA_A, 3, "a.md")]);
+    }
+
+    #[test]
+    fn test_parse_name_status() {
+        assert_eq!(
+            parse_name_status("M\0a.md\0A\0b.md\0D\0c.md\0"),
+            vec![
+                entry("modified", "a.md", None),
+                entry("added", "b.md", None),
+                entry("deleted", "c.md", None),
+            ]
+        );
+        assert_eq!(parse_name_status("T\0link\0"), vec![entry("typeChanged", "link", None)]);
+    }
+
+    #[test]
+    fn test_parse_name_status_renames_and_copies() {
+        assert_eq!(
+            parse_name_status("R097\0old.md\0new.md\0C100\0x.md\0y.md\0M\0z.md\0"),
+            vec![
+                entry("renamed", "new.md", Some("old.md")),
+                entry("copied", "y.md", Some("x.md")),
+                entry("modified", "z.md", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_numstat() {
+        assert_eq!(parse_numstat("3\t1\ta.md\0"), vec![Some((3, 1))]);
+    }
+
+    #[test]
+    fn test_parse_numstat_binary_file() {
+        // Binary files have no line counts
+        assert_eq!(parse_numstat("-\t-\timage.png\0"), vec![None]);
+    }
+
+    #[test]
+    fn test_parse_numstat_renames() {
+        // A rename gives its paths as two more fields
+        assert_eq!(
+            parse_numstat(concat!("2\t0\t\0old.md\0new.md\0", "-\t-\t\0a.png\0b.png\0", "5\t5\tc.md\0")),
+            vec![Some((2, 0)), None, Some((5, 5))]
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_output() {
+        assert!(parse_blame_porcelain("").is_empty());
+        assert!(parse_name_status("").is_empty());
+        assert!(parse_numstat("").is_empty());
+    }
+}
@@ -47,6 +47,21 @@ impl GitHubClient {
         endpoint: String,
         body: Option<serde_json::Value>,
     ) -> Result<T, String>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.request_optional(method, endpoint, body)
+            .await?
+            .ok_or_else(|| "Resource not found.".to_string())
+    }
+
+    /// Makes an authenticated request, returning `None` on a 404.
+    async fn request_optional<T>(
+        &self,
+        method: &str,
+        endpoint: String,
+        body: Option<serde_json::Value>,
+    ) -> Result<Option<T>, String>
     where
         T: serde::de::DeserializeOwned,
     {
@@ -119,7 +134,7 @@ impl GitHubClient {
         }
 
         if status == 404 {
-            return Err("Resource not found.".to_string());
+            return Ok(None);
         }
 
         if !status.is_success() {
@@ -130,6 +145,7 @@ impl GitHubClient {
         response
             .json()
             .await
+            .map(Some)
             .map_err(|e| format!("Failed to parse response: {}", e))
     }
 
@@ -171,19 +187,46 @@ impl GitHubClient {
         repo: &str,
         path: &str,
     ) -> Result<String, String> {
+        self.get_file_contents_if_exists(owner, repo, path)
+            .await?
+            .ok_or_else(|| "Resource not found.".to_string())
+    }
+
+    /// Gets the contents of a file, or `None` if it doesn't exist.
+    pub async fn get_file_contents_if_exists(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+    ) -> Result<Option<String>, String> {
         let endpoint = format!("/repos/{}/{}/contents/{}", owner, repo, path);
+        let response: Option<GitHubContentResponse> = self
+            .request_optional("GET", endpoint, None)
+            .await?;
+
+        response.map(decode_content).transpose()
+    }
+
+    /// Gets the contents of a file as of a specific commit, branch or tag.
+    pub async fn get_file_contents_at_ref(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        git_ref: &str,
+    ) -> Result<String, String> {
+        let endpoint = format!(
+            "/repos/{}/{}/contents/{}?ref={}",
+            owner,
+            repo,
+            path,
+            urlencoding::encode(git_ref)
+        );
         let response: GitHubContentResponse = self
             .request("GET", endpoint, None)
             .await?;
 
-        // Decode base64 content
-        use base64::prelude::*;
-        let content_str = response.content.ok_or("File content not available")?;
-        let content = BASE64_STANDARD
-            .decode(content_str.replace('\n', ""))
-            .map_err(|e| format!("Failed to decode base64: {}", e))?;
-        String::from_utf8(content)
-            .map_err(|e| format!("Failed to convert to UTF-8: {}", e))
+        decode_content(response)
     }
 
     /// Creates or updates a file in a repository.
@@ -243,16 +286,10 @@ impl GitHubClient {
     ) -> Result<Option<String>, String> {
         let endpoint = format!("/repos/{}/{}/contents/{}", owner, repo, path);
         
-        match self.request::<GitHubContentResponse>("GET", endpoint, None).await {
-            Ok(response) => Ok(Some(response.sha)),
-            Err(e) => {
-                if e.contains("404") || e.contains("not found") {
-                    Ok(None)
-                } else {
-                    Err(e)
-                }
-            }
-        }
+        let response: Option<GitHubContentResponse> = self
+            .request_optional("GET", endpoint, None)
+            .await?;
+        Ok(response.map(|response| response.sha))
     }
 
     /// Gets a tree (directory contents) from a repository.
@@ -266,6 +303,52 @@ impl GitHubClient {
         self.request("GET", endpoint, None).await
     }
 
+    /// Gets the full tree of a branch or commit, including subdirectories.
+    ///
+    /// Very large repositories come back with `truncated` set.
+    pub async fn get_tree_recursive(
+        &self,
+        owner: &str,
+        repo: &str,
+        tree_ish: &str,
+    ) -> Result<GitHubTreeResponse, String> {
+        let endpoint = format!(
+            "/repos/{}/{}/git/trees/{}?recursive=1",
+            owner,
+            repo,
+            urlencoding::encode(tree_ish)
+        );
+        self.request("GET", endpoint, None).await
+    }
+
+    /// Searches public repositories (GitHub search syntax, e.g. `topic:foo`).
+    pub async fn search_repositories(
+        &self,
+        query: &str,
+        per_page: u32,
+    ) -> Result<GitHubSearchResponse<GitHubRepo>, String> {
+        let endpoint = format!(
+            "/search/repositories?q={}&sort=stars&per_page={}",
+            urlencoding::encode(query),
+            per_page.min(100)
+        );
+        self.request("GET", endpoint, None).await
+    }
+
+    /// Searches code (GitHub search syntax, e.g. `filename:foo.json`).
+    pub async fn search_code(
+        &self,
+        query: &str,
+        per_page: u32,
+    ) -> Result<GitHubSearchResponse<GitHubCodeSearchItem>, String> {
+        let endpoint = format!(
+            "/search/code?q={}&per_page={}",
+            urlencoding::encode(query),
+            per_page.min(100)
+        );
+        self.request("GET", endpoint, None).await
+    }
+
     /// Gets the contents of a directory from a repository.
     /// Returns a list of files and subdirectories.
     pub async fn get_directory_contents(
@@ -335,6 +418,17 @@ impl GitHubClient {
     }
 }
 
+/// Decodes the base64 body of a file contents response.
+fn decode_content(response: GitHubContentResponse) -> Result<String, String> {
+    use base64::prelude::*;
+    let content_str = response.content.ok_or("File content not available")?;
+    let content = BASE64_STANDARD
+        .decode(content_str.replace('\n', ""))
+        .map_err(|e| format!("Failed to decode base64: {}", e))?;
+    String::from_utf8(content)
+        .map_err(|e| format!("Failed to convert to UTF-8: {}", e))
+}
+
 /// GitHub content response (file or directory).
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GitHubContentResponse {
@@ -425,6 +519,35 @@ pub struct GitHubTreeResponse {
     pub truncated: bool,
 }
 
+/// GitHub search results page.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitHubSearchResponse<T> {
+    pub total_count: u64,
+    pub incomplete_results: bool,
+    pub items: Vec<T>,
+}
+
+/// File matched by a code search.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitHubCodeSearchItem {
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub html_url: String,
+    pub repository: GitHubCodeSearchRepository,
+}
+
+/// Repository summary embedded in code search results.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitHubCodeSearchRepository {
+    pub id: u64,
+    pub name: String,
+    pub full_name: String,
+    pub owner: GitHubRepoOwner,
+    pub description: Option<String>,
+    pub html_url: String,
+}
+
 /// GitHub tree item (file or directory in tree).
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GitHubTreeItem {
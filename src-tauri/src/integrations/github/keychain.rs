@@ -9,17 +9,6 @@
 /// The module uses a trait-based architecture to abstract platform-specific
 /// implementations behind a common interface.
 
-use serde::{Deserialize, Serialize};
-
-/// GitHub token structure for storage in keychain.
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct GitHubToken {
-    pub access_token: String,
-    pub token_type: String, // "bearer"
-    pub scope: String,     // "repo,user,read:org"
-    pub expires_at: Option<i64>, // Unix timestamp (if applicable)
-}
-
 /// Platform-agnostic trait for keychain backends.
 pub trait KeychainBackend {
     fn store(&self, service: &str, key: &str, value: &str) -> Result<(), String>;
@@ -174,7 +163,7 @@ pub struct LinuxKeychain;
 #[cfg(target_os = "linux")]
 impl KeychainBackend for LinuxKeychain {
     fn store(&self, service: &str, key: &str, value: &str) -> Result<(), String> {
-        use secret_service::SecretService;
+        use secret_service::blocking::SecretService;
         use secret_service::EncryptionType;
         
         let ss = SecretService::connect(EncryptionType::Dh)
@@ -184,16 +173,17 @@ impl KeychainBackend for LinuxKeychain {
             .map_err(|e| format!("Failed to get default collection: {}", e))?;
         
         let label = format!("bluekit:{}:{}", service, key);
-        let attributes = vec![
+        let attributes = std::collections::HashMap::from([
             ("service", service),
             ("key", key),
-        ];
+        ]);
         
         collection.create_item(
             &label,
-            &attributes,
+            attributes,
             value.as_bytes(),
             true, // replace if exists
+            "text/plain",
         )
         .map_err(|e| format!("Failed to create secret: {}", e))?;
         
@@ -201,7 +191,7 @@ impl KeychainBackend for LinuxKeychain {
     }
     
     fn retrieve(&self, service: &str, key: &str) -> Result<String, String> {
-        use secret_service::SecretService;
+        use secret_service::blocking::SecretService;
         use secret_service::EncryptionType;
         
         let ss = SecretService::connect(EncryptionType::Dh)
@@ -210,12 +200,12 @@ impl KeychainBackend for LinuxKeychain {
         let collection = ss.get_default_collection()
             .map_err(|e| format!("Failed to get default collection: {}", e))?;
         
-        let attributes = vec![
+        let attributes = std::collections::HashMap::from([
             ("service", service),
             ("key", key),
-        ];
+        ]);
         
-        let search_result = collection.search_items(&attributes)
+        let search_result = collection.search_items(attributes)
             .map_err(|e| format!("Failed to search items: {}", e))?;
         
         if search_result.is_empty() {
@@ -231,7 +221,7 @@ impl KeychainBackend for LinuxKeychain {
     }
     
     fn delete(&self, service: &str, key: &str) -> Result<(), String> {
-        use secret_service::SecretService;
+        use secret_service::blocking::SecretService;
         use secret_service::EncryptionType;
         
         let ss = SecretService::connect(EncryptionType::Dh)
@@ -240,12 +230,12 @@ impl KeychainBackend for LinuxKeychain {
         let collection = ss.get_default_collection()
             .map_err(|e| format!("Failed to get default collection: {}", e))?;
         
-        let attributes = vec![
+        let attributes = std::collections::HashMap::from([
             ("service", service),
             ("key", key),
-        ];
+        ]);
         
-        let search_result = collection.search_items(&attributes)
+        let search_result = collection.search_items(attributes)
             .map_err(|e| format!("Failed to search items: {}", e))?;
         
         if search_result.is_empty() {
@@ -283,22 +273,18 @@ impl KeychainManager {
         Ok(Self { backend })
     }
     
-    /// Stores a GitHub token in the keychain.
-    pub fn store_token(&self, token: &GitHubToken) -> Result<(), String> {
-        let serialized = serde_json::to_string(token)
-            .map_err(|e| format!("Failed to serialize token: {}", e))?;
-        self.backend.store("bluekit", "github_token", &serialized)
+    /// Stores the database encryption key in the keychain.
+    pub fn store_database_key(&self, key: &str) -> Result<(), String> {
+        self.backend.store("bluekit", "database_key", key)
     }
-    
-    /// Retrieves a GitHub token from the keychain.
-    pub fn retrieve_token(&self) -> Result<GitHubToken, String> {
-        let serialized = self.backend.retrieve("bluekit", "github_token")?;
-        serde_json::from_str(&serialized)
-            .map_err(|e| format!("Failed to deserialize token: {}", e))
+
+    /// Retrieves the database encryption key from the keychain.
+    pub fn retrieve_database_key(&self) -> Result<String, String> {
+        self.backend.retrieve("bluekit", "database_key")
     }
-    
-    /// Deletes a GitHub token from the keychain.
-    pub fn delete_token(&self) -> Result<(), String> {
-        self.backend.delete("bluekit", "github_token")
+
+    /// Deletes the database encryption key from the keychain.
+    pub fn delete_database_key(&self) -> Result<(), String> {
+        self.backend.delete("bluekit", "database_key")
     }
 }
@@ -8,6 +8,7 @@
 
 pub mod auth;
 pub mod github;
+pub mod keychain;
 
 pub mod oauth_server;
 pub mod commit_cache;
@@ -0,0 +1,246 @@
+//! Workspace activity feed.
+//!
+//! Combines what the database knows (variation publishes, catalog and
+//! collection creation) with BlueKit-authored commits in the workspace
+//! repository, which are the only record of deletions and folder changes.
+
+use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::db::entities::*;
+use crate::integrations::github::GitHubClient;
+
+/// Commit message prefix used by every BlueKit write to a workspace repo
+const COMMIT_PREFIX: &str = "[BlueKit] ";
+
+/// Commits fetched per page (GitHub's maximum)
+const COMMITS_PER_PAGE: u32 = 100;
+
+/// Upper bound on commit pages read for one feed
+const MAX_COMMIT_PAGES: u32 = 5;
+
+/// Time window for the feed, in Unix seconds (both ends inclusive)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityRange {
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+}
+
+impl ActivityRange {
+    fn contains(&self, timestamp: i64) -> bool {
+        self.since.is_none_or(|since| timestamp >= since)
+            && self.until.is_none_or(|until| timestamp <= until)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    /// "variation_published", "catalog_created", "catalog_deleted",
+    /// "collection_created", "folder_created", "folder_deleted",
+    /// "folder_changed" or "other"
+    pub kind: String,
+    pub timestamp: i64,
+    pub title: String,
+    pub actor: Option<String>,
+    pub catalog_id: Option<String>,
+    pub variation_id: Option<String>,
+    pub commit_sha: Option<String>,
+    /// "database" or "github"
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceActivity {
+    pub workspace_id: String,
+    /// Newest first
+    pub events: Vec<ActivityEvent>,
+    /// Set when commits couldn't be read; the feed then only has DB events
+    pub github_error: Option<String>,
+}
+
+/// Build the activity feed for a workspace.
+///
+/// GitHub commits are only read when `github_token` is given. Publishes
+/// recorded in the database are not repeated from their commits.
+pub async fn get_workspace_activity(
+    db: &DatabaseConnection,
+    workspace_id: &str,
+    range: ActivityRange,
+    github_token: Option<String>,
+) -> Result<WorkspaceActivity, String> {
+    let workspace = library_workspace::Entity::find_by_id(workspace_id)
+        .one(db)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| format!("Workspace not found: {}", workspace_id))?;
+
+    let mut events = Vec::new();
+    let mut known_commits = HashSet::new();
+
+    let catalogs = library_catalog::Entity::find()
+        .filter(library_catalog::Column::WorkspaceId.eq(workspace_id))
+        .find_with_related(library_variation::Entity)
+        .all(db)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    for (catalog, variations) in &catalogs {
+        if range.contains(catalog.created_at) {
+            events.push(ActivityEvent {
+                kind: "catalog_created".to_string(),
+                timestamp: catalog.created_at,
+                title: format!("Created {}", catalog.name),
+                actor: None,
+                catalog_id: Some(catalog.id.clone()),
+                variation_id: None,
+                commit_sha: None,
+                source: "database".to_string(),
+            });
+        }
+
+        for variation in variations {
+            if let Some(sha) = &variation.github_commit_sha {
+                known_commits.insert(sha.clone());
+            }
+            if !range.contains(variation.published_at) {
+                continue;
+            }
+
+            let title = match &variation.version_tag {
+                Some(tag) => format!("Published {} ({})", catalog.name, tag),
+                None => format!("Published {}", catalog.name),
+            };
+            events.push(ActivityEvent {
+                kind: "variation_published".to_string(),
+                timestamp: variation.published_at,
+                title,
+                actor: variation.publisher_name.clone(),
+                catalog_id: Some(catalog.id.clone()),
+                variation_id: Some(variation.id.clone()),
+                commit_sha: variation.github_commit_sha.clone(),
+                source: "database".to_string(),
+            });
+        }
+    }
+
+    let collections = library_collection::Entity::find()
+        .filter(library_collection::Column::WorkspaceId.eq(workspace_id))
+        .all(db)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    for collection in collections.into_iter().filter(|c| range.contains(c.created_at)) {
+        events.push(ActivityEvent {
+            kind: "collection_created".to_string(),
+            timestamp: collection.created_at,
+            title: format!("Created collection {}", collection.name),
+            actor: None,
+            catalog_id: None,
+            variation_id: None,
+            commit_sha: None,
+            source: "database".to_string(),
+        });
+    }
+
+    let mut github_error = None;
+    if let Some(token) = github_token {
+        let client = GitHubClient::new(token);
+        match commit_events(&client, &workspace, &range, &known_commits).await {
+            Ok(commit_events) => events.extend(commit_events),
+            Err(e) => github_error = Some(e),
+        }
+    }
+
+    events.sort_by_key(|event| std::cmp::Reverse(event.timestamp));
+
+    Ok(WorkspaceActivity {
+        workspace_id: workspace_id.to_string(),
+        events,
+        github_error,
+    })
+}
+
+/// Read BlueKit-authored commits in the range, newest first.
+async fn commit_events(
+    client: &GitHubClient,
+    workspace: &library_workspace::Model,
+    range: &ActivityRange,
+    known_commits: &HashSet<String>,
+) -> Result<Vec<ActivityEvent>, String> {
+    let mut events = Vec::new();
+
+    for page in 1..=MAX_COMMIT_PAGES {
+        let commits = client
+            .get_commits(
+                &workspace.github_owner,
+                &workspace.github_repo,
+                None,
+                Some(COMMITS_PER_PAGE),
+                Some(page),
+            )
+            .await?;
+        let last_page = commits.len() < COMMITS_PER_PAGE as usize;
+
+        let mut reached_start = false;
+        for commit in commits {
+            let Ok(date) = chrono::DateTime::parse_from_rfc3339(&commit.commit.author.date) else {
+                continue;
+            };
+            let timestamp = date.timestamp();
+            if range.since.is_some_and(|since| timestamp < since) {
+                reached_start = true;
+                break;
+            }
+            if !range.contains(timestamp) || known_commits.contains(&commit.sha) {
+                continue;
+            }
+
+            let summary = commit.commit.message.lines().next().unwrap_or_default();
+            let Some(summary) = summary.strip_prefix(COMMIT_PREFIX) else {
+                continue;
+            };
+
+            events.push(ActivityEvent {
+                kind: commit_kind(summary).to_string(),
+                timestamp,
+                title: summary.to_string(),
+                actor: commit
+                    .author
+                    .as_ref()
+                    .map(|user| user.login.clone())
+                    .or_else(|| Some(commit.commit.author.name.clone())),
+                catalog_id: None,
+                variation_id: None,
+                commit_sha: Some(commit.sha.clone()),
+                source: "github".to_string(),
+            });
+        }
+
+        if reached_start || last_page {
+            break;
+        }
+    }
+
+    Ok(events)
+}
+
+/// Map a BlueKit commit summary to an event kind.
+fn commit_kind(summary: &str) -> &'static str {
+    if summary.starts_with("Publish:") {
+        // A publish whose variation has since been overwritten or deleted
+        "variation_published"
+    } else if summary.starts_with("Delete catalog:") {
+        "catalog_deleted"
+    } else if summary.starts_with("Create folder:") {
+        "folder_created"
+    } else if summary.starts_with("Delete folder:") {
+        "folder_deleted"
+    } else if summary.starts_with("Move catalog to folder:")
+        || summary.starts_with("Remove catalog from folder:")
+    {
+        "folder_changed"
+    } else {
+        "other"
+    }
+}
@@ -0,0 +1,274 @@
+//! Discovery of public BlueKit libraries on GitHub.
+//!
+//! A repository counts as a public library if it has the `bluekit-library`
+//! topic or a `bluekit-index.json` at its root. Their catalogs can be
+//! browsed and pulled straight into a project without adding the repository
+//! as a workspace, so nothing is stored in the database.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::integrations::github::GitHubClient;
+use super::pull::{determine_local_path, extract_artifact_type_from_content};
+
+/// Topic that marks a repository as a BlueKit library
+const LIBRARY_TOPIC: &str = "bluekit-library";
+
+/// Optional index file listing a library's catalogs
+const INDEX_FILE: &str = "bluekit-index.json";
+
+/// Repositories returned per search
+const MAX_LIBRARIES: u32 = 20;
+
+/// Directories (at the root or under `.bluekit/`) scanned when a library has
+/// no index, with the artifact type they hold
+const ARTIFACT_DIRS: [(&str, &str); 4] = [
+    ("kits", "kit"),
+    ("walkthroughs", "walkthrough"),
+    ("agents", "agent"),
+    ("diagrams", "diagram"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicCatalog {
+    pub name: String,
+    /// Path of the artifact in the repository
+    pub path: String,
+    pub artifact_type: String,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicLibrary {
+    pub owner: String,
+    pub repo: String,
+    pub full_name: String,
+    pub description: Option<String>,
+    pub html_url: String,
+    /// Only known for repositories found by topic
+    pub stars: Option<u32>,
+    pub has_topic: bool,
+    pub has_index: bool,
+    pub catalogs: Vec<PublicCatalog>,
+    /// Set if the catalogs couldn't be listed
+    pub error: Option<String>,
+}
+
+/// Contents of `bluekit-index.json`
+#[derive(Debug, Deserialize)]
+struct LibraryIndex {
+    catalogs: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+    name: String,
+    path: String,
+    #[serde(rename = "artifactType", alias = "type")]
+    artifact_type: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Search GitHub for public libraries matching `query` (may be empty).
+///
+/// Topic and index-file searches are merged by repository. Code search
+/// needs a signed-in user; if it fails, only topic matches are returned.
+pub async fn browse_public_libraries(
+    access_token: String,
+    query: &str,
+) -> Result<Vec<PublicLibrary>, String> {
+    let client = GitHubClient::new(access_token);
+    let query = query.trim();
+
+    let topic_query = format!("topic:{} {}", LIBRARY_TOPIC, query);
+    let index_query = format!("filename:{} path:/ {}", INDEX_FILE, query);
+    let (topic_results, index_results) = tokio::join!(
+        client.search_repositories(topic_query.trim(), MAX_LIBRARIES),
+        client.search_code(index_query.trim(), MAX_LIBRARIES),
+    );
+
+    let mut libraries: Vec<PublicLibrary> = Vec::new();
+    let mut by_name: HashMap<String, usize> = HashMap::new();
+    // Default branch per repository, when the search told us
+    let mut branches: HashMap<String, String> = HashMap::new();
+
+    for repo in topic_results?.items {
+        by_name.insert(repo.full_name.clone(), libraries.len());
+        branches.insert(repo.full_name.clone(), repo.default_branch.clone());
+        libraries.push(PublicLibrary {
+            owner: repo.owner.login,
+            repo: repo.name,
+            full_name: repo.full_name,
+            description: repo.description,
+            html_url: repo.html_url,
+            stars: Some(repo.stargazers_count),
+            has_topic: true,
+            has_index: false,
+            catalogs: Vec::new(),
+            error: None,
+        });
+    }
+
+    match index_results {
+        Ok(results) => {
+            for item in results.items.into_iter().filter(|item| item.path == INDEX_FILE) {
+                if let Some(&index) = by_name.get(&item.repository.full_name) {
+                    libraries[index].has_index = true;
+                    continue;
+                }
+                let repo = item.repository;
+                by_name.insert(repo.full_name.clone(), libraries.len());
+                libraries.push(PublicLibrary {
+                    owner: repo.owner.login,
+                    repo: repo.name,
+                    full_name: repo.full_name,
+                    description: repo.description,
+                    html_url: repo.html_url,
+                    stars: None,
+                    has_topic: false,
+                    has_index: true,
+                    catalogs: Vec::new(),
+                    error: None,
+                });
+            }
+        }
+        Err(e) => tracing::warn!("Index file search failed: {}", e),
+    }
+
+    libraries.truncate(MAX_LIBRARIES as usize);
+
+    let listings = futures::future::join_all(libraries.iter().map(|library| {
+        let branch = branches.get(&library.full_name).map(String::as_str);
+        list_catalogs(&client, &library.owner, &library.repo, branch)
+    }))
+    .await;
+
+    for (library, listing) in libraries.iter_mut().zip(listings) {
+        match listing {
+            Ok((catalogs, has_index)) => {
+                library.catalogs = catalogs;
+                library.has_index |= has_index;
+            }
+            Err(e) => library.error = Some(e),
+        }
+    }
+
+    Ok(libraries)
+}
+
+/// List a library's catalogs from its index, or by scanning the artifact
+/// directories if it has none. Also returns whether an index was found.
+async fn list_catalogs(
+    client: &GitHubClient,
+    owner: &str,
+    repo: &str,
+    branch: Option<&str>,
+) -> Result<(Vec<PublicCatalog>, bool), String> {
+    if let Some(content) = client
+        .get_file_contents_if_exists(owner, repo, INDEX_FILE)
+        .await?
+    {
+        let index: LibraryIndex = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid {}: {}", INDEX_FILE, e))?;
+        let catalogs = index
+            .catalogs
+            .into_iter()
+            .map(|entry| PublicCatalog {
+                artifact_type: entry
+                    .artifact_type
+                    .or_else(|| artifact_type_for_path(&entry.path).map(str::to_string))
+                    .unwrap_or_else(|| "other".to_string()),
+                name: entry.name,
+                path: entry.path,
+                description: entry.description,
+                tags: entry.tags,
+            })
+            .collect();
+        return Ok((catalogs, true));
+    }
+
+    let tree = client
+        .get_tree_recursive(owner, repo, branch.unwrap_or("HEAD"))
+        .await?;
+    let catalogs = tree
+        .tree
+        .into_iter()
+        .filter(|item| item.item_type == "blob" && item.path.ends_with(".md"))
+        .filter_map(|item| {
+            let artifact_type = artifact_type_for_path(&item.path)?;
+            Some(PublicCatalog {
+                name: display_name(&item.path),
+                artifact_type: artifact_type.to_string(),
+                path: item.path,
+                description: None,
+                tags: Vec::new(),
+            })
+        })
+        .collect();
+
+    Ok((catalogs, false))
+}
+
+/// Pull one artifact from a public library into a project.
+///
+/// The file lands where a workspace pull would put it, but no subscription
+/// is recorded, so it won't be offered updates. Returns the path written,
+/// relative to the project.
+pub async fn pull_public_artifact(
+    access_token: String,
+    owner: &str,
+    repo: &str,
+    path: &str,
+    target_project_path: &str,
+    overwrite_if_exists: bool,
+) -> Result<String, String> {
+    let client = GitHubClient::new(access_token);
+    let content = client.get_file_contents(owner, repo, path).await?;
+
+    let file_name = path
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty() && *name != "..")
+        .ok_or_else(|| format!("Invalid artifact path: {}", path))?;
+    let artifact_type = extract_artifact_type_from_content(&content)
+        .or_else(|| artifact_type_for_path(path).map(str::to_string))
+        .unwrap_or_else(|| "other".to_string());
+
+    let relative_path = determine_local_path(&artifact_type, file_name);
+    let full_path = Path::new(target_project_path).join(&relative_path);
+    if full_path.exists() && !overwrite_if_exists {
+        return Err(format!("File already exists: {}", relative_path));
+    }
+
+    if let Some(parent) = full_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    std::fs::write(&full_path, content)
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(relative_path)
+}
+
+/// Artifact type implied by a path like `kits/x.md` or `.bluekit/kits/x.md`.
+fn artifact_type_for_path(path: &str) -> Option<&'static str> {
+    let path = path.strip_prefix(".bluekit/").unwrap_or(path);
+    let dir = path.split('/').next()?;
+    ARTIFACT_DIRS
+        .iter()
+        .find(|(name, _)| *name == dir)
+        .map(|(_, artifact_type)| *artifact_type)
+}
+
+/// Display name from a file path (extension dropped, dashes to spaces).
+fn display_name(path: &str) -> String {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    file_name
+        .strip_suffix(".md")
+        .unwrap_or(file_name)
+        .replace(['-', '_'], " ")
+}
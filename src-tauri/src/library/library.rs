@@ -3,9 +3,9 @@
 /// This module handles Library workspaces, which are GitHub repositories
 /// used for publishing and syncing kits, walkthroughs, and other artifacts.
 
-use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, Set, Update};
+use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, ColumnTrait, Set, Update};
 use serde::{Deserialize, Serialize};
-use crate::db::entities::{library_workspace, library_artifact};
+use crate::db::entities::{library_workspace, library_catalog, library_variation, library_subscription, library_resource, project};
 use crate::integrations::github::GitHubClient;
 use chrono::Utc;
 
@@ -21,7 +21,7 @@ pub struct LibraryWorkspace {
     pub updated_at: i64,
 }
 
-/// Library artifact structure.
+/// Library artifact structure (a published catalog, flattened).
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LibraryArtifact {
     pub id: String,
@@ -247,31 +247,74 @@ pub async fn set_workspace_pinned(
 }
 
 /// Lists all artifacts in a workspace (or all workspaces if None).
+///
+/// Each catalog is reported as one artifact: `library_path` is its remote
+/// path and `published_at` comes from its latest variation. `local_path` is
+/// the first subscribed local copy, or empty if nothing has been pulled.
 pub async fn list_artifacts(
     db: &DatabaseConnection,
     workspace_id: Option<String>,
 ) -> Result<Vec<LibraryArtifact>, String> {
-    let mut query = library_artifact::Entity::find();
+    let mut query = library_catalog::Entity::find();
 
     if let Some(ws_id) = workspace_id {
-        query = query.filter(library_artifact::Column::WorkspaceId.eq(ws_id));
+        query = query.filter(library_catalog::Column::WorkspaceId.eq(ws_id));
     }
 
-    let artifacts = query
+    let catalogs = query
         .all(db)
         .await
         .map_err(|e| format!("Failed to list artifacts: {}", e))?;
 
-    Ok(artifacts
-        .into_iter()
-        .map(|a| LibraryArtifact {
-            id: a.id,
-            workspace_id: a.workspace_id,
-            local_path: a.local_path,
-            library_path: a.library_path,
-            artifact_type: a.artifact_type,
-            published_at: a.published_at,
-            last_synced_at: a.last_synced_at,
-        })
-        .collect())
+    let mut artifacts = Vec::with_capacity(catalogs.len());
+    for catalog in catalogs {
+        let latest_variation = library_variation::Entity::find()
+            .filter(library_variation::Column::CatalogId.eq(&catalog.id))
+            .order_by_desc(library_variation::Column::PublishedAt)
+            .one(db)
+            .await
+            .map_err(|e| format!("Failed to list artifacts: {}", e))?;
+
+        artifacts.push(LibraryArtifact {
+            local_path: subscribed_local_path(db, &catalog.id).await?.unwrap_or_default(),
+            published_at: latest_variation
+                .map(|v| v.published_at)
+                .unwrap_or(catalog.created_at),
+            id: catalog.id,
+            workspace_id: catalog.workspace_id,
+            library_path: catalog.remote_path,
+            artifact_type: catalog.artifact_type,
+            last_synced_at: catalog.updated_at,
+        });
+    }
+
+    Ok(artifacts)
+}
+
+// Absolute path of the first local copy subscribed to a catalog
+async fn subscribed_local_path(
+    db: &DatabaseConnection,
+    catalog_id: &str,
+) -> Result<Option<String>, String> {
+    let Some((_, Some(resource))) = library_subscription::Entity::find()
+        .filter(library_subscription::Column::CatalogId.eq(catalog_id))
+        .find_also_related(library_resource::Entity)
+        .one(db)
+        .await
+        .map_err(|e| format!("Failed to look up subscription: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let project = project::Entity::find_by_id(&resource.project_id)
+        .one(db)
+        .await
+        .map_err(|e| format!("Failed to look up project: {}", e))?;
+
+    Ok(project.map(|p| {
+        std::path::Path::new(&p.path)
+            .join(&resource.relative_path)
+            .to_string_lossy()
+            .to_string()
+    }))
 }
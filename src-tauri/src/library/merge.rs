@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+/// A region both sides changed differently.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictHunk {
+    /// 1-based line in the base where the conflicting region starts
+    #[serde(rename = "baseStartLine")]
+    pub base_start_line: usize,
+    pub base: String,
+    /// Local version of the region
+    pub ours: String,
+    /// Incoming library version of the region
+    pub theirs: String,
+}
+
+/// Result of a three-way merge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeOutcome {
+    /// Merged text; contains conflict markers when `conflicts` is non-empty
+    pub merged: String,
+    pub conflicts: Vec<ConflictHunk>,
+}
+
+impl MergeOutcome {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// A change one side made to a range of base lines.
+struct Change {
+    base_start: usize,
+    base_end: usize,
+    side_start: usize,
+    side_end: usize,
+}
+
+/// Performs a line-based three-way merge (diff3).
+///
+/// Regions changed by only one side are taken from that side; regions both
+/// sides changed identically are taken once; anything else is a conflict,
+/// emitted with `<<<<<<< local` / `>>>>>>> library` markers.
+pub fn three_way_merge(base: &str, ours: &str, theirs: &str) -> MergeOutcome {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let our_lines: Vec<&str> = ours.split_inclusive('\n').collect();
+    let their_lines: Vec<&str> = theirs.split_inclusive('\n').collect();
+
+    let our_changes = changes(&base_lines, &our_lines);
+    let their_changes = changes(&base_lines, &their_lines);
+
+    let mut merged = String::new();
+    let mut conflicts = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let mut base_pos = 0;
+    // Offsets mapping base line numbers to each side before the current chunk
+    let (mut our_offset, mut their_offset): (isize, isize) = (0, 0);
+
+    while i < our_changes.len() || j < their_changes.len() {
+        // Start a chunk at the earliest pending change
+        let first_is_ours = match (our_changes.get(i), their_changes.get(j)) {
+            (Some(a), Some(b)) => a.base_start <= b.base_start,
+            (Some(_), None) => true,
+            _ => false,
+        };
+        let (chunk_start, mut chunk_end) = if first_is_ours {
+            (our_changes[i].base_start, our_changes[i].base_end)
+        } else {
+            (their_changes[j].base_start, their_changes[j].base_end)
+        };
+
+        // Absorb every change (from either side) overlapping the chunk
+        let (our_first, their_first) = (i, j);
+        loop {
+            let mut grew = false;
+            while let Some(c) = our_changes.get(i) {
+                if !overlaps(c, chunk_start, chunk_end) {
+                    break;
+                }
+                chunk_end = chunk_end.max(c.base_end);
+                i += 1;
+                grew = true;
+            }
+            while let Some(c) = their_changes.get(j) {
+                if !overlaps(c, chunk_start, chunk_end) {
+                    break;
+                }
+                chunk_end = chunk_end.max(c.base_end);
+                j += 1;
+                grew = true;
+            }
+            if !grew {
+                break;
+            }
+        }
+
+        // Unchanged lines before the chunk
+        merged.extend(base_lines[base_pos..chunk_start].iter().copied());
+
+        let our_text = side_text(&our_lines, &our_changes[our_first..i], chunk_start, chunk_end, &mut our_offset);
+        let their_text = side_text(&their_lines, &their_changes[their_first..j], chunk_start, chunk_end, &mut their_offset);
+        let base_text: String = base_lines[chunk_start..chunk_end].concat();
+
+        if our_first == i {
+            merged.push_str(&their_text);
+        } else if their_first == j || our_text == their_text {
+            merged.push_str(&our_text);
+        } else {
+            merged.push_str("<<<<<<< local\n");
+            push_terminated(&mut merged, &our_text);
+            merged.push_str("=======\n");
+            push_terminated(&mut merged, &their_text);
+            merged.push_str(">>>>>>> library\n");
+
+            conflicts.push(ConflictHunk {
+                base_start_line: chunk_start + 1,
+                base: base_text,
+                ours: our_text,
+                theirs: their_text,
+            });
+        }
+
+        base_pos = chunk_end;
+    }
+
+    merged.extend(base_lines[base_pos..].iter().copied());
+
+    MergeOutcome { merged, conflicts }
+}
+
+// Collects the non-equal diff ops between base and one side
+fn changes(base: &[&str], side: &[&str]) -> Vec<Change> {
+    capture_diff_slices(Algorithm::Myers, base, side)
+        .into_iter()
+        .filter(|op| !matches!(op, DiffOp::Equal { .. }))
+        .map(|op| {
+            let old = op.old_range();
+            let new = op.new_range();
+            Change {
+                base_start: old.start,
+                base_end: old.end,
+                side_start: new.start,
+                side_end: new.end,
+            }
+        })
+        .collect()
+}
+
+// Two edits conflict if their base ranges intersect, or both insert at the same point
+fn overlaps(change: &Change, chunk_start: usize, chunk_end: usize) -> bool {
+    change.base_start < chunk_end
+        || (change.base_start == chunk_end && (change.base_start == change.base_end || chunk_start == chunk_end))
+}
+
+// Text of one side covering base range [chunk_start, chunk_end)
+fn side_text(
+    side: &[&str],
+    chunk_changes: &[Change],
+    chunk_start: usize,
+    chunk_end: usize,
+    offset: &mut isize,
+) -> String {
+    let start = (chunk_start as isize + *offset) as usize;
+    for c in chunk_changes {
+        *offset += (c.side_end - c.side_start) as isize - (c.base_end - c.base_start) as isize;
+    }
+    let end = (chunk_end as isize + *offset) as usize;
+    side[start..end].concat()
+}
+
+// Appends text, making sure the next conflict marker starts on its own line
+fn push_terminated(out: &mut String, text: &str) {
+    out.push_str(text);
+    if !text.is_empty() && !text.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_overlapping_changes_merge_cleanly() {
+        let base = "a\nb\nc\nd\ne\n";
+        let ours = "A\nb\nc\nd\ne\n";
+        let theirs = "a\nb\nc\nd\nE\n";
+
+        let outcome = three_way_merge(base, ours, theirs);
+
+        assert!(outcome.is_clean());
+        assert_eq!(outcome.merged, "A\nb\nc\nd\nE\n");
+    }
+
+    #[test]
+    fn test_overlapping_changes_conflict() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nX\nc\n";
+        let theirs = "a\nY\nc\n";
+
+        let outcome = three_way_merge(base, ours, theirs);
+
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].base_start_line, 2);
+        assert_eq!(outcome.conflicts[0].ours, "X\n");
+        assert_eq!(outcome.conflicts[0].theirs, "Y\n");
+        assert_eq!(outcome.merged, "a\n<<<<<<< local\nX\n=======\nY\n>>>>>>> library\nc\n");
+    }
+}
@@ -10,6 +10,9 @@ pub mod publishing;
 pub mod sync;
 pub mod pull;
 pub mod updates;
+pub mod merge;
+pub mod activity;
+pub mod discovery;
 
 // Re-export commonly used types
 pub use library::{LibraryWorkspace, LibraryArtifact};
@@ -7,6 +7,8 @@ use uuid::Uuid;
 use crate::db::entities::*;
 use crate::integrations::github::GitHubClient;
 use super::utils::compute_content_hash;
+use crate::core::cancellation::CANCELLED_ERROR;
+use crate::core::progress::ProgressReporter;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "status")]
@@ -38,6 +40,14 @@ pub struct VariationInfo {
     pub github_commit_sha: Option<String>,
 }
 
+/// Outcome of one resource in a bulk publish
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkPublishItem {
+    pub resource_id: String,
+    pub result: Option<PublishResult>,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PublishOptions {
     pub resource_id: String,
@@ -153,6 +163,14 @@ pub async fn publish_resource(
         return Err("Resource content hash mismatch. Re-scan resources first.".to_string());
     }
 
+    // Reject malformed front matter before anything is pushed
+    if matches!(resource.artifact_type.as_str(), "kit" | "walkthrough" | "agent" | "diagram") {
+        let validation = crate::core::metadata_schema::validate_metadata(&content, Some(&resource.artifact_type));
+        if !validation.valid {
+            return Err(format!("Invalid front matter: {}", validation.error_summary()));
+        }
+    }
+
     // Get the workspace
     let workspace = library_workspace::Entity::find_by_id(&options.workspace_id)
         .one(db)
@@ -294,6 +312,45 @@ pub async fn publish_resource(
     })
 }
 
+/// Publish several resources to a workspace, reporting each to `progress`.
+///
+/// Each resource is published as a new variation. A failing resource doesn't
+/// stop the others; its error is recorded in its [`BulkPublishItem`]. Once
+/// cancelled, the remaining resources are marked cancelled; those already
+/// published are kept.
+pub async fn publish_resources(
+    db: &DatabaseConnection,
+    resource_ids: Vec<String>,
+    workspace_id: &str,
+    version_tag: Option<String>,
+    progress: &ProgressReporter,
+) -> Vec<BulkPublishItem> {
+    let total = resource_ids.len() as u64;
+    let mut items = Vec::with_capacity(resource_ids.len());
+
+    for (index, resource_id) in resource_ids.into_iter().enumerate() {
+        if progress.is_cancelled() {
+            items.push(BulkPublishItem { resource_id, result: None, error: Some(CANCELLED_ERROR.to_string()) });
+            continue;
+        }
+        progress.report("publishing", index as u64, Some(total), resource_id.clone());
+
+        let options = PublishOptions {
+            resource_id: resource_id.clone(),
+            workspace_id: workspace_id.to_string(),
+            overwrite_variation_id: None,
+            version_tag: version_tag.clone(),
+        };
+        let item = match publish_resource(db, options).await {
+            Ok(result) => BulkPublishItem { resource_id, result: Some(result), error: None },
+            Err(e) => BulkPublishItem { resource_id, result: None, error: Some(e) },
+        };
+        items.push(item);
+    }
+
+    items
+}
+
 /// Determine the remote path in GitHub based on artifact type and filename.
 fn determine_remote_path(artifact_type: &str, file_name: &str) -> String {
     match artifact_type {
@@ -6,7 +6,10 @@ use uuid::Uuid;
 
 use crate::db::entities::*;
 use crate::integrations::github::GitHubClient;
+use super::merge::{three_way_merge, ConflictHunk};
 use super::utils::compute_content_hash;
+use crate::core::cancellation::CANCELLED_ERROR;
+use crate::core::progress::ProgressReporter;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PullOptions {
@@ -14,6 +17,16 @@ pub struct PullOptions {
     pub target_project_id: String,
     pub target_project_path: String,
     pub overwrite_if_exists: bool,
+    /// Conflict-resolved text to write instead of the library version
+    pub resolved_content: Option<String>,
+}
+
+/// Outcome of one variation in a bulk pull
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkPullItem {
+    pub variation_id: String,
+    pub result: Option<PullResult>,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,9 +35,18 @@ pub struct PullResult {
     pub subscription_id: String,
     pub file_path: String,
     pub content_hash: String,
+    /// "pulled", "merged" (local edits were kept) or "conflicts" (nothing written)
+    pub status: String,
+    pub conflicts: Vec<ConflictHunk>,
+    /// Merged text with conflict markers, for the frontend to resolve
+    pub merged_content: Option<String>,
 }
 
 /// Pull a variation to a local project.
+///
+/// If the local file was edited since it was last pulled, the edits are
+/// three-way merged with the new variation; conflicting merges are returned
+/// without writing anything so the frontend can resolve them.
 pub async fn pull_variation(
     db: &DatabaseConnection,
     options: PullOptions,
@@ -84,12 +106,51 @@ pub async fn pull_variation(
     let relative_path = determine_local_path(&artifact_type, file_name);
     let full_path = Path::new(&options.target_project_path).join(&relative_path);
 
-    // Check if file already exists
-    if full_path.exists() && !options.overwrite_if_exists {
-        return Err(format!(
-            "File already exists: {}. Set overwrite_if_exists to true to replace it.",
-            relative_path
-        ));
+    // Decide what to write: the library version, a merge with local edits,
+    // or the caller's resolution of a previous conflict
+    let mut status = "pulled";
+    let mut write_content = content.clone();
+
+    if let Some(resolved) = options.resolved_content.clone() {
+        write_content = resolved;
+        status = "merged";
+    } else if full_path.exists() && !options.overwrite_if_exists {
+        let local = std::fs::read_to_string(&full_path)
+            .map_err(|e| format!("Failed to read local file: {}", e))?;
+
+        if local != content {
+            let Some(merge_base) = find_merge_base(
+                db,
+                &github_client,
+                &workspace,
+                &options.target_project_id,
+                &relative_path,
+            )
+            .await?
+            else {
+                return Err(format!(
+                    "File already exists: {}. Set overwrite_if_exists to true to replace it.",
+                    relative_path
+                ));
+            };
+
+            let outcome = three_way_merge(&merge_base.base_content, &local, &content);
+            if !outcome.is_clean() {
+                // Leave the local file alone until the conflicts are resolved
+                return Ok(PullResult {
+                    resource_id: merge_base.resource_id,
+                    subscription_id: merge_base.subscription_id,
+                    file_path: relative_path,
+                    content_hash,
+                    status: "conflicts".to_string(),
+                    conflicts: outcome.conflicts,
+                    merged_content: Some(outcome.merged),
+                });
+            }
+
+            write_content = outcome.merged;
+            status = "merged";
+        }
     }
 
     // Ensure parent directory exists
@@ -99,7 +160,7 @@ pub async fn pull_variation(
     }
 
     // Write file to disk
-    std::fs::write(&full_path, &content)
+    std::fs::write(&full_path, &write_content)
         .map_err(|e| format!("Failed to write file: {}", e))?;
 
     // Create or update resource record
@@ -155,6 +216,25 @@ pub async fn pull_variation(
         }
     };
 
+    // Record provenance so check_outdated_copies can compare against newer
+    // variations. The file is already written, so don't fail the pull over it
+    let full_path_str = full_path.to_string_lossy().to_string();
+    if let Err(e) = crate::db::provenance_operations::record_provenance(
+        db,
+        &options.target_project_path,
+        &full_path_str,
+        crate::db::provenance_operations::ProvenanceSource::Library {
+            workspace_id: &workspace.id,
+            catalog_id: &catalog.id,
+            variation_id: &variation.id,
+        },
+        &content_hash,
+    )
+    .await
+    {
+        tracing::warn!("Failed to record provenance for {}: {}", full_path_str, e);
+    }
+
     // Create or update subscription record
     let existing_subscription = library_subscription::Entity::find()
         .filter(library_subscription::Column::ResourceId.eq(&resource_id))
@@ -209,11 +289,133 @@ pub async fn pull_variation(
         subscription_id,
         file_path: relative_path,
         content_hash,
+        status: status.to_string(),
+        conflicts: Vec::new(),
+        merged_content: None,
     })
 }
 
+/// Pull several variations into one project, reporting each to `progress`.
+///
+/// A failing variation doesn't stop the others; its error is recorded in
+/// its [`BulkPullItem`]. Conflicting merges are reported the same way as in
+/// [`pull_variation`] and nothing is written for them. Once cancelled, the
+/// remaining variations are marked cancelled; those already pulled are kept.
+pub async fn pull_variations(
+    db: &DatabaseConnection,
+    variation_ids: Vec<String>,
+    target_project_id: &str,
+    target_project_path: &str,
+    overwrite_if_exists: bool,
+    progress: &ProgressReporter,
+) -> Vec<BulkPullItem> {
+    let total = variation_ids.len() as u64;
+    let mut items = Vec::with_capacity(variation_ids.len());
+
+    for (index, variation_id) in variation_ids.into_iter().enumerate() {
+        if progress.is_cancelled() {
+            items.push(BulkPullItem { variation_id, result: None, error: Some(CANCELLED_ERROR.to_string()) });
+            continue;
+        }
+        progress.report("pulling", index as u64, Some(total), variation_id.clone());
+
+        let options = PullOptions {
+            variation_id: variation_id.clone(),
+            target_project_id: target_project_id.to_string(),
+            target_project_path: target_project_path.to_string(),
+            overwrite_if_exists,
+            resolved_content: None,
+        };
+        let item = match pull_variation(db, options).await {
+            Ok(result) => BulkPullItem { variation_id, result: Some(result), error: None },
+            Err(e) => BulkPullItem { variation_id, result: None, error: Some(e) },
+        };
+        items.push(item);
+    }
+
+    items
+}
+
+/// The previously pulled version of a local file, used as the merge base.
+struct MergeBase {
+    resource_id: String,
+    subscription_id: String,
+    base_content: String,
+}
+
+/// Fetch the variation a local file was last pulled from.
+///
+/// Returns `None` if the file isn't subscribed to a variation (or the base
+/// can no longer be fetched), in which case there is nothing to merge against.
+async fn find_merge_base(
+    db: &DatabaseConnection,
+    github_client: &GitHubClient,
+    workspace: &library_workspace::Model,
+    project_id: &str,
+    relative_path: &str,
+) -> Result<Option<MergeBase>, String> {
+    let Some(resource) = library_resource::Entity::find()
+        .filter(library_resource::Column::ProjectId.eq(project_id))
+        .filter(library_resource::Column::RelativePath.eq(relative_path))
+        .one(db)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let Some(subscription) = library_subscription::Entity::find()
+        .filter(library_subscription::Column::ResourceId.eq(&resource.id))
+        .one(db)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let Some(base_variation) = library_variation::Entity::find_by_id(&subscription.variation_id)
+        .one(db)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    // The base must be fetched at the commit it was published in, since the
+    // path may since have been overwritten by newer variations
+    let Some(commit_sha) = base_variation.github_commit_sha.as_deref() else {
+        return Ok(None);
+    };
+
+    let base_content = match github_client
+        .get_file_contents_at_ref(
+            &workspace.github_owner,
+            &workspace.github_repo,
+            &base_variation.remote_path,
+            commit_sha,
+        )
+        .await
+    {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!("Failed to fetch merge base for {}: {}", relative_path, e);
+            return Ok(None);
+        }
+    };
+
+    if compute_content_hash(&base_content) != base_variation.content_hash {
+        return Ok(None);
+    }
+
+    Ok(Some(MergeBase {
+        resource_id: resource.id,
+        subscription_id: subscription.id,
+        base_content,
+    }))
+}
+
 /// Determine local file path based on artifact type.
-fn determine_local_path(artifact_type: &str, file_name: &str) -> String {
+pub(crate) fn determine_local_path(artifact_type: &str, file_name: &str) -> String {
     match artifact_type {
         "kit" => format!(".bluekit/kits/{}", file_name),
         "walkthrough" => format!(".bluekit/walkthroughs/{}", file_name),
@@ -256,7 +458,7 @@ fn extract_yaml_metadata(content: &str) -> Option<String> {
 
 /// Extract artifact type from markdown content's YAML front matter.
 /// Returns the value of the 'type' field if present (e.g., "kit", "walkthrough").
-fn extract_artifact_type_from_content(content: &str) -> Option<String> {
+pub(crate) fn extract_artifact_type_from_content(content: &str) -> Option<String> {
     let lines: Vec<&str> = content.lines().collect();
     if lines.is_empty() || lines[0] != "---" {
         return None;
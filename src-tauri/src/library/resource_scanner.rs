@@ -94,7 +94,7 @@ pub async fn scan_project_resources(
 }
 
 /// Collects all artifact files from .bluekit directory.
-fn collect_artifact_files(
+pub(crate) fn collect_artifact_files(
     bluekit_path: &Path,
     project_root: &Path,
     results: &mut Vec<(String, PathBuf)>, // (relative_path, absolute_path)
@@ -166,9 +166,9 @@ async fn process_artifact_file(
 
     // Get file metadata
     let file_name = absolute_path.file_name()
-        .and_then(|n| n.to_str())
         .ok_or("Invalid file name")?
-        .to_string();
+        .to_string_lossy()
+        .into_owned();
 
     let artifact_type = infer_artifact_type(relative_path);
 
@@ -6,6 +6,8 @@ use uuid::Uuid;
 use crate::db::entities::*;
 use crate::integrations::github::GitHubClient;
 use super::utils::compute_content_hash;
+use crate::core::dry_run::{DryRunRemoteFile, DryRunReport};
+use crate::core::progress::ProgressReporter;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SyncResult {
@@ -25,6 +27,7 @@ pub struct CatalogWithVariations {
 pub async fn sync_workspace_catalog(
     db: &DatabaseConnection,
     workspace_id: &str,
+    progress: &ProgressReporter,
 ) -> Result<SyncResult, String> {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -57,10 +60,15 @@ pub async fn sync_workspace_catalog(
         ".bluekit/diagrams",
     ];
 
-    for dir_path in artifact_dirs {
+    // Cancelling keeps what has been synced so far: each catalog and
+    // variation is written completely, and the next sync picks up the rest
+    let dir_count = artifact_dirs.len() as u64;
+    for (index, dir_path) in artifact_dirs.into_iter().enumerate() {
+        progress.check_cancelled()?;
         // For each directory, we'll need to list files
         // Using get_tree with main branch would work, but we need a simpler approach
         // For now, we'll use a recursive listing approach based on get_file_contents
+        progress.report("scanning", index as u64, Some(dir_count), format!("Scanning {}", dir_path));
 
         match sync_directory(
             db,
@@ -69,6 +77,7 @@ pub async fn sync_workspace_catalog(
             dir_path,
             now,
             &mut stats,
+            progress,
         )
         .await
         {
@@ -79,6 +88,7 @@ pub async fn sync_workspace_catalog(
             }
         }
     }
+    progress.check_cancelled()?;
 
     Ok(stats)
 }
@@ -92,6 +102,7 @@ async fn sync_directory(
     dir_path: &str,
     now: i64,
     stats: &mut SyncResult,
+    progress: &ProgressReporter,
 ) -> Result<(), String> {
     // GitHub contents API endpoint for directory
     // When called on a directory, it returns an array of items
@@ -113,11 +124,14 @@ async fn sync_directory(
     };
 
     // Process each file in the directory
-    for item in dir_items {
+    let file_count = dir_items.len() as u64;
+    for (index, item) in dir_items.into_iter().enumerate() {
         // Only process markdown files
         if item.item_type != "file" || !item.name.ends_with(".md") {
             continue;
         }
+        progress.check_cancelled()?;
+        progress.report("syncing", index as u64, Some(file_count), item.path.clone());
 
         // Get file contents
         let content = github_client
@@ -401,6 +415,58 @@ pub async fn delete_catalogs(
     Ok(deleted_count)
 }
 
+/// Lists what `delete_catalogs` would delete without touching anything.
+pub async fn preview_delete_catalogs(
+    db: &DatabaseConnection,
+    catalog_ids: &[String],
+) -> Result<DryRunReport, String> {
+    let mut report = DryRunReport::default();
+
+    for catalog_id in catalog_ids {
+        let catalog = library_catalog::Entity::find_by_id(catalog_id)
+            .one(db)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?
+            .ok_or_else(|| format!("Catalog not found: {}", catalog_id))?;
+
+        let workspace = library_workspace::Entity::find_by_id(&catalog.workspace_id)
+            .one(db)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?
+            .ok_or_else(|| format!("Workspace not found: {}", catalog.workspace_id))?;
+
+        report.deleted_remote_files.push(DryRunRemoteFile {
+            repository: format!("{}/{}", workspace.github_owner, workspace.github_repo),
+            path: catalog.remote_path.clone(),
+        });
+        report.delete_row("library_catalogs", &catalog.id, &catalog.name);
+
+        // Rows removed by ON DELETE CASCADE
+        let variations = library_variation::Entity::find()
+            .filter(library_variation::Column::CatalogId.eq(catalog_id))
+            .all(db)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+        for variation in variations {
+            let label = variation
+                .version_tag
+                .unwrap_or_else(|| variation.content_hash.chars().take(8).collect());
+            report.delete_row("library_variations", &variation.id, &label);
+        }
+
+        let subscriptions = library_subscription::Entity::find()
+            .filter(library_subscription::Column::CatalogId.eq(catalog_id))
+            .all(db)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+        for subscription in subscriptions {
+            report.delete_row("library_subscriptions", &subscription.id, &subscription.project_id);
+        }
+    }
+
+    Ok(report)
+}
+
 /// Extract metadata from markdown content (YAML front matter).
 /// Returns: (name, description, tags, artifact_type)
 fn extract_metadata_from_content(content: &str) -> (String, Option<String>, Option<String>, Option<String>) {
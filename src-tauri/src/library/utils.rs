@@ -1,4 +1,7 @@
 use sha2::{Sha256, Digest};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
 
 /// Computes SHA-256 hash of content for duplicate detection and change tracking.
 ///
@@ -10,6 +13,29 @@ pub fn compute_content_hash(content: &str) -> String {
     format!("{:x}", result)
 }
 
+/// Copies a file in fixed-size chunks, hashing it on the way through.
+///
+/// Returns the same hash `compute_content_hash` gives for the file's text,
+/// without ever holding the whole file in memory.
+pub fn copy_file_with_hash(source: &Path, target: &Path) -> io::Result<String> {
+    let mut reader = File::open(source)?;
+    let mut writer = File::create(target)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+    }
+    writer.flush()?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
 /// Extracts artifact type from file path based on directory structure.
 ///
 /// Examples:
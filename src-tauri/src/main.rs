@@ -27,6 +27,12 @@ use tauri::Manager;
 /// 4. Runs the application, which opens the window and starts the event loop
 #[tokio::main]
 async fn main() {
+    // Headless mode (e.g. `bluekit-app validate --staged` from a git hook)
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(exit_code) = core::validation::run_cli(&args) {
+        std::process::exit(exit_code);
+    }
+
     // Load environment variables from .env file (for development)
     #[cfg(debug_assertions)]
     {
@@ -61,47 +67,110 @@ async fn main() {
             commands::get_app_info,      // Returns app metadata
             commands::example_error,      // Demonstrates error handling
             commands::get_project_artifacts,  // Get all artifacts from .bluekit directory
+            commands::get_project_artifacts_page, // Paginated, filtered artifact listing
+            commands::get_project_stats, // Artifact counts, sizes and tag frequency
             commands::get_bluekit_file_tree, // Get recursive file tree of .bluekit directory
             commands::create_folder, // Create folder
             commands::get_changed_artifacts, // Get only changed artifacts (incremental updates)
+            commands::search_project_artifacts, // Ranked full-text search across .bluekit
+            commands::search_all_projects, // Full-text search across every project's .bluekit
+            commands::get_artifact_links, // Local links in an artifact
+            commands::get_artifact_backlinks, // Artifacts linking to an artifact
+            commands::resolve_wikilink, // Resolve [[wikilink]] text to an artifact
+            commands::resolve_wikilinks, // Resolve many wikilinks at once
+            commands::check_broken_links, // Find links to missing files, with suggested fixes
             commands::watch_project_artifacts, // Watch project .bluekit directory for artifact changes
             commands::watch_projects_database, // Watch projects database for changes
             commands::read_file,        // Read file contents
+            commands::read_file_with_encoding, // Read file contents with detected encoding
+            commands::get_artifact_read_stats, // Word count and reading time of an artifact
+            commands::diff_files, // Unified diff and hunks between two artifacts
+            commands::read_file_binary, // Read a binary file (e.g. image asset) as base64
+            commands::read_file_stream, // Stream a large file in chunks via events
+            commands::copy_artifact_to_clipboard, // Copy an artifact to the system clipboard
             commands::write_file,       // Write file contents
-            commands::copy_kit_to_project, // Copy kit file to project
-            commands::copy_walkthrough_to_project, // Copy walkthrough file to project
-            commands::copy_diagram_to_project, // Copy diagram file to project
+            commands::get_file_version, // Get a file's hash and mtime
+            commands::write_file_checked, // Write a file unless it changed on disk
+            commands::copy_resources_to_project, // Copy kits, walkthroughs and diagrams to project
             commands::copy_blueprint_to_project, // Copy blueprint directory to project
             commands::get_scrapbook_items, // Get scrapbook folders and files
+            commands::create_scrapbook_folder, // Create a scrapbook folder
+            commands::rename_scrapbook_folder, // Rename a scrapbook folder
+            commands::move_scrapbook_item, // Move a note between scrapbook folders
+            commands::delete_scrapbook_folder, // Trash a scrapbook folder
             commands::get_folder_markdown_files, // Get markdown files from a folder
             commands::get_plans_files, // Get plan files from ~/.claude/plans or ~/.cursor/plans
+            commands::watch_external_plans, // Watch ~/.claude/plans and ~/.cursor/plans
             commands::get_blueprints, // Get blueprints from .bluekit/blueprints directory
             commands::get_blueprint_task_file, // Get task file content from blueprint
             commands::get_project_diagrams, // Get diagrams from .bluekit/diagrams directory
             commands::get_project_clones, // Get clones from .bluekit/clones.json
+            commands::create_clone, // Record project's git state in clones.json
+            commands::update_clone_metadata, // Edit clone name, description and tags
+            commands::delete_clone, // Remove clone from clones.json
             commands::create_project_from_clone, // Create project from clone
             commands::clone_from_github, // Clone from GitHub
             commands::create_new_project, // Create new project with files
             commands::get_watcher_health, // Get health status of all active file watchers
+            commands::get_app_health, // Aggregate diagnostics report
+            commands::lint_artifacts, // Markdown lint for the project health tab
+            commands::validate_diagram, // Mermaid syntax check with line/column errors
+            commands::validate_artifact_metadata, // Front matter schema check per artifact type
+            commands::generate_progress_report, // Markdown progress report for a period
+            commands::export_project_artifacts, // Zip a project's .bluekit directory
+            commands::import_artifacts_from_archive, // Import artifacts from a zip archive
+            commands::bootstrap_starter_content, // Seed starter project on first run
             commands::stop_watcher, // Stop a file watcher by event name
             commands::db_get_tasks, // Get all tasks (database)
+            commands::db_search_tasks, // Full-text search over tasks
+            commands::global_search, // Search tasks, plans, milestones, walkthroughs and artifacts at once
             commands::db_get_project_tasks, // Get tasks for a project (database)
             commands::db_get_task, // Get a single task (database)
             commands::db_create_task, // Create a new task (database)
             commands::db_update_task, // Update a task (database)
             commands::db_delete_task, // Delete a task (database)
+            commands::db_restore_task, // Restore a deleted task
+            commands::db_get_deleted_tasks, // Deleted tasks that can be restored
+            commands::db_bulk_update_tasks, // Patch several tasks at once
+            commands::db_bulk_delete_tasks, // Delete several tasks at once
+            commands::db_list_saved_views, // List saved task views
+            commands::db_create_saved_view, // Save a named task filter
+            commands::db_update_saved_view, // Update a saved task view
+            commands::db_delete_saved_view, // Delete a saved task view
+            commands::db_get_tasks_for_view, // Run a saved task view
+            commands::db_reorder_tasks, // Save task board order
+            commands::db_get_task_activity, // Task change history
+            commands::db_get_overdue_tasks, // Tasks past their due date
+            commands::db_get_upcoming_tasks, // Tasks due soon
             commands::delete_resources, // Delete resource files
+            commands::list_trash, // List trashed artifacts
+            commands::restore_resource, // Restore a trashed artifact
+            commands::empty_trash, // Permanently delete trashed artifacts
+            commands::attach_asset, // Copy a file into an artifact's asset folder and link it
+            commands::list_artifact_assets, // Files attached to an artifact
+            commands::delete_asset, // Trash an attached asset
+            commands::clean_orphaned_assets, // Trash attachments nothing links to
             commands::update_resource_metadata, // Update resource metadata
+            commands::bulk_update_resource_metadata, // Update tags, aliases and descriptions of many resources
+            commands::get_all_tags, // Tags used across a project's artifacts and tasks
+            commands::rename_tag, // Rename a tag project-wide
+            commands::delete_tag, // Remove a tag project-wide
             commands::get_artifact_folders, // Get folders in artifact directory
             commands::create_artifact_folder, // Create new folder with config.json
             commands::update_folder_config, // Update folder config.json
             commands::delete_artifact_folder, // Delete folder and contents
             commands::rename_artifact_folder, // Rename folder
             commands::move_artifact_to_folder, // Move artifact into folder
+            commands::move_artifact_between_projects, // Move artifact into another project with its DB records
+            commands::move_resource, // Move artifact between .bluekit folders
+            commands::rename_resource, // Rename artifact and update references to it
+            commands::link_artifact_to_project, // Link a shared artifact into a project via a stub
+            commands::unlink_artifact, // Replace a link stub with a standalone copy
             commands::move_folder_to_folder, // Move folder into folder (nesting)
             commands::open_project_in_editor, // Open project in Cursor or VSCode
             commands::open_in_terminal, // Open directory in Terminal
-            commands::open_file_in_editor, // Open file in Cursor or VSCode
+            commands::open_file_in_editor, // Open a file in the chosen or configured editor
+            commands::reveal_in_file_manager, // Show a file in Finder/Explorer
             commands::open_html_in_browser, // Open HTML content in browser
             commands::open_resource_in_window, // Open resource in new Tauri window
             commands::close_preview_window, // Close preview window
@@ -136,14 +205,27 @@ async fn main() {
             commands::check_publish_status, // Check publish status (Phase 3)
             // Library publishing commands (now use tokens from Supabase)
             commands::publish_resource, // Publish resource to GitHub
+            commands::publish_resources, // Publish several resources with progress events
             commands::sync_workspace_catalog, // Sync workspace catalog
             commands::list_workspace_catalogs, // List workspace catalogs
+            commands::get_workspace_activity, // Workspace activity feed
+            commands::browse_public_libraries, // Discover public libraries on GitHub
+            commands::pull_public_artifact, // Pull from a public library
             commands::delete_catalogs, // Delete catalogs
             commands::pull_variation, // Pull variation to project
+            commands::pull_variations, // Pull several variations with progress events
             commands::check_resource_status, // Check resource publish status
             commands::check_project_for_updates, // Check for resource updates
             commands::migrate_projects_to_database, // Migrate JSON to database (Phase 1)
+            commands::validate_project_registry, // Check and repair projectRegistry.json
+            commands::remove_project_from_registry, // Remove entry from projectRegistry.json
+            commands::update_project_entry, // Update entry in projectRegistry.json
+            commands::sync_project_registry, // Sync projectRegistry.json with the database
+            commands::get_projects, // Canonical project list (syncs the registry first)
             commands::db_get_projects, // Get all projects from database (Phase 1)
+            commands::reorder_projects, // Persist manual project order
+            commands::archive_project, // Hide project from listings without deleting it
+            commands::unarchive_project, // Restore archived project to listings
             commands::db_create_project, // Create new project in database (Phase 1)
             commands::db_update_project, // Update project in database (Phase 1)
             commands::db_delete_project, // Delete project from database (Phase 1)
@@ -151,6 +233,7 @@ async fn main() {
             commands::connect_project_git, // Connect project to git (Phase 1)
             commands::disconnect_project_git, // Disconnect project from git (Phase 1)
             commands::list_project_worktrees, // List git worktrees for project
+            commands::install_git_hooks, // Install pre-commit artifact validation hook
             commands::open_worktree_in_window, // Open worktree in new window
             // Commit commands (now use tokens from Supabase)
             commands::fetch_project_commits, // Fetch commits from GitHub
@@ -160,18 +243,32 @@ async fn main() {
             commands::pin_checkpoint, // Pin commit as checkpoint (Phase 3)
             commands::get_project_checkpoints, // Get project checkpoints (Phase 3)
             commands::unpin_checkpoint, // Unpin checkpoint (Phase 3)
+            commands::create_checkpoint, // Pin current commit as checkpoint
+            commands::list_checkpoints, // List project checkpoints
+            commands::update_checkpoint, // Update checkpoint metadata
+            commands::delete_checkpoint, // Delete checkpoint
+            commands::restore_checkpoint, // Restore checkpoint into a new working copy
+            commands::diff_checkpoints, // Diff two checkpoints (or checkpoint vs HEAD)
             commands::create_project_from_checkpoint, // Create project from checkpoint (Phase 3)
             commands::create_plan, // Create a new plan
+            commands::import_plan_from_markdown, // Create plan from a markdown outline
             commands::get_project_plans, // Get all plans for a project
+            commands::generate_plan_diagram, // Render plan as a mermaid diagram
+            commands::export_plan_markdown, // Export plan as a standalone markdown document
+            commands::get_plan_timeline, // Plan phase windows and milestone trend for charts
             commands::get_plan_details, // Get plan details with phases and milestones
             commands::update_plan, // Update a plan
+            commands::duplicate_plan, // Copy a plan with its folder and structure
             commands::delete_plan, // Delete a plan
+            commands::restore_plan, // Restore a deleted plan
+            commands::get_deleted_plans, // Deleted plans that can be restored
             commands::link_brainstorm_to_plan, // Link brainstorm file to plan
             commands::unlink_brainstorm_from_plan, // Unlink brainstorm from plan
             commands::link_multiple_plans_to_plan, // Link multiple plans to a plan
             commands::unlink_plan_from_plan, // Unlink a specific plan from a plan
             commands::create_plan_phase, // Create a plan phase
             commands::update_plan_phase, // Update a plan phase
+            commands::get_at_risk_phases, // Incomplete phases past their target date
             commands::delete_plan_phase, // Delete a plan phase
             commands::reorder_plan_phases, // Reorder plan phases
             commands::create_plan_milestone, // Create a plan milestone
@@ -180,30 +277,79 @@ async fn main() {
             commands::toggle_milestone_completion, // Toggle milestone completion
             commands::get_plan_documents, // Get plan documents
             commands::link_document_to_phase, // Link document to phase
+            commands::move_document_to_phase, // Move a document within or between phases
             commands::reorder_plan_documents, // Reorder plan documents
             commands::watch_plan_folder, // Watch plan folder for changes
+            commands::watch_plan_links, // Watch linked external plan files
             commands::create_walkthrough, // Create a new walkthrough
             commands::get_project_walkthroughs, // Get all walkthroughs for a project
             commands::get_or_create_walkthrough_by_path, // Get or create walkthrough by file path
             commands::get_walkthrough_details, // Get walkthrough details
             commands::update_walkthrough, // Update a walkthrough
             commands::delete_walkthrough, // Delete a walkthrough
+            commands::restore_walkthrough, // Restore a deleted walkthrough
+            commands::get_deleted_walkthroughs, // Deleted walkthroughs that can be restored
             commands::add_walkthrough_takeaway, // Add takeaway
             commands::toggle_takeaway_complete, // Toggle takeaway completion
             commands::update_walkthrough_takeaway, // Update takeaway
             commands::delete_walkthrough_takeaway, // Delete takeaway
             commands::reorder_walkthrough_takeaways, // Reorder takeaways
+            commands::convert_takeaway_to_task, // Create a linked task from a takeaway
             commands::get_walkthrough_notes, // Get walkthrough notes
             commands::add_walkthrough_note, // Add walkthrough note
             commands::update_walkthrough_note, // Update walkthrough note
             commands::delete_walkthrough_note, // Delete walkthrough note
+            commands::attach_walkthrough_note_file, // Copy a file into a note's attachment folder
+            commands::delete_walkthrough_note_attachment, // Delete a note attachment
+            commands::get_walkthrough_steps, // Get walkthrough steps
+            commands::add_walkthrough_step, // Add a step anchored to source lines
+            commands::update_walkthrough_step, // Update walkthrough step
+            commands::delete_walkthrough_step, // Delete walkthrough step
+            commands::reorder_walkthrough_steps, // Reorder walkthrough steps
+            commands::reanchor_walkthrough_steps, // Follow steps' lines to HEAD via git blame
+            commands::sync_walkthrough_front_matter, // Write status and progress into the walkthrough file
+            commands::get_walkthrough_diagrams, // Mermaid sources to render for an export
+            commands::export_walkthrough_html, // Export walkthrough as a self-contained HTML guide
+            commands::create_kit, // Create a new kit
+            commands::create_agent, // Create a new agent
+            commands::create_diagram, // Create a new diagram
+            commands::get_kit_usage, // Get projects a kit has been copied into
+            commands::check_outdated_copies, // Flag copies whose origin has newer content
+            commands::get_project_agents, // Get all agents for a project
+            commands::validate_agent, // Validate agent front matter and prompt
+            commands::export_agent, // Export agent to Claude/Cursor format
             commands::get_bookmarks, // Get bookmarks from .bluekit/bookmarks.json
             commands::save_bookmarks, // Save bookmarks to .bluekit/bookmarks.json
             commands::add_bookmark, // Add a bookmark to the root
             commands::remove_bookmark, // Remove a bookmark by file path
             commands::reconcile_bookmarks, // Prune invalid bookmark paths
+            commands::reconcile_project, // Find (and optionally fix) orphaned records and files
             commands::start_supabase_auth_server, // Start Supabase OAuth callback server
             commands::stop_supabase_auth_server, // Stop Supabase OAuth callback server
+            commands::get_app_settings, // Get application settings
+            commands::update_app_settings, // Save application settings
+            commands::get_data_directory, // Data directory in use and configured
+            commands::preview_data_directory_change, // What moving the data would involve
+            commands::set_data_directory, // Move or switch the data directory
+            commands::run_command, // Run a shell command in a terminal session
+            commands::kill_command, // Kill a running terminal session
+            commands::write_terminal_input, // Send input to a terminal session
+            commands::list_terminal_sessions, // List running terminal sessions
+            commands::list_scheduled_jobs, // List scheduled jobs
+            commands::create_scheduled_job, // Create a recurring job
+            commands::update_scheduled_job, // Update a scheduled job
+            commands::delete_scheduled_job, // Delete a scheduled job
+            commands::run_scheduled_job_now, // Run a scheduled job immediately
+            commands::cancel_operation, // Cancel a long-running operation
+            commands::save_session_state, // Persist open project and artifacts
+            commands::get_session_state, // Restore the last session
+            commands::db_maintenance, // Integrity check, VACUUM and ANALYZE
+            commands::get_schema_version, // Applied and pending migrations
+            commands::get_database_encryption_status, // SQLCipher availability and lock state
+            commands::enable_database_encryption, // Encrypt the database from the next launch
+            commands::disable_database_encryption, // Decrypt the database from the next launch
+            commands::lock_database, // Close the encrypted database
+            commands::unlock_database, // Reopen it with the keychain key
         ])
         .setup(|app| {
             // Initialize database synchronously before app starts accepting commands
@@ -222,7 +368,8 @@ async fn main() {
 
             app.manage(db.clone());
 
-            // Auto-migrate projects on startup if projectRegistry.json exists
+            // Auto-migrate projects on startup if projectRegistry.json exists,
+            // then bring the registry in line with the database
             let db_clone = db.clone();
             tauri::async_runtime::spawn(async move {
                 match crate::db::project_operations::migrate_json_to_database(&db_clone).await {
@@ -237,12 +384,19 @@ async fn main() {
                     }
                     Err(e) => tracing::warn!("Auto-migration failed: {}", e),
                 }
+                if let Err(e) = crate::db::project_operations::sync_registry(&db_clone).await {
+                    tracing::warn!("Project registry sync failed: {}", e);
+                }
             });
 
             // Initialize and register artifact cache
             use crate::core::cache::ArtifactCache;
             app.manage(ArtifactCache::new());
 
+            // Initialize and register the artifact search index
+            app.manage(crate::core::search_index::SearchIndex::new());
+            app.manage(crate::core::link_graph::LinkGraph::new());
+
             // Initialize OAuth state management (state -> code_verifier mapping)
             use std::collections::HashMap;
             use std::sync::{Arc, Mutex};
@@ -252,6 +406,19 @@ async fn main() {
             use crate::integrations::github::CommitCache;
             app.manage(CommitCache::new());
 
+            // Initialize and register terminal session registry
+            use crate::core::terminal::TerminalSessions;
+            app.manage(TerminalSessions::new());
+
+            // Start the recurring job scheduler
+            crate::core::scheduler::start(app.handle(), db.clone());
+
+            // Fire task reminders
+            crate::core::reminders::start(app.handle(), db.clone());
+
+            // Vacuum and analyze a large, fragmented database in the background
+            crate::db::maintenance::start_if_needed(db.clone());
+
             // Register cleanup handler for app shutdown
             let window = app.get_window("main").expect("Failed to get main window");
 
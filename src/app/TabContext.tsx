@@ -275,7 +275,7 @@ export function TabProvider({ children }: { children: ReactNode }) {
 
     // Fetch projects to find path
     try {
-      const projects = await invokeDbGetProjects();
+      const projects = await invokeDbGetProjects(true);
       const project = projects.find((p) => p.id === projectId);
       if (project) {
         projectPathsRef.current.set(projectId, project.path);
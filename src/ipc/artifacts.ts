@@ -3,93 +3,96 @@
  */
 
 import { invokeWithTimeout } from '@/shared/utils/ipcTimeout';
-import type { ArtifactFile, Blueprint, ScrapbookItem, CloneMetadata } from './types';
+import type { ArtifactFile, Blueprint, ScrapbookItem, CloneMetadata, ResourceCopyRequest, ResourceCopyResult, ConflictPolicy, BlueprintCopyResult, BulkMetadataUpdate, BulkMetadataResult, TagUsage, TagChangeResult, TrashEntry, AttachedAsset, ArtifactAsset, DryRunReport, ArtifactExportResult, ArtifactImportResult, DiagramValidation, MetadataValidation, ProjectStats, ArtifactLink, Backlink, WikilinkResolution, BrokenLinkReport, CreatedArtifact, Agent, AgentValidation, AgentExport, AgentExportFormat, KitUsage, OutdatedCopy, MoveArtifactResult, ClipboardOptions, LintReport, RenameResourceResult } from './types';
 
 /**
- * Copies a kit file to a project's .bluekit directory.
- * 
- * This command reads the source kit file and writes it to the target project's
- * .bluekit/kits directory. It creates the directory structure if it doesn't exist.
- * 
- * @param sourceFilePath - The absolute path to the source kit file
+ * Copies kits, walkthroughs and diagrams to a project's .bluekit directory in one call.
+ *
+ * Each file goes to the subdirectory for its type (.bluekit/kits, .bluekit/walkthroughs
+ * or .bluekit/diagrams). Failures are reported per file, so one bad file doesn't stop
+ * the rest.
+ *
+ * @param resources - Source paths with their artifact types
  * @param targetProjectPath - The absolute path to the target project root directory
- * @returns A promise that resolves to the path of the copied file
- * 
+ * @param onConflict - What to do when a target file exists (defaults to 'overwrite')
+ * @returns A promise that resolves to one result per resource, in order
+ *
  * @example
  * ```typescript
- * const result = await invokeCopyKitToProject(
- *   '/path/to/source/kit.md',
- *   '/path/to/target/project'
+ * const results = await invokeCopyResourcesToProject(
+ *   [{ sourcePath: '/path/to/source/kit.md', artifactType: 'kit' }],
+ *   '/path/to/target/project',
+ *   'skip'
  * );
- * console.log(result); // "/path/to/target/project/.bluekit/kits/kit.md"
+ * const failed = results.filter(r => r.error);
+ * const skipped = results.filter(r => r.action === 'skipped');
  * ```
  */
+export async function invokeCopyResourcesToProject(
+  resources: ResourceCopyRequest[],
+  targetProjectPath: string,
+  onConflict?: ConflictPolicy,
+): Promise<ResourceCopyResult[]> {
+  return await invokeWithTimeout<ResourceCopyResult[]>('copy_resources_to_project', {
+    resources,
+    targetProjectPath,
+    onConflict,
+  });
+}
+
+// Copies a single file, throwing its error like the old per-type commands did
+async function copySingleResource(
+  sourcePath: string,
+  artifactType: ResourceCopyRequest['artifactType'],
+  targetProjectPath: string,
+): Promise<string> {
+  const [result] = await invokeCopyResourcesToProject([{ sourcePath, artifactType }], targetProjectPath);
+  if (!result?.targetPath) {
+    throw new Error(result?.error ?? `Failed to copy ${sourcePath}`);
+  }
+  return result.targetPath;
+}
+
+/**
+ * Copies a kit file to a project's .bluekit/kits directory.
+ *
+ * @param sourceFilePath - The absolute path to the source kit file
+ * @param targetProjectPath - The absolute path to the target project root directory
+ * @returns A promise that resolves to the path of the copied file
+ */
 export async function invokeCopyKitToProject(
   sourceFilePath: string,
   targetProjectPath: string,
 ): Promise<string> {
-  return await invokeWithTimeout<string>('copy_kit_to_project', {
-    sourceFilePath,
-    targetProjectPath,
-  });
+  return await copySingleResource(sourceFilePath, 'kit', targetProjectPath);
 }
 
 /**
- * Copies a walkthrough file to a project's .bluekit directory.
- * 
- * This command reads the source walkthrough file and writes it to the target project's
- * .bluekit/walkthroughs directory. It creates the directory structure if it doesn't exist.
- * 
+ * Copies a walkthrough file to a project's .bluekit/walkthroughs directory.
+ *
  * @param sourceFilePath - The absolute path to the source walkthrough file
  * @param targetProjectPath - The absolute path to the target project root directory
  * @returns A promise that resolves to the path of the copied file
- * 
- * @example
- * ```typescript
- * const result = await invokeCopyWalkthroughToProject(
- *   '/path/to/source/walkthrough.md',
- *   '/path/to/target/project'
- * );
- * console.log(result); // "/path/to/target/project/.bluekit/walkthroughs/walkthrough.md"
- * ```
  */
 export async function invokeCopyWalkthroughToProject(
   sourceFilePath: string,
   targetProjectPath: string,
 ): Promise<string> {
-  return await invokeWithTimeout<string>('copy_walkthrough_to_project', {
-    sourceFilePath,
-    targetProjectPath,
-  });
+  return await copySingleResource(sourceFilePath, 'walkthrough', targetProjectPath);
 }
 
 /**
- * Copies a diagram file to a project's .bluekit directory.
- * 
- * This command reads the source diagram file (.mmd or .mermaid) and writes it to the target project's
- * .bluekit/diagrams directory. It creates the directory structure if it doesn't exist.
- * 
+ * Copies a diagram file (.mmd or .mermaid) to a project's .bluekit/diagrams directory.
+ *
  * @param sourceFilePath - The absolute path to the source diagram file
  * @param targetProjectPath - The absolute path to the target project root directory
  * @returns A promise that resolves to the path of the copied file
- * 
- * @example
- * ```typescript
- * const result = await invokeCopyDiagramToProject(
- *   '/path/to/source/diagram.mmd',
- *   '/path/to/target/project'
- * );
- * console.log(result); // "/path/to/target/project/.bluekit/diagrams/diagram.mmd"
- * ```
  */
 export async function invokeCopyDiagramToProject(
   sourceFilePath: string,
   targetProjectPath: string,
 ): Promise<string> {
-  return await invokeWithTimeout<string>('copy_diagram_to_project', {
-    sourceFilePath,
-    targetProjectPath,
-  });
+  return await copySingleResource(sourceFilePath, 'diagram', targetProjectPath);
 }
 
 /**
@@ -100,7 +103,8 @@ export async function invokeCopyDiagramToProject(
  *
  * @param sourceBlueprintPath - The absolute path to the source blueprint directory
  * @param targetProjectPath - The absolute path to the target project root directory
- * @returns A promise that resolves to the path of the copied blueprint directory
+ * @param onConflict - What to do when the blueprint already exists (defaults to 'overwrite')
+ * @returns A promise that resolves to the copied blueprint directory and what happened to each file
  *
  * @example
  * ```typescript
@@ -108,16 +112,18 @@ export async function invokeCopyDiagramToProject(
  *   '/path/to/source/blueprint',
  *   '/path/to/target/project'
  * );
- * console.log(result); // "/path/to/target/project/.bluekit/blueprints/blueprint-name"
+ * console.log(result.targetPath); // "/path/to/target/project/.bluekit/blueprints/blueprint-name"
  * ```
  */
 export async function invokeCopyBlueprintToProject(
   sourceBlueprintPath: string,
   targetProjectPath: string,
-): Promise<string> {
-  return await invokeWithTimeout<string>('copy_blueprint_to_project', {
+  onConflict?: ConflictPolicy,
+): Promise<BlueprintCopyResult> {
+  return await invokeWithTimeout<BlueprintCopyResult>('copy_blueprint_to_project', {
     sourceBlueprintPath,
     targetProjectPath,
+    onConflict,
   });
 }
 
@@ -146,23 +152,97 @@ export async function invokeGetScrapbookItems(projectPath: string): Promise<Scra
   return await invokeWithTimeout<ScrapbookItem[]>('get_scrapbook_items', { projectPath });
 }
 
+/**
+ * Creates a scrapbook folder in the project's .bluekit directory.
+ *
+ * Artifact type folders (kits, walkthroughs, ...), `assets` and hidden names are rejected.
+ *
+ * @param projectPath - The path to the project root directory
+ * @param name - The folder name
+ * @returns A promise that resolves to the new folder
+ */
+export async function invokeCreateScrapbookFolder(projectPath: string, name: string): Promise<ScrapbookItem> {
+  return await invokeWithTimeout<ScrapbookItem>('create_scrapbook_folder', { projectPath, name });
+}
+
+/**
+ * Renames a scrapbook folder.
+ *
+ * @param projectPath - The path to the project root directory
+ * @param folderPath - The folder to rename
+ * @param newName - Its new name
+ * @returns A promise that resolves to the renamed folder
+ */
+export async function invokeRenameScrapbookFolder(
+  projectPath: string,
+  folderPath: string,
+  newName: string
+): Promise<ScrapbookItem> {
+  return await invokeWithTimeout<ScrapbookItem>('rename_scrapbook_folder', { projectPath, folderPath, newName });
+}
+
+/**
+ * Moves a loose note into a scrapbook folder, or back to the top of .bluekit
+ * when `targetFolderPath` is omitted. Fails if a note with the same name is already there.
+ *
+ * @param projectPath - The path to the project root directory
+ * @param itemPath - The note to move
+ * @param targetFolderPath - The scrapbook folder to move it into
+ * @returns A promise that resolves to the note's new path
+ */
+export async function invokeMoveScrapbookItem(
+  projectPath: string,
+  itemPath: string,
+  targetFolderPath?: string
+): Promise<string> {
+  return await invokeWithTimeout<string>('move_scrapbook_item', { projectPath, itemPath, targetFolderPath });
+}
+
+/**
+ * Deletes a scrapbook folder and its notes, moving it to the project's trash
+ * unless `permanent` is set.
+ *
+ * @param projectPath - The path to the project root directory
+ * @param folderPath - The folder to delete
+ * @param permanent - Delete instead of moving to the trash
+ */
+export async function invokeDeleteScrapbookFolder(
+  projectPath: string,
+  folderPath: string,
+  permanent?: boolean
+): Promise<void> {
+  return await invokeWithTimeout<void>('delete_scrapbook_folder', { projectPath, folderPath, permanent });
+}
+
 /**
  * Gets markdown files from a specific folder in the .bluekit directory.
  *
+ * Only the folder itself is listed unless `recursive` is set or `maxDepth` is given.
+ * `maxDepth` is how many levels of subfolders to include (unlimited when `recursive`
+ * is set without it). Hidden subfolders are skipped.
+ *
  * @param folderPath - The absolute path to the folder
+ * @param options - Whether and how deep to include subfolders
  * @returns A promise that resolves to an array of ArtifactFile objects
  *
  * @example
  * ```typescript
- * const files = await invokeGetFolderMarkdownFiles('/path/to/project/.bluekit/custom');
+ * const files = await invokeGetFolderMarkdownFiles('/path/to/project/.bluekit/custom', { recursive: true });
  * files.forEach(file => {
  *   console.log(file.name); // "my-file" (without .md extension)
- *   console.log(file.path); // "/path/to/project/.bluekit/custom/my-file.md"
+ *   console.log(file.relativePath); // "nested/my-file.md"
  * });
  * ```
  */
-export async function invokeGetFolderMarkdownFiles(folderPath: string): Promise<ArtifactFile[]> {
-  return await invokeWithTimeout<ArtifactFile[]>('get_folder_markdown_files', { folderPath });
+export async function invokeGetFolderMarkdownFiles(
+  folderPath: string,
+  options: { recursive?: boolean; maxDepth?: number } = {}
+): Promise<ArtifactFile[]> {
+  return await invokeWithTimeout<ArtifactFile[]>('get_folder_markdown_files', {
+    folderPath,
+    recursive: options.recursive,
+    maxDepth: options.maxDepth,
+  });
 }
 
 /**
@@ -268,13 +348,79 @@ export async function invokeGetProjectClones(projectPath: string): Promise<Clone
   return await invokeWithTimeout<CloneMetadata[]>('get_project_clones', { projectPath });
 }
 
+/**
+ * Records the project's current git state as a new clone in .bluekit/clones.json.
+ *
+ * The ID is generated as `slugified-name-YYYYMMDD`. The project must be a git
+ * repository with an `origin` remote.
+ *
+ * @param projectPath - The path to the project root directory
+ * @param name - Display name for the clone
+ * @param description - What this clone represents
+ * @param tags - Optional tags for categorization
+ * @returns A promise that resolves to the new CloneMetadata entry
+ *
+ * @example
+ * ```typescript
+ * const clone = await invokeCreateClone('/path/to/project', 'BlueKit Foundation', 'Base setup');
+ * console.log(clone.id); // "bluekit-foundation-20250101"
+ * ```
+ */
+export async function invokeCreateClone(
+  projectPath: string,
+  name: string,
+  description: string,
+  tags?: string[]
+): Promise<CloneMetadata> {
+  return await invokeWithTimeout<CloneMetadata>('create_clone', {
+    projectPath,
+    name,
+    description,
+    tags,
+  });
+}
+
+/**
+ * Updates a clone's name, description and/or tags in .bluekit/clones.json.
+ * The clone ID never changes. Omitted fields are left unchanged.
+ *
+ * @param projectPath - The path to the project root directory
+ * @param cloneId - ID of the clone to update
+ * @param updates - New name, description and/or tags
+ * @returns A promise that resolves to the updated CloneMetadata entry
+ */
+export async function invokeUpdateCloneMetadata(
+  projectPath: string,
+  cloneId: string,
+  updates: { name?: string; description?: string; tags?: string[] }
+): Promise<CloneMetadata> {
+  return await invokeWithTimeout<CloneMetadata>('update_clone_metadata', {
+    projectPath,
+    cloneId,
+    ...updates,
+  });
+}
+
+/**
+ * Removes a clone from .bluekit/clones.json.
+ *
+ * @param projectPath - The path to the project root directory
+ * @param cloneId - ID of the clone to remove
+ * @returns A promise that resolves when the clone is removed
+ */
+export async function invokeDeleteClone(projectPath: string, cloneId: string): Promise<void> {
+  return await invokeWithTimeout<void>('delete_clone', { projectPath, cloneId });
+}
+
 /**
  * Delete resource files from the filesystem.
  *
  * This command deletes one or more resource files (kits, walkthroughs, agents, diagrams).
  * All paths are validated to be within `.bluekit` directories for safety.
+ * Files are moved to the project's trash unless `permanent` is set.
  *
  * @param filePaths - Array of absolute file paths to delete
+ * @param permanent - Delete outright instead of moving to the trash
  * @returns Promise that resolves when all files are deleted
  * @throws Error if any deletions fail
  *
@@ -286,8 +432,95 @@ export async function invokeGetProjectClones(projectPath: string): Promise<Clone
  * ]);
  * ```
  */
-export async function deleteResources(filePaths: string[]): Promise<void> {
-  return await invokeWithTimeout<void>('delete_resources', { filePaths }, 10000);
+export async function deleteResources(filePaths: string[], permanent = false): Promise<void> {
+  return await invokeWithTimeout<void>('delete_resources', { filePaths, permanent }, 10000);
+}
+
+/**
+ * Lists a project's trashed artifacts, most recently deleted first.
+ *
+ * @param projectPath - Path to the project root directory
+ * @returns Promise resolving to the trash entries
+ */
+export async function listTrash(projectPath: string): Promise<TrashEntry[]> {
+  return await invokeWithTimeout<TrashEntry[]>('list_trash', { projectPath });
+}
+
+/**
+ * Restores a trashed artifact to its original location.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param trashId - Id of the entry from `listTrash`
+ * @returns Promise resolving to the restored file path
+ * @throws Error if a file already exists at the original path
+ */
+export async function restoreResource(projectPath: string, trashId: string): Promise<string> {
+  return await invokeWithTimeout<string>('restore_resource', { projectPath, trashId });
+}
+
+/**
+ * Permanently deletes trashed artifacts.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param olderThanDays - Only delete entries older than this (all when omitted)
+ * @returns Promise resolving to the number of entries deleted
+ */
+export async function emptyTrash(projectPath: string, olderThanDays?: number): Promise<number> {
+  return await invokeWithTimeout<number>('empty_trash', { projectPath, olderThanDays });
+}
+
+/**
+ * Attaches a file to an artifact.
+ *
+ * Copies the file into `.bluekit/assets/<artifact name>/` and points links
+ * in the artifact that referenced the original at the copy.
+ *
+ * @param artifactPath - The artifact to attach to
+ * @param sourcePath - The file to attach
+ * @returns Promise resolving to the copy's path and a markdown link to insert
+ */
+export async function attachAsset(artifactPath: string, sourcePath: string): Promise<AttachedAsset> {
+  return await invokeWithTimeout<AttachedAsset>('attach_asset', { artifactPath, sourcePath });
+}
+
+/**
+ * Lists the files attached to an artifact.
+ *
+ * @param artifactPath - The artifact
+ * @returns Promise resolving to its attachments, with whether each is still linked
+ */
+export async function listArtifactAssets(artifactPath: string): Promise<ArtifactAsset[]> {
+  return await invokeWithTimeout<ArtifactAsset[]>('list_artifact_assets', { artifactPath });
+}
+
+/**
+ * Deletes an attached asset (to the trash unless `permanent`).
+ *
+ * @param assetPath - Path of the attachment
+ * @param permanent - Delete instead of moving to the trash
+ */
+export async function deleteAsset(assetPath: string, permanent?: boolean): Promise<void> {
+  return await invokeWithTimeout<void>('delete_asset', { assetPath, permanent });
+}
+
+/**
+ * Moves attachments nothing in `.bluekit` links to into the project's trash.
+ *
+ * @param projectPath - Path to the project root directory
+ * @returns Promise resolving to the trashed paths
+ */
+export async function cleanOrphanedAssets(projectPath: string): Promise<string[]> {
+  return await invokeWithTimeout<string[]>('clean_orphaned_assets', { projectPath });
+}
+
+/**
+ * Lists the attachments `cleanOrphanedAssets` would trash, without trashing them.
+ *
+ * @param projectPath - Path to the project root directory
+ * @returns Promise resolving to a report whose `deletedFiles` are the orphans
+ */
+export async function previewOrphanedAssets(projectPath: string): Promise<DryRunReport> {
+  return await invokeWithTimeout<DryRunReport>('clean_orphaned_assets', { projectPath, dryRun: true });
 }
 
 /**
@@ -333,3 +566,532 @@ export async function updateResourceMetadata(
   );
 }
 
+/**
+ * Applies the same metadata change to many resource files.
+ *
+ * Each file is updated and written atomically on its own; failures are
+ * reported per file. Files that already match aren't rewritten.
+ *
+ * @param filePaths - Absolute paths to the resource files
+ * @param update - Tags to add or remove, an alias prefix and/or a new description
+ * @returns Promise resolving to one result per file, in order
+ *
+ * @example
+ * ```typescript
+ * const results = await bulkUpdateResourceMetadata(paths, { addTags: ['auth'], removeTags: ['draft'] });
+ * const failed = results.filter(r => r.error);
+ * ```
+ */
+export async function bulkUpdateResourceMetadata(
+  filePaths: string[],
+  update: BulkMetadataUpdate
+): Promise<BulkMetadataResult[]> {
+  return await invokeWithTimeout<BulkMetadataResult[]>(
+    'bulk_update_resource_metadata',
+    { filePaths, update },
+    30000
+  );
+}
+
+/**
+ * Lists every tag used in a project's artifacts and tasks, most used first.
+ *
+ * @param projectPath - Path to the project root directory
+ * @returns Promise resolving to each tag with its artifact and task counts
+ */
+export async function getAllTags(projectPath: string): Promise<TagUsage[]> {
+  return await invokeWithTimeout<TagUsage[]>('get_all_tags', { projectPath });
+}
+
+/**
+ * Renames a tag in every artifact and task of a project.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param tag - The tag to rename
+ * @param newTag - Its new name
+ * @returns Promise resolving to per-file results and the number of tasks changed
+ */
+export async function renameTag(projectPath: string, tag: string, newTag: string): Promise<TagChangeResult> {
+  return await invokeWithTimeout<TagChangeResult>('rename_tag', { projectPath, tag, newTag }, 30000);
+}
+
+/**
+ * Removes a tag from every artifact and task of a project.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param tag - The tag to remove
+ * @returns Promise resolving to per-file results and the number of tasks changed
+ */
+export async function deleteTag(projectPath: string, tag: string): Promise<TagChangeResult> {
+  return await invokeWithTimeout<TagChangeResult>('delete_tag', { projectPath, tag }, 30000);
+}
+
+/**
+ * Packages a project's `.bluekit` directory (or selected parts of it) into a
+ * zip archive with a checksum manifest.
+ *
+ * Progress is reported through `operation-progress` events; pass the same
+ * `operationId` to `cancel_operation` to stop the export.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param destinationPath - Zip file to write, or a directory to write it into
+ * @param include - Files or folders relative to `.bluekit` (everything if omitted)
+ * @param operationId - Optional id for progress events and cancellation
+ * @returns Promise resolving to the archive path and what it contains
+ */
+export async function exportProjectArtifacts(
+  projectPath: string,
+  destinationPath: string,
+  include?: string[],
+  operationId?: string
+): Promise<ArtifactExportResult> {
+  return await invokeWithTimeout<ArtifactExportResult>(
+    'export_project_artifacts',
+    { projectPath, destinationPath, include, operationId },
+    120000
+  );
+}
+
+/**
+ * Imports artifacts from a zip archive into a project's `.bluekit` directory.
+ *
+ * Fails without changing anything if the archive is corrupt or, unless
+ * `overwrite` is set, if any file already exists.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param archivePath - Zip archive to import
+ * @param overwrite - Replace existing files
+ * @param operationId - Optional id for progress events and cancellation
+ * @returns Promise resolving to the files written
+ */
+export async function importArtifactsFromArchive(
+  projectPath: string,
+  archivePath: string,
+  overwrite = false,
+  operationId?: string
+): Promise<ArtifactImportResult> {
+  return await invokeWithTimeout<ArtifactImportResult>(
+    'import_artifacts_from_archive',
+    { projectPath, archivePath, overwrite, operationId },
+    120000
+  );
+}
+
+/**
+ * Checks a mermaid diagram for syntax errors before saving.
+ *
+ * @param source - Either the editor content or the path of a saved diagram
+ * @returns Promise resolving to the errors found, with 1-based line/column
+ */
+export async function validateDiagram(
+  source: { content: string } | { filePath: string }
+): Promise<DiagramValidation> {
+  return await invokeWithTimeout<DiagramValidation>('validate_diagram', source);
+}
+
+/**
+ * Validates an artifact's front matter against the schema for its type.
+ *
+ * @param filePath - Path of the artifact (decides the expected type)
+ * @param content - Unsaved editor content to check instead of the file on disk
+ * @returns Promise resolving to errors and warnings per field
+ */
+export async function validateArtifactMetadata(
+  filePath: string,
+  content?: string
+): Promise<MetadataValidation> {
+  return await invokeWithTimeout<MetadataValidation>('validate_artifact_metadata', { filePath, content });
+}
+
+/**
+ * Gets artifact counts per type, total sizes, last-modified times and tag
+ * frequency for a project, computed on the backend in one pass.
+ *
+ * @param projectPath - Path to the project root directory
+ * @returns Promise resolving to the project's statistics
+ */
+export async function getProjectStats(projectPath: string): Promise<ProjectStats> {
+  return await invokeWithTimeout<ProjectStats>('get_project_stats', { projectPath });
+}
+
+/**
+ * Gets the local links in an artifact (inline links, reference definitions,
+ * `[[wikilinks]]` and its link stub target), resolved to file paths.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param filePath - The artifact
+ * @returns Promise resolving to its links in order
+ */
+export async function getArtifactLinks(projectPath: string, filePath: string): Promise<ArtifactLink[]> {
+  return await invokeWithTimeout<ArtifactLink[]>('get_artifact_links', { projectPath, filePath });
+}
+
+/**
+ * Gets the artifacts in a project that link to an artifact.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param filePath - The artifact to find backlinks to
+ * @returns Promise resolving to the linking artifacts, by path
+ */
+export async function getArtifactBacklinks(projectPath: string, filePath: string): Promise<Backlink[]> {
+  return await invokeWithTimeout<Backlink[]>('get_artifact_backlinks', { projectPath, filePath });
+}
+
+/**
+ * Resolves `[[wikilink]]` text to an artifact, by file name first and `alias`
+ * front matter second (case-insensitive; spaces, `_` and `-` match each other).
+ *
+ * @param projectPath - Path to the project root directory
+ * @param text - The link text, e.g. `api-client` or `kits/api-client`
+ * @returns Promise resolving to the matching artifact, if any
+ */
+export async function resolveWikilink(projectPath: string, text: string): Promise<WikilinkResolution> {
+  return await invokeWithTimeout<WikilinkResolution>('resolve_wikilink', { projectPath, text });
+}
+
+/**
+ * Resolves many wikilinks at once, in order.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param texts - The link texts
+ * @returns Promise resolving to one resolution per text
+ */
+export async function resolveWikilinks(projectPath: string, texts: string[]): Promise<WikilinkResolution[]> {
+  return await invokeWithTimeout<WikilinkResolution[]>('resolve_wikilinks', { projectPath, texts });
+}
+
+/**
+ * Finds links to missing files across a project's artifacts and blueprint task
+ * files, each with suggested replacements (closest file names first).
+ *
+ * @param projectPath - Path to the project root directory
+ * @returns Promise resolving to the broken links and how much was checked
+ */
+export async function checkBrokenLinks(projectPath: string): Promise<BrokenLinkReport> {
+  return await invokeWithTimeout<BrokenLinkReport>('check_broken_links', { projectPath });
+}
+
+/**
+ * Creates a kit in `.bluekit/kits` from a template and registers it in the database.
+ *
+ * The file name is derived from `name`; fails if a kit with that file name exists.
+ *
+ * @param projectId - The project's database id
+ * @param projectPath - Path to the project root directory
+ * @param name - Kit name, also used for the front matter `alias`
+ * @param description - Optional description for the front matter
+ * @param tags - Optional tags for the front matter
+ * @returns Promise resolving to the created kit
+ *
+ * @example
+ * ```typescript
+ * const kit = await createKit(project.id, project.path, 'API Client', 'Typed fetch wrapper', ['api']);
+ * console.log(kit.filePath); // "/path/to/project/.bluekit/kits/api-client.md"
+ * ```
+ */
+export async function createKit(
+  projectId: string,
+  projectPath: string,
+  name: string,
+  description?: string,
+  tags?: string[]
+): Promise<CreatedArtifact> {
+  return await invokeWithTimeout<CreatedArtifact>('create_kit', {
+    projectId,
+    projectPath,
+    name,
+    description,
+    tags,
+  });
+}
+
+/**
+ * Creates an agent in `.bluekit/agents` from a template and registers it in the database.
+ *
+ * @param projectId - The project's database id
+ * @param projectPath - Path to the project root directory
+ * @param name - Agent name, also used for the front matter `alias`
+ * @param description - Optional description for the front matter
+ * @param tags - Optional tags for the front matter
+ * @param capabilities - Optional capabilities for the front matter
+ * @returns Promise resolving to the created agent
+ */
+export async function createAgent(
+  projectId: string,
+  projectPath: string,
+  name: string,
+  description?: string,
+  tags?: string[],
+  capabilities?: string[]
+): Promise<CreatedArtifact> {
+  return await invokeWithTimeout<CreatedArtifact>('create_agent', {
+    projectId,
+    projectPath,
+    name,
+    description,
+    tags,
+    capabilities,
+  });
+}
+
+/**
+ * Creates a mermaid diagram in `.bluekit/diagrams` from a template and registers
+ * it in the database.
+ *
+ * @param projectId - The project's database id
+ * @param projectPath - Path to the project root directory
+ * @param name - Diagram name, also used for the front matter `alias`
+ * @param description - Optional description for the front matter
+ * @param tags - Optional tags for the front matter
+ * @returns Promise resolving to the created diagram
+ */
+export async function createDiagram(
+  projectId: string,
+  projectPath: string,
+  name: string,
+  description?: string,
+  tags?: string[]
+): Promise<CreatedArtifact> {
+  return await invokeWithTimeout<CreatedArtifact>('create_diagram', {
+    projectId,
+    projectPath,
+    name,
+    description,
+    tags,
+  });
+}
+
+/**
+ * Gets a project's agents, sorted by name.
+ *
+ * With `projectPath`, the database is first synced with the files in
+ * `.bluekit/agents` so added, changed and deleted agents are picked up.
+ *
+ * @param projectId - The project's database id
+ * @param projectPath - Path to the project root directory, to sync first
+ * @returns Promise resolving to the project's agents
+ */
+export async function getProjectAgents(projectId: string, projectPath?: string): Promise<Agent[]> {
+  return await invokeWithTimeout<Agent[]>('get_project_agents', { projectId, projectPath });
+}
+
+/**
+ * Checks an agent file's front matter (model, tools) and system prompt sections.
+ *
+ * @param filePath - Path of the agent file
+ * @returns Promise resolving to the errors and warnings found
+ */
+export async function validateAgent(filePath: string): Promise<AgentValidation> {
+  return await invokeWithTimeout<AgentValidation>('validate_agent', { filePath });
+}
+
+/**
+ * Exports an agent as a Claude subagent (`.claude/agents/`) or a Cursor rule
+ * (`.cursor/rules/`) in a project. Fails if the agent doesn't validate.
+ *
+ * @param filePath - Path of the agent file
+ * @param projectPath - Project to write the exported file into
+ * @param format - 'claude' or 'cursor'
+ * @returns Promise resolving to the written file and its content
+ *
+ * @example
+ * ```typescript
+ * const exported = await exportAgent(agent.filePath, project.path, 'claude');
+ * console.log(exported.filePath); // "/path/to/project/.claude/agents/reviewer.md"
+ * ```
+ */
+export async function exportAgent(
+  filePath: string,
+  projectPath: string,
+  format: AgentExportFormat
+): Promise<AgentExport> {
+  return await invokeWithTimeout<AgentExport>('export_agent', { filePath, projectPath, format });
+}
+
+/**
+ * Gets every project a kit has been copied into.
+ *
+ * Each copy is flagged if it no longer exists or if the source kit changed
+ * after it was copied, so authors know which copies to update.
+ *
+ * @param kitPath - Path of the source kit
+ * @returns Promise resolving to the kit's copies
+ *
+ * @example
+ * ```typescript
+ * const usage = await getKitUsage('/path/to/project/.bluekit/kits/api-client.md');
+ * const stale = usage.filter(u => u.sourceChanged && !u.targetMissing);
+ * ```
+ */
+export async function getKitUsage(kitPath: string): Promise<KitUsage[]> {
+  return await invokeWithTimeout<KitUsage[]>('get_kit_usage', { kitPath });
+}
+
+/**
+ * Checks a project's copied and pulled artifacts against where they came from.
+ *
+ * Returns the copies whose source file or library catalog has newer content,
+ * plus copies whose origin no longer exists.
+ *
+ * @param projectPath - Path to the project root directory
+ * @returns Promise resolving to the outdated copies
+ */
+export async function checkOutdatedCopies(projectPath: string): Promise<OutdatedCopy[]> {
+  return await invokeWithTimeout<OutdatedCopy[]>('check_outdated_copies', { projectPath });
+}
+
+/**
+ * Moves an artifact into another project's `.bluekit` directory.
+ *
+ * The artifact keeps its type directory (e.g. `kits/`). Its database records
+ * (walkthrough, agent, library resource, kit usage, provenance) and bookmarks
+ * go with it, and both projects get a `project-artifacts-changed-*` event.
+ *
+ * @param sourcePath - Path of the artifact (inside a `.bluekit` directory)
+ * @param targetProject - Root path of the destination project
+ * @returns Promise resolving to the new path and the records transferred
+ *
+ * @example
+ * ```typescript
+ * const result = await moveArtifactBetweenProjects(kit.path, '/path/to/other-project');
+ * console.log(result.newPath); // "/path/to/other-project/.bluekit/kits/my-kit.md"
+ * ```
+ */
+export async function moveArtifactBetweenProjects(
+  sourcePath: string,
+  targetProject: string
+): Promise<MoveArtifactResult> {
+  return await invokeWithTimeout<MoveArtifactResult>(
+    'move_artifact_between_projects',
+    { sourcePath, targetProject },
+    30000
+  );
+}
+
+/**
+ * Links an artifact into a project instead of copying it.
+ *
+ * Writes a stub into the target project's `.bluekit/<type>/` that points at
+ * one canonical file; reads, writes and artifact listings follow the stub, so
+ * every linked project shares the same content.
+ *
+ * @param sourcePath - The artifact to link (a stub links to its canonical file)
+ * @param targetProjectPath - Root path of the project that should reference it
+ * @param useSharedPool - Copy the artifact into `~/.bluekit/shared/` and link to that copy
+ * @returns Promise resolving to the path of the created stub
+ *
+ * @example
+ * ```typescript
+ * const stubPath = await linkArtifactToProject(kit.path, '/path/to/other-project');
+ * ```
+ */
+export async function linkArtifactToProject(
+  sourcePath: string,
+  targetProjectPath: string,
+  useSharedPool?: boolean
+): Promise<string> {
+  return await invokeWithTimeout<string>('link_artifact_to_project', {
+    sourcePath,
+    targetProjectPath,
+    useSharedPool,
+  });
+}
+
+/**
+ * Replaces a link stub with a standalone copy of the artifact it points at.
+ *
+ * @param stubPath - Path of the link stub
+ * @returns Promise that resolves when the stub has been replaced
+ */
+export async function unlinkArtifact(stubPath: string): Promise<void> {
+  return await invokeWithTimeout<void>('unlink_artifact', { stubPath });
+}
+
+/**
+ * Copies an artifact to the system clipboard, ready to paste into a chat.
+ *
+ * @param path - Path of the artifact
+ * @param options - Output format and whether to drop the front matter
+ * @returns Promise resolving to the text that was copied
+ *
+ * @example
+ * ```typescript
+ * await copyArtifactToClipboard(agent.filePath, { format: 'prompt' });
+ * ```
+ */
+export async function copyArtifactToClipboard(path: string, options?: ClipboardOptions): Promise<string> {
+  return await invokeWithTimeout<string>('copy_artifact_to_clipboard', { path, options });
+}
+
+/**
+ * Lints a project's `.bluekit` Markdown for the project health tab.
+ *
+ * Reports broken relative links, missing images and diagrams, dangling
+ * `[[wikilinks]]`, unresolvable link stubs and front matter problems,
+ * grouped per file.
+ *
+ * @param projectPath - Path to the project root directory
+ * @returns Promise resolving to the findings and how many files were checked
+ *
+ * @example
+ * ```typescript
+ * const report = await lintArtifacts('/path/to/project');
+ * console.log(`${report.errorCount} errors in ${report.files.length} files`);
+ * ```
+ */
+export async function lintArtifacts(projectPath: string): Promise<LintReport> {
+  return await invokeWithTimeout<LintReport>('lint_artifacts', { projectPath }, 30000);
+}
+
+/**
+ * Renames a kit, walkthrough or diagram and updates everything pointing at it.
+ *
+ * The `alias` front matter is set to `newAlias` (or a title derived from the
+ * new name), and relative links, `[[wikilinks]]` and blueprint task files in
+ * the rest of the project are rewritten to the new file name. Database
+ * records and bookmarks follow the file.
+ *
+ * @param filePath - Path of the resource (inside a `.bluekit` directory)
+ * @param newName - New file name; the old extension is kept if none is given
+ * @param newAlias - Alias to write to the front matter
+ * @returns Promise resolving to the new path and the files that were updated
+ *
+ * @example
+ * ```typescript
+ * const result = await renameResource(kit.path, 'http-client', 'HTTP Client');
+ * console.log(`${result.referenceCount} references updated`);
+ * ```
+ */
+export async function renameResource(
+  filePath: string,
+  newName: string,
+  newAlias?: string
+): Promise<RenameResourceResult> {
+  return await invokeWithTimeout<RenameResourceResult>(
+    'rename_resource',
+    { filePath, newName, newAlias },
+    30000
+  );
+}
+
+/**
+ * Moves a resource to another folder of the same project's `.bluekit` directory.
+ *
+ * The front matter `type` is set to match the destination (`kits` -> `kit`,
+ * `walkthroughs` -> `walkthrough`, `agents` -> `agent`, `diagrams` -> `diagram`)
+ * and removed when the file lands in the scrapbook. Database records follow
+ * the file.
+ *
+ * @param filePath - Path of the resource (inside a `.bluekit` directory)
+ * @param targetDirectory - Destination relative to `.bluekit`, e.g. `kits` or
+ *   `walkthroughs/onboarding` (created if missing)
+ * @returns Promise resolving to the new path
+ *
+ * @example
+ * ```typescript
+ * const newPath = await moveResource('/path/to/project/.bluekit/notes/auth.md', 'kits');
+ * ```
+ */
+export async function moveResource(filePath: string, targetDirectory: string): Promise<string> {
+  return await invokeWithTimeout<string>('move_resource', { filePath, targetDirectory });
+}
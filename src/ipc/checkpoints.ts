@@ -8,7 +8,7 @@
  */
 
 import { invokeWithTimeout } from '@/shared/utils/ipcTimeout';
-import type { Checkpoint } from './types';
+import type { Checkpoint, CheckpointDiff, RestoredCheckpoint } from './types';
 
 /**
  * Pin a commit as a checkpoint.
@@ -97,6 +97,130 @@ export async function invokeUnpinCheckpoint(
   }, 5000);
 }
 
+/**
+ * Pin the project's current commit as a checkpoint.
+ *
+ * The commit SHA, branch and remote URL are read from the project's git repository.
+ *
+ * @param projectId - The project ID
+ * @param name - Checkpoint name
+ * @param checkpointType - Type: "milestone" | "experiment" | "template" | "backup"
+ * @param description - Optional description
+ * @param tags - Optional tags
+ * @returns The created checkpoint
+ */
+export async function invokeCreateCheckpoint(
+  projectId: string,
+  name: string,
+  checkpointType: 'milestone' | 'experiment' | 'template' | 'backup',
+  description?: string,
+  tags?: string[]
+): Promise<Checkpoint> {
+  return await invokeWithTimeout<Checkpoint>('create_checkpoint', {
+    projectId,
+    name,
+    checkpointType,
+    description,
+    tags,
+  }, 10000);
+}
+
+/**
+ * List a project's checkpoints, newest first.
+ *
+ * @param projectId - The project ID
+ * @returns Array of checkpoints
+ */
+export async function invokeListCheckpoints(
+  projectId: string
+): Promise<Checkpoint[]> {
+  return await invokeWithTimeout<Checkpoint[]>('list_checkpoints', {
+    projectId,
+  }, 5000);
+}
+
+/**
+ * Update a checkpoint's metadata. Omitted fields are left unchanged; an empty
+ * description or tag list clears it.
+ *
+ * @param checkpointId - The checkpoint ID
+ * @param updates - Fields to change
+ * @returns The updated checkpoint
+ */
+export async function invokeUpdateCheckpoint(
+  checkpointId: string,
+  updates: {
+    name?: string;
+    checkpointType?: 'milestone' | 'experiment' | 'template' | 'backup';
+    description?: string;
+    tags?: string[];
+  }
+): Promise<Checkpoint> {
+  return await invokeWithTimeout<Checkpoint>('update_checkpoint', {
+    checkpointId,
+    ...updates,
+  }, 5000);
+}
+
+/**
+ * Delete a checkpoint. The commit itself is untouched.
+ *
+ * @param checkpointId - The checkpoint ID
+ */
+export async function invokeDeleteCheckpoint(
+  checkpointId: string
+): Promise<void> {
+  return await invokeWithTimeout<void>('delete_checkpoint', {
+    checkpointId,
+  }, 5000);
+}
+
+/**
+ * Compare two checkpoints of a project, or a checkpoint with the project's current HEAD.
+ *
+ * @param fromCheckpointId - The older checkpoint
+ * @param toCheckpointId - The newer checkpoint; omit to compare with HEAD
+ * @returns Changed files with per-file and total line counts
+ */
+export async function invokeDiffCheckpoints(
+  fromCheckpointId: string,
+  toCheckpointId?: string
+): Promise<CheckpointDiff> {
+  return await invokeWithTimeout<CheckpointDiff>('diff_checkpoints', {
+    fromCheckpointId,
+    toCheckpointId,
+  }, 30000);
+}
+
+/**
+ * Restore a checkpoint into a new working copy and register it as a project.
+ *
+ * The new project gets its own checkpoint at the same commit, linked to the
+ * original through `parentCheckpointId` and `createdFromProjectId`.
+ *
+ * @param checkpointId - The checkpoint ID
+ * @param targetPath - Absolute path for the working copy (must not exist)
+ * @param mode - "clone" clones the repository; "worktree" adds a worktree to the checkpoint's project
+ * @param projectTitle - Optional project name (defaults to "<project> (<checkpoint>)")
+ * @param branch - Optional new branch to check out instead of a detached HEAD
+ * @returns The registered project and its checkpoint
+ */
+export async function invokeRestoreCheckpoint(
+  checkpointId: string,
+  targetPath: string,
+  mode: 'clone' | 'worktree' = 'clone',
+  projectTitle?: string,
+  branch?: string
+): Promise<RestoredCheckpoint> {
+  return await invokeWithTimeout<RestoredCheckpoint>('restore_checkpoint', {
+    checkpointId,
+    targetPath,
+    mode,
+    projectTitle,
+    branch,
+  }, 60000); // 60 second timeout for git operations
+}
+
 /**
  * Create a new project from a checkpoint.
  *
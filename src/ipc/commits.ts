@@ -3,7 +3,7 @@
  */
 
 import { invokeWithTimeout } from '@/shared/utils/ipcTimeout';
-import type { GitHubCommit } from './types';
+import type { GitHubCommit, GitHookInstallResult } from './types';
 
 // ============================================================================
 // COMMIT TIMELINE COMMANDS (Phase 2)
@@ -260,3 +260,22 @@ export async function invokeOpenWorktreeInWindow(
     5000 // 5 second timeout for window creation
   );
 }
+
+/**
+ * Installs a pre-commit hook that validates staged `.bluekit` artifacts.
+ *
+ * The hook runs BlueKit headlessly (`validate --staged`) and blocks the commit
+ * if front matter or blueprint files are broken. An existing hook that BlueKit
+ * didn't write is kept as `pre-commit.local` and still runs first.
+ *
+ * @param projectPath - Path to the project root directory (a git repository)
+ * @returns A promise that resolves to the hook's path and any preserved hook
+ *
+ * @example
+ * ```typescript
+ * const { hookPath, previousHookPath } = await invokeInstallGitHooks('/path/to/project');
+ * ```
+ */
+export async function invokeInstallGitHooks(projectPath: string): Promise<GitHookInstallResult> {
+  return await invokeWithTimeout<GitHookInstallResult>('install_git_hooks', { projectPath });
+}
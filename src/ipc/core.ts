@@ -3,7 +3,7 @@
  */
 
 import { invokeWithTimeout } from '@/shared/utils/ipcTimeout';
-import type { AppInfo } from './types';
+import type { AppHealthReport, AppInfo, AppSettings, DataDirectory, DataMigrationPlan, DatabaseEncryptionStatus, DbHealthReport, GlobalSearchHit, GlobalSearchKind, ScheduledJob, ScheduledJobType, ScheduledJobUpdate, SchemaVersion, SessionState } from './types';
 
 /**
  * Simple ping command to test IPC communication.
@@ -74,3 +74,288 @@ export async function invokeExampleError(shouldFail: boolean): Promise<string> {
   return await invokeWithTimeout<string>('example_error', { shouldFail });
 }
 
+/**
+ * Cancels a running long operation (clone or project creation, export, sync, ...)
+ * by the operation ID its `operation-progress` events carry.
+ *
+ * The operation stops at its next step, cleans up its partial output and fails
+ * with "Operation cancelled".
+ *
+ * @param operationId - The operation's ID
+ * @returns A promise that resolves to false if no operation with that ID is running
+ */
+export async function invokeCancelOperation(operationId: string): Promise<boolean> {
+  return await invokeWithTimeout<boolean>('cancel_operation', { operationId });
+}
+
+/**
+ * Gets application settings, with defaults filled in for missing values.
+ *
+ * @returns A promise that resolves to the current settings
+ */
+export async function invokeGetAppSettings(): Promise<AppSettings> {
+  return await invokeWithTimeout<AppSettings>('get_app_settings', {});
+}
+
+/**
+ * Saves application settings to `~/.bluekit/settings.json`.
+ *
+ * `storage` is ignored; change the data directory with
+ * `invokeSetDataDirectory`.
+ *
+ * @param settings - The complete settings to store
+ * @returns A promise that resolves to the saved settings
+ */
+export async function invokeUpdateAppSettings(settings: AppSettings): Promise<AppSettings> {
+  return await invokeWithTimeout<AppSettings>('update_app_settings', { settings });
+}
+
+/**
+ * Gets the data directory (database, project registry, shared pool) in use
+ * and the one set for the next launch.
+ *
+ * @returns A promise that resolves to the data directory
+ */
+export async function invokeGetDataDirectory(): Promise<DataDirectory> {
+  return await invokeWithTimeout<DataDirectory>('get_data_directory', {});
+}
+
+/**
+ * Lists what moving the data to a new directory would involve, and any
+ * reason it can't be done. Show this before `invokeSetDataDirectory`.
+ *
+ * @param path - The new directory, or null for the default `~/.bluekit`
+ * @returns A promise that resolves to the migration plan
+ */
+export async function invokePreviewDataDirectoryChange(path: string | null): Promise<DataMigrationPlan> {
+  return await invokeWithTimeout<DataMigrationPlan>('preview_data_directory_change', { path }, 60000);
+}
+
+/**
+ * Sets the data directory from the next launch.
+ *
+ * @param path - The new directory, or null for the default `~/.bluekit`
+ * @param migrate - Move the current data there when the app restarts; when
+ *   false, data already in `path` (from a synced drive, say) is used
+ * @returns A promise that resolves to the updated data directory
+ */
+export async function invokeSetDataDirectory(path: string | null, migrate: boolean): Promise<DataDirectory> {
+  return await invokeWithTimeout<DataDirectory>('set_data_directory', { path, migrate }, 60000);
+}
+
+/**
+ * Checks the database's integrity, then runs `VACUUM` and `ANALYZE`.
+ * Compaction is skipped if the integrity check fails.
+ *
+ * @returns A promise that resolves to the health report
+ */
+export async function invokeDbMaintenance(): Promise<DbHealthReport> {
+  return await invokeWithTimeout<DbHealthReport>('db_maintenance', {}, 300000);
+}
+
+/**
+ * Gets the database's schema version: the last applied migration and any
+ * this build hasn't applied yet.
+ *
+ * @returns A promise that resolves to the schema version
+ */
+export async function invokeGetSchemaVersion(): Promise<SchemaVersion> {
+  return await invokeWithTimeout<SchemaVersion>('get_schema_version', {});
+}
+
+/**
+ * Gets whether the database is encrypted, can be, or is locked.
+ *
+ * @returns A promise that resolves to the encryption status
+ */
+export async function invokeGetDatabaseEncryptionStatus(): Promise<DatabaseEncryptionStatus> {
+  return await invokeWithTimeout<DatabaseEncryptionStatus>('get_database_encryption_status', {});
+}
+
+/**
+ * Encrypts the database with a key kept in the OS keychain. Needs a build
+ * with SQLCipher. The database is encrypted on the next launch; calling
+ * this while a decryption is pending cancels it.
+ *
+ * @returns A promise that resolves to the new encryption status
+ */
+export async function invokeEnableDatabaseEncryption(): Promise<DatabaseEncryptionStatus> {
+  return await invokeWithTimeout<DatabaseEncryptionStatus>('enable_database_encryption', {});
+}
+
+/**
+ * Decrypts the database on the next launch; calling this while an
+ * encryption is pending cancels it.
+ *
+ * @returns A promise that resolves to the new encryption status
+ */
+export async function invokeDisableDatabaseEncryption(): Promise<DatabaseEncryptionStatus> {
+  return await invokeWithTimeout<DatabaseEncryptionStatus>('disable_database_encryption', {});
+}
+
+/**
+ * Closes the encrypted database. Commands that use it fail until
+ * `invokeUnlockDatabase`.
+ */
+export async function invokeLockDatabase(): Promise<void> {
+  return await invokeWithTimeout<void>('lock_database', {});
+}
+
+/**
+ * Reads the database key from the keychain (which may prompt the user) and
+ * restarts the app to reopen the database.
+ */
+export async function invokeUnlockDatabase(): Promise<void> {
+  return await invokeWithTimeout<void>('unlock_database', {});
+}
+
+/**
+ * Searches tasks, plans, milestones, walkthroughs and artifact front matter
+ * in one call, best hits first.
+ *
+ * @param query - Words that must each start a word of a hit
+ * @param options - `kinds` to search (default all), `projectIds` to search in
+ *   (default all but archived projects) and `limit` (default 30)
+ * @returns A promise that resolves to the ranked hits
+ */
+export async function invokeGlobalSearch(
+  query: string,
+  options: { kinds?: GlobalSearchKind[]; projectIds?: string[]; limit?: number } = {}
+): Promise<GlobalSearchHit[]> {
+  return await invokeWithTimeout<GlobalSearchHit[]>('global_search', { query, ...options }, 15000);
+}
+
+/**
+ * Lists scheduled jobs with their next and last run.
+ *
+ * @returns A promise that resolves to all scheduled jobs
+ */
+export async function invokeListScheduledJobs(): Promise<ScheduledJob[]> {
+  return await invokeWithTimeout<ScheduledJob[]>('list_scheduled_jobs', {});
+}
+
+/**
+ * Creates a scheduled job that runs every `intervalSeconds` or on a
+ * `cronExpression` (five fields, local time). Give exactly one of the two.
+ *
+ * @param name - Display name
+ * @param jobType - What the job does
+ * @param schedule - `intervalSeconds` or `cronExpression`
+ * @param params - Job-specific settings, e.g. `{ keep: 14 }` for backups
+ * @returns A promise that resolves to the created job
+ *
+ * @example
+ * ```typescript
+ * const job = await invokeCreateScheduledJob('Nightly backup', 'backup', { cronExpression: '0 3 * * *' }, { keep: 14 });
+ * console.log(job.nextRunAt);
+ * ```
+ */
+export async function invokeCreateScheduledJob(
+  name: string,
+  jobType: ScheduledJobType,
+  schedule: { intervalSeconds: number } | { cronExpression: string },
+  params?: Record<string, unknown>
+): Promise<ScheduledJob> {
+  return await invokeWithTimeout<ScheduledJob>('create_scheduled_job', {
+    name,
+    jobType,
+    ...schedule,
+    params,
+  });
+}
+
+/**
+ * Updates a scheduled job's name, schedule, params or enabled state.
+ *
+ * @param jobId - The job's id
+ * @param update - Fields to change; absent fields are kept
+ * @returns A promise that resolves to the updated job
+ */
+export async function invokeUpdateScheduledJob(
+  jobId: string,
+  update: ScheduledJobUpdate
+): Promise<ScheduledJob> {
+  return await invokeWithTimeout<ScheduledJob>('update_scheduled_job', { jobId, update });
+}
+
+/**
+ * Deletes a scheduled job.
+ *
+ * @param jobId - The job's id
+ */
+export async function invokeDeleteScheduledJob(jobId: string): Promise<void> {
+  return await invokeWithTimeout<void>('delete_scheduled_job', { jobId });
+}
+
+/**
+ * Runs a scheduled job now without changing its schedule. Fails if the job
+ * is already running or the run fails.
+ *
+ * @param jobId - The job's id
+ * @returns A promise that resolves to the job's result message
+ */
+export async function invokeRunScheduledJobNow(jobId: string): Promise<string> {
+  return await invokeWithTimeout<string>('run_scheduled_job_now', { jobId }, 120000);
+}
+
+/**
+ * Gets an aggregate health report for the diagnostics screen: database,
+ * watchers, disk space in `~/.bluekit` and the scheduled job queue.
+ *
+ * GitHub and Supabase auth are only checked when their credentials are
+ * passed (tokens live in the frontend); otherwise they are reported as
+ * 'skipped'.
+ *
+ * @param credentials - Optional GitHub token and Supabase session to verify
+ * @returns A promise that resolves to the report
+ *
+ * @example
+ * ```typescript
+ * const report = await invokeGetAppHealth({
+ *   githubToken: token,
+ *   supabase: { url, anonKey, accessToken: session.access_token },
+ * });
+ * console.log(report.status); // "ok", "warning" or "error"
+ * ```
+ */
+export async function invokeGetAppHealth(
+  credentials: {
+    githubToken?: string;
+    supabase?: { url: string; anonKey: string; accessToken: string };
+  } = {}
+): Promise<AppHealthReport> {
+  return await invokeWithTimeout<AppHealthReport>('get_app_health', credentials, 30000);
+}
+
+/**
+ * Saves where the user is (active project, open artifacts, selections),
+ * replacing the previously saved session.
+ *
+ * @param state - The current session
+ *
+ * @example
+ * ```typescript
+ * await invokeSaveSessionState({
+ *   activeProjectId: project.id,
+ *   openArtifactPaths: tabs.map(tab => tab.path),
+ *   activeArtifactPath: activeTab?.path ?? null,
+ *   selectedPlanId: null,
+ *   selectedWalkthroughId: null,
+ * });
+ * ```
+ */
+export async function invokeSaveSessionState(state: SessionState): Promise<void> {
+  return await invokeWithTimeout<void>('save_session_state', { state });
+}
+
+/**
+ * Gets the last saved session for restoring at startup.
+ *
+ * Artifacts that no longer exist are left out; with no saved session every
+ * field is empty.
+ *
+ * @returns A promise that resolves to the saved session
+ */
+export async function invokeGetSessionState(): Promise<SessionState> {
+  return await invokeWithTimeout<SessionState>('get_session_state', {});
+}
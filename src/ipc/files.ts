@@ -3,6 +3,7 @@
  */
 
 import { invokeWithTimeout } from '@/shared/utils/ipcTimeout';
+import type { BinaryFileContent, DecodedText, FileDiff, FileStreamStarted, FileVersion, ReadStats } from './types';
 
 /**
  * Reads the contents of a file.
@@ -20,6 +21,97 @@ export async function invokeReadFile(filePath: string): Promise<string> {
   return await invokeWithTimeout<string>('read_file', { filePath });
 }
 
+/**
+ * Reads a text file like `invokeReadFile`, also returning the detected encoding.
+ *
+ * Non-UTF-8 files (UTF-16, Windows-1252, ...) are converted and come back with
+ * a `warning`; with `strict` they are refused instead.
+ *
+ * @param filePath - The absolute path to the file to read
+ * @param strict - Fail on non-UTF-8 content instead of converting it
+ * @returns A promise that resolves to the content, its encoding and any warning
+ *
+ * @example
+ * ```typescript
+ * const { content, encoding, warning } = await invokeReadFileWithEncoding('/path/to/notes.md');
+ * if (warning) console.warn(warning); // e.g. converted from windows-1252
+ * ```
+ */
+export async function invokeReadFileWithEncoding(filePath: string, strict?: boolean): Promise<DecodedText> {
+  return await invokeWithTimeout<DecodedText>('read_file_with_encoding', { filePath, strict });
+}
+
+/**
+ * Reads a file as bytes, base64-encoded (for images and other assets).
+ *
+ * @param filePath - The absolute path to the file to read
+ * @returns A promise that resolves to the MIME type, size and base64 content
+ *
+ * @example
+ * ```typescript
+ * const file = await invokeReadFileBinary('/path/to/.bluekit/assets/diagram.png');
+ * const src = `data:${file.mimeType};base64,${file.base64}`;
+ * ```
+ */
+export async function invokeReadFileBinary(filePath: string): Promise<BinaryFileContent> {
+  return await invokeWithTimeout<BinaryFileContent>('read_file_binary', { filePath });
+}
+
+/**
+ * Starts streaming a large text file as `file-stream-chunk` events.
+ *
+ * Subscribe to `file-stream-chunk` before calling and filter on `streamId`;
+ * the last chunk has `done: true`. Pass the stream id to `cancel_operation`
+ * to stop early.
+ *
+ * @param filePath - The absolute path to the file to read
+ * @param streamId - Id carried by the events (generated if omitted)
+ * @param chunkSize - Approximate chunk size in bytes
+ * @returns A promise that resolves to the stream id and file size
+ */
+export async function invokeReadFileStream(
+  filePath: string,
+  streamId?: string,
+  chunkSize?: number
+): Promise<FileStreamStarted> {
+  return await invokeWithTimeout<FileStreamStarted>('read_file_stream', { filePath, streamId, chunkSize });
+}
+
+/**
+ * Diffs two files, or a file against content that isn't saved yet.
+ *
+ * Pass either `newPath` or `newContent` as the new side.
+ *
+ * @param oldPath - The file on the old (left) side
+ * @param newSide - `{ newPath }` for another file or `{ newContent }` for unsaved content
+ * @param contextLines - Unchanged lines around each change (default 3)
+ * @returns A promise that resolves to the unified diff text and its hunks
+ *
+ * @example
+ * ```typescript
+ * const diff = await invokeDiffFiles(kit.path, { newContent: editorContent });
+ * if (!diff.identical) console.log(diff.unified);
+ * ```
+ */
+export async function invokeDiffFiles(
+  oldPath: string,
+  newSide: { newPath: string } | { newContent: string },
+  contextLines?: number
+): Promise<FileDiff> {
+  return await invokeWithTimeout<FileDiff>('diff_files', { oldPath, ...newSide, contextLines });
+}
+
+/**
+ * Gets the word count, heading count, code block count and estimated reading
+ * time of a markdown artifact.
+ *
+ * @param filePath - The absolute path to the artifact
+ * @returns A promise that resolves to the artifact's read stats
+ */
+export async function invokeGetArtifactReadStats(filePath: string): Promise<ReadStats> {
+  return await invokeWithTimeout<ReadStats>('get_artifact_read_stats', { filePath });
+}
+
 /**
  * Writes content to a file.
  *
@@ -35,6 +127,42 @@ export async function invokeWriteFile(filePath: string, content: string): Promis
   return await invokeWithTimeout<void>('write_file', { filePath, content });
 }
 
+/**
+ * Gets a file's content hash and modification time, to pass to
+ * `invokeWriteFileChecked` when saving.
+ *
+ * @param filePath - The absolute path to the file
+ * @returns Promise resolving to the file's current version
+ */
+export async function invokeGetFileVersion(filePath: string): Promise<FileVersion> {
+  return await invokeWithTimeout<FileVersion>('get_file_version', { filePath });
+}
+
+/**
+ * Writes a file only if it hasn't changed on disk since it was read.
+ *
+ * On a conflict the promise rejects with a JSON string whose `code` is
+ * `write_conflict` (alongside `path` and `message`, like a `lock_conflict`),
+ * carrying `diskContent`, `diskVersion` and `attemptedContent`.
+ *
+ * @param filePath - The absolute path to the file to write
+ * @param content - The content to write to the file
+ * @param expected - Version from `invokeGetFileVersion` (or a previous write)
+ * @returns Promise resolving to the file's version after the write
+ */
+export async function invokeWriteFileChecked(
+  filePath: string,
+  content: string,
+  expected: Partial<FileVersion>
+): Promise<FileVersion> {
+  return await invokeWithTimeout<FileVersion>('write_file_checked', {
+    filePath,
+    content,
+    expectedHash: expected.hash,
+    expectedModified: expected.modified,
+  });
+}
+
 /**
  * Opens HTML content in the default browser.
  *
@@ -13,6 +13,10 @@ import {
   PublishResult,
   ResourceStatus,
   PullResult,
+  BulkPublishItem,
+  BulkPullItem,
+  WorkspaceActivity,
+  PublicLibrary,
 } from '@/types/github';
 
 /**
@@ -411,6 +415,38 @@ export async function invokePublishResource(
   );
 }
 
+/**
+ * Publishes several resources to a workspace as new variations.
+ *
+ * Progress is reported through `operation-progress` events; pass the same
+ * `operationId` to `cancel_operation` to stop. Failures are reported per
+ * resource, so one bad resource doesn't stop the rest.
+ *
+ * @param resourceIds - Resources to publish
+ * @param workspaceId - Workspace to publish to
+ * @param versionTag - Optional version tag for every new variation
+ * @param operationId - Optional id for progress events and cancellation
+ * @returns Promise resolving to one outcome per resource, in order
+ *
+ * @example
+ * ```typescript
+ * const items = await invokePublishResources(selectedIds, workspace.id, 'v2');
+ * const failed = items.filter(item => item.error);
+ * ```
+ */
+export async function invokePublishResources(
+  resourceIds: string[],
+  workspaceId: string,
+  versionTag?: string,
+  operationId?: string
+): Promise<BulkPublishItem[]> {
+  return await invokeWithTimeout<BulkPublishItem[]>(
+    'publish_resources',
+    { resourceIds, workspaceId, versionTag, operationId },
+    120000
+  );
+}
+
 // ============================================================================
 // CATALOG SYNC COMMANDS
 // ============================================================================
@@ -485,6 +521,34 @@ export async function invokePullVariation(
   );
 }
 
+/**
+ * Pulls several variations into a local project.
+ *
+ * Progress is reported through `operation-progress` events; pass the same
+ * `operationId` to `cancel_operation` to stop. Merges with conflicts are
+ * reported per variation and left for `invokePullVariation` to resolve.
+ *
+ * @param variationIds - Variations to pull
+ * @param targetProjectId - The project's database id
+ * @param targetProjectPath - Path to the project root directory
+ * @param overwriteIfExists - Replace existing files
+ * @param operationId - Optional id for progress events and cancellation
+ * @returns Promise resolving to one outcome per variation, in order
+ */
+export async function invokePullVariations(
+  variationIds: string[],
+  targetProjectId: string,
+  targetProjectPath: string,
+  overwriteIfExists: boolean,
+  operationId?: string
+): Promise<BulkPullItem[]> {
+  return await invokeWithTimeout<BulkPullItem[]>(
+    'pull_variations',
+    { variationIds, targetProjectId, targetProjectPath, overwriteIfExists, operationId },
+    120000
+  );
+}
+
 // ============================================================================
 // UPDATE DETECTION COMMANDS
 // ============================================================================
@@ -517,3 +581,98 @@ export async function invokeCheckProjectForUpdates(
   );
 }
 
+// ============================================================================
+// ACTIVITY COMMANDS
+// ============================================================================
+
+/**
+ * Gets a workspace's activity feed: publishes, catalog and collection changes
+ * and folder changes, newest first.
+ *
+ * Deletions and folder changes are only recorded in the workspace repo's
+ * commits, so they are only included when `githubToken` is given.
+ *
+ * @param workspaceId - The workspace
+ * @param range - Optional time window in Unix seconds (both ends inclusive)
+ * @param githubToken - Token for reading the workspace repo's commits
+ * @returns Promise resolving to the feed
+ *
+ * @example
+ * ```typescript
+ * const weekAgo = Math.floor(Date.now() / 1000) - 7 * 24 * 3600;
+ * const activity = await invokeGetWorkspaceActivity(workspace.id, { since: weekAgo }, token);
+ * ```
+ */
+export async function invokeGetWorkspaceActivity(
+  workspaceId: string,
+  range?: { since?: number; until?: number },
+  githubToken?: string
+): Promise<WorkspaceActivity> {
+  return await invokeWithTimeout<WorkspaceActivity>(
+    'get_workspace_activity',
+    { workspaceId, range, githubToken },
+    30000
+  );
+}
+
+// ============================================================================
+// PUBLIC LIBRARY COMMANDS
+// ============================================================================
+
+/**
+ * Searches GitHub for public BlueKit libraries and lists their catalogs.
+ *
+ * Nothing is stored; use `invokePullPublicArtifact` to copy an artifact into
+ * a project.
+ *
+ * @param accessToken - GitHub access token
+ * @param query - Optional search words (all libraries when omitted)
+ * @returns Promise resolving to the libraries found
+ */
+export async function invokeBrowsePublicLibraries(
+  accessToken: string,
+  query?: string
+): Promise<PublicLibrary[]> {
+  return await invokeWithTimeout<PublicLibrary[]>(
+    'browse_public_libraries',
+    { accessToken, query },
+    60000
+  );
+}
+
+/**
+ * Pulls an artifact from a public library into a project.
+ *
+ * The file lands where a workspace pull would put it, but no subscription is
+ * recorded, so it won't be offered updates.
+ *
+ * @param accessToken - GitHub access token
+ * @param owner - Library repository owner
+ * @param repo - Library repository name
+ * @param path - Path of the artifact in the repository (`PublicCatalog.path`)
+ * @param targetProjectPath - Path to the project root directory
+ * @param overwriteIfExists - Replace an existing file
+ * @returns Promise resolving to the written path, relative to the project
+ *
+ * @example
+ * ```typescript
+ * const library = libraries[0];
+ * const written = await invokePullPublicArtifact(
+ *   token, library.owner, library.repo, library.catalogs[0].path, '/path/to/project', false
+ * );
+ * ```
+ */
+export async function invokePullPublicArtifact(
+  accessToken: string,
+  owner: string,
+  repo: string,
+  path: string,
+  targetProjectPath: string,
+  overwriteIfExists: boolean
+): Promise<string> {
+  return await invokeWithTimeout<string>(
+    'pull_public_artifact',
+    { accessToken, owner, repo, path, targetProjectPath, overwriteIfExists },
+    30000
+  );
+}
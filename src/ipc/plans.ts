@@ -10,6 +10,11 @@ import type {
   PlanPhase,
   PlanMilestone,
   PlanDocument,
+  PlanExport,
+  PlanDiagram,
+  AtRiskPhase,
+  PlanTimeline,
+  PlanLink,
 } from '@/types/plan';
 
 // ============================================================================
@@ -34,10 +39,35 @@ export async function invokeCreatePlan(
 }
 
 /**
- * Get all plans for a project
+ * Create a plan from a Claude/Cursor markdown plan file. `##` headings become
+ * phases and checklist items milestones (checked items start completed); the
+ * file is linked to the new plan.
+ *
+ * @param name - Plan name; defaults to the file's `#` title
+ * @param source - 'claude' or 'cursor'; worked out from the path if omitted
  */
-export async function invokeGetProjectPlans(projectId: string): Promise<Plan[]> {
-  return await invokeWithTimeout<Plan[]>('get_project_plans', { projectId });
+export async function invokeImportPlanFromMarkdown(
+  projectId: string,
+  projectPath: string,
+  filePath: string,
+  name?: string,
+  source?: 'claude' | 'cursor'
+): Promise<PlanDetails> {
+  return await invokeWithTimeout<PlanDetails>('import_plan_from_markdown', {
+    projectId,
+    projectPath,
+    filePath,
+    name,
+    source,
+  });
+}
+
+/**
+ * Get all plans for a project. Archived plans are left out unless
+ * `includeArchived` is set, in which case they come last.
+ */
+export async function invokeGetProjectPlans(projectId: string, includeArchived?: boolean): Promise<Plan[]> {
+  return await invokeWithTimeout<Plan[]>('get_project_plans', { projectId, includeArchived });
 }
 
 /**
@@ -47,6 +77,32 @@ export async function invokeGetPlanDetails(planId: string): Promise<PlanDetails>
   return await invokeWithTimeout<PlanDetails>('get_plan_details', { planId });
 }
 
+/**
+ * Export a plan as one standalone markdown document, written into the plan
+ * folder as `<name>.export.md` (overwritten on every export)
+ */
+export async function invokeExportPlanMarkdown(planId: string): Promise<PlanExport> {
+  return await invokeWithTimeout<PlanExport>('export_plan_markdown', { planId });
+}
+
+/**
+ * Render a plan's phases, milestones and linked plans as a mermaid diagram in
+ * the project's `.bluekit/diagrams/` (overwritten on every call)
+ */
+export async function invokeGeneratePlanDiagram(
+  planId: string,
+  format: 'flowchart' | 'gantt' = 'flowchart'
+): Promise<PlanDiagram> {
+  return await invokeWithTimeout<PlanDiagram>('generate_plan_diagram', { planId, format });
+}
+
+/**
+ * Get phase windows and milestone trend of a plan for a timeline chart
+ */
+export async function invokeGetPlanTimeline(planId: string): Promise<PlanTimeline> {
+  return await invokeWithTimeout<PlanTimeline>('get_plan_timeline', { planId });
+}
+
 /**
  * Update a plan
  */
@@ -65,10 +121,45 @@ export async function invokeUpdatePlan(
 }
 
 /**
- * Delete a plan (removes folder and database records)
+ * Copy a plan with its folder contents, phases, milestones, documents and
+ * links. Named "<name> (copy)" unless `name` is given; progress and dates are
+ * reset unless `keepProgress` is set.
  */
-export async function invokeDeletePlan(planId: string): Promise<void> {
-  return await invokeWithTimeout<void>('delete_plan', { planId });
+export async function invokeDuplicatePlan(
+  planId: string,
+  name?: string,
+  keepProgress?: boolean
+): Promise<PlanDetails> {
+  return await invokeWithTimeout<PlanDetails>('duplicate_plan', { planId, name, keepProgress });
+}
+
+/**
+ * Archive a plan, keeping it for reference outside the active plan list
+ */
+export async function invokeArchivePlan(planId: string): Promise<Plan> {
+  return await invokeUpdatePlan(planId, undefined, undefined, 'archived');
+}
+
+/**
+ * Delete a plan. It can be restored until the trash retention period passes;
+ * with `permanent`, its folder and database records are removed right away.
+ */
+export async function invokeDeletePlan(planId: string, permanent?: boolean): Promise<void> {
+  return await invokeWithTimeout<void>('delete_plan', { planId, permanent });
+}
+
+/**
+ * Restore a deleted plan
+ */
+export async function invokeRestorePlan(planId: string): Promise<Plan> {
+  return await invokeWithTimeout<Plan>('restore_plan', { planId });
+}
+
+/**
+ * Deleted plans of a project that can still be restored, most recently deleted first
+ */
+export async function invokeGetDeletedPlans(projectId: string): Promise<Plan[]> {
+  return await invokeWithTimeout<Plan[]>('get_deleted_plans', { projectId });
 }
 
 /**
@@ -130,13 +221,15 @@ export async function invokeCreatePlanPhase(
   planId: string,
   name: string,
   description?: string,
-  orderIndex?: number
+  orderIndex?: number,
+  targetDate?: number
 ): Promise<PlanPhase> {
   return await invokeWithTimeout<PlanPhase>('create_plan_phase', {
     planId,
     name,
     description,
     orderIndex: orderIndex ?? 0,
+    targetDate,
   });
 }
 
@@ -148,7 +241,8 @@ export async function invokeUpdatePlanPhase(
   name?: string,
   description?: string | null,
   status?: 'pending' | 'in_progress' | 'completed',
-  orderIndex?: number
+  orderIndex?: number,
+  targetDate?: number | null
 ): Promise<PlanPhase> {
   return await invokeWithTimeout<PlanPhase>('update_plan_phase', {
     phaseId,
@@ -156,9 +250,20 @@ export async function invokeUpdatePlanPhase(
     description,
     status,
     orderIndex,
+    targetDate,
+    // null can't reach the backend as such; this clears the date instead
+    clearTargetDate: targetDate === null,
   });
 }
 
+/**
+ * Get phases that aren't completed and are past their target date, across the
+ * active plans of a project (or every project), most overdue first
+ */
+export async function invokeGetAtRiskPhases(projectId?: string): Promise<AtRiskPhase[]> {
+  return await invokeWithTimeout<AtRiskPhase[]>('get_at_risk_phases', { projectId });
+}
+
 /**
  * Delete a plan phase
  */
@@ -190,13 +295,15 @@ export async function invokeCreatePlanMilestone(
   phaseId: string,
   name: string,
   description?: string,
-  orderIndex?: number
+  orderIndex?: number,
+  dueAt?: number
 ): Promise<PlanMilestone> {
   return await invokeWithTimeout<PlanMilestone>('create_plan_milestone', {
     phaseId,
     name,
     description,
     orderIndex: orderIndex ?? 0,
+    dueAt,
   });
 }
 
@@ -207,13 +314,17 @@ export async function invokeUpdatePlanMilestone(
   milestoneId: string,
   name?: string,
   description?: string | null,
-  completed?: boolean
+  completed?: boolean,
+  dueAt?: number | null
 ): Promise<PlanMilestone> {
   return await invokeWithTimeout<PlanMilestone>('update_plan_milestone', {
     milestoneId,
     name,
     description,
     completed,
+    dueAt,
+    // null can't reach the backend as such; this clears the date instead
+    clearDueAt: dueAt === null,
   });
 }
 
@@ -246,6 +357,31 @@ export async function invokeGetPlanDocuments(planId: string): Promise<PlanDocume
   return await invokeWithTimeout<PlanDocument[]>('get_plan_documents', { planId });
 }
 
+/**
+ * Link a document to a phase (or unlink it with no `phaseId`), as the last
+ * of that phase's documents
+ */
+export async function invokeLinkDocumentToPhase(documentId: string, phaseId?: string): Promise<void> {
+  return await invokeWithTimeout<void>('link_document_to_phase', { documentId, phaseId });
+}
+
+/**
+ * Move a document to `position` (0-based) among a phase's documents, or among
+ * those without a phase when `phaseId` is omitted; last when `position` is.
+ * Returns the phase's documents in their new order.
+ */
+export async function invokeMoveDocumentToPhase(
+  documentId: string,
+  phaseId?: string,
+  position?: number
+): Promise<PlanDocument[]> {
+  return await invokeWithTimeout<PlanDocument[]>('move_document_to_phase', {
+    documentId,
+    phaseId,
+    position,
+  });
+}
+
 /**
  * Reorder plan documents
  */
@@ -271,3 +407,24 @@ export async function invokeWatchPlanFolder(
     folderPath,
   });
 }
+
+/**
+ * Watch the plan's linked Claude/Cursor plan files. Each change emits
+ * `plan-link-changed-{planId}` with `PlanLinkChange[]`. Call again after
+ * linking or unlinking to watch the new set. Returns the links, stale ones
+ * marked.
+ */
+export async function invokeWatchPlanLinks(planId: string): Promise<PlanLink[]> {
+  return await invokeWithTimeout<PlanLink[]>('watch_plan_links', { planId });
+}
+
+/**
+ * Watch ~/.claude/plans and ~/.cursor/plans.
+ *
+ * Emits `external-plans-changed-claude` / `external-plans-changed-cursor` with
+ * the changed paths, and `linked-plans-changed` (a `LinkedPlansChanged`) when
+ * a changed file is linked to a plan.
+ */
+export async function invokeWatchExternalPlans(): Promise<void> {
+  return await invokeWithTimeout<void>('watch_external_plans', {});
+}
@@ -3,7 +3,7 @@
  */
 
 import { invokeWithTimeout } from '@/shared/utils/ipcTimeout';
-import type { ProjectEntry, ArtifactFile, Project } from './types';
+import type { ArtifactPage, ArtifactQuery, ArtifactSearchResult, BootstrapResult, ProjectSearchResults, ProjectEntry, ArtifactFile, Project, ProgressReport, RegistryReport, RegistrySyncSummary, ReconcileReport, TerminalSessionInfo } from './types';
 
 // ============================================================================
 // PROJECT REGISTRY (Now uses database backend)
@@ -49,6 +49,7 @@ export function invalidateProjectRegistryCache(): void {
  * - Frontend filtering is cheap compared to file I/O
  *
  * @param projectPath - The path to the project root directory
+ * @param options - `includeReadStats` adds `readStats` to markdown artifacts (for kit/walkthrough cards)
  * @returns A promise that resolves to an array of ArtifactFile objects
  *
  * @example
@@ -58,8 +59,96 @@ export function invalidateProjectRegistryCache(): void {
  * const walkthroughs = artifacts.filter(a => a.frontMatter?.type === 'walkthrough');
  * ```
  */
-export async function invokeGetProjectArtifacts(projectPath: string): Promise<ArtifactFile[]> {
-  return await invokeWithTimeout<ArtifactFile[]>('get_project_artifacts', { projectPath });
+export async function invokeGetProjectArtifacts(
+  projectPath: string,
+  options: { includeReadStats?: boolean } = {}
+): Promise<ArtifactFile[]> {
+  return await invokeWithTimeout<ArtifactFile[]>('get_project_artifacts', {
+    projectPath,
+    includeReadStats: options.includeReadStats,
+  });
+}
+
+/**
+ * Gets one page of a project's artifacts, filtered on the backend.
+ *
+ * Paginated counterpart of `invokeGetProjectArtifacts` for large projects:
+ * only the requested page is read. Artifacts are ordered by path so pages
+ * stay stable between calls.
+ *
+ * @param projectPath - The path to the project root directory
+ * @param query - Filters and page bounds
+ * @returns A promise that resolves to the page and the total number of matches
+ *
+ * @example
+ * ```typescript
+ * const page = await invokeGetProjectArtifactsPage('/path/to/project', {
+ *   offset: 0,
+ *   limit: 50,
+ *   subdirectory: 'kits',
+ *   extensions: ['md'],
+ * });
+ * if (page.hasMore) loadNext(page.offset + page.limit);
+ * ```
+ */
+export async function invokeGetProjectArtifactsPage(
+  projectPath: string,
+  query: ArtifactQuery = {}
+): Promise<ArtifactPage> {
+  return await invokeWithTimeout<ArtifactPage>('get_project_artifacts_page', { projectPath, query });
+}
+
+/**
+ * Searches the Markdown and mermaid files in a project's .bluekit directory.
+ *
+ * The index behind it is built on the first search and kept current from
+ * `watch_project_artifacts` events, so repeated searches are cheap.
+ *
+ * @param projectPath - The path to the project root directory
+ * @param query - Space-separated terms; every term must match
+ * @param limit - Maximum number of files to return (default 50)
+ * @returns A promise that resolves to the matching files, best first
+ *
+ * @example
+ * ```typescript
+ * const results = await invokeSearchProjectArtifacts('/path/to/project', 'auth token');
+ * results.forEach(r => console.log(r.name, r.matches.map(m => m.line)));
+ * ```
+ */
+export async function invokeSearchProjectArtifacts(
+  projectPath: string,
+  query: string,
+  limit?: number
+): Promise<ArtifactSearchResult[]> {
+  return await invokeWithTimeout<ArtifactSearchResult[]>('search_project_artifacts', {
+    projectPath,
+    query,
+    limit,
+  });
+}
+
+/**
+ * Searches the `.bluekit` artifacts of every registered project at once.
+ *
+ * Archived projects are skipped and a project whose search fails is left
+ * out. Only projects with matches are returned, the one with the best
+ * match first.
+ *
+ * @param query - Space-separated terms; every term must match
+ * @param limit - Maximum number of files to return per project (default 50)
+ * @returns A promise that resolves to the matches grouped by project
+ *
+ * @example
+ * ```typescript
+ * const groups = await invokeSearchAllProjects('auth token');
+ * groups.forEach(g => console.log(g.projectName, g.results.length));
+ * ```
+ */
+export async function invokeSearchAllProjects(
+  query: string,
+  limit?: number
+): Promise<ProjectSearchResults[]> {
+  return await invokeWithTimeout<ProjectSearchResults[]>('search_all_projects', { query, limit }, 30000);
 }
 
 /**
@@ -178,35 +267,49 @@ export async function invokeStopWatcher(eventName: string): Promise<void> {
  * 5. Optionally registers the new project in the registry
  * 6. Cleans up the temporary directory
  *
+ * Steps 2-6 run in the background; the promise resolves to the job's operation ID
+ * as soon as the clone and target path are checked. Progress arrives as `ProgressUpdate`
+ * events on `operation-progress/<operationId>` (phases `cloning` with a percentage,
+ * `checkout`, `copying` with file counts, `registering`); the final event's message is
+ * the success message or the error. Pass your own `operationId` to subscribe before
+ * the first event, and to `invokeCancelOperation` to stop the job.
+ *
  * @param cloneId - The unique clone ID
  * @param targetPath - Absolute path where the new project should be created
  * @param projectTitle - Optional title for the new project (used if registering)
  * @param registerProject - Whether to automatically register the new project (default: true)
- * @returns A promise that resolves to a success message with project path
+ * @param operationId - Optional id for progress events and cancellation
+ * @returns A promise that resolves to the job's operation ID
  *
  * @example
  * ```typescript
- * const result = await invokeCreateProjectFromClone(
+ * const operationId = crypto.randomUUID();
+ * const unlisten = await listen<ProgressUpdate>(`operation-progress/${operationId}`, (event) => {
+ *   console.log(event.payload.phase, event.payload.current, event.payload.total);
+ * });
+ * await invokeCreateProjectFromClone(
  *   'bluekit-foundation-20251201',
  *   '/path/to/new/project',
  *   'My New Project',
- *   true
+ *   true,
+ *   operationId
  * );
- * console.log(result); // "Project created successfully at: /path/to/new/project"
  * ```
  */
 export async function invokeCreateProjectFromClone(
   cloneId: string,
   targetPath: string,
   projectTitle?: string,
-  registerProject: boolean = true
+  registerProject: boolean = true,
+  operationId?: string
 ): Promise<string> {
   return await invokeWithTimeout<string>('create_project_from_clone', {
     cloneId,
     targetPath,
     projectTitle,
     registerProject,
-  }, 60000); // 60 second timeout for git operations
+    operationId,
+  });
 }
 
 /**
@@ -247,6 +350,8 @@ export async function invokeCloneFromGithub(
  * @param targetPath - The absolute path where the new project should be created
  * @param projectTitle - Title for the new project
  * @param sourceFiles - Array of tuples containing (filePath, fileType) where fileType is "kit", "walkthrough", or "diagram"
+ * @param operationId - Optional id for progress events and `invokeCancelOperation`; a cancelled
+ *   or failed creation removes the partially created directory
  * @returns A promise that resolves to the project path
  *
  * @example
@@ -264,12 +369,14 @@ export async function invokeCloneFromGithub(
 export async function invokeCreateNewProject(
   targetPath: string,
   projectTitle: string,
-  sourceFiles: Array<[string, string]>
+  sourceFiles: Array<[string, string]>,
+  operationId?: string
 ): Promise<string> {
   return await invokeWithTimeout<string>('create_new_project', {
     targetPath,
     projectTitle,
     sourceFiles,
+    operationId,
   });
 }
 
@@ -321,39 +428,135 @@ export async function invokeOpenInTerminal(path: string): Promise<void> {
 }
 
 /**
- * Opens a file in the specified editor (Cursor, Claude, or VSCode).
+ * Opens a file in an editor, optionally at a line.
+ *
+ * Without `editor`, the editor chosen in settings (`editor.editor`) is used.
+ * Falls back to the app bundle on macOS, the installed `Code.exe`/`Cursor.exe`
+ * on Windows, and finally the system's default app if the editor can't be
+ * launched.
  *
  * @param filePath - Absolute path to the file to open
- * @param editor - The editor to use: 'cursor', 'claude', or 'vscode'
- * @returns Promise that resolves when the editor is opened
+ * @param editor - The editor to use instead of the configured one
+ * @param line - Optional 1-based line number to jump to
+ * @returns Promise that resolves when the editor is launched
  *
  * @example
  * ```typescript
  * await invokeOpenFileInEditor('/path/to/kit.md', 'cursor');
  * await invokeOpenFileInEditor('/path/to/walkthrough.md', 'claude');
+ * await invokeOpenFileInEditor('/path/to/kit.md', undefined, 12);
  * ```
  */
 export async function invokeOpenFileInEditor(
   filePath: string,
-  editor: 'cursor' | 'claude' | 'vscode'
+  editor?: 'cursor' | 'claude' | 'vscode' | 'vim' | 'env' | 'custom',
+  line?: number
 ): Promise<void> {
   return await invokeWithTimeout<void>(
     'open_file_in_editor',
     {
       filePath,
       editor,
+      line,
     },
     10000 // 10 second timeout for opening editor
   );
 }
 
+/**
+ * Shows a file or folder in Finder, Explorer or the Linux file manager, selected
+ * where the platform allows.
+ *
+ * @param path - Absolute path inside a registered project or `~/.bluekit`
+ * @returns Promise that resolves when the file manager is opened
+ */
+export async function invokeRevealInFileManager(path: string): Promise<void> {
+  return await invokeWithTimeout<void>('reveal_in_file_manager', { path }, 5000);
+}
+
+/**
+ * Checks projectRegistry.json for malformed entries, missing project
+ * directories, duplicate IDs and relative paths.
+ *
+ * @param repair - Back up the registry and rewrite it without the broken entries
+ * @returns A promise that resolves to the registry report
+ *
+ * @example
+ * ```typescript
+ * const report = await invokeValidateProjectRegistry();
+ * if (report.issues.length > 0) {
+ *   await invokeValidateProjectRegistry(true);
+ * }
+ * ```
+ */
+export async function invokeValidateProjectRegistry(repair?: boolean): Promise<RegistryReport> {
+  return await invokeWithTimeout<RegistryReport>('validate_project_registry', { repair });
+}
+
+/**
+ * Removes a project from projectRegistry.json. A database project with the
+ * same ID is archived, so the next registry sync doesn't add it back.
+ *
+ * @param projectId - The registry entry's ID
+ * @returns A promise that resolves when the entry is removed
+ */
+export async function invokeRemoveProjectFromRegistry(projectId: string): Promise<void> {
+  return await invokeWithTimeout<void>('remove_project_from_registry', { projectId });
+}
+
+/**
+ * Updates a project's entry in projectRegistry.json, and the database project
+ * with the same ID. Omitted fields are left unchanged.
+ *
+ * @param projectId - The registry entry's ID
+ * @param updates - New title, description and/or absolute path
+ * @returns A promise that resolves to the updated ProjectEntry
+ *
+ * @example
+ * ```typescript
+ * const entry = await invokeUpdateProjectEntry('project-id-123', { title: 'Renamed' });
+ * ```
+ */
+export async function invokeUpdateProjectEntry(
+  projectId: string,
+  updates: { title?: string; description?: string; path?: string }
+): Promise<ProjectEntry> {
+  return await invokeWithTimeout<ProjectEntry>('update_project_entry', {
+    projectId,
+    ...updates,
+  });
+}
+
 // ============================================================================
 // DATABASE-BACKED PROJECT MANAGEMENT (Phase 1)
 // ============================================================================
 
 /**
- * Gets all projects from the database.
+ * Brings projectRegistry.json in line with the projects database, which is canonical.
+ *
+ * @returns Counts of entries imported into the database, exported to the
+ * registry, rewritten, and removed for archived projects
+ */
+export async function invokeSyncProjectRegistry(): Promise<RegistrySyncSummary> {
+  return await invokeWithTimeout<RegistrySyncSummary>('sync_project_registry', {});
+}
+
+/**
+ * The canonical project list, in manual order. Syncs projectRegistry.json
+ * with the database first, so projects registered either way are included.
+ *
+ * @param includeArchived - Also return archived projects (default false)
+ * @returns A promise that resolves to an array of Project objects
+ */
+export async function invokeGetProjects(includeArchived?: boolean): Promise<Project[]> {
+  return await invokeWithTimeout<Project[]>('get_projects', { includeArchived });
+}
+
+/**
+ * Gets all projects from the database, in their manual order. Same as
+ * `invokeGetProjects`.
  *
+ * @param includeArchived - Also return archived projects (default false)
  * @returns A promise that resolves to an array of Project objects from the database
  *
  * @example
@@ -364,8 +567,40 @@ export async function invokeOpenFileInEditor(
  * });
  * ```
  */
-export async function invokeDbGetProjects(): Promise<Project[]> {
-  return await invokeWithTimeout<Project[]>('db_get_projects');
+export async function invokeDbGetProjects(includeArchived?: boolean): Promise<Project[]> {
+  return await invokeWithTimeout<Project[]>('db_get_projects', { includeArchived });
+}
+
+/**
+ * Archives a project, hiding it (and tasks that only belong to it) from listings.
+ *
+ * @param projectId - The project ID
+ * @returns A promise that resolves to the updated Project object
+ */
+export async function invokeArchiveProject(projectId: string): Promise<Project> {
+  return await invokeWithTimeout<Project>('archive_project', { projectId });
+}
+
+/**
+ * Restores an archived project to the project list.
+ *
+ * @param projectId - The project ID
+ * @returns A promise that resolves to the updated Project object
+ */
+export async function invokeUnarchiveProject(projectId: string): Promise<Project> {
+  return await invokeWithTimeout<Project>('unarchive_project', { projectId });
+}
+
+/**
+ * Stores the user's arrangement of the project list.
+ *
+ * Listed projects come first, in the given order; any others follow in their previous order.
+ *
+ * @param projectIds - Project IDs in the desired order
+ * @returns A promise that resolves to all projects in their new order
+ */
+export async function invokeReorderProjects(projectIds: string[]): Promise<Project[]> {
+  return await invokeWithTimeout<Project[]>('reorder_projects', { projectIds });
 }
 
 /**
@@ -489,3 +724,126 @@ export async function invokeDisconnectProjectGit(projectId: string): Promise<Pro
   return await invokeWithTimeout<Project>('disconnect_project_git', { projectId });
 }
 
+/**
+ * Cross-checks a project's database records and bookmarks against its files.
+ *
+ * Reports walkthroughs, plans, plan documents and library resources whose
+ * files are gone, files that have no record, and bookmarks pointing at
+ * missing files. With `fix`, stale records and bookmarks are removed and
+ * untracked files are adopted.
+ *
+ * @param projectPath - Path to the project root directory
+ * @param fix - Repair what was found instead of only reporting it
+ * @returns A promise that resolves to the orphans found
+ *
+ * @example
+ * ```typescript
+ * const report = await invokeReconcileProject('/path/to/project');
+ * if (report.orphans.length > 0) {
+ *   await invokeReconcileProject('/path/to/project', true);
+ * }
+ * ```
+ */
+export async function invokeReconcileProject(projectPath: string, fix = false): Promise<ReconcileReport> {
+  return await invokeWithTimeout<ReconcileReport>('reconcile_project', { projectPath, fix }, 30000);
+}
+
+/**
+ * Seeds an example project on first launch.
+ *
+ * Does nothing (resolves with `seeded: false`) unless this is a fresh install
+ * with no projects, so it is safe to call on every start.
+ *
+ * @returns A promise that resolves to whether a starter project was created
+ *
+ * @example
+ * ```typescript
+ * const result = await invokeBootstrapStarterContent();
+ * if (result.seeded) navigate(`/projects/${result.projectId}`);
+ * ```
+ */
+export async function invokeBootstrapStarterContent(): Promise<BootstrapResult> {
+  return await invokeWithTimeout<BootstrapResult>('bootstrap_starter_content', {}, 30000);
+}
+
+/**
+ * Writes a progress report for a project to `.bluekit/reports/`.
+ *
+ * Lists milestones completed, tasks finished, artifacts added and checkpoints
+ * pinned in the period. Regenerating a period replaces its earlier report.
+ *
+ * @param projectId - The project ID (from database)
+ * @param range - Period in Unix seconds (both ends inclusive); the last seven days if omitted
+ * @returns A promise that resolves to the Markdown and where it was saved
+ *
+ * @example
+ * ```typescript
+ * const now = Math.floor(Date.now() / 1000);
+ * const report = await invokeGenerateProgressReport('project-id-123', { since: weekStart, until: now });
+ * console.log(report.path, report.finishedTasks);
+ * ```
+ */
+export async function invokeGenerateProgressReport(
+  projectId: string,
+  range?: { since?: number; until?: number }
+): Promise<ProgressReport> {
+  return await invokeWithTimeout<ProgressReport>('generate_progress_report', { projectId, range }, 30000);
+}
+
+// ============================================================================
+// TERMINAL
+// ============================================================================
+
+/**
+ * Runs a shell command in a PTY inside a project directory.
+ *
+ * Output streams as `terminal-output` events and completion as a single
+ * `terminal-exit` event, both carrying the returned session id.
+ *
+ * @param projectPath - Directory to run the command in
+ * @param command - The shell command line
+ * @returns A promise that resolves to the session id
+ *
+ * @example
+ * ```typescript
+ * const sessionId = await invokeRunCommand('/path/to/project', 'npm test');
+ * const unlisten = await listen<TerminalOutput>('terminal-output', (event) => {
+ *   if (event.payload.sessionId === sessionId) terminal.write(event.payload.data);
+ * });
+ * ```
+ */
+export async function invokeRunCommand(projectPath: string, command: string): Promise<string> {
+  return await invokeWithTimeout<string>('run_command', { projectPath, command });
+}
+
+/**
+ * Kills the process of a running terminal session.
+ *
+ * The session still sends its `terminal-exit` event, with `killed` set.
+ *
+ * @param sessionId - Id returned by `invokeRunCommand`
+ * @returns A promise that resolves when the process has been signalled
+ */
+export async function invokeKillCommand(sessionId: string): Promise<void> {
+  return await invokeWithTimeout<void>('kill_command', { sessionId });
+}
+
+/**
+ * Sends input (keystrokes or pasted text) to a running terminal session.
+ *
+ * @param sessionId - Id returned by `invokeRunCommand`
+ * @param data - Text to write to the session's PTY
+ * @returns A promise that resolves when the input has been written
+ */
+export async function invokeWriteTerminalInput(sessionId: string, data: string): Promise<void> {
+  return await invokeWithTimeout<void>('write_terminal_input', { sessionId, data });
+}
+
+/**
+ * Lists the terminal sessions that are still running.
+ *
+ * @returns A promise that resolves to the running sessions
+ */
+export async function invokeListTerminalSessions(): Promise<TerminalSessionInfo[]> {
+  return await invokeWithTimeout<TerminalSessionInfo[]>('list_terminal_sessions');
+}
@@ -3,13 +3,25 @@
  */
 
 import { invokeWithTimeout } from '@/shared/utils/ipcTimeout';
-import { Task as DbTask, TaskPriority, TaskStatus, TaskComplexity, TaskType } from '@/types/task';
+import { Task as DbTask, TaskPriority, TaskStatus, TaskComplexity, TaskType, TaskSearchHit, TaskActivity, TaskPatch, TaskFilter, SavedView } from '@/types/task';
 
 /**
- * Get all tasks, optionally filtered by project IDs
+ * Get all tasks, optionally filtered by project IDs.
+ * Tasks that only belong to archived projects are left out unless includeArchived is set.
  */
-export async function invokeDbGetTasks(projectIds?: string[]): Promise<DbTask[]> {
-  return await invokeWithTimeout<DbTask[]>('db_get_tasks', { projectIds }, 15000);
+export async function invokeDbGetTasks(projectIds?: string[], includeArchived?: boolean): Promise<DbTask[]> {
+  return await invokeWithTimeout<DbTask[]>('db_get_tasks', { projectIds, includeArchived }, 15000);
+}
+
+/**
+ * Full-text search over task titles, descriptions and tags, best matches first.
+ * Every word must match as a prefix; matches come back wrapped in `<mark>` (the rest is HTML-escaped).
+ */
+export async function invokeDbSearchTasks(
+  query: string,
+  options: { projectIds?: string[]; limit?: number; includeArchived?: boolean } = {}
+): Promise<TaskSearchHit[]> {
+  return await invokeWithTimeout<TaskSearchHit[]>('db_search_tasks', { query, ...options }, 15000);
 }
 
 /**
@@ -37,7 +49,9 @@ export async function invokeDbCreateTask(
   projectIds: string[],
   status?: TaskStatus,
   complexity?: TaskComplexity,
-  type?: TaskType
+  type?: TaskType,
+  dueAt?: string,
+  remindAt?: string
 ): Promise<DbTask> {
   return await invokeWithTimeout<DbTask>(
     'db_create_task',
@@ -49,14 +63,16 @@ export async function invokeDbCreateTask(
       projectIds,
       status: status ?? null,
       complexity: complexity ?? null,
-      type: type && type.trim().length > 0 ? type : null
+      type: type && type.trim().length > 0 ? type : null,
+      dueAt: dueAt ?? null,
+      remindAt: remindAt ?? null
     },
     10000
   );
 }
 
 /**
- * Update an existing task
+ * Update an existing task. `null` for `dueAt` or `remindAt` clears it.
  */
 export async function invokeDbUpdateTask(
   taskId: string,
@@ -67,7 +83,9 @@ export async function invokeDbUpdateTask(
   projectIds?: string[],
   status?: TaskStatus,
   complexity?: TaskComplexity | null,
-  type?: TaskType | null
+  type?: TaskType | null,
+  dueAt?: string | null,
+  remindAt?: string | null
 ): Promise<DbTask> {
   return await invokeWithTimeout<DbTask>(
     'db_update_task',
@@ -80,16 +98,113 @@ export async function invokeDbUpdateTask(
       projectIds,
       status,
       complexity,
-      type: type
+      type: type,
+      dueAt,
+      remindAt,
+      // null can't reach the backend as such; these clear the dates instead
+      clearDueAt: dueAt === null,
+      clearRemindAt: remindAt === null
     },
     10000
   );
 }
 
 /**
- * Delete a task
+ * Apply one patch to several tasks in a single transaction (all-or-nothing)
+ */
+export async function invokeDbBulkUpdateTasks(taskIds: string[], patch: TaskPatch): Promise<DbTask[]> {
+  return await invokeWithTimeout<DbTask[]>('db_bulk_update_tasks', { taskIds, patch }, 30000);
+}
+
+/**
+ * Delete several tasks in a single transaction, like invokeDbDeleteTask. Returns how many were deleted.
+ */
+export async function invokeDbBulkDeleteTasks(taskIds: string[], permanent?: boolean): Promise<number> {
+  return await invokeWithTimeout<number>('db_bulk_delete_tasks', { taskIds, permanent }, 30000);
+}
+
+/**
+ * List saved task views by name
+ */
+export async function invokeDbListSavedViews(): Promise<SavedView[]> {
+  return await invokeWithTimeout<SavedView[]>('db_list_saved_views', {}, 10000);
+}
+
+/**
+ * Save a named task filter
+ */
+export async function invokeDbCreateSavedView(name: string, filter: TaskFilter): Promise<SavedView> {
+  return await invokeWithTimeout<SavedView>('db_create_saved_view', { name, filter }, 10000);
+}
+
+/**
+ * Rename a saved view and/or replace its filter
+ */
+export async function invokeDbUpdateSavedView(viewId: string, name?: string, filter?: TaskFilter): Promise<SavedView> {
+  return await invokeWithTimeout<SavedView>('db_update_saved_view', { viewId, name, filter }, 10000);
+}
+
+/**
+ * Delete a saved view
+ */
+export async function invokeDbDeleteSavedView(viewId: string): Promise<void> {
+  return await invokeWithTimeout<void>('db_delete_saved_view', { viewId }, 10000);
+}
+
+/**
+ * Run a saved view's filter and sort on the backend
+ */
+export async function invokeDbGetTasksForView(viewId: string): Promise<DbTask[]> {
+  return await invokeWithTimeout<DbTask[]>('db_get_tasks_for_view', { viewId }, 15000);
+}
+
+/**
+ * Save the order of one status column on a project's board.
+ * `taskIds` is the column's full new order; tasks dragged in from another column move to `status`.
+ */
+export async function invokeDbReorderTasks(projectId: string, status: TaskStatus, taskIds: string[]): Promise<void> {
+  return await invokeWithTimeout<void>('db_reorder_tasks', { projectId, status, taskIds }, 10000);
+}
+
+/**
+ * A task's change history (creation, then each changed field), newest first
+ */
+export async function invokeDbGetTaskActivity(taskId: string): Promise<TaskActivity[]> {
+  return await invokeWithTimeout<TaskActivity[]>('db_get_task_activity', { taskId }, 10000);
+}
+
+/**
+ * Tasks past their due date that aren't completed, most overdue first
+ */
+export async function invokeDbGetOverdueTasks(projectIds?: string[]): Promise<DbTask[]> {
+  return await invokeWithTimeout<DbTask[]>('db_get_overdue_tasks', { projectIds }, 15000);
+}
+
+/**
+ * Tasks due in the next `days` days (default 7) that aren't completed, soonest first
+ */
+export async function invokeDbGetUpcomingTasks(days?: number, projectIds?: string[]): Promise<DbTask[]> {
+  return await invokeWithTimeout<DbTask[]>('db_get_upcoming_tasks', { days, projectIds }, 15000);
+}
+
+/**
+ * Delete a task. It can be restored until the trash retention period passes, unless `permanent` is set.
+ */
+export async function invokeDbDeleteTask(taskId: string, permanent?: boolean): Promise<void> {
+  return await invokeWithTimeout<void>('db_delete_task', { taskId, permanent }, 10000);
+}
+
+/**
+ * Restore a deleted task
+ */
+export async function invokeDbRestoreTask(taskId: string): Promise<DbTask> {
+  return await invokeWithTimeout<DbTask>('db_restore_task', { taskId }, 10000);
+}
+
+/**
+ * Deleted tasks that can still be restored, most recently deleted first
  */
-export async function invokeDbDeleteTask(taskId: string): Promise<void> {
-  return await invokeWithTimeout<void>('db_delete_task', { taskId }, 10000);
+export async function invokeDbGetDeletedTasks(projectIds?: string[]): Promise<DbTask[]> {
+  return await invokeWithTimeout<DbTask[]>('db_get_deleted_tasks', { projectIds }, 15000);
 }
 
@@ -41,6 +41,479 @@ export interface ArtifactFile {
   content?: string;
   /** Parsed YAML front matter */
   frontMatter?: KitFrontMatter;
+  /** Typed summary of the front matter, parsed by the backend */
+  metadata?: ArtifactMetadata;
+  /** True when the file is a symlink or was reached through a symlinked folder */
+  symlinked?: boolean;
+  /** `/`-separated path below the listed folder (only set by `get_folder_markdown_files`) */
+  relativePath?: string;
+  /** Word count and reading time, when the listing asked for them */
+  readStats?: ReadStats;
+}
+
+/**
+ * Commonly used front matter fields, returned with artifact listings.
+ *
+ * This interface must match the `ArtifactMetadata` struct in `src-tauri/src/commands.rs`.
+ */
+export interface ArtifactMetadata {
+  /** Artifact type (e.g., 'kit', 'walkthrough', 'agent', 'diagram') */
+  type?: string;
+  /** Display alias/name */
+  alias?: string;
+  /** Short description */
+  description?: string;
+  /** Tags (a comma-separated `tags` string is split) */
+  tags: string[];
+}
+
+/**
+ * Word count and reading time of a markdown artifact, front matter excluded.
+ *
+ * This interface must match the `ReadStats` struct in `src-tauri/src/core/read_stats.rs`.
+ */
+export interface ReadStats {
+  /** Words outside code blocks */
+  wordCount: number;
+  /** Markdown headings */
+  headingCount: number;
+  /** Fenced code blocks */
+  codeBlockCount: number;
+  /** Estimated minutes to read, at 200 words per minute (0 when empty) */
+  readingTimeMinutes: number;
+}
+
+/**
+ * One file to copy with `copy_resources_to_project`.
+ *
+ * This interface must match the `ResourceCopyRequest` struct in `src-tauri/src/commands.rs`.
+ */
+export interface ResourceCopyRequest {
+  /** Absolute path to the source file */
+  sourcePath: string;
+  /** Decides the target subdirectory */
+  artifactType: 'kit' | 'walkthrough' | 'diagram';
+}
+
+/**
+ * What the copy commands do when a target already exists.
+ *
+ * This type must match the `ConflictPolicy` enum in `src-tauri/src/core/copy_conflict.rs`.
+ */
+export type ConflictPolicy = 'overwrite' | 'skip' | 'rename-with-suffix' | 'fail';
+
+/**
+ * What a copy actually did.
+ *
+ * This type must match the `CopyAction` enum in `src-tauri/src/core/copy_conflict.rs`.
+ */
+export type CopyAction = 'copied' | 'overwritten' | 'skipped' | 'renamed';
+
+/**
+ * Per-file result of `copy_resources_to_project`.
+ *
+ * This interface must match the `ResourceCopyResult` struct in `src-tauri/src/commands.rs`.
+ */
+export interface ResourceCopyResult {
+  sourcePath: string;
+  artifactType: string;
+  /** Path of the copy, if it succeeded (the existing file if skipped) */
+  targetPath: string | null;
+  /** What the copy did, if it succeeded */
+  action: CopyAction | null;
+  error: string | null;
+}
+
+/**
+ * One file written (or skipped) by a copy command.
+ *
+ * This interface must match the `CopiedFile` struct in `src-tauri/src/commands.rs`.
+ */
+export interface CopiedFile {
+  path: string;
+  action: CopyAction;
+}
+
+/**
+ * Result of `copy_blueprint_to_project`.
+ *
+ * This interface must match the `BlueprintCopyResult` struct in `src-tauri/src/commands.rs`.
+ */
+export interface BlueprintCopyResult {
+  /** Blueprint directory in the project (the existing one if skipped) */
+  targetPath: string;
+  /** What happened to the blueprint directory as a whole */
+  action: CopyAction;
+  /** Every file copied into it; empty if skipped */
+  files: CopiedFile[];
+}
+
+/**
+ * Changes applied to every file by `bulk_update_resource_metadata`.
+ *
+ * This interface must match the `BulkMetadataUpdate` struct in `src-tauri/src/commands.rs`.
+ */
+export interface BulkMetadataUpdate {
+  /** Tags added to each file */
+  addTags?: string[];
+  /** Tags removed from each file */
+  removeTags?: string[];
+  /** Prefix put in front of each alias (the file name if there's no alias) */
+  aliasPrefix?: string;
+  /** New description for every file */
+  description?: string;
+}
+
+/**
+ * Per-file result of `bulk_update_resource_metadata`.
+ *
+ * This interface must match the `BulkMetadataResult` struct in `src-tauri/src/commands.rs`.
+ */
+export interface BulkMetadataResult {
+  filePath: string;
+  /** Whether the file changed; false if it already matched or failed */
+  updated: boolean;
+  error: string | null;
+}
+
+/**
+ * How often a tag is used in a project.
+ *
+ * This interface must match the `TagUsage` struct in `src-tauri/src/commands.rs`.
+ */
+export interface TagUsage {
+  tag: string;
+  artifactCount: number;
+  taskCount: number;
+  /** Artifacts with the tag */
+  files: string[];
+}
+
+/**
+ * Result of `rename_tag` and `delete_tag`.
+ *
+ * This interface must match the `TagChangeResult` struct in `src-tauri/src/commands.rs`.
+ */
+export interface TagChangeResult {
+  /** One result per artifact that had the tag */
+  files: BulkMetadataResult[];
+  tasksUpdated: number;
+}
+
+/**
+ * Binary file contents returned by `read_file_binary`.
+ *
+ * This interface must match the `BinaryFileContent` struct in `src-tauri/src/commands.rs`.
+ */
+export interface BinaryFileContent {
+  /** MIME type guessed from the extension */
+  mimeType: string;
+  base64: string;
+  size: number;
+}
+
+/**
+ * One line of a diff hunk.
+ *
+ * This interface must match the `DiffLine` struct in `src-tauri/src/core/diff.rs`.
+ */
+export interface DiffLine {
+  kind: 'context' | 'added' | 'removed';
+  /** 1-based line in the old text (null for added lines) */
+  oldLine: number | null;
+  /** 1-based line in the new text (null for removed lines) */
+  newLine: number | null;
+  content: string;
+}
+
+/**
+ * A run of changes with the context around them.
+ *
+ * This interface must match the `DiffHunk` struct in `src-tauri/src/core/diff.rs`.
+ */
+export interface DiffHunk {
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+  /** The `@@ -a,b +c,d @@` line */
+  header: string;
+  lines: DiffLine[];
+}
+
+/**
+ * Result of `diff_files`.
+ *
+ * This interface must match the `FileDiff` struct in `src-tauri/src/core/diff.rs`.
+ */
+export interface FileDiff {
+  oldLabel: string;
+  newLabel: string;
+  identical: boolean;
+  additions: number;
+  deletions: number;
+  /** Unified diff text; empty when identical */
+  unified: string;
+  hunks: DiffHunk[];
+}
+
+/**
+ * Version of a file on disk, used for conflict detection on save.
+ *
+ * This interface must match the `FileVersion` struct in `src-tauri/src/commands.rs`.
+ */
+export interface FileVersion {
+  /** SHA-256 of the file's bytes */
+  hash: string;
+  /** Modification time in Unix milliseconds */
+  modified: number;
+}
+
+/**
+ * Returned by `read_file_stream` once the stream has started.
+ */
+export interface FileStreamStarted {
+  streamId: string;
+  totalBytes: number;
+}
+
+/**
+ * Payload of a `file-stream-chunk` event.
+ *
+ * This interface must match the `FileStreamChunk` struct in `src-tauri/src/core/file_stream.rs`.
+ */
+export interface FileStreamChunk {
+  streamId: string;
+  index: number;
+  chunk: string;
+  /** Byte offset of this chunk in the file */
+  offset: number;
+  totalBytes: number;
+  done: boolean;
+  error: string | null;
+}
+
+/**
+ * Result of `export_project_artifacts`.
+ */
+export interface ArtifactExportResult {
+  archivePath: string;
+  fileCount: number;
+  totalBytes: number;
+}
+
+/**
+ * Result of `import_artifacts_from_archive`.
+ */
+export interface ArtifactImportResult {
+  /** Files written, including replaced ones */
+  importedFiles: string[];
+  /** Existing files that were replaced */
+  overwrittenFiles: string[];
+}
+
+/**
+ * Result of `validate_diagram`.
+ *
+ * This interface must match the `DiagramValidation` struct in `src-tauri/src/core/mermaid.rs`.
+ */
+export interface DiagramValidation {
+  valid: boolean;
+  /** Keyword from the first line, e.g. "flowchart" */
+  diagramType: string | null;
+  errors: Array<{
+    /** 1-based */
+    line: number;
+    /** 1-based */
+    column: number;
+    message: string;
+  }>;
+}
+
+/**
+ * Result of `validate_artifact_metadata`.
+ *
+ * This interface must match the `MetadataValidation` struct in `src-tauri/src/core/metadata_schema.rs`.
+ */
+export interface MetadataValidation {
+  /** True when there are no errors (warnings are allowed) */
+  valid: boolean;
+  artifactType: string | null;
+  diagnostics: Array<{
+    severity: 'error' | 'warning';
+    field: string | null;
+    message: string;
+  }>;
+}
+
+/**
+ * Artifact statistics returned by `get_project_stats`.
+ *
+ * This interface must match the `ProjectStats` struct in `src-tauri/src/commands.rs`.
+ */
+export interface ProjectStats {
+  totalArtifacts: number;
+  totalBytes: number;
+  /** Unix milliseconds */
+  lastModified: number | null;
+  types: Array<{
+    type: 'kit' | 'walkthrough' | 'agent' | 'task' | 'diagram' | 'asset';
+    count: number;
+    totalBytes: number;
+    lastModified: number | null;
+  }>;
+  /** Most used first */
+  tags: Array<{ tag: string; count: number }>;
+}
+
+/**
+ * Result of `attach_asset`.
+ *
+ * This interface must match the `AttachedAsset` struct in `src-tauri/src/core/assets.rs`.
+ */
+export interface AttachedAsset {
+  assetPath: string;
+  /** Path of the asset relative to the artifact */
+  relativePath: string;
+  /** Ready-to-insert markdown link (an image link for images) */
+  markdown: string;
+  /** Links to the source file that now point at the attached copy */
+  linksRewritten: number;
+}
+
+/**
+ * A file attached to an artifact.
+ *
+ * This interface must match the `ArtifactAsset` struct in `src-tauri/src/core/assets.rs`.
+ */
+export interface ArtifactAsset {
+  path: string;
+  name: string;
+  size: number;
+  relativePath: string;
+  /** Whether the artifact links to it */
+  referenced: boolean;
+}
+
+/**
+ * A local link in an artifact.
+ *
+ * This interface must match the `ArtifactLink` struct in `src-tauri/src/core/link_graph.rs`.
+ */
+export interface ArtifactLink {
+  /** The file linked to, resolved against the linking artifact (empty for a wikilink that matches no artifact) */
+  target: string;
+  /** The link as written, including any `#fragment` */
+  href: string;
+  /** Link text or reference label (empty for link stubs) */
+  text: string;
+  /** 1-based line of the link */
+  line: number;
+  kind: 'inline' | 'reference' | 'stub' | 'wikilink';
+  /** Whether the target exists */
+  exists: boolean;
+}
+
+/**
+ * What `[[wikilink]]` text resolved to.
+ *
+ * This interface must match the `WikilinkResolution` struct in `src-tauri/src/core/wikilinks.rs`.
+ */
+export interface WikilinkResolution {
+  text: string;
+  /** The artifact it resolved to; the first by path if several match */
+  path?: string;
+  matchedBy?: 'name' | 'alias' | 'path';
+  /** Every artifact that matched, when more than one did */
+  ambiguous?: string[];
+}
+
+/**
+ * A possible fix for a broken link.
+ *
+ * This interface must match the `LinkSuggestion` struct in `src-tauri/src/core/broken_links.rs`.
+ */
+export interface LinkSuggestion {
+  /** The existing file */
+  path: string;
+  /** Text to put in place of the broken `href` */
+  replacement: string;
+}
+
+/**
+ * A link to a file that doesn't exist.
+ *
+ * This interface must match the `BrokenLink` struct in `src-tauri/src/core/broken_links.rs`.
+ */
+export interface BrokenLink {
+  /** The artifact (or blueprint.json) containing the link */
+  source: string;
+  /** 1-based line; absent for blueprint task files */
+  line?: number;
+  kind: 'inline' | 'reference' | 'stub' | 'wikilink' | 'taskFile';
+  /** The link as written */
+  href: string;
+  text: string;
+  /** Where the link pointed (empty for unresolved wikilinks) */
+  target: string;
+  /** Nearest matches, best first */
+  suggestions: LinkSuggestion[];
+}
+
+/**
+ * Result of `check_broken_links`.
+ *
+ * This interface must match the `BrokenLinkReport` struct in `src-tauri/src/core/broken_links.rs`.
+ */
+export interface BrokenLinkReport {
+  filesChecked: number;
+  linksChecked: number;
+  broken: BrokenLink[];
+}
+
+/**
+ * An artifact linking to another.
+ *
+ * This interface must match the `Backlink` struct in `src-tauri/src/core/link_graph.rs`.
+ */
+export interface Backlink {
+  source: string;
+  sourceName: string;
+  text: string;
+  line: number;
+  kind: 'inline' | 'reference' | 'stub' | 'wikilink';
+}
+
+/**
+ * What a destructive command would do, returned when it's called with `dryRun`.
+ *
+ * This interface must match the `DryRunReport` struct in `src-tauri/src/core/dry_run.rs`.
+ */
+export interface DryRunReport {
+  deletedFiles: string[];
+  createdFiles: string[];
+  overwrittenFiles: string[];
+  deletedRows: { table: string; id: string; label: string }[];
+  deletedRemoteFiles: { repository: string; path: string }[];
+}
+
+/**
+ * Tombstone for a trashed artifact in `.bluekit/.trash`.
+ *
+ * This interface must match the `TrashEntry` struct in `src-tauri/src/core/trash.rs`.
+ */
+export interface TrashEntry {
+  id: string;
+  /** Where the file was, relative to `.bluekit` */
+  originalPath: string;
+  /** File name inside the trash directory */
+  trashedName: string;
+  /** Unix seconds */
+  deletedAt: number;
+  /** Bytes, summed over the files of a folder */
+  size: number;
+  /** A whole folder was trashed (a scrapbook folder) */
+  isFolder: boolean;
 }
 
 /**
@@ -179,6 +652,72 @@ export interface ProjectEntry {
   path: string;
 }
 
+/**
+ * A problem found in the project registry.
+ *
+ * This interface must match the `RegistryIssue` struct in `src-tauri/src/core/registry.rs`.
+ */
+export interface RegistryIssue {
+  kind: 'malformed' | 'missing_path' | 'duplicate_id' | 'relative_path';
+  /** Position of the entry in the file */
+  index: number;
+  id: string | null;
+  path: string | null;
+  message: string;
+  /** What a repair does (or did) about it */
+  action: 'removed' | 'fixed';
+}
+
+/**
+ * Result of checking (and optionally repairing) the project registry.
+ *
+ * This interface must match the `RegistryReport` struct in `src-tauri/src/core/registry.rs`.
+ */
+export interface RegistryReport {
+  registryPath: string;
+  /** Entries in the file as found */
+  entryCount: number;
+  issues: RegistryIssue[];
+  /** Whether the repaired registry was written */
+  repaired: boolean;
+  backupPath: string | null;
+}
+
+/**
+ * What a registry sync changed.
+ *
+ * This interface must match the `RegistrySyncSummary` struct in `src-tauri/src/db/project_operations.rs`.
+ */
+export interface RegistrySyncSummary {
+  /** Registry entries added to the database */
+  imported: number;
+  /** Database projects added to the registry */
+  exported: number;
+  /** Registry entries rewritten to match the database */
+  updated: number;
+  /** Registry entries dropped because their project is archived */
+  removed: number;
+}
+
+/**
+ * Progress update for a long-running operation, sent on `operation-progress`
+ * and on the operation's own `operation-progress/<operationId>` channel.
+ *
+ * This interface must match the `ProgressUpdate` struct in `src-tauri/src/core/progress.rs`.
+ */
+export interface ProgressUpdate {
+  operationId: string;
+  /** Operation kind, e.g. "library_sync" or "clone_creation" */
+  kind: string;
+  /** Current step, e.g. "cloning" or "copying" */
+  phase: string;
+  current: number;
+  /** Null while the amount of work is still unknown */
+  total: number | null;
+  message: string;
+  status: 'running' | 'completed' | 'failed' | 'cancelled';
+}
+
 /**
  * Type definition for a scrapbook item (folder or file).
  *
@@ -367,6 +906,10 @@ export interface Project {
   lastOpenedAt?: number;
   /** Whether this project is the vault */
   isVault?: boolean;
+  /** Position in the user's manual ordering (null until the list is arranged) */
+  order?: number | null;
+  /** Whether the project is archived (hidden from listings by default) */
+  archived?: boolean;
 }
 
 /**
@@ -433,6 +976,49 @@ export interface Checkpoint {
   updatedAt: number;
 }
 
+/**
+ * A file changed between two checkpoints.
+ *
+ * This interface must match the `GitFileChange` struct in `src-tauri/src/integrations/git/operations.rs`.
+ */
+export interface CheckpointFileChange {
+  /** Path in the newer commit (the old path for deletions) */
+  path: string;
+  /** Path in the older commit, for renames and copies */
+  oldPath?: string;
+  status: 'added' | 'modified' | 'deleted' | 'renamed' | 'copied' | 'typeChanged';
+  additions: number;
+  deletions: number;
+  /** Binary files have no line counts */
+  binary: boolean;
+}
+
+/**
+ * Changes between two checkpoints (or a checkpoint and HEAD).
+ *
+ * This interface must match the `GitDiffSummary` struct in `src-tauri/src/integrations/git/operations.rs`.
+ */
+export interface CheckpointDiff {
+  fromSha: string;
+  toSha: string;
+  files: CheckpointFileChange[];
+  filesChanged: number;
+  additions: number;
+  deletions: number;
+}
+
+/**
+ * A checkpoint restored into a new working copy.
+ *
+ * This interface must match the `RestoredCheckpoint` struct in `src-tauri/src/db/checkpoint_operations.rs`.
+ */
+export interface RestoredCheckpoint {
+  /** The project registered for the working copy */
+  project: Project;
+  /** The new project's checkpoint at the restored commit */
+  checkpoint: Checkpoint;
+}
+
 /**
  * Type definition for GitHub user information (simplified version from commit responses).
  *
@@ -518,3 +1104,684 @@ export interface BookmarksData {
   items: BookmarkItem[];
 }
 
+/**
+ * Application settings stored in `~/.bluekit/settings.json`.
+ *
+ * This interface must match the `AppSettings` struct in `src-tauri/src/core/settings.rs`.
+ */
+export interface AppSettings {
+  fileSizeLimits: {
+    /** Artifacts larger than this are listed without their content */
+    listContentMaxBytes: number;
+    /** `read_file` refuses files larger than this */
+    readMaxBytes: number;
+  };
+  editor: {
+    /** 'env' uses the `$VISUAL` or `$EDITOR` command */
+    editor: 'vscode' | 'cursor' | 'vim' | 'env' | 'custom';
+    /** Command for 'custom', with `{path}` and `{line}` placeholders */
+    customCommand: string | null;
+  };
+  trash: {
+    /** Days trashed files are kept; 0 keeps them forever */
+    retentionDays: number;
+  };
+  scan: {
+    /** Follow symlinks inside `.bluekit` */
+    followSymlinks: boolean;
+  };
+  reminders: {
+    /** Show a system notification when a task reminder fires */
+    nativeNotifications: boolean;
+  };
+  /** SQLite connection tuning; applied on the next launch */
+  database: {
+    /** Write-ahead logging, so readers don't block the writer */
+    walMode: boolean;
+    /** How long to wait on a locked database before failing */
+    busyTimeoutMs: number;
+    /** Connection pool size */
+    maxConnections: number;
+  };
+  walkthroughs: {
+    /** Mirror status and takeaway progress into each walkthrough file's front matter */
+    syncProgressToFrontMatter: boolean;
+  };
+  storage: {
+    /** Data directory from the next launch; null for `~/.bluekit`. Change it with `set_data_directory` */
+    dataDirectory: string | null;
+  };
+}
+
+/**
+ * Result of `get_data_directory` and `set_data_directory`.
+ *
+ * This interface must match the `DataDirectory` struct in `src-tauri/src/core/data_dir.rs`.
+ */
+export interface DataDirectory {
+  /** The directory in use this session */
+  path: string;
+  /** The directory used from the next launch */
+  configuredPath: string;
+  /** Where the configured directory comes from: `BLUEKIT_DATA_DIR`, the settings, or the default */
+  source: 'env' | 'settings' | 'default';
+  defaultPath: string;
+  /** Data moved into the configured directory on the next launch */
+  pendingMigrationFrom: string | null;
+}
+
+/**
+ * Result of `preview_data_directory_change`.
+ *
+ * This interface must match the `DataMigrationPlan` struct in `src-tauri/src/core/data_dir.rs`.
+ */
+export interface DataMigrationPlan {
+  from: string;
+  to: string;
+  entries: {
+    name: string;
+    sizeBytes: number;
+    /** The target already has an entry with this name */
+    conflict: boolean;
+  }[];
+  totalBytes: number;
+  /** Free space on the target's volume, if it could be read */
+  availableBytes: number | null;
+  /** Reasons the move would be refused; empty when it can go ahead */
+  problems: string[];
+}
+
+/** Kind of record found by `global_search` */
+export type GlobalSearchKind = 'task' | 'plan' | 'milestone' | 'walkthrough' | 'artifact';
+
+/**
+ * A hit from `global_search`.
+ *
+ * This interface must match the `GlobalSearchHit` struct in `src-tauri/src/core/global_search.rs`.
+ */
+export interface GlobalSearchHit {
+  kind: GlobalSearchKind;
+  /** Record ID; the file path for artifacts */
+  id: string;
+  title: string;
+  /** HTML-escaped title with matched words wrapped in `<mark>` */
+  titleHighlight: string;
+  /** Where the hit lives, e.g. "Plan › Phase" for a milestone */
+  context: string | null;
+  projectId: string | null;
+  /** Plan of a milestone */
+  planId?: string;
+  /** File (or plan folder) to open */
+  path: string | null;
+  /** Higher is better; comparable across kinds */
+  score: number;
+}
+
+/**
+ * Result of `db_maintenance`.
+ *
+ * This interface must match the `DbHealthReport` struct in `src-tauri/src/db/maintenance.rs`.
+ */
+export interface DbHealthReport {
+  /** Whether `PRAGMA integrity_check` reported "ok" */
+  integrityOk: boolean;
+  /** Problems found by the integrity check (empty when ok) */
+  integrityMessages: string[];
+  /** Skipped when the integrity check fails */
+  vacuumed: boolean;
+  analyzed: boolean;
+  sizeBeforeBytes: number;
+  sizeAfterBytes: number;
+  /** Unused pages before the vacuum */
+  freePagesBefore: number;
+  durationMs: number;
+}
+
+/**
+ * Result of `get_schema_version`.
+ *
+ * This interface must match the `SchemaVersion` struct in `src-tauri/src/db/migrations.rs`.
+ */
+export interface SchemaVersion {
+  /** Name of the last applied migration */
+  version: string | null;
+  /** Number of applied migrations */
+  applied: number;
+  /** Migrations this build knows about that haven't run */
+  pending: string[];
+  /** Name of the newest migration in this build */
+  latest: string | null;
+}
+
+/**
+ * Result of the database encryption commands.
+ *
+ * This interface must match the `EncryptionStatus` struct in `src-tauri/src/db/encryption.rs`.
+ */
+export interface DatabaseEncryptionStatus {
+  /** Whether this build's SQLite is SQLCipher */
+  available: boolean;
+  /** Whether the database file is encrypted */
+  encrypted: boolean;
+  /** The database is converted on the next launch */
+  pendingRestart: boolean;
+  /** The database is closed until it is unlocked */
+  locked: boolean;
+}
+
+/**
+ * An artifact created by `create_kit`, `create_agent` or `create_diagram`.
+ *
+ * This interface must match the `ArtifactDto` struct in `src-tauri/src/db/artifact_operations.rs`.
+ */
+export interface CreatedArtifact {
+  id: string;
+  projectId: string;
+  name: string;
+  /** 'kit', 'agent' or 'diagram' */
+  artifactType: string;
+  /** Absolute path of the new file */
+  filePath: string;
+  /** Path relative to the project root, e.g. `.bluekit/kits/my-kit.md` */
+  relativePath: string;
+  description: string | null;
+  tags: string[];
+  /** Unix timestamp in seconds */
+  createdAt: number;
+  /** Unix timestamp in seconds */
+  updatedAt: number;
+}
+
+/**
+ * An agent registered for a project, returned by `get_project_agents`.
+ *
+ * This interface must match the `AgentDto` struct in `src-tauri/src/db/agent_operations.rs`.
+ */
+export interface Agent {
+  id: string;
+  projectId: string;
+  filePath: string;
+  name: string;
+  description: string | null;
+  /** Model from the front matter, if set */
+  model: string | null;
+  /** Tools from the front matter */
+  tools: string[];
+  /** Unix timestamp in seconds */
+  createdAt: number;
+  /** Unix timestamp in seconds */
+  updatedAt: number;
+}
+
+/**
+ * Result of `validate_agent`.
+ *
+ * This interface must match the `AgentValidationDto` struct in `src-tauri/src/db/agent_operations.rs`.
+ */
+export interface AgentValidation {
+  valid: boolean;
+  errors: string[];
+  warnings: string[];
+}
+
+/** Formats `export_agent` can write */
+export type AgentExportFormat = 'claude' | 'cursor';
+
+/**
+ * Result of `export_agent`.
+ *
+ * This interface must match the `AgentExportDto` struct in `src-tauri/src/db/agent_operations.rs`.
+ */
+export interface AgentExport {
+  format: AgentExportFormat;
+  /** Path of the written file */
+  filePath: string;
+  /** What was written */
+  content: string;
+}
+
+/**
+ * One copy of a kit in another project, returned by `get_kit_usage`.
+ *
+ * This interface must match the `KitUsageDto` struct in `src-tauri/src/db/kit_usage_operations.rs`.
+ */
+export interface KitUsage {
+  id: string;
+  /** The source kit */
+  kitPath: string;
+  targetProjectPath: string;
+  /** Database id of the target project, if it's registered */
+  targetProjectId: string | null;
+  targetFilePath: string;
+  /** Hash of the kit's content when it was copied */
+  contentHash: string;
+  /** Unix timestamp in seconds */
+  copiedAt: number;
+  /** The copy no longer exists on disk */
+  targetMissing: boolean;
+  /** The source kit has been edited since this copy was made */
+  sourceChanged: boolean;
+}
+
+/**
+ * A copied or pulled artifact whose origin has moved on, returned by `check_outdated_copies`.
+ *
+ * This interface must match the `OutdatedCopyDto` struct in `src-tauri/src/db/provenance_operations.rs`.
+ */
+export interface OutdatedCopy {
+  /** The copy in this project */
+  localPath: string;
+  /** 'local' for a file copied from another project, 'library' for a pulled variation */
+  sourceKind: 'local' | 'library';
+  /** Source file, for local copies */
+  sourcePath: string | null;
+  /** Library catalog, for pulled copies */
+  catalogId: string | null;
+  /** Variation that was pulled */
+  variationId: string | null;
+  /** Newest variation in the catalog */
+  latestVariationId: string | null;
+  /** Hash of the content when it was copied */
+  copiedHash: string;
+  /** Hash of the origin's current content */
+  sourceHash: string | null;
+  /** Unix timestamp in seconds */
+  copiedAt: number;
+  /** The origin no longer exists (file deleted or catalog removed) */
+  sourceMissing: boolean;
+  /** The local copy has been edited since it was copied */
+  locallyModified: boolean;
+}
+
+/**
+ * Result of `move_artifact_between_projects`.
+ *
+ * This interface must match the `MoveArtifactResult` struct in `src-tauri/src/commands.rs`.
+ */
+export interface MoveArtifactResult {
+  /** Path of the artifact in the target project */
+  newPath: string;
+  /** Number of records of each kind moved to the target project */
+  transferred: {
+    walkthroughs: number;
+    agents: number;
+    resources: number;
+    kitUsage: number;
+    provenance: number;
+  };
+}
+
+/**
+ * Result of `read_file_with_encoding`.
+ *
+ * This interface must match the `DecodedText` struct in `src-tauri/src/core/encoding.rs`.
+ */
+export interface DecodedText {
+  content: string;
+  /** WHATWG encoding label, e.g. "UTF-8", "UTF-16LE", "windows-1252" */
+  encoding: string;
+  /** Set when the file was not clean UTF-8 */
+  warning: string | null;
+}
+
+/**
+ * Options for `copy_artifact_to_clipboard`.
+ *
+ * This interface must match the `ClipboardOptions` struct in `src-tauri/src/commands.rs`.
+ */
+export interface ClipboardOptions {
+  /** Drop the YAML front matter (only applies to the 'raw' format) */
+  stripFrontMatter?: boolean;
+  /**
+   * 'raw' (default), 'prompt' (body only, e.g. an agent's prompt) or
+   * 'share' (title and description header followed by the body)
+   */
+  format?: 'raw' | 'prompt' | 'share';
+}
+
+/**
+ * A running terminal session, returned by `list_terminal_sessions`.
+ *
+ * This interface must match the `TerminalSessionInfo` struct in `src-tauri/src/core/terminal.rs`.
+ */
+export interface TerminalSessionInfo {
+  sessionId: string;
+  projectPath: string;
+  command: string;
+  /** Unix timestamp in seconds */
+  startedAt: number;
+}
+
+/**
+ * Payload of the `terminal-output` event: a chunk of a session's output.
+ */
+export interface TerminalOutput {
+  sessionId: string;
+  data: string;
+}
+
+/**
+ * Payload of the `terminal-exit` event, sent once when a session's process exits.
+ */
+export interface TerminalExit {
+  sessionId: string;
+  exitCode: number | null;
+  success: boolean;
+  /** The process was stopped with `kill_command` */
+  killed: boolean;
+}
+
+/**
+ * Result of `install_git_hooks`.
+ *
+ * This interface must match the `GitHookInstallResult` struct in `src-tauri/src/integrations/git/hooks.rs`.
+ */
+export interface GitHookInstallResult {
+  /** Path of the installed pre-commit hook */
+  hookPath: string;
+  /** An existing non-BlueKit hook was moved here and is still run first */
+  previousHookPath: string | null;
+}
+
+/** Work a scheduled job can do */
+export type ScheduledJobType = 'workspace_sync' | 'backup' | 'update_check' | 'trash_purge';
+
+/**
+ * A scheduled job, returned by the scheduler commands.
+ *
+ * This interface must match the `ScheduledJobDto` struct in `src-tauri/src/db/scheduler_operations.rs`.
+ */
+export interface ScheduledJob {
+  id: string;
+  name: string;
+  jobType: ScheduledJobType;
+  /** Set for interval jobs */
+  intervalSeconds: number | null;
+  /** Set for cron jobs (five fields, local time) */
+  cronExpression: string | null;
+  /** Job-specific settings, e.g. `{ keep: 14 }` for backups */
+  params: Record<string, unknown>;
+  enabled: boolean;
+  /** Unix timestamp in seconds */
+  nextRunAt: number | null;
+  /** Unix timestamp in seconds */
+  lastRunAt: number | null;
+  lastStatus: 'success' | 'failed' | null;
+  lastMessage: string | null;
+  /** Unix timestamp in seconds */
+  createdAt: number;
+  /** Unix timestamp in seconds */
+  updatedAt: number;
+}
+
+/**
+ * Changes to a scheduled job; absent fields are kept.
+ *
+ * This interface must match the `ScheduledJobUpdate` struct in `src-tauri/src/db/scheduler_operations.rs`.
+ */
+export interface ScheduledJobUpdate {
+  name?: string;
+  enabled?: boolean;
+  /** Setting an interval replaces any cron expression */
+  intervalSeconds?: number;
+  /** Setting a cron expression replaces any interval */
+  cronExpression?: string;
+  params?: Record<string, unknown>;
+}
+
+/**
+ * Payload of the `scheduled-job-finished` event, sent after every run.
+ */
+export interface ScheduledJobFinished {
+  jobId: string;
+  jobType: ScheduledJobType;
+  success: boolean;
+  message: string;
+}
+
+/**
+ * Outcome of one check in the `get_app_health` report.
+ *
+ * This interface must match the `HealthCheck` struct in `src-tauri/src/core/health.rs`.
+ */
+export interface HealthCheck {
+  status: 'ok' | 'warning' | 'error' | 'skipped';
+  message: string;
+}
+
+/**
+ * Result of `get_app_health`, for the diagnostics screen.
+ *
+ * This interface must match the `AppHealthReport` struct in `src-tauri/src/core/health.rs`.
+ */
+export interface AppHealthReport {
+  /** Worst status across all checks */
+  status: 'ok' | 'warning' | 'error';
+  /** Unix timestamp in seconds */
+  checkedAt: number;
+  database: {
+    check: HealthCheck;
+    path: string;
+    /** Database file plus its WAL/shared-memory files */
+    sizeBytes: number;
+  };
+  watchers: {
+    check: HealthCheck;
+    total: number;
+    active: number;
+    /** Event names of watchers that have stopped */
+    inactive: string[];
+  };
+  github: HealthCheck;
+  supabase: HealthCheck;
+  disk: {
+    check: HealthCheck;
+    /** Total size of `~/.bluekit` */
+    bluekitDirBytes: number;
+    /** Free space on the volume holding `~/.bluekit` */
+    availableBytes: number | null;
+  };
+  jobs: {
+    check: HealthCheck;
+    /** Enabled jobs whose next run is already due */
+    pending: number;
+    running: number;
+    enabled: number;
+  };
+}
+
+/**
+ * A record or file found out of sync by `reconcile_project`.
+ *
+ * This interface must match the `OrphanDto` struct in `src-tauri/src/db/reconcile_operations.rs`.
+ */
+export interface ReconcileOrphan {
+  kind: 'walkthrough' | 'plan' | 'plan_document' | 'library_resource' | 'bookmark';
+  /**
+   * 'missing_file' (record points at a file that is gone) or
+   * 'untracked_file' (file exists but has no record)
+   */
+  issue: 'missing_file' | 'untracked_file';
+  /** The record, for 'missing_file' */
+  recordId: string | null;
+  path: string;
+  /** Repaired by this call (only with `fix`) */
+  fixed: boolean;
+}
+
+/**
+ * Result of `reconcile_project`.
+ *
+ * This interface must match the `ReconcileReportDto` struct in `src-tauri/src/db/reconcile_operations.rs`.
+ */
+export interface ReconcileReport {
+  projectPath: string;
+  /** Null if the project isn't registered (only file-based data is checked) */
+  projectId: string | null;
+  orphans: ReconcileOrphan[];
+  fixedCount: number;
+}
+
+/**
+ * Result of `bootstrap_starter_content`.
+ *
+ * This interface must match the `BootstrapResult` struct in `src-tauri/src/core/starter_content.rs`.
+ */
+export interface BootstrapResult {
+  /** False if this isn't a first run and nothing was created */
+  seeded: boolean;
+  /** The starter project, when one was created */
+  projectId: string | null;
+  projectPath: string | null;
+}
+
+/**
+ * Where the user left off, saved with `save_session_state`.
+ *
+ * This interface must match the `SessionStateDto` struct in `src-tauri/src/db/session_operations.rs`.
+ */
+export interface SessionState {
+  activeProjectId: string | null;
+  /** Open artifacts in tab order */
+  openArtifactPaths: string[];
+  activeArtifactPath: string | null;
+  selectedPlanId: string | null;
+  selectedWalkthroughId: string | null;
+  /** Unix timestamp in seconds; null until a session has been saved */
+  updatedAt?: number | null;
+}
+
+/**
+ * One problem found by `lint_artifacts`.
+ *
+ * This interface must match the `LintFinding` struct in `src-tauri/src/core/lint.rs`.
+ */
+export interface LintFinding {
+  severity: 'error' | 'warning';
+  rule:
+    | 'broken_link'
+    | 'missing_asset'
+    | 'missing_diagram'
+    | 'dangling_wikilink'
+    | 'broken_link_stub'
+    | 'front_matter'
+    | 'missing_front_matter';
+  message: string;
+  /** 1-based line, when the finding points at one */
+  line: number | null;
+}
+
+/**
+ * Result of `lint_artifacts`.
+ *
+ * This interface must match the `LintReport` struct in `src-tauri/src/core/lint.rs`.
+ */
+export interface LintReport {
+  filesChecked: number;
+  errorCount: number;
+  warningCount: number;
+  /** Only files with findings; paths are relative to the project root */
+  files: Array<{ path: string; findings: LintFinding[] }>;
+}
+
+/**
+ * Result of `generate_progress_report`.
+ *
+ * This interface must match the `ProgressReport` struct in `src-tauri/src/core/progress_report.rs`.
+ */
+export interface ProgressReport {
+  /** Where the Markdown was written */
+  path: string;
+  markdown: string;
+  /** Start of the period, Unix seconds */
+  since: number;
+  /** End of the period, Unix seconds */
+  until: number;
+  completedMilestones: number;
+  finishedTasks: number;
+  newArtifacts: number;
+  checkpoints: number;
+}
+
+/**
+ * A file matching `search_project_artifacts`, with its best lines.
+ *
+ * This interface must match the `SearchResult` struct in `src-tauri/src/core/search_index.rs`.
+ */
+export interface ArtifactSearchResult {
+  path: string;
+  /** File name without the extension */
+  name: string;
+  /** Higher is better; results come sorted by it */
+  score: number;
+  /** Matching lines in the whole file */
+  matchCount: number;
+  /** Up to five matching lines */
+  matches: Array<{
+    /** 1-based line number */
+    line: number;
+    snippet: string;
+  }>;
+}
+
+/**
+ * Filters and page bounds for `get_project_artifacts_page`.
+ *
+ * This interface must match the `ArtifactQuery` struct in `src-tauri/src/commands.rs`.
+ */
+export interface ArtifactQuery {
+  /** Index of the first artifact to return */
+  offset?: number;
+  /** Page size (default 100) */
+  limit?: number;
+  /** Only artifacts under this directory, relative to .bluekit (e.g. "kits") */
+  subdirectory?: string;
+  /** Only these extensions, without the dot (e.g. ["md"]) */
+  extensions?: string[];
+  /** Only artifacts modified at or after this Unix time, in milliseconds */
+  modifiedSince?: number;
+  /** Set to false to list front matter and metadata only (default true) */
+  includeContent?: boolean;
+  /** Set to true to add read stats to markdown artifacts (default false) */
+  includeReadStats?: boolean;
+}
+
+/**
+ * One page of artifacts, returned by `get_project_artifacts_page`.
+ *
+ * This interface must match the `ArtifactPage` struct in `src-tauri/src/commands.rs`.
+ */
+export interface ArtifactPage {
+  artifacts: ArtifactFile[];
+  /** Number of artifacts matching the filters, across all pages */
+  total: number;
+  offset: number;
+  limit: number;
+  hasMore: boolean;
+}
+
+/**
+ * Result of `rename_resource`.
+ *
+ * This interface must match the `RenameResourceResult` struct in `src-tauri/src/commands.rs`.
+ */
+export interface RenameResourceResult {
+  newPath: string;
+  /** Artifacts and blueprint.json files whose references were rewritten */
+  updatedFiles: string[];
+  /** Total number of references rewritten across `updatedFiles` */
+  referenceCount: number;
+}
+
+/**
+ * Matches in one project, returned by `search_all_projects`.
+ *
+ * This interface must match the `ProjectSearchResults` struct in `src-tauri/src/commands.rs`.
+ */
+export interface ProjectSearchResults {
+  projectId: string;
+  projectName: string;
+  projectPath: string;
+  /** Matching files, best first */
+  results: ArtifactSearchResult[];
+}
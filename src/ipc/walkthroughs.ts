@@ -9,7 +9,13 @@ import type {
     WalkthroughDetails,
     Takeaway,
     WalkthroughNote,
+    WalkthroughNoteFormat,
+    NoteAttachment,
+    WalkthroughStep,
+    StepReanchor,
+    WalkthroughHtmlExport,
 } from '@/types/walkthrough';
+import type { Task, TaskPriority } from '@/types/task';
 
 // ============================================================================
 // WALKTHROUGH CRUD OPERATIONS
@@ -79,10 +85,25 @@ export async function invokeUpdateWalkthrough(
 }
 
 /**
- * Delete a walkthrough (removes file and database records)
+ * Delete a walkthrough. It can be restored until the trash retention period passes;
+ * with `permanent`, its file and database records are removed right away.
  */
-export async function invokeDeleteWalkthrough(walkthroughId: string): Promise<void> {
-    return await invokeWithTimeout<void>('delete_walkthrough', { walkthroughId });
+export async function invokeDeleteWalkthrough(walkthroughId: string, permanent?: boolean): Promise<void> {
+    return await invokeWithTimeout<void>('delete_walkthrough', { walkthroughId, permanent });
+}
+
+/**
+ * Restore a deleted walkthrough
+ */
+export async function invokeRestoreWalkthrough(walkthroughId: string): Promise<Walkthrough> {
+    return await invokeWithTimeout<Walkthrough>('restore_walkthrough', { walkthroughId });
+}
+
+/**
+ * Deleted walkthroughs of a project that can still be restored, most recently deleted first
+ */
+export async function invokeGetDeletedWalkthroughs(projectId: string): Promise<Walkthrough[]> {
+    return await invokeWithTimeout<Walkthrough[]>('get_deleted_walkthroughs', { projectId });
 }
 
 // ============================================================================
@@ -146,6 +167,14 @@ export async function invokeReorderWalkthroughTakeaways(
     });
 }
 
+/**
+ * Create a task from a takeaway in the walkthrough's project and link them.
+ * Returns the already linked task if there is one.
+ */
+export async function invokeConvertTakeawayToTask(takeawayId: string, priority?: TaskPriority): Promise<Task> {
+    return await invokeWithTimeout<Task>('convert_takeaway_to_task', { takeawayId, priority });
+}
+
 // ============================================================================
 // NOTE OPERATIONS
 // ============================================================================
@@ -158,34 +187,175 @@ export async function invokeGetWalkthroughNotes(walkthroughId: string): Promise<
 }
 
 /**
- * Add a note to a walkthrough
+ * Add a note to a walkthrough (plain text unless `format` says otherwise)
  */
 export async function invokeAddWalkthroughNote(
     walkthroughId: string,
-    content: string
+    content: string,
+    format?: WalkthroughNoteFormat
 ): Promise<WalkthroughNote> {
     return await invokeWithTimeout<WalkthroughNote>('add_walkthrough_note', {
         walkthroughId,
         content,
+        format,
     });
 }
 
 /**
- * Update a walkthrough note
+ * Update a walkthrough note, and its format if given
  */
 export async function invokeUpdateWalkthroughNote(
     noteId: string,
-    content: string
+    content: string,
+    format?: WalkthroughNoteFormat
 ): Promise<WalkthroughNote> {
     return await invokeWithTimeout<WalkthroughNote>('update_walkthrough_note', {
         noteId,
         content,
+        format,
     });
 }
 
 /**
- * Delete a walkthrough note
+ * Delete a walkthrough note and its attachments
  */
 export async function invokeDeleteWalkthroughNote(noteId: string): Promise<void> {
     return await invokeWithTimeout<void>('delete_walkthrough_note', { noteId });
 }
+
+/**
+ * Attach a file to a walkthrough note. Insert the returned `markdown` into
+ * a markdown note to link it.
+ */
+export async function invokeAttachWalkthroughNoteFile(
+    noteId: string,
+    sourcePath: string
+): Promise<NoteAttachment> {
+    return await invokeWithTimeout<NoteAttachment>('attach_walkthrough_note_file', {
+        noteId,
+        sourcePath,
+    });
+}
+
+/**
+ * Delete a note attachment by file name
+ */
+export async function invokeDeleteWalkthroughNoteAttachment(noteId: string, name: string): Promise<void> {
+    return await invokeWithTimeout<void>('delete_walkthrough_note_attachment', { noteId, name });
+}
+
+// ============================================================================
+// STEP OPERATIONS
+// ============================================================================
+
+/**
+ * Get walkthrough steps in order
+ */
+export async function invokeGetWalkthroughSteps(walkthroughId: string): Promise<WalkthroughStep[]> {
+    return await invokeWithTimeout<WalkthroughStep[]>('get_walkthrough_steps', { walkthroughId });
+}
+
+/**
+ * Add a step anchored to lines of a file (absolute, or relative to the
+ * project root) at the project's current commit
+ */
+export async function invokeAddWalkthroughStep(
+    walkthroughId: string,
+    title: string,
+    filePath: string,
+    startLine: number,
+    endLine: number,
+    description?: string
+): Promise<WalkthroughStep> {
+    return await invokeWithTimeout<WalkthroughStep>('add_walkthrough_step', {
+        walkthroughId,
+        title,
+        description,
+        filePath,
+        startLine,
+        endLine,
+    });
+}
+
+/**
+ * Update a walkthrough step. A new file or line range (give both lines)
+ * anchors it to the current commit.
+ */
+export async function invokeUpdateWalkthroughStep(
+    stepId: string,
+    updates: {
+        title?: string;
+        description?: string | null;
+        filePath?: string;
+        startLine?: number;
+        endLine?: number;
+    }
+): Promise<WalkthroughStep> {
+    return await invokeWithTimeout<WalkthroughStep>('update_walkthrough_step', {
+        stepId,
+        ...updates,
+    });
+}
+
+/**
+ * Delete a walkthrough step
+ */
+export async function invokeDeleteWalkthroughStep(stepId: string): Promise<void> {
+    return await invokeWithTimeout<void>('delete_walkthrough_step', { stepId });
+}
+
+/**
+ * Reorder walkthrough steps
+ */
+export async function invokeReorderWalkthroughSteps(
+    walkthroughId: string,
+    stepIds: string[]
+): Promise<void> {
+    return await invokeWithTimeout<void>('reorder_walkthrough_steps', { walkthroughId, stepIds });
+}
+
+/**
+ * Move each step to where its lines are at the project's HEAD, following them
+ * with git blame
+ */
+export async function invokeReanchorWalkthroughSteps(walkthroughId: string): Promise<StepReanchor[]> {
+    return await invokeWithTimeout<StepReanchor[]>('reanchor_walkthrough_steps', { walkthroughId });
+}
+
+/**
+ * Write a walkthrough's status and progress into its file's front matter.
+ * Happens on every change when `walkthroughs.syncProgressToFrontMatter` is on;
+ * call this to bring a file up to date right after turning it on.
+ */
+export async function invokeSyncWalkthroughFrontMatter(walkthroughId: string): Promise<boolean> {
+    return await invokeWithTimeout<boolean>('sync_walkthrough_front_matter', { walkthroughId });
+}
+
+/**
+ * Get the mermaid sources in a walkthrough (its markdown and notes)
+ */
+export async function invokeGetWalkthroughDiagrams(walkthroughId: string): Promise<string[]> {
+    return await invokeWithTimeout<string[]>('get_walkthrough_diagrams', { walkthroughId });
+}
+
+/**
+ * Export a walkthrough as a self-contained HTML file next to its markdown.
+ * `render` turns a mermaid source into an SVG (e.g. with `mermaid.render`);
+ * without it, or when it fails, diagrams are exported as source.
+ */
+export async function invokeExportWalkthroughHtml(
+    walkthroughId: string,
+    render?: (source: string) => Promise<string>
+): Promise<WalkthroughHtmlExport> {
+    const diagrams: Record<string, string> = {};
+    if (render) {
+        for (const source of await invokeGetWalkthroughDiagrams(walkthroughId)) {
+            try {
+                diagrams[source] = await render(source);
+            } catch (error) {
+                console.warn('Failed to render diagram for export:', error);
+            }
+        }
+    }
+    return await invokeWithTimeout<WalkthroughHtmlExport>('export_walkthrough_html', { walkthroughId, diagrams }, 30000);
+}
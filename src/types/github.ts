@@ -377,3 +377,85 @@ export interface PullResult {
 }
 
 
+
+/**
+ * Outcome of one resource in a bulk publish.
+ */
+export interface BulkPublishItem {
+  resource_id: string;
+  result: PublishResult | null;
+  error: string | null;
+}
+
+/**
+ * Outcome of one variation in a bulk pull.
+ */
+export interface BulkPullItem {
+  variation_id: string;
+  result: PullResult | null;
+  error: string | null;
+}
+
+/**
+ * One entry in a workspace's activity feed.
+ */
+export interface ActivityEvent {
+  kind:
+    | 'variation_published'
+    | 'catalog_created'
+    | 'catalog_deleted'
+    | 'collection_created'
+    | 'folder_created'
+    | 'folder_deleted'
+    | 'folder_changed'
+    | 'other';
+  /** Unix timestamp in seconds */
+  timestamp: number;
+  title: string;
+  actor: string | null;
+  catalog_id: string | null;
+  variation_id: string | null;
+  commit_sha: string | null;
+  source: 'database' | 'github';
+}
+
+/**
+ * Workspace activity feed, newest first.
+ */
+export interface WorkspaceActivity {
+  workspace_id: string;
+  events: ActivityEvent[];
+  /** Set when commits couldn't be read; the feed then only has database events */
+  github_error: string | null;
+}
+
+/**
+ * An artifact listed by a public library.
+ */
+export interface PublicCatalog {
+  name: string;
+  /** Path of the artifact in the repository */
+  path: string;
+  artifact_type: string;
+  description: string | null;
+  tags: string[];
+}
+
+/**
+ * A public BlueKit library: a GitHub repository with the `bluekit-library`
+ * topic or a `bluekit-index.json` at its root.
+ */
+export interface PublicLibrary {
+  owner: string;
+  repo: string;
+  full_name: string;
+  description: string | null;
+  html_url: string;
+  /** Only known for repositories found by topic */
+  stars: number | null;
+  has_topic: boolean;
+  has_index: boolean;
+  catalogs: PublicCatalog[];
+  /** Set if the catalogs couldn't be listed */
+  error: string | null;
+}
@@ -14,6 +14,7 @@ export interface Plan {
   createdAt: number;
   updatedAt: number;
   progress: number; // 0-100 based on milestone completion
+  deletedAt?: number; // Set on plans from getDeletedPlans
 }
 
 export interface PlanPhase {
@@ -25,6 +26,10 @@ export interface PlanPhase {
   status: 'pending' | 'in_progress' | 'completed';
   startedAt?: number;
   completedAt?: number;
+  /** When the phase should be done (Unix seconds) */
+  targetDate?: number;
+  /** Not completed and past its target date */
+  atRisk: boolean;
   createdAt: number;
   updatedAt: number;
 }
@@ -37,6 +42,8 @@ export interface PlanMilestone {
   orderIndex: number;
   completed: boolean;
   completedAt?: number;
+  /** Unix seconds */
+  dueAt?: number;
   createdAt: number;
   updatedAt: number;
 }
@@ -44,11 +51,14 @@ export interface PlanMilestone {
 export interface PlanDocument {
   id: string;
   planId: string;
+  phaseId?: string;
   filePath: string;
   fileName: string;
   createdAt: number;
   updatedAt: number;
   orderIndex: number;
+  /** Position among the documents of the same phase (or those without one) */
+  phaseOrderIndex: number;
 }
 
 export interface PlanLink {
@@ -58,6 +68,24 @@ export interface PlanLink {
   source: 'claude' | 'cursor';
   createdAt: number;
   updatedAt: number;
+  /** The linked file no longer exists */
+  stale: boolean;
+}
+
+/** One entry of the `plan-link-changed-{planId}` event payload */
+export interface PlanLinkChange {
+  path: string;
+  /** The file was deleted, so its link is now stale */
+  deleted: boolean;
+}
+
+/** Payload of the `linked-plans-changed` event */
+export interface LinkedPlansChanged {
+  source: 'claude' | 'cursor';
+  /** Plans linked to a changed file */
+  planIds: string[];
+  /** Every changed plan file */
+  paths: string[];
 }
 
 export interface PlanPhaseWithMilestones extends PlanPhase {
@@ -69,4 +97,76 @@ export interface PlanDetails extends Plan {
   documents: PlanDocument[];
   linkedPlans: PlanLink[];
   progress: number; // 0-100 based on milestone completion
+  /** Phases past their target date and not completed */
+  atRiskPhases: number;
+}
+
+/** An at-risk phase with the plan it belongs to */
+export interface AtRiskPhase {
+  planId: string;
+  planName: string;
+  projectId: string;
+  phase: PlanPhaseWithMilestones;
+  /** Whole days since the target date */
+  daysOverdue: number;
+}
+
+/** A plan written out as one markdown document by `export_plan_markdown` */
+export interface PlanExport {
+  /** The `<name>.export.md` file in the plan folder */
+  filePath: string;
+  content: string;
+}
+
+/** A mermaid diagram of a plan written by `generate_plan_diagram` */
+export interface PlanDiagram {
+  /** `plan-<name>.mmd` (or `plan-<name>-gantt.mmd`) in `.bluekit/diagrams/` */
+  filePath: string;
+  format: 'flowchart' | 'gantt';
+  content: string;
+}
+
+/** A milestone placed on the plan timeline */
+export interface TimelineMilestone {
+  id: string;
+  name: string;
+  completed: boolean;
+  /** Completion time, else the due date, if either is known */
+  at?: number;
+  dueAt?: number;
+  /** Not completed and past its due date */
+  overdue: boolean;
+}
+
+/** A phase's window on the plan timeline */
+export interface TimelinePhase {
+  id: string;
+  name: string;
+  status: string;
+  start: number;
+  /** Not started yet; the start follows the previous phase */
+  startEstimated: boolean;
+  end: number;
+  endKind: 'completed' | 'target' | 'ongoing';
+  targetDate?: number;
+  atRisk: boolean;
+  milestones: TimelineMilestone[];
+}
+
+/** Cumulative milestone completions at the end of a day */
+export interface TimelineTrendPoint {
+  /** YYYY-MM-DD (UTC) */
+  date: string;
+  completed: number;
+  total: number;
+}
+
+/** Timeline / Gantt data returned by `get_plan_timeline` */
+export interface PlanTimeline {
+  planId: string;
+  start: number;
+  end: number;
+  now: number;
+  phases: TimelinePhase[];
+  trend: TimelineTrendPoint[];
 }
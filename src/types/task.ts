@@ -19,5 +19,91 @@ export interface Task {
   status: TaskStatus;
   complexity?: TaskComplexity;
   type?: TaskType;
+  /** Due date (RFC 3339, UTC) */
+  dueAt?: string;
+  /** When to send a `task-reminder` event (RFC 3339, UTC); cleared once it fires */
+  remindAt?: string;
+  /** Board position within its status column; only set when listing a single project's tasks */
+  orderIndex?: number;
+  /** When the task was deleted (RFC 3339, UTC); only set on tasks from getDeletedTasks */
+  deletedAt?: string;
 }
 
+
+/**
+ * Changes applied to every task in a bulk update. Unset fields are left alone;
+ * add/remove lists apply after the matching replacement list.
+ * This interface must match the `TaskPatch` struct in `src-tauri/src/db/task_operations.rs`.
+ */
+export interface TaskPatch {
+  status?: TaskStatus;
+  priority?: TaskPriority;
+  /** Replaces each task's tags */
+  tags?: string[];
+  addTags?: string[];
+  removeTags?: string[];
+  /** Replaces each task's projects */
+  projectIds?: string[];
+  addProjectIds?: string[];
+  removeProjectIds?: string[];
+}
+
+/**
+ * One entry in a task's history (`db_get_task_activity`)
+ */
+export interface TaskActivity {
+  id: number;
+  taskId: string;
+  action: 'created' | 'updated' | 'deleted' | 'restored';
+  /** The changed field for 'updated': 'title', 'description', 'priority', 'status', 'complexity', 'type', 'tags', 'dueAt', 'remindAt' or 'projects' */
+  field?: string;
+  /** Previous value; tags and projects are JSON arrays */
+  oldValue?: string;
+  newValue?: string;
+  createdAt: string;
+}
+
+/**
+ * A task matching a full-text search (`db_search_tasks`)
+ */
+export interface TaskSearchHit {
+  task: Task;
+  /** Higher is better */
+  score: number;
+  /** HTML-escaped title with matches wrapped in `<mark>` */
+  titleHighlight: string;
+  /** HTML-escaped description excerpt around the matches, if the description matched */
+  descriptionSnippet?: string;
+}
+
+export type TaskSortField = 'createdAt' | 'updatedAt' | 'dueAt' | 'priority' | 'status' | 'title' | 'order';
+
+/**
+ * Which tasks a saved view shows. Empty lists don't filter; within a list any
+ * value matches, except tags with `matchAllTags`.
+ * This interface must match the `TaskFilter` struct in `src-tauri/src/db/saved_view_operations.rs`.
+ */
+export interface TaskFilter {
+  statuses?: TaskStatus[];
+  priorities?: TaskPriority[];
+  tags?: string[];
+  /** Require every tag instead of any */
+  matchAllTags?: boolean;
+  projectIds?: string[];
+  /** Keep tasks that only belong to archived projects */
+  includeArchived?: boolean;
+  /** `order` is the board order of a single-project view */
+  sort?: { field: TaskSortField; descending?: boolean } | null;
+}
+
+/**
+ * A named task filter.
+ * This interface must match the `SavedViewDto` struct in `src-tauri/src/db/saved_view_operations.rs`.
+ */
+export interface SavedView {
+  id: string;
+  name: string;
+  filter: TaskFilter;
+  createdAt: string;
+  updatedAt: string;
+}
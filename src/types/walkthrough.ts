@@ -13,6 +13,7 @@ export interface Walkthrough {
     createdAt: number;
     updatedAt: number;
     progress: number; // 0-100 based on takeaway completion
+    deletedAt?: number; // Set on walkthroughs from getDeletedWalkthroughs
 }
 
 export interface Takeaway {
@@ -24,17 +25,66 @@ export interface Takeaway {
     completed: boolean;
     completedAt?: number;
     createdAt: number;
+    /** Task made from this takeaway; completing either completes the other */
+    taskId?: string | null;
+}
+
+export type WalkthroughNoteFormat = 'text' | 'markdown';
+
+/** A file attached to a walkthrough note */
+export interface NoteAttachment {
+    path: string;
+    name: string;
+    size: number;
+    /** Relative to the walkthrough file, for links in the note's markdown */
+    relativePath: string;
+    isImage: boolean;
+    /** Ready-to-insert markdown link (an image link for images) */
+    markdown: string;
 }
 
 export interface WalkthroughNote {
     id: string;
     walkthroughId: string;
     content: string;
+    format: WalkthroughNoteFormat;
+    attachments: NoteAttachment[];
     createdAt: number;
     updatedAt: number;
 }
 
+/** A stop in a walkthrough at a range of lines of a project file */
+export interface WalkthroughStep {
+    id: string;
+    walkthroughId: string;
+    title: string;
+    description?: string;
+    /** Relative to the project root */
+    filePath: string;
+    /** 1-based, inclusive */
+    startLine: number;
+    endLine: number;
+    /** The commit the lines refer to; absent outside a git repository */
+    commitSha?: string;
+    sortOrder: number;
+    createdAt: number;
+    updatedAt: number;
+}
+
+/** What `reanchorWalkthroughSteps` did with one step */
+export interface StepReanchor {
+    step: WalkthroughStep;
+    status: 'unchanged' | 'moved' | 'changed' | 'lost' | 'unanchored';
+}
+
+export interface WalkthroughHtmlExport {
+    filePath: string;
+    /** Diagrams included as source because no SVG was given for them */
+    unrenderedDiagrams: number;
+}
+
 export interface WalkthroughDetails extends Walkthrough {
     takeaways: Takeaway[];
     notes: WalkthroughNote[];
+    steps: WalkthroughStep[];
 }
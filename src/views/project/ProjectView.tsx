@@ -686,7 +686,7 @@ export default function ProjectView({ project, onBack, onProjectSelect, isWorktr
   useEffect(() => {
     const loadDbProject = async () => {
       try {
-        const projects = await invokeDbGetProjects();
+        const projects = await invokeDbGetProjects(true);
         // Find project by matching path
         const matchingProject = projects.find(p => p.path === project.path);
         setDbProject(matchingProject || null);